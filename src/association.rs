@@ -0,0 +1,273 @@
+//! Association classes resolved into typed links between the instances of two states.
+//!
+//! WMI association classes such as `Win32_DiskDriveToDiskPartition` relate two instances through
+//! object paths, e.g. `\\HOST\root\cimv2:Win32_DiskDrive.DeviceID="\\\\.\\PHYSICALDRIVE0"`.
+//! [`resolve`] parses both ends of every association and matches them against the key properties
+//! of the instances already held by two states, so callers never compare paths by hand.
+//!
+//! Classes opt in with two macros: `referenced!` names the key properties of a class that can be
+//! referenced, `associates!` names the reference properties of an association class.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::association::{dependents_of, resolve};
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.update();
+//!
+//! let links = resolve(
+//!     &k.disk_drive_to_disk_partitions.disk_drive_to_disk_partitions,
+//!     &k.disk_drives.disk_drives,
+//!     &k.disk_partition.disk_partitions,
+//! );
+//!
+//! for drive in &k.disk_drives.disk_drives {
+//!     println!("{:?}: {}", drive.DeviceID, dependents_of(&links, drive).len());
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A class whose instances association classes reference by their key properties
+pub trait Referenced {
+    /// Key properties of the class, as named in object paths
+    const KEYS: &'static [&'static str];
+
+    /// Values of the key properties, in [`Referenced::KEYS`] order, `None` for unset ones
+    fn key_values(&self) -> Vec<Option<String>>;
+}
+
+/// An association class, relating two instances through their object paths
+pub trait Association {
+    /// Path of the instance the association starts from, its `Antecedent` or `GroupComponent`
+    fn antecedent(&self) -> Option<&str>;
+
+    /// Path of the instance the association leads to, its `Dependent` or `PartComponent`
+    fn dependent(&self) -> Option<&str>;
+}
+
+/// Macro to implement [`Referenced`] for a class from its key properties, e.g.
+/// `referenced!(Win32_UserAccount { Domain, Name })`
+#[macro_export]
+macro_rules! referenced {
+    ($class: ident { $($key: ident),+ $(,)? }) => {
+        impl $crate::association::Referenced for $class {
+            const KEYS: &'static [&'static str] = &[$(stringify!($key)),+];
+
+            fn key_values(&self) -> Vec<Option<String>> {
+                vec![$(self.$key.as_ref().map(|value| value.to_string())),+]
+            }
+        }
+    };
+}
+
+/// Macro to implement [`Association`] for a class from its reference properties, e.g.
+/// `associates!(Win32_GroupUser { GroupComponent => PartComponent })`
+#[macro_export]
+macro_rules! associates {
+    ($class: ident { $antecedent: ident => $dependent: ident }) => {
+        impl $crate::association::Association for $class {
+            fn antecedent(&self) -> Option<&str> {
+                self.$antecedent.as_deref()
+            }
+
+            fn dependent(&self) -> Option<&str> {
+                self.$dependent.as_deref()
+            }
+        }
+    };
+}
+
+/// A WMI object path, e.g. `\\HOST\root\cimv2:Win32_Group.Domain="HOST",Name="Users"`
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ObjectPath {
+    /// Computer the instance lives on, `HOST` above, `None` for a relative path
+    pub server: Option<String>,
+    /// Namespace of the class, `root\cimv2` above, `None` for a relative path
+    pub namespace: Option<String>,
+    /// Class of the instance, `Win32_Group` above
+    pub class: String,
+    /// Key properties and their unescaped values, empty for a singleton (`Class=@`)
+    pub keys: Vec<(String, String)>,
+}
+
+impl ObjectPath {
+    /// Parses `path`, `None` if it does not name a class
+    pub fn parse(path: &str) -> Option<Self> {
+        let quote = path.find('"').unwrap_or(path.len());
+        let (location, relative) = match path[..quote].find(':') {
+            Some(colon) => (Some(&path[..colon]), &path[colon + 1..]),
+            None => (None, path),
+        };
+
+        let (server, namespace) = match location {
+            Some(location) => match location.strip_prefix("\\\\") {
+                Some(rest) => {
+                    let (server, namespace) = rest.split_once('\\').unwrap_or((rest, ""));
+                    (Some(server.to_string()), Some(namespace.to_string()))
+                }
+                None => (None, Some(location.to_string())),
+            },
+            None => (None, None),
+        };
+
+        let (class, keys) = match relative.split_once('.') {
+            Some((class, keys)) => (class, parse_keys(keys)?),
+            None => (relative.split('=').next().unwrap_or_default(), Vec::new()),
+        };
+
+        if class.is_empty() {
+            return None;
+        }
+
+        Some(ObjectPath {
+            server,
+            namespace,
+            class: class.to_string(),
+            keys,
+        })
+    }
+
+    /// Value of the key property `name`, compared case insensitively
+    pub fn key(&self, name: &str) -> Option<&str> {
+        self.keys
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns `true` if the path references `instance`, key values are compared case
+    /// insensitively like WMI does
+    pub fn refers_to<T: Referenced>(&self, instance: &T) -> bool {
+        let key = self.index_key(T::KEYS);
+
+        key.is_some() && key == instance_key(instance)
+    }
+
+    /// Values of the keys `names`, joined for lookups, `None` if one is missing
+    fn index_key(&self, names: &[&str]) -> Option<String> {
+        let values: Option<Vec<String>> = names
+            .iter()
+            .map(|name| self.key(name).map(str::to_lowercase))
+            .collect();
+
+        Some(values?.join("\0"))
+    }
+}
+
+/// Parses `Key="value",Other=5` into unescaped pairs
+fn parse_keys(keys: &str) -> Option<Vec<(String, String)>> {
+    let mut pairs = Vec::new();
+    let mut chars = keys.chars().peekable();
+
+    while chars.peek().is_some() {
+        let name: String = chars.by_ref().take_while(|&c| c != '=').collect();
+        let mut value = String::new();
+
+        if chars.peek() == Some(&'"') {
+            chars.next();
+
+            loop {
+                match chars.next()? {
+                    '\\' => value.push(chars.next()?),
+                    '"' => break,
+                    c => value.push(c),
+                }
+            }
+
+            if matches!(chars.next(), Some(c) if c != ',') {
+                return None;
+            }
+        } else {
+            value = chars.by_ref().take_while(|&c| c != ',').collect();
+        }
+
+        pairs.push((name, value));
+    }
+
+    Some(pairs)
+}
+
+/// Values of the keys of `instance`, joined like [`ObjectPath::index_key`]
+fn instance_key<T: Referenced>(instance: &T) -> Option<String> {
+    let values: Option<Vec<String>> = instance
+        .key_values()
+        .into_iter()
+        .map(|value| value.map(|value| value.to_lowercase()))
+        .collect();
+
+    Some(values?.join("\0"))
+}
+
+/// Two instances related by an association
+#[derive(Serialize, Debug)]
+pub struct Link<'a, A, D> {
+    /// Instance the association starts from
+    pub antecedent: &'a A,
+    /// Instance the association leads to
+    pub dependent: &'a D,
+}
+
+impl<A, D> Clone for Link<'_, A, D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, D> Copy for Link<'_, A, D> {}
+
+/// Matches both ends of every association of `associations` against `antecedents` and
+/// `dependents`, associations with an end missing from the states are left out
+pub fn resolve<'a, S, A, D>(associations: &[S], antecedents: &'a [A], dependents: &'a [D]) -> Vec<Link<'a, A, D>>
+where
+    S: Association,
+    A: Referenced,
+    D: Referenced,
+{
+    let antecedents = index(antecedents);
+    let dependents = index(dependents);
+
+    associations
+        .iter()
+        .filter_map(|association| {
+            let antecedent = ObjectPath::parse(association.antecedent()?)?.index_key(A::KEYS)?;
+            let dependent = ObjectPath::parse(association.dependent()?)?.index_key(D::KEYS)?;
+
+            Some(Link {
+                antecedent: *antecedents.get(&antecedent)?,
+                dependent: *dependents.get(&dependent)?,
+            })
+        })
+        .collect()
+}
+
+/// Instances `links` relate `antecedent` to, `antecedent` being one of the instances resolved
+pub fn dependents_of<'a, A, D>(links: &[Link<'a, A, D>], antecedent: &A) -> Vec<&'a D> {
+    links
+        .iter()
+        .filter(|link| std::ptr::eq(link.antecedent, antecedent))
+        .map(|link| link.dependent)
+        .collect()
+}
+
+/// Instances `links` relate to `dependent`, `dependent` being one of the instances resolved
+pub fn antecedents_of<'a, A, D>(links: &[Link<'a, A, D>], dependent: &D) -> Vec<&'a A> {
+    links
+        .iter()
+        .filter(|link| std::ptr::eq(link.dependent, dependent))
+        .map(|link| link.antecedent)
+        .collect()
+}
+
+/// Indexes `instances` by their joined key values, instances without a key are left out
+fn index<T: Referenced>(instances: &[T]) -> HashMap<String, &T> {
+    instances
+        .iter()
+        .filter_map(|instance| Some((instance_key(instance)?, instance)))
+        .collect()
+}
@@ -0,0 +1,358 @@
+//! Which WMI classes the current host exposes compared to the classes this crate supports.
+//!
+//! The matrix is built at runtime from the `meta_class` listing of every namespace the crate
+//! queries, and can be serialized or rendered as a Markdown table for the docs. Classes the host
+//! exposes but the crate does not support yet are listed too, so a fleet of snapshots tells which
+//! class modules are worth adding next.
+
+use crate::{
+    DEFAULT_NAMESPACE as CIMV2, RSOP_COMPUTER_NAMESPACE as RSOP_COMPUTER,
+    SECURITY_CENTER2_NAMESPACE as SECURITY_CENTER2,
+    STANDARD_CIMV2_NAMESPACE as STANDARD_CIMV2, STORAGE_NAMESPACE as STORAGE,
+    TASK_SCHEDULER_NAMESPACE as TASK_SCHEDULER, TPM_NAMESPACE as TPM,
+    VIRTUALIZATION_NAMESPACE as VIRTUALIZATION, VOLUME_ENCRYPTION_NAMESPACE as VOLUME_ENCRYPTION,
+    WMI_NAMESPACE as WMI,
+};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection};
+
+/// Namespaces listed in the matrix
+pub const NAMESPACES: [&str; 10] = [
+    CIMV2,
+    STANDARD_CIMV2,
+    STORAGE,
+    WMI,
+    TPM,
+    VOLUME_ENCRYPTION,
+    SECURITY_CENTER2,
+    TASK_SCHEDULER,
+    VIRTUALIZATION,
+    RSOP_COMPUTER,
+];
+
+/// Every `(namespace, class)` the crate has a struct for
+pub const SUPPORTED_CLASSES: &[(&str, &str)] = &[
+    (STANDARD_CIMV2, "MSFT_DNSClientCache"),
+    (STANDARD_CIMV2, "MSFT_NetAdapter"),
+    (STANDARD_CIMV2, "MSFT_NetAddressFilter"),
+    (STANDARD_CIMV2, "MSFT_NetApplicationFilter"),
+    (STANDARD_CIMV2, "MSFT_NetFirewallProfile"),
+    (STANDARD_CIMV2, "MSFT_NetFirewallRule"),
+    (STANDARD_CIMV2, "MSFT_NetNeighbor"),
+    (STANDARD_CIMV2, "MSFT_NetProtocolPortFilter"),
+    (STANDARD_CIMV2, "MSFT_NetQosPolicySettingData"),
+    (STANDARD_CIMV2, "MSFT_NetTCPConnection"),
+    (STANDARD_CIMV2, "MSFT_NetUDPEndpoint"),
+    (STORAGE, "MSFT_Disk"),
+    (STORAGE, "MSFT_PhysicalDisk"),
+    (STORAGE, "MSFT_StorageReliabilityCounter"),
+    (WMI, "MSStorageDriver_FailurePredictStatus"),
+    (TPM, "Win32_Tpm"),
+    (VOLUME_ENCRYPTION, "Win32_EncryptableVolume"),
+    (SECURITY_CENTER2, "AntiSpywareProduct"),
+    (SECURITY_CENTER2, "AntiVirusProduct"),
+    (SECURITY_CENTER2, "FirewallProduct"),
+    (TASK_SCHEDULER, "MSFT_ScheduledTask"),
+    #[cfg(feature = "hyperv")]
+    (VIRTUALIZATION, "Msvm_ComputerSystem"),
+    #[cfg(feature = "hyperv")]
+    (VIRTUALIZATION, "Msvm_MemorySettingData"),
+    #[cfg(feature = "hyperv")]
+    (VIRTUALIZATION, "Msvm_ProcessorSettingData"),
+    (CIMV2, "CIM_ProcessExecutable"),
+    (CIMV2, "SoftwareLicensingProduct"),
+    (CIMV2, "SoftwareLicensingService"),
+    (CIMV2, "SoftwareLicensingTokenActivationLicense"),
+    (CIMV2, "Win32_ACE"),
+    (CIMV2, "Win32_Account"),
+    (CIMV2, "Win32_AutochkSetting"),
+    (CIMV2, "Win32_BIOS"),
+    (CIMV2, "Win32_Battery"),
+    (CIMV2, "Win32_BootConfiguration"),
+    (CIMV2, "Win32_CDROMDrive"),
+    (CIMV2, "Win32_COMSetting"),
+    (CIMV2, "Win32_ClassicCOMClassSetting"),
+    (CIMV2, "Win32_CodecFile"),
+    (CIMV2, "Win32_ComputerSystem"),
+    (CIMV2, "Win32_ComputerSystemProduct"),
+    (CIMV2, "Win32_CurrentProbe"),
+    (CIMV2, "Win32_DCOMApplication"),
+    (CIMV2, "Win32_DCOMApplicationSetting"),
+    (CIMV2, "Win32_DependentService"),
+    (CIMV2, "Win32_Desktop"),
+    (CIMV2, "Win32_DesktopMonitor"),
+    (CIMV2, "Win32_Directory"),
+    (CIMV2, "Win32_DirectorySpecification"),
+    (CIMV2, "Win32_DiskDrive"),
+    (CIMV2, "Win32_DiskDriveToDiskPartition"),
+    (CIMV2, "Win32_DiskPartition"),
+    (CIMV2, "Win32_DisplayControllerConfiguration"),
+    (CIMV2, "Win32_DriverForDevice"),
+    (CIMV2, "Win32_Environment"),
+    (CIMV2, "Win32_Fan"),
+    (CIMV2, "Win32_Group"),
+    (CIMV2, "Win32_GroupUser"),
+    (CIMV2, "Win32_HeatPipe"),
+    (CIMV2, "Win32_IP4PersistedRouteTable"),
+    (CIMV2, "Win32_IP4RouteTable"),
+    (CIMV2, "Win32_IP4RouteTableEvent"),
+    (CIMV2, "Win32_Keyboard"),
+    (CIMV2, "Win32_LUID"),
+    (CIMV2, "Win32_LUIDandAttributes"),
+    (CIMV2, "Win32_LoadOrderGroup"),
+    (CIMV2, "Win32_LocalTime"),
+    (CIMV2, "Win32_LogicalDisk"),
+    (CIMV2, "Win32_LogicalDiskToPartition"),
+    (CIMV2, "Win32_LogicalFileSecuritySetting"),
+    (CIMV2, "Win32_LogicalProgramGroup"),
+    (CIMV2, "Win32_LogicalProgramGroupItem"),
+    (CIMV2, "Win32_LogicalShareSecuritySetting"),
+    (CIMV2, "Win32_LogonSession"),
+    (CIMV2, "Win32_MappedLogicalDisk"),
+    (CIMV2, "Win32_NTDomain"),
+    (CIMV2, "Win32_NTEventlogFile"),
+    (CIMV2, "Win32_NTLogEvent"),
+    (CIMV2, "Win32_NamedJobObject"),
+    (CIMV2, "Win32_NamedJobObjectActgInfo"),
+    (CIMV2, "Win32_NamedJobObjectLimitSetting"),
+    (CIMV2, "Win32_NetworkAdapter"),
+    (CIMV2, "Win32_NetworkAdapterConfiguration"),
+    (CIMV2, "Win32_NetworkClient"),
+    (CIMV2, "Win32_NetworkConnection"),
+    (CIMV2, "Win32_NetworkLoginProfile"),
+    (CIMV2, "Win32_NetworkProtocol"),
+    (CIMV2, "Win32_OSRecoveryConfiguration"),
+    (CIMV2, "Win32_OperatingSystem"),
+    (CIMV2, "Win32_OptionalFeature"),
+    (CIMV2, "Win32_POTSModem"),
+    (CIMV2, "Win32_POTSModemToSerialPort"),
+    (CIMV2, "Win32_PageFile"),
+    (CIMV2, "Win32_PageFileSetting"),
+    (CIMV2, "Win32_PageFileUsage"),
+    (CIMV2, "Win32_ParallelPort"),
+    (CIMV2, "Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine"),
+    (CIMV2, "Win32_PerfFormattedData_PerfDisk_LogicalDisk"),
+    (CIMV2, "Win32_PerfFormattedData_PerfOS_Memory"),
+    (CIMV2, "Win32_PerfFormattedData_PerfOS_Processor"),
+    (CIMV2, "Win32_PhysicalMedia"),
+    (CIMV2, "Win32_PnPEntity"),
+    (CIMV2, "Win32_PointingDevice"),
+    (CIMV2, "Win32_PortableBattery"),
+    (CIMV2, "Win32_PowerManagementEvent"),
+    (CIMV2, "Win32_Printer"),
+    (CIMV2, "Win32_PrinterDriver"),
+    (CIMV2, "Win32_PrivilegesStatus"),
+    (CIMV2, "Win32_Process"),
+    (CIMV2, "Win32_Product"),
+    (CIMV2, "Win32_ProgramGroupOrItem"),
+    (CIMV2, "Win32_Proxy"),
+    (CIMV2, "Win32_QuickFixEngineering"),
+    (CIMV2, "Win32_QuotaSetting"),
+    (CIMV2, "Win32_Refrigeration"),
+    (CIMV2, "Win32_Registry"),
+    (CIMV2, "Win32_ScheduledJob"),
+    (CIMV2, "Win32_SecurityDescriptor"),
+    (CIMV2, "Win32_SecuritySetting"),
+    (CIMV2, "Win32_SerialPortConfiguration"),
+    (CIMV2, "Win32_ServerConnection"),
+    (CIMV2, "Win32_ServerFeature"),
+    (CIMV2, "Win32_ServerSession"),
+    (CIMV2, "Win32_Service"),
+    (CIMV2, "Win32_ShadowContext"),
+    (CIMV2, "Win32_ShadowCopy"),
+    (CIMV2, "Win32_ShadowProvider"),
+    (CIMV2, "Win32_Share"),
+    (CIMV2, "Win32_ShortcutFile"),
+    (CIMV2, "Win32_StartupCommand"),
+    (CIMV2, "Win32_SystemAccount"),
+    (CIMV2, "Win32_SystemDriver"),
+    (CIMV2, "Win32_SystemEnclosure"),
+    (CIMV2, "Win32_SystemServices"),
+    (CIMV2, "Win32_TCPIPPrinterPort"),
+    (CIMV2, "Win32_TapeDrive"),
+    (CIMV2, "Win32_TemperatureProbe"),
+    (CIMV2, "Win32_Thread"),
+    (CIMV2, "Win32_TimeZone"),
+    (CIMV2, "Win32_Trustee"),
+    (CIMV2, "Win32_USBControllerDevice"),
+    (CIMV2, "Win32_UTCTime"),
+    (CIMV2, "Win32_UserAccount"),
+    (CIMV2, "Win32_UserDesktop"),
+    (CIMV2, "Win32_UserProfile"),
+    (CIMV2, "Win32_VideoController"),
+    (CIMV2, "Win32_VoltageProbe"),
+    (CIMV2, "Win32_Volume"),
+    (CIMV2, "Win32_WindowsProductActivation"),
+    (RSOP_COMPUTER, "RSOP_ExtensionStatus"),
+    (RSOP_COMPUTER, "RSOP_GPLink"),
+    (RSOP_COMPUTER, "RSOP_GPO"),
+];
+
+/// Query listing every class of a namespace
+const META_CLASS_QUERY: &str = "SELECT * FROM meta_class";
+
+/// Represents the WMI classes available on the host
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct AvailabilityMatrix {
+    /// Sequence of supported and available classes
+    pub classes: Vec<ClassAvailability>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+}
+
+impl AvailabilityMatrix {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let mut host_classes = Vec::new();
+
+        for namespace in NAMESPACES {
+            let com_con = unsafe { COMLibrary::assume_initialized() };
+
+            // A missing namespace (e.g. Storage on older builds) exposes no class
+            let Ok(wmi_con) = WMIConnection::with_namespace_path(namespace, com_con) else {
+                continue;
+            };
+            let classes: Vec<MetaClass> = wmi_con.raw_query(META_CLASS_QUERY).unwrap_or_default();
+
+            host_classes.extend(classes.into_iter().map(|meta| (namespace, meta.class)));
+        }
+
+        self.refresh(host_classes);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let mut host_classes = Vec::new();
+
+        for namespace in NAMESPACES {
+            let com_con = unsafe { COMLibrary::assume_initialized() };
+
+            // A missing namespace (e.g. Storage on older builds) exposes no class
+            let Ok(wmi_con) = WMIConnection::with_namespace_path(namespace, com_con) else {
+                continue;
+            };
+            let classes: Vec<MetaClass> = wmi_con
+                .async_raw_query(META_CLASS_QUERY)
+                .await
+                .unwrap_or_default();
+
+            host_classes.extend(classes.into_iter().map(|meta| (namespace, meta.class)));
+        }
+
+        self.refresh(host_classes);
+    }
+
+    /// Returns `true` if the host exposes `class` in `namespace`
+    pub fn is_available(&self, namespace: &str, class: &str) -> bool {
+        self.classes.iter().any(|entry| {
+            entry.available
+                && entry.namespace.eq_ignore_ascii_case(namespace)
+                && entry.class.eq_ignore_ascii_case(class)
+        })
+    }
+
+    /// Supported classes the host does not expose
+    pub fn missing(&self) -> impl Iterator<Item = &ClassAvailability> {
+        self.classes.iter().filter(|entry| entry.supported && !entry.available)
+    }
+
+    /// Classes the host exposes that the crate does not support yet
+    pub fn unsupported(&self) -> impl Iterator<Item = &ClassAvailability> {
+        self.classes.iter().filter(|entry| !entry.supported)
+    }
+
+    /// Renders the matrix as a Markdown table, one row per class
+    pub fn to_markdown(&self) -> String {
+        let mut table = String::from("| Namespace | Class | Supported | Available |\n|---|---|---|---|\n");
+
+        for entry in &self.classes {
+            table.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                entry.namespace,
+                entry.class,
+                if entry.supported { "yes" } else { "no" },
+                if entry.available { "yes" } else { "no" },
+            ));
+        }
+
+        table
+    }
+
+    fn refresh(&mut self, host_classes: Vec<(&str, String)>) {
+        let old_vec = std::mem::take(&mut self.classes);
+
+        self.classes = SUPPORTED_CLASSES
+            .iter()
+            .map(|&(namespace, class)| ClassAvailability {
+                namespace: namespace.to_string(),
+                class: class.to_string(),
+                supported: true,
+                available: host_classes.iter().any(|(host_namespace, host_class)| {
+                    *host_namespace == namespace && host_class.eq_ignore_ascii_case(class)
+                }),
+            })
+            .collect();
+
+        // System (`__`) and abstract CIM (`CIM_`) classes are not worth a module of their own
+        self.classes.extend(
+            host_classes
+                .into_iter()
+                .filter(|(_, class)| !class.starts_with("__") && !class.starts_with("CIM_"))
+                .filter(|(namespace, class)| {
+                    !SUPPORTED_CLASSES.iter().any(|(supported_namespace, supported_class)| {
+                        supported_namespace == namespace && supported_class.eq_ignore_ascii_case(class)
+                    })
+                })
+                .map(|(namespace, class)| ClassAvailability {
+                    namespace: namespace.to_string(),
+                    class,
+                    supported: false,
+                    available: true,
+                }),
+        );
+
+        self.state_change = self.classes.len() != old_vec.len()
+            || crate::hash_vec(&self.classes) != crate::hash_vec(&old_vec);
+    }
+}
+
+impl Default for AvailabilityMatrix {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        AvailabilityMatrix {
+            classes: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+        }
+    }
+}
+
+/// Availability of a single WMI class
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ClassAvailability {
+    /// Namespace of the class
+    pub namespace: String,
+    /// Name of the class
+    pub class: String,
+    /// Whether the crate has a struct for the class
+    pub supported: bool,
+    /// Whether the host exposes the class
+    pub available: bool,
+}
+
+/// Class definition returned by `meta_class`, only its name is needed
+#[derive(Deserialize)]
+struct MetaClass {
+    #[serde(rename = "__CLASS")]
+    class: String,
+}
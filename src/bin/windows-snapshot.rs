@@ -0,0 +1,280 @@
+// cargo run --bin windows-snapshot --features cli --release -- snapshot --select processes,services
+
+use futures::StreamExt;
+use std::process::ExitCode;
+use std::time::Duration;
+use windows_snapshot::config::parse_duration;
+use windows_snapshot::redact::Redactor;
+use windows_snapshot::snapshot::{
+    load_snapshot, save_snapshot, validate_snapshot, SnapshotFormat, SnapshotOptions,
+};
+use windows_snapshot::state::{SnapshotProfile, StateKind, Windows};
+use windows_snapshot::watch::WindowsDiff;
+use windows_snapshot::COMLibrary;
+
+const USAGE: &str = "\
+Usage:
+    windows-snapshot snapshot [--select <members>] [--format <format>] [--out <file>] [--redact]
+    windows-snapshot diff <before> <after> [--format json|text]
+    windows-snapshot validate <snapshot>
+    windows-snapshot get <snapshot> <path>
+    windows-snapshot watch [--select <members>] [--interval <duration>]
+    windows-snapshot members
+
+Options:
+    --select <members>      Comma separated members to update, e.g. processes,services;
+                            every member for snapshot, the quick profile for watch
+    --format <format>       json, bincode or postcard for snapshot, json or text for diff
+    --compress <level>      zstd compression level of the snapshot, needs --out
+    --out <file>            File to save the snapshot to instead of printing it as JSON
+    --redact                Hash user names, host names, serial numbers and addresses
+    --interval <duration>   Time between two updates of a watch, e.g. 500ms, 30s, 5m or 1h [default: 30s]
+";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("snapshot") => snapshot(&args[1..]),
+        Some("diff") => diff(&args[1..]),
+        Some("validate") => validate(&args[1..]),
+        Some("get") => get(&args[1..]),
+        Some("watch") => watch(&args[1..]).await,
+        Some("members") => {
+            for kind in StateKind::ALL {
+                println!("{}", kind.name());
+            }
+            Ok(())
+        }
+        Some("help" | "--help" | "-h") => {
+            print!("{USAGE}");
+            Ok(())
+        }
+        _ => Err(format!("expected a command\n\n{USAGE}")),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("windows-snapshot: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Updates the selected members and saves them to `--out`, or prints them as JSON
+fn snapshot(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args)?;
+    let profile = options.profile()?.unwrap_or_else(SnapshotProfile::all);
+    let mut snapshot_options = SnapshotOptions::new(options.snapshot_format()?);
+
+    if let Some(level) = &options.compress {
+        snapshot_options.compression_level =
+            Some(level.parse().map_err(|_| format!("invalid compression level `{level}`"))?);
+    }
+
+    if options.out.is_none() {
+        if snapshot_options.compression_level.is_some() {
+            return Err("compression needs --out".to_string());
+        }
+        if snapshot_options.format != SnapshotFormat::Json {
+            return Err("binary snapshots need --out".to_string());
+        }
+    }
+
+    let _com_con = COMLibrary::new().map_err(|e| e.to_string())?; // initialise security context
+    let mut k = Windows::default();
+    k.update_profile(&profile);
+
+    if options.redact {
+        k = Redactor::default().redacted(&k).map_err(|e| e.to_string())?;
+    }
+
+    match &options.out {
+        Some(out) => save_snapshot(&k, out, &snapshot_options).map_err(|e| e.to_string()),
+        None => {
+            println!("{}", serde_json::to_string_pretty(&k).map_err(|e| e.to_string())?);
+            Ok(())
+        }
+    }
+}
+
+/// Prints what changed between two saved snapshots
+fn diff(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args)?;
+    let [before, after] = options.positional.as_slice() else {
+        return Err(format!("diff expects two snapshot files\n\n{USAGE}"));
+    };
+
+    let before: Windows = load_snapshot(before).map_err(|e| format!("{before}: {e}"))?;
+    let after: Windows = load_snapshot(after).map_err(|e| format!("{after}: {e}"))?;
+    let diff = WindowsDiff::of(&before, &after);
+
+    match options.format.as_deref() {
+        None | Some("text") => print_diff(&diff),
+        Some("json") => {
+            println!("{}", serde_json::to_string_pretty(&diff).map_err(|e| e.to_string())?)
+        }
+        Some(format) => return Err(format!("unknown diff format `{format}`")),
+    }
+
+    Ok(())
+}
+
+/// Tells which members of a saved snapshot no longer load, failing if any
+fn validate(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args)?;
+    let [snapshot] = options.positional.as_slice() else {
+        return Err(format!("validate expects a snapshot file\n\n{USAGE}"));
+    };
+
+    let validation = validate_snapshot(snapshot).map_err(|e| format!("{snapshot}: {e}"))?;
+    println!("{validation}");
+
+    if !validation.is_valid() {
+        return Err(format!("{snapshot} does not load"));
+    }
+
+    Ok(())
+}
+
+/// Prints the values a path such as `os.services.services[Name=wuauserv].State` addresses in a
+/// saved snapshot, one line of JSON each
+fn get(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args)?;
+    let [snapshot, path] = options.positional.as_slice() else {
+        return Err(format!("get expects a snapshot file and a path\n\n{USAGE}"));
+    };
+
+    let k: Windows = load_snapshot(snapshot).map_err(|e| format!("{snapshot}: {e}"))?;
+
+    for value in k.lookup(path).map_err(|e| e.to_string())? {
+        println!("{value}");
+    }
+
+    Ok(())
+}
+
+/// Updates the selected members every `--interval` and prints every change as a line of JSON
+async fn watch(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args)?;
+    let profile = options.profile()?.unwrap_or_else(SnapshotProfile::quick);
+    let interval = match &options.interval {
+        Some(interval) => parse_interval(interval)?,
+        None => Duration::from_secs(30),
+    };
+
+    let _com_con = COMLibrary::new().map_err(|e| e.to_string())?; // initialise security context
+    let mut diffs = Box::pin(Windows::default().watch(interval, profile));
+
+    while let Some(diff) = diffs.next().await {
+        println!("{}", serde_json::to_string(&diff).map_err(|e| e.to_string())?);
+    }
+
+    Ok(())
+}
+
+fn print_diff(diff: &WindowsDiff) {
+    if diff.is_empty() {
+        println!("no changes");
+    }
+
+    for member in &diff.members {
+        println!(
+            "{}: +{} -{}",
+            member.kind.name(),
+            member.added.len(),
+            member.removed.len()
+        );
+    }
+
+    for custom in &diff.custom {
+        println!("{}: +{} -{}", custom.name, custom.added.len(), custom.removed.len());
+    }
+}
+
+/// Options and positional arguments following a command
+#[derive(Default)]
+struct Options {
+    select: Option<String>,
+    format: Option<String>,
+    compress: Option<String>,
+    out: Option<String>,
+    interval: Option<String>,
+    redact: bool,
+    positional: Vec<String>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let slot = match name {
+                "--select" => &mut options.select,
+                "--format" => &mut options.format,
+                "--compress" => &mut options.compress,
+                "--out" => &mut options.out,
+                "--interval" => &mut options.interval,
+                "--redact" => {
+                    options.redact = true;
+                    continue;
+                }
+                _ if name.starts_with("--") => return Err(format!("unknown option `{name}`")),
+                _ => {
+                    options.positional.push(arg.clone());
+                    continue;
+                }
+            };
+
+            *slot = Some(match inline {
+                Some(value) => value,
+                None => args
+                    .next()
+                    .cloned()
+                    .ok_or_else(|| format!("`{name}` expects a value"))?,
+            });
+        }
+
+        Ok(options)
+    }
+
+    /// Profile of the members named by `--select`, `None` without it
+    fn profile(&self) -> Result<Option<SnapshotProfile>, String> {
+        let Some(select) = &self.select else {
+            return Ok(None);
+        };
+
+        SnapshotProfile::from_names(select)
+            .map(Some)
+            .map_err(|name| format!("unknown member `{name}`, see `windows-snapshot members`"))
+    }
+
+    fn snapshot_format(&self) -> Result<SnapshotFormat, String> {
+        match self.format.as_deref() {
+            None | Some("json") => Ok(SnapshotFormat::Json),
+            #[cfg(feature = "bincode")]
+            Some("bincode") => Ok(SnapshotFormat::Bincode),
+            #[cfg(feature = "postcard")]
+            Some("postcard") => Ok(SnapshotFormat::Postcard),
+            Some(format) => Err(format!("unknown or disabled snapshot format `{format}`")),
+        }
+    }
+}
+
+/// Parses the `--interval` of a watch, see [`parse_duration`]
+fn parse_interval(value: &str) -> Result<Duration, String> {
+    let duration = parse_duration(value)?;
+
+    if duration.is_zero() {
+        return Err("the interval must not be zero".to_string());
+    }
+
+    Ok(duration)
+}
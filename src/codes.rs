@@ -0,0 +1,805 @@
+//! Typed values of the coded WMI properties.
+//!
+//! Properties such as `Availability` or `DriveType` are reported by WMI as integers whose meaning
+//! is only documented. Classes holding them get accessors returning the enums below, e.g.
+//! `Win32_LogicalDisk::drive_type`, while the raw field stays as WMI returned it. Codes missing from
+//! the documentation are kept as `Undocumented`, so no value is lost.
+//!
+//! Every enum converts from and into its raw integer:
+//!
+//! ```rust
+//! use windows_snapshot::codes::DriveType;
+//!
+//! assert_eq!(DriveType::from(3), DriveType::LocalDisk);
+//! assert_eq!(u32::from(DriveType::LocalDisk), 3);
+//! assert_eq!(DriveType::from(42).code(), 42);
+//! ```
+//!
+//! Some properties hold strings rather than integers, such as `Status` or the `Type` of a
+//! partition, and some providers localize them, so "Error" may read "Fehler". Such properties get
+//! accessors returning the enums declared with labels, e.g. `Win32_DiskPartition::partition_type`,
+//! which accept the documented English value in any case and spacing as well as the known
+//! localized ones. Labels that match none are kept as `Unrecognized`.
+//!
+//! ```rust
+//! use windows_snapshot::codes::DeviceStatus;
+//!
+//! assert_eq!(DeviceStatus::from("Pred Fail"), DeviceStatus::PredFail);
+//! assert_eq!(DeviceStatus::from("fehler"), DeviceStatus::Error);
+//! assert_eq!(DeviceStatus::from("Error").label(), "Error");
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Declares an enum for a coded property, with conversions from and into the raw code
+macro_rules! codes {
+    (
+        $(#[$meta: meta])*
+        $name: ident: $raw: ty {
+            $($(#[$variant_meta: meta])* $variant: ident = $value: literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// A code the documentation does not list
+            Undocumented($raw),
+        }
+
+        impl $name {
+            /// Raw code as reported by WMI
+            pub fn code(&self) -> $raw {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Undocumented(code) => *code,
+                }
+            }
+        }
+
+        impl From<$raw> for $name {
+            fn from(code: $raw) -> Self {
+                match code {
+                    $($value => $name::$variant,)*
+                    code => $name::Undocumented(code),
+                }
+            }
+        }
+
+        impl From<$name> for $raw {
+            fn from(value: $name) -> Self {
+                value.code()
+            }
+        }
+    };
+}
+
+/// Macro to make accessors returning the typed value of coded properties, e.g.
+/// `coded!(Win32_LogicalDisk { DriveType => drive_type: DriveType })`
+#[macro_export]
+macro_rules! coded {
+    ($class: ident { $($field: ident => $method: ident: $code: ident),* $(,)? }) => {
+        impl $class {
+            $(
+                #[doc = concat!("`", stringify!($field), "` as a [`", stringify!($code), "`](crate::codes::", stringify!($code), ")")]
+                pub fn $method(&self) -> Option<$crate::codes::$code> {
+                    self.$field.map($crate::codes::$code::from)
+                }
+            )*
+        }
+    };
+}
+
+/// Declares an enum for a string property, matching the documented label and its localized
+/// variants regardless of case, spaces and punctuation
+macro_rules! labels {
+    (
+        $(#[$meta: meta])*
+        $name: ident {
+            $($(#[$variant_meta: meta])* $variant: ident = $label: literal $(| $alias: literal)*,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// A label that matches no documented or known localized one
+            Unrecognized(String),
+        }
+
+        impl $name {
+            /// Documented English label, or the raw label when unrecognized
+            pub fn label(&self) -> &str {
+                match self {
+                    $($name::$variant => $label,)*
+                    $name::Unrecognized(label) => label,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(label: &str) -> Self {
+                let normalized = normalize_label(label);
+
+                $(
+                    if normalized == normalize_label($label) $(|| normalized == normalize_label($alias))* {
+                        return $name::$variant;
+                    }
+                )*
+
+                $name::Unrecognized(label.to_string())
+            }
+        }
+    };
+}
+
+/// Macro to make accessors returning the typed value of string properties, e.g.
+/// `labeled!(Win32_Service { Status => status: DeviceStatus })`
+#[macro_export]
+macro_rules! labeled {
+    ($class: ident { $($field: ident => $method: ident: $label: ident),* $(,)? }) => {
+        impl $class {
+            $(
+                #[doc = concat!("`", stringify!($field), "` as a [`", stringify!($label), "`](crate::codes::", stringify!($label), ")")]
+                pub fn $method(&self) -> Option<$crate::codes::$label> {
+                    self.$field.as_deref().map($crate::codes::$label::from)
+                }
+            )*
+        }
+    };
+}
+
+/// Lowercase alphanumeric characters of `label`, so "Pred Fail", "PRED-FAIL" and "predfail" match
+fn normalize_label(label: &str) -> String {
+    label
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+codes! {
+    /// `Availability` of a device
+    Availability: u16 {
+        /// Other (1)
+        Other = 1,
+        /// Unknown (2)
+        Unknown = 2,
+        /// Running or Full Power (3)
+        RunningOrFullPower = 3,
+        /// Warning (4)
+        Warning = 4,
+        /// In Test (5)
+        InTest = 5,
+        /// Not Applicable (6)
+        NotApplicable = 6,
+        /// Power Off (7)
+        PowerOff = 7,
+        /// Off Line (8)
+        OffLine = 8,
+        /// Off Duty (9)
+        OffDuty = 9,
+        /// Degraded (10)
+        Degraded = 10,
+        /// Not Installed (11)
+        NotInstalled = 11,
+        /// Install Error (12)
+        InstallError = 12,
+        /// Power Save - Unknown (13): in a power save mode, exact status unknown
+        PowerSaveUnknown = 13,
+        /// Power Save - Low Power Mode (14): in a power save state, still functioning
+        PowerSaveLowPowerMode = 14,
+        /// Power Save - Standby (15): not functioning, could be brought to full power quickly
+        PowerSaveStandby = 15,
+        /// Power Cycle (16)
+        PowerCycle = 16,
+        /// Power Save - Warning (17): in a warning state, though also in a power save mode
+        PowerSaveWarning = 17,
+        /// Paused (18)
+        Paused = 18,
+        /// Not Ready (19)
+        NotReady = 19,
+        /// Not Configured (20)
+        NotConfigured = 20,
+        /// Quiesced (21): quiet
+        Quiesced = 21,
+    }
+}
+
+codes! {
+    /// `StatusInfo` of a logical device
+    StatusInfo: u16 {
+        /// Other (1)
+        Other = 1,
+        /// Unknown (2)
+        Unknown = 2,
+        /// Enabled (3)
+        Enabled = 3,
+        /// Disabled (4)
+        Disabled = 4,
+        /// Not Applicable (5)
+        NotApplicable = 5,
+    }
+}
+
+codes! {
+    /// `ConfigManagerErrorCode` of a device, as reported by the Configuration Manager
+    ConfigManagerErrorCode: u32 {
+        /// The device is working properly (0)
+        WorkingProperly = 0,
+        /// The device is not configured correctly (1)
+        NotConfigured = 1,
+        /// Windows cannot load the driver for this device (2)
+        CannotLoadDriver = 2,
+        /// The driver may be corrupted, or the system may be low on memory (3)
+        DriverCorrupted = 3,
+        /// The device is not working properly, a driver or the registry may be corrupted (4)
+        NotWorkingProperly = 4,
+        /// The driver needs a resource that Windows cannot manage (5)
+        NeedsResource = 5,
+        /// The boot configuration conflicts with other devices (6)
+        BootConfigurationConflict = 6,
+        /// Cannot filter (7)
+        CannotFilter = 7,
+        /// The driver loader for the device is missing (8)
+        DriverLoaderMissing = 8,
+        /// The firmware reports the resources of the device incorrectly (9)
+        FirmwareResourcesIncorrect = 9,
+        /// The device cannot start (10)
+        CannotStart = 10,
+        /// The device failed (11)
+        Failed = 11,
+        /// The device cannot find enough free resources (12)
+        NotEnoughFreeResources = 12,
+        /// Windows cannot verify the resources of the device (13)
+        CannotVerifyResources = 13,
+        /// The device cannot work properly until the computer is restarted (14)
+        NeedsRestart = 14,
+        /// The device is not working properly due to a possible re-enumeration problem (15)
+        ReenumerationProblem = 15,
+        /// Windows cannot identify all of the resources the device uses (16)
+        ResourcesNotIdentified = 16,
+        /// The device is requesting an unknown resource type (17)
+        UnknownResourceType = 17,
+        /// The device drivers must be reinstalled (18)
+        ReinstallDrivers = 18,
+        /// Failure using the VxD loader (19)
+        VxdLoaderFailure = 19,
+        /// The registry might be corrupted (20)
+        RegistryCorrupted = 20,
+        /// Windows is removing the device after a system failure (21)
+        DriverRemoved = 21,
+        /// The device is disabled (22)
+        Disabled = 22,
+        /// System failure, changing the driver may help (23)
+        SystemFailure = 23,
+        /// The device is not present, not working properly, or missing drivers (24)
+        NotPresent = 24,
+        /// Windows is still setting up the device (25)
+        SettingUp = 25,
+        /// Windows is still setting up the device (26)
+        StillSettingUp = 26,
+        /// The device does not have a valid log configuration (27)
+        NoValidLogConfiguration = 27,
+        /// The device drivers are not installed (28)
+        DriversNotInstalled = 28,
+        /// The device is disabled because its firmware did not provide the required resources (29)
+        DisabledByFirmware = 29,
+        /// The device is using an IRQ resource that another device is using (30)
+        IrqConflict = 30,
+        /// Windows cannot load the drivers the device needs (31)
+        DriversCannotLoad = 31,
+    }
+}
+
+codes! {
+    /// `DriveType` of a logical disk or volume
+    DriveType: u32 {
+        /// Unknown (0)
+        Unknown = 0,
+        /// No Root Directory (1)
+        NoRootDirectory = 1,
+        /// Removable Disk (2)
+        RemovableDisk = 2,
+        /// Local Disk (3)
+        LocalDisk = 3,
+        /// Network Drive (4)
+        NetworkDrive = 4,
+        /// Compact Disc (5)
+        CompactDisc = 5,
+        /// RAM Disk (6)
+        RamDisk = 6,
+    }
+}
+
+codes! {
+    /// `BatteryStatus` of a battery
+    BatteryStatus: u16 {
+        /// Other (1): the battery is discharging
+        Discharging = 1,
+        /// Unknown (2): the system has access to AC power, the battery is not necessarily
+        /// charging
+        OnAcPower = 2,
+        /// Fully Charged (3)
+        FullyCharged = 3,
+        /// Low (4)
+        Low = 4,
+        /// Critical (5)
+        Critical = 5,
+        /// Charging (6)
+        Charging = 6,
+        /// Charging and High (7)
+        ChargingAndHigh = 7,
+        /// Charging and Low (8)
+        ChargingAndLow = 8,
+        /// Charging and Critical (9)
+        ChargingAndCritical = 9,
+        /// Undefined (10)
+        Undefined = 10,
+        /// Partially Charged (11)
+        PartiallyCharged = 11,
+    }
+}
+
+codes! {
+    /// `Chemistry` of a battery
+    Chemistry: u16 {
+        /// Other (1)
+        Other = 1,
+        /// Unknown (2)
+        Unknown = 2,
+        /// Lead Acid (3)
+        LeadAcid = 3,
+        /// Nickel Cadmium (4)
+        NickelCadmium = 4,
+        /// Nickel Metal Hydride (5)
+        NickelMetalHydride = 5,
+        /// Lithium-ion (6)
+        LithiumIon = 6,
+        /// Zinc air (7)
+        ZincAir = 7,
+        /// Lithium Polymer (8)
+        LithiumPolymer = 8,
+    }
+}
+
+codes! {
+    /// `DomainRole` of a computer system
+    DomainRole: u16 {
+        /// Standalone Workstation (0)
+        StandaloneWorkstation = 0,
+        /// Member Workstation (1)
+        MemberWorkstation = 1,
+        /// Standalone Server (2)
+        StandaloneServer = 2,
+        /// Member Server (3)
+        MemberServer = 3,
+        /// Backup Domain Controller (4)
+        BackupDomainController = 4,
+        /// Primary Domain Controller (5)
+        PrimaryDomainController = 5,
+    }
+}
+
+codes! {
+    /// `PCSystemType` of a computer system
+    PCSystemType: u16 {
+        /// Unspecified (0)
+        Unspecified = 0,
+        /// Desktop (1)
+        Desktop = 1,
+        /// Mobile (2)
+        Mobile = 2,
+        /// Workstation (3)
+        Workstation = 3,
+        /// Enterprise Server (4)
+        EnterpriseServer = 4,
+        /// Small Office and Home Office (SOHO) Server (5)
+        SohoServer = 5,
+        /// Appliance PC (6)
+        AppliancePc = 6,
+        /// Performance Server (7)
+        PerformanceServer = 7,
+        /// Maximum (8)
+        Maximum = 8,
+    }
+}
+
+codes! {
+    /// `ChassisTypes` of a system enclosure, as defined by SMBIOS
+    ChassisType: u16 {
+        /// Other (1)
+        Other = 1,
+        /// Unknown (2)
+        Unknown = 2,
+        /// Desktop (3)
+        Desktop = 3,
+        /// Low Profile Desktop (4)
+        LowProfileDesktop = 4,
+        /// Pizza Box (5)
+        PizzaBox = 5,
+        /// Mini Tower (6)
+        MiniTower = 6,
+        /// Tower (7)
+        Tower = 7,
+        /// Portable (8)
+        Portable = 8,
+        /// Laptop (9)
+        Laptop = 9,
+        /// Notebook (10)
+        Notebook = 10,
+        /// Hand Held (11)
+        HandHeld = 11,
+        /// Docking Station (12)
+        DockingStation = 12,
+        /// All in One (13)
+        AllInOne = 13,
+        /// Sub Notebook (14)
+        SubNotebook = 14,
+        /// Space-Saving (15)
+        SpaceSaving = 15,
+        /// Lunch Box (16)
+        LunchBox = 16,
+        /// Main System Chassis (17)
+        MainSystemChassis = 17,
+        /// Expansion Chassis (18)
+        ExpansionChassis = 18,
+        /// SubChassis (19)
+        SubChassis = 19,
+        /// Bus Expansion Chassis (20)
+        BusExpansionChassis = 20,
+        /// Peripheral Chassis (21)
+        PeripheralChassis = 21,
+        /// Storage Chassis (22)
+        StorageChassis = 22,
+        /// Rack Mount Chassis (23)
+        RackMountChassis = 23,
+        /// Sealed-Case PC (24)
+        SealedCasePc = 24,
+        /// Tablet (30)
+        Tablet = 30,
+        /// Convertible (31)
+        Convertible = 31,
+        /// Detachable (32)
+        Detachable = 32,
+        /// IoT Gateway (33)
+        IotGateway = 33,
+        /// Embedded PC (34)
+        EmbeddedPc = 34,
+        /// Mini PC (35)
+        MiniPc = 35,
+        /// Stick PC (36)
+        StickPc = 36,
+    }
+}
+
+impl ChassisType {
+    /// Returns `true` for the chassis of computers meant to be carried, such as laptops and
+    /// tablets
+    pub fn is_portable(&self) -> bool {
+        matches!(
+            self,
+            ChassisType::Portable
+                | ChassisType::Laptop
+                | ChassisType::Notebook
+                | ChassisType::HandHeld
+                | ChassisType::SubNotebook
+                | ChassisType::Tablet
+                | ChassisType::Convertible
+                | ChassisType::Detachable
+        )
+    }
+}
+
+codes! {
+    /// `ProductType` of an operating system
+    ProductType: u32 {
+        /// Work Station (1)
+        WorkStation = 1,
+        /// Domain Controller (2)
+        DomainController = 2,
+        /// Server (3)
+        Server = 3,
+    }
+}
+
+codes! {
+    /// `InstallState` of an optional feature
+    OptionalFeatureState: u32 {
+        /// Enabled (1)
+        Enabled = 1,
+        /// Disabled (2)
+        Disabled = 2,
+        /// Absent (3): the payload of the feature was removed from the image
+        Absent = 3,
+        /// Unknown (4)
+        Unknown = 4,
+    }
+}
+
+codes! {
+    /// Boolean setting that Group Policy may leave unset, e.g. `Enabled` of a firewall profile
+    GpoBoolean: u16 {
+        /// False (0)
+        False = 0,
+        /// True (1)
+        True = 1,
+        /// Not Configured (2)
+        NotConfigured = 2,
+    }
+}
+
+codes! {
+    /// `Action` of a firewall rule, or default action of a firewall profile
+    FirewallAction: u16 {
+        /// Not Configured (0)
+        NotConfigured = 0,
+        /// Allow (2)
+        Allow = 2,
+        /// Allow Bypass (3): allows traffic that would otherwise be blocked by IPsec rules
+        AllowBypass = 3,
+        /// Block (4)
+        Block = 4,
+    }
+}
+
+codes! {
+    /// `Direction` of a firewall rule
+    FirewallDirection: u16 {
+        /// Inbound (1)
+        Inbound = 1,
+        /// Outbound (2)
+        Outbound = 2,
+    }
+}
+
+codes! {
+    /// `State` of a TCP connection
+    TcpState: u8 {
+        /// Closed (1)
+        Closed = 1,
+        /// Listen (2)
+        Listen = 2,
+        /// Syn Sent (3)
+        SynSent = 3,
+        /// Syn Received (4)
+        SynReceived = 4,
+        /// Established (5)
+        Established = 5,
+        /// Fin Wait 1 (6)
+        FinWait1 = 6,
+        /// Fin Wait 2 (7)
+        FinWait2 = 7,
+        /// Close Wait (8)
+        CloseWait = 8,
+        /// Closing (9)
+        Closing = 9,
+        /// Last Ack (10)
+        LastAck = 10,
+        /// Time Wait (11)
+        TimeWait = 11,
+        /// Delete TCB (12)
+        DeleteTcb = 12,
+        /// Bound (100): bound to a port, neither listening nor connected
+        Bound = 100,
+    }
+}
+
+codes! {
+    /// `State` of a neighbor cache entry
+    NeighborState: u8 {
+        /// Unreachable (0)
+        Unreachable = 0,
+        /// Incomplete (1): address resolution is in progress
+        Incomplete = 1,
+        /// Probe (2)
+        Probe = 2,
+        /// Delay (3)
+        Delay = 3,
+        /// Stale (4)
+        Stale = 4,
+        /// Reachable (5)
+        Reachable = 5,
+        /// Permanent (6): a static entry
+        Permanent = 6,
+    }
+}
+
+codes! {
+    /// `EnabledState` of a Hyper-V virtual machine
+    VirtualMachineState: u16 {
+        /// Unknown (0)
+        Unknown = 0,
+        /// Other (1)
+        Other = 1,
+        /// Enabled (2): running
+        Running = 2,
+        /// Disabled (3): off
+        Off = 3,
+        /// Shutting Down (4)
+        ShuttingDown = 4,
+        /// Not Applicable (5)
+        NotApplicable = 5,
+        /// Enabled but Offline (6): saved
+        Saved = 6,
+        /// In Test (7)
+        InTest = 7,
+        /// Deferred (8)
+        Deferred = 8,
+        /// Quiesce (9): paused
+        Paused = 9,
+        /// Starting (10)
+        Starting = 10,
+    }
+}
+
+codes! {
+    /// `EventType` of a power management event
+    PowerEventType: u16 {
+        /// Entering Suspend (4)
+        EnteringSuspend = 4,
+        /// Resume from Suspend (7)
+        ResumeFromSuspend = 7,
+        /// Power Status Change (10): e.g. switching between battery and AC power
+        PowerStatusChange = 10,
+        /// OEM Event (11): see `OEMEventCode`
+        OemEvent = 11,
+        /// Resume Automatic (18): awakened by an event rather than by the user
+        ResumeAutomatic = 18,
+    }
+}
+
+codes! {
+    /// `PointingType` of a pointing device
+    PointingType: u16 {
+        /// Other (1)
+        Other = 1,
+        /// Unknown (2)
+        Unknown = 2,
+        /// Mouse (3)
+        Mouse = 3,
+        /// Track Ball (4)
+        TrackBall = 4,
+        /// Track Point (5)
+        TrackPoint = 5,
+        /// Glide Point (6)
+        GlidePoint = 6,
+        /// Touch Pad (7)
+        TouchPad = 7,
+        /// Touch Screen (8)
+        TouchScreen = 8,
+        /// Mouse - Optical Sensor (9)
+        OpticalMouse = 9,
+    }
+}
+
+codes! {
+    /// `DeviceInterface` of a pointing device, the bus it is connected through
+    PointingInterface: u16 {
+        /// Other (1)
+        Other = 1,
+        /// Unknown (2)
+        Unknown = 2,
+        /// Serial (3)
+        Serial = 3,
+        /// PS/2 (4)
+        Ps2 = 4,
+        /// Infrared (5)
+        Infrared = 5,
+        /// HP-HIL (6)
+        HpHil = 6,
+        /// Bus mouse (7)
+        BusMouse = 7,
+        /// ADB (Apple Desktop Bus) (8)
+        Adb = 8,
+        /// Bus mouse DB-9 (160)
+        BusMouseDb9 = 160,
+        /// Bus mouse micro-DIN (161)
+        BusMouseMicroDin = 161,
+        /// USB (162)
+        Usb = 162,
+    }
+}
+
+codes! {
+    /// `Handedness` of a pointing device
+    Handedness: u16 {
+        /// Unknown (0)
+        Unknown = 0,
+        /// Not Applicable (1)
+        NotApplicable = 1,
+        /// Right Handed Operation (2)
+        RightHanded = 2,
+        /// Left Handed Operation (3)
+        LeftHanded = 3,
+    }
+}
+
+labels! {
+    /// `Status` of a managed element
+    DeviceStatus {
+        /// OK
+        Ok = "OK",
+        /// Error
+        Error = "Error" | "Fehler" | "Erreur",
+        /// Degraded
+        Degraded = "Degraded" | "Beeinträchtigt" | "Dégradé",
+        /// Unknown
+        Unknown = "Unknown" | "Unbekannt" | "Inconnu" | "Desconocido" | "Sconosciuto",
+        /// Pred Fail: a failure is predicted
+        PredFail = "Pred Fail",
+        /// Starting
+        Starting = "Starting",
+        /// Stopping
+        Stopping = "Stopping",
+        /// Service: the element is being serviced
+        Service = "Service",
+        /// Stressed
+        Stressed = "Stressed",
+        /// NonRecover: an unrecoverable error occurred
+        NonRecover = "NonRecover",
+        /// No Contact
+        NoContact = "No Contact",
+        /// Lost Comm: communication was lost
+        LostComm = "Lost Comm",
+    }
+}
+
+labels! {
+    /// `Type` of a disk partition, GPT partitions being reported with a `GPT:` prefix
+    PartitionType {
+        /// Unused
+        Unused = "Unused",
+        /// 12-bit FAT
+        Fat12 = "12-bit FAT",
+        /// Xenix Type 1
+        XenixType1 = "Xenix Type 1",
+        /// Xenix Type 2
+        XenixType2 = "Xenix Type 2",
+        /// 16-bit FAT
+        Fat16 = "16-bit FAT",
+        /// Extended Partition
+        ExtendedPartition = "Extended Partition",
+        /// MS-DOS V4 Huge
+        MsDosV4Huge = "MS-DOS V4 Huge",
+        /// Installable File System: an NTFS or exFAT MBR partition
+        InstallableFileSystem = "Installable File System",
+        /// PowerPC Reference Platform
+        PowerPcReferencePlatform = "PowerPC Reference Platform",
+        /// UNIX
+        Unix = "UNIX",
+        /// NTFS
+        Ntfs = "NTFS",
+        /// Win95 w/Extended Int 13
+        Win95ExtendedInt13 = "Win95 w/Extended Int 13",
+        /// Extended w/Extended Int 13
+        ExtendedInt13 = "Extended w/Extended Int 13",
+        /// Logical Disk Manager
+        LogicalDiskManager = "Logical Disk Manager",
+        /// Unknown
+        Unknown = "Unknown" | "Unbekannt" | "Inconnu" | "Desconocido" | "Sconosciuto",
+        /// GPT: System, the EFI system partition
+        GptSystem = "GPT: System",
+        /// GPT: Basic Data
+        GptBasicData = "GPT: Basic Data",
+        /// GPT: Logical Disk Manager Data
+        GptLogicalDiskManagerData = "GPT: Logical Disk Manager Data",
+        /// GPT: Unknown, e.g. a recovery or Microsoft Reserved partition
+        GptUnknown = "GPT: Unknown" | "GPT: Unbekannt" | "GPT: Inconnu",
+    }
+}
+
+impl PartitionType {
+    /// Returns `true` if the partition belongs to a GPT disk, judged from the `GPT:` prefix that
+    /// stays untranslated when the rest of the label is localized
+    pub fn is_gpt(&self) -> bool {
+        match self {
+            PartitionType::GptSystem
+            | PartitionType::GptBasicData
+            | PartitionType::GptLogicalDiskManagerData
+            | PartitionType::GptUnknown => true,
+            PartitionType::Unrecognized(label) => label.trim_start().to_ascii_uppercase().starts_with("GPT:"),
+            _ => false,
+        }
+    }
+}
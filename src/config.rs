@@ -0,0 +1,409 @@
+//! Configuration of agents embedding the crate, read from environment variables.
+//!
+//! Packaged or containerized agents are tuned by their environment rather than recompiled:
+//! [`Config::from_env`] reads the variables below, [`Config::apply`] installs the process-wide
+//! settings and the custom members, and
+//! [`Windows::async_update_with_config`](crate::state::Windows::async_update_with_config) updates
+//! the selected members within the configured concurrency and timeout, every interval with
+//! [`Windows::watch_with_config`](crate::state::Windows::watch_with_config).
+//!
+//! | Variable                                      | Example                                | Setting                                   |
+//! |-----------------------------------------------|----------------------------------------|-------------------------------------------|
+//! | `WINDOWS_SNAPSHOT_SELECT`                     | `processes,services`, `quick` or `all` | [`Config::selection`]                     |
+//! | `WINDOWS_SNAPSHOT_INTERVAL`                   | `30s`                                  | [`Config::interval`]                      |
+//! | `WINDOWS_SNAPSHOT_CONCURRENCY`                | `4`                                    | [`Config::concurrency`]                   |
+//! | `WINDOWS_SNAPSHOT_TIMEOUT`                    | `2m`                                   | [`Config::timeout`]                       |
+//! | `WINDOWS_SNAPSHOT_RETRY_ATTEMPTS`             | `5`                                    | [`RetryPolicy::attempts`]                 |
+//! | `WINDOWS_SNAPSHOT_RETRY_DELAY`                | `250ms`                                | [`RetryPolicy::initial_delay`]            |
+//! | `WINDOWS_SNAPSHOT_RETRY_MAX_DELAY`            | `10s`                                  | [`RetryPolicy::max_delay`]                |
+//! | `WINDOWS_SNAPSHOT_ROW_LIMITS`                 | `Win32_Directory=10000,Win32_NTLogEvent=5000:spread` | [`Config::row_limits`]      |
+//! | `WINDOWS_SNAPSHOT_HOSTS_FILE`                 | `true`                                 | [`Config::hosts_file`]                    |
+//! | `WINDOWS_SNAPSHOT_CUSTOM_<NAME>`              | `SELECT * FROM AntiVirusProduct`       | WQL of the custom member `<name>`         |
+//! | `WINDOWS_SNAPSHOT_CUSTOM_<NAME>_NAMESPACE`    | `root\SecurityCenter2`                 | Namespace of the custom member `<name>`   |
+//!
+//! Durations are written `500ms`, `30s`, `5m` or `1h`, plain numbers being seconds. Other
+//! variables starting with `WINDOWS_SNAPSHOT_` are rejected, so a misspelt setting is not silently
+//! ignored, and so are settings of categories the crate was built without, such as
+//! `WINDOWS_SNAPSHOT_HOSTS_FILE` without the `os-dns` feature.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::config::Config;
+//! use windows_snapshot::COMLibrary;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let config = Config::from_env().unwrap();
+//!
+//!     let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//!     let mut k = windows_snapshot::state::Windows::default();
+//!     config.apply(&mut k);
+//!     k.async_update_with_config(&config).await;
+//! }
+//! ```
+
+use crate::custom::CustomState;
+use crate::limits::RowLimit;
+use crate::retry::RetryPolicy;
+use crate::state::{SnapshotProfile, Windows};
+use crate::DEFAULT_NAMESPACE;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Prefix of the environment variables [`Config::from_env`] reads
+pub const ENV_PREFIX: &str = "WINDOWS_SNAPSHOT_";
+
+/// Time between two updates when [`Config::interval`] is not set
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Settings of an agent, see the [module documentation](self) for the variables setting them
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Config {
+    /// Members to update, `None` for every member
+    pub selection: Option<SnapshotProfile>,
+    /// Time between two updates of
+    /// [`Windows::watch_with_config`](crate::state::Windows::watch_with_config), `None` for
+    /// [`DEFAULT_INTERVAL`]
+    pub interval: Option<Duration>,
+    /// Number of queries asynchronous updates run at a time, `None` for
+    /// [`DEFAULT_CONCURRENCY`](crate::state::DEFAULT_CONCURRENCY)
+    pub concurrency: Option<usize>,
+    /// Time the asynchronous update of a member may take before it is given up, `None` for no
+    /// limit
+    pub timeout: Option<Duration>,
+    /// How failed queries are retried, `None` to keep the current policy
+    pub retry: Option<RetryPolicy>,
+    /// Row limits by class, e.g. `Win32_Directory`
+    pub row_limits: BTreeMap<String, RowLimit>,
+    /// Read the hosts file, see `HostsFile::enabled`
+    pub hosts_file: bool,
+    /// Custom members by name
+    pub custom: BTreeMap<String, CustomQuery>,
+}
+
+/// Query of a custom member, see [`CustomState`]
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CustomQuery {
+    /// Namespace the query runs in
+    pub namespace: String,
+    /// The WQL query
+    pub wql: String,
+}
+
+/// A variable [`Config::from_env`] could not read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    /// Name of the variable
+    pub variable: String,
+    /// Value of the variable
+    pub value: String,
+    /// Why the value was rejected
+    pub reason: String,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}=`{}`: {}", self.variable, self.value, self.reason)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Reads the settings from the environment variables of the process
+    ///
+    /// Variables that are not valid Unicode are ignored, unless their name starts with the
+    /// [`ENV_PREFIX`].
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut vars = Vec::new();
+
+        for (variable, value) in std::env::vars_os() {
+            match (variable.into_string(), value.into_string()) {
+                (Ok(variable), Ok(value)) => vars.push((variable, value)),
+                (variable, value) => {
+                    let variable = variable.unwrap_or_else(|variable| variable.to_string_lossy().into_owned());
+
+                    if variable.starts_with(ENV_PREFIX) {
+                        return Err(ConfigError {
+                            variable,
+                            value: value.unwrap_or_else(|value| value.to_string_lossy().into_owned()),
+                            reason: "not valid Unicode".to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Config::from_vars(vars)
+    }
+
+    /// Reads the settings from `vars`, named like environment variables, e.g. from a service
+    /// configuration file. Variables without the [`ENV_PREFIX`] are ignored.
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Result<Self, ConfigError> {
+        let mut config = Config::default();
+        let mut retry = None;
+        let mut namespaces = BTreeMap::new();
+
+        for (variable, value) in vars {
+            let Some(setting) = variable.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let error = |reason: String| ConfigError {
+                variable: variable.clone(),
+                value: value.clone(),
+                reason,
+            };
+
+            match setting {
+                "SELECT" => config.selection = Some(parse_selection(&value).map_err(error)?),
+                "INTERVAL" => match parse_duration(&value).map_err(error)? {
+                    Duration::ZERO => return Err(error("the interval must not be zero".to_string())),
+                    interval => config.interval = Some(interval),
+                },
+                "CONCURRENCY" => config.concurrency = Some(parse_number(&value).map_err(error)?),
+                "TIMEOUT" => config.timeout = Some(parse_duration(&value).map_err(error)?),
+                "RETRY_ATTEMPTS" => {
+                    let attempts = parse_number(&value).map_err(error)?;
+                    retry.get_or_insert_with(RetryPolicy::default).attempts = attempts;
+                }
+                "RETRY_DELAY" => {
+                    let initial_delay = parse_duration(&value).map_err(error)?;
+                    retry.get_or_insert_with(RetryPolicy::default).initial_delay = initial_delay;
+                }
+                "RETRY_MAX_DELAY" => {
+                    let max_delay = parse_duration(&value).map_err(error)?;
+                    retry.get_or_insert_with(RetryPolicy::default).max_delay = max_delay;
+                }
+                "ROW_LIMITS" => config.row_limits = parse_row_limits(&value).map_err(error)?,
+                #[cfg(feature = "os-dns")]
+                "HOSTS_FILE" => config.hosts_file = parse_bool(&value).map_err(error)?,
+                #[cfg(not(feature = "os-dns"))]
+                "HOSTS_FILE" => return Err(error("built without the `os-dns` feature".to_string())),
+                _ => match setting.strip_prefix("CUSTOM_") {
+                    Some(name) => match name.strip_suffix("_NAMESPACE") {
+                        Some(name) => {
+                            namespaces.insert(name.to_lowercase(), value.clone());
+                        }
+                        None => {
+                            config.custom.insert(
+                                name.to_lowercase(),
+                                CustomQuery {
+                                    namespace: DEFAULT_NAMESPACE.to_string(),
+                                    wql: value.clone(),
+                                },
+                            );
+                        }
+                    },
+                    None => return Err(error("unknown variable".to_string())),
+                },
+            }
+        }
+
+        for (name, namespace) in namespaces {
+            let Some(query) = config.custom.get_mut(&name) else {
+                return Err(ConfigError {
+                    variable: format!("{ENV_PREFIX}CUSTOM_{}_NAMESPACE", name.to_uppercase()),
+                    value: namespace,
+                    reason: format!("no {ENV_PREFIX}CUSTOM_{} query", name.to_uppercase()),
+                });
+            };
+
+            query.namespace = namespace;
+        }
+
+        config.retry = retry;
+
+        Ok(config)
+    }
+
+    /// Time between two updates, [`DEFAULT_INTERVAL`] without an interval
+    pub fn interval(&self) -> Duration {
+        self.interval.unwrap_or(DEFAULT_INTERVAL)
+    }
+
+    /// Members to update, every member without a selection
+    pub fn profile(&self) -> SnapshotProfile {
+        self.selection.clone().unwrap_or_else(SnapshotProfile::all)
+    }
+
+    /// Installs the retry policy and the row limits, process-wide, registers the custom members on
+    /// `windows` and enables its hosts file when configured
+    pub fn apply(&self, windows: &mut Windows) {
+        if let Some(retry) = &self.retry {
+            crate::retry::set_policy(retry.clone());
+        }
+
+        for (class, limit) in &self.row_limits {
+            crate::limits::set_limit(class, *limit);
+        }
+
+        for (name, query) in &self.custom {
+            windows.register_custom(name, CustomState::in_namespace(&query.namespace, &query.wql));
+        }
+
+        #[cfg(feature = "os-dns")]
+        if self.hosts_file {
+            windows.hosts_file.enabled = true;
+        }
+    }
+}
+
+/// Parses durations such as `500ms`, `30s`, `5m` or `1h`, plain numbers being seconds
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let digits = value.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let amount: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid duration `{value}`"))?;
+
+    let overflow = || format!("duration `{value}` is too long");
+
+    match &value[digits.len()..] {
+        "ms" => Ok(Duration::from_millis(amount)),
+        "" | "s" => Ok(Duration::from_secs(amount)),
+        "m" => Ok(Duration::from_secs(amount.checked_mul(60).ok_or_else(overflow)?)),
+        "h" => Ok(Duration::from_secs(amount.checked_mul(60 * 60).ok_or_else(overflow)?)),
+        unit => Err(format!("unknown duration unit `{unit}`")),
+    }
+}
+
+/// Parses comma separated member names, or `all` or `quick` for [`SnapshotProfile::all`] and
+/// [`SnapshotProfile::quick`]
+fn parse_selection(value: &str) -> Result<SnapshotProfile, String> {
+    match value.trim() {
+        "all" => Ok(SnapshotProfile::all()),
+        "quick" => Ok(SnapshotProfile::quick()),
+        names => SnapshotProfile::from_names(names).map_err(|name| format!("unknown member `{name}`")),
+    }
+}
+
+/// Parses `true` or `false`, `1` or `0`
+#[allow(dead_code)] // unused when the categories relying on it are disabled
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(format!("invalid boolean `{value}`")),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid number `{value}`"))
+}
+
+/// Parses `Class=rows` pairs separated by commas, `:spread` after the rows sampling the instances
+fn parse_row_limits(value: &str) -> Result<BTreeMap<String, RowLimit>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|limit| !limit.is_empty())
+        .map(|limit| {
+            let (class, rows) = limit
+                .split_once('=')
+                .ok_or_else(|| format!("expected `Class=rows`, found `{limit}`"))?;
+            let (rows, sampling) = rows.split_once(':').unwrap_or((rows, ""));
+            let limit = RowLimit::new(parse_number(rows)?);
+
+            let limit = match sampling {
+                "" | "first" => limit,
+                "spread" => limit.sampled(),
+                sampling => return Err(format!("unknown sampling `{sampling}`")),
+            };
+
+            Ok((class.trim().to_string(), limit))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::limits::Sampling;
+
+    fn from_vars(vars: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let vars = vars
+            .iter()
+            .map(|(variable, value)| (format!("{ENV_PREFIX}{variable}"), value.to_string()));
+
+        Config::from_vars(vars)
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration(" 45 "), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(5 * 60)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(60 * 60)));
+        assert_eq!(parse_duration(&format!("{}ms", u64::MAX)), Ok(Duration::from_millis(u64::MAX)));
+    }
+
+    #[test]
+    fn rejects_invalid_and_overflowing_durations() {
+        for value in ["", "s", "-1s", "1.5s", "ten"] {
+            assert_eq!(parse_duration(value), Err(format!("invalid duration `{}`", value.trim())));
+        }
+        assert_eq!(parse_duration("2d"), Err("unknown duration unit `d`".to_string()));
+        assert_eq!(parse_duration("2 s"), Err("invalid duration `2 s`".to_string()));
+
+        let minutes = format!("{}m", u64::MAX / 60 + 1);
+        let hours = format!("{}h", u64::MAX / 3600 + 1);
+
+        assert_eq!(parse_duration(&minutes), Err(format!("duration `{minutes}` is too long")));
+        assert_eq!(parse_duration(&hours), Err(format!("duration `{hours}` is too long")));
+
+        let seconds = (u128::from(u64::MAX) + 1).to_string();
+        assert_eq!(parse_duration(&seconds), Err(format!("invalid duration `{seconds}`")));
+
+        let error = from_vars(&[("TIMEOUT", &hours)]).unwrap_err();
+        assert_eq!(error.variable, "WINDOWS_SNAPSHOT_TIMEOUT");
+        assert_eq!(error.value, hours);
+
+        assert_eq!(from_vars(&[("INTERVAL", "0s")]).unwrap_err().reason, "the interval must not be zero");
+        assert_eq!(from_vars(&[("INTERVAL", "2m")]).unwrap().interval(), Duration::from_secs(120));
+        assert_eq!(Config::default().interval(), DEFAULT_INTERVAL);
+    }
+
+    #[test]
+    fn parses_row_limits() {
+        let limits =
+            parse_row_limits(" Win32_Directory=10000, Win32_NTLogEvent=5000:spread,,Win32_Process=10:first ").unwrap();
+
+        assert_eq!(limits.len(), 3);
+        assert_eq!(limits["Win32_Directory"], RowLimit::new(10000));
+        assert_eq!(limits["Win32_NTLogEvent"], RowLimit::new(5000).sampled());
+        assert_eq!(limits["Win32_Process"].sampling, Sampling::First);
+        assert!(parse_row_limits("").unwrap().is_empty());
+
+        assert_eq!(
+            parse_row_limits("Win32_Directory"),
+            Err("expected `Class=rows`, found `Win32_Directory`".to_string())
+        );
+        assert_eq!(parse_row_limits("Win32_Directory=many"), Err("invalid number `many`".to_string()));
+        assert_eq!(parse_row_limits("Win32_Directory=-1"), Err("invalid number `-1`".to_string()));
+        assert_eq!(parse_row_limits("Win32_Directory=10:random"), Err("unknown sampling `random`".to_string()));
+
+        let config = from_vars(&[("ROW_LIMITS", "Win32_Directory=10")]).unwrap();
+        assert_eq!(config.row_limits["Win32_Directory"], RowLimit::new(10));
+        assert_eq!(
+            from_vars(&[("ROW_LIMITS", "Win32_Directory=10:all")]).unwrap_err().variable,
+            "WINDOWS_SNAPSHOT_ROW_LIMITS"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_unavailable_settings() {
+        assert_eq!(from_vars(&[("INTERVALL", "30s")]).unwrap_err().reason, "unknown variable");
+
+        #[cfg(feature = "os-dns")]
+        assert!(from_vars(&[("HOSTS_FILE", "1")]).unwrap().hosts_file);
+        #[cfg(not(feature = "os-dns"))]
+        assert!(from_vars(&[("HOSTS_FILE", "1")]).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! User-defined state members backed by arbitrary WQL queries.
+//!
+//! The built-in members cover the classes the crate models. A [`CustomState`] wraps any WQL query,
+//! e.g. over a vendor class or a class of another namespace, and is updated like the built-in
+//! states: it records when it was updated, whether the update changed it and why the query failed.
+//!
+//! Its instances are typed as any `T` deserializing from WMI, or left untyped as
+//! [`CustomInstance`]s. Untyped custom states registered on
+//! [`Windows`](crate::state::Windows) with `Windows::register_custom` are updated with the
+//! built-in members, saved with the snapshot and compared by [`WindowsDiff`](crate::watch::WindowsDiff).
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::custom::CustomState;
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.register_custom(
+//!     "antivirus",
+//!     CustomState::in_namespace("root\\SecurityCenter2", "SELECT * FROM AntiVirusProduct"),
+//! );
+//! k.update();
+//!
+//! for instance in &k.custom["antivirus"].instances {
+//!     println!("{:?}", instance.get("displayName"));
+//! }
+//! ```
+
+use crate::extra::JsonText;
+use crate::{async_raw_query_in, raw_query_in, Unavailable, DEFAULT_NAMESPACE};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::time::SystemTime;
+
+/// An untyped instance, its properties and their values, `null` for unset ones
+///
+/// Values are kept as [`JsonText`] so snapshots holding custom members still encode with the
+/// binary formats of [`snapshot`](crate::snapshot).
+pub type CustomInstance = BTreeMap<String, JsonText>;
+
+/// Represents the state of the instances a WQL query returns
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct CustomState<T = CustomInstance> {
+    /// Namespace the query runs in
+    pub namespace: String,
+    /// The WQL query, e.g. `SELECT * FROM AntiVirusProduct`
+    pub wql: String,
+    /// Sequence of the instances the query returned
+    pub instances: Vec<T>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the query failed, `None` when it succeeded
+    pub unavailable: Option<Unavailable>,
+}
+
+impl<T: Serialize> Hash for CustomState<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.namespace.hash(state);
+        self.wql.hash(state);
+        serde_json::to_string(&self.instances)
+            .unwrap_or_default()
+            .hash(state);
+        self.last_updated.hash(state);
+        self.state_change.hash(state);
+        self.unavailable.hash(state);
+    }
+}
+
+impl<T> CustomState<T> {
+    /// A state running `wql` in [`DEFAULT_NAMESPACE`], empty until it is updated
+    pub fn new(wql: &str) -> Self {
+        CustomState::in_namespace(DEFAULT_NAMESPACE, wql)
+    }
+
+    /// A state running `wql` in `namespace`, empty until it is updated
+    pub fn in_namespace(namespace: &str, wql: &str) -> Self {
+        CustomState {
+            namespace: namespace.to_string(),
+            wql: wql.to_string(),
+            instances: Vec::new(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+
+    /// Number of instances the state holds
+    pub fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    /// Returns `true` if the state holds no instance
+    pub fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+}
+
+impl<T: DeserializeOwned + PartialEq> CustomState<T> {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let result = raw_query_in(&self.namespace, &self.wql)
+            .and_then(|instances| self.typed(instances));
+        self.keep(result);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let result = async_raw_query_in(&self.namespace, &self.wql)
+            .await
+            .and_then(|instances| self.typed(instances));
+        self.keep(result);
+    }
+
+    /// Converts the untyped `instances` into `T`, leaving out the system properties such as
+    /// `__PATH`
+    fn typed(&self, instances: Vec<HashMap<String, wmi::Variant>>) -> Result<Vec<T>, Unavailable> {
+        instances
+            .into_iter()
+            .map(|instance| {
+                let properties = instance
+                    .into_iter()
+                    .filter(|(name, _)| !name.starts_with("__"))
+                    .map(|(name, value)| (name, crate::extra::to_json(&value)))
+                    .collect();
+
+                serde_json::from_value(Value::Object(properties)).map_err(|e| {
+                    Unavailable::new(
+                        &self.namespace,
+                        &crate::queried_class::<T>(&self.wql),
+                        &wmi::WMIError::SerdeError(e.to_string()),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Replaces the instances with the outcome of a query
+    fn keep(&mut self, result: Result<Vec<T>, Unavailable>) {
+        match result {
+            Ok(instances) => {
+                self.state_change = instances != self.instances || self.unavailable.is_some();
+                self.instances = instances;
+                self.unavailable = None;
+            }
+            Err(unavailable) => {
+                let unavailable = Some(unavailable);
+
+                self.state_change = !self.instances.is_empty() || self.unavailable != unavailable;
+                self.instances.clear();
+                self.unavailable = unavailable;
+            }
+        }
+    }
+}
+
+impl<T: Serialize> CustomState<T> {
+    /// The instances as `U`, e.g. to read the untyped instances of a registered state as a struct
+    pub fn instances_as<U: DeserializeOwned>(&self) -> Result<Vec<U>, serde_json::Error> {
+        self.instances
+            .iter()
+            .map(|instance| serde_json::from_value(serde_json::to_value(instance)?))
+            .collect()
+    }
+}
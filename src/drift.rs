@@ -0,0 +1,429 @@
+//! Configuration drift: how the live state deviates from a baseline snapshot.
+//!
+//! A [`Baseline`] holds any earlier [`Windows`] snapshot, taken with [`Baseline::of`] or loaded
+//! from disk and converted with [`Baseline::from`]. [`Baseline::drift`] then lists the instances
+//! added, removed or changed since, matched by their natural key. Only the properties
+//! that describe configuration are compared (a service's start mode, not its process id), so a
+//! report taken right after the baseline is empty.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::drift::Baseline;
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.update();
+//! let baseline = Baseline::of(&k);
+//!
+//! // ... later
+//! k.update();
+//!
+//! for deviation in baseline.drift(&k).deviations {
+//!     println!("{deviation}");
+//! }
+//! ```
+
+use crate::report::{Normalize, ReportValue};
+use crate::state::Windows;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::time::SystemTime;
+
+/// Part of the snapshot a deviation was found in
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DriftCategory {
+    /// `Win32_Service`, keyed by `Name`
+    Service,
+    /// `Win32_SystemDriver`, keyed by `Name`
+    Driver,
+    /// `Win32_UserAccount`, keyed by `SID`
+    UserAccount,
+    /// `Win32_Group`, keyed by `SID`
+    Group,
+    /// `Win32_StartupCommand`, keyed by `Location`, `User` and `Name`
+    StartupCommand,
+    /// `Win32_Share`, keyed by `Name`
+    Share,
+    /// Installed applications, keyed by their identifier
+    InstalledSoftware,
+    /// `Win32_QuickFixEngineering`, keyed by `HotFixID`
+    QuickFixEngineering,
+    /// `Win32_OptionalFeature`, keyed by `Name`
+    OptionalFeature,
+    /// Hosts file entries, keyed by address and host name
+    HostsEntry,
+    /// Audit policy subcategories, keyed by their GUID
+    AuditPolicy,
+    /// UAC and LSA registry values, keyed by their key and name
+    SecurityPolicyValue,
+    /// `Win32_Environment`, keyed by `UserName` and `Name`
+    EnvironmentVariable,
+}
+
+/// What happened to an instance since the baseline
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DeviationKind {
+    /// The instance is not in the baseline
+    Added,
+    /// The instance is no longer present
+    Removed,
+    /// The instance is present in both with different properties
+    Changed,
+}
+
+/// A property whose value differs from the baseline
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PropertyChange {
+    /// Name of the property
+    pub property: String,
+    /// Value in the baseline, `None` when unknown
+    pub baseline: Option<String>,
+    /// Current value, `None` when unknown
+    pub current: Option<String>,
+}
+
+/// An instance that deviates from the baseline
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Deviation {
+    /// Part of the snapshot the instance belongs to
+    pub category: DriftCategory,
+    /// Natural key of the instance
+    pub key: String,
+    /// What happened to the instance
+    pub kind: DeviationKind,
+    /// Changed properties, empty unless `kind` is [`DeviationKind::Changed`]
+    pub changes: Vec<PropertyChange>,
+}
+
+impl fmt::Display for Deviation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {} {:?}", self.category, self.key, self.kind)?;
+
+        for change in &self.changes {
+            write!(
+                f,
+                ", {}: {} -> {}",
+                change.property,
+                change.baseline.as_deref().unwrap_or(crate::report::UNKNOWN),
+                change.current.as_deref().unwrap_or(crate::report::UNKNOWN)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A snapshot later states are compared against, kept apart from the live [`Windows`] so it is
+/// neither serialized, hashed nor cloned with it
+#[derive(Debug, Clone)]
+pub struct Baseline {
+    snapshot: Windows,
+}
+
+impl Baseline {
+    /// The current state of `snapshot` as the baseline
+    pub fn of(snapshot: &Windows) -> Self {
+        Baseline {
+            snapshot: snapshot.clone(),
+        }
+    }
+
+    /// The snapshot compared against
+    pub fn snapshot(&self) -> &Windows {
+        &self.snapshot
+    }
+
+    /// Compares `current` against the baseline
+    pub fn drift(&self, current: &Windows) -> DriftReport {
+        DriftReport::between(&self.snapshot, current)
+    }
+}
+
+impl From<Windows> for Baseline {
+    /// Uses `snapshot`, e.g. loaded from disk, as the baseline
+    fn from(snapshot: Windows) -> Self {
+        Baseline { snapshot }
+    }
+}
+
+/// Deviations of a snapshot from its baseline
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct DriftReport {
+    /// When the capture of the baseline finished, or started if it never finished,
+    /// `UNIX_EPOCH` for a baseline that was never updated
+    pub baseline_taken: SystemTime,
+    /// When the report was generated
+    pub generated: SystemTime,
+    /// Deviations, ordered by category then key
+    pub deviations: Vec<Deviation>,
+}
+
+impl DriftReport {
+    /// Compares `current` against `baseline`
+    #[allow(unused_variables)] // without the categories compared, nothing reads the snapshots
+    pub fn between(baseline: &Windows, current: &Windows) -> Self {
+        #[allow(unused_mut)]
+        let mut deviations = Vec::new();
+
+        #[cfg(feature = "os-services")]
+        compare(
+            &mut deviations,
+            DriftCategory::Service,
+            &baseline.services.services,
+            &current.services.services,
+            |service| service.Name.clone(),
+            &["PathName", "StartMode", "StartName"],
+        );
+        #[cfg(feature = "os-drivers")]
+        compare(
+            &mut deviations,
+            DriftCategory::Driver,
+            &baseline.drivers.drivers,
+            &current.drivers.drivers,
+            |driver| driver.Name.clone(),
+            &["PathName", "StartMode"],
+        );
+        #[cfg(feature = "os-users")]
+        compare(
+            &mut deviations,
+            DriftCategory::UserAccount,
+            &baseline.user_accounts.user_accounts,
+            &current.user_accounts.user_accounts,
+            |user| user.SID.clone(),
+            &["Name", "Disabled", "Lockout", "PasswordRequired", "PasswordExpires"],
+        );
+        #[cfg(feature = "os-users")]
+        compare(
+            &mut deviations,
+            DriftCategory::Group,
+            &baseline.groups.groups,
+            &current.groups.groups,
+            |group| group.SID.clone(),
+            &["Name"],
+        );
+        #[cfg(feature = "os-operating-system-settings")]
+        compare(
+            &mut deviations,
+            DriftCategory::StartupCommand,
+            &baseline.startup_commands.startup_commands,
+            &current.startup_commands.startup_commands,
+            |command| {
+                Some(format!(
+                    "{}\\{}\\{}",
+                    command.Location.as_deref().unwrap_or_default(),
+                    command.User.as_deref().unwrap_or_default(),
+                    command.Name.as_deref()?
+                ))
+            },
+            &["Command"],
+        );
+        #[cfg(feature = "os-shares")]
+        compare(
+            &mut deviations,
+            DriftCategory::Share,
+            &baseline.shares.shares,
+            &current.shares.shares,
+            |share| share.Name.clone(),
+            &["Path", "Type"],
+        );
+        #[cfg(feature = "os-installed-software")]
+        compare(
+            &mut deviations,
+            DriftCategory::InstalledSoftware,
+            &baseline.installed_software.installed_software,
+            &current.installed_software.installed_software,
+            |application| Some(application.identifier.clone()),
+            &["name", "version", "vendor"],
+        );
+        #[cfg(feature = "os-operating-system-settings")]
+        compare(
+            &mut deviations,
+            DriftCategory::QuickFixEngineering,
+            &baseline.quick_fix_engineerings.quick_fix_engineerings,
+            &current.quick_fix_engineerings.quick_fix_engineerings,
+            |update| update.HotFixID.clone(),
+            &[],
+        );
+        #[cfg(feature = "os-optional-features")]
+        compare(
+            &mut deviations,
+            DriftCategory::OptionalFeature,
+            &baseline.optional_features.optional_features,
+            &current.optional_features.optional_features,
+            |feature| feature.Name.clone(),
+            &["InstallState"],
+        );
+        #[cfg(feature = "os-dns")]
+        compare(
+            &mut deviations,
+            DriftCategory::HostsEntry,
+            &baseline.hosts_file.hosts_entries,
+            &current.hosts_file.hosts_entries,
+            |entry| Some(format!("{} {}", entry.address, entry.hostname.to_ascii_lowercase())),
+            &[],
+        );
+        #[cfg(feature = "os-security-settings")]
+        compare(
+            &mut deviations,
+            DriftCategory::AuditPolicy,
+            &baseline.security_settings.audit_policies,
+            &current.security_settings.audit_policies,
+            |policy| Some(policy.subcategory_guid.clone()),
+            &["success", "failure"],
+        );
+        #[cfg(feature = "os-security-settings")]
+        compare(
+            &mut deviations,
+            DriftCategory::SecurityPolicyValue,
+            &baseline.security_settings.policy_values,
+            &current.security_settings.policy_values,
+            |value| Some(format!("{}\\{}", value.key, value.name)),
+            &["value"],
+        );
+        #[cfg(feature = "os-desktop")]
+        environment_drift(&mut deviations, &baseline.environment, &current.environment);
+
+        let baseline_taken = baseline
+            .meta
+            .capture_finished
+            .or(baseline.meta.capture_started)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        DriftReport {
+            baseline_taken,
+            generated: SystemTime::now(),
+            deviations,
+        }
+    }
+
+    /// Returns `true` if the snapshot matches its baseline
+    pub fn is_empty(&self) -> bool {
+        self.deviations.is_empty()
+    }
+
+    /// Deviations found in `category`
+    pub fn in_category(&self, category: DriftCategory) -> impl Iterator<Item = &Deviation> {
+        self.deviations
+            .iter()
+            .filter(move |deviation| deviation.category == category)
+    }
+}
+
+/// Matches `baseline` and `current` instances by `key`, comparing `properties` of the instances
+/// present in both. Instances without a key are ignored.
+#[allow(dead_code)]
+fn compare<T: Serialize>(
+    deviations: &mut Vec<Deviation>,
+    category: DriftCategory,
+    baseline: &[T],
+    current: &[T],
+    key: impl Fn(&T) -> Option<String>,
+    properties: &[&str],
+) {
+    let index = |instances: &[T]| -> BTreeMap<String, Vec<Option<String>>> {
+        instances
+            .iter()
+            .filter_map(|instance| {
+                let row = instance.normalized();
+                let values = properties
+                    .iter()
+                    .map(|property| property_value(row.get(property)))
+                    .collect();
+
+                Some((key(instance)?, values))
+            })
+            .collect()
+    };
+
+    let baseline = index(baseline);
+    let current = index(current);
+
+    let keys: BTreeSet<&String> = baseline.keys().chain(current.keys()).collect();
+
+    for key in keys {
+        let (kind, changes) = match (baseline.get(key), current.get(key)) {
+            (None, Some(_)) => (DeviationKind::Added, Vec::new()),
+            (Some(_), None) => (DeviationKind::Removed, Vec::new()),
+            (Some(old), Some(new)) => {
+                let changes: Vec<PropertyChange> = properties
+                    .iter()
+                    .zip(old.iter().zip(new))
+                    .filter(|(_, (old, new))| old != new)
+                    .map(|(property, (old, new))| PropertyChange {
+                        property: property.to_string(),
+                        baseline: old.clone(),
+                        current: new.clone(),
+                    })
+                    .collect();
+
+                if changes.is_empty() {
+                    continue;
+                }
+
+                (DeviationKind::Changed, changes)
+            }
+            (None, None) => continue,
+        };
+
+        deviations.push(Deviation {
+            category,
+            key: key.clone(),
+            kind,
+            changes,
+        });
+    }
+}
+
+/// The variables [`Environments::drift`](crate::operating_system::desktop::Environments::drift)
+/// finds added, removed or modified, keyed by owner and name
+#[cfg(feature = "os-desktop")]
+fn environment_drift(
+    deviations: &mut Vec<Deviation>,
+    baseline: &crate::operating_system::desktop::Environments,
+    current: &crate::operating_system::desktop::Environments,
+) {
+    use crate::operating_system::desktop::EnvironmentChangeKind;
+
+    let mut changes = current
+        .drift(baseline)
+        .into_iter()
+        .map(|change| {
+            let key = format!(
+                "{}\\{}",
+                change.user_name.as_deref().unwrap_or_default(),
+                change.name.as_deref().unwrap_or_default()
+            );
+            let (kind, changes) = match change.kind {
+                EnvironmentChangeKind::Added => (DeviationKind::Added, Vec::new()),
+                EnvironmentChangeKind::Removed => (DeviationKind::Removed, Vec::new()),
+                EnvironmentChangeKind::Modified => (
+                    DeviationKind::Changed,
+                    vec![PropertyChange {
+                        property: "VariableValue".to_string(),
+                        baseline: change.old_value,
+                        current: change.new_value,
+                    }],
+                ),
+            };
+
+            Deviation {
+                category: DriftCategory::EnvironmentVariable,
+                key,
+                kind,
+                changes,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    changes.sort_by(|a, b| a.key.cmp(&b.key));
+    deviations.extend(changes);
+}
+
+#[allow(dead_code)]
+fn property_value(value: &ReportValue) -> Option<String> {
+    (!value.is_unknown()).then(|| value.to_string())
+}
@@ -0,0 +1,176 @@
+//! Subscriptions to WMI events, delivered through a channel or a stream as they happen.
+//!
+//! Event classes such as `Win32_PowerManagementEvent` hold no instances, a one-shot query returns
+//! nothing meaningful. [`subscribe`] registers a notification query on a dedicated thread with its
+//! own COM apartment and pushes every event into a channel, usable from synchronous code with
+//! `blocking_recv` and from asynchronous code with `recv`. [`stream`] wraps the channel in a
+//! [`Stream`].
+//!
+//! Both extrinsic events, e.g. `SELECT * FROM Win32_PowerManagementEvent`, and intrinsic events,
+//! e.g. `SELECT * FROM __InstanceModificationEvent WITHIN 2 WHERE TargetInstance ISA 'Win32_Service'`,
+//! can be subscribed to.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::hardware::power::Win32_PowerManagementEvent;
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut events = windows_snapshot::events::subscribe::<Win32_PowerManagementEvent>(
+//!     windows_snapshot::DEFAULT_NAMESPACE,
+//!     "SELECT * FROM Win32_PowerManagementEvent",
+//! )
+//! .unwrap();
+//!
+//! while let Some(event) = events.blocking_recv() {
+//!     println!("{:?}", event.event_type());
+//! }
+//! ```
+
+use crate::Unavailable;
+use futures::stream::{self, Stream};
+use serde::de::DeserializeOwned;
+use std::thread;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use wmi::{COMLibrary, WMIConnection};
+
+/// Subscribes to the events `wql` selects in `namespace` and pushes them into the returned channel
+///
+/// The channel closes when the subscription fails. Dropping the receiver ends the subscription at
+/// the next event. Events that do not deserialize into `T` are skipped, with a warning under the
+/// `tracing` feature, and the subscription goes on, see [`subscribe_results`] to receive them.
+pub fn subscribe<T>(namespace: &str, wql: &str) -> Result<UnboundedReceiver<T>, Unavailable>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    subscribe_map(namespace, wql, |event: T| event)
+}
+
+/// Like [`subscribe`], converting every event with `convert` before pushing it, e.g. to turn an
+/// intrinsic event into a change of the instance it targets
+pub fn subscribe_map<E, T>(
+    namespace: &str,
+    wql: &str,
+    mut convert: impl FnMut(E) -> T + Send + 'static,
+) -> Result<UnboundedReceiver<T>, Unavailable>
+where
+    E: DeserializeOwned,
+    T: Send + 'static,
+{
+    subscribe_with(namespace, wql, move |event: Result<E, Unavailable>| match event {
+        Ok(event) => Some(convert(event)),
+        Err(unavailable) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                class = %unavailable.class,
+                namespace = %unavailable.namespace,
+                error = %unavailable.message,
+                "event skipped"
+            );
+            #[cfg(not(feature = "tracing"))]
+            let _ = unavailable;
+
+            None
+        }
+    })
+}
+
+/// Like [`subscribe`], also pushing the events that do not deserialize into `T` as errors instead
+/// of skipping them
+pub fn subscribe_results<T>(
+    namespace: &str,
+    wql: &str,
+) -> Result<UnboundedReceiver<Result<T, Unavailable>>, Unavailable>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    subscribe_with(namespace, wql, Some)
+}
+
+/// Registers the notification query on its own thread and pushes what `convert` makes of every
+/// event, or of the error deserializing it, leaving out `None`s
+fn subscribe_with<E, T>(
+    namespace: &str,
+    wql: &str,
+    mut convert: impl FnMut(Result<E, Unavailable>) -> Option<T> + Send + 'static,
+) -> Result<UnboundedReceiver<T>, Unavailable>
+where
+    E: DeserializeOwned,
+    T: Send + 'static,
+{
+    let class = crate::queried_class::<E>(wql);
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let (subscribed, subscription) = std::sync::mpsc::sync_channel(1);
+
+    let unavailable = {
+        let (namespace, class) = (namespace.to_string(), class.clone());
+        move |e: &wmi::WMIError| Unavailable::new(&namespace, &class, e)
+    };
+    let (thread_namespace, thread_wql) = (namespace.to_string(), wql.to_string());
+
+    thread::spawn(move || {
+        let wmi_con = match COMLibrary::without_security()
+            .and_then(|com_con| WMIConnection::with_namespace_path(&thread_namespace, com_con))
+        {
+            Ok(wmi_con) => wmi_con,
+            Err(e) => {
+                let _ = subscribed.send(Err(unavailable(&e)));
+                return;
+            }
+        };
+        let events = match wmi_con.raw_notification::<E>(&thread_wql) {
+            Ok(events) => events,
+            Err(e) => {
+                let _ = subscribed.send(Err(unavailable(&e)));
+                return;
+            }
+        };
+
+        let _ = subscribed.send(Ok(()));
+
+        for event in events {
+            let converted = match event {
+                Ok(event) => convert(Ok(event)),
+                Err(e @ wmi::WMIError::SerdeError(_)) => convert(Err(unavailable(&e))),
+                Err(_) => break,
+            };
+
+            if let Some(converted) = converted {
+                if sender.send(converted).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    subscription
+        .recv()
+        .unwrap_or_else(|e| {
+            Err(Unavailable {
+                namespace: namespace.to_string(),
+                class,
+                reason: crate::UnavailableReason::Other,
+                message: e.to_string(),
+            })
+        })
+        .map(|_| receiver)
+}
+
+/// [`subscribe`] as a [`Stream`] of events, which ends when the subscription fails
+pub fn stream<T>(namespace: &str, wql: &str) -> Result<impl Stream<Item = T>, Unavailable>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    subscribe(namespace, wql).map(into_stream)
+}
+
+/// Turns the receiver of a subscription into a [`Stream`]
+pub fn into_stream<T>(receiver: UnboundedReceiver<T>) -> impl Stream<Item = T> {
+    stream::unfold(receiver, |mut receiver| async move {
+        let event = receiver.recv().await?;
+
+        Some((event, receiver))
+    })
+}
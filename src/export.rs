@@ -0,0 +1,266 @@
+//! Flat file exports of state collections for analytics tooling.
+//!
+//! Every state built with [`update!`](crate::update) gets `export_csv`, and `export_parquet` with
+//! the `parquet` feature, writing its collection through [`write_csv`] and [`write_parquet`].
+//! Those functions take any slice of serializable instances, so collections of hand-written states
+//! export the same way. Columns come from [`Table::from_instances`]: one per property, nested
+//! values flattened to text, columns empty in every row left out.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.logical_disks.update();
+//!
+//! k.logical_disks.export_csv("logical_disks.csv").unwrap();
+//! ```
+
+use crate::report::{ReportValue, Table};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Writes `instances` as CSV to the file at `path`, replacing it
+pub fn write_csv<T: Serialize>(instances: &[T], path: impl AsRef<Path>) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    to_csv(instances, &mut writer)?;
+
+    writer.flush()
+}
+
+/// Writes `instances` as CSV to `writer`: a header row then one row per instance, unknown values
+/// as empty fields
+///
+/// Nothing is written when there are no columns, e.g. without instances.
+pub fn to_csv<T: Serialize>(instances: &[T], mut writer: impl Write) -> io::Result<()> {
+    let table = Table::from_instances(instances);
+
+    if table.columns.is_empty() {
+        return Ok(());
+    }
+
+    write_csv_record(&mut writer, table.columns.iter().map(String::as_str))?;
+
+    for row in &table.rows {
+        let fields: Vec<String> = row.iter().map(csv_field).collect();
+
+        write_csv_record(&mut writer, fields.iter().map(String::as_str))?;
+    }
+
+    Ok(())
+}
+
+fn csv_field(value: &ReportValue) -> String {
+    if value.is_unknown() {
+        String::new()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Writes one CSV record, quoting fields as RFC 4180 requires
+fn write_csv_record<'a>(
+    writer: &mut impl Write,
+    fields: impl Iterator<Item = &'a str>,
+) -> io::Result<()> {
+    for (index, field) in fields.enumerate() {
+        if index > 0 {
+            writer.write_all(b",")?;
+        }
+
+        if field.contains(['"', ',', '\r', '\n']) {
+            write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+        } else {
+            writer.write_all(field.as_bytes())?;
+        }
+    }
+
+    writer.write_all(b"\r\n")
+}
+
+/// Error returned when a Parquet export fails
+#[cfg(feature = "parquet")]
+#[derive(Debug)]
+pub enum ExportError {
+    /// The file could not be created
+    Io(io::Error),
+    /// The instances could not be converted to Arrow arrays
+    Arrow(arrow_schema::ArrowError),
+    /// The Parquet file could not be written
+    Parquet(parquet::errors::ParquetError),
+}
+
+#[cfg(feature = "parquet")]
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Io(e) => write!(f, "export failed: {e}"),
+            ExportError::Arrow(e) => write!(f, "conversion failed: {e}"),
+            ExportError::Parquet(e) => write!(f, "parquet write failed: {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl std::error::Error for ExportError {}
+
+#[cfg(feature = "parquet")]
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::Io(e)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<arrow_schema::ArrowError> for ExportError {
+    fn from(e: arrow_schema::ArrowError) -> Self {
+        ExportError::Arrow(e)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<parquet::errors::ParquetError> for ExportError {
+    fn from(e: parquet::errors::ParquetError) -> Self {
+        ExportError::Parquet(e)
+    }
+}
+
+/// Writes `instances` as a Parquet file at `path`, replacing it, available with the `parquet`
+/// feature
+#[cfg(feature = "parquet")]
+pub fn write_parquet<T: Serialize>(
+    instances: &[T],
+    path: impl AsRef<Path>,
+) -> Result<(), ExportError> {
+    let batch = to_record_batch(instances)?;
+
+    let mut writer = parquet::arrow::ArrowWriter::try_new(File::create(path)?, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+
+    Ok(())
+}
+
+/// Converts `instances` to an Arrow record batch, available with the `parquet` feature
+///
+/// Every column is nullable and typed after its known values: boolean, unsigned or signed 64 bit
+/// integer, 64 bit float, or text when they mix kinds or are nested.
+#[cfg(feature = "parquet")]
+pub fn to_record_batch<T: Serialize>(
+    instances: &[T],
+) -> Result<arrow_array::RecordBatch, arrow_schema::ArrowError> {
+    use arrow_array::builder::{
+        BooleanBuilder, Float64Builder, Int64Builder, StringBuilder, UInt64Builder,
+    };
+    use arrow_array::{ArrayRef, RecordBatch, RecordBatchOptions};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    let table = Table::from_instances(instances);
+
+    let mut fields = Vec::with_capacity(table.columns.len());
+    let mut arrays: Vec<ArrayRef> = Vec::with_capacity(table.columns.len());
+
+    for (index, name) in table.columns.iter().enumerate() {
+        let values = table.rows.iter().map(|row| &row[index]);
+        let data_type = column_type(values.clone());
+
+        let array: ArrayRef = match data_type {
+            DataType::Boolean => {
+                let mut builder = BooleanBuilder::new();
+                for value in values {
+                    builder.append_option(match value {
+                        ReportValue::Bool(value) => Some(*value),
+                        _ => None,
+                    });
+                }
+                Arc::new(builder.finish())
+            }
+            DataType::UInt64 => {
+                let mut builder = UInt64Builder::new();
+                for value in values {
+                    builder.append_option(value.as_u64());
+                }
+                Arc::new(builder.finish())
+            }
+            DataType::Int64 => {
+                let mut builder = Int64Builder::new();
+                for value in values {
+                    builder.append_option(match value {
+                        ReportValue::Signed(value) => Some(*value),
+                        ReportValue::Unsigned(value) => i64::try_from(*value).ok(),
+                        _ => None,
+                    });
+                }
+                Arc::new(builder.finish())
+            }
+            DataType::Float64 => {
+                let mut builder = Float64Builder::new();
+                for value in values {
+                    builder.append_option(match value {
+                        ReportValue::Float(value) => Some(*value),
+                        ReportValue::Signed(value) => Some(*value as f64),
+                        ReportValue::Unsigned(value) => Some(*value as f64),
+                        _ => None,
+                    });
+                }
+                Arc::new(builder.finish())
+            }
+            _ => {
+                let mut builder = StringBuilder::new();
+                for value in values {
+                    builder.append_option((!value.is_unknown()).then(|| value.to_string()));
+                }
+                Arc::new(builder.finish())
+            }
+        };
+
+        fields.push(Field::new(name, data_type, true));
+        arrays.push(array);
+    }
+
+    RecordBatch::try_new_with_options(
+        Arc::new(Schema::new(fields)),
+        arrays,
+        &RecordBatchOptions::new().with_row_count(Some(table.rows.len())),
+    )
+}
+
+/// Narrowest Arrow type holding every known value of a column
+#[cfg(feature = "parquet")]
+fn column_type<'a>(values: impl Iterator<Item = &'a ReportValue>) -> arrow_schema::DataType {
+    use arrow_schema::DataType;
+
+    let mut data_type = None;
+
+    for value in values.filter(|value| !value.is_unknown()) {
+        let value_type = match value {
+            ReportValue::Bool(_) => DataType::Boolean,
+            ReportValue::Unsigned(_) => DataType::UInt64,
+            ReportValue::Signed(_) => DataType::Int64,
+            ReportValue::Float(_) => DataType::Float64,
+            _ => return DataType::Utf8,
+        };
+
+        data_type = Some(match (data_type, value_type) {
+            (None, value_type) => value_type,
+            (Some(current), value_type) if current == value_type => current,
+            (Some(DataType::UInt64 | DataType::Int64), DataType::UInt64 | DataType::Int64) => {
+                DataType::Int64
+            }
+            (
+                Some(DataType::UInt64 | DataType::Int64 | DataType::Float64),
+                DataType::UInt64 | DataType::Int64 | DataType::Float64,
+            ) => DataType::Float64,
+            _ => return DataType::Utf8,
+        });
+    }
+
+    data_type.unwrap_or(DataType::Utf8)
+}
@@ -0,0 +1,192 @@
+//! Properties of WMI instances the typed structs do not model, kept so snapshots are lossless.
+//!
+//! Vendors extend classes such as `Win32_DiskDrive` with properties of their own, and newer
+//! Windows releases add properties the structs predate. Deserializing into the structs drops them
+//! silently. [`extra_properties`] queries a class again untyped and keeps, per instance, every
+//! property the struct has no field for, identified by the key properties of the instance so the
+//! extras can be matched back to the typed instance, e.g. for forensic archiving.
+//!
+//! Values are kept as [`JsonText`], so snapshots holding them still encode with the binary
+//! formats of [`snapshot`](crate::snapshot), which cannot carry untyped `serde_json::Value`s.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.update();
+//! k.capture_extra_properties();
+//!
+//! for (class, instances) in &k.extra_properties {
+//!     for instance in instances {
+//!         println!("{class} {}: {:?}", instance.key, instance.properties);
+//!     }
+//! }
+//! ```
+
+use crate::association::Referenced;
+use crate::{async_raw_query_in, class_name, raw_query_in, Unavailable};
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+
+/// Properties of one instance the struct of its class has no field for
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ExtraProperties {
+    /// Values of the key properties of the instance, in [`Referenced::KEYS`] order, joined by `,`
+    pub key: String,
+    /// Properties left out of the struct and their values, `null` for unset ones
+    pub properties: BTreeMap<String, JsonText>,
+}
+
+/// A JSON value kept as its text
+///
+/// Human-readable formats such as JSON write the value itself, binary formats its text, which
+/// they can decode without knowing the type of the value beforehand.
+#[derive(Default, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct JsonText(pub String);
+
+impl JsonText {
+    /// The value, `null` if the text is not valid JSON
+    pub fn value(&self) -> serde_json::Value {
+        serde_json::from_str(&self.0).unwrap_or_default()
+    }
+}
+
+impl From<&serde_json::Value> for JsonText {
+    fn from(value: &serde_json::Value) -> Self {
+        JsonText(value.to_string())
+    }
+}
+
+impl std::fmt::Display for JsonText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Serialize for JsonText {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => self.value().serialize(serializer),
+            false => serializer.serialize_str(&self.0),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonText {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => serde_json::Value::deserialize(deserializer).map(|value| JsonText::from(&value)),
+            false => String::deserialize(deserializer).map(JsonText),
+        }
+    }
+}
+
+/// Queries every instance of `T`'s class in `namespace` and keeps the properties `T` does not
+/// model, instances without any are left out
+pub fn extra_properties<T: DeserializeOwned + Referenced>(
+    namespace: &str,
+) -> Result<Vec<ExtraProperties>, Unavailable> {
+    let instances = raw_query_in(namespace, &all_of::<T>())?;
+
+    Ok(unmodeled::<T>(instances))
+}
+
+/// Asynchronous [`extra_properties`]
+pub async fn async_extra_properties<T: DeserializeOwned + Referenced>(
+    namespace: &str,
+) -> Result<Vec<ExtraProperties>, Unavailable> {
+    let instances = async_raw_query_in(namespace, &all_of::<T>()).await?;
+
+    Ok(unmodeled::<T>(instances))
+}
+
+/// `SELECT *` over the class of `T`
+fn all_of<T>() -> String {
+    format!("SELECT * FROM {}", class_name::<T>(&[]))
+}
+
+/// Drops the properties `T` models from untyped `instances`
+fn unmodeled<T: DeserializeOwned + Referenced>(
+    instances: Vec<HashMap<String, wmi::Variant>>,
+) -> Vec<ExtraProperties> {
+    let fields = struct_fields::<T>();
+
+    instances
+        .into_iter()
+        .filter_map(|mut instance| {
+            let key = T::KEYS
+                .iter()
+                .map(|name| match take(&mut instance, name) {
+                    Some(serde_json::Value::String(value)) => value,
+                    Some(serde_json::Value::Null) | None => String::new(),
+                    Some(value) => value.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+
+            let properties: BTreeMap<String, JsonText> = instance
+                .into_iter()
+                .filter(|(name, _)| !name.starts_with("__"))
+                .filter(|(name, _)| !fields.iter().any(|field| field.eq_ignore_ascii_case(name)))
+                .map(|(name, value)| (name, JsonText::from(&to_json(&value))))
+                .collect();
+
+            (!properties.is_empty()).then_some(ExtraProperties { key, properties })
+        })
+        .collect()
+}
+
+/// Removes the property `name`, compared case insensitively, and converts its value
+fn take(instance: &mut HashMap<String, wmi::Variant>, name: &str) -> Option<serde_json::Value> {
+    let property = instance.keys().find(|key| key.eq_ignore_ascii_case(name))?.clone();
+
+    instance.remove(&property).map(|value| to_json(&value))
+}
+
+/// JSON form of a WMI value, `null` for embedded objects which do not serialize
+pub(crate) fn to_json(value: &wmi::Variant) -> serde_json::Value {
+    match value {
+        wmi::Variant::Unknown(_) | wmi::Variant::Object(_) => serde_json::Value::Null,
+        value => serde_json::to_value(value).unwrap_or_default(),
+    }
+}
+
+/// Names of the fields `T` deserializes, as WMI names them, empty if `T` is not a plain struct
+fn struct_fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(StructFields(&mut fields));
+
+    fields
+}
+
+/// Deserializer recording the fields a struct asks for, then failing before reading any value
+struct StructFields<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for StructFields<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+
+        Err(de::Error::custom("fields recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
@@ -0,0 +1,216 @@
+//! Bluetooth devices paired with or remembered by the machine, available with the `bluetooth`
+//! feature.
+//!
+//! WMI has no dedicated Bluetooth class, so devices are enumerated with the Bluetooth APIs
+//! (`BluetoothFindFirstDevice`), which report pairing and connection state, and matched against
+//! the [**Win32\_PnPEntity**](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-pnpentity)
+//! instances of the `Bluetooth` class through the device address embedded in their
+//! `PNPDeviceID`. The battery level is read from the matching device nodes when the driver
+//! exposes it.
+
+use crate::hardware::motherboard_controller_port::{
+    device_node_property, locate_device_node, Win32_PnPEntity,
+};
+use crate::{state_member, Unavailable};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use windows::core::GUID;
+use windows::Win32::Devices::Bluetooth::{
+    BluetoothFindDeviceClose, BluetoothFindFirstDevice, BluetoothFindNextDevice,
+    BLUETOOTH_DEVICE_INFO, BLUETOOTH_DEVICE_SEARCH_PARAMS,
+};
+use windows::Win32::Devices::Properties::{DEVPROPKEY, DEVPROP_TYPE_BYTE};
+use windows::Win32::Foundation::{SYSTEMTIME, TRUE};
+
+/// Query used to select the Plug and Play side of Bluetooth devices
+const BLUETOOTH_PNP_QUERY: &str = "SELECT * FROM Win32_PnPEntity WHERE PNPClass = 'Bluetooth'";
+
+/// `DEVPKEY_Bluetooth_Battery`, the battery level in percent reported by hands-free and LE devices
+const DEVPKEY_BLUETOOTH_BATTERY: DEVPROPKEY = DEVPROPKEY {
+    fmtid: GUID::from_u128(0x104ea319_6ee2_4701_bd47_8ddbf425bbe5),
+    pid: 2,
+};
+
+/// Represents the state of Windows user's BluetoothDevices
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct BluetoothDevices {
+    /// Sequence of windows BluetoothDevices states
+    pub bluetooth_devices: Vec<BluetoothDevice>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(BluetoothDevices, bluetooth_devices);
+
+impl BluetoothDevices {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let pnp_entities = crate::raw_query(BLUETOOTH_PNP_QUERY);
+
+        self.refresh(pnp_entities);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let pnp_entities = crate::async_raw_query(BLUETOOTH_PNP_QUERY).await;
+
+        self.refresh(pnp_entities);
+    }
+
+    fn refresh(&mut self, pnp_entities: Result<Vec<Win32_PnPEntity>, Unavailable>) {
+        let old_vec = std::mem::take(&mut self.bluetooth_devices);
+        let old_unavailable = self.unavailable.take();
+
+        match pnp_entities {
+            Ok(pnp_entities) => self.bluetooth_devices = collect_devices(&pnp_entities),
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.bluetooth_devices.len() != old_vec.len()
+            || crate::hash_vec(&self.bluetooth_devices) != crate::hash_vec(&old_vec);
+    }
+}
+
+impl Default for BluetoothDevices {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        BluetoothDevices {
+            bluetooth_devices: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// A Bluetooth device known to the local radios
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct BluetoothDevice {
+    /// Name of the device, as reported by the device.
+    pub name: String,
+    /// Address of the device in the `XX:XX:XX:XX:XX:XX` form.
+    pub address: String,
+    /// Class of device, a bit field describing the device's major and minor class and services.
+    pub class_of_device: u32,
+    /// Device is currently connected.
+    pub connected: bool,
+    /// Device is paired (authenticated) with the machine.
+    pub paired: bool,
+    /// Device is remembered by the machine.
+    pub remembered: bool,
+    /// Last time the device was seen, in UTC, formatted as `YYYY-MM-DDTHH:MM:SS`.
+    pub last_seen: Option<String>,
+    /// Last time the device was used, in UTC, formatted as `YYYY-MM-DDTHH:MM:SS`.
+    pub last_used: Option<String>,
+    /// Plug and Play device identifiers belonging to the device.
+    pub pnp_device_ids: Vec<String>,
+    /// Battery level in percent, if exposed by one of the device's drivers.
+    pub battery_level: Option<u8>,
+}
+
+/// Enumerates the devices known to the Bluetooth radios and joins them with their PnP entities
+fn collect_devices(pnp_entities: &[Win32_PnPEntity]) -> Vec<BluetoothDevice> {
+    let mut devices = find_devices();
+
+    for device in devices.iter_mut() {
+        let needle = device.address.replace(':', "");
+
+        device.pnp_device_ids = pnp_entities
+            .iter()
+            .filter_map(|entity| entity.PNPDeviceID.as_ref())
+            .filter(|id| id.to_uppercase().contains(&needle))
+            .cloned()
+            .collect();
+
+        device.battery_level = device.pnp_device_ids.iter().find_map(|id| battery_level(id));
+    }
+
+    devices
+}
+
+/// Lists every authenticated, remembered or connected device without issuing a new inquiry
+fn find_devices() -> Vec<BluetoothDevice> {
+    let search_params = BLUETOOTH_DEVICE_SEARCH_PARAMS {
+        dwSize: std::mem::size_of::<BLUETOOTH_DEVICE_SEARCH_PARAMS>() as u32,
+        fReturnAuthenticated: TRUE,
+        fReturnRemembered: TRUE,
+        fReturnConnected: TRUE,
+        ..Default::default()
+    };
+    let mut device_info = BLUETOOTH_DEVICE_INFO {
+        dwSize: std::mem::size_of::<BLUETOOTH_DEVICE_INFO>() as u32,
+        ..Default::default()
+    };
+
+    let mut devices = Vec::new();
+
+    // No radio or no device simply yields an empty snapshot
+    let Ok(find) = (unsafe { BluetoothFindFirstDevice(&search_params, &mut device_info) }) else {
+        return devices;
+    };
+
+    loop {
+        devices.push(to_device(&device_info));
+
+        if !unsafe { BluetoothFindNextDevice(find, &mut device_info) }.as_bool() {
+            break;
+        }
+    }
+
+    unsafe { BluetoothFindDeviceClose(find) };
+
+    devices
+}
+
+/// Converts the raw device information, the PnP part is filled in by [`collect_devices`]
+fn to_device(info: &BLUETOOTH_DEVICE_INFO) -> BluetoothDevice {
+    let address = unsafe { info.Address.Anonymous.rgBytes };
+    let name_len = info.szName.iter().position(|&c| c == 0).unwrap_or(info.szName.len());
+
+    BluetoothDevice {
+        name: String::from_utf16_lossy(&info.szName[..name_len]),
+        address: address
+            .iter()
+            .rev()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<Vec<_>>()
+            .join(":"),
+        class_of_device: info.ulClassofDevice,
+        connected: info.fConnected.as_bool(),
+        paired: info.fAuthenticated.as_bool(),
+        remembered: info.fRemembered.as_bool(),
+        last_seen: format_system_time(&info.stLastSeen),
+        last_used: format_system_time(&info.stLastUsed),
+        pnp_device_ids: Vec::new(),
+        battery_level: None,
+    }
+}
+
+/// Formats a `SYSTEMTIME`, an all zero time means the event never happened
+fn format_system_time(time: &SYSTEMTIME) -> Option<String> {
+    (time.wYear != 0).then(|| {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            time.wYear, time.wMonth, time.wDay, time.wHour, time.wMinute, time.wSecond
+        )
+    })
+}
+
+/// Reads `DEVPKEY_Bluetooth_Battery` from the device node identified by `pnp_device_id`
+fn battery_level(pnp_device_id: &str) -> Option<u8> {
+    let dev_inst = locate_device_node(pnp_device_id)?;
+
+    device_node_property::<1>(dev_inst, &DEVPKEY_BLUETOOTH_BATTERY, DEVPROP_TYPE_BYTE).map(|[level]| level)
+}
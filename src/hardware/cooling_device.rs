@@ -7,9 +7,10 @@
 //! | [**Win32\_Refrigeration**](win32-refrigeration)          | Represents the properties of a refrigeration device.                        |
 //! | [**Win32\_TemperatureProbe**](win32-temperatureprobe)    | Represents the properties of a temperature sensor (electronic thermometer). |
 
-use crate::update;
+use crate::{coded, update};
 use serde::{Deserialize, Serialize};
-use std::time::SystemTime;
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
 
 /// Represents the state of Windows user's fans
@@ -24,9 +25,102 @@ pub struct Fans {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+    /// Speeds of the fans over the last sampling window, empty until [`Fans::sample_speeds`] runs
+    #[serde(default)]
+    pub speed_stats: Vec<FanSpeedStats>,
 }
 
-update!(Fans, fans);
+update!(Fans, fans; speed_stats);
+
+/// Speed of a fan over a sampling window, in revolutions per minute
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct FanSpeedStats {
+    /// `DeviceID` of the fan
+    pub device_id: String,
+    /// Number of readings of the speed
+    pub samples: usize,
+    /// Lowest speed read
+    pub min: u64,
+    /// Highest speed read
+    pub max: u64,
+    /// Average of the speeds read, rounded down
+    pub average: u64,
+}
+
+impl Fans {
+    /// Synchronously updates the fans `samples` times spread over `window`, and records the
+    /// lowest, highest and average `DesiredSpeed` of every fan in `speed_stats`
+    ///
+    /// Single readings are noisy on fans with `VariableSpeed`. `fans` holds the last reading.
+    /// Fans that never report a speed are left out.
+    pub fn sample_speeds(&mut self, samples: u32, window: Duration) {
+        let mut readings = BTreeMap::new();
+
+        for sample in 0..samples.max(1) {
+            if sample > 0 {
+                std::thread::sleep(sample_interval(samples, window));
+            }
+
+            self.update();
+            self.record_speeds(&mut readings);
+        }
+
+        self.speed_stats = speed_stats(readings);
+    }
+
+    /// Asynchronous [`Fans::sample_speeds`]
+    pub async fn async_sample_speeds(&mut self, samples: u32, window: Duration) {
+        let mut readings = BTreeMap::new();
+
+        for sample in 0..samples.max(1) {
+            if sample > 0 {
+                tokio::time::sleep(sample_interval(samples, window)).await;
+            }
+
+            self.async_update().await;
+            self.record_speeds(&mut readings);
+        }
+
+        self.speed_stats = speed_stats(readings);
+    }
+
+    /// Speed statistics of the fan `device_id`, `None` if it was not sampled
+    pub fn speed_stats_of(&self, device_id: &str) -> Option<&FanSpeedStats> {
+        self.speed_stats
+            .iter()
+            .find(|stats| stats.device_id == device_id)
+    }
+
+    fn record_speeds(&self, readings: &mut BTreeMap<String, Vec<u64>>) {
+        for fan in &self.fans {
+            if let (Some(device_id), Some(speed)) = (&fan.DeviceID, fan.DesiredSpeed) {
+                readings.entry(device_id.clone()).or_default().push(speed);
+            }
+        }
+    }
+}
+
+/// Time between two of `samples` readings spread over `window`
+fn sample_interval(samples: u32, window: Duration) -> Duration {
+    window / samples.saturating_sub(1).max(1)
+}
+
+fn speed_stats(readings: BTreeMap<String, Vec<u64>>) -> Vec<FanSpeedStats> {
+    readings
+        .into_iter()
+        .filter_map(|(device_id, speeds)| {
+            Some(FanSpeedStats {
+                samples: speeds.len(),
+                min: *speeds.iter().min()?,
+                max: *speeds.iter().max()?,
+                average: speeds.iter().sum::<u64>() / speeds.len() as u64,
+                device_id,
+            })
+        })
+        .collect()
+}
 
 /// Represents the state of Windows user's HeatPipes
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -40,6 +134,8 @@ pub struct HeatPipes {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(HeatPipes, heat_pipes);
@@ -56,6 +152,8 @@ pub struct Refrigerations {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Refrigerations, refrigerations);
@@ -72,6 +170,8 @@ pub struct TemperatureProbes {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(TemperatureProbes, temperature_probes);
@@ -232,6 +332,12 @@ pub struct Win32_Fan {
     pub VariableSpeed: Option<bool>,
 }
 
+coded!(Win32_Fan {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
 /// The `Win32_HeatPipe` WMI class represents the properties of a heat pipe cooling device.
 /// 
 /// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-heatpipe>
@@ -381,6 +487,12 @@ pub struct Win32_HeatPipe {
     pub SystemName: Option<String>,
 }
 
+coded!(Win32_HeatPipe {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
 /// The `Win32_Refrigeration` WMI class represents the properties of a refrigeration device.
 /// 
 /// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-refrigeration>
@@ -529,6 +641,12 @@ pub struct Win32_Refrigeration {
     pub SystemName: Option<String>,
 }
 
+coded!(Win32_Refrigeration {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
 /// The `Win32_TemperatureProbe` WMI class represents the properties of a temperature sensor 
 /// (electronic thermometer).
 /// 
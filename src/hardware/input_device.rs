@@ -4,8 +4,12 @@
 //! |-------------------------------------------------------|---------------------------------------------------------------------------------------------------------------------|
 //! | [**Win32\_Keyboard**](win32-keyboard)                | Represents a keyboard installed on a computer system running Windows.                                               |
 //! | [**Win32\_PointingDevice**](win32-pointingdevice)    | Represents an input device used to point to and select regions on the display of a computer system running Windows. |
+//!
+//! [`InputDeviceEvents`] compares the keyboards and pointing devices between updates to report the
+//! ones plugged in or removed, e.g. to spot a keystroke injection device showing up as a new keyboard.
 
-use crate::update;
+use crate::codes::PointingInterface;
+use crate::{coded, state_member, update, Unavailable, DEFAULT_NAMESPACE};
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
@@ -22,6 +26,8 @@ pub struct Keyboards {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Keyboards, keyboards);
@@ -38,10 +44,211 @@ pub struct PointingDevices {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(PointingDevices, pointing_devices);
 
+impl PointingDevices {
+    /// Pointing devices connected through `interface`, e.g. [`PointingInterface::Usb`]
+    pub fn by_interface(&self, interface: PointingInterface) -> Vec<&Win32_PointingDevice> {
+        self.pointing_devices
+            .iter()
+            .filter(|device| device.device_interface() == Some(interface))
+            .collect()
+    }
+}
+
+/// Represents the keyboards and pointing devices added or removed between updates
+///
+/// Devices are matched by their `PNPDeviceID`. A device plugged in and removed again between two
+/// updates is not reported.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct InputDeviceEvents {
+    /// Input devices present on the last update
+    pub known_devices: Vec<InputDevice>,
+    /// Whether `known_devices` holds a previous update, no event is emitted before it does
+    pub primed: bool,
+    /// Sequence of devices added or removed during the last update
+    pub input_device_events: Vec<InputDeviceEvent>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(InputDeviceEvents, input_device_events; known_devices, primed);
+
+impl InputDeviceEvents {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let devices = crate::query(DEFAULT_NAMESPACE, "Win32_Keyboard").and_then(|keyboards| {
+            Ok((keyboards, crate::query(DEFAULT_NAMESPACE, "Win32_PointingDevice")?))
+        });
+
+        self.refresh(devices);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let devices = match crate::async_query(DEFAULT_NAMESPACE, "Win32_Keyboard").await {
+            Ok(keyboards) => crate::async_query(DEFAULT_NAMESPACE, "Win32_PointingDevice")
+                .await
+                .map(|pointing_devices| (keyboards, pointing_devices)),
+            Err(unavailable) => Err(unavailable),
+        };
+
+        self.refresh(devices);
+    }
+
+    /// `known_devices` is kept when the classes cannot be queried, so no device is reported as
+    /// removed
+    fn refresh(
+        &mut self,
+        devices: Result<(Vec<Win32_Keyboard>, Vec<Win32_PointingDevice>), Unavailable>,
+    ) {
+        let old_unavailable = self.unavailable.take();
+
+        let (keyboards, pointing_devices) = match devices {
+            Ok(devices) => devices,
+            Err(unavailable) => {
+                self.input_device_events.clear();
+                self.state_change = old_unavailable.as_ref() != Some(&unavailable);
+                self.unavailable = Some(unavailable);
+                return;
+            }
+        };
+
+        let devices: Vec<InputDevice> = keyboards
+            .iter()
+            .map(InputDevice::from_keyboard)
+            .chain(pointing_devices.iter().map(InputDevice::from_pointing_device))
+            .collect();
+
+        let old_devices = std::mem::replace(&mut self.known_devices, devices);
+
+        self.input_device_events.clear();
+
+        if self.primed {
+            let added = self
+                .known_devices
+                .iter()
+                .filter(|device| !old_devices.iter().any(|old| old.same_device(device)))
+                .map(|device| (InputDeviceChange::Added, device));
+            let removed = old_devices
+                .iter()
+                .filter(|device| !self.known_devices.iter().any(|new| new.same_device(device)))
+                .map(|device| (InputDeviceChange::Removed, device));
+
+            self.input_device_events = added
+                .chain(removed)
+                .map(|(change, device)| InputDeviceEvent {
+                    change,
+                    device: device.clone(),
+                    time_detected: self.last_updated,
+                })
+                .collect();
+        }
+
+        self.primed = true;
+        self.state_change = old_unavailable.is_some() || !self.input_device_events.is_empty();
+    }
+}
+
+impl Default for InputDeviceEvents {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        InputDeviceEvents {
+            known_devices: Default::default(),
+            primed: false,
+            input_device_events: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// Kind of input device
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum InputDeviceType {
+    /// `Win32_Keyboard`
+    Keyboard,
+    /// `Win32_PointingDevice`
+    PointingDevice,
+}
+
+/// A keyboard or pointing device, two devices are the same if their type and `PNPDeviceID` match
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct InputDevice {
+    /// Kind of the device
+    pub device_type: InputDeviceType,
+    /// Windows Plug and Play device identifier, falls back to `DeviceID` when missing
+    pub pnp_device_id: Option<String>,
+    /// Label by which the device is known
+    pub name: Option<String>,
+    /// Description of the device
+    pub description: Option<String>,
+}
+
+impl InputDevice {
+    /// Returns `true` if both describe the same physical device
+    pub fn same_device(&self, other: &InputDevice) -> bool {
+        self.device_type == other.device_type && self.pnp_device_id == other.pnp_device_id
+    }
+
+    fn from_keyboard(keyboard: &Win32_Keyboard) -> Self {
+        InputDevice {
+            device_type: InputDeviceType::Keyboard,
+            pnp_device_id: keyboard.PNPDeviceID.clone().or_else(|| keyboard.DeviceID.clone()),
+            name: keyboard.Name.clone(),
+            description: keyboard.Description.clone(),
+        }
+    }
+
+    fn from_pointing_device(pointing_device: &Win32_PointingDevice) -> Self {
+        InputDevice {
+            device_type: InputDeviceType::PointingDevice,
+            pnp_device_id: pointing_device
+                .PNPDeviceID
+                .clone()
+                .or_else(|| pointing_device.DeviceID.clone()),
+            name: pointing_device.Name.clone(),
+            description: pointing_device.Description.clone(),
+        }
+    }
+}
+
+/// Whether a device was plugged in or removed
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum InputDeviceChange {
+    /// The device was not present on the previous update
+    Added,
+    /// The device is no longer present
+    Removed,
+}
+
+/// An input device plugged in or removed
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct InputDeviceEvent {
+    /// Whether the device was added or removed
+    pub change: InputDeviceChange,
+    /// The device concerned
+    pub device: InputDevice,
+    /// Update during which the change was detected
+    pub time_detected: SystemTime,
+}
+
 /// The `Win32_Keyboard` WMI class represents a keyboard installed on a computer system running Windows.
 /// 
 /// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-keyboard>
@@ -204,6 +411,26 @@ pub struct Win32_Keyboard {
     pub SystemName: Option<String>,
 }
 
+coded!(Win32_Keyboard {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
+impl Win32_Keyboard {
+    /// Keyboard layout identifier held by `Layout`, e.g. `0x00000409` for the US layout, `None`
+    /// when `Layout` is not one
+    pub fn layout_id(&self) -> Option<u32> {
+        u32::from_str_radix(self.Layout.as_deref()?.trim(), 16).ok()
+    }
+
+    /// Language identifier of the layout, the low word of [`Win32_Keyboard::layout_id`], e.g.
+    /// `0x0409` for English (United States)
+    pub fn language_id(&self) -> Option<u16> {
+        self.layout_id().map(|layout| (layout & 0xFFFF) as u16)
+    }
+}
+
 /// The `Win32_PointingDevice` WMI class represents an input device used to point to and select 
 /// regions on the display of a computer system running Windows. Any device used to manipulate 
 /// a pointer, or point to the display on a computer system running Windows is a member of 
@@ -426,4 +653,12 @@ pub struct Win32_PointingDevice {
     pub SystemCreationClassName: Option<String>,
     /// Name of the scoping system.
     pub SystemName: Option<String>,
-}
\ No newline at end of file
+}
+coded!(Win32_PointingDevice {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+    DeviceInterface => device_interface: PointingInterface,
+    PointingType => pointing_type: PointingType,
+    Handedness => handedness: Handedness,
+});
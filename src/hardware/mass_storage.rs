@@ -8,10 +8,27 @@
 //! | [**Win32\_FloppyDrive**](win32-floppydrive)              | Manages the capabilities of a floppy disk drive.                                             |
 //! | [**Win32\_PhysicalMedia**](/previous-versions/windows/desktop/cimwin32a/win32-physicalmedia) | Represents any type of documentation or storage medium.                                      |
 //! | [**Win32\_TapeDrive**](win32-tapedrive)                  | Represents a tape drive on a computer system running Windows.                                |
+//! | [**MSFT\_PhysicalDisk**](https://learn.microsoft.com/en-us/windows-hardware/drivers/storage/msft-physicaldisk) | Represents a physical disk, lives in the `root\Microsoft\Windows\Storage` namespace. |
+//! | [**MSFT\_Disk**](https://learn.microsoft.com/en-us/windows-hardware/drivers/storage/msft-disk) | Represents a disk as seen by the operating system, lives in the `root\Microsoft\Windows\Storage` namespace. |
+//! | [**MSFT\_StorageReliabilityCounter**](https://learn.microsoft.com/en-us/windows-hardware/drivers/storage/msft-storagereliabilitycounter) | Reliability (SMART) counters of a physical disk, lives in the `root\Microsoft\Windows\Storage` namespace. |
+//! | **MSStorageDriver\_FailurePredictStatus**                 | Failure prediction (SMART) status reported by the storage driver, lives in the `root\wmi` namespace. |
 
-use crate::update;
+use crate::codes::DriveType;
+use crate::hardware::motherboard_controller_port::{
+    device_node_id, device_node_property, locate_device_node, parent_device_node, Win32_PnPEntity,
+};
+use crate::operating_system::file_system::{
+    DiskDriveToDiskPartitions, DiskPartitions, LogicalDiskToPartitions, LogicalDisks, Volumes,
+    Win32_DiskDriveToDiskPartition, Win32_DiskPartition, Win32_LogicalDisk, Win32_LogicalDiskToPartition,
+    Win32_Volume,
+};
+use crate::operating_system::operating_system_settings::Win32_OperatingSystem;
+use crate::{association, coded, keyed, labeled, referenced, state_member, update, Unavailable, DEFAULT_NAMESPACE, STORAGE_NAMESPACE, WMI_NAMESPACE};
 use serde::{Deserialize, Serialize};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
+use windows::core::GUID;
+use windows::Win32::Devices::Properties::{DEVPROPKEY, DEVPROP_TYPE_UINT32};
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
 
 /// Represents the state of Windows user's AutochkSettings
@@ -26,6 +43,8 @@ pub struct AutochkSettings {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(AutochkSettings, autochk_settings);
@@ -42,6 +61,8 @@ pub struct CDROMDrives {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(CDROMDrives, cd_rom_drives);
@@ -58,9 +79,388 @@ pub struct DiskDrives {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(DiskDrives, disk_drives);
+keyed!(DiskDrives, disk_drives, Win32_DiskDrive, DeviceID: String);
+
+/// Physical disks with the partitions on each and the logical disks on each partition, joined
+/// through the `Win32_DiskDriveToDiskPartition` and `Win32_LogicalDiskToPartition` associations
+///
+/// Volumes are attached to logical disks by drive letter: volumes without one, such as the EFI and
+/// recovery partitions, are not part of the tree.
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct DiskTopology {
+    /// Physical disks, ordered by `Index`
+    pub disks: Vec<DiskNode>,
+}
+
+impl DiskTopology {
+    /// Queries the disks, partitions, logical disks, volumes and their associations synchronously
+    pub fn build() -> Result<Self, Unavailable> {
+        Ok(DiskTopology::from_instances(
+            &crate::query(DEFAULT_NAMESPACE, "Win32_DiskDrive")?,
+            &crate::query(DEFAULT_NAMESPACE, "Win32_DiskPartition")?,
+            &crate::query(DEFAULT_NAMESPACE, "Win32_LogicalDisk")?,
+            &crate::query(DEFAULT_NAMESPACE, "Win32_Volume")?,
+            &crate::query(DEFAULT_NAMESPACE, "Win32_DiskDriveToDiskPartition")?,
+            &crate::query(DEFAULT_NAMESPACE, "Win32_LogicalDiskToPartition")?,
+        ))
+    }
+
+    /// Queries the disks, partitions, logical disks, volumes and their associations asynchronously
+    pub async fn async_build() -> Result<Self, Unavailable> {
+        Ok(DiskTopology::from_instances(
+            &crate::async_query(DEFAULT_NAMESPACE, "Win32_DiskDrive").await?,
+            &crate::async_query(DEFAULT_NAMESPACE, "Win32_DiskPartition").await?,
+            &crate::async_query(DEFAULT_NAMESPACE, "Win32_LogicalDisk").await?,
+            &crate::async_query(DEFAULT_NAMESPACE, "Win32_Volume").await?,
+            &crate::async_query(DEFAULT_NAMESPACE, "Win32_DiskDriveToDiskPartition").await?,
+            &crate::async_query(DEFAULT_NAMESPACE, "Win32_LogicalDiskToPartition").await?,
+        ))
+    }
+
+    /// Joins the instances held by already updated states
+    pub fn from_states(
+        disk_drives: &DiskDrives,
+        disk_partitions: &DiskPartitions,
+        logical_disks: &LogicalDisks,
+        volumes: &Volumes,
+        disk_drive_to_disk_partitions: &DiskDriveToDiskPartitions,
+        logical_disk_to_partitions: &LogicalDiskToPartitions,
+    ) -> Self {
+        DiskTopology::from_instances(
+            &disk_drives.disk_drives,
+            &disk_partitions.disk_partitions,
+            &logical_disks.logical_disks,
+            &volumes.volumes,
+            &disk_drive_to_disk_partitions.disk_drive_to_disk_partitions,
+            &logical_disk_to_partitions.logical_disk_to_partitions,
+        )
+    }
+
+    /// Joins the instances of each class
+    pub fn from_instances(
+        disk_drives: &[Win32_DiskDrive],
+        disk_partitions: &[Win32_DiskPartition],
+        logical_disks: &[Win32_LogicalDisk],
+        volumes: &[Win32_Volume],
+        disk_drive_to_disk_partitions: &[Win32_DiskDriveToDiskPartition],
+        logical_disk_to_partitions: &[Win32_LogicalDiskToPartition],
+    ) -> Self {
+        let drive_links = association::resolve(disk_drive_to_disk_partitions, disk_drives, disk_partitions);
+        let partition_links = association::resolve(logical_disk_to_partitions, disk_partitions, logical_disks);
+
+        let volume_of = |logical_disk: &Win32_LogicalDisk| {
+            let device_id = logical_disk.DeviceID.as_deref()?;
+
+            volumes
+                .iter()
+                .find(|volume| {
+                    volume
+                        .DriveLetter
+                        .as_deref()
+                        .is_some_and(|letter| letter.eq_ignore_ascii_case(device_id))
+                })
+                .cloned()
+        };
+
+        let mut disks: Vec<DiskNode> = disk_drives
+            .iter()
+            .map(|disk| {
+                let mut partitions: Vec<PartitionNode> = association::dependents_of(&drive_links, disk)
+                    .into_iter()
+                    .map(|partition| PartitionNode {
+                        partition: partition.clone(),
+                        logical_disks: association::dependents_of(&partition_links, partition)
+                            .into_iter()
+                            .map(|logical_disk| LogicalDiskNode {
+                                logical_disk: logical_disk.clone(),
+                                volume: volume_of(logical_disk),
+                            })
+                            .collect(),
+                    })
+                    .collect();
+                partitions.sort_by_key(|node| node.partition.Index);
+
+                DiskNode {
+                    disk: disk.clone(),
+                    partitions,
+                }
+            })
+            .collect();
+        disks.sort_by_key(|node| node.disk.Index);
+
+        DiskTopology { disks }
+    }
+
+    /// Physical disk holding the logical disk `device_id`, e.g. `C:`
+    pub fn disk_of(&self, device_id: &str) -> Option<&DiskNode> {
+        self.disks.iter().find(|disk| {
+            disk.partitions
+                .iter()
+                .any(|partition| partition.logical_disk(device_id).is_some())
+        })
+    }
+}
+
+/// A physical disk and its partitions
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct DiskNode {
+    /// The disk
+    pub disk: Win32_DiskDrive,
+    /// Partitions on the disk, ordered by `Index`
+    pub partitions: Vec<PartitionNode>,
+}
+
+/// A partition and the logical disks residing on it
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct PartitionNode {
+    /// The partition
+    pub partition: Win32_DiskPartition,
+    /// Logical disks on the partition, empty for partitions without a drive letter
+    pub logical_disks: Vec<LogicalDiskNode>,
+}
+
+impl PartitionNode {
+    /// Logical disk `device_id` of the partition, e.g. `C:`
+    pub fn logical_disk(&self, device_id: &str) -> Option<&LogicalDiskNode> {
+        self.logical_disks.iter().find(|node| {
+            node.logical_disk
+                .DeviceID
+                .as_deref()
+                .is_some_and(|id| id.eq_ignore_ascii_case(device_id))
+        })
+    }
+}
+
+/// A logical disk and the volume behind it
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct LogicalDiskNode {
+    /// The logical disk
+    pub logical_disk: Win32_LogicalDisk,
+    /// Volume with the drive letter of the logical disk, `None` if no volume has it
+    pub volume: Option<Win32_Volume>,
+}
+
+/// Removable disks, such as USB sticks and memory cards, and optical drives, joined from the
+/// logical disks and the CD-ROM drives
+///
+/// Kiosk and data loss prevention agents check it once, then follow the changes pushed by a
+/// [`RemovableMediaMonitor`] instead of polling.
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct RemovableDrives {
+    /// Every removable or optical drive with a drive letter, ordered by drive letter
+    pub drives: Vec<RemovableDrive>,
+}
+
+impl RemovableDrives {
+    /// Queries the logical disks and the CD-ROM drives
+    pub fn build() -> Result<Self, Unavailable> {
+        Ok(RemovableDrives::from_instances(
+            &crate::query(DEFAULT_NAMESPACE, "Win32_LogicalDisk")?,
+            &crate::query(DEFAULT_NAMESPACE, "Win32_CDROMDrive")?,
+        ))
+    }
+
+    /// Queries the logical disks and the CD-ROM drives asynchronously
+    pub async fn async_build() -> Result<Self, Unavailable> {
+        Ok(RemovableDrives::from_instances(
+            &crate::async_query(DEFAULT_NAMESPACE, "Win32_LogicalDisk").await?,
+            &crate::async_query(DEFAULT_NAMESPACE, "Win32_CDROMDrive").await?,
+        ))
+    }
+
+    /// Joins the instances held by already updated states
+    pub fn from_states(logical_disks: &LogicalDisks, cd_rom_drives: &CDROMDrives) -> Self {
+        RemovableDrives::from_instances(&logical_disks.logical_disks, &cd_rom_drives.cd_rom_drives)
+    }
+
+    /// Joins the instances of each class, CD-ROM drives are matched to logical disks by drive
+    /// letter
+    pub fn from_instances(logical_disks: &[Win32_LogicalDisk], cd_rom_drives: &[Win32_CDROMDrive]) -> Self {
+        let mut drives: Vec<RemovableDrive> = logical_disks
+            .iter()
+            .filter(|logical_disk| is_removable(logical_disk))
+            .map(|logical_disk| RemovableDrive {
+                logical_disk: logical_disk.clone(),
+                cd_rom_drive: cd_rom_drives
+                    .iter()
+                    .find(|cd_rom_drive| {
+                        cd_rom_drive
+                            .Drive
+                            .as_deref()
+                            .zip(logical_disk.DeviceID.as_deref())
+                            .is_some_and(|(drive, device_id)| drive.eq_ignore_ascii_case(device_id))
+                    })
+                    .cloned(),
+            })
+            .collect();
+        drives.sort_by(|a, b| a.logical_disk.DeviceID.cmp(&b.logical_disk.DeviceID));
+
+        RemovableDrives { drives }
+    }
+
+    /// Drives holding media, such as a plugged USB stick or an inserted disc
+    pub fn with_media(&self) -> Vec<&RemovableDrive> {
+        self.drives.iter().filter(|drive| drive.media_present()).collect()
+    }
+}
+
+/// A removable or optical drive
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct RemovableDrive {
+    /// The logical disk of the drive, e.g. `E:`
+    pub logical_disk: Win32_LogicalDisk,
+    /// The CD-ROM drive with the drive letter of the logical disk, `None` for removable disks
+    pub cd_rom_drive: Option<Win32_CDROMDrive>,
+}
+
+impl RemovableDrive {
+    /// Returns `true` if the drive holds media
+    ///
+    /// Logical disks of empty optical drives and card readers have no size.
+    pub fn media_present(&self) -> bool {
+        match &self.cd_rom_drive {
+            Some(cd_rom_drive) if cd_rom_drive.MediaLoaded.is_some() => cd_rom_drive.MediaLoaded == Some(true),
+            _ => has_media(&self.logical_disk),
+        }
+    }
+}
+
+/// Returns `true` for removable disks and optical drives
+fn is_removable(logical_disk: &Win32_LogicalDisk) -> bool {
+    matches!(
+        logical_disk.drive_type(),
+        Some(DriveType::RemovableDisk | DriveType::CompactDisc)
+    )
+}
+
+/// Returns `true` if the logical disk holds media
+fn has_media(logical_disk: &Win32_LogicalDisk) -> bool {
+    logical_disk.Size.is_some_and(|size| size > 0)
+}
+
+/// Pushes removable drives and media as they come and go into a channel, see
+/// [`RemovableMediaMonitor::subscribe`]
+///
+/// Subscribes to `__InstanceOperationEvent WITHIN n` on the removable and optical logical disks,
+/// which WMI answers by polling them every `within` and reporting only the drives that were added,
+/// modified or removed.
+///
+/// Example:
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use windows_snapshot::hardware::mass_storage::{RemovableMediaChangeKind, RemovableMediaMonitor};
+/// use windows_snapshot::COMLibrary;
+///
+/// let _com_con = COMLibrary::new().unwrap(); // initialise security context
+///
+/// let mut changes = RemovableMediaMonitor::new(Duration::from_secs(2)).subscribe().unwrap();
+///
+/// while let Some(change) = changes.blocking_recv() {
+///     if change.kind == RemovableMediaChangeKind::MediaInserted {
+///         println!("media in {:?}: {:?}", change.logical_disk.DeviceID, change.logical_disk.VolumeName);
+///     }
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct RemovableMediaMonitor {
+    /// Interval WMI polls the drives at, rounded down to whole seconds and at least one
+    pub within: Duration,
+}
+
+impl RemovableMediaMonitor {
+    /// Creates a monitor polling the drives every `within`
+    pub fn new(within: Duration) -> Self {
+        RemovableMediaMonitor { within }
+    }
+
+    /// Subscribes to removable drive and media changes and pushes them into the returned channel,
+    /// see [`crate::events::subscribe`]
+    pub fn subscribe(&self) -> Result<mpsc::UnboundedReceiver<RemovableMediaChange>, Unavailable> {
+        crate::events::subscribe_map(DEFAULT_NAMESPACE, &self.query(), RemovableMediaEvent::into_change)
+    }
+
+    fn query(&self) -> String {
+        format!(
+            "SELECT * FROM __InstanceOperationEvent WITHIN {} WHERE TargetInstance ISA 'Win32_LogicalDisk' \
+             AND (TargetInstance.DriveType = 2 OR TargetInstance.DriveType = 5)",
+            self.within.as_secs().max(1)
+        )
+    }
+}
+
+impl Default for RemovableMediaMonitor {
+    /// Polls the drives every 2 seconds
+    fn default() -> Self {
+        RemovableMediaMonitor::new(Duration::from_secs(2))
+    }
+}
+
+/// How a removable drive changed
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum RemovableMediaChangeKind {
+    /// A drive appeared, e.g. a USB stick was plugged in
+    DriveAdded,
+    /// A drive disappeared, e.g. a USB stick was unplugged
+    DriveRemoved,
+    /// Media was inserted in a drive, e.g. a disc in an optical drive
+    MediaInserted,
+    /// Media was removed from a drive
+    MediaRemoved,
+    /// Another property of the drive changed, e.g. its `FreeSpace` while files are written to it
+    Modified,
+}
+
+/// A change of a removable drive pushed by a [`RemovableMediaMonitor`]
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct RemovableMediaChange {
+    /// How the drive changed
+    pub kind: RemovableMediaChangeKind,
+    /// The logical disk of the drive after the change, or as it was before its removal
+    pub logical_disk: Win32_LogicalDisk,
+    /// The logical disk before a modification, `None` for the other changes
+    pub previous: Option<Win32_LogicalDisk>,
+    /// When the change was received
+    pub observed: SystemTime,
+}
+
+/// `__InstanceCreationEvent`, `__InstanceModificationEvent` or `__InstanceDeletionEvent` of a
+/// removable logical disk
+#[derive(Deserialize, Debug)]
+#[allow(non_snake_case)]
+struct RemovableMediaEvent {
+    __CLASS: String,
+    TargetInstance: Win32_LogicalDisk,
+    PreviousInstance: Option<Win32_LogicalDisk>,
+}
+
+impl RemovableMediaEvent {
+    fn into_change(self) -> RemovableMediaChange {
+        let kind = match (self.__CLASS.as_str(), &self.PreviousInstance) {
+            ("__InstanceCreationEvent", _) => RemovableMediaChangeKind::DriveAdded,
+            ("__InstanceDeletionEvent", _) => RemovableMediaChangeKind::DriveRemoved,
+            (_, Some(previous)) if !has_media(previous) && has_media(&self.TargetInstance) => {
+                RemovableMediaChangeKind::MediaInserted
+            }
+            (_, Some(previous)) if has_media(previous) && !has_media(&self.TargetInstance) => {
+                RemovableMediaChangeKind::MediaRemoved
+            }
+            _ => RemovableMediaChangeKind::Modified,
+        };
+
+        RemovableMediaChange {
+            kind,
+            logical_disk: self.TargetInstance,
+            previous: self.PreviousInstance,
+            observed: SystemTime::now(),
+        }
+    }
+}
 
 /// Represents the state of Windows user's PhysicalMedias
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -74,6 +474,8 @@ pub struct PhysicalMedias {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(PhysicalMedias, physical_medias);
@@ -90,10 +492,346 @@ pub struct TapeDrives {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(TapeDrives, tape_drives);
 
+/// Represents the state of Windows user's PhysicalDisks
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct PhysicalDisks {
+    /// Sequence of windows PhysicalDisks states
+    pub physical_disks: Vec<MSFT_PhysicalDisk>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(PhysicalDisks, physical_disks, STORAGE_NAMESPACE);
+
+/// Represents the state of Windows user's Disks
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct Disks {
+    /// Sequence of windows Disks states
+    pub disks: Vec<MSFT_Disk>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(Disks, disks, STORAGE_NAMESPACE);
+
+/// Represents the state of Windows user's StorageReliabilityCounters
+///
+/// Reading the counters requires administrative rights.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct StorageReliabilityCounters {
+    /// Sequence of windows StorageReliabilityCounters states
+    pub storage_reliability_counters: Vec<MSFT_StorageReliabilityCounter>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(StorageReliabilityCounters, storage_reliability_counters, STORAGE_NAMESPACE);
+keyed!(StorageReliabilityCounters, storage_reliability_counters, MSFT_StorageReliabilityCounter, DeviceId: String);
+
+/// Represents the state of Windows user's FailurePredictStatuses
+///
+/// Only disks whose driver supports SMART failure prediction are listed.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct FailurePredictStatuses {
+    /// Sequence of windows FailurePredictStatuses states
+    pub failure_predict_statuses: Vec<MSStorageDriver_FailurePredictStatus>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(FailurePredictStatuses, failure_predict_statuses, WMI_NAMESPACE);
+
+impl FailurePredictStatuses {
+    /// Disks whose driver predicts an imminent failure
+    pub fn predicting_failure(&self) -> Vec<&MSStorageDriver_FailurePredictStatus> {
+        self.failure_predict_statuses
+            .iter()
+            .filter(|status| status.PredictFailure == Some(true))
+            .collect()
+    }
+}
+
+/// `BusType` of NVMe disks in `MSFT_PhysicalDisk`
+const BUS_TYPE_NVME: u16 = 17;
+
+/// `MediaType` of solid state disks in `MSFT_PhysicalDisk`
+const MEDIA_TYPE_SSD: u16 = 4;
+
+/// First build (Windows 10 1909) DirectStorage runs on
+const DIRECTSTORAGE_MIN_BUILD: u32 = 18363;
+
+/// First build (Windows 11) with the storage stack optimisations (BypassIO) DirectStorage benefits
+/// from
+const OPTIMIZED_STORAGE_STACK_MIN_BUILD: u32 = 22000;
+
+/// Service of the standard NVM Express controller driver shipped with Windows
+const STANDARD_NVME_SERVICE: &str = "stornvme";
+
+/// Storage controllers, among which the NVMe controllers
+const STORAGE_CONTROLLERS_QUERY: &str = "SELECT * FROM Win32_PnPEntity WHERE PNPClass = 'SCSIAdapter'";
+
+/// Property set of `DEVPKEY_PciDevice_*`
+const PCI_DEVICE_PROPERTIES: GUID = GUID::from_u128(0x3ab22e31_8264_4b4e_9af5_a8d2d8e33e62);
+
+/// Represents the NVMe capabilities of the disks attached to the system
+///
+/// PCIe link speeds and widths are read from the `DEVPKEY_PciDevice_*` properties of the
+/// controllers' device nodes, not from the storage reliability counters.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct NvmeCapabilities {
+    /// Sequence of NVMe disks
+    pub nvme_devices: Vec<NvmeDevice>,
+    /// Build number of the operating system, used to evaluate DirectStorage prerequisites
+    pub os_build: Option<u32>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(NvmeCapabilities, nvme_devices; os_build);
+
+impl NvmeCapabilities {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let sources = crate::query(STORAGE_NAMESPACE, "MSFT_PhysicalDisk").and_then(|physical_disks| {
+            Ok((
+                physical_disks,
+                crate::query(DEFAULT_NAMESPACE, "Win32_DiskDrive")?,
+                crate::query(DEFAULT_NAMESPACE, "Win32_OperatingSystem")?,
+                crate::raw_query(STORAGE_CONTROLLERS_QUERY)?,
+            ))
+        });
+
+        self.refresh(sources);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let sources = async {
+            Ok::<_, Unavailable>((
+                crate::async_query(STORAGE_NAMESPACE, "MSFT_PhysicalDisk").await?,
+                crate::async_query(DEFAULT_NAMESPACE, "Win32_DiskDrive").await?,
+                crate::async_query(DEFAULT_NAMESPACE, "Win32_OperatingSystem").await?,
+                crate::async_raw_query(STORAGE_CONTROLLERS_QUERY).await?,
+            ))
+        };
+
+        self.refresh(sources.await);
+    }
+
+    /// No device is reported when any of the classes cannot be queried, e.g. on editions without
+    /// the Storage Management provider
+    fn refresh(&mut self, sources: Result<NvmeSources, Unavailable>) {
+        let old_unavailable = self.unavailable.take();
+
+        let (physical_disks, disk_drives, operating_systems, storage_controllers) = sources.unwrap_or_else(|unavailable| {
+            self.unavailable = Some(unavailable);
+            Default::default()
+        });
+
+        let old_vec = std::mem::take(&mut self.nvme_devices);
+
+        self.os_build = operating_systems
+            .iter()
+            .find_map(|os| os.BuildNumber.as_deref())
+            .and_then(|build| build.trim().parse().ok());
+
+        let os_build = self.os_build.unwrap_or(0);
+
+        self.nvme_devices = physical_disks
+            .iter()
+            .filter(|disk| disk.BusType == Some(BUS_TYPE_NVME))
+            .map(|disk| {
+                // `DeviceId` of a physical disk is the `Index` of the matching `Win32_DiskDrive`
+                let pnp_device_id = disk_drives
+                    .iter()
+                    .find(|drive| drive.Index.map(|index| index.to_string()) == disk.DeviceId)
+                    .and_then(|drive| drive.PNPDeviceID.clone());
+                let controller = pnp_device_id
+                    .as_deref()
+                    .and_then(locate_device_node)
+                    .and_then(parent_device_node);
+                let link = |pid| {
+                    controller
+                        .and_then(|dev_inst| {
+                            device_node_property::<4>(
+                                dev_inst,
+                                &DEVPROPKEY { fmtid: PCI_DEVICE_PROPERTIES, pid },
+                                DEVPROP_TYPE_UINT32,
+                            )
+                        })
+                        .map(u32::from_le_bytes)
+                };
+                let controller_pnp_device_id = controller.and_then(device_node_id);
+                let controller_service = controller_pnp_device_id.as_deref().and_then(|id| {
+                    storage_controllers
+                        .iter()
+                        .find(|entity| entity.PNPDeviceID.as_deref().is_some_and(|found| found.eq_ignore_ascii_case(id)))
+                        .and_then(|entity| entity.Service.clone())
+                });
+                let solid_state = disk.MediaType == Some(MEDIA_TYPE_SSD);
+                let standard_driver = controller_service
+                    .as_deref()
+                    .is_some_and(|service| service.eq_ignore_ascii_case(STANDARD_NVME_SERVICE));
+
+                NvmeDevice {
+                    friendly_name: disk.FriendlyName.clone(),
+                    serial_number: disk.SerialNumber.clone(),
+                    firmware_version: disk.FirmwareVersion.clone(),
+                    size: disk.Size,
+                    solid_state,
+                    pnp_device_id,
+                    controller_pnp_device_id,
+                    controller_service,
+                    namespace_count: 1,
+                    current_link_speed: link(9),
+                    current_link_width: link(10),
+                    max_link_speed: link(11),
+                    max_link_width: link(12),
+                    directstorage_supported: disk.BusType == Some(BUS_TYPE_NVME)
+                        && solid_state
+                        && standard_driver
+                        && os_build >= DIRECTSTORAGE_MIN_BUILD,
+                    optimized_storage_stack: os_build >= OPTIMIZED_STORAGE_STACK_MIN_BUILD,
+                }
+            })
+            .collect();
+
+        // Every namespace shows up as its own disk below the same controller
+        let controllers = self
+            .nvme_devices
+            .iter()
+            .map(|device| device.controller_pnp_device_id.clone())
+            .collect::<Vec<_>>();
+
+        for device in self.nvme_devices.iter_mut() {
+            if device.controller_pnp_device_id.is_some() {
+                device.namespace_count = controllers
+                    .iter()
+                    .filter(|controller| **controller == device.controller_pnp_device_id)
+                    .count() as u32;
+            }
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.nvme_devices.len() != old_vec.len()
+            || crate::hash_vec(&self.nvme_devices) != crate::hash_vec(&old_vec);
+    }
+}
+
+/// Instances [`NvmeCapabilities`] is built from
+type NvmeSources = (
+    Vec<MSFT_PhysicalDisk>,
+    Vec<Win32_DiskDrive>,
+    Vec<Win32_OperatingSystem>,
+    Vec<Win32_PnPEntity>,
+);
+
+impl Default for NvmeCapabilities {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        NvmeCapabilities {
+            nvme_devices: Default::default(),
+            os_build: None,
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// Capabilities of an NVMe disk and whether it meets the DirectStorage prerequisites
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct NvmeDevice {
+    /// Friendly name of the disk
+    pub friendly_name: Option<String>,
+    /// Serial number of the disk
+    pub serial_number: Option<String>,
+    /// Firmware version of the disk
+    pub firmware_version: Option<String>,
+    /// Size of the disk in bytes
+    pub size: Option<u64>,
+    /// The disk reports solid state media
+    pub solid_state: bool,
+    /// Plug and Play device identifier of the disk
+    pub pnp_device_id: Option<String>,
+    /// Plug and Play device identifier of the NVMe controller the disk belongs to
+    pub controller_pnp_device_id: Option<String>,
+    /// Service of the driver of the NVMe controller, `stornvme` for the standard driver
+    pub controller_service: Option<String>,
+    /// Number of namespaces exposed by the controller
+    pub namespace_count: u32,
+    /// Negotiated PCIe link speed, `DEVPKEY_PciDevice_CurrentLinkSpeed` of the controller, as the
+    /// PCIe generation (1 = 2.5 GT/s, 2 = 5 GT/s, 3 = 8 GT/s,
+    /// 4 = 16 GT/s, 5 = 32 GT/s)
+    pub current_link_speed: Option<u32>,
+    /// Negotiated PCIe link width in lanes, `DEVPKEY_PciDevice_CurrentLinkWidth` of the controller
+    pub current_link_width: Option<u32>,
+    /// Maximum PCIe link speed supported by the controller, `DEVPKEY_PciDevice_MaxLinkSpeed`, as the
+    /// PCIe generation
+    pub max_link_speed: Option<u32>,
+    /// Maximum PCIe link width supported by the controller in lanes, `DEVPKEY_PciDevice_MaxLinkWidth`
+    pub max_link_width: Option<u32>,
+    /// The disk meets the DirectStorage prerequisites: a solid state NVMe disk, driven by the
+    /// standard NVMe driver, on an operating system recent enough to run DirectStorage
+    pub directstorage_supported: bool,
+    /// The operating system has the optimised storage stack (BypassIO) DirectStorage benefits from
+    pub optimized_storage_stack: bool,
+}
+
+impl NvmeDevice {
+    /// Returns `true` if the link negotiated below what the controller supports, e.g. a Gen4 x4
+    /// disk in a Gen3 or x2 slot
+    pub fn link_degraded(&self) -> bool {
+        matches!((self.current_link_speed, self.max_link_speed), (Some(current), Some(max)) if current < max)
+            || matches!((self.current_link_width, self.max_link_width), (Some(current), Some(max)) if current < max)
+    }
+}
+
 /// The `Win32_AutochkSetting` WMI class represents the settings for the autocheck operation of 
 /// a disk.
 /// 
@@ -407,6 +1145,12 @@ pub struct Win32_CDROMDrive {
     pub VolumeSerialNumber: Option<String>,
 }
 
+coded!(Win32_CDROMDrive {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
 /// The `Win32_DiskDrive` WMI class represents a physical disk drive as seen by a computer 
 /// running the Windows operating system.
 /// 
@@ -706,6 +1450,18 @@ pub struct Win32_DiskDrive {
     pub TracksPerCylinder: Option<u32>,
 }
 
+coded!(Win32_DiskDrive {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
+labeled!(Win32_DiskDrive {
+    Status => status: DeviceStatus,
+});
+
+referenced!(Win32_DiskDrive { DeviceID });
+
 /// The `Win32_PhysicalMedia` class represents any type of documentation or storage medium, such 
 /// as tapes, CD ROMs, and so on. To obtain the characteristics of the media in a CD drive, 
 /// such as whether it is writeable, use `Win32_CDROMDrive` and the `Capabilities` property.
@@ -1113,4 +1869,220 @@ pub struct Win32_TapeDrive {
     pub SystemCreationClassName: Option<String>,
     /// Name of the scoping system.
     pub SystemName: Option<String>,
-}
\ No newline at end of file
+}
+
+coded!(Win32_TapeDrive {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
+/// The `MSFT_PhysicalDisk` class represents a physical disk on the system, as seen by the Storage
+/// Management API.
+/// 
+/// <https://learn.microsoft.com/en-us/windows-hardware/drivers/storage/msft-physicaldisk>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_PhysicalDisk {
+    /// Size of the disk that is allocated to storage pools, in bytes.
+    pub AllocatedSize: Option<u64>,
+    /// Storage bus type of the physical disk.
+    /// 
+    /// - `Unknown` (0)
+    /// - `SCSI` (1)
+    /// - `ATAPI` (2)
+    /// - `ATA` (3)
+    /// - `1394` (4)
+    /// - `SSA` (5)
+    /// - `Fibre Channel` (6)
+    /// - `USB` (7)
+    /// - `RAID` (8)
+    /// - `iSCSI` (9)
+    /// - `SAS` (10)
+    /// - `SATA` (11)
+    /// - `SD` (12)
+    /// - `MMC` (13)
+    /// - `Virtual` (14)
+    /// - `File Backed Virtual` (15)
+    /// - `Storage Spaces` (16)
+    /// - `NVMe` (17)
+    pub BusType: Option<u16>,
+    /// Indicates whether the physical disk can be added to a storage pool.
+    pub CanPool: Option<bool>,
+    /// Address or other identifying information to uniquely name the disk, equal to the `Index`
+    /// of the matching `Win32_DiskDrive`.
+    pub DeviceId: Option<String>,
+    /// Firmware version of the physical disk.
+    pub FirmwareVersion: Option<String>,
+    /// Friendly name of the physical disk.
+    pub FriendlyName: Option<String>,
+    /// Health status of the physical disk.
+    /// 
+    /// - `Healthy` (0)
+    /// - `Warning` (1)
+    /// - `Unhealthy` (2)
+    /// - `Unknown` (5)
+    pub HealthStatus: Option<u16>,
+    /// Logical sector size of the physical disk, in bytes.
+    pub LogicalSectorSize: Option<u64>,
+    /// Media type of the physical disk.
+    /// 
+    /// - `Unspecified` (0)
+    /// - `HDD` (3)
+    /// - `SSD` (4)
+    /// - `SCM` (5)
+    pub MediaType: Option<u16>,
+    /// Model of the physical disk.
+    pub Model: Option<String>,
+    /// Operational status of the physical disk, e.g. 2 for OK.
+    pub OperationalStatus: Option<Vec<u16>>,
+    /// Physical location of the disk, e.g. the slot it is attached to.
+    pub PhysicalLocation: Option<String>,
+    /// Physical sector size of the physical disk, in bytes.
+    pub PhysicalSectorSize: Option<u64>,
+    /// Serial number of the physical disk.
+    pub SerialNumber: Option<String>,
+    /// Total size of the physical disk, in bytes.
+    pub Size: Option<u64>,
+    /// Rotational speed of the disk in RPM, 0 for solid state media.
+    pub SpindleSpeed: Option<u32>,
+    /// Unique identifier of the physical disk.
+    pub UniqueId: Option<String>,
+    /// Intended usage of the physical disk within a storage pool.
+    /// 
+    /// - `Unknown` (0)
+    /// - `Auto-Select` (1)
+    /// - `Manual-Select` (2)
+    /// - `Hot Spare` (3)
+    /// - `Retired` (4)
+    /// - `Journal` (5)
+    pub Usage: Option<u16>,
+}
+
+/// The `MSFT_Disk` class represents a disk as seen by the operating system, such as a physical
+/// disk, a virtual disk from a storage space or a VHD.
+/// 
+/// <https://learn.microsoft.com/en-us/windows-hardware/drivers/storage/msft-disk>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_Disk {
+    /// Currently allocated size of the disk, in bytes.
+    pub AllocatedSize: Option<u64>,
+    /// Storage bus type of the disk, same values as `MSFT_PhysicalDisk.BusType`.
+    pub BusType: Option<u16>,
+    /// Firmware version of the disk.
+    pub FirmwareVersion: Option<String>,
+    /// User friendly name of the disk.
+    pub FriendlyName: Option<String>,
+    /// Health of the disk.
+    /// 
+    /// - `Healthy` (0)
+    /// - `Warning` (1)
+    /// - `Unhealthy` (2)
+    pub HealthStatus: Option<u16>,
+    /// Disk holds the partition the system boots from.
+    pub IsBoot: Option<bool>,
+    /// Disk is offline.
+    pub IsOffline: Option<bool>,
+    /// Disk is read only.
+    pub IsReadOnly: Option<bool>,
+    /// Disk holds the Windows partition.
+    pub IsSystem: Option<bool>,
+    /// Location of the disk, e.g. its adapter and port.
+    pub Location: Option<String>,
+    /// Model of the disk.
+    pub Model: Option<String>,
+    /// Disk number, matches `Win32_DiskDrive.Index`.
+    pub Number: Option<u32>,
+    /// Number of partitions on the disk.
+    pub NumberOfPartitions: Option<u32>,
+    /// Operational states of the disk.
+    /// 
+    /// - `Unknown` (0)
+    /// - `Other` (1)
+    /// - `OK` (2)
+    /// - `Degraded` (3)
+    /// - `Predictive Failure` (5)
+    /// - `Offline` (0xD012)
+    pub OperationalStatus: Option<Vec<u16>>,
+    /// Partition style of the disk.
+    /// 
+    /// - `Unknown` (0)
+    /// - `MBR` (1)
+    /// - `GPT` (2)
+    pub PartitionStyle: Option<u16>,
+    /// Device path of the disk.
+    pub Path: Option<String>,
+    /// Serial number of the disk.
+    pub SerialNumber: Option<String>,
+    /// Total size of the disk, in bytes.
+    pub Size: Option<u64>,
+    /// Unique identifier of the disk.
+    pub UniqueId: Option<String>,
+}
+
+/// The `MSFT_StorageReliabilityCounter` class holds the reliability (SMART) counters of a physical
+/// disk.
+/// 
+/// <https://learn.microsoft.com/en-us/windows-hardware/drivers/storage/msft-storagereliabilitycounter>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_StorageReliabilityCounter {
+    /// `DeviceId` of the `MSFT_PhysicalDisk` the counters belong to.
+    pub DeviceId: Option<String>,
+    /// Longest flush latency, in milliseconds.
+    pub FlushLatencyMax: Option<u64>,
+    /// Number of load/unload cycles of the heads.
+    pub LoadUnloadCycleCount: Option<u32>,
+    /// Maximum number of load/unload cycles the disk is rated for.
+    pub LoadUnloadCycleCountMax: Option<u32>,
+    /// Date the disk was manufactured, as reported by the disk.
+    pub ManufactureDate: Option<String>,
+    /// Number of hours the disk has been powered on.
+    pub PowerOnHours: Option<u32>,
+    /// Number of read errors corrected by the disk.
+    pub ReadErrorsCorrected: Option<u64>,
+    /// Total number of read errors.
+    pub ReadErrorsTotal: Option<u64>,
+    /// Number of read errors the disk could not correct.
+    pub ReadErrorsUncorrected: Option<u64>,
+    /// Longest read latency, in milliseconds.
+    pub ReadLatencyMax: Option<u64>,
+    /// Number of start/stop cycles of the disk.
+    pub StartStopCycleCount: Option<u32>,
+    /// Maximum number of start/stop cycles the disk is rated for.
+    pub StartStopCycleCountMax: Option<u32>,
+    /// Current temperature of the disk, in degrees Celsius.
+    pub Temperature: Option<u8>,
+    /// Highest temperature the disk is rated for, in degrees Celsius.
+    pub TemperatureMax: Option<u8>,
+    /// Percentage of the rated endurance of a solid state disk that has been used.
+    pub Wear: Option<u8>,
+    /// Number of write errors corrected by the disk.
+    pub WriteErrorsCorrected: Option<u64>,
+    /// Total number of write errors.
+    pub WriteErrorsTotal: Option<u64>,
+    /// Number of write errors the disk could not correct.
+    pub WriteErrorsUncorrected: Option<u64>,
+    /// Longest write latency, in milliseconds.
+    pub WriteLatencyMax: Option<u64>,
+}
+
+/// The `MSStorageDriver_FailurePredictStatus` class reports whether the storage driver predicts a
+/// failure of the disk, based on its SMART attributes.
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSStorageDriver_FailurePredictStatus {
+    /// Failure prediction is enabled for the disk.
+    pub Active: Option<bool>,
+    /// Instance name of the disk, its PnP device instance path followed by `_0`.
+    pub InstanceName: Option<String>,
+    /// The disk predicts an imminent failure.
+    pub PredictFailure: Option<bool>,
+    /// Vendor specific reason of the prediction, 0 when no failure is predicted.
+    pub Reason: Option<u32>,
+}
@@ -9,15 +9,32 @@
 //! - [Cooling Device Classes](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/computer-system-hardware-classes#cooling-device-classes)
 //! - [Input Device Classes](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/computer-system-hardware-classes#input-device-classes)
 //! - [Mass Storage Classes](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/computer-system-hardware-classes#mass-storage-classes)
+//! - [Motherboard, Controller, and Port Classes](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/computer-system-hardware-classes#motherboard-controller-and-port-classes)
 //! - [Networking Device Classes](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/computer-system-hardware-classes#networking-device-classes)
 //! - [Power Classes](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/computer-system-hardware-classes#power-classes)
+//! - [Printing Classes](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/computer-system-hardware-classes#printing-classes)
 //! - [Telephony Classes](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/computer-system-hardware-classes#telephony-classes)
 //! - [Video and Monitor Classes](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/computer-system-hardware-classes#video-and-monitor-classes)
 
+#[cfg(feature = "hw-cooling-device")]
 pub mod cooling_device;
+#[cfg(feature = "hw-input-device")]
 pub mod input_device;
+#[cfg(feature = "hw-mass-storage")]
 pub mod mass_storage;
+#[cfg(feature = "hw-motherboard-controller-port")]
+pub mod motherboard_controller_port;
+#[cfg(feature = "hw-networking-device")]
 pub mod networking_device;
+#[cfg(feature = "hw-power")]
 pub mod power;
+#[cfg(feature = "hw-printing")]
+pub mod printing;
+#[cfg(feature = "hw-telephony")]
 pub mod telephony;
-pub mod video_monitor;
\ No newline at end of file
+#[cfg(feature = "hw-usb")]
+pub mod usb;
+#[cfg(feature = "hw-video-monitor")]
+pub mod video_monitor;
+#[cfg(feature = "bluetooth")]
+pub mod bluetooth;
@@ -53,3 +53,691 @@
 //! | [**Win32\_USBController**](win32-usbcontroller)                         | Manages the capabilities of a universal serial bus (USB) controller.<br/>                                                                                                                                           |
 //! | [**Win32\_USBControllerDevice**](win32-usbcontrollerdevice)             | Relates a USB controller and the [**CIM\_LogicalDevice**](cim-logicaldevice) instances connected to it.<br/>                                                                                                    |
 //! | [**Win32\_USBHub**](/previous-versions/windows/desktop/cimwin32a/win32-usbhub)                                 | Represents the management characteristics of a USB hub.<br/>                                                                                                                                                        |
+//!
+//! [`PnPEntities::in_error`] lists the devices Device Manager flags as not working, with the reason
+//! given by [`Win32_PnPEntity::config_manager_error_code`].
+
+use crate::{coded, keyed, labeled, referenced, update};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use windows::core::HSTRING;
+use windows::Win32::Devices::DeviceAndDriverInstallation::{
+    CM_Get_DevNode_PropertyW, CM_Get_Device_IDW, CM_Get_Parent, CM_Locate_DevNodeW,
+    CM_LOCATE_DEVNODE_NORMAL, CR_SUCCESS,
+};
+use windows::Win32::Devices::Properties::{DEVPROPKEY, DEVPROPTYPE};
+use wmi::{COMLibrary, WMIConnection, WMIDateTime};
+
+/// Represents the state of Windows user's PnPEntities
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct PnPEntities {
+    /// Sequence of windows PnPEntities states
+    pub pnp_entities: Vec<Win32_PnPEntity>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(PnPEntities, pnp_entities);
+keyed!(PnPEntities, pnp_entities, Win32_PnPEntity, DeviceID: String);
+
+impl PnPEntities {
+    /// Devices in an error state, whose `ConfigManagerErrorCode` is not 0, the ones Device Manager
+    /// flags with a warning sign
+    pub fn in_error(&self) -> Vec<&Win32_PnPEntity> {
+        self.pnp_entities
+            .iter()
+            .filter(|entity| entity.ConfigManagerErrorCode.is_some_and(|code| code != 0))
+            .collect()
+    }
+
+    /// Parent of `entity` in the device tree, e.g. the USB hub a keyboard is plugged into
+    pub fn parent_of(&self, entity: &Win32_PnPEntity) -> Option<&Win32_PnPEntity> {
+        let parent = entity.parent_device_id()?;
+
+        self.get_by_key(parent.as_str())
+    }
+
+    /// Devices whose parent in the device tree is `entity`
+    pub fn children_of(&self, entity: &Win32_PnPEntity) -> Vec<&Win32_PnPEntity> {
+        let Some(device_id) = entity.DeviceID.as_deref() else {
+            return Vec::new();
+        };
+
+        self.pnp_entities
+            .iter()
+            .filter(|child| {
+                child
+                    .parent_device_id()
+                    .is_some_and(|parent| parent.eq_ignore_ascii_case(device_id))
+            })
+            .collect()
+    }
+}
+
+/// Represents the state of Windows SerialPortConfigurations
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct SerialPortConfigurations {
+    /// Sequence of windows SerialPortConfigurations states
+    pub serial_port_configurations: Vec<Win32_SerialPortConfiguration>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(SerialPortConfigurations, serial_port_configurations);
+keyed!(SerialPortConfigurations, serial_port_configurations, Win32_SerialPortConfiguration, Name: String);
+
+/// Represents the state of Windows ParallelPorts
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ParallelPorts {
+    /// Sequence of windows ParallelPorts states
+    pub parallel_ports: Vec<Win32_ParallelPort>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(ParallelPorts, parallel_ports);
+keyed!(ParallelPorts, parallel_ports, Win32_ParallelPort, DeviceID: String);
+
+/// Represents the state of Windows BIOSes
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct BIOSes {
+    /// Sequence of windows BIOS states
+    pub bioses: Vec<Win32_BIOS>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(BIOSes, bioses);
+
+impl BIOSes {
+    /// Firmware version of the primary BIOS as reported through SMBIOS, e.g. `1.15.0`
+    pub fn firmware_version(&self) -> Option<&str> {
+        self.primary()?.SMBIOSBIOSVersion.as_deref()
+    }
+
+    /// The primary BIOS of the computer
+    pub fn primary(&self) -> Option<&Win32_BIOS> {
+        self.bioses
+            .iter()
+            .find(|bios| bios.PrimaryBIOS != Some(false))
+    }
+}
+
+/// Represents the state of Windows SystemEnclosures
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct SystemEnclosures {
+    /// Sequence of windows SystemEnclosures states
+    pub system_enclosures: Vec<Win32_SystemEnclosure>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(SystemEnclosures, system_enclosures);
+keyed!(SystemEnclosures, system_enclosures, Win32_SystemEnclosure, Tag: String);
+
+impl SystemEnclosures {
+    /// Asset tag of the first enclosure that has one, as set by the vendor or in the firmware setup
+    pub fn asset_tag(&self) -> Option<&str> {
+        self.system_enclosures
+            .iter()
+            .find_map(Win32_SystemEnclosure::asset_tag)
+    }
+
+    /// Serial number of the first enclosure that has one
+    pub fn serial_number(&self) -> Option<&str> {
+        self.system_enclosures
+            .iter()
+            .find_map(Win32_SystemEnclosure::serial_number)
+    }
+
+    /// Returns `true` if an enclosure is the chassis of a laptop, tablet or other portable
+    /// computer
+    pub fn is_portable(&self) -> bool {
+        self.system_enclosures
+            .iter()
+            .flat_map(Win32_SystemEnclosure::chassis_types)
+            .any(|chassis_type| chassis_type.is_portable())
+    }
+}
+
+/// The `Win32_SystemEnclosure` WMI class represents the properties that are associated with a
+/// physical system enclosure, the chassis of the computer.
+/// 
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-systemenclosure>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_SystemEnclosure {
+    /// If `TRUE`, the frame is equipped with an audible alarm.
+    pub AudibleAlarm: Option<bool>,
+    /// Free-form string that describes the way in which the chassis was breached, when
+    /// `SecurityBreach` is 1 (Other).
+    pub BreachDescription: Option<String>,
+    /// Free-form string that describes how the cables are connected and bundled for the frame.
+    pub CableManagementStrategy: Option<String>,
+    /// A short textual description of the object.
+    pub Caption: Option<String>,
+    /// Array of the types of the chassis, as defined by SMBIOS, e.g. `Desktop` (3) or
+    /// `Notebook` (10).
+    pub ChassisTypes: Option<Vec<u16>>,
+    /// Amount of current drawn by the chassis, in amps, negative if it provides power.
+    pub CurrentRequiredOrProduced: Option<i16>,
+    /// A textual description of the object.
+    pub Description: Option<String>,
+    /// Amount of heat generated by the chassis, in BTU/hour.
+    pub HeatGeneration: Option<u16>,
+    /// If `TRUE`, the package can be hot-swapped.
+    pub HotSwappable: Option<bool>,
+    /// Date and time the object was installed.
+    pub InstallDate: Option<WMIDateTime>,
+    /// If `TRUE`, the frame is protected with a lock.
+    pub LockPresent: Option<bool>,
+    /// Name of the organization responsible for producing the physical element.
+    pub Manufacturer: Option<String>,
+    /// Name by which the physical element is generally known.
+    pub Model: Option<String>,
+    /// Label by which the object is known.
+    pub Name: Option<String>,
+    /// Number of power cords which must be connected to the chassis for all the components to
+    /// operate.
+    pub NumberOfPowerCords: Option<u16>,
+    /// Additional data, beyond asset tag information, that can be used to identify a physical
+    /// element, e.g. bar code data.
+    pub OtherIdentifyingInfo: Option<String>,
+    /// Part number assigned by the organization responsible for producing or manufacturing the
+    /// physical element.
+    pub PartNumber: Option<String>,
+    /// If `TRUE`, the physical element is powered on.
+    pub PoweredOn: Option<bool>,
+    /// If `TRUE`, the package can be removed from its container without impairing its function.
+    pub Removable: Option<bool>,
+    /// If `TRUE`, the package can be replaced with a physically different one.
+    pub Replaceable: Option<bool>,
+    /// Whether the chassis was breached, e.g. `No Breach` (3) or `Breach Attempted` (4).
+    pub SecurityBreach: Option<u16>,
+    /// Security status of the chassis, e.g. `None` (3) or `External interface locked out` (4).
+    pub SecurityStatus: Option<u16>,
+    /// Manufacturer-allocated number used to identify the physical element.
+    pub SerialNumber: Option<String>,
+    /// Stock keeping unit number for the physical element.
+    pub SKU: Option<String>,
+    /// Asset tag number of the system enclosure, as reported by SMBIOS.
+    pub SMBIOSAssetTag: Option<String>,
+    /// String that indicates the current status of the object, e.g. "OK", "Error" or "Degraded".
+    pub Status: Option<String>,
+    /// Unique identifier of the system enclosure, e.g. `System Enclosure 0`.
+    pub Tag: Option<String>,
+    /// Array of free-form strings providing more information on the `ChassisTypes` array entries.
+    pub TypeDescriptions: Option<Vec<String>>,
+    /// Version of the physical element.
+    pub Version: Option<String>,
+    /// If `TRUE`, the equipment includes a visible alarm.
+    pub VisibleAlarm: Option<bool>,
+}
+
+labeled!(Win32_SystemEnclosure {
+    Status => status: DeviceStatus,
+});
+
+impl Win32_SystemEnclosure {
+    /// `ChassisTypes` as [`ChassisType`](crate::codes::ChassisType)s
+    pub fn chassis_types(&self) -> Vec<crate::codes::ChassisType> {
+        self.ChassisTypes
+            .iter()
+            .flatten()
+            .map(|&code| crate::codes::ChassisType::from(code))
+            .collect()
+    }
+
+    /// `SMBIOSAssetTag`, `None` when unset or left as a vendor placeholder such as
+    /// `No Asset Tag`
+    pub fn asset_tag(&self) -> Option<&str> {
+        meaningful(self.SMBIOSAssetTag.as_deref()?)
+    }
+
+    /// `SerialNumber`, `None` when unset or left as a vendor placeholder such as
+    /// `To Be Filled By O.E.M.`
+    pub fn serial_number(&self) -> Option<&str> {
+        meaningful(self.SerialNumber.as_deref()?)
+    }
+}
+
+/// `value` trimmed, `None` if it is empty or a placeholder firmware vendors leave in unset
+/// SMBIOS strings
+fn meaningful(value: &str) -> Option<&str> {
+    const PLACEHOLDERS: &[&str] = &[
+        "",
+        "default string",
+        "no asset tag",
+        "no asset information",
+        "none",
+        "not specified",
+        "not available",
+        "system serial number",
+        "to be filled by o.e.m.",
+        "0",
+    ];
+    let value = value.trim();
+
+    (!PLACEHOLDERS.contains(&value.to_ascii_lowercase().as_str())).then_some(value)
+}
+
+/// The `Win32_BIOS` WMI class represents the attributes of the computer system's basic
+/// input/output services (BIOS) that are installed on a computer.
+/// 
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-bios>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_BIOS {
+    /// Array of BIOS characteristics supported by the system as defined by the System Management
+    /// BIOS Reference Specification, e.g. `Plug and Play is supported` (9) or `Boot from CD is
+    /// supported` (15).
+    pub BiosCharacteristics: Option<Vec<u16>>,
+    /// Array of the complete system BIOS information. In many computers there can be several
+    /// version strings that are stored in the registry and represent the system BIOS information.
+    pub BIOSVersion: Option<Vec<String>>,
+    /// Internal identifier for this compilation of this software element.
+    pub BuildNumber: Option<String>,
+    /// A short textual description of the object.
+    pub Caption: Option<String>,
+    /// Name of the current BIOS language.
+    pub CurrentLanguage: Option<String>,
+    /// A textual description of the object.
+    pub Description: Option<String>,
+    /// Major release of the embedded controller firmware.
+    pub EmbeddedControllerMajorVersion: Option<u8>,
+    /// Minor release of the embedded controller firmware.
+    pub EmbeddedControllerMinorVersion: Option<u8>,
+    /// Number of languages available for installation on this system.
+    pub InstallableLanguages: Option<u16>,
+    /// Manufacturer of this software element.
+    pub Manufacturer: Option<String>,
+    /// Label by which the object is known.
+    pub Name: Option<String>,
+    /// If `TRUE`, this is the primary BIOS of the computer system.
+    pub PrimaryBIOS: Option<bool>,
+    /// Release date of the Windows BIOS.
+    pub ReleaseDate: Option<WMIDateTime>,
+    /// Assigned serial number of the software element.
+    pub SerialNumber: Option<String>,
+    /// BIOS version as reported by SMBIOS.
+    pub SMBIOSBIOSVersion: Option<String>,
+    /// Major SMBIOS version number.
+    pub SMBIOSMajorVersion: Option<u16>,
+    /// Minor SMBIOS version number.
+    pub SMBIOSMinorVersion: Option<u16>,
+    /// If `TRUE`, the SMBIOS is available on this computer system.
+    pub SMBIOSPresent: Option<bool>,
+    /// String that indicates the current status of the object, e.g. "OK", "Error" or "Degraded".
+    pub Status: Option<String>,
+    /// Major release of the System BIOS.
+    pub SystemBiosMajorVersion: Option<u8>,
+    /// Minor release of the System BIOS.
+    pub SystemBiosMinorVersion: Option<u8>,
+    /// Version of the BIOS. This string is created by the BIOS manufacturer.
+    pub Version: Option<String>,
+}
+
+/// The `Win32_PnPEntity` WMI class represents the properties of a Plug and Play device. Plug and
+/// Play entities are shown as entries in the Device Manager located in Control Panel.
+/// 
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-pnpentity>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_PnPEntity {
+    /// Availability and status of the device.
+    /// 
+    /// - `Other` (1)
+    /// - `Unknown` (2)
+    /// - `Running` / `Full Power` (3)
+    /// - `Warning` (4)
+    /// - `In Test` (5)
+    /// - `Not Applicable` (6)
+    /// - `Power Off` (7)
+    /// - `Off Line` (8)
+    /// - `Off Duty` (9)
+    /// - `Degraded` (10)
+    /// - `Not Installed` (11)
+    /// - `Install Error` (12)
+    /// - `Power Save - Unknown` (13)
+    /// - `Power Save - Low Power Mode` (14)
+    /// - `Power Save - Standby` (15)
+    /// - `Power Cycle` (16)
+    /// - `Power Save - Warning` (17)
+    /// - `Paused` (18)
+    /// - `Not Ready` (19)
+    /// - `Not Configured` (20)
+    /// - `Quiesced` (21)
+    pub Availability: Option<u16>,
+    /// A short textual description of the object.
+    pub Caption: Option<String>,
+    /// The globally unique identifier (GUID) of this Plug and Play device.
+    pub ClassGuid: Option<String>,
+    /// An vendor-defined identification string that Setup uses to match a device to an INF file.
+    pub CompatibleID: Option<Vec<String>>,
+    /// Win32 Configuration Manager error code, 0 means the device is working properly.
+    pub ConfigManagerErrorCode: Option<u32>,
+    /// If `TRUE`, the device is using a user-defined configuration.
+    pub ConfigManagerUserConfig: Option<bool>,
+    /// Name of the first concrete class to appear in the inheritance chain used in the creation of
+    /// an instance.
+    pub CreationClassName: Option<String>,
+    /// A textual description of the object.
+    pub Description: Option<String>,
+    /// The identifier of the Plug and Play device.
+    pub DeviceID: Option<String>,
+    /// If `TRUE`, the error reported in `LastErrorCode` is now cleared.
+    pub ErrorCleared: Option<bool>,
+    /// More information about the error recorded in `LastErrorCode`, and information about any
+    /// corrective actions that may be taken.
+    pub ErrorDescription: Option<String>,
+    /// A vendor-defined identification string that Setup uses to match a device to an INF file.
+    pub HardwareID: Option<Vec<String>>,
+    /// Indicates when the object was installed.
+    pub InstallDate: Option<WMIDateTime>,
+    /// Last error code reported by the logical device.
+    pub LastErrorCode: Option<u32>,
+    /// The name of the manufacturer of the Plug and Play device.
+    pub Manufacturer: Option<String>,
+    /// Label by which the object is known.
+    pub Name: Option<String>,
+    /// The name of the type of this Plug and Play device, e.g. `Bluetooth`, `USB` or `Net`.
+    pub PNPClass: Option<String>,
+    /// Windows Plug and Play device identifier of the logical device.
+    pub PNPDeviceID: Option<String>,
+    /// Array of the specific power-related capabilities of a logical device.
+    pub PowerManagementCapabilities: Option<Vec<u16>>,
+    /// If `TRUE`, the device can be power-managed.
+    pub PowerManagementSupported: Option<bool>,
+    /// Indicates if the Plug and Play device is currently present in the system.
+    pub Present: Option<bool>,
+    /// Name of the service that supports this Plug and Play device.
+    pub Service: Option<String>,
+    /// String that indicates the current status of the object, e.g. "OK", "Error" or "Degraded".
+    pub Status: Option<String>,
+    /// State of the logical device.
+    /// 
+    /// - `Other` (1)
+    /// - `Unknown` (2)
+    /// - `Enabled` (3)
+    /// - `Disabled` (4)
+    /// - `Not Applicable` (5)
+    pub StatusInfo: Option<u16>,
+    /// The value of the scoping computer's `CreationClassName` property.
+    pub SystemCreationClassName: Option<String>,
+    /// The name of the scoping system.
+    pub SystemName: Option<String>,
+}
+
+referenced!(Win32_PnPEntity { DeviceID });
+
+coded!(Win32_PnPEntity {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
+labeled!(Win32_PnPEntity {
+    Status => status: DeviceStatus,
+});
+
+impl Win32_PnPEntity {
+    /// Plug and Play device identifier of the parent of the device, read from the device tree of
+    /// the running system, `None` if the device is no longer present
+    pub fn parent_device_id(&self) -> Option<String> {
+        let dev_inst = locate_device_node(self.PNPDeviceID.as_deref().or(self.DeviceID.as_deref())?)?;
+
+        device_node_id(parent_device_node(dev_inst)?)
+    }
+}
+
+/// The `Win32_SerialPortConfiguration` WMI class represents the settings for data transmission on
+/// a Windows serial port.
+/// 
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-serialportconfiguration>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_SerialPortConfiguration {
+    /// If `TRUE`, the read and write operations are terminated when an error occurs.
+    pub AbortReadWriteOnError: Option<bool>,
+    /// Baud rate at which the communications device operates, e.g. 9600.
+    pub BaudRate: Option<u32>,
+    /// If `TRUE`, the port is in binary mode, the end-of-file check being disabled.
+    pub BinaryModeEnabled: Option<bool>,
+    /// Number of data bits in each byte transmitted and received, from 4 to 8.
+    pub BitsPerByte: Option<u32>,
+    /// A short textual description of the object.
+    pub Caption: Option<String>,
+    /// If `TRUE`, transmission continues after the input buffer is within `XOffXMitThreshold`
+    /// bytes of being full and the driver has transmitted the `XOffCharacter`.
+    pub ContinueXMitOnXOff: Option<bool>,
+    /// If `TRUE`, the clear to send (CTS) signal is monitored for output flow control.
+    pub CTSOutflowControl: Option<bool>,
+    /// A textual description of the object.
+    pub Description: Option<String>,
+    /// If `TRUE`, null bytes are discarded when received.
+    pub DiscardNULLBytes: Option<bool>,
+    /// If `TRUE`, the data set ready (DSR) signal is monitored for output flow control.
+    pub DSROutflowControl: Option<bool>,
+    /// If `TRUE`, bytes received are ignored while the DSR modem input line is low.
+    pub DSRSensitivity: Option<bool>,
+    /// Data terminal ready (DTR) flow control, "Disable", "Enable" or "Handshake".
+    pub DTRFlowControlType: Option<String>,
+    /// Value of the character used to signal the end of data.
+    pub EOFCharacter: Option<u32>,
+    /// Value of the character used to replace bytes received with a parity error.
+    pub ErrorReplaceCharacter: Option<u32>,
+    /// If `TRUE`, bytes received with parity errors are replaced with `ErrorReplaceCharacter`.
+    pub ErrorReplacementEnabled: Option<bool>,
+    /// Value of the character used to signal an event.
+    pub EventCharacter: Option<u32>,
+    /// If `TRUE`, the serial port is busy.
+    pub IsBusy: Option<bool>,
+    /// Name of the port the settings apply to, e.g. "COM1".
+    pub Name: Option<String>,
+    /// Parity used, "None", "Odd", "Even", "Mark" or "Space".
+    pub Parity: Option<String>,
+    /// If `TRUE`, parity checking is performed.
+    pub ParityCheckEnabled: Option<bool>,
+    /// Request to send (RTS) flow control, "Disable", "Enable", "Handshake" or "Toggle".
+    pub RTSFlowControlType: Option<String>,
+    /// Identifier by which the object is known.
+    pub SettingID: Option<String>,
+    /// Number of stop bits, "1", "1.5" or "2".
+    pub StopBits: Option<String>,
+    /// Value of the XOFF character for both transmission and reception.
+    pub XOffCharacter: Option<u32>,
+    /// Maximum number of bytes allowed in the input buffer before the XOFF character is sent.
+    pub XOffXMitThreshold: Option<u32>,
+    /// Value of the XON character for both transmission and reception.
+    pub XOnCharacter: Option<u32>,
+    /// Minimum number of bytes allowed in the input buffer before the XON character is sent.
+    pub XOnXMitThreshold: Option<u32>,
+    /// XON/XOFF flow control used during reception.
+    pub XOnXOffInFlowControl: Option<u32>,
+    /// XON/XOFF flow control used during transmission.
+    pub XOnXOffOutFlowControl: Option<u32>,
+}
+
+/// The `Win32_ParallelPort` WMI class represents the properties of a parallel port on a computer
+/// system running Windows.
+/// 
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-parallelport>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_ParallelPort {
+    /// Availability and status of the device, see `Win32_PnPEntity::Availability` for the codes.
+    pub Availability: Option<u16>,
+    /// Capabilities of the parallel port.
+    /// 
+    /// - `Unknown` (0)
+    /// - `Other` (1)
+    /// - `XT/AT Compatible` (2)
+    /// - `PS/2 Compatible` (3)
+    /// - `ECP` (4)
+    /// - `EPP` (5)
+    /// - `PC-98` (6)
+    /// - `PC-98-Hireso` (7)
+    /// - `PC-H98` (8)
+    pub Capabilities: Option<Vec<u16>>,
+    /// Free-form descriptions of the entries of `Capabilities`.
+    pub CapabilityDescriptions: Option<Vec<String>>,
+    /// A short textual description of the object.
+    pub Caption: Option<String>,
+    /// Win32 Configuration Manager error code, 0 means the device is working properly.
+    pub ConfigManagerErrorCode: Option<u32>,
+    /// If `TRUE`, the device is using a user-defined configuration.
+    pub ConfigManagerUserConfig: Option<bool>,
+    /// Name of the first concrete class to appear in the inheritance chain used in the creation of
+    /// an instance.
+    pub CreationClassName: Option<String>,
+    /// A textual description of the object.
+    pub Description: Option<String>,
+    /// Unique identifier of the parallel port, e.g. "LPT1".
+    pub DeviceID: Option<String>,
+    /// If `TRUE`, the parallel port supports direct memory access (DMA).
+    pub DMASupport: Option<bool>,
+    /// If `TRUE`, the error reported in `LastErrorCode` is now cleared.
+    pub ErrorCleared: Option<bool>,
+    /// More information about the error recorded in `LastErrorCode`, and information about any
+    /// corrective actions that may be taken.
+    pub ErrorDescription: Option<String>,
+    /// Indicates when the object was installed.
+    pub InstallDate: Option<WMIDateTime>,
+    /// Last error code reported by the logical device.
+    pub LastErrorCode: Option<u32>,
+    /// Maximum number of directly addressable entities supported by the controller, 0 if unknown.
+    pub MaxNumberControlled: Option<u32>,
+    /// Label by which the object is known.
+    pub Name: Option<String>,
+    /// If `TRUE`, the port was automatically discovered by the operating system.
+    pub OSAutoDiscovered: Option<bool>,
+    /// Windows Plug and Play device identifier of the logical device.
+    pub PNPDeviceID: Option<String>,
+    /// Array of the specific power-related capabilities of a logical device.
+    pub PowerManagementCapabilities: Option<Vec<u16>>,
+    /// If `TRUE`, the device can be power-managed.
+    pub PowerManagementSupported: Option<bool>,
+    /// Protocol used by the controller to access controlled devices, e.g. `Parallel Protocol`
+    /// (17) or `IEEE 1284` (27).
+    pub ProtocolSupported: Option<u16>,
+    /// String that indicates the current status of the object, e.g. "OK", "Error" or "Degraded".
+    pub Status: Option<String>,
+    /// State of the logical device.
+    /// 
+    /// - `Other` (1)
+    /// - `Unknown` (2)
+    /// - `Enabled` (3)
+    /// - `Disabled` (4)
+    /// - `Not Applicable` (5)
+    pub StatusInfo: Option<u16>,
+    /// The value of the scoping computer's `CreationClassName` property.
+    pub SystemCreationClassName: Option<String>,
+    /// The name of the scoping system.
+    pub SystemName: Option<String>,
+    /// Date and time the controller was last reset.
+    pub TimeOfLastReset: Option<WMIDateTime>,
+}
+
+coded!(Win32_ParallelPort {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
+/// Returns the device node of the device with the given Plug and Play device identifier
+pub(crate) fn locate_device_node(pnp_device_id: &str) -> Option<u32> {
+    let mut dev_inst = 0u32;
+
+    let result = unsafe {
+        CM_Locate_DevNodeW(&mut dev_inst, &HSTRING::from(pnp_device_id), CM_LOCATE_DEVNODE_NORMAL)
+    };
+
+    (result == CR_SUCCESS).then_some(dev_inst)
+}
+
+/// Returns the parent of the device node `dev_inst`
+pub(crate) fn parent_device_node(dev_inst: u32) -> Option<u32> {
+    let mut parent = 0u32;
+
+    (unsafe { CM_Get_Parent(&mut parent, dev_inst, 0) } == CR_SUCCESS).then_some(parent)
+}
+
+/// Returns the Plug and Play device identifier of the device node `dev_inst`
+pub(crate) fn device_node_id(dev_inst: u32) -> Option<String> {
+    // MAX_DEVICE_ID_LEN is 200 characters, plus the terminator
+    let mut buffer = [0u16; 201];
+
+    if unsafe { CM_Get_Device_IDW(dev_inst, &mut buffer, 0) } != CR_SUCCESS {
+        return None;
+    }
+
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+
+    Some(String::from_utf16_lossy(&buffer[..len]))
+}
+
+/// Reads a fixed size property of the device node `dev_inst`, returning `None` if it is missing or
+/// is not of `expected_type`
+#[cfg_attr(not(feature = "hw-mass-storage"), allow(dead_code))]
+pub(crate) fn device_node_property<const N: usize>(
+    dev_inst: u32,
+    key: &DEVPROPKEY,
+    expected_type: DEVPROPTYPE,
+) -> Option<[u8; N]> {
+    let mut property_type = DEVPROPTYPE::default();
+    let mut buffer = [0u8; N];
+    let mut size = N as u32;
+
+    let result = unsafe {
+        CM_Get_DevNode_PropertyW(
+            dev_inst,
+            key,
+            &mut property_type,
+            Some(buffer.as_mut_ptr()),
+            &mut size,
+            0,
+        )
+    };
+
+    (result == CR_SUCCESS && property_type == expected_type && size as usize == N).then_some(buffer)
+}
@@ -5,8 +5,9 @@
 //! | [**Win32\_NetworkAdapter**](win32-networkadapter)                           | Represents a network adapter on a computer system running Windows.<br/>                                                                                                                                          |
 //! | [**Win32\_NetworkAdapterConfiguration**](win32-networkadapterconfiguration) | Represents the attributes and behaviors of a network adapter. The class is not guaranteed to be supported after the ratification of the Distributed Management Task Force (DMTF) CIM network specification.<br/> |
 //! | [**Win32\_NetworkAdapterSetting**](win32-networkadaptersetting)             | Relates a network adapter and its configuration settings.<br/>                                                                                                                                                   |
+//! | [**MSFT\_NetAdapter**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/legacy/hh968170(v=vs.85)) | Represents a network adapter of a computer, lives in the `root\StandardCimv2` namespace.<br/>                                                                                        |
 
-use crate::update;
+use crate::{coded, keyed, update, STANDARD_CIMV2_NAMESPACE};
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
@@ -23,9 +24,12 @@ pub struct NetworkAdapters {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NetworkAdapters, network_adapters);
+keyed!(NetworkAdapters, network_adapters, Win32_NetworkAdapter, DeviceID: String);
 
 /// Represents the state of Windows user's NetworkAdapterConfigurations
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -39,9 +43,102 @@ pub struct NetworkAdapterConfigurations {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NetworkAdapterConfigurations, network_adapter_configurations);
+keyed!(NetworkAdapterConfigurations, network_adapter_configurations, Win32_NetworkAdapterConfiguration, Index: u32);
+
+/// Represents the state of the network adapters reported by `MSFT_NetAdapter`, physical, virtual
+/// and VPN alike
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct NetAdapters {
+    /// Sequence of network adapters, in the order `root\StandardCimv2` returns them
+    pub net_adapters: Vec<MSFT_NetAdapter>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(NetAdapters, net_adapters, STANDARD_CIMV2_NAMESPACE);
+
+impl NetAdapters {
+    /// Returns the [`AdapterClass`] of the adapter with the given `InterfaceIndex`, or
+    /// [`AdapterClass::Unknown`] if no such adapter is present in the snapshot
+    pub fn class_of(&self, interface_index: u32) -> AdapterClass {
+        self.net_adapters
+            .iter()
+            .find(|adapter| adapter.InterfaceIndex == Some(interface_index))
+            .map(MSFT_NetAdapter::class)
+            .unwrap_or(AdapterClass::Unknown)
+    }
+}
+
+impl NetworkAdapterConfigurations {
+    /// Tags every adapter configuration with the [`AdapterClass`] of the adapter it belongs to
+    pub fn classify(&self, adapters: &NetAdapters) -> Vec<Classified<Win32_NetworkAdapterConfiguration>> {
+        self.network_adapter_configurations
+            .iter()
+            .map(|configuration| Classified {
+                adapter_class: configuration
+                    .InterfaceIndex
+                    .map_or(AdapterClass::Unknown, |index| adapters.class_of(index)),
+                item: configuration.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Kind of a network adapter, as inferred by [`MSFT_NetAdapter::class`]
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum AdapterClass {
+    /// Backed by real hardware, e.g. an Ethernet or Wi-Fi card
+    Physical,
+    /// Software adapter such as a Hyper-V switch, WSL or container NIC
+    Virtual,
+    /// Tunnel adapter belonging to a VPN client or a `WAN Miniport`
+    Vpn,
+    /// Software loopback interface
+    Loopback,
+    /// The adapter could not be found or classified
+    #[default]
+    Unknown,
+}
+
+/// An item tagged with the [`AdapterClass`] of the adapter it is bound to
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct Classified<T> {
+    /// Class of the adapter the item is bound to
+    pub adapter_class: AdapterClass,
+    /// The tagged item
+    pub item: T,
+}
+
+/// Substrings (lowercase) of `PnPDeviceID`, `ComponentID` or `InterfaceDescription` that identify
+/// well known VPN and tunnel drivers
+const VPN_MARKERS: [&str; 14] = [
+    "wan miniport",
+    "tap0901",
+    "tap-windows",
+    "wintun",
+    "wireguard",
+    "anyconnect",
+    "vpn",
+    "fortinet",
+    "pangp",
+    "juniper",
+    "openvpn",
+    "nordlynx",
+    "zscaler",
+    "ms_l2tp",
+];
 
 /// The `Win32_NetworkAdapter` class is deprecated. Use the MSFT_NetAdapter class instead. 
 /// The Win32_NetworkAdapterWMI class represents a network adapter of a computer running a 
@@ -306,6 +403,12 @@ pub struct Win32_NetworkAdapter {
     pub TimeOfLastReset: Option<WMIDateTime>,
 }
 
+coded!(Win32_NetworkAdapter {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
 /// The `Win32_NetworkAdapterConfiguration` WMI class represents the attributes and behaviors 
 /// of a network adapter. This class includes extra properties and methods that support the 
 /// management of the TCP/IP protocol that are independent from the network adapter.
@@ -627,3 +730,102 @@ pub struct Win32_NetworkAdapterConfiguration {
     /// IP address for the secondary WINS server.
     pub WINSSecondaryServer: Option<String>,
 }
+
+/// The `MSFT_NetAdapter` class represents a network adapter of a computer. It supersedes
+/// `Win32_NetworkAdapter` and lives in the `root\StandardCimv2` namespace.
+/// 
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/legacy/hh968170(v=vs.85)>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_NetAdapter {
+    /// The network adapter's component ID, i.e. the hardware ID the driver was installed for.
+    pub ComponentID: Option<String>,
+    /// Indicates if a connector is present on the network adapter. This value is set to `TRUE` if
+    /// this is a physical adapter or `FALSE` if this is not a physical adapter.
+    pub ConnectorPresent: Option<bool>,
+    /// The description of the driver of the network adapter.
+    pub DriverDescription: Option<String>,
+    /// Indicates if the network adapter is an endpoint device only and is not the true network
+    /// interface that connects to the network, for example a USB NIC tethered to a phone.
+    pub EndPointInterface: Option<bool>,
+    /// Indicates if the network adapter is a hardware based network interface.
+    pub HardwareInterface: Option<bool>,
+    /// Indicates if the network adapter is hidden from the user.
+    pub Hidden: Option<bool>,
+    /// The description of the network interface.
+    pub InterfaceDescription: Option<String>,
+    /// The GUID of the network interface.
+    pub InterfaceGuid: Option<String>,
+    /// The index of the network interface, equal to the `InterfaceIndex` of `Win32_NetworkAdapter`
+    /// and `Win32_IP4RouteTable`.
+    pub InterfaceIndex: Option<u32>,
+    /// The name of the network interface, as shown to the user.
+    pub InterfaceName: Option<String>,
+    /// The interface type as defined by the Internet Assigned Names Authority (IANA), for example
+    /// 6 for Ethernet, 24 for software loopback, 71 for 802.11 and 131 for tunnels.
+    pub InterfaceType: Option<u32>,
+    /// The current network address of the adapter.
+    pub MacAddress: Option<String>,
+    /// The connect state of the media.
+    /// 
+    /// - `Unknown` (0)
+    /// - `Connected` (1)
+    /// - `Disconnected` (2)
+    pub MediaConnectState: Option<u32>,
+    /// The NDIS media type of the adapter.
+    pub MediaType: Option<u32>,
+    /// Label by which the object is known.
+    pub Name: Option<String>,
+    /// The NDIS physical medium type of the adapter.
+    pub NdisPhysicalMedium: Option<u32>,
+    /// The Plug and Play device instance ID of the adapter.
+    pub PnPDeviceID: Option<String>,
+    /// String that indicates the current status of the object.
+    pub Status: Option<String>,
+    /// Indicates if the network adapter is a virtual adapter.
+    pub Virtual: Option<bool>,
+}
+
+impl MSFT_NetAdapter {
+    /// Classifies the adapter as physical, virtual, VPN or loopback
+    /// 
+    /// The `InterfaceType` is checked first for loopback, PPP and tunnel interfaces, then the
+    /// `PnPDeviceID`, `ComponentID` and `InterfaceDescription` are matched against well known VPN
+    /// drivers, and finally `HardwareInterface`, `Virtual` and the PnP enumerator decide between
+    /// physical and virtual adapters.
+    pub fn class(&self) -> AdapterClass {
+        match self.InterfaceType {
+            Some(24) => return AdapterClass::Loopback,
+            Some(23) | Some(131) => return AdapterClass::Vpn,
+            _ => {}
+        }
+
+        let identifiers = [&self.PnPDeviceID, &self.ComponentID, &self.InterfaceDescription]
+            .iter()
+            .filter_map(|identifier| identifier.as_deref())
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>();
+
+        if identifiers
+            .iter()
+            .any(|identifier| VPN_MARKERS.iter().any(|marker| identifier.contains(marker)))
+        {
+            return AdapterClass::Vpn;
+        }
+
+        let software_enumerated = self.PnPDeviceID.as_deref().is_some_and(|id| {
+            let id = id.to_uppercase();
+            id.starts_with("ROOT\\") || id.starts_with("SWD\\")
+        });
+
+        match (self.HardwareInterface, self.Virtual) {
+            (_, Some(true)) => AdapterClass::Virtual,
+            (Some(true), _) if !software_enumerated => AdapterClass::Physical,
+            (Some(false), _) => AdapterClass::Virtual,
+            _ if software_enumerated => AdapterClass::Virtual,
+            (None, None) => AdapterClass::Unknown,
+            _ => AdapterClass::Physical,
+        }
+    }
+}
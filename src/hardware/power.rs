@@ -10,9 +10,12 @@
 //! | [**Win32\_PowerManagementEvent**](win32-powermanagementevent) | Represents power management events resulting from power state changes.<br/>                     |
 //! | [**Win32\_VoltageProbe**](win32-voltageprobe)                 | Represents the properties of a voltage sensor (electronic voltmeter).<br/>                      |
 
-use crate::update;
+use crate::codes::BatteryStatus;
+use crate::{coded, update, Unavailable};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
+use tokio::sync::mpsc::UnboundedReceiver;
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
 
 /// Represents the state of Windows user's Batteries
@@ -27,10 +30,25 @@ pub struct Batteries {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Batteries, batteries);
 
+impl Batteries {
+    /// Returns `true` if a battery is discharging with less than `threshold` percent of its charge
+    /// remaining, i.e. the machine runs on a battery running low
+    pub fn discharging_below(&self, threshold: u16) -> bool {
+        self.batteries.iter().any(|battery| {
+            matches!(
+                battery.battery_status(),
+                Some(BatteryStatus::Discharging | BatteryStatus::Low | BatteryStatus::Critical)
+            ) && battery.EstimatedChargeRemaining.is_some_and(|charge| charge < threshold)
+        })
+    }
+}
+
 /// Represents the state of Windows user's CurrentProbes
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct CurrentProbes {
@@ -43,6 +61,8 @@ pub struct CurrentProbes {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(CurrentProbes, current_probes);
@@ -59,6 +79,8 @@ pub struct PortableBatteries {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(PortableBatteries, portable_batteries);
@@ -75,10 +97,37 @@ pub struct PowerManagementEvents {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(PowerManagementEvents, power_management_events);
 
+impl PowerManagementEvents {
+    /// Subscribes to suspend, resume and power status change events, see
+    /// [`crate::events::subscribe`]
+    ///
+    /// `Win32_PowerManagementEvent` is an event class, updating this state returns no instances.
+    /// Keep the events received with [`PowerManagementEvents::record`] instead.
+    pub fn subscribe() -> Result<UnboundedReceiver<Win32_PowerManagementEvent>, Unavailable> {
+        crate::events::subscribe(crate::DEFAULT_NAMESPACE, POWER_EVENTS)
+    }
+
+    /// [`PowerManagementEvents::subscribe`] as a [`Stream`]
+    pub fn stream() -> Result<impl Stream<Item = Win32_PowerManagementEvent>, Unavailable> {
+        crate::events::stream(crate::DEFAULT_NAMESPACE, POWER_EVENTS)
+    }
+
+    /// Appends an event received from a subscription, an update clears the events recorded
+    pub fn record(&mut self, event: Win32_PowerManagementEvent) {
+        self.power_management_events.push(event);
+        self.last_updated = SystemTime::now();
+        self.state_change = true;
+    }
+}
+
+const POWER_EVENTS: &str = "SELECT * FROM Win32_PowerManagementEvent";
+
 /// Represents the state of Windows user's VoltageProbes
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct VoltageProbes {
@@ -91,6 +140,8 @@ pub struct VoltageProbes {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(VoltageProbes, voltage_probes);
@@ -312,6 +363,14 @@ pub struct Win32_Battery {
     pub TimeToFullCharge: Option<u32>,
 }
 
+coded!(Win32_Battery {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+    BatteryStatus => battery_status: BatteryStatus,
+    Chemistry => chemistry: Chemistry,
+});
+
 /// The `Win32_CurrentProbe` WMI class represents the properties of a current monitoring sensor (ammeter).
 /// 
 /// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-currentprobe>
@@ -515,6 +574,12 @@ pub struct Win32_CurrentProbe {
     pub UpperThresholdNonCritical: Option<i32>,
 }
 
+coded!(Win32_CurrentProbe {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
 /// The `Win32_PortableBattery` WMI class contains the properties related to a portable battery, 
 /// such as a notebook computer battery.
 /// 
@@ -740,6 +805,14 @@ pub struct Win32_PortableBattery {
     pub TimeToFullCharge: Option<u32>,
 }
 
+coded!(Win32_PortableBattery {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+    BatteryStatus => battery_status: BatteryStatus,
+    Chemistry => chemistry: Chemistry,
+});
+
 /// The `Win32_PowerManagementEvent` WMI class represents power management events resulting from power 
 /// state changes. These state changes are associated with either the Advanced Power Management (APM) 
 /// or the Advanced Configuration and Power Interface (ACPI) system management protocols.
@@ -770,6 +843,10 @@ pub struct Win32_PowerManagementEvent {
     pub OEMEventCode: Option<u16>,
 }
 
+coded!(Win32_PowerManagementEvent {
+    EventType => event_type: PowerEventType,
+});
+
 /// The `Win32_VoltageProbe` WMI class represents the properties of a voltage sensor 
 /// (electronic voltmeter).
 /// 
@@ -11,3 +11,651 @@
 //! | [**Win32\_PrinterSetting**](win32-printersetting)             | Relates a printer and its configuration settings.<br/>                                                                             |
 //! | [**Win32\_PrintJob**](win32-printjob)                         | Represents a print job generated by a Windows-based application.<br/>                                                              |
 //! | [**Win32\_TCPIPPrinterPort**](win32-tcpipprinterport)         | Represents a TCP/IP service access point.<br/>                                                                                     |
+
+use crate::{association, associates, coded, labeled, referenced, update};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection, WMIDateTime};
+
+/// Represents the state of Windows user's Printers
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct Printers {
+    /// Sequence of windows Printers states
+    pub printers: Vec<Win32_Printer>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(Printers, printers);
+
+impl Printers {
+    /// The default printer of the user updating the state, `None` if there is none
+    pub fn default_printer(&self) -> Option<&Win32_Printer> {
+        self.printers.iter().find(|printer| printer.Default == Some(true))
+    }
+}
+
+/// Represents the state of Windows user's PrinterDrivers
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct PrinterDrivers {
+    /// Sequence of windows PrinterDrivers states
+    pub printer_drivers: Vec<Win32_PrinterDriver>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(PrinterDrivers, printer_drivers);
+
+/// Represents the state of Windows user's DriverForDevices
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct DriverForDevices {
+    /// Sequence of windows DriverForDevices states
+    pub driver_for_devices: Vec<Win32_DriverForDevice>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(DriverForDevices, driver_for_devices);
+
+/// Represents the state of Windows user's TCPIPPrinterPorts
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct TCPIPPrinterPorts {
+    /// Sequence of windows TCPIPPrinterPorts states
+    pub tcpip_printer_ports: Vec<Win32_TCPIPPrinterPort>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(TCPIPPrinterPorts, tcpip_printer_ports);
+
+/// Printers with the port and the driver each one uses
+///
+/// Drivers are joined through the `Win32_DriverForDevice` association, ports by the `PortName` of
+/// the printer: only TCP/IP ports are modeled, printers on local ports such as `USB001` or
+/// `FILE:` have no port.
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct PrinterMap {
+    /// Every printer, ordered by name
+    pub printers: Vec<PrinterNode>,
+}
+
+impl PrinterMap {
+    /// Joins the instances held by already updated states
+    pub fn from_states(
+        printers: &Printers,
+        printer_drivers: &PrinterDrivers,
+        driver_for_devices: &DriverForDevices,
+        tcpip_printer_ports: &TCPIPPrinterPorts,
+    ) -> Self {
+        PrinterMap::from_instances(
+            &printers.printers,
+            &printer_drivers.printer_drivers,
+            &driver_for_devices.driver_for_devices,
+            &tcpip_printer_ports.tcpip_printer_ports,
+        )
+    }
+
+    /// Joins the instances of each class
+    pub fn from_instances(
+        printers: &[Win32_Printer],
+        printer_drivers: &[Win32_PrinterDriver],
+        driver_for_devices: &[Win32_DriverForDevice],
+        tcpip_printer_ports: &[Win32_TCPIPPrinterPort],
+    ) -> Self {
+        let driver_links = association::resolve(driver_for_devices, printer_drivers, printers);
+
+        let port_of = |printer: &Win32_Printer| {
+            let port_name = printer.PortName.as_deref()?;
+
+            tcpip_printer_ports
+                .iter()
+                .find(|port| {
+                    port.Name
+                        .as_deref()
+                        .is_some_and(|name| name.eq_ignore_ascii_case(port_name))
+                })
+                .cloned()
+        };
+
+        let mut printers: Vec<PrinterNode> = printers
+            .iter()
+            .map(|printer| PrinterNode {
+                printer: printer.clone(),
+                port: port_of(printer),
+                driver: association::antecedents_of(&driver_links, printer)
+                    .first()
+                    .map(|&driver| driver.clone()),
+            })
+            .collect();
+        printers.sort_by(|a, b| a.printer.Name.cmp(&b.printer.Name));
+
+        PrinterMap { printers }
+    }
+
+    /// The default printer, `None` if there is none
+    pub fn default_printer(&self) -> Option<&PrinterNode> {
+        self.printers
+            .iter()
+            .find(|node| node.printer.Default == Some(true))
+    }
+
+    /// Printers sending raw TCP print jobs to a host outside `known_hosts`, compared case
+    /// insensitively
+    ///
+    /// Raw ports, usually on TCP port 9100, are a common way to exfiltrate documents: a printer
+    /// added by malware or a user can silently copy every job to any listener.
+    pub fn raw_to_unknown_hosts(&self, known_hosts: &[&str]) -> Vec<&PrinterNode> {
+        self.printers
+            .iter()
+            .filter(|node| {
+                node.port.as_ref().is_some_and(|port| {
+                    port.is_raw()
+                        && port.HostAddress.as_deref().is_some_and(|host| {
+                            !known_hosts.iter().any(|known| known.eq_ignore_ascii_case(host))
+                        })
+                })
+            })
+            .collect()
+    }
+}
+
+/// A printer, its port and its driver
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct PrinterNode {
+    /// The printer
+    pub printer: Win32_Printer,
+    /// The TCP/IP port the printer prints to, `None` for other ports
+    pub port: Option<Win32_TCPIPPrinterPort>,
+    /// The driver of the printer, `None` if it was not found
+    pub driver: Option<Win32_PrinterDriver>,
+}
+
+/// The `Win32_Printer` class represents a device connected to a computer running on a Microsoft 
+/// Windows operating system that can produce a printed image or text on paper or other medium.
+/// 
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-printer>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_Printer {
+    /// Bitmap of attribute flags of the printer.
+    /// 
+    /// - `Queued` (0x1): Print jobs are buffered and queued.
+    /// - `Direct` (0x2): Document to be sent directly to the printer.
+    /// - `Default` (0x4): Default printer on a computer.
+    /// - `Shared` (0x8): Available as a shared network resource.
+    /// - `Network` (0x10): Attached to a network.
+    /// - `Hidden` (0x20): Hidden from some users on the network.
+    /// - `Local` (0x40): Directly connected to a computer.
+    /// - `EnableDevQ` (0x80): Enable the queue on the printer if available.
+    /// - `KeepPrintedJobs` (0x100): Spooler should not delete documents after they are printed.
+    /// - `DoCompleteFirst` (0x200): Start jobs that are finished spooling first.
+    /// - `WorkOffline` (0x400): Queue print jobs when a printer is not available.
+    /// - `EnableBIDI` (0x800): Enable bidirectional printing.
+    /// - `RawOnly` (0x1000): Allow only raw data type jobs to be spooled.
+    /// - `Published` (0x2000): Published in the network directory service.
+    pub Attributes: Option<u32>,
+    /// Availability and status of the device.
+    /// 
+    /// - `Other` (1)
+    /// - `Unknown` (2)
+    /// - `Running` / `Full Power` (3): Running or Full Power
+    /// - `Warning` (4)
+    /// - `In Test` (5)
+    /// - `Not Applicable` (6)
+    /// - `Power Off` (7)
+    /// - `Off Line` (8)
+    /// - `Off Duty` (9)
+    /// - `Degraded` (10)
+    /// - `Not Installed` (11)
+    /// - `Install Error` (12)
+    /// - `Power Save - Unknown` (13)
+    /// - `Power Save - Low Power Mode` (14)
+    /// - `Power Save - Standby` (15)
+    /// - `Power Cycle` (16)
+    /// - `Power Save - Warning` (17)
+    /// - `Paused` (18)
+    /// - `Not Ready` (19)
+    /// - `Not Configured` (20)
+    /// - `Quiesced` (21)
+    pub Availability: Option<u16>,
+    /// Array of all the job sheets available on a printer.
+    pub AvailableJobSheets: Option<Vec<String>>,
+    /// Printing rate, in average number of pages per minute, that a printer can produce output.
+    pub AveragePagesPerMinute: Option<u32>,
+    /// Array of printer capabilities, e.g. `Copies` (4), `Color` (2), `Duplex` (3) or
+    /// `Collate` (5).
+    pub Capabilities: Option<Vec<u16>>,
+    /// Array of free-form strings providing more detailed explanations for any of the printer 
+    /// features indicated in the `Capabilities` array.
+    pub CapabilityDescriptions: Option<Vec<String>>,
+    /// Short description of the object.
+    pub Caption: Option<String>,
+    /// Array of available character sets for output.
+    pub CharSetsSupported: Option<Vec<String>>,
+    /// Comment for a print queue.
+    pub Comment: Option<String>,
+    /// Win32 Configuration Manager error code, 0 when the device is working properly and 22 when 
+    /// it is disabled.
+    pub ConfigManagerErrorCode: Option<u32>,
+    /// If `TRUE`, the device is using a user-defined configuration.
+    pub ConfigManagerUserConfig: Option<bool>,
+    /// Name of the first concrete class to appear in the inheritance chain used in the creation 
+    /// of an instance.
+    pub CreationClassName: Option<String>,
+    /// Array of printer capabilities that are being used currently.
+    pub CurrentCapabilities: Option<Vec<u16>>,
+    /// The character set currently used for output.
+    pub CurrentCharSet: Option<String>,
+    /// Printer language currently used.
+    pub CurrentLanguage: Option<u16>,
+    /// Specifies the mime type currently being used by the printer if the `CurrentLanguage` is 
+    /// set to indicate a mime type is in use (value = 47).
+    pub CurrentMimeType: Option<String>,
+    /// Identifies the current language being used by the printer for management.
+    pub CurrentNaturalLanguage: Option<String>,
+    /// Paper type that the printer is currently using.
+    pub CurrentPaperType: Option<String>,
+    /// If `TRUE`, the printer is the default printer.
+    pub Default: Option<bool>,
+    /// Array of the printer capabilities that are used by default.
+    pub DefaultCapabilities: Option<Vec<u16>>,
+    /// Number of copies produced by the printer by default.
+    pub DefaultCopies: Option<u32>,
+    /// Default printer language.
+    pub DefaultLanguage: Option<u16>,
+    /// Default mime type used by the printer.
+    pub DefaultMimeType: Option<String>,
+    /// Number of print-image pages that the printer normally places on one sheet of paper.
+    pub DefaultNumberUp: Option<u32>,
+    /// Paper type that the printer uses by default.
+    pub DefaultPaperType: Option<String>,
+    /// Default priority value assigned to each print job.
+    pub DefaultPriority: Option<u32>,
+    /// Description of the printer.
+    pub Description: Option<String>,
+    /// Printer error information.
+    /// 
+    /// - `Unknown` (0)
+    /// - `Other` (1)
+    /// - `No Error` (2)
+    /// - `Low Paper` (3)
+    /// - `No Paper` (4)
+    /// - `Low Toner` (5)
+    /// - `No Toner` (6)
+    /// - `Door Open` (7)
+    /// - `Jammed` (8)
+    /// - `Offline` (9)
+    /// - `Service Requested` (10)
+    /// - `Output Bin Full` (11)
+    pub DetectedErrorState: Option<u16>,
+    /// Unique identifier of the printer on the system.
+    pub DeviceID: Option<String>,
+    /// If `TRUE`, the print job is sent directly to the printer. If `FALSE`, the print job is 
+    /// spooled.
+    pub Direct: Option<bool>,
+    /// If `TRUE`, the printer starts jobs that are finished spooling. If `FALSE`, the printer 
+    /// starts jobs in the order that the jobs are received.
+    pub DoCompleteFirst: Option<bool>,
+    /// Name of the Windows printer driver.
+    pub DriverName: Option<String>,
+    /// If `TRUE`, the printer can print bidirectionally.
+    pub EnableBIDI: Option<bool>,
+    /// If `TRUE`, the printer holds documents in the queue when document and printer setups do 
+    /// not match.
+    pub EnableDevQueryPrint: Option<bool>,
+    /// If `TRUE`, the error reported in `LastErrorCode` is now cleared.
+    pub ErrorCleared: Option<bool>,
+    /// More information about the error recorded in `LastErrorCode`, and information about any 
+    /// corrective actions that may be taken.
+    pub ErrorDescription: Option<String>,
+    /// Array of supplemental information for the current error state, indicated in 
+    /// `DetectedErrorState`.
+    pub ErrorInformation: Option<Vec<String>>,
+    /// Reports standard error information, `DetectedErrorState` giving more.
+    pub ExtendedDetectedErrorState: Option<u16>,
+    /// Status information for a printer that is different from information specified in the 
+    /// logical device `Availability` property.
+    /// 
+    /// - `Other` (1)
+    /// - `Unknown` (2)
+    /// - `Idle` (3)
+    /// - `Printing` (4)
+    /// - `Warming Up` (5)
+    /// - `Stopped Printing` (6)
+    /// - `Offline` (7)
+    /// - `Paused` (8)
+    /// - `Error` (9)
+    /// - `Busy` (10)
+    /// - `Not Available` (11)
+    /// - `Waiting` (12)
+    /// - `Processing` (13)
+    /// - `Initialization` (14)
+    /// - `Power Save` (15)
+    /// - `Pending Deletion` (16)
+    /// - `I/O Active` (17)
+    /// - `Manual Feed` (18)
+    pub ExtendedPrinterStatus: Option<u16>,
+    /// If `TRUE`, the printer is hidden from network users.
+    pub Hidden: Option<bool>,
+    /// Horizontal resolution of the printer, in pixels per inch.
+    pub HorizontalResolution: Option<u32>,
+    /// Date and time the object was installed. This property does not need a value to indicate 
+    /// that the object is installed.
+    pub InstallDate: Option<WMIDateTime>,
+    /// Printer jobs processed since the last reset.
+    pub JobCountSinceLastReset: Option<u32>,
+    /// If `TRUE`, the print spooler does not delete the completed jobs.
+    pub KeepPrintedJobs: Option<bool>,
+    /// Array of the print languages natively supported.
+    pub LanguagesSupported: Option<Vec<u16>>,
+    /// Last error code reported by the logical device.
+    pub LastErrorCode: Option<u32>,
+    /// If `TRUE`, the printer is not attached to a network.
+    pub Local: Option<bool>,
+    /// Physical location of the printer.
+    /// 
+    /// Example: "Bldg. 38, Room 1164"
+    pub Location: Option<String>,
+    /// Marking technology used by the printer, e.g. `Electrophotographic LED` (3) or 
+    /// `Inkjet Aqueous` (12).
+    pub MarkingTechnology: Option<u16>,
+    /// Maximum number of copies the printer can produce.
+    pub MaxCopies: Option<u32>,
+    /// Maximum number of print-image pages that the printer can print on one sheet.
+    pub MaxNumberUp: Option<u32>,
+    /// Largest job, as a byte stream in kilobytes, that the printer can accept.
+    pub MaxSizeSupported: Option<u32>,
+    /// Array of detailed explanations of the mime types supported by the printer.
+    pub MimeTypesSupported: Option<Vec<String>>,
+    /// Name of the printer.
+    pub Name: Option<String>,
+    /// Array of languages supported by the printer for management.
+    pub NaturalLanguagesSupported: Option<Vec<String>>,
+    /// If `TRUE`, the printer is a network printer.
+    pub Network: Option<bool>,
+    /// Array of paper types that the printer supports.
+    pub PaperSizesSupported: Option<Vec<u16>>,
+    /// Array of paper types that are currently available on the printer.
+    pub PaperTypesAvailable: Option<Vec<String>>,
+    /// Optional parameters for the print processor.
+    pub Parameters: Option<String>,
+    /// Windows Plug and Play device identifier of the logical device.
+    pub PNPDeviceID: Option<String>,
+    /// Port that is used to transmit data to the printer, e.g. `LPT1:` or the name of a 
+    /// [`Win32_TCPIPPrinterPort`].
+    pub PortName: Option<String>,
+    /// Array of the specific power-related capabilities of a logical device.
+    pub PowerManagementCapabilities: Option<Vec<u16>>,
+    /// If `TRUE`, the device can be power-managed.
+    pub PowerManagementSupported: Option<bool>,
+    /// Array of paper sizes supported by the printer.
+    pub PrinterPaperNames: Option<Vec<String>>,
+    /// One of the possible states relating to this printer. This property is obsolete, use 
+    /// `PrinterStatus`.
+    pub PrinterState: Option<u32>,
+    /// Status information for a printer that is different from information specified in the 
+    /// logical device `Availability` property.
+    /// 
+    /// - `Other` (1)
+    /// - `Unknown` (2)
+    /// - `Idle` (3)
+    /// - `Printing` (4)
+    /// - `Warmup` (5)
+    /// - `Stopped Printing` (6)
+    /// - `Offline` (7)
+    pub PrinterStatus: Option<u16>,
+    /// Data type of a print job waiting for the Windows-based printing device.
+    pub PrintJobDataType: Option<String>,
+    /// Name of the print spooler that handles print jobs.
+    /// 
+    /// Example: "WinPrint"
+    pub PrintProcessor: Option<String>,
+    /// Priority of the printer, jobs on a higher priority printer are scheduled first.
+    pub Priority: Option<u32>,
+    /// If `TRUE`, the printer is published in the network directory service.
+    pub Published: Option<bool>,
+    /// If `TRUE`, the printer buffers and queues print jobs.
+    pub Queued: Option<bool>,
+    /// If `TRUE`, the printer accepts only raw data to be spooled.
+    pub RawOnly: Option<bool>,
+    /// Name of the file used to create a separator page.
+    pub SeparatorFile: Option<String>,
+    /// Name of the server that controls the printer. If this string is `NULL`, the printer is 
+    /// controlled locally.
+    pub ServerName: Option<String>,
+    /// If `TRUE`, the printer is available as a shared network resource.
+    pub Shared: Option<bool>,
+    /// Share name of the printer.
+    pub ShareName: Option<String>,
+    /// If `TRUE`, spooling is enabled for the printer. This property is obsolete.
+    pub SpoolEnabled: Option<bool>,
+    /// Date and time that a printer can start to print a job, if the printer is limited to print 
+    /// at specific times.
+    pub StartTime: Option<WMIDateTime>,
+    /// Current status of the object.
+    pub Status: Option<String>,
+    /// State of the logical device.
+    /// 
+    /// - `Other` (1)
+    /// - `Unknown` (2)
+    /// - `Enabled` (3)
+    /// - `Disabled` (4)
+    /// - `Not Applicable` (5)
+    pub StatusInfo: Option<u16>,
+    /// Value of the scoping computer's `CreationClassName` property.
+    pub SystemCreationClassName: Option<String>,
+    /// Name of the scoping system.
+    pub SystemName: Option<String>,
+    /// Date and time the printer was last reset.
+    pub TimeOfLastReset: Option<WMIDateTime>,
+    /// Date and time that a printer can print the last job, if the printer is limited to print 
+    /// at specific times.
+    pub UntilTime: Option<WMIDateTime>,
+    /// Vertical resolution of the printer, in pixels per inch.
+    pub VerticalResolution: Option<u32>,
+    /// If `TRUE`, the printer queues print jobs when it is unavailable.
+    pub WorkOffline: Option<bool>,
+}
+
+coded!(Win32_Printer {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
+labeled!(Win32_Printer {
+    Status => status: DeviceStatus,
+});
+
+referenced!(Win32_Printer { DeviceID });
+
+/// The `Win32_PrinterDriver` WMI class represents the drivers for a [`Win32_Printer`] instance.
+/// 
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-printerdriver>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_PrinterDriver {
+    /// Short description of the object.
+    pub Caption: Option<String>,
+    /// Configuration file for this printer driver.
+    /// 
+    /// Example: "pscrptui.dll"
+    pub ConfigFile: Option<String>,
+    /// Name of the first concrete class to appear in the inheritance chain used in the creation 
+    /// of an instance.
+    pub CreationClassName: Option<String>,
+    /// Data file for this printer driver.
+    /// 
+    /// Example: "qms810.ppd"
+    pub DataFile: Option<String>,
+    /// Default data type for this printer driver.
+    pub DefaultDataType: Option<String>,
+    /// Array of dependent files for this printer driver.
+    pub DependentFiles: Option<Vec<String>>,
+    /// Description of the object.
+    pub Description: Option<String>,
+    /// Path for this printer driver.
+    /// 
+    /// Example: "C:\\drivers\\pscript.dll"
+    pub DriverPath: Option<String>,
+    /// Path to the INF file being used.
+    pub FilePath: Option<String>,
+    /// Help file for this printer driver.
+    pub HelpFile: Option<String>,
+    /// Name of the INF file being used.
+    pub InfName: Option<String>,
+    /// Date and time the object was installed. This property does not need a value to indicate 
+    /// that the object is installed.
+    pub InstallDate: Option<WMIDateTime>,
+    /// Name of the language monitor for this printer driver.
+    /// 
+    /// Example: "PJL monitor"
+    pub MonitorName: Option<String>,
+    /// Name of the printer driver, followed by its version and environment.
+    /// 
+    /// Example: "Microsoft Print To PDF,3,Windows x64"
+    pub Name: Option<String>,
+    /// Manufacturer's website.
+    pub OEMUrl: Option<String>,
+    /// If `TRUE`, the service has been started.
+    pub Started: Option<bool>,
+    /// Start mode of the service, e.g. "Automatic" or "Manual".
+    pub StartMode: Option<String>,
+    /// Current status of the object.
+    pub Status: Option<String>,
+    /// Operating environment that the driver is intended for.
+    /// 
+    /// Example: "Windows x64"
+    pub SupportedPlatform: Option<String>,
+    /// Type name of the system that hosts this service.
+    pub SystemCreationClassName: Option<String>,
+    /// Name of the system that hosts this service.
+    pub SystemName: Option<String>,
+    /// Operating system version for the printer driver.
+    /// 
+    /// - `Win9x` (0)
+    /// - `Win351` (1)
+    /// - `NT40` (2)
+    /// - `Win2k` (3)
+    pub Version: Option<u16>,
+}
+
+labeled!(Win32_PrinterDriver {
+    Status => status: DeviceStatus,
+});
+
+referenced!(Win32_PrinterDriver { Name });
+
+/// The `Win32_DriverForDevice` association WMI class relates a printer to a printer driver.
+/// 
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/cimwin32a/win32-driverfordevice>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_DriverForDevice {
+    /// Object path of the printer driver.
+    pub Antecedent: Option<String>,
+    /// Object path of the printer using the driver.
+    pub Dependent: Option<String>,
+}
+
+associates!(Win32_DriverForDevice { Antecedent => Dependent });
+
+/// The `Win32_TCPIPPrinterPort` WMI class represents a TCP/IP service access point.
+/// 
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-tcpipprinterport>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_TCPIPPrinterPort {
+    /// If `TRUE`, the printer port supports byte counting for LPR ports.
+    pub ByteCount: Option<bool>,
+    /// Short description of the object.
+    pub Caption: Option<String>,
+    /// Name of the first concrete class to appear in the inheritance chain used in the creation 
+    /// of an instance.
+    pub CreationClassName: Option<String>,
+    /// Description of the object.
+    pub Description: Option<String>,
+    /// Address of the device or print server, a host name or an IP address.
+    pub HostAddress: Option<String>,
+    /// Date and time the object was installed. This property does not need a value to indicate 
+    /// that the object is installed.
+    pub InstallDate: Option<WMIDateTime>,
+    /// Name of the port, as referenced by the `PortName` of the printers using it.
+    pub Name: Option<String>,
+    /// Number of the TCP port used by the port monitor to communicate with the device.
+    /// 
+    /// Example: 9100
+    pub PortNumber: Option<u32>,
+    /// Printing protocol used.
+    /// 
+    /// - `RAW` (1): Print directly to a device or print server.
+    /// - `LPR` (2): Legacy protocol, which is eventually replaced by RAW.
+    pub Protocol: Option<u32>,
+    /// Name of the print queue on the server used with the LPR protocol.
+    pub Queue: Option<String>,
+    /// Security level value for the device.
+    /// 
+    /// Example: "public"
+    pub SNMPCommunity: Option<String>,
+    /// SNMP index number of this device for the LPR protocol.
+    pub SNMPDevIndex: Option<u32>,
+    /// If `TRUE`, this printer port is enabled for SNMP.
+    pub SNMPEnabled: Option<bool>,
+    /// Current status of the object.
+    pub Status: Option<String>,
+    /// Type name of the system that hosts this service.
+    pub SystemCreationClassName: Option<String>,
+    /// Name of the system that hosts this service.
+    pub SystemName: Option<String>,
+    /// Type of transport or service access point.
+    /// 
+    /// - `Write` (1)
+    /// - `Read` (2)
+    /// - `Read/Write` (3)
+    pub Type: Option<u32>,
+}
+
+impl Win32_TCPIPPrinterPort {
+    /// Returns `true` if the port sends jobs as raw data, rather than through LPR
+    pub fn is_raw(&self) -> bool {
+        self.Protocol == Some(1)
+    }
+}
+
+labeled!(Win32_TCPIPPrinterPort {
+    Status => status: DeviceStatus,
+});
+
+referenced!(Win32_TCPIPPrinterPort { Name });
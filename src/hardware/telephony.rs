@@ -5,7 +5,8 @@
 //! | [**Win32\_POTSModem**](win32-potsmodem)                         | Represents the services and characteristics of a Plain Old Telephone Service (POTS) modem on a computer system running Windows.<br/> |
 //! | [**Win32\_POTSModemToSerialPort**](win32-potsmodemtoserialport) | Relates a modem and the serial port the modem uses.<br/>                                                                             |
 
-use crate::update;
+use crate::codes::{ConfigManagerErrorCode, StatusInfo};
+use crate::{associates, coded, labeled, referenced, update};
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
@@ -22,10 +23,47 @@ pub struct POTSModems {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(POTSModems, pot_modems);
 
+impl POTSModems {
+    /// Returns `true` if the machine has any modem device, enabled or not
+    pub fn present(&self) -> bool {
+        !self.pot_modems.is_empty()
+    }
+
+    /// Modems that are enabled, able to dial out
+    pub fn enabled(&self) -> Vec<&Win32_POTSModem> {
+        self.pot_modems.iter().filter(|modem| modem.enabled()).collect()
+    }
+
+    /// Returns `true` if any modem is enabled, the dial-up hardware security baselines flag
+    pub fn any_enabled(&self) -> bool {
+        self.pot_modems.iter().any(Win32_POTSModem::enabled)
+    }
+}
+
+/// Represents the state of Windows POTSModemToSerialPorts
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct POTSModemToSerialPorts {
+    /// Sequence of windows POTSModemToSerialPorts states
+    pub pot_modem_to_serial_ports: Vec<Win32_POTSModemToSerialPort>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(POTSModemToSerialPorts, pot_modem_to_serial_ports);
+
 /// The `Win32_POTSModem` WMI class represents the services and characteristics of a Plain Old 
 /// Telephone Service (POTS) modem on a computer system running Windows.
 /// 
@@ -445,4 +483,41 @@ pub struct Win32_POTSModem {
     /// 
     /// Example: "AT+V"
     pub VoiceSwitchFeature: Option<String>,
-}
\ No newline at end of file
+}
+
+impl Win32_POTSModem {
+    /// Returns `true` unless the modem is disabled in the Device Manager or reports itself
+    /// disabled
+    pub fn enabled(&self) -> bool {
+        self.config_manager_error_code() != Some(ConfigManagerErrorCode::Disabled)
+            && self.status_info() != Some(StatusInfo::Disabled)
+    }
+}
+
+coded!(Win32_POTSModem {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
+labeled!(Win32_POTSModem {
+    Status => status: DeviceStatus,
+});
+
+referenced!(Win32_POTSModem { DeviceID });
+
+/// The `Win32_POTSModemToSerialPort` association WMI class relates a modem and the serial port the
+/// modem uses.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-potsmodemtoserialport>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_POTSModemToSerialPort {
+    /// Object path of the serial port the modem uses.
+    pub Antecedent: Option<String>,
+    /// Object path of the modem using the serial port.
+    pub Dependent: Option<String>,
+}
+
+associates!(Win32_POTSModemToSerialPort { Antecedent => Dependent });
@@ -0,0 +1,312 @@
+//! USB devices attached to the machine, and the mass storage devices attached in the past.
+//!
+//! | Source                                                                                  | Description                                                                                                                    |
+//! |-----------------------------------------------------------------------------------------|--------------------------------------------------------------------------------------------------------------------------------|
+//! | [**Win32\_USBControllerDevice**](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-usbcontrollerdevice) | Association class<br/> Relates a USB controller and the devices connected to it, used to select the attached devices. |
+//! | [**Win32\_PnPEntity**](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-pnpentity) | Instance class<br/> Plug and Play devices, joined with the association through their `DeviceID`.                  |
+//! | `USBSTOR` registry keys                                                                 | Every USB mass storage device ever attached, kept by Windows after removal. Read on request, see [`UsbDevices::with_history`]. |
+//!
+//! Vendor and product IDs are parsed from the `VID_xxxx&PID_xxxx` part of the device identifier.
+//! Devices enumerated by another bus driver below a USB device, such as `USBSTOR` disks or `HID`
+//! interfaces, only carry the IDs when their hardware IDs include them.
+
+use crate::hardware::motherboard_controller_port::Win32_PnPEntity;
+use crate::operating_system::registry::{self, Hive, RegistryValue};
+use crate::{associates, state_member, Unavailable, DEFAULT_NAMESPACE};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+/// Key holding a subkey per model of USB mass storage device ever attached
+pub const USBSTOR: &str = "SYSTEM\\CurrentControlSet\\Enum\\USBSTOR";
+
+/// Represents the state of Windows user's UsbDevices
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct UsbDevices {
+    /// Whether the `USBSTOR` registry keys are read on every update
+    pub include_history: bool,
+    /// Sequence of USB devices currently attached
+    pub usb_devices: Vec<UsbDevice>,
+    /// Sequence of USB mass storage devices ever attached, empty unless `include_history` is set
+    pub storage_history: Vec<UsbStorageDevice>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(UsbDevices, usb_devices; storage_history);
+
+impl UsbDevices {
+    /// Creates an empty state that also reads the USB mass storage devices attached in the past
+    pub fn with_history() -> Self {
+        UsbDevices {
+            include_history: true,
+            ..Default::default()
+        }
+    }
+
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let devices = crate::query(DEFAULT_NAMESPACE, "Win32_USBControllerDevice").and_then(
+            |associations| Ok((associations, crate::query(DEFAULT_NAMESPACE, "Win32_PnPEntity")?)),
+        );
+
+        self.refresh(devices);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let devices = match crate::async_query(DEFAULT_NAMESPACE, "Win32_USBControllerDevice").await {
+            Ok(associations) => crate::async_query(DEFAULT_NAMESPACE, "Win32_PnPEntity")
+                .await
+                .map(|entities| (associations, entities)),
+            Err(unavailable) => Err(unavailable),
+        };
+
+        self.refresh(devices);
+    }
+
+    /// Returns the attached devices of the vendor `vendor_id`, e.g. `"0781"`
+    pub fn by_vendor<'a>(&'a self, vendor_id: &'a str) -> impl Iterator<Item = &'a UsbDevice> {
+        self.usb_devices.iter().filter(move |device| {
+            device
+                .vendor_id
+                .as_deref()
+                .is_some_and(|id| id.eq_ignore_ascii_case(vendor_id))
+        })
+    }
+
+    /// The registry is still read when the WMI classes are missing
+    fn refresh(
+        &mut self,
+        devices: Result<(Vec<Win32_USBControllerDevice>, Vec<Win32_PnPEntity>), Unavailable>,
+    ) {
+        let old_devices = std::mem::take(&mut self.usb_devices);
+        let old_history = std::mem::take(&mut self.storage_history);
+        let old_unavailable = self.unavailable.take();
+
+        match devices {
+            Ok((associations, entities)) => {
+                self.usb_devices = collect_devices(&associations, &entities)
+            }
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        if self.include_history {
+            self.storage_history = storage_history(&self.usb_devices);
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.usb_devices.len() != old_devices.len()
+            || crate::hash_vec(&self.usb_devices) != crate::hash_vec(&old_devices)
+            || crate::hash_vec(&self.storage_history) != crate::hash_vec(&old_history);
+    }
+}
+
+impl Default for UsbDevices {
+    /// `include_history` defaults to `false` and `last_updated` to the current `SystemTime`
+    fn default() -> Self {
+        UsbDevices {
+            include_history: false,
+            usb_devices: Default::default(),
+            storage_history: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// The `Win32_USBControllerDevice` association WMI class relates a USB controller and the
+/// `CIM_LogicalDevice` instances connected to it.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-usbcontrollerdevice>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_USBControllerDevice {
+    /// Object path of the USB controller.
+    pub Antecedent: Option<String>,
+    /// Object path of the device connected to the USB controller.
+    pub Dependent: Option<String>,
+}
+
+associates!(Win32_USBControllerDevice { Antecedent => Dependent });
+
+/// A device attached through a USB controller
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct UsbDevice {
+    /// Plug and Play device identifier, e.g. `USB\VID_0781&PID_5567\4C530001230822117211`.
+    pub device_id: String,
+    /// Name of the device, as shown in the Device Manager.
+    pub name: Option<String>,
+    /// Description of the device.
+    pub description: Option<String>,
+    /// Manufacturer of the device, as reported by its driver.
+    pub manufacturer: Option<String>,
+    /// Type of the device, e.g. `USB`, `DiskDrive` or `HIDClass`.
+    pub pnp_class: Option<String>,
+    /// Name of the service driving the device.
+    pub service: Option<String>,
+    /// Status of the device, e.g. "OK", "Error" or "Degraded".
+    pub status: Option<String>,
+    /// USB vendor ID as four hexadecimal digits, e.g. `0781`.
+    pub vendor_id: Option<String>,
+    /// USB product ID as four hexadecimal digits, e.g. `5567`.
+    pub product_id: Option<String>,
+    /// Serial number reported by the device, `None` when Windows generated the instance ID.
+    pub serial_number: Option<String>,
+    /// Plug and Play device identifier of the USB controller the device is attached through.
+    pub controller_id: Option<String>,
+}
+
+/// A USB mass storage device attached at some point, as kept under [`USBSTOR`]
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct UsbStorageDevice {
+    /// Vendor, as reported by the device.
+    pub vendor: Option<String>,
+    /// Product, as reported by the device.
+    pub product: Option<String>,
+    /// Product revision, as reported by the device.
+    pub revision: Option<String>,
+    /// Serial number reported by the device, `None` when Windows generated the instance ID.
+    pub serial_number: Option<String>,
+    /// Name of the device, as shown in the Device Manager.
+    pub friendly_name: Option<String>,
+    /// Instance ID of the device, the subkey name under the model key.
+    pub instance_id: String,
+    /// Whether the device is currently attached.
+    pub attached: bool,
+}
+
+/// Joins the devices the associations point to with their Plug and Play entities
+fn collect_devices(
+    associations: &[Win32_USBControllerDevice],
+    entities: &[Win32_PnPEntity],
+) -> Vec<UsbDevice> {
+    associations
+        .iter()
+        .filter_map(|association| {
+            let device_id = referenced_device_id(association.Dependent.as_deref()?)?;
+            let entity = entities.iter().find(|entity| {
+                entity
+                    .DeviceID
+                    .as_deref()
+                    .is_some_and(|id| id.eq_ignore_ascii_case(&device_id))
+            })?;
+            let (vendor_id, product_id) = std::iter::once(&device_id)
+                .chain(entity.HardwareID.iter().flatten())
+                .find_map(|id| vendor_product(id))
+                .unzip();
+
+            Some(UsbDevice {
+                serial_number: serial_number(&device_id),
+                device_id,
+                name: entity.Name.clone(),
+                description: entity.Description.clone(),
+                manufacturer: entity.Manufacturer.clone(),
+                pnp_class: entity.PNPClass.clone(),
+                service: entity.Service.clone(),
+                status: entity.Status.clone(),
+                vendor_id,
+                product_id,
+                controller_id: association
+                    .Antecedent
+                    .as_deref()
+                    .and_then(referenced_device_id),
+            })
+        })
+        .collect()
+}
+
+/// Reads the `USBSTOR` keys, marking the devices among `attached`
+fn storage_history(attached: &[UsbDevice]) -> Vec<UsbStorageDevice> {
+    let mut devices = Vec::new();
+
+    for model in registry::subkeys(Hive::LocalMachine, USBSTOR) {
+        // e.g. `Disk&Ven_SanDisk&Prod_Cruzer_Blade&Rev_1.00`
+        let field = |prefix: &str| {
+            model
+                .split('&')
+                .find_map(|part| part.strip_prefix(prefix))
+                .map(|value| value.replace('_', " "))
+        };
+
+        for instance_id in registry::subkeys(Hive::LocalMachine, &format!("{USBSTOR}\\{model}")) {
+            let device_id = format!("USBSTOR\\{model}\\{instance_id}");
+
+            devices.push(UsbStorageDevice {
+                vendor: field("Ven_"),
+                product: field("Prod_"),
+                revision: field("Rev_"),
+                serial_number: serial_number(&device_id),
+                friendly_name: registry::read_value(
+                    Hive::LocalMachine,
+                    &format!("{USBSTOR}\\{model}\\{instance_id}"),
+                    "FriendlyName",
+                )
+                .as_ref()
+                .and_then(RegistryValue::as_str)
+                .map(String::from),
+                attached: attached
+                    .iter()
+                    .any(|device| device.device_id.eq_ignore_ascii_case(&device_id)),
+                instance_id,
+            });
+        }
+    }
+
+    devices
+}
+
+/// Extracts the `DeviceID` key of a WMI object path, e.g.
+/// `\\HOST\root\cimv2:Win32_PnPEntity.DeviceID="USB\\VID_0781&PID_5567\\4C53"`
+fn referenced_device_id(path: &str) -> Option<String> {
+    let (_, key) = path.split_once("DeviceID=\"")?;
+
+    Some(key.strip_suffix('"').unwrap_or(key).replace("\\\\", "\\"))
+}
+
+/// Parses the vendor and product IDs out of a `VID_xxxx&PID_xxxx` identifier
+fn vendor_product(id: &str) -> Option<(String, String)> {
+    let id = id.to_ascii_uppercase();
+    let hex = |prefix: &str| {
+        let start = id.find(prefix)? + prefix.len();
+        let value = id.get(start..start + 4)?;
+
+        value
+            .chars()
+            .all(|c| c.is_ascii_hexdigit())
+            .then(|| value.to_string())
+    };
+
+    Some((hex("VID_")?, hex("PID_")?))
+}
+
+/// Serial number held by the instance ID of `device_id`
+///
+/// Windows generates an instance ID with `&` as second character for devices without a serial
+/// number, and appends `&<interface>` to the serial of mass storage devices.
+fn serial_number(device_id: &str) -> Option<String> {
+    let instance_id = device_id.rsplit('\\').next()?;
+
+    if instance_id.is_empty() || instance_id.chars().nth(1) == Some('&') {
+        return None;
+    }
+
+    match instance_id.rsplit_once('&') {
+        Some((serial, interface)) if interface.chars().all(|c| c.is_ascii_digit()) => {
+            Some(serial.to_string())
+        }
+        _ => Some(instance_id.to_string()),
+    }
+}
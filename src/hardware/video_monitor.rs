@@ -7,7 +7,7 @@
 //! | [**Win32\_VideoController**](win32-videocontroller)                               | Represents the capabilities and management capacity of the video controller on a computer system running Windows.<br/>                                                                                                                                                                                                                                                       |
 //! | [**Win32\_VideoSettings**](win32-videosettings)                                   | Relates a video controller and video settings that can be applied to it.<br/>                                                                                                                                                                                                                                                                                                |
 
-use crate::update;
+use crate::{coded, keyed, update};
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
@@ -24,6 +24,8 @@ pub struct DesktopMonitors {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(DesktopMonitors, desktop_monitors);
@@ -40,6 +42,8 @@ pub struct DisplayControllerConfigurations {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(DisplayControllerConfigurations, display_controller_configurations);
@@ -56,9 +60,12 @@ pub struct VideoControllers {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(VideoControllers, video_controllers);
+keyed!(VideoControllers, video_controllers, Win32_VideoController, DeviceID: String);
 
 /// The `Win32_DesktopMonitor` WMI class represents the type of monitor or display device 
 /// attached to the computer system.
@@ -237,6 +244,12 @@ pub struct Win32_DesktopMonitor {
     pub SystemName: Option<String>,
 }
 
+coded!(Win32_DesktopMonitor {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
 /// The `Win32_DisplayControllerConfiguration` WMI class represents the video adapter configuration 
 /// information of a computer system running Windows.
 /// 
@@ -0,0 +1,109 @@
+//! `tracing` instrumentation of the update paths, available with the `tracing` feature.
+//!
+//! Every member updated through [`Windows`](crate::state::Windows) runs in an `update` span
+//! carrying the `member` name, and records the number of `rows` it holds and the `elapsed_ms` it took. A member whose class is
+//! unavailable also emits a warning with the class, namespace and error, downgraded to a debug
+//! event when the host does not provide the class, and every retried query emits a debug event.
+//! Without the feature these functions only run the update.
+
+use crate::state::StateKind;
+use crate::Unavailable;
+use std::future::Future;
+
+/// Outcome of a member update: the number of instances it holds, or why its class is unavailable
+pub(crate) type Outcome = Result<usize, Unavailable>;
+
+/// Runs the synchronous update of the member `kind` in its span
+pub(crate) fn update(kind: StateKind, update: impl FnOnce() -> Outcome) -> Outcome {
+    #[cfg(feature = "tracing")]
+    {
+        let span = span(kind);
+        let start = std::time::Instant::now();
+        let outcome = span.in_scope(update);
+
+        record(&span, &outcome, start);
+        outcome
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = kind;
+        update()
+    }
+}
+
+/// Runs the asynchronous update of the member `kind` in its span
+pub(crate) async fn async_update(kind: StateKind, update: impl Future<Output = Outcome>) -> Outcome {
+    #[cfg(feature = "tracing")]
+    {
+        use tracing::Instrument;
+
+        let span = span(kind);
+        let start = std::time::Instant::now();
+        let outcome = update.instrument(span.clone()).await;
+
+        record(&span, &outcome, start);
+        outcome
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = kind;
+        update.await
+    }
+}
+
+/// Reports that a query that failed with `error` is retried after `delay`
+pub(crate) fn retrying(error: &wmi::WMIError, retry: u32, delay: std::time::Duration) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        error = %error,
+        retry,
+        delay_ms = delay.as_millis() as u64,
+        "retrying query"
+    );
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = (error, retry, delay);
+}
+
+#[cfg(feature = "tracing")]
+fn span(kind: StateKind) -> tracing::Span {
+    tracing::info_span!(
+        "update",
+        member = kind.name(),
+        rows = tracing::field::Empty,
+        elapsed_ms = tracing::field::Empty
+    )
+}
+
+#[cfg(feature = "tracing")]
+fn record(span: &tracing::Span, outcome: &Outcome, start: std::time::Instant) {
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+
+    span.record("elapsed_ms", elapsed_ms);
+
+    match outcome {
+        Ok(rows) => {
+            span.record("rows", *rows as u64);
+            tracing::debug!(parent: span, rows = *rows as u64, elapsed_ms, "updated");
+        }
+        Err(unavailable) if unavailable.unsupported() => tracing::debug!(
+            parent: span,
+            class = %unavailable.class,
+            namespace = %unavailable.namespace,
+            reason = ?unavailable.reason,
+            elapsed_ms,
+            "class unsupported"
+        ),
+        Err(unavailable) => tracing::warn!(
+            parent: span,
+            class = %unavailable.class,
+            namespace = %unavailable.namespace,
+            reason = ?unavailable.reason,
+            error = %unavailable.message,
+            elapsed_ms,
+            "class unavailable"
+        ),
+    }
+}
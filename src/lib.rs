@@ -4,6 +4,26 @@
 //!
 //! Our global state is `windows_snapshot::state::Windows` which stores the states of Windows machine at any given instance.
 //! Each state member can be updated synchronously or asynchronously, alone or alongside other states.
+//! `windows_snapshot::state::Windows::async_update_with_progress` reports each member as it completes.
+//! `windows_snapshot::state::Windows::update_parallel` spreads synchronous updates over threads, without an async runtime.
+//! `windows_snapshot::association::resolve` turns association classes into links between the instances of two states.
+//! `windows_snapshot::availability::AvailabilityMatrix` tells which of the supported WMI classes the host exposes.
+//! `windows_snapshot::drift::Baseline::drift` lists how the state deviates from a baseline snapshot.
+//! `windows_snapshot::events::subscribe` streams WMI events such as power state changes as they happen.
+//! `windows_snapshot::state::Windows::watch` updates the state at an interval and streams only what changed.
+//! `windows_snapshot::snapshot::save_snapshot` saves snapshots as JSON or compact, compressed binary.
+//! `windows_snapshot::codes` types coded properties such as `Availability` or `DriveType`.
+//! `windows_snapshot::raw_query` runs arbitrary WQL for classes the crate does not model, with the same retries.
+//! `windows_snapshot::state::Windows::capture_extra_properties` keeps the properties vendors add beyond what the structs model.
+//! `windows_snapshot::raw_query_batches` hands huge results such as `Win32_NTLogEvent` over in batches instead of one `Vec`.
+//! `windows_snapshot::naming::Renamed` exports any state with snake_case, camelCase or PascalCase field names.
+//! `windows_snapshot::state::Windows::summary` renders a short human-readable report, also used by its `Display`.
+//! `windows_snapshot::method::exec_method` invokes WMI methods such as `GetOwner` and types their output parameters.
+//! `windows_snapshot::redact::Redactor` strips or hashes user names, host names and serial numbers before snapshots are shared.
+//! `windows_snapshot::limits::set_limit` caps or samples the instances kept for classes such as `Win32_Directory`.
+//! `windows_snapshot::custom::CustomState` adds members of your own, backed by any WQL query, to snapshots and diffs.
+//! `windows_snapshot::config::Config::from_env` tunes the selection, concurrency, timeouts and retries of agents from environment variables.
+//! `windows_snapshot::path::SnapshotPath` addresses members, instances and properties by stable paths such as `os.services.services[Name=wuauserv].State`.
 //!
 //! Example:
 //!
@@ -25,10 +45,33 @@
 
 pub use std::collections::hash_map::DefaultHasher;
 pub use std::hash::{Hash, Hasher};
+use serde::{Deserialize, Serialize};
 
+pub mod association;
+pub mod availability;
+pub mod codes;
+pub mod config;
+pub mod custom;
+pub mod drift;
+pub mod events;
+pub mod export;
+pub mod extra;
 pub mod operating_system;
 pub mod hardware;
+mod instrument;
+pub mod limits;
+pub mod method;
+pub mod metrics;
+pub mod naming;
+pub mod path;
+pub mod redact;
+pub mod report;
+pub mod retry;
+pub mod snapshot;
 pub mod state;
+#[cfg(feature = "upload")]
+pub mod upload;
+pub mod watch;
 
 pub use wmi::COMLibrary;
 
@@ -38,28 +81,550 @@ pub fn hash_vec<T: Hash>(vec: &[T]) -> u64 {
     hasher.finish()
 }
 
+/// Expands `%NAME%` environment variables, leaving unknown ones as written
+#[allow(dead_code)] // unused when the categories relying on it are disabled
+pub(crate) fn expand_environment(path: &str) -> String {
+    let mut expanded = String::new();
+    let mut rest = path;
+
+    while let Some(start) = rest.find('%') {
+        let Some(length) = rest[start + 1..].find('%') else {
+            break;
+        };
+        let name = &rest[start + 1..start + 1 + length];
+
+        expanded.push_str(&rest[..start]);
+
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => expanded.push_str(&value),
+            _ => expanded.push_str(&rest[start..start + length + 2]),
+        }
+
+        rest = &rest[start + length + 2..];
+    }
+
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Formats `time` as a UTC CIM datetime (`yyyymmddHHMMSS.mmmmmm+000`), the form WQL compares
+/// datetime properties against
+#[allow(dead_code)] // unused when the categories relying on it are disabled
+pub(crate) fn cim_datetime(time: std::time::SystemTime) -> String {
+    let since_epoch = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (days, seconds_of_day) = ((seconds / 86_400) as i64, seconds % 86_400);
+
+    // Civil from days, http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}{:02}{:02}{:02}.{:06}+000",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60,
+        since_epoch.subsec_micros()
+    )
+}
+
+/// Midnight UTC of the given civil date, `None` for an invalid date or one before the epoch
+#[allow(dead_code)] // unused when the categories relying on it are disabled
+pub(crate) fn civil_date(year: i64, month: u32, day: u32) -> Option<std::time::SystemTime> {
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => return None,
+    };
+
+    if day == 0 || day > days_in_month {
+        return None;
+    }
+
+    // Days from civil, http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (i64::from(month) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(u64::try_from(days).ok()? * 86_400))
+}
+
+/// Namespace states are queried from unless they say otherwise
+pub const DEFAULT_NAMESPACE: &str = "root\\cimv2";
+
+/// Namespace of the `MSFT_Net*` classes
+pub const STANDARD_CIMV2_NAMESPACE: &str = "root\\StandardCimv2";
+
+/// Namespace of the Storage Management API classes
+pub const STORAGE_NAMESPACE: &str = "root\\Microsoft\\Windows\\Storage";
+
+/// Namespace of the classes exposed by kernel drivers
+pub const WMI_NAMESPACE: &str = "root\\wmi";
+
+/// Namespace of the TPM classes
+pub const TPM_NAMESPACE: &str = "root\\cimv2\\Security\\MicrosoftTpm";
+
+/// Namespace of the BitLocker classes
+pub const VOLUME_ENCRYPTION_NAMESPACE: &str = "root\\cimv2\\Security\\MicrosoftVolumeEncryption";
+
+/// Namespace of the Task Scheduler classes
+pub const TASK_SCHEDULER_NAMESPACE: &str = "root\\Microsoft\\Windows\\TaskScheduler";
+
+/// Namespace of the Windows Security Center classes, client editions only
+pub const SECURITY_CENTER2_NAMESPACE: &str = "root\\SecurityCenter2";
+
+/// Namespace of the Resultant Set of Policy logged for the computer by Group Policy
+pub const RSOP_COMPUTER_NAMESPACE: &str = "root\\RSOP\\Computer";
+
+/// Namespace of the Hyper-V classes, present when the Hyper-V role is installed
+pub const VIRTUALIZATION_NAMESPACE: &str = "root\\virtualization\\v2";
+
+/// Records why a state holds no data, so "not collected" can be told apart from an empty result
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Unavailable {
+    /// Namespace that was checked
+    pub namespace: String,
+    /// Class that was queried
+    pub class: String,
+    /// Why the query failed
+    pub reason: UnavailableReason,
+    /// Error reported by WMI
+    pub message: String,
+}
+
+impl Unavailable {
+    /// Classifies the error returned when querying `class` in `namespace`
+    pub fn new(namespace: &str, class: &str, error: &wmi::WMIError) -> Self {
+        let reason = match error {
+            wmi::WMIError::HResultError { hres } => match *hres as u32 {
+                WBEM_E_INVALID_CLASS | WBEM_E_NOT_FOUND => UnavailableReason::InvalidClass,
+                WBEM_E_INVALID_NAMESPACE => UnavailableReason::InvalidNamespace,
+                WBEM_E_ACCESS_DENIED | E_ACCESSDENIED => UnavailableReason::AccessDenied,
+                _ => UnavailableReason::Other,
+            },
+            _ => UnavailableReason::Other,
+        };
+
+        Unavailable {
+            namespace: namespace.to_string(),
+            class: class.to_string(),
+            reason,
+            message: error.to_string(),
+        }
+    }
+
+    /// Classifies the error returned when reading the file at `path`, for states that do not come
+    /// from WMI. `namespace` is left empty and `class` holds the path.
+    pub fn from_io(path: &str, error: &std::io::Error) -> Self {
+        let reason = match error.kind() {
+            std::io::ErrorKind::PermissionDenied => UnavailableReason::AccessDenied,
+            _ => UnavailableReason::Other,
+        };
+
+        Unavailable {
+            namespace: String::new(),
+            class: path.to_string(),
+            reason,
+            message: error.to_string(),
+        }
+    }
+
+    /// Classifies the error returned by the COM interface or Windows function `interface`, for
+    /// states that do not come from WMI. `namespace` is left empty and `class` holds the interface.
+    pub fn from_com(interface: &str, error: &windows::core::Error) -> Self {
+        let reason = match error.code().0 as u32 {
+            E_ACCESSDENIED | E_PRIVILEGE_NOT_HELD => UnavailableReason::AccessDenied,
+            _ => UnavailableReason::Other,
+        };
+
+        Unavailable {
+            namespace: String::new(),
+            class: interface.to_string(),
+            reason,
+            message: error.to_string(),
+        }
+    }
+
+    /// Classifies the failure of the blocking task that read `class`, which panicked or was
+    /// cancelled with its runtime. `namespace` is left empty.
+    pub fn from_join(class: &str, error: &tokio::task::JoinError) -> Self {
+        Unavailable {
+            namespace: String::new(),
+            class: class.to_string(),
+            reason: UnavailableReason::Other,
+            message: error.to_string(),
+        }
+    }
+
+    /// Records that the update of the state `class` was given up after `timeout`. `namespace` is
+    /// left empty.
+    pub fn timed_out(class: &str, timeout: std::time::Duration) -> Self {
+        Unavailable {
+            namespace: String::new(),
+            class: class.to_string(),
+            reason: UnavailableReason::TimedOut,
+            message: format!("gave up after {timeout:?}"),
+        }
+    }
+
+    /// Returns `true` if the host does not provide the class or its namespace, as happens with
+    /// classes missing from some editions, so retrying cannot help
+    pub fn unsupported(&self) -> bool {
+        matches!(
+            self.reason,
+            UnavailableReason::InvalidClass | UnavailableReason::InvalidNamespace
+        )
+    }
+}
+
+/// Why a class could not be queried
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum UnavailableReason {
+    /// The host does not provide the class
+    InvalidClass,
+    /// The host does not provide the namespace
+    InvalidNamespace,
+    /// The caller is not allowed to query the class
+    AccessDenied,
+    /// The update took longer than it was allowed to
+    TimedOut,
+    /// Any other failure
+    Other,
+}
+
+const WBEM_E_NOT_FOUND: u32 = 0x8004_1002;
+const WBEM_E_ACCESS_DENIED: u32 = 0x8004_1003;
+const WBEM_E_INVALID_NAMESPACE: u32 = 0x8004_100E;
+const WBEM_E_INVALID_CLASS: u32 = 0x8004_1010;
+const E_ACCESSDENIED: u32 = 0x8007_0005;
+/// `ERROR_PRIVILEGE_NOT_HELD` as an `HRESULT`
+const E_PRIVILEGE_NOT_HELD: u32 = 0x8007_0522;
+
+/// Name of the class held by a state field, e.g. `Win32_Process` for `Vec<Win32_Process>`
+pub fn class_name<T>(_instances: &[T]) -> &'static str {
+    let name = std::any::type_name::<T>();
+
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Quotes `value` as a WQL string literal
+#[allow(dead_code)] // unused when the categories relying on it are disabled
+pub(crate) fn wql_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Queries every instance of `class` in `namespace`, retried following the [`retry`] policy
+#[allow(dead_code)] // unused when the categories relying on it are disabled
+pub(crate) fn query<T: serde::de::DeserializeOwned>(
+    namespace: &str,
+    class: &str,
+) -> Result<Vec<T>, Unavailable> {
+    let com_con = unsafe { wmi::COMLibrary::assume_initialized() };
+
+    retry::policy()
+        .run(|| {
+            wmi::WMIConnection::with_namespace_path(namespace, com_con)
+                .and_then(|wmi_con| wmi_con.query())
+        })
+        .map_err(|e| Unavailable::new(namespace, class, &e))
+}
+
+/// Runs `read` on a blocking thread of the runtime with the COM library initialised, for the
+/// asynchronous updates of states read from the registry, files or Windows functions, which have
+/// no asynchronous form
+///
+/// A task that panicked is classified as an [`Unavailable`] naming `class`.
+#[allow(dead_code)] // unused when the categories relying on it are disabled
+pub(crate) async fn blocking<T: Send + 'static>(
+    class: &str,
+    read: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, Unavailable> {
+    tokio::task::spawn_blocking(move || {
+        let _com_con = wmi::COMLibrary::without_security();
+        read()
+    })
+    .await
+    .map_err(|e| Unavailable::from_join(class, &e))
+}
+
+/// Asynchronous [`query`]
+#[allow(dead_code)] // unused when the categories relying on it are disabled
+pub(crate) async fn async_query<T: serde::de::DeserializeOwned>(
+    namespace: &str,
+    class: &str,
+) -> Result<Vec<T>, Unavailable> {
+    let com_con = unsafe { wmi::COMLibrary::assume_initialized() };
+
+    retry::policy()
+        .run_async(|| async move {
+            match wmi::WMIConnection::with_namespace_path(namespace, com_con) {
+                Ok(wmi_con) => wmi_con.async_query().await,
+                Err(e) => Err(e),
+            }
+        })
+        .await
+        .map_err(|e| Unavailable::new(namespace, class, &e))
+}
+
+/// Runs the WQL query `wql` in [`DEFAULT_NAMESPACE`], for classes the crate does not model yet
+///
+/// The query goes through the same connection handling as the states: the COM library must be
+/// initialised on the calling thread, transient failures are retried following the [`retry`]
+/// policy, and a failure is classified as an [`Unavailable`] naming the class after `FROM`, or the
+/// type `T` when the query has none.
+///
+/// ```rust,no_run
+/// use serde::Deserialize;
+/// use windows_snapshot::COMLibrary;
+///
+/// #[derive(Deserialize, Debug)]
+/// #[allow(non_snake_case, non_camel_case_types)]
+/// struct Win32_PortConnector {
+///     Tag: Option<String>,
+///     ExternalReferenceDesignator: Option<String>,
+/// }
+///
+/// let _com_con = COMLibrary::new().unwrap(); // initialise security context
+///
+/// let connectors: Vec<Win32_PortConnector> =
+///     windows_snapshot::raw_query("SELECT Tag, ExternalReferenceDesignator FROM Win32_PortConnector")
+///         .unwrap();
+/// ```
+pub fn raw_query<T: serde::de::DeserializeOwned>(wql: &str) -> Result<Vec<T>, Unavailable> {
+    raw_query_in(DEFAULT_NAMESPACE, wql)
+}
+
+/// Asynchronous [`raw_query`]
+pub async fn async_raw_query<T: serde::de::DeserializeOwned>(wql: &str) -> Result<Vec<T>, Unavailable> {
+    async_raw_query_in(DEFAULT_NAMESPACE, wql).await
+}
+
+/// [`raw_query`] in `namespace`, e.g. [`STANDARD_CIMV2_NAMESPACE`]
+pub fn raw_query_in<T: serde::de::DeserializeOwned>(
+    namespace: &str,
+    wql: &str,
+) -> Result<Vec<T>, Unavailable> {
+    let com_con = unsafe { wmi::COMLibrary::assume_initialized() };
+
+    retry::policy()
+        .run(|| {
+            wmi::WMIConnection::with_namespace_path(namespace, com_con)
+                .and_then(|wmi_con| wmi_con.raw_query(wql))
+        })
+        .map_err(|e| Unavailable::new(namespace, &queried_class::<T>(wql), &e))
+}
+
+/// Asynchronous [`raw_query_in`]
+pub async fn async_raw_query_in<T: serde::de::DeserializeOwned>(
+    namespace: &str,
+    wql: &str,
+) -> Result<Vec<T>, Unavailable> {
+    let com_con = unsafe { wmi::COMLibrary::assume_initialized() };
+
+    retry::policy()
+        .run_async(|| async move {
+            match wmi::WMIConnection::with_namespace_path(namespace, com_con) {
+                Ok(wmi_con) => wmi_con.async_raw_query(wql).await,
+                Err(e) => Err(e),
+            }
+        })
+        .await
+        .map_err(|e| Unavailable::new(namespace, &queried_class::<T>(wql), &e))
+}
+
+/// Class named after `FROM` in `wql`, or the name of the type `T` for queries without one such as
+/// `REFERENCES OF`
+fn queried_class<T>(wql: &str) -> String {
+    let mut words = wql.split_whitespace();
+
+    words
+        .by_ref()
+        .find(|word| word.eq_ignore_ascii_case("FROM"))
+        .and_then(|_| words.next())
+        .map(String::from)
+        .unwrap_or_else(|| class_name::<T>(&[]).to_string())
+}
+
+/// Runs `wql` in [`DEFAULT_NAMESPACE`] and hands the instances to `each` in batches of at most
+/// `batch_size`, so a result of hundreds of thousands of instances is never held at once
+///
+/// Connecting is retried following the [`retry`] policy. A failure while enumerating the instances
+/// ends the query, after the batches before it were handed over.
+///
+/// ```rust,no_run
+/// use windows_snapshot::operating_system::event_log::Win32_NTLogEvent;
+/// use windows_snapshot::COMLibrary;
+///
+/// let _com_con = COMLibrary::new().unwrap(); // initialise security context
+///
+/// let mut errors = 0;
+///
+/// windows_snapshot::raw_query_batches(
+///     "SELECT * FROM Win32_NTLogEvent WHERE EventType = 1",
+///     1_000,
+///     |events: Vec<Win32_NTLogEvent>| errors += events.len(),
+/// )
+/// .unwrap();
+/// ```
+pub fn raw_query_batches<T: serde::de::DeserializeOwned>(
+    wql: &str,
+    batch_size: usize,
+    each: impl FnMut(Vec<T>),
+) -> Result<(), Unavailable> {
+    raw_query_batches_in(DEFAULT_NAMESPACE, wql, batch_size, each)
+}
+
+/// Asynchronous [`raw_query_batches`]
+pub async fn async_raw_query_batches<T: serde::de::DeserializeOwned>(
+    wql: &str,
+    batch_size: usize,
+    each: impl FnMut(Vec<T>),
+) -> Result<(), Unavailable> {
+    async_raw_query_batches_in(DEFAULT_NAMESPACE, wql, batch_size, each).await
+}
+
+/// [`raw_query_batches`] in `namespace`
+pub fn raw_query_batches_in<T: serde::de::DeserializeOwned>(
+    namespace: &str,
+    wql: &str,
+    batch_size: usize,
+    mut each: impl FnMut(Vec<T>),
+) -> Result<(), Unavailable> {
+    let com_con = unsafe { wmi::COMLibrary::assume_initialized() };
+    let unavailable = |e: wmi::WMIError| Unavailable::new(namespace, &queried_class::<T>(wql), &e);
+    let batch_size = batch_size.max(1);
+
+    let wmi_con = retry::policy()
+        .run(|| wmi::WMIConnection::with_namespace_path(namespace, com_con))
+        .map_err(unavailable)?;
+
+    let mut batch = Vec::with_capacity(batch_size);
+
+    for instance in wmi_con.exec_query_native_wrapper(wql).map_err(unavailable)? {
+        batch.push(instance.and_then(|instance| instance.into_desr()).map_err(unavailable)?);
+
+        if batch.len() == batch_size {
+            each(std::mem::replace(&mut batch, Vec::with_capacity(batch_size)));
+        }
+    }
+
+    if !batch.is_empty() {
+        each(batch);
+    }
+
+    Ok(())
+}
+
+/// Asynchronous [`raw_query_batches_in`]
+pub async fn async_raw_query_batches_in<T: serde::de::DeserializeOwned>(
+    namespace: &str,
+    wql: &str,
+    batch_size: usize,
+    mut each: impl FnMut(Vec<T>),
+) -> Result<(), Unavailable> {
+    use futures::StreamExt;
+
+    let com_con = unsafe { wmi::COMLibrary::assume_initialized() };
+    let unavailable = |e: wmi::WMIError| Unavailable::new(namespace, &queried_class::<T>(wql), &e);
+
+    let wmi_con = retry::policy()
+        .run(|| wmi::WMIConnection::with_namespace_path(namespace, com_con))
+        .map_err(unavailable)?;
+
+    let mut batches = Box::pin(
+        wmi_con
+            .exec_query_async_native_wrapper(wql)
+            .map_err(unavailable)?
+            .chunks(batch_size.max(1)),
+    );
+
+    while let Some(batch) = batches.next().await {
+        each(
+            batch
+                .into_iter()
+                .map(|instance| instance.and_then(|instance| instance.into_desr()))
+                .collect::<Result<Vec<T>, _>>()
+                .map_err(unavailable)?,
+        );
+    }
+
+    Ok(())
+}
+
 /// Macro to automatically make `update` and `async_update` for a given state field
+///
+/// A class the host does not provide is recorded in the `unavailable` field of the state instead
+/// of failing the update. Transient failures are retried first, see [`retry`].
+///
+/// Classes are queried from [`DEFAULT_NAMESPACE`] unless the state declares its namespace, e.g.
+/// `update!(FailurePredictStatuses, failure_predict_statuses, WMI_NAMESPACE)`. Fields of the state
+/// besides the usual ones are listed after a semicolon and default to their `Default`, e.g.
+/// `update!(Fans, fans; speed_stats)`. A method named after `=>` is called once the instances are
+/// replaced, with the instances and the `last_updated` of the previous update, e.g.
+/// `update!(Processes, processes; deltas => record_deltas)`.
 #[macro_export]
 macro_rules! update {
-    ($struct_name: ident, $struct_field: ident) => {
+    ($struct_name: ident, $struct_field: ident $(; $($extra_field: ident),+)? $(=> $hook: ident)?) => {
+        $crate::update!($struct_name, $struct_field, $crate::DEFAULT_NAMESPACE $(; $($extra_field),+)? $(=> $hook)?);
+    };
+    ($struct_name: ident, $struct_field: ident, $namespace: expr $(; $($extra_field: ident),+)? $(=> $hook: ident)?) => {
         impl $struct_name {
             /// Update fields synchronously
             pub fn update(&mut self) {
                 let com_con = unsafe { COMLibrary::assume_initialized() };
 
-                let wmi_con = WMIConnection::new(com_con).unwrap();
+                let _previous_updated = std::mem::replace(&mut self.last_updated, SystemTime::now());
+
+                let result = $crate::retry::policy().run(|| {
+                    WMIConnection::with_namespace_path($namespace, com_con)
+                        .and_then(|wmi_con| $crate::limits::query(&wmi_con))
+                });
+
+                match result {
+                    Ok(instances) => {
+                        let old_vec = std::mem::replace(&mut self.$struct_field, instances);
 
-                self.last_updated = SystemTime::now();
-                
-                let old_vec = self.$struct_field.clone();
-                self.$struct_field = wmi_con.query().unwrap();
+                        if (self.$struct_field.len() != old_vec.len()) {
+                            self.state_change = true;
+                        } else if (crate::hash_vec(&(self.$struct_field)) != crate::hash_vec(&old_vec)) {
+                            self.state_change = true;
+                        } else {
+                            self.state_change = self.unavailable.is_some();
+                        }
 
-                if(self.$struct_field.len() != old_vec.len()) {
-                    self.state_change = true;
-                } else if (crate::hash_vec(&(self.$struct_field)) != crate::hash_vec(&old_vec)) {
-                    self.state_change = true;
-                } else {
-                    self.state_change = false;
+                        self.unavailable = None;
+                        $(self.$hook(&old_vec, _previous_updated);)?
+                    }
+                    Err(e) => {
+                        let unavailable = Some(crate::Unavailable::new(
+                            $namespace,
+                            crate::class_name(&self.$struct_field),
+                            &e,
+                        ));
+
+                        self.state_change = !self.$struct_field.is_empty() || self.unavailable != unavailable;
+                        let _old_vec = std::mem::take(&mut self.$struct_field);
+                        self.unavailable = unavailable;
+                        $(self.$hook(&_old_vec, _previous_updated);)?
+                    }
                 }
             }
 
@@ -67,21 +632,60 @@ macro_rules! update {
             pub async fn async_update(&mut self) {
                 let com_con = unsafe { COMLibrary::assume_initialized() };
 
-                let wmi_con = WMIConnection::new(com_con).unwrap();
+                let _previous_updated = std::mem::replace(&mut self.last_updated, SystemTime::now());
+
+                let result = $crate::retry::policy()
+                    .run_async(|| async move {
+                        match WMIConnection::with_namespace_path($namespace, com_con) {
+                            Ok(wmi_con) => $crate::limits::async_query(&wmi_con).await,
+                            Err(e) => Err(e),
+                        }
+                    })
+                    .await;
+
+                match result {
+                    Ok(instances) => {
+                        let old_vec = std::mem::replace(&mut self.$struct_field, instances);
 
-                self.last_updated = SystemTime::now();
+                        if (self.$struct_field.len() != old_vec.len()) {
+                            self.state_change = true;
+                        } else if (crate::hash_vec(&(self.$struct_field)) != crate::hash_vec(&old_vec)) {
+                            self.state_change = true;
+                        } else {
+                            self.state_change = self.unavailable.is_some();
+                        }
 
-                let old_vec = self.$struct_field.clone();
-                self.$struct_field = wmi_con.async_query().await.unwrap();
+                        self.unavailable = None;
+                        $(self.$hook(&old_vec, _previous_updated);)?
+                    }
+                    Err(e) => {
+                        let unavailable = Some(crate::Unavailable::new(
+                            $namespace,
+                            crate::class_name(&self.$struct_field),
+                            &e,
+                        ));
 
-                if (self.$struct_field.len() != old_vec.len()) {
-                    self.state_change = true;
-                } else if (crate::hash_vec(&(self.$struct_field)) != crate::hash_vec(&old_vec)) {
-                    self.state_change = true;
-                } else {
-                    self.state_change = false;
+                        self.state_change = !self.$struct_field.is_empty() || self.unavailable != unavailable;
+                        let _old_vec = std::mem::take(&mut self.$struct_field);
+                        self.unavailable = unavailable;
+                        $(self.$hook(&_old_vec, _previous_updated);)?
+                    }
                 }
             }
+
+            /// Writes the collection as CSV to the file at `path`
+            pub fn export_csv(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+                $crate::export::write_csv(&self.$struct_field, path)
+            }
+
+            /// Writes the collection as a Parquet file at `path`
+            #[cfg(feature = "parquet")]
+            pub fn export_parquet(
+                &self,
+                path: impl AsRef<std::path::Path>,
+            ) -> Result<(), $crate::export::ExportError> {
+                $crate::export::write_parquet(&self.$struct_field, path)
+            }
         }
 
         impl Default for $struct_name {
@@ -91,8 +695,141 @@ macro_rules! update {
                     $struct_field: Default::default(),
                     last_updated: SystemTime::now(),
                     state_change: false,
+                    unavailable: None,
+                    $($($extra_field: Default::default(),)+)?
                 }
             }
         }
+
+        $crate::state_member!($struct_name, $struct_field $(; $($extra_field),+)?);
+    };
+}
+
+/// Macro to implement [`StateMember`](state::StateMember) for a state from the field holding its
+/// instances, e.g. `state_member!(HostsFile, hosts_entries)`, states made with [`update!`] already
+/// implement it. Fields holding data derived from the instances, which a merge moves along with
+/// them, are listed after a semicolon, e.g. `state_member!(Drivers, drivers; driver_files)`.
+#[macro_export]
+macro_rules! state_member {
+    ($struct_name: ident, $struct_field: ident $(; $($data_field: ident),+)?) => {
+        impl $crate::state::StateMember for $struct_name {
+            fn name(&self) -> &'static str {
+                stringify!($struct_field)
+            }
+
+            fn class(&self) -> &'static str {
+                $crate::class_name(&self.$struct_field)
+            }
+
+            fn category(&self) -> &'static str {
+                match module_path!().contains("::hardware::") {
+                    true => "hw",
+                    false => "os",
+                }
+            }
+
+            fn last_updated(&self) -> SystemTime {
+                self.last_updated
+            }
+
+            fn state_change(&self) -> bool {
+                self.state_change
+            }
+
+            fn unavailable(&self) -> Option<&$crate::Unavailable> {
+                self.unavailable.as_ref()
+            }
+
+            fn len(&self) -> usize {
+                self.$struct_field.len()
+            }
+
+            fn to_json(&self) -> serde_json::Value {
+                serde_json::to_value(self).unwrap_or_default()
+            }
+
+            fn merge_from(&mut self, partial: &mut Self) {
+                self.$struct_field = std::mem::take(&mut partial.$struct_field);
+                $($(self.$data_field = std::mem::take(&mut partial.$data_field);)+)?
+                self.last_updated = partial.last_updated;
+                self.state_change = partial.state_change;
+                self.unavailable = partial.unavailable.take();
+            }
+        }
+    };
+}
+
+/// Macro to make `by_key` and `get_by_key` for a given state field, indexing its instances by the
+/// natural key of their class (e.g. processes by `ProcessId`, services by `Name`)
+#[macro_export]
+macro_rules! keyed {
+    ($struct_name: ident, $struct_field: ident, $class: ty, $key_field: ident: $key_type: ty) => {
+        impl $struct_name {
+            /// Returns the instances keyed by their natural key, instances without a key are left out
+            pub fn by_key(&self) -> std::collections::HashMap<$key_type, &$class> {
+                self.$struct_field
+                    .iter()
+                    .filter_map(|instance| Some((instance.$key_field.clone()?, instance)))
+                    .collect()
+            }
+
+            /// Returns the instance whose natural key is `key`
+            pub fn get_by_key<Q>(&self, key: &Q) -> Option<&$class>
+            where
+                $key_type: std::borrow::Borrow<Q>,
+                Q: PartialEq + ?Sized,
+            {
+                use std::borrow::Borrow;
+
+                self.$struct_field.iter().find(|instance| {
+                    instance
+                        .$key_field
+                        .as_ref()
+                        .is_some_and(|instance_key| instance_key.borrow() == key)
+                })
+            }
+        }
+    };
+}
+
+/// Macro to make `for_each_batch` and `async_for_each_batch` for a state whose class can hold too
+/// many instances to collect at once, e.g. `batched!(Directories, Win32_Directory)`
+///
+/// The batches are handed to the caller and never stored in the state.
+#[macro_export]
+macro_rules! batched {
+    ($struct_name: ident, $class: ty) => {
+        $crate::batched!($struct_name, $class, $crate::DEFAULT_NAMESPACE);
+    };
+    ($struct_name: ident, $class: ty, $namespace: expr) => {
+        impl $struct_name {
+            /// Synchronously hands every instance to `each`, in batches of at most `batch_size`,
+            /// see [`raw_query_batches`]($crate::raw_query_batches)
+            pub fn for_each_batch(
+                batch_size: usize,
+                each: impl FnMut(Vec<$class>),
+            ) -> Result<(), $crate::Unavailable> {
+                $crate::raw_query_batches_in(
+                    $namespace,
+                    &format!("SELECT * FROM {}", $crate::class_name::<$class>(&[])),
+                    batch_size,
+                    each,
+                )
+            }
+
+            /// Asynchronously hands every instance to `each`, in batches of at most `batch_size`
+            pub async fn async_for_each_batch(
+                batch_size: usize,
+                each: impl FnMut(Vec<$class>),
+            ) -> Result<(), $crate::Unavailable> {
+                $crate::async_raw_query_batches_in(
+                    $namespace,
+                    &format!("SELECT * FROM {}", $crate::class_name::<$class>(&[])),
+                    batch_size,
+                    each,
+                )
+                .await
+            }
+        }
     };
 }
@@ -0,0 +1,314 @@
+//! Capping the number of instances a state holds.
+//!
+//! Some classes hold an unbounded number of instances: `Win32_Directory` on a file server or
+//! `Win32_NTLogEvent` on a busy domain controller can exhaust the memory of the process updating
+//! them. A [`RowLimit`] set for a class with [`set_limit`] bounds the instances kept by the
+//! `update` and `async_update` methods generated by [`update!`](crate::update), either the first
+//! ones WMI returns or a [`Sampling::Spread`] sample of all of them.
+//!
+//! Limits are process-wide like the [`retry`](crate::retry) policy. Whether the last update of a
+//! class was truncated is reported by [`truncation`], or by
+//! [`StateMember::truncation`](crate::state::StateMember::truncation) for a state.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::limits::{set_limit, RowLimit};
+//! use windows_snapshot::state::StateMember;
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! set_limit("Win32_Directory", RowLimit::new(10_000));
+//! set_limit("Win32_NTLogEvent", RowLimit::new(5_000).sampled());
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.directories.update();
+//!
+//! if let Some(truncation) = k.directories.truncation() {
+//!     println!("kept {} directories of {:?}", truncation.kept, truncation.total);
+//! }
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use wmi::{WMIConnection, WMIResult};
+
+static LIMITS: RwLock<Option<HashMap<String, RowLimit>>> = RwLock::new(None);
+
+static TRUNCATIONS: RwLock<Option<HashMap<String, Truncation>>> = RwLock::new(None);
+
+/// Which instances a [`RowLimit`] keeps
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum Sampling {
+    /// The first instances WMI returns, the rest are never read
+    #[default]
+    First,
+    /// Instances spread evenly over all of them, which are all read. Between half of `max_rows`
+    /// and `max_rows` instances are kept.
+    Spread,
+}
+
+/// Bound on the instances kept for a class
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct RowLimit {
+    /// Number of instances kept at most
+    pub max_rows: usize,
+    /// Which instances are kept
+    pub sampling: Sampling,
+}
+
+impl RowLimit {
+    /// Keeps the first `max_rows` instances
+    pub fn new(max_rows: usize) -> Self {
+        RowLimit {
+            max_rows,
+            sampling: Sampling::First,
+        }
+    }
+
+    /// Keeps instances spread over all of them instead of the first ones
+    pub fn sampled(mut self) -> Self {
+        self.sampling = Sampling::Spread;
+        self
+    }
+}
+
+/// How the last update of a class was truncated
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Truncation {
+    /// Number of instances kept
+    pub kept: usize,
+    /// Number of instances of the class, `None` when the query stopped at the limit
+    pub total: Option<usize>,
+}
+
+/// Bounds the instances kept for `class`, e.g. `Win32_Directory`, replacing its previous limit
+pub fn set_limit(class: &str, limit: RowLimit) {
+    if let Ok(mut limits) = LIMITS.write() {
+        limits.get_or_insert_with(HashMap::new).insert(class.to_string(), limit);
+    }
+}
+
+/// Keeps every instance of `class` again
+pub fn clear_limit(class: &str) {
+    if let Ok(mut limits) = LIMITS.write() {
+        limits.get_or_insert_with(HashMap::new).remove(class);
+    }
+}
+
+/// Limit set for `class`, `None` if its instances are all kept
+pub fn limit(class: &str) -> Option<RowLimit> {
+    LIMITS.read().ok()?.as_ref()?.get(class).copied()
+}
+
+/// How the last update of `class` was truncated, `None` if it kept every instance
+pub fn truncation(class: &str) -> Option<Truncation> {
+    TRUNCATIONS.read().ok()?.as_ref()?.get(class).copied()
+}
+
+/// Records how the last update of `class` was truncated
+fn record(class: &str, truncation: Option<Truncation>) {
+    if let Ok(mut truncations) = TRUNCATIONS.write() {
+        let truncations = truncations.get_or_insert_with(HashMap::new);
+
+        match truncation {
+            Some(truncation) => truncations.insert(class.to_string(), truncation),
+            None => truncations.remove(class),
+        };
+    }
+}
+
+/// Keeps the instances offered to it following a [`RowLimit`]
+struct Sampler<T> {
+    limit: RowLimit,
+    rows: Vec<T>,
+    /// Every `stride`th instance is kept while spreading
+    stride: usize,
+    seen: usize,
+}
+
+impl<T> Sampler<T> {
+    fn new(limit: RowLimit) -> Self {
+        Sampler {
+            limit: RowLimit {
+                max_rows: limit.max_rows.max(1),
+                ..limit
+            },
+            rows: Vec::new(),
+            stride: 1,
+            seen: 0,
+        }
+    }
+
+    /// Returns `true` if the instance about to be offered should be read
+    fn wants(&self) -> bool {
+        self.seen.is_multiple_of(self.stride)
+    }
+
+    /// Returns `false` once no further instance can be kept, the query can then stop
+    fn offer(&mut self, instance: Option<T>) -> bool {
+        self.seen += 1;
+        self.rows.extend(instance);
+
+        if self.rows.len() > self.limit.max_rows {
+            match self.limit.sampling {
+                Sampling::First => {
+                    self.rows.truncate(self.limit.max_rows);
+                    return false;
+                }
+                Sampling::Spread => {
+                    self.rows = std::mem::take(&mut self.rows).into_iter().step_by(2).collect();
+                    self.stride *= 2;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// The instances kept and how they were truncated, having stopped early when `complete` is
+    /// `false`
+    fn finish(self, complete: bool) -> (Vec<T>, Option<Truncation>) {
+        let truncation = (!complete || self.rows.len() < self.seen).then_some(Truncation {
+            kept: self.rows.len(),
+            total: complete.then_some(self.seen),
+        });
+
+        (self.rows, truncation)
+    }
+}
+
+/// Queries every instance of the class `T`, keeping those its [`RowLimit`] says
+pub(crate) fn query<T: DeserializeOwned>(wmi_con: &WMIConnection) -> WMIResult<Vec<T>> {
+    let class = crate::class_name::<T>(&[]);
+
+    let Some(limit) = limit(class) else {
+        record(class, None);
+        return wmi_con.query();
+    };
+
+    let mut sampler = Sampler::new(limit);
+    let mut complete = true;
+
+    for instance in wmi_con.exec_query_native_wrapper(format!("SELECT * FROM {class}"))? {
+        let instance = if sampler.wants() {
+            Some(instance?.into_desr()?)
+        } else {
+            None
+        };
+
+        if !sampler.offer(instance) {
+            complete = false;
+            break;
+        }
+    }
+
+    let (rows, truncation) = sampler.finish(complete);
+    record(class, truncation);
+
+    Ok(rows)
+}
+
+/// Asynchronous [`query`]
+pub(crate) async fn async_query<T: DeserializeOwned>(wmi_con: &WMIConnection) -> WMIResult<Vec<T>> {
+    use futures::StreamExt;
+
+    let class = crate::class_name::<T>(&[]);
+
+    let Some(limit) = limit(class) else {
+        record(class, None);
+        return wmi_con.async_query().await;
+    };
+
+    let mut sampler = Sampler::new(limit);
+    let mut complete = true;
+    let mut instances =
+        Box::pin(wmi_con.exec_query_async_native_wrapper(format!("SELECT * FROM {class}"))?);
+
+    while let Some(instance) = instances.next().await {
+        let instance = if sampler.wants() {
+            Some(instance?.into_desr()?)
+        } else {
+            None
+        };
+
+        if !sampler.offer(instance) {
+            complete = false;
+            break;
+        }
+    }
+
+    let (rows, truncation) = sampler.finish(complete);
+    record(class, truncation);
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Offers the instances `0..len` to a sampler following `limit`, as [`query`] does
+    fn sample(limit: RowLimit, len: usize) -> (Vec<usize>, Option<Truncation>) {
+        let mut sampler = Sampler::new(limit);
+        let mut complete = true;
+
+        for instance in 0..len {
+            let instance = sampler.wants().then_some(instance);
+
+            if !sampler.offer(instance) {
+                complete = false;
+                break;
+            }
+        }
+
+        sampler.finish(complete)
+    }
+
+    #[test]
+    fn spread_keeps_one_instance_at_limit_zero() {
+        let (rows, truncation) = sample(RowLimit::new(0).sampled(), 10);
+
+        assert_eq!(rows, vec![0]);
+        assert_eq!(truncation, Some(Truncation { kept: 1, total: Some(10) }));
+    }
+
+    #[test]
+    fn spread_keeps_everything_within_the_limit() {
+        for max_rows in [10, 11, 1000] {
+            let (rows, truncation) = sample(RowLimit::new(max_rows).sampled(), 10);
+
+            assert_eq!(rows, (0..10).collect::<Vec<_>>());
+            assert_eq!(truncation, None);
+        }
+
+        assert_eq!(sample(RowLimit::new(0).sampled(), 0), (Vec::new(), None));
+    }
+
+    #[test]
+    fn spread_keeps_evenly_spaced_instances_past_the_limit() {
+        let (rows, truncation) = sample(RowLimit::new(9).sampled(), 10);
+
+        assert_eq!(rows, vec![0, 2, 4, 6, 8]);
+        assert_eq!(truncation, Some(Truncation { kept: 5, total: Some(10) }));
+
+        for len in [100, 1000, 1001] {
+            let (rows, truncation) = sample(RowLimit::new(64).sampled(), len);
+            let stride = rows[1] - rows[0];
+
+            assert!((32..=64).contains(&rows.len()));
+            assert!(rows.iter().enumerate().all(|(index, row)| *row == index * stride));
+            assert_eq!(truncation, Some(Truncation { kept: rows.len(), total: Some(len) }));
+        }
+    }
+
+    #[test]
+    fn first_stops_at_the_limit() {
+        assert_eq!(sample(RowLimit::new(0), 10), (vec![0], Some(Truncation { kept: 1, total: None })));
+        assert_eq!(sample(RowLimit::new(3), 10), (vec![0, 1, 2], Some(Truncation { kept: 3, total: None })));
+        assert_eq!(sample(RowLimit::new(10), 10), ((0..10).collect(), None));
+    }
+}
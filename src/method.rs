@@ -0,0 +1,336 @@
+//! WMI methods invoked on classes and instances, with their output parameters as typed structs.
+//!
+//! Some information is only reachable through methods, e.g. the owner of a process through
+//! `Win32_Process.GetOwner` or the ACL of a share through
+//! `Win32_LogicalShareSecuritySetting.GetSecurityDescriptor`. [`exec_method`] runs such a method
+//! on the object at an object path and deserializes its output parameters into any type, the same
+//! way queries deserialize instances. [`exec_method_with`] also passes input parameters, and a
+//! [`MethodSession`] runs many methods over one connection.
+//!
+//! Object paths name a class for static methods, e.g. `Win32_Process`, or an instance through its
+//! key properties, e.g. `Win32_Process.Handle="4"`; [`instance_path`] and [`path_of`] build the
+//! latter with the quoting WMI expects.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use serde::Deserialize;
+//! use windows_snapshot::method::{exec_method, instance_path};
+//! use windows_snapshot::{COMLibrary, DEFAULT_NAMESPACE};
+//!
+//! #[derive(Deserialize, Debug)]
+//! #[allow(non_snake_case)]
+//! struct GetOwnerOutput {
+//!     ReturnValue: u32,
+//!     Domain: Option<String>,
+//!     User: Option<String>,
+//! }
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let path = instance_path("Win32_Process", &[("Handle", "4")]);
+//! let owner: GetOwnerOutput = exec_method(DEFAULT_NAMESPACE, &path, "GetOwner").unwrap();
+//!
+//! println!("{:?}", owner);
+//! ```
+
+use crate::association::Referenced;
+use crate::{retry, Unavailable};
+use serde::de::DeserializeOwned;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::mem::ManuallyDrop;
+use windows::core::{BSTR, HSTRING};
+use windows::Win32::Foundation::VARIANT_BOOL;
+use windows::Win32::System::Com::{
+    VARENUM, VARIANT, VARIANT_0, VARIANT_0_0, VARIANT_0_0_0, VT_BOOL, VT_BSTR, VT_I4,
+};
+use windows::Win32::System::Ole::VariantClear;
+use windows::Win32::System::Wmi::IWbemClassObject;
+
+/// Value of an input parameter of a WMI method
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MethodArg {
+    /// A `string` parameter
+    String(String),
+    /// A `sint32` parameter
+    I32(i32),
+    /// A `uint32` parameter, which WMI takes as a `VT_I4`
+    U32(u32),
+    /// A `boolean` parameter
+    Bool(bool),
+}
+
+impl From<&str> for MethodArg {
+    fn from(value: &str) -> Self {
+        MethodArg::String(value.to_string())
+    }
+}
+
+impl From<String> for MethodArg {
+    fn from(value: String) -> Self {
+        MethodArg::String(value)
+    }
+}
+
+impl From<i32> for MethodArg {
+    fn from(value: i32) -> Self {
+        MethodArg::I32(value)
+    }
+}
+
+impl From<u32> for MethodArg {
+    fn from(value: u32) -> Self {
+        MethodArg::U32(value)
+    }
+}
+
+impl From<bool> for MethodArg {
+    fn from(value: bool) -> Self {
+        MethodArg::Bool(value)
+    }
+}
+
+impl MethodArg {
+    /// The argument as a `VARIANT`, to be released with `VariantClear`
+    fn to_variant(&self) -> VARIANT {
+        let (vt, value): (VARENUM, VARIANT_0_0_0) = match self {
+            MethodArg::String(value) => (
+                VT_BSTR,
+                VARIANT_0_0_0 {
+                    bstrVal: ManuallyDrop::new(BSTR::from(value.as_str())),
+                },
+            ),
+            MethodArg::I32(value) => (VT_I4, VARIANT_0_0_0 { lVal: *value }),
+            MethodArg::U32(value) => (VT_I4, VARIANT_0_0_0 { lVal: *value as i32 }),
+            MethodArg::Bool(value) => (
+                VT_BOOL,
+                VARIANT_0_0_0 {
+                    boolVal: VARIANT_BOOL(if *value { -1 } else { 0 }),
+                },
+            ),
+        };
+
+        VARIANT {
+            Anonymous: VARIANT_0 {
+                Anonymous: ManuallyDrop::new(VARIANT_0_0 {
+                    vt,
+                    wReserved1: 0,
+                    wReserved2: 0,
+                    wReserved3: 0,
+                    Anonymous: value,
+                }),
+            },
+        }
+    }
+}
+
+/// Runs `method` without input parameters on the class or instance at `object_path` in
+/// `namespace`, and deserializes its output parameters into `T`
+///
+/// The COM library must be initialised on the calling thread. Transient failures are retried
+/// following the [`retry`] policy, and a failure is classified as an [`Unavailable`] naming the
+/// class of `object_path`. The `ReturnValue` of the method is an output parameter like the others:
+/// a method that ran but failed is not an error here, and `T` should keep it to tell.
+pub fn exec_method<T: DeserializeOwned>(
+    namespace: &str,
+    object_path: &str,
+    method: &str,
+) -> Result<T, Unavailable> {
+    exec_method_with(namespace, object_path, method, &[])
+}
+
+/// Runs `method` with the input parameters `inputs` on the class or instance at `object_path` in
+/// `namespace`, and deserializes its output parameters into `T`, as [`exec_method`] does
+///
+/// Input parameters the method does not declare fail the call, those it declares but `inputs`
+/// leaves out are passed as `NULL`.
+pub fn exec_method_with<T: DeserializeOwned>(
+    namespace: &str,
+    object_path: &str,
+    method: &str,
+    inputs: &[(&str, MethodArg)],
+) -> Result<T, Unavailable> {
+    MethodSession::new(namespace).exec(object_path, method, inputs)
+}
+
+/// Runs methods in one namespace over a single connection, keeping the input parameter
+/// signature of every method it ran
+///
+/// [`exec_method_with`] connects and fetches the class definition on each call, which dominates
+/// the cost of cheap methods run many times, e.g. the `StdRegProv` getters. A failed call drops
+/// the connection and the signatures, so the next attempt starts afresh.
+pub struct MethodSession {
+    namespace: String,
+    connection: Option<wmi::WMIConnection>,
+    signatures: HashMap<(String, String), IWbemClassObject>,
+}
+
+impl MethodSession {
+    /// Creates a session for `namespace`, connecting on the first call
+    pub fn new(namespace: &str) -> Self {
+        MethodSession {
+            namespace: namespace.to_string(),
+            connection: None,
+            signatures: HashMap::new(),
+        }
+    }
+
+    /// Runs `method` with the input parameters `inputs` on the class or instance at
+    /// `object_path`, as [`exec_method_with`] does
+    pub fn exec<T: DeserializeOwned>(
+        &mut self,
+        object_path: &str,
+        method: &str,
+        inputs: &[(&str, MethodArg)],
+    ) -> Result<T, Unavailable> {
+        let class = class_of(object_path);
+
+        retry::policy()
+            .run(|| {
+                let output = self.try_exec(object_path, class, method, inputs);
+
+                if output.is_err() {
+                    self.connection = None;
+                    self.signatures.clear();
+                }
+
+                output
+            })
+            .map_err(|e| Unavailable::new(&self.namespace, class, &e))
+    }
+
+    fn try_exec<T: DeserializeOwned>(
+        &mut self,
+        object_path: &str,
+        class: &str,
+        method: &str,
+        inputs: &[(&str, MethodArg)],
+    ) -> Result<T, wmi::WMIError> {
+        if self.connection.is_none() {
+            let com_con = unsafe { wmi::COMLibrary::assume_initialized() };
+            self.connection = Some(wmi::WMIConnection::with_namespace_path(&self.namespace, com_con)?);
+        }
+        let Some(wmi_con) = &self.connection else {
+            return Err(wmi::WMIError::NullPointerResult);
+        };
+
+        let in_params = if inputs.is_empty() {
+            None
+        } else {
+            let signature = match self.signatures.entry((class.to_string(), method.to_string())) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(in_signature(wmi_con, class, method)?),
+            };
+
+            Some(in_params(signature, inputs)?)
+        };
+        let mut output = None;
+
+        unsafe {
+            wmi_con.svc.ExecMethod(
+                &BSTR::from(object_path),
+                &BSTR::from(method),
+                0,
+                None,
+                in_params.as_ref(),
+                Some(&mut output),
+                None,
+            )?;
+        }
+
+        output
+            .map(wmi::IWbemClassWrapper::new)
+            .ok_or(wmi::WMIError::NullPointerResult)?
+            .into_desr()
+    }
+}
+
+/// The definition of the input parameters of `class.method`
+fn in_signature(
+    wmi_con: &wmi::WMIConnection,
+    class: &str,
+    method: &str,
+) -> Result<IWbemClassObject, wmi::WMIError> {
+    let mut class_object = None;
+    let mut in_signature = None;
+
+    unsafe {
+        wmi_con.svc.GetObject(
+            &BSTR::from(class),
+            0,
+            None,
+            Some(&mut class_object),
+            None,
+        )?;
+
+        class_object
+            .ok_or(wmi::WMIError::NullPointerResult)?
+            .GetMethod(&HSTRING::from(method), 0, &mut in_signature, std::ptr::null_mut())?;
+    }
+
+    in_signature.ok_or(wmi::WMIError::NullPointerResult)
+}
+
+/// An instance of the input parameters `in_signature` defines, holding `inputs`
+fn in_params(
+    in_signature: &IWbemClassObject,
+    inputs: &[(&str, MethodArg)],
+) -> Result<IWbemClassObject, wmi::WMIError> {
+    unsafe {
+        let in_params = in_signature.SpawnInstance(0)?;
+
+        for (name, value) in inputs {
+            let mut variant = value.to_variant();
+            let put = in_params.Put(&HSTRING::from(*name), 0, &variant, 0);
+
+            VariantClear(&mut variant)?;
+            put?;
+        }
+
+        Ok(in_params)
+    }
+}
+
+/// The object path of the instance of `class` with the key properties `keys`, e.g.
+/// `Win32_Process.Handle="4"`
+///
+/// Values are quoted as strings, which WMI also accepts for numeric keys.
+pub fn instance_path(class: &str, keys: &[(&str, &str)]) -> String {
+    let keys = keys
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                "{name}=\"{}\"",
+                value.replace('\\', "\\\\").replace('"', "\\\"")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("{class}.{keys}")
+}
+
+/// The object path of `instance`, an instance of `class`, from its [`Referenced`] key properties,
+/// `None` when one of them is unset
+pub fn path_of<T: Referenced>(class: &str, instance: &T) -> Option<String> {
+    let values = instance
+        .key_values()
+        .into_iter()
+        .collect::<Option<Vec<_>>>()?;
+    let keys = T::KEYS
+        .iter()
+        .copied()
+        .zip(values.iter().map(String::as_str))
+        .collect::<Vec<_>>();
+
+    Some(instance_path(class, &keys))
+}
+
+/// The class an object path names, e.g. `Win32_Process` for `Win32_Process.Handle="4"`
+fn class_of(object_path: &str) -> &str {
+    let head = object_path.split('"').next().unwrap_or(object_path);
+    let head = head.rsplit(':').next().unwrap_or(head);
+
+    head.split(['.', '=']).next().unwrap_or(head)
+}
@@ -0,0 +1,195 @@
+//! Prometheus metrics from snapshot data.
+//!
+//! [`collect`] turns selected state members into gauges and [`encode`] renders them in the
+//! Prometheus text exposition format, ready to be served on a scrape endpoint. Members that were
+//! never updated, or whose class is unavailable, simply produce no samples.
+//!
+//! | Metric                                  | Labels                     | Source                          |
+//! |-----------------------------------------|----------------------------|---------------------------------|
+//! | `windows_logical_disk_free_bytes`       | `volume`                   | `Win32_LogicalDisk.FreeSpace`   |
+//! | `windows_logical_disk_size_bytes`       | `volume`                   | `Win32_LogicalDisk.Size`        |
+//! | `windows_battery_charge_ratio`          | `battery`                  | `Win32_Battery.EstimatedChargeRemaining` |
+//! | `windows_processes`                     |                            | `Win32_Process`                 |
+//! | `windows_threads`                       |                            | `Win32_Thread`                  |
+//! | `windows_service_up`                    | `service`, `start_mode`    | `Win32_Service.State`           |
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.update();
+//!
+//! print!("{}", windows_snapshot::metrics::encode(&windows_snapshot::metrics::collect(&k)));
+//! ```
+
+use crate::state::Windows;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// A gauge and its samples
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Metric {
+    /// Metric name, e.g. `windows_processes`
+    pub name: String,
+    /// Description rendered as the `# HELP` line
+    pub help: String,
+    /// One sample per label set
+    pub samples: Vec<Sample>,
+}
+
+impl Metric {
+    /// Creates a gauge without samples
+    pub fn gauge(name: &str, help: &str) -> Self {
+        Metric {
+            name: name.to_string(),
+            help: help.to_string(),
+            samples: Vec::new(),
+        }
+    }
+
+    /// Adds a sample with `labels`
+    pub fn sample(&mut self, labels: &[(&str, &str)], value: f64) {
+        self.samples.push(Sample {
+            labels: labels
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            value,
+        });
+    }
+}
+
+/// A value of a metric
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Sample {
+    /// `(name, value)` label pairs
+    pub labels: Vec<(String, String)>,
+    /// Value of the sample
+    pub value: f64,
+}
+
+/// Collects the gauges listed in the module documentation from `windows`
+#[allow(unused_mut, unused_variables)]
+pub fn collect(windows: &Windows) -> Vec<Metric> {
+    let mut free = Metric::gauge(
+        "windows_logical_disk_free_bytes",
+        "Free space of the logical disk in bytes.",
+    );
+    let mut size = Metric::gauge(
+        "windows_logical_disk_size_bytes",
+        "Size of the logical disk in bytes.",
+    );
+
+    #[cfg(feature = "os-file-system")]
+    for disk in &windows.logical_disks.logical_disks {
+        let Some(volume) = disk.DeviceID.as_deref() else {
+            continue;
+        };
+
+        if let Some(free_space) = disk.FreeSpace {
+            free.sample(&[("volume", volume)], free_space as f64);
+        }
+        if let Some(disk_size) = disk.Size {
+            size.sample(&[("volume", volume)], disk_size as f64);
+        }
+    }
+
+    let mut charge = Metric::gauge(
+        "windows_battery_charge_ratio",
+        "Estimated remaining charge of the battery, from 0 to 1.",
+    );
+
+    #[cfg(feature = "hw-power")]
+    for battery in &windows.batteries.batteries {
+        if let (Some(device_id), Some(remaining)) =
+            (battery.DeviceID.as_deref(), battery.EstimatedChargeRemaining)
+        {
+            charge.sample(&[("battery", device_id)], f64::from(remaining) / 100.0);
+        }
+    }
+
+    let mut processes = Metric::gauge("windows_processes", "Number of running processes.");
+    #[cfg(feature = "os-processes")]
+    if windows.processes.unavailable.is_none() && !windows.processes.processes.is_empty() {
+        processes.sample(&[], windows.processes.processes.len() as f64);
+    }
+
+    let mut threads = Metric::gauge("windows_threads", "Number of threads.");
+    #[cfg(feature = "os-processes")]
+    if windows.threads.unavailable.is_none() && !windows.threads.threads.is_empty() {
+        threads.sample(&[], windows.threads.threads.len() as f64);
+    }
+
+    let mut service_up = Metric::gauge(
+        "windows_service_up",
+        "Whether the service is running (1) or not (0).",
+    );
+
+    #[cfg(feature = "os-services")]
+    for service in &windows.services.services {
+        if let (Some(name), Some(state)) = (service.Name.as_deref(), service.State.as_deref()) {
+            service_up.sample(
+                &[
+                    ("service", name),
+                    ("start_mode", service.StartMode.as_deref().unwrap_or_default()),
+                ],
+                if state == "Running" { 1.0 } else { 0.0 },
+            );
+        }
+    }
+
+    vec![free, size, charge, processes, threads, service_up]
+}
+
+/// Renders `metrics` in the Prometheus text exposition format, skipping metrics without samples
+pub fn encode(metrics: &[Metric]) -> String {
+    let mut encoded = String::new();
+
+    for metric in metrics.iter().filter(|metric| !metric.samples.is_empty()) {
+        let _ = writeln!(encoded, "# HELP {} {}", metric.name, escape(&metric.help, false));
+        let _ = writeln!(encoded, "# TYPE {} gauge", metric.name);
+
+        for sample in &metric.samples {
+            encoded.push_str(&metric.name);
+
+            if !sample.labels.is_empty() {
+                let labels: Vec<String> = sample
+                    .labels
+                    .iter()
+                    .map(|(name, value)| format!("{name}=\"{}\"", escape(value, true)))
+                    .collect();
+
+                let _ = write!(encoded, "{{{}}}", labels.join(","));
+            }
+
+            let _ = writeln!(encoded, " {}", format_value(sample.value));
+        }
+    }
+
+    encoded
+}
+
+/// Escapes backslashes and newlines, and double quotes in label values
+fn escape(text: &str, label_value: bool) -> String {
+    let mut escaped = text.replace('\\', "\\\\").replace('\n', "\\n");
+
+    if label_value {
+        escaped = escaped.replace('"', "\\\"");
+    }
+
+    escaped
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        String::from("NaN")
+    } else if value.is_infinite() {
+        String::from(if value > 0.0 { "+Inf" } else { "-Inf" })
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,485 @@
+//! Consistent field naming for exported snapshots.
+//!
+//! WMI classes keep their PascalCase property names (`ProcessId`, `PNPDeviceID`) so they can be
+//! queried, while the state structs use snake_case. [`Renamed`] wraps any serializable value and
+//! rewrites every struct field name to a single [`FieldCase`] on the way out, whatever the format,
+//! so exported snapshots follow the naming of the pipeline ingesting them. Deserialization and the
+//! queries themselves are untouched.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::naming::{FieldCase, Renamed};
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.processes.update();
+//!
+//! // {"processes": [{"processId": 4, "parentProcessId": 0, ...}], "lastUpdated": ...}
+//! let json = serde_json::to_string(&Renamed::new(&k.processes, FieldCase::CamelCase)).unwrap();
+//! ```
+
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+
+/// Naming convention applied to field names
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum FieldCase {
+    /// Names as declared, PascalCase for WMI properties and snake_case for states
+    #[default]
+    Original,
+    /// `process_id`, `pnp_device_id`
+    SnakeCase,
+    /// `processId`, `pnpDeviceId`
+    CamelCase,
+    /// `ProcessId`, `PnpDeviceId`
+    PascalCase,
+}
+
+impl FieldCase {
+    /// Converts `name` to this case
+    ///
+    /// Words are split on underscores and case changes, a run of capitals being one word
+    /// (`PNPDeviceID` is `PNP`, `Device`, `ID`) and digits staying with the word before them.
+    pub fn convert(self, name: &str) -> String {
+        if self == FieldCase::Original {
+            return name.to_string();
+        }
+
+        let mut converted = String::with_capacity(name.len() + 4);
+
+        for (index, word) in words(name).iter().enumerate() {
+            match self {
+                FieldCase::SnakeCase => {
+                    if index > 0 {
+                        converted.push('_');
+                    }
+                    converted.push_str(&word.to_lowercase());
+                }
+                FieldCase::CamelCase if index == 0 => converted.push_str(&word.to_lowercase()),
+                _ => {
+                    let mut chars = word.chars();
+                    converted.extend(chars.next().map(|first| first.to_ascii_uppercase()));
+                    converted.push_str(&chars.as_str().to_lowercase());
+                }
+            }
+        }
+
+        converted
+    }
+
+    /// Converted `name`, interned so it can stand in for a `&'static str` field name
+    ///
+    /// Each distinct field name is leaked once per case, the set of names being bounded by the
+    /// structs of the crate.
+    fn rename(self, name: &'static str) -> &'static str {
+        if self == FieldCase::Original {
+            return name;
+        }
+
+        static RENAMED: Mutex<BTreeMap<(FieldCase, &'static str), &'static str>> =
+            Mutex::new(BTreeMap::new());
+
+        let mut renamed = RENAMED.lock().unwrap_or_else(PoisonError::into_inner);
+
+        renamed
+            .entry((self, name))
+            .or_insert_with(|| Box::leak(self.convert(name).into_boxed_str()))
+    }
+}
+
+/// Splits `name` into its words
+fn words(name: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = name.char_indices().collect();
+    let mut words = Vec::new();
+    let mut start = None;
+
+    for (position, &(index, c)) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if let Some(word_start) = start.take() {
+                words.push(&name[word_start..index]);
+            }
+            continue;
+        }
+
+        let Some(word_start) = start else {
+            start = Some(index);
+            continue;
+        };
+
+        let previous = chars[position - 1].1;
+        let next = chars.get(position + 1).map(|&(_, next)| next);
+
+        let boundary = c.is_uppercase()
+            && (previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next.is_some_and(char::is_lowercase)));
+
+        if boundary {
+            words.push(&name[word_start..index]);
+            start = Some(index);
+        }
+    }
+
+    if let Some(word_start) = start {
+        words.push(&name[word_start..]);
+    }
+
+    words
+}
+
+/// Serializes the wrapped value with every struct field name converted to `case`
+///
+/// Map keys and enum variants are data, not field names, and are left as they are.
+#[derive(Debug, Clone, Copy)]
+pub struct Renamed<'a, T: ?Sized> {
+    value: &'a T,
+    case: FieldCase,
+}
+
+impl<'a, T: ?Sized> Renamed<'a, T> {
+    /// Wraps `value`, renaming its fields to `case`
+    pub fn new(value: &'a T, case: FieldCase) -> Self {
+        Renamed { value, case }
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for Renamed<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(Renaming {
+            inner: serializer,
+            case: self.case,
+        })
+    }
+}
+
+/// Forwards to `inner`, wrapping nested values in [`Renamed`] and renaming struct fields
+struct Renaming<S> {
+    inner: S,
+    case: FieldCase,
+}
+
+impl<S> Renaming<S> {
+    fn wrap<'a, T: ?Sized>(&self, value: &'a T) -> Renamed<'a, T> {
+        Renamed::new(value, self.case)
+    }
+}
+
+impl<S: Serializer> Serializer for Renaming<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Renaming<S::SerializeSeq>;
+    type SerializeTuple = Renaming<S::SerializeTuple>;
+    type SerializeTupleStruct = Renaming<S::SerializeTupleStruct>;
+    type SerializeTupleVariant = Renaming<S::SerializeTupleVariant>;
+    type SerializeMap = Renaming<S::SerializeMap>;
+    type SerializeStruct = Renaming<S::SerializeStruct>;
+    type SerializeStructVariant = Renaming<S::SerializeStructVariant>;
+
+    fn serialize_bool(self, v: bool) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_bool(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_i8(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_i16(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_i32(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_i64(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_i128(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_u8(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_u16(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_u32(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_u64(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_u128(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_f32(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_f64(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_char(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_str(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_none()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_some(&value)
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit()
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit_struct(name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit_variant(name, variant_index, variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_newtype_struct(name, &value)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        let value = self.wrap(value);
+        self.inner
+            .serialize_newtype_variant(name, variant_index, variant, &value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        let case = self.case;
+        self.inner
+            .serialize_seq(len)
+            .map(|inner| Renaming { inner, case })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        let case = self.case;
+        self.inner
+            .serialize_tuple(len)
+            .map(|inner| Renaming { inner, case })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        let case = self.case;
+        self.inner
+            .serialize_tuple_struct(name, len)
+            .map(|inner| Renaming { inner, case })
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        let case = self.case;
+        self.inner
+            .serialize_tuple_variant(name, variant_index, variant, len)
+            .map(|inner| Renaming { inner, case })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        let case = self.case;
+        self.inner
+            .serialize_map(len)
+            .map(|inner| Renaming { inner, case })
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        let case = self.case;
+        self.inner
+            .serialize_struct(name, len)
+            .map(|inner| Renaming { inner, case })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        let case = self.case;
+        self.inner
+            .serialize_struct_variant(name, variant_index, variant, len)
+            .map(|inner| Renaming { inner, case })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+impl<S: ser::SerializeSeq> ser::SerializeSeq for Renaming<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_element(&value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<S: ser::SerializeTuple> ser::SerializeTuple for Renaming<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_element(&value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<S: ser::SerializeTupleStruct> ser::SerializeTupleStruct for Renaming<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_field(&value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<S: ser::SerializeTupleVariant> ser::SerializeTupleVariant for Renaming<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_field(&value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<S: ser::SerializeMap> ser::SerializeMap for Renaming<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), S::Error> {
+        let key = self.wrap(key);
+        self.inner.serialize_key(&key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_value(&value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<S: ser::SerializeStruct> ser::SerializeStruct for Renaming<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), S::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_field(self.case.rename(key), &value)
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), S::Error> {
+        self.inner.skip_field(self.case.rename(key))
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<S: ser::SerializeStructVariant> ser::SerializeStructVariant for Renaming<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), S::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_field(self.case.rename(key), &value)
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), S::Error> {
+        self.inner.skip_field(self.case.rename(key))
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
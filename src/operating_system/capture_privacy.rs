@@ -0,0 +1,277 @@
+//! Camera and microphone presence together with the privacy settings that govern access to them.
+//!
+//! Capture devices are the [**Win32\_PnPEntity**](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-pnpentity)
+//! instances of the `Camera` class, the webcams of the `Image` class driven by the USB video class
+//! driver, and the capture endpoints of the `AudioEndpoint` class. Scanners, which are `Image`
+//! devices too, are left out. Consent is read from the registry:
+//!
+//! | Key                                                                                                  | Description                                                                            |
+//! |------------------------------------------------------------------------------------------------------|----------------------------------------------------------------------------------------|
+//! | `HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\<capability>`   | Device wide switch, the `Value` is `Allow` or `Deny`.                                  |
+//! | `HKCU\SOFTWARE\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\<capability>`   | Per user switch, with a subkey per packaged app and per desktop app under `NonPackaged`. |
+//! | `HKLM\SOFTWARE\Policies\Microsoft\Windows\AppPrivacy`                                                | Group Policy `LetAppsAccessCamera` and `LetAppsAccessMicrophone` values.               |
+
+use crate::hardware::motherboard_controller_port::Win32_PnPEntity;
+use crate::operating_system::registry::{self, Hive};
+use crate::{state_member, Unavailable};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+/// Query used to select camera, imaging and audio endpoint devices, the imaging devices that are
+/// not webcams are left out by [`CaptureDevice::from_pnp_entity`]
+const CAPTURE_DEVICE_QUERY: &str = "SELECT * FROM Win32_PnPEntity WHERE PNPClass = 'Camera' OR PNPClass = 'Image' OR PNPClass = 'AudioEndpoint'";
+
+/// `Service` of the USB video class driver, which drives the webcams older drivers register in the
+/// `Image` class
+const USB_VIDEO_SERVICE: &str = "usbvideo";
+
+/// Prefix of the `PNPDeviceID` of audio capture endpoints, render endpoints use `{0.0.0.`
+const AUDIO_CAPTURE_ENDPOINT_PREFIX: &str = "SWD\\MMDEVAPI\\{0.0.1.";
+
+const CONSENT_STORE: &str = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\CapabilityAccessManager\\ConsentStore";
+
+const APP_PRIVACY_POLICY: &str = "SOFTWARE\\Policies\\Microsoft\\Windows\\AppPrivacy";
+
+/// Represents the state of Windows camera and microphone access
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct CapturePrivacy {
+    /// Sequence of camera and microphone devices
+    pub capture_devices: Vec<CaptureDevice>,
+    /// Access settings of the camera
+    pub camera: CaptureConsent,
+    /// Access settings of the microphone
+    pub microphone: CaptureConsent,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(CapturePrivacy, capture_devices; camera, microphone);
+
+impl CapturePrivacy {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let pnp_entities = crate::raw_query(CAPTURE_DEVICE_QUERY);
+
+        self.refresh(pnp_entities);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let pnp_entities = crate::async_raw_query(CAPTURE_DEVICE_QUERY).await;
+
+        self.refresh(pnp_entities);
+    }
+
+    /// Returns `true` if no camera is present and enabled, or if the camera is denied for every
+    /// app through policy or the device wide switch
+    pub fn camera_disabled(&self) -> bool {
+        !self.has_working(CaptureKind::Camera) || self.camera.denied_for_all()
+    }
+
+    /// Returns `true` if no microphone is present and enabled, or if the microphone is denied for
+    /// every app through policy or the device wide switch
+    pub fn microphone_disabled(&self) -> bool {
+        !self.has_working(CaptureKind::Microphone) || self.microphone.denied_for_all()
+    }
+
+    fn has_working(&self, kind: CaptureKind) -> bool {
+        self.capture_devices.iter().any(|device| {
+            device.kind == kind
+                && device.present != Some(false)
+                && device.config_manager_error_code.unwrap_or(0) == 0
+        })
+    }
+
+    /// Consent settings are still read when Plug and Play entities cannot be queried
+    fn refresh(&mut self, pnp_entities: Result<Vec<Win32_PnPEntity>, Unavailable>) {
+        let old_hash = crate::hash_vec(&self.capture_devices);
+        let old_consent = crate::hash_vec(&[&self.camera, &self.microphone]);
+        let old_unavailable = self.unavailable.take();
+
+        match pnp_entities {
+            Ok(pnp_entities) => {
+                self.capture_devices =
+                    pnp_entities.iter().filter_map(CaptureDevice::from_pnp_entity).collect()
+            }
+            Err(unavailable) => {
+                self.capture_devices.clear();
+                self.unavailable = Some(unavailable);
+            }
+        }
+
+        self.camera = CaptureConsent::read("webcam", "LetAppsAccessCamera");
+        self.microphone = CaptureConsent::read("microphone", "LetAppsAccessMicrophone");
+
+        self.state_change = old_unavailable != self.unavailable
+            || old_hash != crate::hash_vec(&self.capture_devices)
+            || old_consent != crate::hash_vec(&[&self.camera, &self.microphone]);
+    }
+}
+
+impl Default for CapturePrivacy {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        CapturePrivacy {
+            capture_devices: Default::default(),
+            camera: Default::default(),
+            microphone: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// Kind of capture device
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum CaptureKind {
+    /// Webcams
+    Camera,
+    /// Audio capture endpoints
+    Microphone,
+}
+
+/// A camera or microphone known to Plug and Play
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct CaptureDevice {
+    /// Kind of the device
+    pub kind: CaptureKind,
+    /// Name of the device
+    pub name: Option<String>,
+    /// Windows Plug and Play device identifier of the device
+    pub pnp_device_id: Option<String>,
+    /// Status of the device, e.g. "OK", "Error" or "Degraded"
+    pub status: Option<String>,
+    /// Whether the device is currently present in the system
+    pub present: Option<bool>,
+    /// Configuration Manager error code, 22 means the device was disabled in Device Manager
+    pub config_manager_error_code: Option<u32>,
+}
+
+impl CaptureDevice {
+    fn from_pnp_entity(entity: &Win32_PnPEntity) -> Option<Self> {
+        let kind = match entity.PNPClass.as_deref() {
+            Some("Camera") => CaptureKind::Camera,
+            Some("Image")
+                if entity
+                    .Service
+                    .as_deref()
+                    .is_some_and(|service| service.eq_ignore_ascii_case(USB_VIDEO_SERVICE)) =>
+            {
+                CaptureKind::Camera
+            }
+            Some("AudioEndpoint")
+                if entity
+                    .PNPDeviceID
+                    .as_deref()
+                    .is_some_and(|id| id.to_uppercase().starts_with(AUDIO_CAPTURE_ENDPOINT_PREFIX)) =>
+            {
+                CaptureKind::Microphone
+            }
+            _ => return None,
+        };
+
+        Some(CaptureDevice {
+            kind,
+            name: entity.Name.clone(),
+            pnp_device_id: entity.PNPDeviceID.clone(),
+            status: entity.Status.clone(),
+            present: entity.Present,
+            config_manager_error_code: entity.ConfigManagerErrorCode,
+        })
+    }
+}
+
+/// Consent settings of one capability, `webcam` or `microphone`
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct CaptureConsent {
+    /// Device wide switch, `Allow` or `Deny`
+    pub system_access: Option<String>,
+    /// Switch of the current user, `Allow` or `Deny`
+    pub user_access: Option<String>,
+    /// Group Policy setting
+    ///
+    /// - `User in control` (0)
+    /// - `Force Allow` (1)
+    /// - `Force Deny` (2)
+    pub policy: Option<u32>,
+    /// Per app consent of the current user
+    pub apps: Vec<AppConsent>,
+}
+
+impl CaptureConsent {
+    fn read(capability: &str, policy_value: &str) -> Self {
+        let path = format!("{CONSENT_STORE}\\{capability}");
+        let non_packaged = format!("{path}\\NonPackaged");
+
+        let packaged_apps = registry::subkeys(Hive::CurrentUser, &path)
+            .into_iter()
+            .filter(|app| app != "NonPackaged")
+            .map(|app| AppConsent::read(&path, app, true));
+        let desktop_apps = registry::subkeys(Hive::CurrentUser, &non_packaged)
+            .into_iter()
+            .map(|app| AppConsent::read(&non_packaged, app, false));
+
+        CaptureConsent {
+            system_access: read_string(Hive::LocalMachine, &path, "Value"),
+            user_access: read_string(Hive::CurrentUser, &path, "Value"),
+            policy: registry::read_value(Hive::LocalMachine, APP_PRIVACY_POLICY, policy_value)
+                .and_then(|value| value.as_u64())
+                .map(|value| value as u32),
+            apps: packaged_apps.chain(desktop_apps).collect(),
+        }
+    }
+
+    /// Returns `true` if access is denied regardless of per user and per app settings
+    pub fn denied_for_all(&self) -> bool {
+        self.policy == Some(2) || self.system_access.as_deref() == Some("Deny")
+    }
+}
+
+/// Consent given to a single app
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct AppConsent {
+    /// Package family name of packaged apps, or the executable path with `#` as separator for
+    /// desktop apps
+    pub app: String,
+    /// Whether the app is a packaged (Store) app
+    pub packaged: bool,
+    /// `Allow` or `Deny`
+    pub value: Option<String>,
+    /// When the app last started using the device, as a `FILETIME`
+    pub last_used_time_start: Option<u64>,
+    /// When the app last stopped using the device, as a `FILETIME`
+    pub last_used_time_stop: Option<u64>,
+}
+
+impl AppConsent {
+    fn read(parent: &str, app: String, packaged: bool) -> Self {
+        let path = format!("{parent}\\{app}");
+        let read_u64 = |name| {
+            registry::read_value(Hive::CurrentUser, &path, name).and_then(|value| value.as_u64())
+        };
+
+        AppConsent {
+            value: read_string(Hive::CurrentUser, &path, "Value"),
+            last_used_time_start: read_u64("LastUsedTimeStart"),
+            last_used_time_stop: read_u64("LastUsedTimeStop"),
+            app,
+            packaged,
+        }
+    }
+}
+
+fn read_string(hive: Hive, path: &str, name: &str) -> Option<String> {
+    registry::read_value(hive, path, name).and_then(|value| value.as_str().map(String::from))
+}
@@ -0,0 +1,271 @@
+//! The COM subcategory groups classes that represent the Component Object Model (COM) components
+//! and Distributed COM (DCOM) applications registered on the computer.
+//!
+//! | Class                                                                                  | Description                                                                                                           |
+//! |----------------------------------------------------------------------------------------|-----------------------------------------------------------------------------------------------------------------------|
+//! | [**Win32\_ClassicCOMClassSetting**](win32-classiccomclasssetting)                   | Instance class<br/> Represents the settings of a COM component, from the `HKEY_CLASSES_ROOT\CLSID` registry key.<br/> |
+//! | [**Win32\_COMSetting**](win32-comsetting)                                           | Instance class<br/> Represents the settings associated with a COM component or DCOM application.<br/>                 |
+//! | [**Win32\_DCOMApplication**](win32-dcomapplication)                                 | Instance class<br/> Represents the properties of a DCOM application.<br/>                                            |
+//! | [**Win32\_DCOMApplicationSetting**](win32-dcomapplicationsetting)                   | Instance class<br/> Represents the settings of a DCOM application, from the `HKEY_CLASSES_ROOT\AppID` key.<br/>       |
+//!
+//! `Win32_COMSetting` and `Win32_ClassicCOMClassSetting` return an instance per registered class,
+//! often several thousand, and are slow to enumerate.
+
+use crate::{keyed, update};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection, WMIDateTime};
+
+/// Represents the state of Windows DCOM applications
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct DCOMApplications {
+    /// Sequence of windows DCOM applications
+    pub dcom_applications: Vec<Win32_DCOMApplication>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(DCOMApplications, dcom_applications);
+keyed!(DCOMApplications, dcom_applications, Win32_DCOMApplication, AppID: String);
+
+/// Represents the state of Windows DCOM application settings
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct DCOMApplicationSettings {
+    /// Sequence of windows DCOM application settings
+    pub dcom_application_settings: Vec<Win32_DCOMApplicationSetting>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(DCOMApplicationSettings, dcom_application_settings);
+keyed!(DCOMApplicationSettings, dcom_application_settings, Win32_DCOMApplicationSetting, AppID: String);
+
+impl DCOMApplicationSettings {
+    /// Applications running as the interactive user, which lets a remote caller act in the
+    /// session of whoever is logged on
+    pub fn runs_as_interactive_user(&self) -> Vec<&Win32_DCOMApplicationSetting> {
+        self.dcom_application_settings
+            .iter()
+            .filter(|setting| {
+                setting
+                    .RunAsUser
+                    .as_deref()
+                    .is_some_and(|user| user.eq_ignore_ascii_case("Interactive User"))
+            })
+            .collect()
+    }
+
+    /// Applications overriding the machine-wide default with an authentication level below
+    /// `Packet`, so their calls are not integrity checked
+    pub fn weak_authentication(&self) -> Vec<&Win32_DCOMApplicationSetting> {
+        self.dcom_application_settings
+            .iter()
+            .filter(|setting| matches!(setting.AuthenticationLevel, Some(1..=3)))
+            .collect()
+    }
+}
+
+/// Represents the state of Windows COM settings
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct COMSettings {
+    /// Sequence of windows COM settings
+    pub com_settings: Vec<Win32_COMSetting>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(COMSettings, com_settings);
+
+/// Represents the state of Windows COM class settings
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ClassicCOMClassSettings {
+    /// Sequence of windows COM class settings
+    pub classic_com_class_settings: Vec<Win32_ClassicCOMClassSetting>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(ClassicCOMClassSettings, classic_com_class_settings);
+keyed!(ClassicCOMClassSettings, classic_com_class_settings, Win32_ClassicCOMClassSetting, ComponentId: String);
+
+impl ClassicCOMClassSettings {
+    /// Classes whose server runs out of process and can be activated through DCOM
+    pub fn with_local_server(&self) -> Vec<&Win32_ClassicCOMClassSetting> {
+        self.classic_com_class_settings
+            .iter()
+            .filter(|setting| setting.LocalServer32.is_some() || setting.LocalServer.is_some())
+            .collect()
+    }
+}
+
+/// The `Win32_DCOMApplication` WMI class represents the properties of a DCOM application.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-dcomapplication>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_DCOMApplication {
+    /// Globally unique identifier (GUID) of the DCOM application. Every COM class registered under
+    /// this GUID runs in the same process.
+    pub AppID: Option<String>,
+    /// A short textual description of the object.
+    pub Caption: Option<String>,
+    /// A textual description of the object.
+    pub Description: Option<String>,
+    /// Indicates when the object was installed.
+    pub InstallDate: Option<WMIDateTime>,
+    /// Name of the DCOM application.
+    pub Name: Option<String>,
+    /// String that indicates the current status of the object.
+    pub Status: Option<String>,
+}
+
+/// The `Win32_DCOMApplicationSetting` WMI class represents the settings of a DCOM application.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-dcomapplicationsetting>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_DCOMApplicationSetting {
+    /// Globally unique identifier (GUID) of the DCOM application.
+    pub AppID: Option<String>,
+    /// Authentication level used by the application, the machine-wide default when `None`.
+    ///
+    /// - None (1)
+    /// - Connect (2)
+    /// - Call (3)
+    /// - Packet (4)
+    /// - Packet Integrity (5)
+    /// - Packet Privacy (6)
+    pub AuthenticationLevel: Option<u32>,
+    /// A short textual description of the object.
+    pub Caption: Option<String>,
+    /// Name of the custom surrogate in which the process runs.
+    pub CustomSurrogate: Option<String>,
+    /// A textual description of the object.
+    pub Description: Option<String>,
+    /// If `True`, the client can only connect to the instance of the object that is already running.
+    pub EnableAtStorageActivation: Option<bool>,
+    /// Name of the service that hosts the application, if it runs as a service.
+    pub LocalService: Option<String>,
+    /// Name of the remote computer on which the application runs.
+    pub RemoteServerName: Option<String>,
+    /// User account under which the application runs, e.g. `Interactive User`. `None` means the
+    /// launching user.
+    pub RunAsUser: Option<String>,
+    /// Command-line parameters passed to `LocalService`.
+    pub ServiceParameters: Option<String>,
+    /// Identifier by which the object is known.
+    pub SettingID: Option<String>,
+    /// If `True`, the application is a DLL loaded in the default surrogate process.
+    pub UseSurrogate: Option<bool>,
+}
+
+/// The `Win32_COMSetting` WMI class represents the settings associated with a COM component or
+/// DCOM application.
+///
+/// Instances are the `Win32_ClassicCOMClassSetting` and `Win32_DCOMApplicationSetting` instances,
+/// reduced to the properties they share.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-comsetting>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_COMSetting {
+    /// A short textual description of the object.
+    pub Caption: Option<String>,
+    /// A textual description of the object.
+    pub Description: Option<String>,
+    /// Identifier by which the object is known.
+    pub SettingID: Option<String>,
+}
+
+/// The `Win32_ClassicCOMClassSetting` WMI class represents the properties of a Component Object
+/// Model (COM) component, read from the `HKEY_CLASSES_ROOT\CLSID` registry key.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-classiccomclasssetting>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_ClassicCOMClassSetting {
+    /// AppID of the DCOM application hosting the class.
+    pub AppID: Option<String>,
+    /// CLSID of the class to which the class is converted automatically.
+    pub AutoConvertToClsid: Option<String>,
+    /// CLSID of the class emulating this class by default.
+    pub AutoTreatAsClsid: Option<String>,
+    /// A short textual description of the object.
+    pub Caption: Option<String>,
+    /// Class identifier (CLSID) of the COM component.
+    pub ComponentId: Option<String>,
+    /// If `True`, the component is an ActiveX control.
+    pub Control: Option<bool>,
+    /// Path to the executable file and resource ID of the default icon.
+    pub DefaultIcon: Option<String>,
+    /// A textual description of the object.
+    pub Description: Option<String>,
+    /// If `True`, the component can be inserted into OLE containers.
+    pub Insertable: Option<bool>,
+    /// Path to the 16-bit in-process handler DLL.
+    pub InprocHandler: Option<String>,
+    /// Path to the 32-bit in-process handler DLL.
+    pub InprocHandler32: Option<String>,
+    /// Path to the 16-bit in-process server DLL.
+    pub InprocServer: Option<String>,
+    /// Path to the 32-bit in-process server DLL.
+    pub InprocServer32: Option<String>,
+    /// Class file name of a Java component.
+    pub JavaClass: Option<String>,
+    /// Path to the 16-bit local server executable.
+    pub LocalServer: Option<String>,
+    /// Path to the 32-bit local server executable.
+    pub LocalServer32: Option<String>,
+    /// Long display name of the component.
+    pub LongDisplayName: Option<String>,
+    /// Programmatic identifier, e.g. `Excel.Application.16`.
+    pub ProgId: Option<String>,
+    /// Identifier by which the object is known.
+    pub SettingID: Option<String>,
+    /// Short display name of the component.
+    pub ShortDisplayName: Option<String>,
+    /// Threading model of the in-process server, e.g. `Apartment`, `Both`, `Free` or `Neutral`.
+    pub ThreadingModel: Option<String>,
+    /// Path to the executable file and resource ID of the toolbar button bitmap.
+    pub ToolBoxBitmap32: Option<String>,
+    /// CLSID of the class emulating this class.
+    pub TreatAsClsid: Option<String>,
+    /// Type library identifier (LIBID) of the component.
+    pub TypeLibraryId: Option<String>,
+    /// Version of the component.
+    pub Version: Option<String>,
+    /// Programmatic identifier that does not change across versions, e.g. `Excel.Application`.
+    pub VersionIndependentProgId: Option<String>,
+}
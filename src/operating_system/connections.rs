@@ -0,0 +1,282 @@
+//! Sockets and the neighbor (ARP) cache, from the `root\StandardCimv2` namespace.
+//!
+//! | Class                                                                                              | Description                                                                                         |
+//! |----------------------------------------------------------------------------------------------------|-----------------------------------------------------------------------------------------------------|
+//! | [**MSFT\_NetTCPConnection**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/nettcpipprov/msft-nettcpconnection) | Instance class<br/> Represents a TCP connection or listening socket.<br/>                |
+//! | [**MSFT\_NetUDPEndpoint**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/nettcpipprov/msft-netudpendpoint)     | Instance class<br/> Represents a bound UDP socket.<br/>                                  |
+//! | [**MSFT\_NetNeighbor**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/nettcpipprov/msft-netneighbor)           | Instance class<br/> Represents an entry of the neighbor cache, ARP for IPv4 and NDP for IPv6.<br/> |
+//!
+//! Sockets carry the `OwningProcess` identifier, which [`TcpConnections::with_processes`] and
+//! [`UdpEndpoints::with_processes`] resolve against a [`Processes`] snapshot taken at the same
+//! time. A process that exited in between resolves to `None`.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.tcp_connections.update();
+//! k.processes.update();
+//!
+//! for (connection, process) in k.tcp_connections.with_processes(&k.processes) {
+//!     println!(
+//!         "{:?}:{:?} {:?}",
+//!         connection.LocalAddress,
+//!         connection.LocalPort,
+//!         process.and_then(|process| process.Name.as_ref())
+//!     );
+//! }
+//! ```
+
+use crate::codes::{NeighborState, TcpState};
+use crate::operating_system::processes::{Processes, Win32_Process};
+use crate::{coded, update, STANDARD_CIMV2_NAMESPACE};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection, WMIDateTime};
+
+/// Represents the state of Windows TCP connections and listening sockets
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct TcpConnections {
+    /// Sequence of windows TCP connections, in any state
+    pub tcp_connections: Vec<MSFT_NetTCPConnection>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(TcpConnections, tcp_connections, STANDARD_CIMV2_NAMESPACE);
+
+impl TcpConnections {
+    /// Sockets listening for connections
+    pub fn listening(&self) -> Vec<&MSFT_NetTCPConnection> {
+        self.in_state(TcpState::Listen)
+    }
+
+    /// Connections that are established
+    pub fn established(&self) -> Vec<&MSFT_NetTCPConnection> {
+        self.in_state(TcpState::Established)
+    }
+
+    /// Connections and sockets owned by the process `pid`
+    pub fn owned_by(&self, pid: u32) -> Vec<&MSFT_NetTCPConnection> {
+        self.tcp_connections
+            .iter()
+            .filter(|connection| connection.OwningProcess == Some(pid))
+            .collect()
+    }
+
+    /// Pairs every connection with its owning process in `processes`
+    pub fn with_processes<'a>(
+        &'a self,
+        processes: &'a Processes,
+    ) -> Vec<(&'a MSFT_NetTCPConnection, Option<&'a Win32_Process>)> {
+        self.tcp_connections
+            .iter()
+            .map(|connection| (connection, owning_process(connection.OwningProcess, processes)))
+            .collect()
+    }
+
+    fn in_state(&self, state: TcpState) -> Vec<&MSFT_NetTCPConnection> {
+        self.tcp_connections
+            .iter()
+            .filter(|connection| connection.state() == Some(state))
+            .collect()
+    }
+}
+
+/// Represents the state of Windows UDP endpoints
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct UdpEndpoints {
+    /// Sequence of windows UDP endpoints
+    pub udp_endpoints: Vec<MSFT_NetUDPEndpoint>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(UdpEndpoints, udp_endpoints, STANDARD_CIMV2_NAMESPACE);
+
+impl UdpEndpoints {
+    /// Endpoints owned by the process `pid`
+    pub fn owned_by(&self, pid: u32) -> Vec<&MSFT_NetUDPEndpoint> {
+        self.udp_endpoints
+            .iter()
+            .filter(|endpoint| endpoint.OwningProcess == Some(pid))
+            .collect()
+    }
+
+    /// Pairs every endpoint with its owning process in `processes`
+    pub fn with_processes<'a>(
+        &'a self,
+        processes: &'a Processes,
+    ) -> Vec<(&'a MSFT_NetUDPEndpoint, Option<&'a Win32_Process>)> {
+        self.udp_endpoints
+            .iter()
+            .map(|endpoint| (endpoint, owning_process(endpoint.OwningProcess, processes)))
+            .collect()
+    }
+}
+
+/// Represents the state of the Windows neighbor cache
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct NetNeighbors {
+    /// Sequence of windows neighbor cache entries
+    pub net_neighbors: Vec<MSFT_NetNeighbor>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(NetNeighbors, net_neighbors, STANDARD_CIMV2_NAMESPACE);
+
+impl NetNeighbors {
+    /// Entries whose link layer address is known
+    pub fn resolved(&self) -> Vec<&MSFT_NetNeighbor> {
+        self.net_neighbors
+            .iter()
+            .filter(|neighbor| {
+                !matches!(
+                    neighbor.state(),
+                    None | Some(NeighborState::Unreachable | NeighborState::Incomplete)
+                )
+            })
+            .collect()
+    }
+}
+
+fn owning_process(pid: Option<u32>, processes: &Processes) -> Option<&Win32_Process> {
+    processes.get_by_key(&pid?)
+}
+
+/// The `MSFT_NetTCPConnection` WMI class represents a TCP connection or listening socket.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/nettcpipprov/msft-nettcpconnection>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_NetTCPConnection {
+    /// Unique identifier of the connection.
+    pub InstanceID: Option<String>,
+    /// Local IP address, `0.0.0.0` or `::` for sockets listening on every address.
+    pub LocalAddress: Option<String>,
+    /// Local port.
+    pub LocalPort: Option<u16>,
+    /// Remote IP address.
+    pub RemoteAddress: Option<String>,
+    /// Remote port.
+    pub RemotePort: Option<u16>,
+    /// State of the connection.
+    ///
+    /// - `Closed` (1)
+    /// - `Listen` (2)
+    /// - `SynSent` (3)
+    /// - `SynReceived` (4)
+    /// - `Established` (5)
+    /// - `FinWait1` (6)
+    /// - `FinWait2` (7)
+    /// - `CloseWait` (8)
+    /// - `Closing` (9)
+    /// - `LastAck` (10)
+    /// - `TimeWait` (11)
+    /// - `DeleteTCB` (12)
+    /// - `Bound` (100)
+    pub State: Option<u8>,
+    /// TCP setting template applied to the connection.
+    ///
+    /// - `Internet` (0)
+    /// - `Datacenter` (1)
+    /// - `Compat` (2)
+    /// - `Custom` (3)
+    pub AppliedSetting: Option<u8>,
+    /// Identifier of the process owning the connection.
+    pub OwningProcess: Option<u32>,
+    /// When the connection was created.
+    pub CreationTime: Option<WMIDateTime>,
+    /// Whether the connection is offloaded to the network adapter.
+    ///
+    /// - `InHost` (0)
+    /// - `Offloading` (1)
+    /// - `Offloaded` (2)
+    /// - `Uploading` (3)
+    pub OffloadState: Option<u8>,
+}
+
+coded!(MSFT_NetTCPConnection {
+    State => state: TcpState,
+});
+
+/// The `MSFT_NetUDPEndpoint` WMI class represents a bound UDP socket.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/nettcpipprov/msft-netudpendpoint>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_NetUDPEndpoint {
+    /// Unique identifier of the endpoint.
+    pub InstanceID: Option<String>,
+    /// Local IP address, `0.0.0.0` or `::` for sockets bound to every address.
+    pub LocalAddress: Option<String>,
+    /// Local port.
+    pub LocalPort: Option<u16>,
+    /// Identifier of the process owning the endpoint.
+    pub OwningProcess: Option<u32>,
+    /// When the endpoint was created.
+    pub CreationTime: Option<WMIDateTime>,
+}
+
+/// The `MSFT_NetNeighbor` WMI class represents an entry of the neighbor cache.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/nettcpipprov/msft-netneighbor>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_NetNeighbor {
+    /// Index of the interface the neighbor is reached through.
+    pub InterfaceIndex: Option<u32>,
+    /// Alias of the interface the neighbor is reached through.
+    pub InterfaceAlias: Option<String>,
+    /// IP address of the neighbor.
+    pub IPAddress: Option<String>,
+    /// Link layer (MAC) address of the neighbor.
+    pub LinkLayerAddress: Option<String>,
+    /// Address family of `IPAddress`: IPv4 (2) or IPv6 (23).
+    pub AddressFamily: Option<u16>,
+    /// State of the entry.
+    ///
+    /// - `Unreachable` (0)
+    /// - `Incomplete` (1)
+    /// - `Probe` (2)
+    /// - `Delay` (3)
+    /// - `Stale` (4)
+    /// - `Reachable` (5)
+    /// - `Permanent` (6)
+    pub State: Option<u8>,
+    /// Store the entry lives in: `Persistent` (0) or `Active` (1).
+    pub Store: Option<u8>,
+}
+
+coded!(MSFT_NetNeighbor {
+    State => state: NeighborState,
+});
@@ -10,7 +10,7 @@
 use crate::operating_system::users::Win32_UserAccount;
 use crate::update;
 use serde::{Deserialize, Serialize};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
 
 /// Represents the state of Windows user's desktops
@@ -25,10 +25,81 @@ pub struct Desktops {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Desktops, desktops);
 
+impl Desktops {
+    /// Desktop settings of `user_name`, as shown in `Name` (e.g. `DOMAIN\\JSmith`)
+    pub fn of_user(&self, user_name: &str) -> Option<&Win32_Desktop> {
+        self.desktops.iter().find(|desktop| {
+            desktop
+                .Name
+                .as_deref()
+                .is_some_and(|name| name.eq_ignore_ascii_case(user_name))
+        })
+    }
+
+    /// Screen saver lock status of every user, checked against a `max_timeout` of inactivity
+    ///
+    /// Settings enforced by Group Policy under `Software\Policies` are not reported by
+    /// `Win32_Desktop`, a user whose policy enforces the lock can show up as not compliant
+    pub fn screen_locks(&self, max_timeout: Duration) -> Vec<ScreenLockStatus> {
+        self.desktops
+            .iter()
+            .map(|desktop| ScreenLockStatus {
+                user_name: desktop.Name.clone(),
+                active: desktop.ScreenSaverActive == Some(true),
+                secure: desktop.ScreenSaverSecure == Some(true),
+                timeout: desktop.ScreenSaverTimeout,
+                compliant: desktop.locks_within(max_timeout),
+            })
+            .collect()
+    }
+
+    /// Users whose screen does not lock after at most `max_timeout` of inactivity
+    pub fn unlocked_screens(&self, max_timeout: Duration) -> Vec<ScreenLockStatus> {
+        self.screen_locks(max_timeout)
+            .into_iter()
+            .filter(|status| !status.compliant)
+            .collect()
+    }
+}
+
+impl Win32_Desktop {
+    /// Inactivity after which the screen saver starts and asks for the password, `None` if it
+    /// never locks the screen
+    pub fn screen_lock_timeout(&self) -> Option<Duration> {
+        match (self.ScreenSaverActive, self.ScreenSaverSecure, self.ScreenSaverTimeout) {
+            (Some(true), Some(true), Some(timeout)) => Some(Duration::from_secs(u64::from(timeout))),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the screen locks after at most `max_timeout` of inactivity
+    pub fn locks_within(&self, max_timeout: Duration) -> bool {
+        self.screen_lock_timeout()
+            .is_some_and(|timeout| timeout <= max_timeout)
+    }
+}
+
+/// Whether the screen saver of a user locks the workstation
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ScreenLockStatus {
+    /// User the settings belong to, e.g. `DOMAIN\JSmith` or `.DEFAULT` for new profiles
+    pub user_name: Option<String>,
+    /// Whether the screen saver is active
+    pub active: bool,
+    /// Whether the screen saver asks for the password
+    pub secure: bool,
+    /// Seconds of inactivity before the screen saver starts
+    pub timeout: Option<u32>,
+    /// Whether the screen locks within the maximum timeout checked against
+    pub compliant: bool,
+}
+
 /// Represents the state of Windows Environment
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct Environments {
@@ -41,10 +112,136 @@ pub struct Environments {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Environments, environments);
 
+impl Environments {
+    /// System wide variables, owned by `<SYSTEM>`
+    pub fn system(&self) -> Vec<&Win32_Environment> {
+        self.environments
+            .iter()
+            .filter(|variable| variable.SystemVariable == Some(true))
+            .collect()
+    }
+
+    /// Variables of `user_name`, as shown in `UserName` (e.g. `DOMAIN\\JSmith`)
+    pub fn of_user(&self, user_name: &str) -> Vec<&Win32_Environment> {
+        self.environments
+            .iter()
+            .filter(|variable| {
+                variable.SystemVariable != Some(true)
+                    && variable
+                        .UserName
+                        .as_deref()
+                        .is_some_and(|owner| owner.eq_ignore_ascii_case(user_name))
+            })
+            .collect()
+    }
+
+    /// Variables added, removed or modified since `baseline`, matched by owner and name
+    pub fn drift(&self, baseline: &Environments) -> Vec<EnvironmentChange> {
+        let find = |environments: &'_ [Win32_Environment], variable: &Win32_Environment| {
+            environments
+                .iter()
+                .find(|candidate| same_variable(candidate, variable))
+                .cloned()
+        };
+
+        let added_or_modified = self.environments.iter().filter_map(|variable| {
+            match find(&baseline.environments, variable) {
+                None => Some(EnvironmentChange::new(EnvironmentChangeKind::Added, None, Some(variable))),
+                Some(old) if old.VariableValue != variable.VariableValue => Some(
+                    EnvironmentChange::new(EnvironmentChangeKind::Modified, Some(&old), Some(variable)),
+                ),
+                Some(_) => None,
+            }
+        });
+        let removed = baseline
+            .environments
+            .iter()
+            .filter(|variable| find(&self.environments, variable).is_none())
+            .map(|variable| EnvironmentChange::new(EnvironmentChangeKind::Removed, Some(variable), None));
+
+        added_or_modified.chain(removed).collect()
+    }
+}
+
+fn same_variable(a: &Win32_Environment, b: &Win32_Environment) -> bool {
+    let eq = |x: &Option<String>, y: &Option<String>| match (x, y) {
+        (Some(x), Some(y)) => x.eq_ignore_ascii_case(y),
+        (x, y) => x == y,
+    };
+
+    eq(&a.Name, &b.Name) && eq(&a.UserName, &b.UserName)
+}
+
+/// How an environment variable changed
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum EnvironmentChangeKind {
+    /// The variable did not exist in the baseline
+    Added,
+    /// The variable no longer exists
+    Removed,
+    /// The value of the variable changed
+    Modified,
+}
+
+/// An environment variable that differs from the baseline
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct EnvironmentChange {
+    /// How the variable changed
+    pub kind: EnvironmentChangeKind,
+    /// Name of the variable
+    pub name: Option<String>,
+    /// Owner of the variable, `<SYSTEM>` for system variables
+    pub user_name: Option<String>,
+    /// Value in the baseline
+    pub old_value: Option<String>,
+    /// Current value
+    pub new_value: Option<String>,
+    /// `;` separated entries present in the new value but not in the old one, the entries a
+    /// `PATH` hijack introduces
+    pub added_entries: Vec<String>,
+}
+
+impl EnvironmentChange {
+    fn new(
+        kind: EnvironmentChangeKind,
+        old: Option<&Win32_Environment>,
+        new: Option<&Win32_Environment>,
+    ) -> Self {
+        let old_value = old.and_then(|old| old.VariableValue.clone());
+        let new_value = new.and_then(|new| new.VariableValue.clone());
+        let variable = new.or(old);
+
+        let old_entries: Vec<&str> = old_value.as_deref().map(entries).unwrap_or_default();
+        let added_entries = new_value
+            .as_deref()
+            .map(entries)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| !old_entries.iter().any(|old| old.eq_ignore_ascii_case(entry)))
+            .map(String::from)
+            .collect();
+
+        EnvironmentChange {
+            kind,
+            name: variable.and_then(|variable| variable.Name.clone()),
+            user_name: variable.and_then(|variable| variable.UserName.clone()),
+            old_value,
+            new_value,
+            added_entries,
+        }
+    }
+}
+
+fn entries(value: &str) -> Vec<&str> {
+    value.split(';').map(str::trim).filter(|entry| !entry.is_empty()).collect()
+}
+
 /// Represents the state of Windows `TimeZone`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct TimeZones {
@@ -57,6 +254,8 @@ pub struct TimeZones {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(TimeZones, timezones);
@@ -73,6 +272,8 @@ pub struct UserDesktops {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(UserDesktops, user_desktops);
@@ -137,7 +338,7 @@ pub struct Win32_Desktop {
     pub ScreenSaverExecutable: Option<String>,
     /// Password is enabled for the screen saver.
     pub ScreenSaverSecure: Option<bool>,
-    /// Amount of time that passes before the screen saver starts.
+    /// Amount of time that passes before the screen saver starts, in seconds.
     pub ScreenSaverTimeout: Option<u32>,
     /// File name for the wallpaper design on the background of the desktop.
     ///
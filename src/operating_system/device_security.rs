@@ -0,0 +1,270 @@
+//! TPM and BitLocker status, the classes live in their own namespaces under `root\cimv2\Security`
+//! and can only be queried with administrative rights.
+//!
+//! | Class                                                                                                  | Description                                                                                   |
+//! |--------------------------------------------------------------------------------------------------------|-----------------------------------------------------------------------------------------------|
+//! | [**Win32\_Tpm**](https://learn.microsoft.com/en-us/windows/win32/secprov/win32-tpm)                     | Represents the Trusted Platform Module of the computer, in `root\cimv2\Security\MicrosoftTpm`. |
+//! | [**Win32\_EncryptableVolume**](https://learn.microsoft.com/en-us/windows/win32/secprov/win32-encryptablevolume) | Represents a volume that can be protected by BitLocker, in `root\cimv2\Security\MicrosoftVolumeEncryption`. |
+//!
+//! No WMI class reports Secure Boot, [`SecureBoot`] reads the firmware type and Secure Boot state
+//! Windows records in the registry at boot instead.
+
+use crate::operating_system::registry::{self, Hive, RegistryValue};
+use crate::{state_member, update, Unavailable, TPM_NAMESPACE, VOLUME_ENCRYPTION_NAMESPACE};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection};
+
+/// Represents the state of Windows Trusted Platform Modules
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct Tpms {
+    /// Sequence of windows Tpm states, empty when the computer has no TPM
+    pub tpms: Vec<Win32_Tpm>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(Tpms, tpms, TPM_NAMESPACE);
+
+impl Tpms {
+    /// Returns `true` if a TPM is present, enabled and activated
+    pub fn ready(&self) -> bool {
+        self.tpms.iter().any(|tpm| {
+            tpm.IsEnabled_InitialValue == Some(true) && tpm.IsActivated_InitialValue == Some(true)
+        })
+    }
+}
+
+impl Win32_Tpm {
+    /// Highest TCG specification version the TPM supports, e.g. `2.0`, read from `SpecVersion`
+    pub fn version(&self) -> Option<&str> {
+        self.SpecVersion.as_deref()?.split(',').next().map(str::trim)
+    }
+}
+
+/// Key holding the Secure Boot state
+pub const SECURE_BOOT_STATE: &str = "SYSTEM\\CurrentControlSet\\Control\\SecureBoot\\State";
+
+/// Represents the state of Windows Secure Boot
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct SecureBoot {
+    /// Sequence holding the firmware and Secure Boot status of the last boot
+    pub secure_boot: Vec<SecureBootStatus>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(SecureBoot, secure_boot);
+
+impl SecureBoot {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+        self.refresh(Ok(SecureBootStatus::read()));
+    }
+
+    /// Update fields asynchronously
+    ///
+    /// The registry has no asynchronous reads, so they run on a blocking thread of the runtime
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+        self.refresh(crate::blocking("SecureBootStatus", SecureBootStatus::read).await);
+    }
+
+    fn refresh(&mut self, status: Result<SecureBootStatus, Unavailable>) {
+        let old_vec = std::mem::take(&mut self.secure_boot);
+        let old_unavailable = self.unavailable.take();
+
+        match status {
+            Ok(status) => self.secure_boot.push(status),
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || crate::hash_vec(&self.secure_boot) != crate::hash_vec(&old_vec);
+    }
+
+    /// Whether Secure Boot is enabled, `None` when unknown, e.g. on legacy BIOS firmware
+    pub fn enabled(&self) -> Option<bool> {
+        self.secure_boot.first()?.secure_boot_enabled
+    }
+}
+
+impl Default for SecureBoot {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        SecureBoot {
+            secure_boot: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// Firmware and Secure Boot status of the last boot
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct SecureBootStatus {
+    /// Firmware the machine booted from, read from `PEFirmwareType`
+    pub firmware_type: Option<FirmwareType>,
+    /// Whether Secure Boot is enabled, read from `UEFISecureBootEnabled`. `None` when Windows did
+    /// not record it, as on legacy BIOS firmware.
+    pub secure_boot_enabled: Option<bool>,
+}
+
+impl SecureBootStatus {
+    fn read() -> Self {
+        let number = |path: &str, name: &str| {
+            registry::read_value(Hive::LocalMachine, path, name)
+                .as_ref()
+                .and_then(RegistryValue::as_u64)
+        };
+
+        SecureBootStatus {
+            firmware_type: number("SYSTEM\\CurrentControlSet\\Control", "PEFirmwareType").map(
+                |firmware_type| match firmware_type {
+                    1 => FirmwareType::Bios,
+                    2 => FirmwareType::Uefi,
+                    _ => FirmwareType::Unknown,
+                },
+            ),
+            secure_boot_enabled: number(SECURE_BOOT_STATE, "UEFISecureBootEnabled")
+                .map(|enabled| enabled != 0),
+        }
+    }
+}
+
+/// Firmware interface the machine booted from
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum FirmwareType {
+    /// Legacy BIOS (1)
+    Bios,
+    /// UEFI (2)
+    Uefi,
+    /// Any other value
+    Unknown,
+}
+
+/// Represents the state of Windows BitLocker volumes
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct EncryptableVolumes {
+    /// Sequence of windows EncryptableVolume states
+    pub encryptable_volumes: Vec<Win32_EncryptableVolume>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(EncryptableVolumes, encryptable_volumes, VOLUME_ENCRYPTION_NAMESPACE);
+
+impl EncryptableVolumes {
+    /// Volumes whose BitLocker protection is not on
+    pub fn unprotected(&self) -> Vec<&Win32_EncryptableVolume> {
+        self.encryptable_volumes
+            .iter()
+            .filter(|volume| volume.ProtectionStatus != Some(1))
+            .collect()
+    }
+}
+
+/// The `Win32_Tpm` WMI class represents the Trusted Platform Module (TPM), a hardware security
+/// chip that provides a root of trust for a computer system.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/secprov/win32-tpm>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_Tpm {
+    /// Whether the TPM was activated when the provider was loaded.
+    pub IsActivated_InitialValue: Option<bool>,
+    /// Whether the TPM was enabled when the provider was loaded.
+    pub IsEnabled_InitialValue: Option<bool>,
+    /// Whether the TPM had an owner when the provider was loaded.
+    pub IsOwned_InitialValue: Option<bool>,
+    /// Manufacturer identifier, the four ASCII characters of the vendor packed in an integer.
+    pub ManufacturerId: Option<u32>,
+    /// Manufacturer identifier as text, e.g. "INTC" or "MSFT".
+    pub ManufacturerIdTxt: Option<String>,
+    /// Firmware version of the TPM.
+    pub ManufacturerVersion: Option<String>,
+    /// Full firmware version of a TPM 2.0.
+    pub ManufacturerVersionFull20: Option<String>,
+    /// Additional firmware information from the manufacturer.
+    pub ManufacturerVersionInfo: Option<String>,
+    /// Version of the Physical Presence Interface supported by the computer.
+    pub PhysicalPresenceVersionInfo: Option<String>,
+    /// TCG specification versions the TPM supports, e.g. "2.0, 0, 1.59": the specification
+    /// version, level and revision.
+    pub SpecVersion: Option<String>,
+}
+
+/// The `Win32_EncryptableVolume` WMI class represents an area of storage on a hard disk that can
+/// be protected by using BitLocker Drive Encryption.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/secprov/win32-encryptablevolume>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_EncryptableVolume {
+    /// Encryption state of the volume.
+    ///
+    /// - `Fully Decrypted` (0)
+    /// - `Fully Encrypted` (1)
+    /// - `Encryption In Progress` (2)
+    /// - `Decryption In Progress` (3)
+    /// - `Encryption Paused` (4)
+    /// - `Decryption Paused` (5)
+    pub ConversionStatus: Option<u32>,
+    /// Unique identifier of the volume, matches `Win32_Volume.DeviceID`.
+    pub DeviceID: Option<String>,
+    /// Drive letter of the volume, `null` for volumes without one.
+    pub DriveLetter: Option<String>,
+    /// Algorithm used to encrypt the volume.
+    ///
+    /// - `None` (0)
+    /// - `AES 128 With Diffuser` (1)
+    /// - `AES 256 With Diffuser` (2)
+    /// - `AES 128` (3)
+    /// - `AES 256` (4)
+    /// - `Hardware Encryption` (5)
+    /// - `XTS-AES 128` (6)
+    /// - `XTS-AES 256` (7)
+    pub EncryptionMethod: Option<u32>,
+    /// Whether the volume has been initialized for protection.
+    pub IsVolumeInitializedForProtection: Option<bool>,
+    /// Persistent identifier of the volume, stable across reboots.
+    pub PersistentVolumeID: Option<String>,
+    /// Whether the volume is protected, i.e. encrypted with at least one key protector enabled.
+    ///
+    /// - `Protection Off` (0)
+    /// - `Protection On` (1)
+    /// - `Protection Unknown` (2): the volume is locked
+    pub ProtectionStatus: Option<u32>,
+    /// Kind of volume.
+    ///
+    /// - `Operating System Volume` (0)
+    /// - `Fixed Data Volume` (1)
+    /// - `Portable Data Volume` (2)
+    /// - `Virtual Data Volume` (3)
+    pub VolumeType: Option<u32>,
+}
@@ -0,0 +1,221 @@
+//! Name resolution: the DNS client cache and the hosts file.
+//!
+//! | Source                                                                                  | Description                                                                                                                    |
+//! |-----------------------------------------------------------------------------------------|--------------------------------------------------------------------------------------------------------------------------------|
+//! | [**MSFT\_DNSClientCache**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/dnsclientcimprov/msft-dnsclientcache) | Instance class<br/> Represents a record cached by the DNS client, lives in the `root\StandardCimv2` namespace. |
+//! | `hosts` file                                                                            | Static names under `%SystemRoot%\System32\drivers\etc`, resolved before DNS is asked.                                         |
+//!
+//! The hosts file can list internal names, so it is only read once [`HostsFile::enabled`] is set,
+//! e.g. with [`HostsFile::collected`] or the `WINDOWS_SNAPSHOT_HOSTS_FILE` variable of
+//! [`Config`](crate::config::Config). Malware redirecting update or security vendor domains
+//! through the hosts file then shows up as
+//! [`DriftCategory::HostsEntry`](crate::drift::DriftCategory::HostsEntry) deviations. Cached records
+//! count down their `TimeToLive`, so [`DnsClientCache`] reports a state change on nearly every
+//! update.
+
+use crate::{state_member, update, Unavailable, STANDARD_CIMV2_NAMESPACE};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection};
+
+/// Represents the state of the Windows DNS client cache
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct DnsClientCache {
+    /// Sequence of cached DNS records
+    pub dns_client_cache: Vec<MSFT_DNSClientCache>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(DnsClientCache, dns_client_cache, STANDARD_CIMV2_NAMESPACE);
+
+impl DnsClientCache {
+    /// Cached records of the name `entry`, e.g. `www.example.com`
+    pub fn lookup(&self, entry: &str) -> Vec<&MSFT_DNSClientCache> {
+        self.dns_client_cache
+            .iter()
+            .filter(|record| {
+                record
+                    .Entry
+                    .as_deref()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(entry))
+            })
+            .collect()
+    }
+}
+
+/// Represents the state of the Windows hosts file
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct HostsFile {
+    /// Sequence of names the hosts file maps, one entry per name
+    pub hosts_entries: Vec<HostsEntry>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the file could not be read, `None` when it was or does not exist
+    pub unavailable: Option<crate::Unavailable>,
+    /// Read the hosts file on update, off by default, `hosts_entries` stays empty until set
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+state_member!(HostsFile, hosts_entries);
+
+impl HostsFile {
+    /// Creates an empty state that reads the hosts file on update
+    pub fn collected() -> Self {
+        HostsFile {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        if !self.enabled {
+            return self.refresh(Ok(Vec::new()));
+        }
+
+        let path = hosts_path();
+
+        self.refresh(match std::fs::read(&path) {
+            Ok(contents) => Ok(parse_hosts(&String::from_utf8_lossy(&contents))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(Unavailable::from_io(&path.to_string_lossy(), &e)),
+        });
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        if !self.enabled {
+            return self.refresh(Ok(Vec::new()));
+        }
+
+        let path = hosts_path();
+
+        self.refresh(match tokio::fs::read(&path).await {
+            Ok(contents) => Ok(parse_hosts(&String::from_utf8_lossy(&contents))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(Unavailable::from_io(&path.to_string_lossy(), &e)),
+        });
+    }
+
+    /// Address the hosts file maps `hostname` to, the first one if it is listed several times
+    pub fn resolve(&self, hostname: &str) -> Option<&str> {
+        self.hosts_entries
+            .iter()
+            .find(|entry| entry.hostname.eq_ignore_ascii_case(hostname))
+            .map(|entry| entry.address.as_str())
+    }
+
+    fn refresh(&mut self, entries: Result<Vec<HostsEntry>, Unavailable>) {
+        let old_vec = std::mem::take(&mut self.hosts_entries);
+        let old_unavailable = self.unavailable.take();
+
+        match entries {
+            Ok(entries) => self.hosts_entries = entries,
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.hosts_entries.len() != old_vec.len()
+            || crate::hash_vec(&self.hosts_entries) != crate::hash_vec(&old_vec);
+    }
+}
+
+impl Default for HostsFile {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        HostsFile {
+            hosts_entries: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+            enabled: false,
+        }
+    }
+}
+
+/// A name mapped by the hosts file
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct HostsEntry {
+    /// Address the name resolves to, e.g. `127.0.0.1`
+    pub address: String,
+    /// Name mapped, as written in the file
+    pub hostname: String,
+}
+
+/// The `MSFT_DNSClientCache` WMI class represents a record cached by the DNS client.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/dnsclientcimprov/msft-dnsclientcache>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_DNSClientCache {
+    /// Name that was looked up.
+    pub Entry: Option<String>,
+    /// Owner name of the record, e.g. the canonical name of an alias.
+    pub Name: Option<String>,
+    /// Type of the record, e.g. `A` (1), `CNAME` (5), `PTR` (12) or `AAAA` (28).
+    pub Type: Option<u16>,
+    /// Status of the lookup, 0 on success or a `DNS_ERROR` code such as 9003 (name does not exist).
+    pub Status: Option<u32>,
+    /// Section of the response the record comes from.
+    ///
+    /// - `Answer` (1)
+    /// - `Authority` (2)
+    /// - `Additional` (3)
+    pub Section: Option<u8>,
+    /// Seconds left before the record expires.
+    pub TimeToLive: Option<u32>,
+    /// Length of `Data`, in bytes.
+    pub DataLength: Option<u16>,
+    /// Data of the record, e.g. the address of an `A` record.
+    pub Data: Option<String>,
+}
+
+/// Path of the hosts file, under `%SystemRoot%`
+fn hosts_path() -> PathBuf {
+    let system_root = std::env::var_os("SystemRoot").unwrap_or_else(|| "C:\\Windows".into());
+
+    PathBuf::from(system_root)
+        .join("System32")
+        .join("drivers")
+        .join("etc")
+        .join("hosts")
+}
+
+/// Parses hosts file lines of the form `address name [name ...] [# comment]`
+fn parse_hosts(contents: &str) -> Vec<HostsEntry> {
+    // Notepad used to save the file with a byte order mark
+    contents
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .flat_map(|line| {
+            let line = line.split('#').next().unwrap_or_default();
+            let mut fields = line.split_whitespace();
+            let address = fields.next().unwrap_or_default();
+
+            fields.map(move |hostname| HostsEntry {
+                address: address.to_string(),
+                hostname: hostname.to_string(),
+            })
+        })
+        .collect()
+}
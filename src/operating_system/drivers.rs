@@ -3,17 +3,41 @@
 //! | Class                                             | Description                                                                           |
 //! |---------------------------------------------------|---------------------------------------------------------------------------------------|
 //! | [**Win32\_SystemDriver**](Win32_SystemDriver) | Instance class<br/> Represents the system driver for a base service.<br/> |
+//!
+//! With [`DriversEnrichmentOptions`] the driver files are also hashed and their Authenticode
+//! signature verified, which WMI does not report.
 
-use crate::update;
+use crate::{keyed, state_member, Unavailable, DEFAULT_NAMESPACE};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::os::windows::io::AsRawHandle;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
-use wmi::{COMLibrary, WMIConnection, WMIDateTime};
+use windows::core::{HSTRING, PCWSTR};
+use windows::Win32::Foundation::{HANDLE, HWND};
+use windows::Win32::Security::Cryptography::Catalog::{
+    CryptCATAdminAcquireContext2, CryptCATAdminCalcHashFromFileHandle2,
+    CryptCATAdminEnumCatalogFromHash, CryptCATAdminReleaseCatalogContext,
+    CryptCATAdminReleaseContext, CryptCATCatalogInfoFromContext, CATALOG_INFO,
+};
+use windows::Win32::Security::WinTrust::{
+    WinVerifyTrust, WINTRUST_ACTION_GENERIC_VERIFY_V2, WINTRUST_CATALOG_INFO, WINTRUST_DATA,
+    WINTRUST_FILE_INFO, WTD_CHOICE_CATALOG, WTD_CHOICE_FILE, WTD_REVOKE_NONE,
+    WTD_STATEACTION_CLOSE, WTD_STATEACTION_VERIFY, WTD_UI_NONE,
+};
+use wmi::{COMLibrary, WMIConnection, WMIDateTime, WMIResult};
 
 /// Represents the state of Windows Drivers
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct Drivers {
     /// Sequence of Drivers based on when they were loaded in chronological order
     pub drivers: Vec<Win32_SystemDriver>,
+    /// Details read from the driver files on every update
+    pub enrichment: DriversEnrichmentOptions,
+    /// Details of the driver files, one per driver with a `PathName`, empty unless `enrichment`
+    /// asks for something
+    pub driver_files: Vec<DriverFile>,
     /// When was the record last updated
     pub last_updated: SystemTime,
     /// Signifies change in state
@@ -21,9 +45,427 @@ pub struct Drivers {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(Drivers, drivers; driver_files);
+
+keyed!(Drivers, drivers, Win32_SystemDriver, Name: String);
+
+impl Drivers {
+    /// Creates an empty state that also reads the details of the driver files selected by
+    /// `enrichment`
+    pub fn with_enrichment(enrichment: DriversEnrichmentOptions) -> Self {
+        Drivers {
+            enrichment,
+            ..Default::default()
+        }
+    }
+
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        let com_con = unsafe { COMLibrary::assume_initialized() };
+
+        self.last_updated = SystemTime::now();
+
+        let drivers = crate::retry::policy().run(|| {
+            WMIConnection::new(com_con).and_then(|wmi_con| crate::limits::query(&wmi_con))
+        });
+
+        self.refresh(drivers);
+    }
+
+    /// Update fields asynchronously
+    ///
+    /// The driver files are read and verified on the calling thread once the query completes.
+    pub async fn async_update(&mut self) {
+        let com_con = unsafe { COMLibrary::assume_initialized() };
+
+        self.last_updated = SystemTime::now();
+
+        let drivers = crate::retry::policy()
+            .run_async(|| async move {
+                match WMIConnection::new(com_con) {
+                    Ok(wmi_con) => crate::limits::async_query(&wmi_con).await,
+                    Err(e) => Err(e),
+                }
+            })
+            .await;
+
+        self.refresh(drivers);
+    }
+
+    fn refresh(&mut self, drivers: WMIResult<Vec<Win32_SystemDriver>>) {
+        let old_vec = std::mem::take(&mut self.drivers);
+        let old_files = std::mem::take(&mut self.driver_files);
+        let old_unavailable = self.unavailable.take();
+
+        match drivers {
+            Ok(drivers) => self.drivers = drivers,
+            Err(e) => {
+                self.unavailable = Some(Unavailable::new(DEFAULT_NAMESPACE, "Win32_SystemDriver", &e))
+            }
+        }
+
+        if self.enrichment.enabled() {
+            self.driver_files = self
+                .drivers
+                .iter()
+                .filter_map(|driver| DriverFile::read(driver, &self.enrichment, &old_files))
+                .collect();
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.drivers.len() != old_vec.len()
+            || crate::hash_vec(&self.drivers) != crate::hash_vec(&old_vec)
+            || crate::hash_vec(&self.driver_files) != crate::hash_vec(&old_files);
+    }
+
+    /// Details of the file of the driver `name`
+    pub fn file_of(&self, name: &str) -> Option<&DriverFile> {
+        self.driver_files
+            .iter()
+            .find(|file| file.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Driver files whose signature was checked and is not valid
+    pub fn unsigned_or_invalid(&self) -> Vec<&DriverFile> {
+        self.driver_files
+            .iter()
+            .filter(|file| file.signature.is_some_and(|signature| !signature.is_valid()))
+            .collect()
+    }
+}
+
+impl Default for Drivers {
+    /// Driver files are not read by default, `last_updated` defaults to the current `SystemTime`
+    fn default() -> Self {
+        Drivers {
+            drivers: Vec::new(),
+            enrichment: DriversEnrichmentOptions::default(),
+            driver_files: Vec::new(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
 }
 
-update!(Drivers, drivers);
+/// Details read from the driver files, in addition to what WMI reports
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub struct DriversEnrichmentOptions {
+    /// Compute the SHA-256 hash of every driver file
+    pub sha256: bool,
+    /// Verify the Authenticode signature of every driver file, embedded or from a catalog
+    pub signature: bool,
+}
+
+impl DriversEnrichmentOptions {
+    /// Hash and verify every driver file
+    pub fn all() -> Self {
+        DriversEnrichmentOptions {
+            sha256: true,
+            signature: true,
+        }
+    }
+
+    /// Returns `true` if anything is read from the driver files
+    pub fn enabled(&self) -> bool {
+        self.sha256 || self.signature
+    }
+}
+
+/// Details of the file implementing a driver
+///
+/// Files are only read again when their size or modification time changed since the previous
+/// update.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct DriverFile {
+    /// `Name` of the driver
+    pub name: String,
+    /// `PathName` of the driver resolved to a file path, e.g. `\SystemRoot\` replaced by the
+    /// Windows directory
+    pub path: PathBuf,
+    /// Size of the file in bytes
+    pub size: Option<u64>,
+    /// When the file was last modified
+    pub modified: Option<SystemTime>,
+    /// SHA-256 hash of the file, as lowercase hexadecimal
+    pub sha256: Option<String>,
+    /// Authenticode signature status of the file
+    pub signature: Option<SignatureStatus>,
+    /// Why the file could not be read, `None` when it was
+    pub error: Option<String>,
+}
+
+impl DriverFile {
+    fn read(
+        driver: &Win32_SystemDriver,
+        options: &DriversEnrichmentOptions,
+        previous: &[DriverFile],
+    ) -> Option<Self> {
+        let name = driver.Name.clone()?;
+        let path = resolve_driver_path(driver.PathName.as_deref()?);
+
+        let mut file = DriverFile {
+            name,
+            path,
+            size: None,
+            modified: None,
+            sha256: None,
+            signature: None,
+            error: None,
+        };
+
+        let metadata = match std::fs::metadata(&file.path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                file.error = Some(e.to_string());
+                return Some(file);
+            }
+        };
+
+        file.size = Some(metadata.len());
+        file.modified = metadata.modified().ok();
+
+        let unchanged = previous.iter().find(|old| {
+            old.path == file.path
+                && old.size == file.size
+                && old.modified == file.modified
+                && old.error.is_none()
+                && (!options.sha256 || old.sha256.is_some())
+                && (!options.signature || old.signature.is_some())
+        });
+
+        if let Some(old) = unchanged {
+            file.sha256 = old.sha256.clone().filter(|_| options.sha256);
+            file.signature = old.signature.filter(|_| options.signature);
+            return Some(file);
+        }
+
+        if options.sha256 {
+            match sha256(&file.path) {
+                Ok(hash) => file.sha256 = Some(hash),
+                Err(e) => file.error = Some(e.to_string()),
+            }
+        }
+
+        if options.signature {
+            file.signature = Some(verify_signature(&file.path));
+        }
+
+        Some(file)
+    }
+}
+
+/// Authenticode signature status of a file
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// Signed with an embedded signature chaining to a trusted root
+    Signed,
+    /// Listed in a signed system catalog, as most inbox drivers are
+    SignedByCatalog,
+    /// Neither signed nor listed in a catalog
+    Unsigned,
+    /// Signed, but the certificate is not trusted, expired or revoked
+    Untrusted,
+    /// Signed, but the file does not match its signature
+    Tampered,
+    /// Verification failed with another `HRESULT`
+    Error(u32),
+}
+
+impl SignatureStatus {
+    /// Returns `true` for a valid embedded or catalog signature
+    pub fn is_valid(&self) -> bool {
+        matches!(self, SignatureStatus::Signed | SignatureStatus::SignedByCatalog)
+    }
+
+    fn from_hresult(hresult: i32, by_catalog: bool) -> Self {
+        match hresult as u32 {
+            0 if by_catalog => SignatureStatus::SignedByCatalog,
+            0 => SignatureStatus::Signed,
+            TRUST_E_NOSIGNATURE | TRUST_E_SUBJECT_FORM_UNKNOWN | TRUST_E_PROVIDER_UNKNOWN => {
+                SignatureStatus::Unsigned
+            }
+            TRUST_E_BAD_DIGEST => SignatureStatus::Tampered,
+            TRUST_E_SUBJECT_NOT_TRUSTED | TRUST_E_EXPLICIT_DISTRUST | CERT_E_EXPIRED
+            | CERT_E_REVOKED | CERT_E_UNTRUSTEDROOT | CERT_E_CHAINING => SignatureStatus::Untrusted,
+            hresult => SignatureStatus::Error(hresult),
+        }
+    }
+}
+
+const TRUST_E_PROVIDER_UNKNOWN: u32 = 0x800B_0001;
+const TRUST_E_SUBJECT_FORM_UNKNOWN: u32 = 0x800B_0003;
+const TRUST_E_SUBJECT_NOT_TRUSTED: u32 = 0x800B_0004;
+const TRUST_E_NOSIGNATURE: u32 = 0x800B_0100;
+const CERT_E_EXPIRED: u32 = 0x800B_0101;
+const CERT_E_UNTRUSTEDROOT: u32 = 0x800B_0109;
+const CERT_E_CHAINING: u32 = 0x800B_010A;
+const CERT_E_REVOKED: u32 = 0x800B_010C;
+const TRUST_E_EXPLICIT_DISTRUST: u32 = 0x800B_0111;
+const TRUST_E_BAD_DIGEST: u32 = 0x8009_6010;
+
+/// Resolves the forms `PathName` takes for drivers: `\SystemRoot\...`, `System32\...`,
+/// `\??\C:\...` and plain paths
+pub(crate) fn resolve_driver_path(path_name: &str) -> PathBuf {
+    let path = path_name.trim().trim_matches('"');
+    let system_root = std::env::var("SystemRoot").unwrap_or_else(|_| String::from("C:\\Windows"));
+
+    let strip_prefix = |prefix: &str| {
+        path.get(..prefix.len())
+            .filter(|start| start.eq_ignore_ascii_case(prefix))
+            .map(|_| &path[prefix.len()..])
+    };
+
+    if let Some(rest) = strip_prefix("\\SystemRoot\\") {
+        Path::new(&system_root).join(rest)
+    } else if let Some(rest) = strip_prefix("\\??\\") {
+        PathBuf::from(rest)
+    } else if strip_prefix("System32\\").is_some() || strip_prefix("SysWOW64\\").is_some() {
+        Path::new(&system_root).join(path)
+    } else {
+        PathBuf::from(path)
+    }
+}
+
+fn sha256(path: &Path) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+
+    std::io::copy(&mut File::open(path)?, &mut hasher)?;
+
+    Ok(hex(&hasher.finalize()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Verifies the embedded signature of `path`, falling back to the system catalogs
+pub(crate) fn verify_signature(path: &Path) -> SignatureStatus {
+    let wide_path = HSTRING::from(path);
+
+    let mut file_info = WINTRUST_FILE_INFO {
+        cbStruct: std::mem::size_of::<WINTRUST_FILE_INFO>() as u32,
+        pcwszFilePath: PCWSTR(wide_path.as_ptr()),
+        ..Default::default()
+    };
+
+    let mut data = WINTRUST_DATA {
+        dwUnionChoice: WTD_CHOICE_FILE,
+        ..trust_data()
+    };
+    data.Anonymous.pFile = &mut file_info;
+
+    let hresult = unsafe { win_verify_trust(&mut data) };
+
+    if hresult as u32 != TRUST_E_NOSIGNATURE {
+        return SignatureStatus::from_hresult(hresult, false);
+    }
+
+    // Catalogs are indexed by SHA-256 on current systems and SHA-1 on older ones
+    for algorithm in [Some("SHA256"), None] {
+        if let Some(hresult) = unsafe { verify_catalog_signature(path, &wide_path, algorithm) } {
+            return SignatureStatus::from_hresult(hresult, true);
+        }
+    }
+
+    SignatureStatus::Unsigned
+}
+
+/// Verifies `path` against the catalog listing its hash, `None` when no catalog lists it
+unsafe fn verify_catalog_signature(
+    path: &Path,
+    wide_path: &HSTRING,
+    algorithm: Option<&str>,
+) -> Option<i32> {
+    let file = File::open(path).ok()?;
+    let handle = HANDLE(file.as_raw_handle() as isize);
+
+    let mut cat_admin = 0;
+    let algorithm = algorithm.map(HSTRING::from);
+    let algorithm = algorithm
+        .as_ref()
+        .map_or(PCWSTR::null(), |algorithm| PCWSTR(algorithm.as_ptr()));
+
+    if !CryptCATAdminAcquireContext2(&mut cat_admin, None, algorithm, None, 0).as_bool() {
+        return None;
+    }
+
+    let mut size = 0;
+    CryptCATAdminCalcHashFromFileHandle2(cat_admin, handle, &mut size, None, 0);
+
+    let mut hash = vec![0u8; size as usize];
+    let mut hresult = None;
+
+    if CryptCATAdminCalcHashFromFileHandle2(cat_admin, handle, &mut size, Some(hash.as_mut_ptr()), 0)
+        .as_bool()
+    {
+        let cat_info = CryptCATAdminEnumCatalogFromHash(cat_admin, &hash, 0, None);
+
+        if cat_info != 0 {
+            let mut catalog = CATALOG_INFO {
+                cbStruct: std::mem::size_of::<CATALOG_INFO>() as u32,
+                ..Default::default()
+            };
+
+            if CryptCATCatalogInfoFromContext(cat_info, &mut catalog, 0).as_bool() {
+                let member_tag = HSTRING::from(hex(&hash).to_uppercase());
+
+                let mut catalog_info = WINTRUST_CATALOG_INFO {
+                    cbStruct: std::mem::size_of::<WINTRUST_CATALOG_INFO>() as u32,
+                    pcwszCatalogFilePath: PCWSTR(catalog.wszCatalogFile.as_ptr()),
+                    pcwszMemberTag: PCWSTR(member_tag.as_ptr()),
+                    pcwszMemberFilePath: PCWSTR(wide_path.as_ptr()),
+                    hMemberFile: handle,
+                    pbCalculatedFileHash: hash.as_mut_ptr(),
+                    cbCalculatedFileHash: size,
+                    hCatAdmin: cat_admin,
+                    ..Default::default()
+                };
+
+                let mut data = WINTRUST_DATA {
+                    dwUnionChoice: WTD_CHOICE_CATALOG,
+                    ..trust_data()
+                };
+                data.Anonymous.pCatalog = &mut catalog_info;
+
+                hresult = Some(win_verify_trust(&mut data));
+            }
+
+            CryptCATAdminReleaseCatalogContext(cat_admin, cat_info, 0);
+        }
+    }
+
+    CryptCATAdminReleaseContext(cat_admin, 0);
+
+    hresult
+}
+
+/// `WINTRUST_DATA` without UI and revocation checks, which would go to the network
+fn trust_data() -> WINTRUST_DATA {
+    WINTRUST_DATA {
+        cbStruct: std::mem::size_of::<WINTRUST_DATA>() as u32,
+        dwUIChoice: WTD_UI_NONE,
+        fdwRevocationChecks: WTD_REVOKE_NONE,
+        ..Default::default()
+    }
+}
+
+/// Runs the generic Authenticode policy on `data` and releases the state it allocates
+unsafe fn win_verify_trust(data: &mut WINTRUST_DATA) -> i32 {
+    let mut action = WINTRUST_ACTION_GENERIC_VERIFY_V2;
+
+    data.dwStateAction = WTD_STATEACTION_VERIFY;
+    let hresult = WinVerifyTrust(HWND::default(), &mut action, data as *mut _ as *mut _);
+
+    data.dwStateAction = WTD_STATEACTION_CLOSE;
+    WinVerifyTrust(HWND::default(), &mut action, data as *mut _ as *mut _);
+
+    hresult
+}
 
 /// The `Win32_SystemDriver` WMI class represents a process on an operating system.
 ///
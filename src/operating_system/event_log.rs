@@ -8,11 +8,14 @@
 //! | [**Win32\_NTLogEventLog**](/previous-versions/windows/desktop/eventlogprov/win32-ntlogeventlog)           | Association class<br/> Relates instances of [**Win32\_NTLogEvent**](/previous-versions/windows/desktop/eventlogprov/win32-ntlogevent) and [**Win32\_NTEventlogFile**](/previous-versions/windows/desktop/legacy/aa394225(v=vs.85)) classes.<br/> |
 //! | [**Win32\_NTLogEventUser**](/previous-versions/windows/desktop/eventlogprov/win32-ntlogeventuser)         | Association class<br/> Relates instances of [**Win32\_NTLogEvent**](/previous-versions/windows/desktop/eventlogprov/win32-ntlogevent) and [**Win32\_UserAccount**](win32-useraccount).<br/>               |
 
-use crate::update;
+use crate::{batched, cim_datetime, state_member, update, wql_string, Unavailable};
 use serde::{Deserialize, Serialize};
-use std::time::SystemTime;
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
 
+const LOG_FILES_QUERY: &str = "SELECT LogfileName, NumberOfRecords FROM Win32_NTEventlogFile";
+
 /// Represents the state of Windows `NTEventlogFiles`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct NTEventlogFiles {
@@ -25,6 +28,8 @@ pub struct NTEventlogFiles {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NTEventlogFiles, nt_event_log_files);
@@ -41,9 +46,720 @@ pub struct NTLogEvents {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NTLogEvents, nt_log_events);
+batched!(NTLogEvents, Win32_NTLogEvent);
+
+/// Restricts which `Win32_NTLogEvent` instances are queried, empty criteria match everything
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct EventLogFilter {
+    /// Names of the logs to query, e.g. "System" or "Security"
+    pub logs: Vec<String>,
+    /// Event identifiers (`EventCode`) to match
+    pub event_codes: Vec<u16>,
+    /// Severities (`EventType`) to match
+    ///
+    /// - `Error` (1)
+    /// - `Warning` (2)
+    /// - `Information` (3)
+    /// - `Security Audit Success` (4)
+    /// - `Security Audit Failure` (5)
+    pub event_types: Vec<u8>,
+    /// Only events written at or after this time
+    pub since: Option<SystemTime>,
+    /// Only events written at or before this time
+    pub until: Option<SystemTime>,
+}
+
+impl EventLogFilter {
+    /// Builds the WQL query for `log`, skipping records up to and including `after_record`
+    fn query(&self, log: &str, after_record: Option<u32>) -> String {
+        let mut conditions = vec![format!("Logfile = {}", wql_string(log))];
+
+        if !self.event_codes.is_empty() {
+            conditions.push(any_of("EventCode", &self.event_codes));
+        }
+        if !self.event_types.is_empty() {
+            conditions.push(any_of("EventType", &self.event_types));
+        }
+        if let Some(since) = self.since {
+            conditions.push(format!("TimeWritten >= '{}'", cim_datetime(since)));
+        }
+        if let Some(until) = self.until {
+            conditions.push(format!("TimeWritten <= '{}'", cim_datetime(until)));
+        }
+        if let Some(record) = after_record {
+            conditions.push(format!("RecordNumber > {record}"));
+        }
+
+        format!("SELECT * FROM Win32_NTLogEvent WHERE {}", conditions.join(" AND "))
+    }
+}
+
+fn any_of<T: std::fmt::Display>(property: &str, values: &[T]) -> String {
+    let alternatives = values
+        .iter()
+        .map(|value| format!("{property} = {value}"))
+        .collect::<Vec<_>>();
+
+    format!("({})", alternatives.join(" OR "))
+}
+
+/// Represents the state of Windows `NTLogEvents` matching an [`EventLogFilter`]
+///
+/// In incremental mode the highest `RecordNumber` seen in every log is remembered, and later
+/// updates only fetch the events written since, so `nt_log_events` holds just the new events.
+/// A log is considered cleared, and fetched again from its first record, when its
+/// `NumberOfRecords` decreased or no record at or past the remembered `RecordNumber` remains.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct FilteredLogEvents {
+    /// Filter applied to every query
+    pub filter: EventLogFilter,
+    /// Only fetch events newer than the ones seen by the previous update
+    pub incremental: bool,
+    /// Highest `RecordNumber` seen per log
+    pub last_record_numbers: BTreeMap<String, u32>,
+    /// `NumberOfRecords` per log at the previous incremental update
+    #[serde(default)]
+    pub record_counts: BTreeMap<String, u32>,
+    /// Represents sequence of Windows `NTLogEvents`
+    pub nt_log_events: Vec<Win32_NTLogEvent>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+impl FilteredLogEvents {
+    /// Creates an empty state that fetches every event matching `filter` on each update
+    pub fn new(filter: EventLogFilter) -> Self {
+        FilteredLogEvents {
+            filter,
+            incremental: false,
+            last_record_numbers: BTreeMap::new(),
+            record_counts: BTreeMap::new(),
+            nt_log_events: Vec::new(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+
+    /// Creates an empty state that only fetches events written since the previous update
+    pub fn incremental(filter: EventLogFilter) -> Self {
+        FilteredLogEvents {
+            incremental: true,
+            ..FilteredLogEvents::new(filter)
+        }
+    }
+
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let events = self.fetch();
+
+        self.refresh(events);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let events = self.async_fetch().await;
+
+        self.refresh(events);
+    }
+
+    /// Events of every log, failing as a whole if any log cannot be queried
+    fn fetch(&self) -> Result<Vec<LogEvents>, Unavailable> {
+        let mut fetched = Vec::new();
+        let files: Vec<Win32_NTEventlogFile> = match self.incremental || self.filter.logs.is_empty() {
+            true => crate::raw_query(LOG_FILES_QUERY)?,
+            false => Vec::new(),
+        };
+
+        for log in self.logs(&files) {
+            let record_count = record_count(&files, &log);
+            let cleared = match self.after_record(&log) {
+                Some(cursor) if !self.shrunk(&log, record_count) => {
+                    crate::raw_query::<Win32_NTLogEvent>(&newer_records_query(&log, cursor))?.is_empty()
+                }
+                Some(_) => true,
+                None => false,
+            };
+            let after_record = self.after_record(&log).filter(|_| !cleared);
+            let events = crate::raw_query(&self.filter.query(&log, after_record))?;
+
+            fetched.push(LogEvents {
+                log,
+                record_count,
+                cleared,
+                events,
+            });
+        }
+
+        Ok(fetched)
+    }
+
+    /// Asynchronous [`FilteredLogEvents::fetch`]
+    async fn async_fetch(&self) -> Result<Vec<LogEvents>, Unavailable> {
+        let mut fetched = Vec::new();
+        let files: Vec<Win32_NTEventlogFile> = match self.incremental || self.filter.logs.is_empty() {
+            true => crate::async_raw_query(LOG_FILES_QUERY).await?,
+            false => Vec::new(),
+        };
+
+        for log in self.logs(&files) {
+            let record_count = record_count(&files, &log);
+            let cleared = match self.after_record(&log) {
+                Some(cursor) if !self.shrunk(&log, record_count) => {
+                    crate::async_raw_query::<Win32_NTLogEvent>(&newer_records_query(&log, cursor))
+                        .await?
+                        .is_empty()
+                }
+                Some(_) => true,
+                None => false,
+            };
+            let after_record = self.after_record(&log).filter(|_| !cleared);
+            let events = crate::async_raw_query(&self.filter.query(&log, after_record)).await?;
+
+            fetched.push(LogEvents {
+                log,
+                record_count,
+                cleared,
+                events,
+            });
+        }
+
+        Ok(fetched)
+    }
+
+    /// Logs named by the filter, or every log in `files`
+    fn logs(&self, files: &[Win32_NTEventlogFile]) -> Vec<String> {
+        if !self.filter.logs.is_empty() {
+            return self.filter.logs.clone();
+        }
+
+        files.iter().filter_map(|file| file.LogfileName.clone()).collect()
+    }
+
+    /// Returns `true` if `log` holds fewer records than at the previous update
+    fn shrunk(&self, log: &str, record_count: Option<u32>) -> bool {
+        record_count
+            .zip(self.record_counts.get(log))
+            .is_some_and(|(count, previous)| count < *previous)
+    }
+
+    /// Events of a log are kept only if every log could be queried
+    fn refresh(&mut self, events: Result<Vec<LogEvents>, Unavailable>) {
+        let old_vec = std::mem::take(&mut self.nt_log_events);
+        let old_unavailable = self.unavailable.take();
+
+        match events {
+            Ok(events) => {
+                for events in events {
+                    self.record(events);
+                }
+            }
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable || self.changed(&old_vec);
+    }
+
+    fn after_record(&self, log: &str) -> Option<u32> {
+        self.incremental
+            .then(|| self.last_record_numbers.get(log).copied())
+            .flatten()
+    }
+
+    fn record(&mut self, fetched: LogEvents) {
+        let LogEvents {
+            log,
+            record_count,
+            cleared,
+            events,
+        } = fetched;
+
+        if cleared {
+            self.last_record_numbers.remove(&log);
+        }
+
+        if let Some(count) = record_count.filter(|_| self.incremental) {
+            self.record_counts.insert(log.clone(), count);
+        }
+
+        if let Some(highest) = events.iter().filter_map(|event| event.RecordNumber).max() {
+            let last = self.last_record_numbers.entry(log).or_insert(highest);
+            *last = (*last).max(highest);
+        }
+
+        self.nt_log_events.extend(events);
+    }
+
+    fn changed(&self, old_vec: &[Win32_NTLogEvent]) -> bool {
+        if self.incremental {
+            !self.nt_log_events.is_empty()
+        } else {
+            self.nt_log_events.len() != old_vec.len()
+                || crate::hash_vec(&self.nt_log_events) != crate::hash_vec(old_vec)
+        }
+    }
+}
+
+/// Events fetched from a log by [`FilteredLogEvents`]
+struct LogEvents {
+    log: String,
+    /// `NumberOfRecords` of the log, when its file was queried
+    record_count: Option<u32>,
+    /// The log was cleared since the previous update, so `events` starts from its first record
+    cleared: bool,
+    events: Vec<Win32_NTLogEvent>,
+}
+
+/// `NumberOfRecords` of `log` among `files`
+fn record_count(files: &[Win32_NTEventlogFile], log: &str) -> Option<u32> {
+    files
+        .iter()
+        .find(|file| file.LogfileName.as_deref().is_some_and(|name| name.eq_ignore_ascii_case(log)))
+        .and_then(|file| file.NumberOfRecords)
+}
+
+/// Query for the records of `log` at or past `record`, regardless of the filter, empty once the
+/// log was cleared
+fn newer_records_query(log: &str, record: u32) -> String {
+    format!(
+        "SELECT RecordNumber FROM Win32_NTLogEvent WHERE Logfile = {} AND RecordNumber >= {record}",
+        wql_string(log)
+    )
+}
+
+impl Default for FilteredLogEvents {
+    /// An unfiltered, non incremental state, `last_updated` defaults to the current `SystemTime`
+    fn default() -> Self {
+        FilteredLogEvents::new(EventLogFilter::default())
+    }
+}
+
+/// How far back [`BootHistory`] looks by default, 30 days
+const BOOT_HISTORY_WINDOW: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Startup, shutdown and crash events recorded in the System log
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BootEventKind {
+    /// The event log service started, written at every boot (`EventLog` 6005)
+    Startup,
+    /// The event log service stopped, written at every clean shutdown (`EventLog` 6006)
+    Shutdown,
+    /// The previous shutdown was unexpected (`EventLog` 6008)
+    UnexpectedShutdown,
+    /// The system rebooted without cleanly shutting down first, after a crash, a hang or a power
+    /// loss (`Microsoft-Windows-Kernel-Power` 41)
+    KernelPowerLoss,
+}
+
+impl BootEventKind {
+    const EVENT_CODES: [u16; 4] = [6005, 6006, 6008, 41];
+
+    /// Kind of `event`, `None` for events of another source sharing one of the codes
+    fn of(event: &Win32_NTLogEvent) -> Option<Self> {
+        match (event.EventCode?, event.SourceName.as_deref()?) {
+            (6005, "EventLog") => Some(BootEventKind::Startup),
+            (6006, "EventLog") => Some(BootEventKind::Shutdown),
+            (6008, "EventLog") => Some(BootEventKind::UnexpectedShutdown),
+            (41, "Microsoft-Windows-Kernel-Power") => Some(BootEventKind::KernelPowerLoss),
+            _ => None,
+        }
+    }
+}
+
+/// A startup, shutdown or crash recorded in the System log
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct BootEvent {
+    /// What happened
+    pub kind: BootEventKind,
+    /// When the event was generated
+    pub time: Option<SystemTime>,
+    /// `RecordNumber` of the event in the System log
+    pub record_number: Option<u32>,
+}
+
+/// Represents the recent boot history of the machine, from the System log
+///
+/// Complements `LastBootUpTime` of `Win32_OperatingSystem` with the earlier boots, and with the
+/// crashes and power losses that preceded them.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct BootHistory {
+    /// How far back events are fetched
+    pub window: Duration,
+    /// Sequence of boot events, oldest first
+    pub boot_events: Vec<BootEvent>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(BootHistory, boot_events);
+
+impl BootHistory {
+    /// Creates an empty state that fetches the boot events of the last `window`
+    pub fn new(window: Duration) -> Self {
+        BootHistory {
+            window,
+            boot_events: Vec::new(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let events = crate::raw_query(&self.query());
+
+        self.refresh(events);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let events = crate::async_raw_query(&self.query()).await;
+
+        self.refresh(events);
+    }
+
+    /// Boots recorded in the window, oldest first
+    pub fn boots(&self) -> impl Iterator<Item = &BootEvent> {
+        self.of_kind(BootEventKind::Startup)
+    }
+
+    /// Crashes and power losses recorded in the window, oldest first
+    pub fn unexpected_shutdowns(&self) -> impl Iterator<Item = &BootEvent> {
+        self.of_kind(BootEventKind::KernelPowerLoss)
+    }
+
+    fn of_kind(&self, kind: BootEventKind) -> impl Iterator<Item = &BootEvent> {
+        self.boot_events
+            .iter()
+            .filter(move |event| event.kind == kind)
+    }
+
+    fn query(&self) -> String {
+        let filter = EventLogFilter {
+            event_codes: BootEventKind::EVENT_CODES.to_vec(),
+            since: SystemTime::now().checked_sub(self.window),
+            ..EventLogFilter::default()
+        };
+
+        filter.query("System", None)
+    }
+
+    fn refresh(&mut self, events: Result<Vec<Win32_NTLogEvent>, Unavailable>) {
+        let old_vec = std::mem::take(&mut self.boot_events);
+        let old_unavailable = self.unavailable.take();
+
+        match events {
+            Ok(events) => {
+                self.boot_events = events
+                    .iter()
+                    .filter_map(|event| {
+                        Some(BootEvent {
+                            kind: BootEventKind::of(event)?,
+                            time: event.TimeGenerated.as_ref().map(|time| SystemTime::from(time.0)),
+                            record_number: event.RecordNumber,
+                        })
+                    })
+                    .collect();
+                self.boot_events.sort_by_key(|event| event.record_number);
+            }
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.boot_events.len() != old_vec.len()
+            || crate::hash_vec(&self.boot_events) != crate::hash_vec(&old_vec);
+    }
+}
+
+impl Default for BootHistory {
+    /// The last 30 days of boot events, `last_updated` defaults to the current `SystemTime`
+    fn default() -> Self {
+        BootHistory::new(BOOT_HISTORY_WINDOW)
+    }
+}
+
+/// Event codes [`BootInfo`] is derived from: kernel power loss (41), shutdown initiated by a process
+/// (`User32` 1074), event log started (6005) and stopped (6006), unexpected shutdown (6008) and
+/// user logon notification (`Microsoft-Windows-Winlogon` 7001)
+const BOOT_INFO_EVENT_CODES: [u16; 6] = [41, 1074, 6005, 6006, 6008, 7001];
+
+/// How the shutdown preceding a boot ended
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ShutdownKind {
+    /// The system shut down cleanly, stopping the event log (`EventLog` 6006)
+    Clean,
+    /// The system crashed, hung or lost power (`EventLog` 6008 or `Microsoft-Windows-Kernel-Power`
+    /// 41 at the next boot)
+    Unexpected,
+}
+
+/// Why a process shut the system down or restarted it, from `User32` event 1074
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ShutdownReason {
+    /// Process that initiated the shutdown, e.g. `C:\Windows\system32\winlogon.exe (HOST)`
+    pub process: Option<String>,
+    /// User on whose behalf the process shut the system down, e.g. `CONTOSO\alice`
+    pub user: Option<String>,
+    /// Reason selected for the shutdown, e.g. `Operating System: Upgrade (Planned)`
+    pub reason: Option<String>,
+    /// Reason code, e.g. `0x80020003`
+    pub reason_code: Option<String>,
+    /// Type of shutdown, e.g. `restart` or `power off`
+    pub shutdown_type: Option<String>,
+    /// Comment given with the shutdown
+    pub comment: Option<String>,
+}
+
+impl ShutdownReason {
+    fn of(event: &Win32_NTLogEvent) -> Self {
+        let strings = event.InsertionStrings.as_deref().unwrap_or_default();
+        let string = |index: usize| {
+            strings
+                .get(index)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+        };
+
+        ShutdownReason {
+            process: string(0),
+            user: string(6),
+            reason: string(2),
+            reason_code: string(3),
+            shutdown_type: string(4),
+            comment: string(5),
+        }
+    }
+}
+
+/// The last boot, how the shutdown before it ended and how long the machine took to come up
+///
+/// Durations are measured from `LastBootUpTime` of `Win32_OperatingSystem`, to the start of the
+/// event log service for the services and to the first logon notification for the desktop.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct BootInfo {
+    /// When the system last booted
+    pub boot_time: Option<SystemTime>,
+    /// How the shutdown before the boot ended, `None` if the System log does not tell
+    pub last_shutdown: Option<ShutdownKind>,
+    /// When the system cleanly shut down before the boot, `None` after an unexpected shutdown
+    pub last_shutdown_time: Option<SystemTime>,
+    /// Why the system was shut down before the boot, `None` when no process recorded a reason
+    pub last_shutdown_reason: Option<ShutdownReason>,
+    /// Time from the boot to the start of the event log service
+    pub boot_to_event_log: Option<Duration>,
+    /// Time from the boot to the first user logon notification
+    pub boot_to_logon: Option<Duration>,
+}
+
+impl BootInfo {
+    /// Derives the boot information from the boot events in `events`, falling back on the last
+    /// start of the event log when `boot_time` is unknown
+    fn new(boot_time: Option<SystemTime>, events: &[Win32_NTLogEvent]) -> Self {
+        let mut events = events
+            .iter()
+            .filter_map(|event| {
+                let time = SystemTime::from(event.TimeGenerated.as_ref()?.0);
+
+                Some((time, event.EventCode?, event.SourceName.as_deref()?, event))
+            })
+            .collect::<Vec<_>>();
+        events.sort_by_key(|(time, ..)| *time);
+
+        let boot_time = boot_time.or_else(|| {
+            events
+                .iter()
+                .rev()
+                .find(|(_, code, source, _)| (*code, *source) == (6005, "EventLog"))
+                .map(|(time, ..)| *time)
+        });
+
+        let Some(boot_time) = boot_time else {
+            return BootInfo {
+                boot_time: None,
+                last_shutdown: None,
+                last_shutdown_time: None,
+                last_shutdown_reason: None,
+                boot_to_event_log: None,
+                boot_to_logon: None,
+            };
+        };
+
+        let (before, after) = events.split_at(events.partition_point(|(time, ..)| *time < boot_time));
+
+        // events of the previous run, since the event log last started before the boot
+        let previous_run = before
+            .iter()
+            .rposition(|(_, code, source, _)| (*code, *source) == (6005, "EventLog"))
+            .map_or(before, |start| &before[start + 1..]);
+
+        let unexpected = after.iter().any(|(_, code, source, _)| {
+            matches!(
+                (*code, *source),
+                (6008, "EventLog") | (41, "Microsoft-Windows-Kernel-Power")
+            )
+        });
+        let clean_shutdown = previous_run
+            .iter()
+            .rev()
+            .find(|(_, code, source, _)| (*code, *source) == (6006, "EventLog"))
+            .map(|(time, ..)| *time);
+        let last_shutdown = match (unexpected, clean_shutdown) {
+            (true, _) => Some(ShutdownKind::Unexpected),
+            (false, Some(_)) => Some(ShutdownKind::Clean),
+            (false, None) => None,
+        };
+
+        let since_boot = |code: u16, source: &str| {
+            after
+                .iter()
+                .find(|(_, event_code, event_source, _)| (*event_code, *event_source) == (code, source))
+                .and_then(|(time, ..)| time.duration_since(boot_time).ok())
+        };
+
+        BootInfo {
+            boot_time: Some(boot_time),
+            last_shutdown,
+            last_shutdown_time: clean_shutdown.filter(|_| !unexpected),
+            last_shutdown_reason: previous_run
+                .iter()
+                .rev()
+                .find(|(_, code, source, _)| (*code, *source) == (1074, "User32"))
+                .map(|(.., event)| ShutdownReason::of(event)),
+            boot_to_event_log: since_boot(6005, "EventLog"),
+            boot_to_logon: since_boot(7001, "Microsoft-Windows-Winlogon"),
+        }
+    }
+}
+
+/// `LastBootUpTime` of `Win32_OperatingSystem`
+#[derive(Deserialize)]
+#[allow(non_snake_case)]
+struct LastBootUpTime {
+    LastBootUpTime: Option<WMIDateTime>,
+}
+
+/// Represents the state of the last boot of the machine, from `Win32_OperatingSystem` and the
+/// System log
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct BootInfos {
+    /// The last boot, a single entry once updated
+    pub boot_infos: Vec<BootInfo>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(BootInfos, boot_infos);
+
+impl BootInfos {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let boot_time = crate::raw_query(BOOT_TIME_QUERY).ok().and_then(boot_time);
+        let events = crate::raw_query(&boot_info_query(boot_time));
+
+        self.refresh(boot_time, events);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let boot_time = crate::async_raw_query(BOOT_TIME_QUERY).await.ok().and_then(boot_time);
+        let events = crate::async_raw_query(&boot_info_query(boot_time)).await;
+
+        self.refresh(boot_time, events);
+    }
+
+    /// The last boot, `None` until updated
+    pub fn last(&self) -> Option<&BootInfo> {
+        self.boot_infos.first()
+    }
+
+    fn refresh(&mut self, boot_time: Option<SystemTime>, events: Result<Vec<Win32_NTLogEvent>, Unavailable>) {
+        let old_vec = std::mem::take(&mut self.boot_infos);
+        let old_unavailable = self.unavailable.take();
+
+        match events {
+            Ok(events) => self.boot_infos = vec![BootInfo::new(boot_time, &events)],
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.boot_infos.len() != old_vec.len()
+            || crate::hash_vec(&self.boot_infos) != crate::hash_vec(&old_vec);
+    }
+}
+
+impl Default for BootInfos {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        BootInfos {
+            boot_infos: Vec::new(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+const BOOT_TIME_QUERY: &str = "SELECT LastBootUpTime FROM Win32_OperatingSystem";
+
+fn boot_time(instances: Vec<LastBootUpTime>) -> Option<SystemTime> {
+    let boot_time = instances.into_iter().next()?.LastBootUpTime?;
+
+    Some(SystemTime::from(boot_time.0))
+}
+
+/// Boot events of the System log since [`BOOT_HISTORY_WINDOW`] before the boot, to find the
+/// shutdown preceding it even after a long uptime
+fn boot_info_query(boot_time: Option<SystemTime>) -> String {
+    let filter = EventLogFilter {
+        event_codes: BOOT_INFO_EVENT_CODES.to_vec(),
+        since: boot_time
+            .unwrap_or_else(SystemTime::now)
+            .checked_sub(BOOT_HISTORY_WINDOW),
+        ..EventLogFilter::default()
+    };
+
+    filter.query("System", None)
+}
 
 /// The `Win32_NTEventlogFile` WMI class represents a logical file or directory of operating system
 /// events. The file is also known as the event log.
@@ -26,8 +26,10 @@
 //! | [**Win32\_VolumeQuotaSetting**](/previous-versions/windows/desktop/wmipdskq/win32-volumequotasetting)                  | Association class<br/> Relates disk quota settings with a specific disk volume.<br/>                                                                                     |
 //! | [**Win32\_VolumeUserQuota**](/previous-versions/windows/desktop/vdswmi/win32-volumeuserquota)                             | Association class<br/> Relates per user quotas to quota-enabled volumes.<br/>
 
-use crate::update;
+use crate::report::{format_bytes, Summary};
+use crate::{associates, batched, coded, keyed, labeled, referenced, update};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::time::SystemTime;
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
 
@@ -43,9 +45,12 @@ pub struct Directories {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Directories, directories);
+batched!(Directories, Win32_Directory);
 
 /// Represents the state of Windows Directory Specification
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -59,6 +64,8 @@ pub struct DirectorySpecifications {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(DirectorySpecifications, directory_specifications);
@@ -75,9 +82,30 @@ pub struct DiskPartitions {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(DiskPartitions, disk_partitions);
+keyed!(DiskPartitions, disk_partitions, Win32_DiskPartition, DeviceID: String);
+
+/// Represents the state of Windows DiskDriveToDiskPartitions
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct DiskDriveToDiskPartitions {
+    /// Sequence of associations between disk drives and their partitions
+    pub disk_drive_to_disk_partitions: Vec<Win32_DiskDriveToDiskPartition>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(DiskDriveToDiskPartitions, disk_drive_to_disk_partitions);
 
 /// Represents the state of Windows Logical Disks
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -91,9 +119,53 @@ pub struct LogicalDisks {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(LogicalDisks, logical_disks);
+keyed!(LogicalDisks, logical_disks, Win32_LogicalDisk, DeviceID: String);
+
+/// Represents the state of Windows LogicalDiskToPartitions
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct LogicalDiskToPartitions {
+    /// Sequence of associations between logical disks and the partitions they reside on
+    pub logical_disk_to_partitions: Vec<Win32_LogicalDiskToPartition>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(LogicalDiskToPartitions, logical_disk_to_partitions);
+
+impl LogicalDisks {
+    /// Used space of every disk that reports a size
+    pub fn summary(&self) -> Summary {
+        self.usage().iter().fold(Summary::new("Logical disks"), |summary, disk| {
+            summary.line(
+                disk.name.as_deref().unwrap_or(&disk.device_id),
+                format!(
+                    "{} of {} used ({:.1}%)",
+                    format_bytes(disk.used),
+                    format_bytes(disk.size),
+                    disk.used_percent
+                ),
+            )
+        })
+    }
+}
+
+impl fmt::Display for LogicalDisks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.summary().fmt(f)
+    }
+}
 
 /// Represents the state of Windows Mapped Logical Disks
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -107,6 +179,8 @@ pub struct MappedLogicalDisks {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(MappedLogicalDisks, mapped_logical_disks);
@@ -123,6 +197,8 @@ pub struct QuotaSettings {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(QuotaSettings, quota_settings);
@@ -139,9 +215,12 @@ pub struct ShortcutFiles {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(ShortcutFiles, shortcut_files);
+batched!(ShortcutFiles, Win32_ShortcutFile);
 
 /// Represents the state of Windows Volumes
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -155,9 +234,118 @@ pub struct Volumes {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Volumes, volumes);
+keyed!(Volumes, volumes, Win32_Volume, DeviceID: String);
+
+/// Space usage of a logical disk or volume
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct DiskUsage {
+    /// `DeviceID` of the logical disk or volume
+    pub device_id: String,
+    /// Drive letter, mount point or, for a volume that is not mounted, volume path
+    pub name: Option<String>,
+    /// Size in bytes
+    pub size: u64,
+    /// Free space in bytes
+    pub free_space: u64,
+    /// Used space in bytes
+    pub used: u64,
+    /// Used space as a percentage of the size
+    pub used_percent: f64,
+}
+
+impl DiskUsage {
+    /// Derives the usage from the `Size` and `FreeSpace` of an instance, `None` if either is missing
+    /// or the size is 0, as for empty optical drives
+    fn new(
+        device_id: &Option<String>,
+        name: &Option<String>,
+        size: Option<u64>,
+        free_space: Option<u64>,
+    ) -> Option<Self> {
+        let (size, free_space) = (size.filter(|size| *size > 0)?, free_space?);
+        let used = size.saturating_sub(free_space);
+
+        Some(DiskUsage {
+            device_id: device_id.clone()?,
+            name: name.clone(),
+            size,
+            free_space,
+            used,
+            used_percent: used as f64 * 100.0 / size as f64,
+        })
+    }
+}
+
+/// A state whose instances report space usage, either [`LogicalDisks`] or [`Volumes`]
+pub trait DiskUsages {
+    /// Usage of every instance that reports a size
+    fn usage(&self) -> Vec<DiskUsage>;
+}
+
+impl DiskUsages for LogicalDisks {
+    fn usage(&self) -> Vec<DiskUsage> {
+        self.logical_disks
+            .iter()
+            .filter_map(Win32_LogicalDisk::usage)
+            .collect()
+    }
+}
+
+impl DiskUsages for Volumes {
+    fn usage(&self) -> Vec<DiskUsage> {
+        self.volumes.iter().filter_map(Win32_Volume::usage).collect()
+    }
+}
+
+/// Disks or volumes whose used space is above a threshold
+///
+/// Example:
+///
+/// ```rust,no_run
+/// use windows_snapshot::operating_system::file_system::{DiskAlerts, LogicalDisks};
+///
+/// let mut disks = LogicalDisks::default();
+/// disks.update();
+///
+/// for disk in DiskAlerts::evaluate(&disks, 90.0).over_threshold {
+///     println!("{} is {:.1}% full", disk.device_id, disk.used_percent);
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct DiskAlerts {
+    /// Used space, in percent, above which a disk is reported
+    pub threshold_percent: f64,
+    /// Disks above the threshold, fullest first
+    pub over_threshold: Vec<DiskUsage>,
+}
+
+impl DiskAlerts {
+    /// Reports the instances of `disks` whose used space is above `threshold_percent`
+    pub fn evaluate(disks: &impl DiskUsages, threshold_percent: f64) -> Self {
+        let mut over_threshold: Vec<DiskUsage> = disks
+            .usage()
+            .into_iter()
+            .filter(|disk| disk.used_percent > threshold_percent)
+            .collect();
+
+        over_threshold.sort_by(|a, b| b.used_percent.total_cmp(&a.used_percent));
+
+        DiskAlerts {
+            threshold_percent,
+            over_threshold,
+        }
+    }
+
+    /// Returns `true` if no disk is above the threshold
+    pub fn is_empty(&self) -> bool {
+        self.over_threshold.is_empty()
+    }
+}
 
 /// The `Win32_Directory` WMI class represents a directory entry on a computer system running Windows.
 /// A directory is a type of file that logically groups data files and provides path information for
@@ -767,6 +955,34 @@ pub struct Win32_DiskPartition {
     pub Type: Option<String>,
 }
 
+coded!(Win32_DiskPartition {
+    Availability => availability: Availability,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+labeled!(Win32_DiskPartition {
+    Status => status: DeviceStatus,
+    Type => partition_type: PartitionType,
+});
+referenced!(Win32_DiskPartition { DeviceID });
+
+/// The `Win32_DiskDriveToDiskPartition` association WMI class relates a disk drive and a partition
+/// existing on it.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-diskdrivetodiskpartition>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_DiskDriveToDiskPartition {
+    /// Reference to the disk drive, e.g.
+    /// `\\HOST\root\cimv2:Win32_DiskDrive.DeviceID="\\\\.\\PHYSICALDRIVE0"`.
+    pub Antecedent: Option<String>,
+    /// Reference to the partition on the disk drive, e.g.
+    /// `\\HOST\root\cimv2:Win32_DiskPartition.DeviceID="Disk #0, Partition #1"`.
+    pub Dependent: Option<String>,
+}
+
+associates!(Win32_DiskDriveToDiskPartition { Antecedent => Dependent });
+
 /// The `Win32_LogicalDisk` WMI class represents a data source
 /// that resolves to an actual local storage device on a computer system running Windows.
 ///
@@ -1097,6 +1313,47 @@ pub struct Win32_LogicalDisk {
     pub VolumeSerialNumber: Option<String>,
 }
 
+coded!(Win32_LogicalDisk {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+    DriveType => drive_type: DriveType,
+});
+
+labeled!(Win32_LogicalDisk {
+    Status => status: DeviceStatus,
+});
+referenced!(Win32_LogicalDisk { DeviceID });
+
+/// The `Win32_LogicalDiskToPartition` association WMI class relates a logical disk drive and the
+/// disk partition it resides on.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-logicaldisktopartition>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_LogicalDiskToPartition {
+    /// Reference to the partition, e.g.
+    /// `\\HOST\root\cimv2:Win32_DiskPartition.DeviceID="Disk #0, Partition #1"`.
+    pub Antecedent: Option<String>,
+    /// Reference to the logical disk residing on the partition, e.g.
+    /// `\\HOST\root\cimv2:Win32_LogicalDisk.DeviceID="C:"`.
+    pub Dependent: Option<String>,
+    /// Starting address, in bytes, of the logical disk on the partition.
+    pub StartingAddress: Option<u64>,
+    /// Ending address, in bytes, of the logical disk on the partition.
+    pub EndingAddress: Option<u64>,
+}
+
+associates!(Win32_LogicalDiskToPartition { Antecedent => Dependent });
+
+impl Win32_LogicalDisk {
+    /// Space usage of the disk, `None` if it does not report a size
+    pub fn usage(&self) -> Option<DiskUsage> {
+        DiskUsage::new(&self.DeviceID, &self.Name, self.Size, self.FreeSpace)
+    }
+}
+
 /// The `Win32_MappedLogicalDisk` WMI class represents network storage devices
 /// that are mapped as logical disks on the computer system.
 ///
@@ -1323,6 +1580,12 @@ pub struct Win32_MappedLogicalDisk {
     pub VolumeSerialNumber: Option<String>,
 }
 
+coded!(Win32_MappedLogicalDisk {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+});
+
 /// The `Win32_QuotaSetting` WMI class contains setting information for disk quotas on a volume.
 ///
 /// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/wmipdskq/win32-quotasetting>
@@ -1768,3 +2031,17 @@ pub struct Win32_Volume {
     /// This property is False when the Compressed property is True.
     pub SupportsFileBasedCompression: Option<bool>,
 }
+
+coded!(Win32_Volume {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+    DriveType => drive_type: DriveType,
+});
+
+impl Win32_Volume {
+    /// Space usage of the volume, `None` if it does not report a capacity
+    pub fn usage(&self) -> Option<DiskUsage> {
+        DiskUsage::new(&self.DeviceID, &self.Name, self.Capacity, self.FreeSpace)
+    }
+}
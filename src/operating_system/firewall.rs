@@ -0,0 +1,398 @@
+//! Windows Defender Firewall profiles and rules, from the `root\StandardCimv2` namespace.
+//!
+//! | Class                                                                                              | Description                                                                                         |
+//! |----------------------------------------------------------------------------------------------------|-----------------------------------------------------------------------------------------------------|
+//! | [**MSFT\_NetFirewallProfile**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/wfascimprov/msft-netfirewallprofile)         | Instance class<br/> Represents the settings of the Domain, Private and Public profiles.<br/>        |
+//! | [**MSFT\_NetFirewallRule**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/wfascimprov/msft-netfirewallrule)               | Instance class<br/> Represents a firewall rule, inbound or outbound.<br/>                           |
+//! | [**MSFT\_NetProtocolPortFilter**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/wfascimprov/msft-netprotocolportfilter)   | Instance class<br/> Represents the protocol and ports a rule matches.<br/>                          |
+//! | [**MSFT\_NetApplicationFilter**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/wfascimprov/msft-netapplicationfilter)     | Instance class<br/> Represents the program or package a rule matches.<br/>                          |
+//! | [**MSFT\_NetAddressFilter**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/wfascimprov/msft-netaddressfilter)             | Instance class<br/> Represents the local and remote addresses a rule matches.<br/>                  |
+//!
+//! The conditions of a rule live in its filters, which share the `InstanceID` of the rule, see
+//! [`FirewallRules::with_filters`].
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.firewall_profiles.update();
+//!
+//! for profile in k.firewall_profiles.disabled() {
+//!     println!("firewall disabled for the {:?} profile", profile.Name);
+//! }
+//! ```
+
+use crate::codes::{FirewallAction, FirewallDirection, GpoBoolean};
+use crate::{coded, keyed, update, STANDARD_CIMV2_NAMESPACE};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection};
+
+/// Represents the state of Windows firewall profiles
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct FirewallProfiles {
+    /// Sequence of windows firewall profiles
+    pub firewall_profiles: Vec<MSFT_NetFirewallProfile>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(FirewallProfiles, firewall_profiles, STANDARD_CIMV2_NAMESPACE);
+keyed!(FirewallProfiles, firewall_profiles, MSFT_NetFirewallProfile, Name: String);
+
+impl FirewallProfiles {
+    /// Profiles whose firewall is turned off
+    pub fn disabled(&self) -> Vec<&MSFT_NetFirewallProfile> {
+        self.firewall_profiles
+            .iter()
+            .filter(|profile| profile.enabled() == Some(GpoBoolean::False))
+            .collect()
+    }
+}
+
+/// Represents the state of Windows firewall rules
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct FirewallRules {
+    /// Sequence of windows firewall rules, enabled or not
+    pub firewall_rules: Vec<MSFT_NetFirewallRule>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(FirewallRules, firewall_rules, STANDARD_CIMV2_NAMESPACE);
+keyed!(FirewallRules, firewall_rules, MSFT_NetFirewallRule, InstanceID: String);
+
+impl FirewallRules {
+    /// Rules that are enabled
+    pub fn enabled(&self) -> Vec<&MSFT_NetFirewallRule> {
+        self.firewall_rules
+            .iter()
+            .filter(|rule| rule.is_enabled())
+            .collect()
+    }
+
+    /// Enabled rules letting inbound traffic in
+    pub fn inbound_allowed(&self) -> Vec<&MSFT_NetFirewallRule> {
+        self.firewall_rules
+            .iter()
+            .filter(|rule| {
+                rule.is_enabled()
+                    && rule.direction() == Some(FirewallDirection::Inbound)
+                    && matches!(
+                        rule.action(),
+                        Some(FirewallAction::Allow | FirewallAction::AllowBypass)
+                    )
+            })
+            .collect()
+    }
+
+    /// Returns the rule `instance_id` with the filters holding its conditions, `None` if there is
+    /// no such rule
+    pub fn with_filters<'a>(
+        &'a self,
+        instance_id: &str,
+        ports: &'a FirewallPortFilters,
+        applications: &'a FirewallApplicationFilters,
+        addresses: &'a FirewallAddressFilters,
+    ) -> Option<FirewallRuleFilters<'a>> {
+        Some(FirewallRuleFilters {
+            rule: self.get_by_key(instance_id)?,
+            port: ports.get_by_key(instance_id),
+            application: applications.get_by_key(instance_id),
+            address: addresses.get_by_key(instance_id),
+        })
+    }
+}
+
+/// Represents the state of Windows firewall port filters
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct FirewallPortFilters {
+    /// Sequence of windows firewall port filters
+    pub port_filters: Vec<MSFT_NetProtocolPortFilter>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(FirewallPortFilters, port_filters, STANDARD_CIMV2_NAMESPACE);
+keyed!(FirewallPortFilters, port_filters, MSFT_NetProtocolPortFilter, InstanceID: String);
+
+/// Represents the state of Windows firewall application filters
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct FirewallApplicationFilters {
+    /// Sequence of windows firewall application filters
+    pub application_filters: Vec<MSFT_NetApplicationFilter>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(FirewallApplicationFilters, application_filters, STANDARD_CIMV2_NAMESPACE);
+keyed!(FirewallApplicationFilters, application_filters, MSFT_NetApplicationFilter, InstanceID: String);
+
+/// Represents the state of Windows firewall address filters
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct FirewallAddressFilters {
+    /// Sequence of windows firewall address filters
+    pub address_filters: Vec<MSFT_NetAddressFilter>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(FirewallAddressFilters, address_filters, STANDARD_CIMV2_NAMESPACE);
+keyed!(FirewallAddressFilters, address_filters, MSFT_NetAddressFilter, InstanceID: String);
+
+/// A firewall rule and its conditions, `None` for a filter the rule does not have
+#[derive(Debug, Clone, Copy)]
+pub struct FirewallRuleFilters<'a> {
+    /// The rule
+    pub rule: &'a MSFT_NetFirewallRule,
+    /// Protocol and ports the rule matches
+    pub port: Option<&'a MSFT_NetProtocolPortFilter>,
+    /// Program or package the rule matches
+    pub application: Option<&'a MSFT_NetApplicationFilter>,
+    /// Local and remote addresses the rule matches
+    pub address: Option<&'a MSFT_NetAddressFilter>,
+}
+
+/// The `MSFT_NetFirewallProfile` WMI class represents the settings of a firewall profile.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/wfascimprov/msft-netfirewallprofile>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_NetFirewallProfile {
+    /// Unique identifier of the profile.
+    pub InstanceID: Option<String>,
+    /// Name of the profile: `Domain`, `Private` or `Public`.
+    pub Name: Option<String>,
+    /// Whether the firewall is turned on for the profile.
+    ///
+    /// - `False` (0)
+    /// - `True` (1)
+    /// - `NotConfigured` (2)
+    pub Enabled: Option<u16>,
+    /// Action applied to inbound traffic no rule matches.
+    ///
+    /// - `NotConfigured` (0)
+    /// - `Allow` (2)
+    /// - `Block` (4)
+    pub DefaultInboundAction: Option<u16>,
+    /// Action applied to outbound traffic no rule matches.
+    ///
+    /// - `NotConfigured` (0)
+    /// - `Allow` (2)
+    /// - `Block` (4)
+    pub DefaultOutboundAction: Option<u16>,
+    /// Whether inbound rules are applied, `False` blocks all inbound traffic.
+    pub AllowInboundRules: Option<u16>,
+    /// Whether rules created by local administrators are merged with Group Policy rules.
+    pub AllowLocalFirewallRules: Option<u16>,
+    /// Whether connection security rules created by local administrators are merged with Group
+    /// Policy rules.
+    pub AllowLocalIPsecRules: Option<u16>,
+    /// Whether rules for applications created by local administrators are applied.
+    pub AllowUserApps: Option<u16>,
+    /// Whether rules for ports created by local administrators are applied.
+    pub AllowUserPorts: Option<u16>,
+    /// Whether unicast responses to multicast and broadcast traffic are allowed.
+    pub AllowUnicastResponseToMulticast: Option<u16>,
+    /// Whether the user is notified when a program is blocked from listening.
+    pub NotifyOnListen: Option<u16>,
+    /// Whether stealth mode is turned off for IPsec protected traffic.
+    pub EnableStealthModeForIPsec: Option<u16>,
+    /// Path of the firewall log file.
+    pub LogFileName: Option<String>,
+    /// Maximum size of the log file, in kilobytes.
+    pub LogMaxSizeKilobytes: Option<u64>,
+    /// Whether allowed packets are logged.
+    pub LogAllowed: Option<u16>,
+    /// Whether dropped packets are logged.
+    pub LogBlocked: Option<u16>,
+    /// Whether ignored packets are logged.
+    pub LogIgnored: Option<u16>,
+    /// Aliases of the interfaces the profile does not apply to.
+    pub DisabledInterfaceAliases: Option<Vec<String>>,
+}
+
+coded!(MSFT_NetFirewallProfile {
+    Enabled => enabled: GpoBoolean,
+    DefaultInboundAction => default_inbound_action: FirewallAction,
+    DefaultOutboundAction => default_outbound_action: FirewallAction,
+});
+
+/// The `MSFT_NetFirewallRule` WMI class represents a firewall rule.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/wfascimprov/msft-netfirewallrule>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_NetFirewallRule {
+    /// Unique identifier of the rule, its `Name` in PowerShell.
+    pub InstanceID: Option<String>,
+    /// Localized name of the rule.
+    pub DisplayName: Option<String>,
+    /// Description of the rule.
+    pub Description: Option<String>,
+    /// Localized name of the group the rule belongs to.
+    pub DisplayGroup: Option<String>,
+    /// Group the rule belongs to, often an indirect string such as `@FirewallAPI.dll,-28502`.
+    pub RuleGroup: Option<String>,
+    /// Action taken on traffic the rule matches.
+    ///
+    /// - `NotConfigured` (0)
+    /// - `Allow` (2)
+    /// - `AllowBypass` (3)
+    /// - `Block` (4)
+    pub Action: Option<u16>,
+    /// Direction of the traffic the rule matches.
+    ///
+    /// - `Inbound` (1)
+    /// - `Outbound` (2)
+    pub Direction: Option<u16>,
+    /// Whether the rule is enabled.
+    ///
+    /// - `True` (1)
+    /// - `False` (2)
+    pub Enabled: Option<u16>,
+    /// How traffic traversing a NAT edge device is handled.
+    ///
+    /// - `Block` (0)
+    /// - `Allow` (1)
+    /// - `DeferToUser` (2)
+    /// - `DeferToApp` (3)
+    pub EdgeTraversalPolicy: Option<u16>,
+    /// Profiles the rule applies to, a bit field of `Domain` (1), `Private` (2) and `Public` (4),
+    /// 0 for any profile.
+    pub Profiles: Option<u16>,
+    /// Platforms the rule applies to, empty for any platform.
+    pub Platforms: Option<Vec<String>>,
+    /// Security identifier of the user owning the rule, for rules of packaged applications.
+    pub Owner: Option<String>,
+    /// Whether the rule matches loose source mapping traffic.
+    pub LooseSourceMapping: Option<bool>,
+    /// Whether the rule only matches traffic to the local subnet.
+    pub LocalOnlyMapping: Option<bool>,
+    /// Policy store the rule comes from, e.g. `PersistentStore` or a Group Policy object.
+    pub PolicyStoreSource: Option<String>,
+    /// Type of the policy store the rule comes from.
+    ///
+    /// - `None` (0)
+    /// - `Local` (1)
+    /// - `GroupPolicy` (2)
+    /// - `Dynamic` (3)
+    /// - `Generated` (4)
+    /// - `Hardcoded` (5)
+    pub PolicyStoreSourceType: Option<u16>,
+    /// Whether the rule is enforced.
+    ///
+    /// - `Unknown` (0)
+    /// - `OK` (1)
+    /// - `Degraded` (2)
+    /// - `Error` (3)
+    pub PrimaryStatus: Option<u16>,
+    /// Status of the rule, as text.
+    pub Status: Option<String>,
+    /// Status of the rule, as code.
+    pub StatusCode: Option<u32>,
+}
+
+coded!(MSFT_NetFirewallRule {
+    Action => action: FirewallAction,
+    Direction => direction: FirewallDirection,
+});
+
+impl MSFT_NetFirewallRule {
+    /// Whether the rule is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.Enabled == Some(1)
+    }
+}
+
+/// The `MSFT_NetProtocolPortFilter` WMI class represents the protocol and ports a firewall rule
+/// matches.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/wfascimprov/msft-netprotocolportfilter>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_NetProtocolPortFilter {
+    /// Identifier of the rule the filter belongs to.
+    pub InstanceID: Option<String>,
+    /// Protocol matched, e.g. `TCP`, `UDP`, `ICMPv4` or `Any`.
+    pub Protocol: Option<String>,
+    /// Local ports or port ranges matched, e.g. `445` or `RPC`.
+    pub LocalPort: Option<Vec<String>>,
+    /// Remote ports or port ranges matched.
+    pub RemotePort: Option<Vec<String>>,
+    /// ICMP types and codes matched, e.g. `8:*`.
+    pub IcmpType: Option<Vec<String>>,
+}
+
+/// The `MSFT_NetApplicationFilter` WMI class represents the program or package a firewall rule
+/// matches.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/wfascimprov/msft-netapplicationfilter>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_NetApplicationFilter {
+    /// Identifier of the rule the filter belongs to.
+    pub InstanceID: Option<String>,
+    /// Path of the program matched, `Any` for every program.
+    pub AppPath: Option<String>,
+    /// Security identifier of the packaged application matched.
+    pub Package: Option<String>,
+}
+
+/// The `MSFT_NetAddressFilter` WMI class represents the addresses a firewall rule matches.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/wfascimprov/msft-netaddressfilter>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_NetAddressFilter {
+    /// Identifier of the rule the filter belongs to.
+    pub InstanceID: Option<String>,
+    /// Local addresses, subnets or ranges matched, `Any` for every address.
+    pub LocalAddress: Option<Vec<String>>,
+    /// Remote addresses, subnets, ranges or keywords such as `LocalSubnet` matched.
+    pub RemoteAddress: Option<Vec<String>>,
+}
@@ -0,0 +1,216 @@
+//! Group Policy objects applied to the computer, as recorded by the Resultant Set of Policy (RSoP)
+//! logging of the last policy refresh in the `root\RSOP\Computer` namespace, the data `gpresult`
+//! reports. The namespace can only be queried with administrative rights, and stays empty when
+//! RSoP logging is turned off by policy.
+//!
+//! | Class                                                                                                | Description                                                                                               |
+//! |------------------------------------------------------------------------------------------------------|-----------------------------------------------------------------------------------------------------------|
+//! | [**RSOP\_GPO**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/policy/rsop-gpo)       | Instance class<br/> Represents a Group Policy object the last refresh processed.<br/>                     |
+//! | [**RSOP\_GPLink**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/policy/rsop-gplink) | Association class<br/> Relates a GPO to the site, domain or organizational unit it is linked to.<br/>    |
+//! | [**RSOP\_ExtensionStatus**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/policy/rsop-extensionstatus) | Instance class<br/> Outcome of a client-side extension, e.g. Security or Registry, during the refresh.<br/> |
+//!
+//! [`GPLinks::applied`] lists the GPOs in effect in the order they were applied, the last one
+//! winning conflicts.
+
+use crate::association::ObjectPath;
+use crate::{update, RSOP_COMPUTER_NAMESPACE};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection, WMIDateTime};
+
+/// Represents the state of the Group Policy objects processed for the computer
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct GPOs {
+    /// Sequence of GPOs processed by the last policy refresh, applied or filtered out
+    pub gpos: Vec<RSOP_GPO>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(GPOs, gpos, RSOP_COMPUTER_NAMESPACE);
+
+impl GPOs {
+    /// GPO with the identifier `id`, compared case insensitively
+    pub fn get(&self, id: &str) -> Option<&RSOP_GPO> {
+        self.gpos
+            .iter()
+            .find(|gpo| gpo.id.as_deref().is_some_and(|gpo_id| gpo_id.eq_ignore_ascii_case(id)))
+    }
+
+    /// GPOs that were processed but not applied, because they are disabled, access to them was
+    /// denied or a WMI filter excluded them
+    pub fn filtered_out(&self) -> Vec<&RSOP_GPO> {
+        self.gpos.iter().filter(|gpo| !gpo.in_effect()).collect()
+    }
+}
+
+/// Represents the state of the links between GPOs and the scopes they apply to
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct GPLinks {
+    /// Sequence of links of the GPOs processed by the last policy refresh
+    pub gp_links: Vec<RSOP_GPLink>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(GPLinks, gp_links, RSOP_COMPUTER_NAMESPACE);
+
+impl GPLinks {
+    /// GPOs in effect with the link they were applied through, in the order they were applied
+    ///
+    /// Links with an `appliedOrder` of 0 were not applied, they are left out along with the GPOs
+    /// missing from `gpos` or not [`RSOP_GPO::in_effect`].
+    pub fn applied<'a>(&'a self, gpos: &'a GPOs) -> Vec<(&'a RSOP_GPO, &'a RSOP_GPLink)> {
+        let mut applied: Vec<_> = self
+            .gp_links
+            .iter()
+            .filter(|link| link.appliedOrder.unwrap_or_default() > 0)
+            .filter_map(|link| Some((gpos.get(&link.gpo_id()?)?, link)))
+            .filter(|(gpo, _)| gpo.in_effect())
+            .collect();
+
+        applied.sort_by_key(|(_, link)| link.appliedOrder);
+        applied
+    }
+}
+
+/// Represents the state of the client-side extensions run by the last policy refresh
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct GPExtensionStatuses {
+    /// Sequence of client-side extensions and how their processing went
+    pub gp_extension_statuses: Vec<RSOP_ExtensionStatus>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(GPExtensionStatuses, gp_extension_statuses, RSOP_COMPUTER_NAMESPACE);
+
+impl GPExtensionStatuses {
+    /// Extensions that reported an error during the last refresh
+    pub fn failed(&self) -> Vec<&RSOP_ExtensionStatus> {
+        self.gp_extension_statuses
+            .iter()
+            .filter(|status| status.error.unwrap_or_default() != 0)
+            .collect()
+    }
+}
+
+/// The `RSOP_GPO` WMI class represents a Group Policy object (GPO) processed by the last policy
+/// refresh of the computer.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/policy/rsop-gpo>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct RSOP_GPO {
+    /// Indicates whether the computer was denied access to the GPO, in which case it was not
+    /// applied.
+    pub accessDenied: Option<bool>,
+    /// Indicates whether the GPO is enabled.
+    pub enabled: Option<bool>,
+    /// File system path of the GPO template, e.g.
+    /// "\\\\example.com\\SysVol\\example.com\\Policies\\{31B2F340-016D-11D2-945F-00C04FB984F9}\\Machine".
+    pub fileSystemPath: Option<String>,
+    /// Indicates whether the WMI filter of the GPO allowed it to apply.
+    pub filterAllowed: Option<bool>,
+    /// Path of the WMI filter of the GPO, empty when it has none.
+    pub filterId: Option<String>,
+    /// GUID of the GPO, e.g. "{31B2F340-016D-11D2-945F-00C04FB984F9}".
+    pub guidName: Option<String>,
+    /// Unique identifier of the GPO, its Active Directory path or "LocalGPO" for the local one.
+    pub id: Option<String>,
+    /// Friendly name of the GPO, e.g. "Default Domain Policy".
+    pub name: Option<String>,
+    /// Version of the GPO, incremented with every change made to it.
+    pub version: Option<u32>,
+}
+
+impl RSOP_GPO {
+    /// Returns `true` if the GPO was applied: enabled, readable and allowed by its WMI filter
+    pub fn in_effect(&self) -> bool {
+        self.enabled != Some(false) && self.accessDenied != Some(true) && self.filterAllowed != Some(false)
+    }
+}
+
+/// The `RSOP_GPLink` WMI class represents the link of a GPO to a scope of management: a site, a
+/// domain or an organizational unit.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/policy/rsop-gplink>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct RSOP_GPLink {
+    /// Order the GPO was applied in, 0 if it was not applied through this link.
+    pub appliedOrder: Option<u32>,
+    /// Indicates whether the link is enabled.
+    pub enabled: Option<bool>,
+    /// Reference to the linked `RSOP_GPO`.
+    pub GPO: Option<String>,
+    /// Order of the link among the links of its scope of management.
+    pub linkOrder: Option<u32>,
+    /// Indicates whether the link is enforced, so GPOs linked closer to the computer cannot
+    /// override its settings.
+    pub noOverride: Option<bool>,
+    /// Reference to the `RSOP_SOM` scope of management the GPO is linked to.
+    pub SOM: Option<String>,
+    /// Order of the scope of management, from the local computer to the organizational unit
+    /// closest to it.
+    pub somOrder: Option<u32>,
+}
+
+impl RSOP_GPLink {
+    /// Identifier of the linked GPO, the `id` key of the `GPO` reference
+    pub fn gpo_id(&self) -> Option<String> {
+        let path = ObjectPath::parse(self.GPO.as_deref()?)?;
+
+        path.key("id").map(String::from)
+    }
+}
+
+/// The `RSOP_ExtensionStatus` WMI class represents the outcome of a Group Policy client-side
+/// extension, such as Security, Registry or Scripts, during the last policy refresh.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/policy/rsop-extensionstatus>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct RSOP_ExtensionStatus {
+    /// When the extension started processing.
+    pub beginTime: Option<WMIDateTime>,
+    /// Friendly name of the extension, e.g. "Security".
+    pub displayName: Option<String>,
+    /// When the extension finished processing.
+    pub endTime: Option<WMIDateTime>,
+    /// Win32 error code the extension finished with, 0 on success.
+    pub error: Option<u32>,
+    /// GUID of the extension.
+    pub extensionGuid: Option<String>,
+    /// Status of the RSoP logging of the extension.
+    ///
+    /// - `Not Started` (0)
+    /// - `Running` (1)
+    /// - `Completed` (2)
+    /// - `Failed` (3)
+    pub loggingStatus: Option<u32>,
+}
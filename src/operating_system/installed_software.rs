@@ -0,0 +1,253 @@
+//! Installed applications, as listed in "Apps & features".
+//!
+//! | Source                                                                                  | Description                                                                                                                    |
+//! |-----------------------------------------------------------------------------------------|--------------------------------------------------------------------------------------------------------------------------------|
+//! | `Uninstall` registry keys                                                               | Machine wide (native and `WOW6432Node`) and per user entries written by every installer. Used by default.                      |
+//! | [**Win32\_Product**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/legacy/aa394378(v=vs.85)) | Instance class<br/> Products installed by Windows Installer. Querying it runs a consistency check that can reconfigure MSI packages, so it is opt-in. |
+
+use crate::operating_system::registry::{self, Hive, RegistryValue, UNINSTALL, UNINSTALL_WOW64};
+use crate::{state_member, Unavailable, DEFAULT_NAMESPACE};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection, WMIResult};
+
+/// Represents the state of Windows installed software
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct InstalledSoftware {
+    /// Sources queried on every update
+    pub sources: Vec<SoftwareSource>,
+    /// Sequence of installed applications
+    pub installed_software: Vec<InstalledApplication>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(InstalledSoftware, installed_software);
+
+impl InstalledSoftware {
+    /// Creates an empty state that queries `sources` instead of only the registry
+    pub fn with_sources(sources: Vec<SoftwareSource>) -> Self {
+        InstalledSoftware {
+            sources,
+            ..Default::default()
+        }
+    }
+
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let mut products = Ok(Vec::new());
+
+        if self.sources.contains(&SoftwareSource::WindowsInstaller) {
+            let com_con = unsafe { COMLibrary::assume_initialized() };
+
+            products = crate::retry::policy().run(|| {
+                WMIConnection::new(com_con).and_then(|wmi_con| crate::limits::query(&wmi_con))
+            });
+        }
+
+        self.refresh(products);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let mut products = Ok(Vec::new());
+
+        if self.sources.contains(&SoftwareSource::WindowsInstaller) {
+            let com_con = unsafe { COMLibrary::assume_initialized() };
+
+            products = crate::retry::policy()
+                .run_async(|| async move {
+                    match WMIConnection::new(com_con) {
+                        Ok(wmi_con) => crate::limits::async_query(&wmi_con).await,
+                        Err(e) => Err(e),
+                    }
+                })
+                .await;
+        }
+
+        self.refresh(products);
+    }
+
+    /// Registry entries are still read when the Windows Installer provider is missing, as on
+    /// Server Core
+    fn refresh(&mut self, products: WMIResult<Vec<Win32_Product>>) {
+        let old_vec = std::mem::take(&mut self.installed_software);
+        let old_unavailable = self.unavailable.take();
+
+        let products = products.unwrap_or_else(|e| {
+            self.unavailable = Some(Unavailable::new(DEFAULT_NAMESPACE, "Win32_Product", &e));
+            Vec::new()
+        });
+
+        if self.sources.contains(&SoftwareSource::Registry) {
+            for (hive, path) in [
+                (Hive::LocalMachine, UNINSTALL),
+                (Hive::LocalMachine, UNINSTALL_WOW64),
+                (Hive::CurrentUser, UNINSTALL),
+            ] {
+                self.installed_software.extend(
+                    registry::subkeys(hive, path)
+                        .into_iter()
+                        .filter_map(|key| InstalledApplication::from_uninstall_key(hive, path, key)),
+                );
+            }
+        }
+
+        self.installed_software
+            .extend(products.iter().map(InstalledApplication::from_product));
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.installed_software.len() != old_vec.len()
+            || crate::hash_vec(&self.installed_software) != crate::hash_vec(&old_vec);
+    }
+}
+
+impl Default for InstalledSoftware {
+    /// `sources` defaults to the registry only and `last_updated` to the current `SystemTime`
+    fn default() -> Self {
+        InstalledSoftware {
+            sources: vec![SoftwareSource::Registry],
+            installed_software: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// Where an installed application was found
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum SoftwareSource {
+    /// `Uninstall` registry keys
+    Registry,
+    /// `Win32_Product`
+    WindowsInstaller,
+}
+
+/// An installed application
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct InstalledApplication {
+    /// Display name of the application
+    pub name: String,
+    /// Version of the application
+    pub version: Option<String>,
+    /// Publisher of the application
+    pub vendor: Option<String>,
+    /// Install date, in the `YYYYMMDD` form
+    pub install_date: Option<String>,
+    /// Directory the application was installed to
+    pub install_location: Option<String>,
+    /// Command that uninstalls the application
+    pub uninstall_string: Option<String>,
+    /// Uninstall key name, or the product code for Windows Installer products
+    pub identifier: String,
+    /// Whether the application was installed for the current user only
+    pub per_user: bool,
+    /// Where the application was found
+    pub source: SoftwareSource,
+}
+
+impl InstalledApplication {
+    /// Reads an uninstall entry, skipping entries hidden from "Apps & features" (no display name,
+    /// system components and updates)
+    fn from_uninstall_key(hive: Hive, parent: &str, key: String) -> Option<Self> {
+        let path = format!("{parent}\\{key}");
+        let values = registry::values(hive, &path);
+        let value = |name: &str| {
+            values
+                .iter()
+                .find(|(value_name, _)| value_name.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value)
+        };
+        let string = |name: &str| value(name).and_then(RegistryValue::as_str).map(String::from);
+
+        if value("SystemComponent").and_then(RegistryValue::as_u64) == Some(1)
+            || value("ParentKeyName").is_some()
+        {
+            return None;
+        }
+
+        Some(InstalledApplication {
+            name: string("DisplayName")?,
+            version: string("DisplayVersion"),
+            vendor: string("Publisher"),
+            install_date: string("InstallDate"),
+            install_location: string("InstallLocation"),
+            uninstall_string: string("UninstallString"),
+            identifier: key,
+            per_user: hive == Hive::CurrentUser,
+            source: SoftwareSource::Registry,
+        })
+    }
+
+    fn from_product(product: &Win32_Product) -> Self {
+        InstalledApplication {
+            name: product.Name.clone().unwrap_or_default(),
+            version: product.Version.clone(),
+            vendor: product.Vendor.clone(),
+            install_date: product.InstallDate.clone(),
+            install_location: product.InstallLocation.clone(),
+            uninstall_string: None,
+            identifier: product.IdentifyingNumber.clone().unwrap_or_default(),
+            per_user: product.AssignmentType == Some(0),
+            source: SoftwareSource::WindowsInstaller,
+        }
+    }
+}
+
+/// The `Win32_Product` WMI class represents products as they are installed by Windows Installer.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/legacy/aa394378(v=vs.85)>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_Product {
+    /// Scope of the installation.
+    ///
+    /// - `Per user` (0)
+    /// - `Per machine` (1)
+    pub AssignmentType: Option<u16>,
+    /// Short textual description of the product.
+    pub Caption: Option<String>,
+    /// Product description.
+    pub Description: Option<String>,
+    /// Product identifier (product code).
+    pub IdentifyingNumber: Option<String>,
+    /// Date this product was installed on the system, in the `YYYYMMDD` form. This is a string,
+    /// not a datetime, in the Windows Installer provider.
+    pub InstallDate: Option<String>,
+    /// Location of the installed product.
+    pub InstallLocation: Option<String>,
+    /// Installed state of the product.
+    ///
+    /// - `Bad Configuration` (-6)
+    /// - `Invalid Argument` (-2)
+    /// - `Unknown Package` (-1)
+    /// - `Advertised` (1)
+    /// - `Absent` (2)
+    /// - `Installed` (5)
+    pub InstallState: Option<i16>,
+    /// Commonly used product name.
+    pub Name: Option<String>,
+    /// Location of the locally cached package for this product.
+    pub LocalPackage: Option<String>,
+    /// Location of the package this product was installed from.
+    pub InstallSource: Option<String>,
+    /// Language of the product.
+    pub Language: Option<String>,
+    /// Name of the product supplier, or the organization that produced the product.
+    pub Vendor: Option<String>,
+    /// Product version information.
+    pub Version: Option<String>,
+}
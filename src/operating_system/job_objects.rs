@@ -34,6 +34,8 @@ pub struct LUIDs {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(LUIDs, luids);
@@ -50,6 +52,8 @@ pub struct LUIDandAttributes {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(LUIDandAttributes, luid_and_attributes);
@@ -66,6 +70,8 @@ pub struct NamedJobObjects {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NamedJobObjects, named_job_objects);
@@ -82,6 +88,8 @@ pub struct NamedJobObjectActgInfos {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NamedJobObjectActgInfos, named_job_object_actg_infos);
@@ -98,6 +106,8 @@ pub struct NamedJobObjectLimitSettings {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NamedJobObjectLimitSettings, named_job_object_limit_settings);
@@ -7,6 +7,8 @@
 //! | [**Win32\_PageFileSetting**](win32-pagefilesetting)                  | Instance class<br/> Represents the settings of a page file.<br/>                                                                  |
 //! | [**Win32\_PageFileUsage**](win32-pagefileusage)                      | Instance class<br/> Represents the file used for handling virtual memory file swapping on a computer system running Windows.<br/> |
 
+use crate::operating_system::operating_system_settings::OSRecoveryConfigurations;
+use crate::operating_system::registry::{self, Hive};
 use crate::update;
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
@@ -24,6 +26,8 @@ pub struct PageFiles {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(PageFiles, pagefiles);
@@ -40,6 +44,8 @@ pub struct PageFileSettings {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(PageFileSettings, pagefile_settings);
@@ -56,10 +62,116 @@ pub struct PageFileUsages {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(PageFileUsages, pagefile_usage);
 
+const MEMORY_MANAGEMENT: &str = "SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Memory Management";
+
+const FILE_SYSTEM: &str = "SYSTEM\\CurrentControlSet\\Control\\FileSystem";
+
+const CRASH_CONTROL: &str = "SYSTEM\\CurrentControlSet\\Control\\CrashControl";
+
+/// Settings the memory related configuration of a machine is expected to follow
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct MemoryPolicy {
+    /// The page file must be encrypted (`NtfsEncryptPagingFile`)
+    pub require_pagefile_encryption: bool,
+    /// The page file must be wiped at shutdown (`ClearPageFileAtShutdown`)
+    pub require_clear_pagefile_at_shutdown: bool,
+    /// Crash dump types that are acceptable, using the `CrashDumpEnabled` codes
+    ///
+    /// - `None` (0)
+    /// - `Complete memory dump` (1)
+    /// - `Kernel memory dump` (2)
+    /// - `Small memory dump` (3)
+    /// - `Automatic memory dump` (7)
+    pub allowed_crash_dump_types: Vec<u32>,
+}
+
+impl Default for MemoryPolicy {
+    /// Hardened defaults: an encrypted page file that is wiped at shutdown, and crash dumps that
+    /// are either disabled or limited to small memory dumps
+    fn default() -> Self {
+        MemoryPolicy {
+            require_pagefile_encryption: true,
+            require_clear_pagefile_at_shutdown: true,
+            allowed_crash_dump_types: vec![0, 3],
+        }
+    }
+}
+
+/// Memory related settings of the machine evaluated against a [`MemoryPolicy`]
+///
+/// A setting that could not be read is reported as `None` and counts as non compliant when the
+/// policy requires it.
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct MemoryPolicyCompliance {
+    /// Policy the settings were evaluated against
+    pub policy: MemoryPolicy,
+    /// Whether the page file is encrypted
+    pub pagefile_encrypted: Option<bool>,
+    /// Whether the page file is wiped at shutdown
+    pub clear_pagefile_at_shutdown: Option<bool>,
+    /// Configured crash dump type, from `CrashDumpEnabled` or else the `DebugInfoType` of
+    /// `Win32_OSRecoveryConfiguration`
+    pub crash_dump_type: Option<u32>,
+    /// Page file encryption satisfies the policy
+    pub pagefile_encryption_compliant: bool,
+    /// Page file wiping satisfies the policy
+    pub clear_pagefile_at_shutdown_compliant: bool,
+    /// Crash dump type satisfies the policy
+    pub crash_dump_type_compliant: bool,
+}
+
+impl MemoryPolicyCompliance {
+    /// Reads the current settings and evaluates them against `policy`
+    ///
+    /// `os_recovery_configurations` is only used when the crash dump type cannot be read from the
+    /// registry, so it should have been updated beforehand.
+    pub fn evaluate(policy: &MemoryPolicy, os_recovery_configurations: &OSRecoveryConfigurations) -> Self {
+        let read_flag = |path, name| {
+            registry::read_value(Hive::LocalMachine, path, name)
+                .and_then(|value| value.as_u64())
+                .map(|value| value != 0)
+        };
+
+        let pagefile_encrypted = read_flag(FILE_SYSTEM, "NtfsEncryptPagingFile");
+        let clear_pagefile_at_shutdown = read_flag(MEMORY_MANAGEMENT, "ClearPageFileAtShutdown");
+        let crash_dump_type = registry::read_value(Hive::LocalMachine, CRASH_CONTROL, "CrashDumpEnabled")
+            .and_then(|value| value.as_u64())
+            .map(|value| value as u32)
+            .or_else(|| {
+                os_recovery_configurations
+                    .os_recovery_configurations
+                    .iter()
+                    .find_map(|configuration| configuration.DebugInfoType)
+            });
+
+        MemoryPolicyCompliance {
+            policy: policy.clone(),
+            pagefile_encryption_compliant: !policy.require_pagefile_encryption
+                || pagefile_encrypted == Some(true),
+            clear_pagefile_at_shutdown_compliant: !policy.require_clear_pagefile_at_shutdown
+                || clear_pagefile_at_shutdown == Some(true),
+            crash_dump_type_compliant: crash_dump_type
+                .is_some_and(|dump_type| policy.allowed_crash_dump_types.contains(&dump_type)),
+            pagefile_encrypted,
+            clear_pagefile_at_shutdown,
+            crash_dump_type,
+        }
+    }
+
+    /// Returns `true` if every check satisfies the policy
+    pub fn compliant(&self) -> bool {
+        self.pagefile_encryption_compliant
+            && self.clear_pagefile_at_shutdown_compliant
+            && self.crash_dump_type_compliant
+    }
+}
+
 /// The `Win32_PageFile` WMI class represents the file used for handling virtual memory file swapping 
 /// on a Win32 system. This class has been deprecated.
 /// 
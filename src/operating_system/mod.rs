@@ -29,23 +29,75 @@
 //! - [Users](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/operating-system-classes#users)
 //! - [Windows product activation](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/operating-system-classes#windows-product-activation)
 
+#[cfg(feature = "os-com")]
+pub mod com;
+#[cfg(feature = "os-desktop")]
 pub mod desktop;
+#[cfg(feature = "os-drivers")]
 pub mod drivers;
+#[cfg(feature = "os-file-system")]
 pub mod file_system;
+#[cfg(feature = "os-processes")]
 pub mod processes;
+#[cfg(feature = "os-registry")]
 pub mod registry;
+#[cfg(feature = "os-services")]
 pub mod services;
+#[cfg(feature = "os-users")]
 pub mod users;
+#[cfg(feature = "os-event-log")]
 pub mod event_log;
+#[cfg(feature = "os-memory-and-pagefiles")]
 pub mod memory_and_pagefiles;
+#[cfg(feature = "os-scheduler-jobs")]
 pub mod scheduler_jobs;
+#[cfg(feature = "os-product-activation")]
 pub mod product_activation;
+#[cfg(feature = "os-software-license-provider")]
 pub mod software_license_provider;
+#[cfg(feature = "os-shares")]
 pub mod shares;
+#[cfg(feature = "os-multimedia-audio-visual")]
 pub mod multimedia_audio_visual;
+#[cfg(feature = "os-storage")]
 pub mod storage;
+#[cfg(feature = "os-security")]
 pub mod security;
+#[cfg(feature = "os-start-menu")]
 pub mod start_menu;
+#[cfg(feature = "os-networking")]
 pub mod networking;
+#[cfg(feature = "os-job-objects")]
 pub mod job_objects;
-pub mod operating_system_settings;
\ No newline at end of file
+#[cfg(feature = "os-operating-system-settings")]
+pub mod operating_system_settings;
+#[cfg(feature = "os-capture-privacy")]
+pub mod capture_privacy;
+#[cfg(feature = "os-installed-software")]
+pub mod installed_software;
+#[cfg(feature = "os-perf")]
+pub mod perf;
+#[cfg(feature = "os-device-security")]
+pub mod device_security;
+#[cfg(feature = "os-security-center")]
+pub mod security_center;
+#[cfg(feature = "os-security-settings")]
+pub mod security_settings;
+#[cfg(feature = "os-optional-features")]
+pub mod optional_features;
+#[cfg(feature = "os-firewall")]
+pub mod firewall;
+#[cfg(feature = "os-connections")]
+pub mod connections;
+#[cfg(feature = "os-dns")]
+pub mod dns;
+#[cfg(feature = "os-group-policy")]
+pub mod group_policy;
+#[cfg(feature = "os-persistence")]
+pub mod persistence;
+#[cfg(feature = "os-time-service")]
+pub mod time_service;
+#[cfg(feature = "os-updates")]
+pub mod updates;
+#[cfg(feature = "hyperv")]
+pub mod virtualization;
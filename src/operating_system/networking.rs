@@ -12,11 +12,14 @@
 //! | [**Win32\_NTDomain**](/previous-versions/windows/desktop/cimwin32a/win32-ntdomain)                                        | Instance class<br/> Represents a Windows NT domain.<br/>                                                             |
 //! | [**Win32\_PingStatus**](/previous-versions/windows/desktop/wmipicmp/win32-pingstatus)                               | Instance class<br/> Represents the values returned by the standard **ping** command.<br/>                            |
 //! | [**Win32\_ProtocolBinding**](win32-protocolbinding)                          | Association class<br/> Relates a system-level driver, network protocol, and network adapter.<br/>                    |
+//! | [**MSFT\_NetQosPolicySettingData**](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/qos/msft-netqospolicysettingdata) | Instance class<br/> Represents a QoS policy, lives in the `root\StandardCimv2` namespace.<br/>                    |
 
-use crate::update;
+use crate::hardware::networking_device::{AdapterClass, Classified, NetAdapters};
+use crate::operating_system::registry::{self, Hive};
+use crate::{state_member, update, Unavailable, STANDARD_CIMV2_NAMESPACE};
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
-use wmi::{COMLibrary, WMIConnection, WMIDateTime};
+use wmi::{COMLibrary, WMIConnection, WMIDateTime, WMIResult};
 
 /// Represents the state of Windows `IP4PersistedRouteTables`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -30,6 +33,8 @@ pub struct IP4PersistedRouteTables {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(IP4PersistedRouteTables, ip4_persisted_route_tables);
@@ -46,10 +51,29 @@ pub struct IP4RouteTables {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(IP4RouteTables, ip4_route_tables);
 
+impl IP4RouteTables {
+    /// Tags every route with the [`AdapterClass`] of the interface it goes through, useful to
+    /// diagnose VPN split-tunnel setups
+    pub fn classify(&self, adapters: &NetAdapters) -> Vec<Classified<Win32_IP4RouteTable>> {
+        self.ip4_route_tables
+            .iter()
+            .map(|route| Classified {
+                adapter_class: route
+                    .InterfaceIndex
+                    .and_then(|index| u32::try_from(index).ok())
+                    .map_or(AdapterClass::Unknown, |index| adapters.class_of(index)),
+                item: route.clone(),
+            })
+            .collect()
+    }
+}
+
 /// Represents the state of Windows `NetworkClients`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct NetworkClients {
@@ -62,6 +86,8 @@ pub struct NetworkClients {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NetworkClients, nework_clients);
@@ -78,6 +104,8 @@ pub struct NetworkConnections {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NetworkConnections, nework_connections);
@@ -94,6 +122,8 @@ pub struct NetworkProtocols {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NetworkProtocols, nework_protocols);
@@ -110,6 +140,8 @@ pub struct NTDomains {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NTDomains, nt_domains);
@@ -126,10 +158,176 @@ pub struct IP4RouteTableEvents {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(IP4RouteTableEvents, ip4_route_table_events);
 
+/// Registry key Group Policy writes QoS policies to, one subkey per policy
+const QOS_POLICY_KEY: &str = "SOFTWARE\\Policies\\Microsoft\\Windows\\QoS";
+
+/// Represents the state of Windows QoS policies
+///
+/// Policies created locally (`New-NetQosPolicy`) come from `MSFT_NetQosPolicySettingData`, while
+/// policies delivered by Group Policy are read from the registry keys they are applied to.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct QosPolicies {
+    /// Sequence of locally stored QoS policies
+    pub qos_policies: Vec<MSFT_NetQosPolicySettingData>,
+    /// Sequence of QoS policies applied through Group Policy
+    pub group_policy_qos_policies: Vec<GroupPolicyQos>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(QosPolicies, qos_policies; group_policy_qos_policies);
+
+impl QosPolicies {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        let com_con = unsafe { COMLibrary::assume_initialized() };
+
+        self.last_updated = SystemTime::now();
+
+        let qos_policies = crate::retry::policy().run(|| {
+            WMIConnection::with_namespace_path(STANDARD_CIMV2_NAMESPACE, com_con)
+                .and_then(|wmi_con| crate::limits::query(&wmi_con))
+        });
+
+        self.refresh(qos_policies);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        let com_con = unsafe { COMLibrary::assume_initialized() };
+
+        self.last_updated = SystemTime::now();
+
+        let qos_policies = crate::retry::policy()
+            .run_async(|| async move {
+                match WMIConnection::with_namespace_path(STANDARD_CIMV2_NAMESPACE, com_con) {
+                    Ok(wmi_con) => crate::limits::async_query(&wmi_con).await,
+                    Err(e) => Err(e),
+                }
+            })
+            .await;
+
+        self.refresh(qos_policies);
+    }
+
+    /// Group Policy settings are still read when the class is missing (e.g. before Windows 8)
+    fn refresh(&mut self, qos_policies: WMIResult<Vec<MSFT_NetQosPolicySettingData>>) {
+        let old_hash = crate::hash_vec(&[
+            crate::hash_vec(&self.qos_policies),
+            crate::hash_vec(&self.group_policy_qos_policies),
+        ]);
+        let old_unavailable = self.unavailable.take();
+
+        match qos_policies {
+            Ok(qos_policies) => self.qos_policies = qos_policies,
+            Err(e) => {
+                self.qos_policies.clear();
+                self.unavailable = Some(Unavailable::new(
+                    STANDARD_CIMV2_NAMESPACE,
+                    "MSFT_NetQosPolicySettingData",
+                    &e,
+                ));
+            }
+        }
+
+        self.group_policy_qos_policies = [Hive::LocalMachine, Hive::CurrentUser]
+            .into_iter()
+            .flat_map(|hive| {
+                registry::subkeys(hive, QOS_POLICY_KEY)
+                    .into_iter()
+                    .map(move |name| GroupPolicyQos::read(hive, name))
+            })
+            .collect();
+
+        self.state_change = old_unavailable != self.unavailable
+            || old_hash
+                != crate::hash_vec(&[
+                    crate::hash_vec(&self.qos_policies),
+                    crate::hash_vec(&self.group_policy_qos_policies),
+                ]);
+    }
+}
+
+impl Default for QosPolicies {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        QosPolicies {
+            qos_policies: Default::default(),
+            group_policy_qos_policies: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// A QoS policy applied through Group Policy
+///
+/// Every setting is kept as the string Group Policy writes, `*` meaning any.
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct GroupPolicyQos {
+    /// Name of the policy
+    pub name: String,
+    /// Whether the policy applies to the computer (`HKLM`) or the user (`HKCU`)
+    pub per_user: bool,
+    /// Executable the policy applies to
+    pub application_name: Option<String>,
+    /// Protocol the policy applies to, `TCP`, `UDP` or `*`
+    pub protocol: Option<String>,
+    /// Local IP address or prefix
+    pub local_ip: Option<String>,
+    /// Local port or port range
+    pub local_port: Option<String>,
+    /// Remote IP address or prefix
+    pub remote_ip: Option<String>,
+    /// Remote port or port range
+    pub remote_port: Option<String>,
+    /// DSCP value packets are marked with, `-1` leaves them unmarked
+    pub dscp_value: Option<String>,
+    /// Outbound throttle rate in KBps, `-1` means unlimited
+    pub throttle_rate: Option<String>,
+}
+
+impl GroupPolicyQos {
+    fn read(hive: Hive, name: String) -> Self {
+        let path = format!("{QOS_POLICY_KEY}\\{name}");
+        let values = registry::values(hive, &path);
+        let string = |value_name: &str| {
+            values
+                .iter()
+                .find(|(candidate, _)| candidate.eq_ignore_ascii_case(value_name))
+                .and_then(|(_, value)| value.as_str())
+                .map(String::from)
+        };
+
+        GroupPolicyQos {
+            per_user: hive == Hive::CurrentUser,
+            application_name: string("Application Name"),
+            protocol: string("Protocol"),
+            local_ip: string("Local IP"),
+            local_port: string("Local Port"),
+            remote_ip: string("Remote IP"),
+            remote_port: string("Remote Port"),
+            dscp_value: string("DSCP Value"),
+            throttle_rate: string("Throttle Rate"),
+            name,
+        }
+    }
+}
+
 /// The `Win32_IP4PersistedRouteTable` WMI class represents persisted IP routes. By default, the routes 
 /// added to the routing table are not permanent. Rebooting the computer clears the routes from the 
 /// table. However, the following command makes the route persist after the computer is restarted: 
@@ -757,3 +955,75 @@ pub struct Win32_IP4RouteTableEvent {
     /// Times (UTC) format.
     pub TIME_CREATED: Option<u64>,
 }
+
+/// The `MSFT_NetQosPolicySettingData` class represents a QoS policy: the conditions traffic is
+/// matched on and the actions (DSCP marking, 802.1p priority, throttling) applied to it.
+/// 
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/qos/msft-netqospolicysettingdata>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_NetQosPolicySettingData {
+    /// Name of the policy.
+    pub Name: Option<String>,
+    /// Owner of the policy, e.g. "Group Policy (Machine)" or "PowerShell / WMI".
+    pub Owner: Option<String>,
+    /// Network profile the policy applies to.
+    /// 
+    /// - `All` (0)
+    /// - `Domain` (1)
+    /// - `Private` (2)
+    /// - `Public` (3)
+    pub NetworkProfile: Option<u32>,
+    /// Precedence of the policy when several policies match, higher wins.
+    pub Precedence: Option<u32>,
+    /// Built-in traffic template matched by the policy.
+    /// 
+    /// - `None` (0)
+    /// - `Default` (1)
+    /// - `iSCSI` (2)
+    /// - `FCoE` (3)
+    /// - `SMB` (4)
+    /// - `NFS` (5)
+    /// - `LiveMigration` (6)
+    /// - `Cluster` (7)
+    pub TemplateMatchCondition: Option<u16>,
+    /// User or group the policy applies to.
+    pub UserMatchCondition: Option<String>,
+    /// Executable the policy applies to.
+    pub AppPathNameMatchCondition: Option<String>,
+    /// IP protocol matched by the policy.
+    /// 
+    /// - `Both` (0)
+    /// - `TCP` (1)
+    /// - `UDP` (2)
+    pub IPProtocolMatchCondition: Option<u16>,
+    /// Source or destination port matched by the policy.
+    pub IPPortMatchCondition: Option<u16>,
+    /// Source IP address or prefix matched by the policy.
+    pub IPSrcPrefixMatchCondition: Option<String>,
+    /// First source port matched by the policy.
+    pub IPSrcPortStartMatchCondition: Option<u16>,
+    /// Last source port matched by the policy.
+    pub IPSrcPortEndMatchCondition: Option<u16>,
+    /// Destination IP address or prefix matched by the policy.
+    pub IPDstPrefixMatchCondition: Option<String>,
+    /// First destination port matched by the policy.
+    pub IPDstPortStartMatchCondition: Option<u16>,
+    /// Last destination port matched by the policy.
+    pub IPDstPortEndMatchCondition: Option<u16>,
+    /// NetworkDirect (RDMA) port matched by the policy.
+    pub NetDirectPortMatchCondition: Option<u16>,
+    /// URI matched by the policy, for HTTP server traffic.
+    pub URIMatchCondition: Option<String>,
+    /// Whether `URIMatchCondition` also matches the URIs below it.
+    pub URIRecursiveMatchCondition: Option<bool>,
+    /// IEEE 802.1p priority value tagged on matching traffic, -1 when unset.
+    pub PriorityValue8021Action: Option<i8>,
+    /// DSCP value marked on matching traffic, -1 when unset.
+    pub DSCPAction: Option<i8>,
+    /// Minimum bandwidth weight given to matching traffic.
+    pub MinBandwidthWeightAction: Option<u8>,
+    /// Throttle rate of matching traffic, in bits per second.
+    pub ThrottleRateAction: Option<u64>,
+}
@@ -30,9 +30,14 @@
 //! | [**Win32\_SystemTimeZone**](win32-systemtimezone)                                       | Association class<br/> Relates a computer system and a time zone.<br/>                                                                                                 |
 //! | [**Win32\_SystemUsers**](win32-systemusers)                                             | Association class<br/> Relates a computer system and a user account on that system.<br/>                                                                               |
 
-use crate::update;
+use crate::codes::{DomainRole, ProductType};
+#[cfg(feature = "os-perf")]
+use crate::operating_system::perf::Win32_PerfFormattedData_PerfOS_Memory;
+use crate::report::{format_bytes, format_duration, Summary};
+use crate::{associates, coded, keyed, referenced, update};
 use serde::{Deserialize, Serialize};
-use std::time::SystemTime;
+use std::fmt;
+use std::time::{Duration, SystemTime};
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
 
 /// Represents the state of Windows `BootConfigurations`
@@ -47,6 +52,8 @@ pub struct BootConfigurations {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(BootConfigurations, boot_configurations);
@@ -63,10 +70,55 @@ pub struct ComputerSystems {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(ComputerSystems, computer_systems);
 
+impl ComputerSystems {
+    /// Name, domain, model and memory of the computer
+    pub fn summary(&self) -> Summary {
+        let mut summary = Summary::new("Computer system");
+
+        if let Some(computer) = self.computer_systems.first() {
+            summary = summary
+                .optional_line("Name", computer.Name.as_deref())
+                .optional_line("Domain", computer.Domain.as_deref())
+                .optional_line("Manufacturer", computer.Manufacturer.as_deref())
+                .optional_line("Model", computer.Model.as_deref())
+                .optional_line("Logical processors", computer.NumberOfLogicalProcessors)
+                .optional_line("Memory", computer.TotalPhysicalMemory.map(format_bytes));
+        }
+
+        summary
+    }
+}
+
+impl fmt::Display for ComputerSystems {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.summary().fmt(f)
+    }
+}
+
+/// Represents the state of Windows SystemServices
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct SystemServices {
+    /// Sequence of associations between the computer system and its services
+    pub system_services: Vec<Win32_SystemServices>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(SystemServices, system_services);
+
 /// Represents the state of Windows `ComputerSystemProducts`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct ComputerSystemProducts {
@@ -79,6 +131,8 @@ pub struct ComputerSystemProducts {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(ComputerSystemProducts, computer_system_products);
@@ -95,6 +149,8 @@ pub struct LoadOrderGroups {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(LoadOrderGroups, load_order_groups);
@@ -111,10 +167,264 @@ pub struct OperatingSystems {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(OperatingSystems, operating_systems);
 
+impl OperatingSystems {
+    /// Time the operating system had been running when the state was updated, see
+    /// [`Win32_OperatingSystem::uptime`]
+    pub fn uptime(&self) -> Option<Duration> {
+        self.operating_systems
+            .iter()
+            .find_map(Win32_OperatingSystem::uptime)
+    }
+
+    /// Caption, version, architecture, last boot and uptime of the operating system
+    pub fn summary(&self) -> Summary {
+        let mut summary = Summary::new("Operating system");
+
+        if let Some(os) = self.operating_systems.first() {
+            summary = summary
+                .optional_line("Name", os.Caption.as_deref())
+                .optional_line("Version", os.Version.as_deref())
+                .optional_line("Build", os.BuildNumber.as_deref())
+                .optional_line("Architecture", os.OSArchitecture.as_deref())
+                .optional_line("Last boot", os.LastBootUpTime.as_ref().map(|time| time.0))
+                .optional_line("Uptime", os.uptime().map(format_duration));
+        }
+
+        summary
+    }
+}
+
+impl fmt::Display for OperatingSystems {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.summary().fmt(f)
+    }
+}
+
+/// Traits of a machine consumers commonly branch on, derived from `Win32_OperatingSystem` and
+/// `Win32_ComputerSystem`
+///
+/// ```rust,no_run
+/// let _com_con = windows_snapshot::COMLibrary::new().unwrap(); // initialise security context
+///
+/// let mut k = windows_snapshot::state::Windows::default();
+/// k.operating_systems.update();
+/// k.computer_systems.update();
+///
+/// if let Some(profile) = k.machine_profile() {
+///     println!("server: {}, virtual: {:?}", profile.server(), profile.virtual_machine);
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct MachineProfile {
+    /// Role of the operating system
+    pub role: MachineRole,
+    /// Whether the machine runs under a hypervisor, `None` when the model and manufacturer are
+    /// unknown
+    pub virtual_machine: Option<bool>,
+    /// Platform the machine is virtualized on, e.g. `Hyper-V` or `VMware`, when recognized
+    pub virtualization_platform: Option<String>,
+    /// Whether a hypervisor is present. Also `true` on physical hosts running Hyper-V or
+    /// virtualization based security, so it does not tell a virtual machine apart.
+    pub hypervisor_present: Option<bool>,
+    /// Whether the machine is joined to a domain
+    pub domain_joined: bool,
+    /// Domain the machine belongs to, the workgroup when it is not joined
+    pub domain: Option<String>,
+}
+
+/// Role of a machine
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum MachineRole {
+    /// Client edition
+    Workstation,
+    /// Server edition
+    Server,
+    /// Server edition acting as a domain controller
+    DomainController,
+}
+
+/// Substrings of `Manufacturer` or `Model` identifying a virtualization platform, lowercase
+const VIRTUALIZATION_PLATFORMS: &[(&str, &str)] = &[
+    ("virtual machine", "Hyper-V"),
+    ("vmware", "VMware"),
+    ("virtualbox", "VirtualBox"),
+    ("innotek", "VirtualBox"),
+    ("kvm", "KVM"),
+    ("qemu", "QEMU"),
+    ("xen", "Xen"),
+    ("parallels", "Parallels"),
+    ("bhyve", "bhyve"),
+    ("amazon ec2", "Amazon EC2"),
+    ("google compute engine", "Google Compute Engine"),
+];
+
+impl MachineProfile {
+    /// Derives the profile from the first instances of both states, `None` if either is empty
+    pub fn from_states(
+        operating_systems: &OperatingSystems,
+        computer_systems: &ComputerSystems,
+    ) -> Option<Self> {
+        Some(MachineProfile::new(
+            operating_systems.operating_systems.first()?,
+            computer_systems.computer_systems.first()?,
+        ))
+    }
+
+    /// Derives the profile of the machine running `operating_system` on `computer_system`
+    ///
+    /// The role comes from `ProductType`, falling back on `DomainRole` when it is missing.
+    pub fn new(operating_system: &Win32_OperatingSystem, computer_system: &Win32_ComputerSystem) -> Self {
+        let role = match (operating_system.product_type(), computer_system.domain_role()) {
+            (Some(ProductType::DomainController), _) => MachineRole::DomainController,
+            (Some(ProductType::Server), _) => MachineRole::Server,
+            (Some(ProductType::WorkStation), _) => MachineRole::Workstation,
+            (
+                _,
+                Some(DomainRole::BackupDomainController | DomainRole::PrimaryDomainController),
+            ) => MachineRole::DomainController,
+            (_, Some(DomainRole::StandaloneServer | DomainRole::MemberServer)) => MachineRole::Server,
+            _ => MachineRole::Workstation,
+        };
+
+        let identity = [&computer_system.Manufacturer, &computer_system.Model]
+            .into_iter()
+            .flatten()
+            .map(|value| value.to_lowercase())
+            .collect::<Vec<_>>();
+
+        let virtualization_platform = VIRTUALIZATION_PLATFORMS
+            .iter()
+            .find(|(pattern, _)| identity.iter().any(|value| value.contains(pattern)))
+            .map(|(_, platform)| platform.to_string());
+
+        MachineProfile {
+            role,
+            virtual_machine: (!identity.is_empty()).then_some(virtualization_platform.is_some()),
+            virtualization_platform,
+            hypervisor_present: computer_system.HypervisorPresent,
+            domain_joined: computer_system.PartOfDomain.unwrap_or(false),
+            domain: computer_system.Domain.clone(),
+        }
+    }
+
+    /// Returns `true` for server editions, domain controllers included
+    pub fn server(&self) -> bool {
+        self.role != MachineRole::Workstation
+    }
+}
+
+/// Physical memory, commit and page file usage, derived from `Win32_OperatingSystem` and, when
+/// available, `Win32_PerfFormattedData_PerfOS_Memory`
+///
+/// Every size is in bytes. The performance counters are preferred for the commit charge and limit,
+/// `Win32_OperatingSystem` only approximates them through its virtual memory sizes.
+///
+/// ```rust,no_run
+/// let _com_con = windows_snapshot::COMLibrary::new().unwrap(); // initialise security context
+///
+/// let mut k = windows_snapshot::state::Windows::default();
+/// k.operating_systems.update();
+///
+/// if let Some(memory) = k.memory_summary() {
+///     println!("memory: {:?}%, commit: {:?}%", memory.physical_used_percent(), memory.commit_percent());
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct MemorySummary {
+    /// Physical memory available to the operating system
+    pub total_physical: Option<u64>,
+    /// Physical memory currently unused and available
+    pub free_physical: Option<u64>,
+    /// Virtual memory committed by the system and processes
+    pub commit_charge: Option<u64>,
+    /// Virtual memory that can be committed without extending the page files
+    pub commit_limit: Option<u64>,
+    /// Size of the page files, 0 without page files
+    pub page_file_size: Option<u64>,
+    /// Space left in the page files
+    pub page_file_free: Option<u64>,
+    /// Modified, dirty pages waiting to be written to disk, from the performance counters
+    pub modified_pages: Option<u64>,
+    /// Resident part of the system file cache, from the performance counters
+    pub cache: Option<u64>,
+}
+
+impl MemorySummary {
+    /// Derives the summary from the first instance of `operating_systems`, `None` if it is empty
+    pub fn from_state(operating_systems: &OperatingSystems) -> Option<Self> {
+        Some(MemorySummary::new(operating_systems.operating_systems.first()?))
+    }
+
+    /// Derives the summary from `operating_system` alone, its sizes converted from kilobytes
+    pub fn new(operating_system: &Win32_OperatingSystem) -> Self {
+        let bytes = |kilobytes: Option<u64>| kilobytes.map(|kilobytes| kilobytes * 1024);
+        let total_virtual = bytes(operating_system.TotalVirtualMemorySize);
+        let free_virtual = bytes(operating_system.FreeVirtualMemory);
+
+        MemorySummary {
+            total_physical: bytes(operating_system.TotalVisibleMemorySize),
+            free_physical: bytes(operating_system.FreePhysicalMemory),
+            commit_charge: total_virtual
+                .zip(free_virtual)
+                .map(|(total, free)| total.saturating_sub(free)),
+            commit_limit: total_virtual,
+            page_file_size: bytes(operating_system.SizeStoredInPagingFiles),
+            page_file_free: bytes(operating_system.FreeSpaceInPagingFiles),
+            modified_pages: None,
+            cache: None,
+        }
+    }
+
+    /// Refines the summary with the memory performance counters in `memory`
+    #[cfg(feature = "os-perf")]
+    pub fn with_performance(mut self, memory: &Win32_PerfFormattedData_PerfOS_Memory) -> Self {
+        self.free_physical = memory.AvailableBytes.or(self.free_physical);
+        self.commit_charge = memory.CommittedBytes.or(self.commit_charge);
+        self.commit_limit = memory.CommitLimit.or(self.commit_limit);
+        self.modified_pages = memory.ModifiedPageListBytes;
+        self.cache = memory.CacheBytes;
+        self
+    }
+
+    /// Physical memory in use
+    pub fn used_physical(&self) -> Option<u64> {
+        Some(self.total_physical?.saturating_sub(self.free_physical?))
+    }
+
+    /// Page file space in use
+    pub fn page_file_used(&self) -> Option<u64> {
+        Some(self.page_file_size?.saturating_sub(self.page_file_free?))
+    }
+
+    /// Share of the physical memory in use, in percent
+    pub fn physical_used_percent(&self) -> Option<f64> {
+        percent(self.used_physical(), self.total_physical)
+    }
+
+    /// Commit charge relative to the commit limit, in percent
+    pub fn commit_percent(&self) -> Option<f64> {
+        percent(self.commit_charge, self.commit_limit)
+    }
+
+    /// Share of the page files in use, in percent, `None` without page files
+    pub fn page_file_used_percent(&self) -> Option<f64> {
+        percent(self.page_file_used(), self.page_file_size)
+    }
+}
+
+/// `part` relative to `whole`, in percent, `None` if either is unknown or `whole` is 0
+fn percent(part: Option<u64>, whole: Option<u64>) -> Option<f64> {
+    let whole = whole.filter(|whole| *whole > 0)?;
+
+    Some(part? as f64 * 100.0 / whole as f64)
+}
+
 /// Represents the state of Windows `OSRecoveryConfigurations`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct OSRecoveryConfigurations {
@@ -127,10 +437,124 @@ pub struct OSRecoveryConfigurations {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(OSRecoveryConfigurations, os_recovery_configurations);
 
+impl OSRecoveryConfigurations {
+    /// Crash dump settings that do not follow `policy`, empty when they all do
+    ///
+    /// Settings are read from the first configuration, when there is none every setting the
+    /// policy checks is reported with an unknown value.
+    pub fn check(&self, policy: &CrashDumpPolicy) -> Vec<CrashDumpMismatch> {
+        let configuration = self.os_recovery_configurations.first().cloned().unwrap_or_default();
+        let mut mismatches = Vec::new();
+
+        if !policy.allowed_debug_info_types.is_empty()
+            && !configuration
+                .DebugInfoType
+                .is_some_and(|debug_info_type| policy.allowed_debug_info_types.contains(&debug_info_type))
+        {
+            mismatches.push(CrashDumpMismatch {
+                setting: "DebugInfoType",
+                expected: format!("{:?}", policy.allowed_debug_info_types),
+                actual: configuration.DebugInfoType.map(|debug_info_type| debug_info_type.to_string()),
+            });
+        }
+
+        let flags = [
+            ("AutoReboot", policy.auto_reboot, configuration.AutoReboot),
+            ("WriteToSystemLog", policy.write_to_system_log, configuration.WriteToSystemLog),
+            ("SendAdminAlert", policy.send_admin_alert, configuration.SendAdminAlert),
+            (
+                "OverwriteExistingDebugFile",
+                policy.overwrite_existing_debug_file,
+                configuration.OverwriteExistingDebugFile,
+            ),
+        ];
+
+        for (setting, expected, actual) in flags {
+            if let Some(expected) = expected.filter(|&expected| actual != Some(expected)) {
+                mismatches.push(CrashDumpMismatch {
+                    setting,
+                    expected: expected.to_string(),
+                    actual: actual.map(|actual| actual.to_string()),
+                });
+            }
+        }
+
+        mismatches
+    }
+
+    /// Returns `true` if the crash dump settings follow `policy`
+    pub fn compliant(&self, policy: &CrashDumpPolicy) -> bool {
+        self.check(policy).is_empty()
+    }
+}
+
+/// Crash dump settings a machine is expected to follow, checked with
+/// [`OSRecoveryConfigurations::check`]
+///
+/// Settings left to `None`, or an empty `allowed_debug_info_types`, are not checked.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CrashDumpPolicy {
+    /// Dump types that are acceptable, using the `DebugInfoType` codes
+    ///
+    /// - `None` (0)
+    /// - `Complete memory dump` (1)
+    /// - `Kernel memory dump` (2)
+    /// - `Small memory dump` (3)
+    /// - `Automatic memory dump` (7)
+    pub allowed_debug_info_types: Vec<u32>,
+    /// Expected `AutoReboot`
+    pub auto_reboot: Option<bool>,
+    /// Expected `WriteToSystemLog`
+    pub write_to_system_log: Option<bool>,
+    /// Expected `SendAdminAlert`
+    pub send_admin_alert: Option<bool>,
+    /// Expected `OverwriteExistingDebugFile`
+    pub overwrite_existing_debug_file: Option<bool>,
+}
+
+impl Default for CrashDumpPolicy {
+    /// Recommended defaults: a kernel, small or automatic dump is written, the crash is logged and
+    /// the machine reboots on its own
+    fn default() -> Self {
+        CrashDumpPolicy {
+            allowed_debug_info_types: vec![2, 3, 7],
+            auto_reboot: Some(true),
+            write_to_system_log: Some(true),
+            send_admin_alert: None,
+            overwrite_existing_debug_file: None,
+        }
+    }
+}
+
+/// A crash dump setting that does not follow the [`CrashDumpPolicy`]
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CrashDumpMismatch {
+    /// `Win32_OSRecoveryConfiguration` property holding the setting, e.g. `AutoReboot`
+    pub setting: &'static str,
+    /// Value the policy expects
+    pub expected: String,
+    /// Configured value, `None` when unknown
+    pub actual: Option<String>,
+}
+
+impl fmt::Display for CrashDumpMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, found {}",
+            self.setting,
+            self.expected,
+            self.actual.as_deref().unwrap_or(crate::report::UNKNOWN)
+        )
+    }
+}
+
 /// Represents the state of Windows `QuickFixEngineerings`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct QuickFixEngineerings {
@@ -143,9 +567,123 @@ pub struct QuickFixEngineerings {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(QuickFixEngineerings, quick_fix_engineerings);
+keyed!(QuickFixEngineerings, quick_fix_engineerings, Win32_QuickFixEngineering, HotFixID: String);
+
+impl QuickFixEngineerings {
+    /// Installation date of the most recently installed update, `None` if no update has a date
+    pub fn latest_patch_date(&self) -> Option<SystemTime> {
+        self.quick_fix_engineerings
+            .iter()
+            .filter_map(Win32_QuickFixEngineering::installed_on)
+            .max()
+    }
+
+    /// Time elapsed since the most recently installed update
+    pub fn patch_age(&self) -> Option<Duration> {
+        SystemTime::now()
+            .duration_since(self.latest_patch_date()?)
+            .ok()
+    }
+
+    /// Updates ordered by installation date, oldest first, updates without a date last
+    pub fn sorted_by_installed_on(&self) -> Vec<&Win32_QuickFixEngineering> {
+        let mut updates = self.quick_fix_engineerings.iter().collect::<Vec<_>>();
+
+        updates.sort_by_key(|update| match update.installed_on() {
+            Some(installed_on) => (false, installed_on),
+            None => (true, SystemTime::UNIX_EPOCH),
+        });
+
+        updates
+    }
+
+    /// Updates ordered by installation date, newest first, updates without a date last
+    pub fn sorted_by_installed_on_desc(&self) -> Vec<&Win32_QuickFixEngineering> {
+        let mut updates = self.quick_fix_engineerings.iter().collect::<Vec<_>>();
+
+        updates.sort_by_key(|update| std::cmp::Reverse(update.installed_on()));
+
+        updates
+    }
+
+    /// Updates installed at or after `since`
+    pub fn installed_since(&self, since: SystemTime) -> Vec<&Win32_QuickFixEngineering> {
+        self.quick_fix_engineerings
+            .iter()
+            .filter(|update| update.installed_on().is_some_and(|installed_on| installed_on >= since))
+            .collect()
+    }
+
+    /// Number of installed updates and the most recent one
+    pub fn summary(&self) -> Summary {
+        let latest = self.sorted_by_installed_on_desc().into_iter().next();
+
+        Summary::new("Updates")
+            .line("Installed", self.quick_fix_engineerings.len())
+            .optional_line("Latest", latest.and_then(|update| update.HotFixID.as_deref()))
+            .optional_line("Patch age", self.patch_age().map(format_duration))
+    }
+}
+
+impl fmt::Display for QuickFixEngineerings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.summary().fmt(f)
+    }
+}
+
+impl Win32_QuickFixEngineering {
+    /// Parses `InstalledOn`, which comes as `M/D/YYYY` on most systems but also as `D-M-YYYY`,
+    /// `YYYY-MM-DD`, `YYYYMMDD` or a hexadecimal `FILETIME` on others
+    ///
+    /// Dates are taken as midnight UTC. A separated date whose order is ambiguous is read as
+    /// month first with `/` and day first with `-` or `.`.
+    pub fn installed_on(&self) -> Option<SystemTime> {
+        let installed_on = self.InstalledOn.as_deref()?.trim();
+
+        if installed_on.len() == 16 && installed_on.bytes().all(|b| b.is_ascii_hexdigit()) {
+            // 100 nanosecond intervals since 1601-01-01
+            const FILETIME_UNIX_EPOCH: u64 = 116_444_736_000_000_000;
+
+            let filetime = u64::from_str_radix(installed_on, 16).ok()?;
+
+            let nanos = filetime.checked_sub(FILETIME_UNIX_EPOCH)?.checked_mul(100)?;
+
+            return SystemTime::UNIX_EPOCH.checked_add(Duration::from_nanos(nanos));
+        }
+
+        if installed_on.len() == 8 && installed_on.bytes().all(|b| b.is_ascii_digit()) {
+            return crate::civil_date(
+                installed_on[..4].parse().ok()?,
+                installed_on[4..6].parse().ok()?,
+                installed_on[6..].parse().ok()?,
+            );
+        }
+
+        let parts = installed_on
+            .split(['/', '-', '.'])
+            .map(|part| part.trim().parse::<u32>().ok())
+            .collect::<Option<Vec<_>>>()?;
+
+        let [first, second, third] = parts[..] else {
+            return None;
+        };
+
+        let (year, month, day) = if first > 31 {
+            (first, second, third)
+        } else if (installed_on.contains('/') && first <= 12) || second > 12 {
+            (third, first, second)
+        } else {
+            (third, second, first)
+        };
+
+        crate::civil_date(i64::from(year), month, day)
+    }
+}
 
 /// Represents the state of Windows `StartupCommands`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -159,6 +697,8 @@ pub struct StartupCommands {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(StartupCommands, startup_commands);
@@ -608,6 +1148,29 @@ pub struct Win32_ComputerSystem {
     pub Workgroup: Option<String>,
 }
 
+coded!(Win32_ComputerSystem {
+    DomainRole => domain_role: DomainRole,
+    PCSystemType => pc_system_type: PCSystemType,
+});
+
+referenced!(Win32_ComputerSystem { Name });
+
+/// The `Win32_SystemServices` association WMI class relates a computer system and a service
+/// program that exists on the system.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-systemservices>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_SystemServices {
+    /// Reference to the computer system, e.g. `\\HOST\root\cimv2:Win32_ComputerSystem.Name="HOST"`.
+    pub GroupComponent: Option<String>,
+    /// Reference to the service, e.g. `\\HOST\root\cimv2:Win32_Service.Name="RpcSs"`.
+    pub PartComponent: Option<String>,
+}
+
+associates!(Win32_SystemServices { GroupComponent => PartComponent });
+
 /// The `Win32_ComputerSystemProduct` WMI class represents a product. This includes software and hardware used on this 
 /// computer system.
 /// 
@@ -1254,6 +1817,28 @@ pub struct Win32_OperatingSystem {
     */
 }
 
+coded!(Win32_OperatingSystem {
+    ProductType => product_type: ProductType,
+});
+
+impl Win32_OperatingSystem {
+    /// `LastBootUpTime` as a `SystemTime`
+    pub fn last_boot_up_time(&self) -> Option<SystemTime> {
+        self.LastBootUpTime.as_ref().map(|time| SystemTime::from(time.0))
+    }
+
+    /// Time elapsed between `LastBootUpTime` and `LocalDateTime`, the clock of the machine when the
+    /// instance was queried, or the current time if `LocalDateTime` is missing
+    pub fn uptime(&self) -> Option<Duration> {
+        let now = self
+            .LocalDateTime
+            .as_ref()
+            .map_or_else(SystemTime::now, |time| SystemTime::from(time.0));
+
+        now.duration_since(self.last_boot_up_time()?).ok()
+    }
+}
+
 /// The `Win32_OSRecoveryConfiguration` WMI class represents the types of information that will 
 /// be gathered from memory when the operating system fails. This includes boot failures and 
 /// system crashes.
@@ -0,0 +1,156 @@
+//! Windows optional features and, on Windows Server, installed roles and features.
+//!
+//! | Class                                                                                              | Description                                                                                         |
+//! |----------------------------------------------------------------------------------------------------|-----------------------------------------------------------------------------------------------------|
+//! | [**Win32\_OptionalFeature**](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-optionalfeature) | Instance class<br/> Represents an optional feature, e.g. `SMB1Protocol` or `Microsoft-Hyper-V`.<br/> |
+//! | [**Win32\_ServerFeature**](https://learn.microsoft.com/en-us/windows/win32/wmisdk/win32-serverfeature)           | Instance class<br/> Represents a role, role service or feature installed on Windows Server.<br/>     |
+//!
+//! `Win32_ServerFeature` only exists on Windows Server, elsewhere [`ServerFeatures`] records the
+//! class as unavailable.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.optional_features.update();
+//!
+//! if k.optional_features.is_enabled("SMB1Protocol") == Some(true) {
+//!     println!("SMBv1 is enabled");
+//! }
+//! ```
+
+use crate::codes::OptionalFeatureState;
+use crate::{coded, keyed, update};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection, WMIDateTime};
+
+/// Represents the state of Windows optional features
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct OptionalFeatures {
+    /// Sequence of windows optional features, enabled or not
+    pub optional_features: Vec<Win32_OptionalFeature>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(OptionalFeatures, optional_features);
+keyed!(OptionalFeatures, optional_features, Win32_OptionalFeature, Name: String);
+
+impl OptionalFeatures {
+    /// Features that are enabled
+    pub fn enabled(&self) -> Vec<&Win32_OptionalFeature> {
+        self.optional_features
+            .iter()
+            .filter(|feature| feature.install_state() == Some(OptionalFeatureState::Enabled))
+            .collect()
+    }
+
+    /// Whether the feature `name` is enabled, `None` if there is no such feature or its state is
+    /// unknown
+    pub fn is_enabled(&self, name: &str) -> Option<bool> {
+        let feature = self
+            .optional_features
+            .iter()
+            .find(|feature| feature.Name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(name)))?;
+
+        match feature.install_state()? {
+            OptionalFeatureState::Enabled => Some(true),
+            OptionalFeatureState::Disabled | OptionalFeatureState::Absent => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// Represents the state of Windows Server roles, role services and features
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ServerFeatures {
+    /// Sequence of installed server roles, role services and features
+    pub server_features: Vec<Win32_ServerFeature>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(ServerFeatures, server_features);
+keyed!(ServerFeatures, server_features, Win32_ServerFeature, ID: u32);
+
+impl ServerFeatures {
+    /// Returns `true` if the role or feature `name`, e.g. `Web Server (IIS)`, is installed
+    pub fn is_installed(&self, name: &str) -> bool {
+        self.server_features
+            .iter()
+            .any(|feature| feature.Name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+    }
+
+    /// Roles and features installed under the role or feature `id`
+    pub fn children_of(&self, id: u32) -> Vec<&Win32_ServerFeature> {
+        self.server_features
+            .iter()
+            .filter(|feature| feature.ParentID == Some(id))
+            .collect()
+    }
+}
+
+/// The `Win32_OptionalFeature` WMI class represents an optional feature.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-optionalfeature>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_OptionalFeature {
+    /// A short textual description of the feature.
+    pub Caption: Option<String>,
+    /// A textual description of the feature.
+    pub Description: Option<String>,
+    /// When the feature was installed.
+    pub InstallDate: Option<WMIDateTime>,
+    /// Identifies the feature, e.g. `SMB1Protocol`, `Microsoft-Hyper-V` or `IIS-WebServerRole`.
+    pub Name: Option<String>,
+    /// Current status of the feature.
+    pub Status: Option<String>,
+    /// Whether the feature is enabled.
+    ///
+    /// - Enabled (1)
+    /// - Disabled (2)
+    /// - Absent (3)
+    /// - Unknown (4)
+    pub InstallState: Option<u32>,
+}
+
+coded!(Win32_OptionalFeature {
+    InstallState => install_state: OptionalFeatureState,
+});
+
+/// The `Win32_ServerFeature` WMI class represents a role, role service, or feature that is
+/// installed on a computer running Windows Server.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/wmisdk/win32-serverfeature>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_ServerFeature {
+    /// Unique identifier of the role, role service or feature.
+    pub ID: Option<u32>,
+    /// `ID` of the parent role or feature, 0 for roles.
+    pub ParentID: Option<u32>,
+    /// Name of the role, role service or feature, e.g. `Web Server (IIS)`.
+    pub Name: Option<String>,
+}
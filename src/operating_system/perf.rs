@@ -0,0 +1,353 @@
+//! The Performance Counter classes provide formatted performance data, the same cooked values
+//! shown by Performance Monitor, computed over the interval since the provider last sampled them.
+//!
+//! | Class                                                                                 | Description                                                                                     |
+//! |---------------------------------------------------------------------------------------|-------------------------------------------------------------------------------------------------|
+//! | [**Win32\_PerfFormattedData\_PerfOS\_Processor**](Win32_PerfFormattedData_PerfOS_Processor)       | Formatted data class<br/> Processor utilization, one instance per logical processor and `_Total`.<br/> |
+//! | [**Win32\_PerfFormattedData\_PerfOS\_Memory**](Win32_PerfFormattedData_PerfOS_Memory)             | Formatted data class<br/> Physical and virtual memory usage and paging activity.<br/>          |
+//! | [**Win32\_PerfFormattedData\_PerfDisk\_LogicalDisk**](Win32_PerfFormattedData_PerfDisk_LogicalDisk) | Formatted data class<br/> Activity of logical disks, one instance per volume and `_Total`.<br/> |
+//! | [**Win32\_PerfFormattedData\_GPUPerformanceCounters\_GPUEngine**](Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine) | Formatted data class<br/> Utilization of GPU engines, one instance per process and engine.<br/> |
+
+use crate::{keyed, update};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection};
+
+/// Represents the state of Windows processor utilization
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ProcessorPerformances {
+    /// Sequence of windows processor utilization states
+    pub processor_performances: Vec<Win32_PerfFormattedData_PerfOS_Processor>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(ProcessorPerformances, processor_performances);
+keyed!(ProcessorPerformances, processor_performances, Win32_PerfFormattedData_PerfOS_Processor, Name: String);
+
+impl ProcessorPerformances {
+    /// Returns the `_Total` instance, which aggregates every logical processor
+    pub fn total(&self) -> Option<&Win32_PerfFormattedData_PerfOS_Processor> {
+        self.get_by_key("_Total")
+    }
+}
+
+/// Represents the state of Windows memory usage
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct MemoryPerformances {
+    /// Sequence of windows memory usage states
+    pub memory_performances: Vec<Win32_PerfFormattedData_PerfOS_Memory>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(MemoryPerformances, memory_performances);
+
+/// Represents the state of Windows logical disk activity
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct LogicalDiskPerformances {
+    /// Sequence of windows logical disk activity states
+    pub logical_disk_performances: Vec<Win32_PerfFormattedData_PerfDisk_LogicalDisk>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(LogicalDiskPerformances, logical_disk_performances);
+keyed!(LogicalDiskPerformances, logical_disk_performances, Win32_PerfFormattedData_PerfDisk_LogicalDisk, Name: String);
+
+impl LogicalDiskPerformances {
+    /// Returns the `_Total` instance, which aggregates every logical disk
+    pub fn total(&self) -> Option<&Win32_PerfFormattedData_PerfDisk_LogicalDisk> {
+        self.get_by_key("_Total")
+    }
+}
+
+/// Represents the state of Windows GPU engine utilization
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct GPUEnginePerformances {
+    /// Sequence of windows GPU engine utilization states
+    pub gpu_engine_performances: Vec<Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(GPUEnginePerformances, gpu_engine_performances);
+keyed!(GPUEnginePerformances, gpu_engine_performances, Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine, Name: String);
+
+impl GPUEnginePerformances {
+    /// Utilization summed per engine type, e.g. `3D` or `VideoDecode`, capped at 100 percent
+    ///
+    /// Each instance is the share of one engine used by one process, so the sum over the
+    /// instances of a type is the load of that type of engine, as Task Manager shows it.
+    pub fn utilization_by_engine_type(&self) -> BTreeMap<String, u64> {
+        let mut utilization = BTreeMap::new();
+
+        for engine in &self.gpu_engine_performances {
+            if let Some(engine_type) = engine.engine_type() {
+                *utilization.entry(engine_type.to_string()).or_insert(0) +=
+                    engine.UtilizationPercentage.unwrap_or_default();
+            }
+        }
+
+        for percentage in utilization.values_mut() {
+            *percentage = (*percentage).min(100);
+        }
+
+        utilization
+    }
+
+    /// Utilization summed per process, over every engine it uses
+    pub fn utilization_by_process(&self) -> BTreeMap<u32, u64> {
+        let mut utilization = BTreeMap::new();
+
+        for engine in &self.gpu_engine_performances {
+            if let Some(pid) = engine.process_id() {
+                *utilization.entry(pid).or_insert(0) += engine.UtilizationPercentage.unwrap_or_default();
+            }
+        }
+
+        utilization
+    }
+
+    /// Highest utilization of any engine type, `None` when no engine was sampled
+    pub fn busiest_engine_type(&self) -> Option<(String, u64)> {
+        self.utilization_by_engine_type()
+            .into_iter()
+            .max_by_key(|(_, percentage)| *percentage)
+    }
+}
+
+/// The `Win32_PerfFormattedData_PerfOS_Processor` WMI class represents the Processor performance
+/// object, which measures aspects of processor activity.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/aa394271(v=vs.85)>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_PerfFormattedData_PerfOS_Processor {
+    /// Processor the instance applies to, e.g. "0" or "_Total".
+    pub Name: Option<String>,
+    /// Average rate, per second, at which the processor entered the C1 low-power state.
+    pub C1TransitionsPersec: Option<u64>,
+    /// Average rate, per second, at which the processor entered the C2 low-power state.
+    pub C2TransitionsPersec: Option<u64>,
+    /// Average rate, per second, at which the processor entered the C3 low-power state.
+    pub C3TransitionsPersec: Option<u64>,
+    /// Rate at which deferred procedure calls (DPCs) were added to the processor's DPC queue
+    /// between clock ticks.
+    pub DPCRate: Option<u32>,
+    /// Average rate, per second, at which DPCs were added to the processor's DPC queue.
+    pub DPCsQueuedPersec: Option<u32>,
+    /// Average rate, per second, at which the processor received and serviced hardware interrupts.
+    pub InterruptsPersec: Option<u32>,
+    /// Percentage of time the processor spent in the C1 low-power state.
+    pub PercentC1Time: Option<u64>,
+    /// Percentage of time the processor spent in the C2 low-power state.
+    pub PercentC2Time: Option<u64>,
+    /// Percentage of time the processor spent in the C3 low-power state.
+    pub PercentC3Time: Option<u64>,
+    /// Percentage of time the processor spent receiving and servicing DPCs.
+    pub PercentDPCTime: Option<u64>,
+    /// Percentage of time the processor was idle.
+    pub PercentIdleTime: Option<u64>,
+    /// Percentage of time the processor spent receiving and servicing hardware interrupts.
+    pub PercentInterruptTime: Option<u64>,
+    /// Percentage of time the processor spent executing in privileged (kernel) mode.
+    pub PercentPrivilegedTime: Option<u64>,
+    /// Percentage of time the processor spent executing a non-idle thread.
+    pub PercentProcessorTime: Option<u64>,
+    /// Percentage of time the processor spent executing in user mode.
+    pub PercentUserTime: Option<u64>,
+}
+
+/// The `Win32_PerfFormattedData_PerfOS_Memory` WMI class represents the Memory performance object,
+/// which describes the behavior of physical and virtual memory.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/aa394268(v=vs.85)>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_PerfFormattedData_PerfOS_Memory {
+    /// Physical memory, in bytes, immediately available for allocation to a process or for system
+    /// use.
+    pub AvailableBytes: Option<u64>,
+    /// Physical memory, in megabytes, immediately available for allocation to a process or for
+    /// system use.
+    pub AvailableMBytes: Option<u64>,
+    /// Size, in bytes, of the portion of the system file cache that is currently resident in
+    /// physical memory.
+    pub CacheBytes: Option<u64>,
+    /// Rate, per second, at which faults occurred when a page sought in the file system cache was
+    /// not found.
+    pub CacheFaultsPersec: Option<u32>,
+    /// Amount of virtual memory, in bytes, that can be committed without extending the paging
+    /// files.
+    pub CommitLimit: Option<u64>,
+    /// Amount of committed virtual memory, in bytes.
+    pub CommittedBytes: Option<u64>,
+    /// Rate, per second, at which a page fault required a zeroed page to satisfy the fault.
+    pub DemandZeroFaultsPersec: Option<u32>,
+    /// Size, in bytes, of the free and zero page lists.
+    pub FreeAndZeroPageListBytes: Option<u64>,
+    /// Size, in bytes, of the modified page list.
+    pub ModifiedPageListBytes: Option<u64>,
+    /// Average number of pages faulted per second.
+    pub PageFaultsPersec: Option<u32>,
+    /// Rate, per second, at which the disk was read to resolve hard page faults.
+    pub PageReadsPersec: Option<u32>,
+    /// Rate, per second, at which pages were read from disk to resolve hard page faults.
+    pub PagesInputPersec: Option<u32>,
+    /// Rate, per second, at which pages were written to disk to free up space in physical memory.
+    pub PagesOutputPersec: Option<u32>,
+    /// Rate, per second, at which pages were read from or written to disk to resolve hard page
+    /// faults.
+    pub PagesPersec: Option<u32>,
+    /// Rate, per second, at which pages were written to disk to free up space in physical memory.
+    pub PageWritesPersec: Option<u32>,
+    /// Ratio of `CommittedBytes` to `CommitLimit`, in percent.
+    pub PercentCommittedBytesInUse: Option<u32>,
+    /// Size, in bytes, of the nonpaged pool.
+    pub PoolNonpagedBytes: Option<u64>,
+    /// Size, in bytes, of the paged pool.
+    pub PoolPagedBytes: Option<u64>,
+    /// Size, in bytes, of the standby cache at normal priority.
+    pub StandbyCacheNormalPriorityBytes: Option<u64>,
+    /// Rate, per second, at which page faults were resolved by recovering pages in transition.
+    pub TransitionFaultsPersec: Option<u32>,
+}
+
+/// The `Win32_PerfFormattedData_PerfDisk_LogicalDisk` WMI class represents the LogicalDisk
+/// performance object, which monitors logical partitions of hard or fixed disk drives.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/aa394261(v=vs.85)>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_PerfFormattedData_PerfDisk_LogicalDisk {
+    /// Logical disk the instance applies to, e.g. "C:" or "_Total".
+    pub Name: Option<String>,
+    /// Average number of bytes transferred from the disk during read operations.
+    pub AvgDiskBytesPerRead: Option<u64>,
+    /// Average number of bytes transferred to or from the disk during transfers.
+    pub AvgDiskBytesPerTransfer: Option<u64>,
+    /// Average number of bytes transferred to the disk during write operations.
+    pub AvgDiskBytesPerWrite: Option<u64>,
+    /// Average number of read and write requests queued for the disk.
+    pub AvgDiskQueueLength: Option<u64>,
+    /// Average number of read requests queued for the disk.
+    pub AvgDiskReadQueueLength: Option<u64>,
+    /// Average time, in milliseconds, of a read of data from the disk.
+    pub AvgDisksecPerRead: Option<u32>,
+    /// Average time, in milliseconds, of a disk transfer.
+    pub AvgDisksecPerTransfer: Option<u32>,
+    /// Average time, in milliseconds, of a write of data to the disk.
+    pub AvgDisksecPerWrite: Option<u32>,
+    /// Average number of write requests queued for the disk.
+    pub AvgDiskWriteQueueLength: Option<u64>,
+    /// Number of requests outstanding on the disk when the data was collected.
+    pub CurrentDiskQueueLength: Option<u32>,
+    /// Rate at which bytes were transferred to or from the disk during read or write operations.
+    pub DiskBytesPersec: Option<u64>,
+    /// Rate at which bytes were transferred from the disk during read operations.
+    pub DiskReadBytesPersec: Option<u64>,
+    /// Rate of read operations on the disk.
+    pub DiskReadsPersec: Option<u32>,
+    /// Rate of read and write operations on the disk.
+    pub DiskTransfersPersec: Option<u32>,
+    /// Rate at which bytes were transferred to the disk during write operations.
+    pub DiskWriteBytesPersec: Option<u64>,
+    /// Rate of write operations on the disk.
+    pub DiskWritesPersec: Option<u32>,
+    /// Unallocated space on the disk, in megabytes.
+    pub FreeMegabytes: Option<u32>,
+    /// Percentage of elapsed time the disk was busy servicing read or write requests.
+    pub PercentDiskTime: Option<u64>,
+    /// Percentage of elapsed time the disk was busy servicing read requests.
+    pub PercentDiskReadTime: Option<u64>,
+    /// Percentage of elapsed time the disk was busy servicing write requests.
+    pub PercentDiskWriteTime: Option<u64>,
+    /// Ratio of free space to the total usable space of the disk, in percent.
+    pub PercentFreeSpace: Option<u32>,
+    /// Percentage of time the disk was idle.
+    pub PercentIdleTime: Option<u64>,
+    /// Rate at which I/Os to the disk were split into multiple I/Os.
+    pub SplitIOPerSec: Option<u32>,
+}
+
+/// The `Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine` WMI class represents the GPU
+/// Engine performance object, which measures the utilization of each engine of each GPU by each
+/// process.
+///
+/// Available from Windows 10 version 1709 with a WDDM 2.0 or later display driver.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/perfctrs/performance-counters-portal>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine {
+    /// Process, adapter and engine the instance applies to, e.g.
+    /// "pid_1234_luid_0x00000000_0x0000D1C3_phys_0_eng_0_engtype_3D".
+    pub Name: Option<String>,
+    /// Time, in 100 nanosecond units, the engine spent running work of the process.
+    pub RunningTime: Option<u64>,
+    /// Percentage of time the engine spent running work of the process.
+    pub UtilizationPercentage: Option<u64>,
+}
+
+impl Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine {
+    /// Type of the engine, the part of `Name` after `engtype_`, e.g. `3D`, `Copy` or `VideoDecode`
+    pub fn engine_type(&self) -> Option<&str> {
+        let name = self.Name.as_deref()?;
+
+        name.find("_engtype_").map(|start| &name[start + "_engtype_".len()..])
+    }
+
+    /// Process the instance applies to, parsed from the `pid_` prefix of `Name`
+    pub fn process_id(&self) -> Option<u32> {
+        self.Name
+            .as_deref()?
+            .strip_prefix("pid_")?
+            .split('_')
+            .next()?
+            .parse()
+            .ok()
+    }
+
+    /// Locally unique identifier of the adapter, e.g. `0x00000000_0x0000D1C3`
+    pub fn adapter_luid(&self) -> Option<&str> {
+        let name = self.Name.as_deref()?;
+        let start = name.find("_luid_")? + "_luid_".len();
+        let end = name[start..].find("_phys_")? + start;
+
+        Some(&name[start..end])
+    }
+}
@@ -0,0 +1,397 @@
+//! Everything set to run automatically at boot or logon, consolidated into one list tagged with
+//! where each item was found.
+//!
+//! | Source                                                     | Description                                                                                          |
+//! |------------------------------------------------------------|------------------------------------------------------------------------------------------------------|
+//! | `Run` and `RunOnce` keys of `HKEY_LOCAL_MACHINE`            | Commands run for every user at logon, 32-bit and policy keys included.                              |
+//! | `Run` and `RunOnce` keys of every profile in `HKEY_USERS`   | Commands run at the logon of one user, only for the profiles whose hive is loaded.                  |
+//! | Common `StartUp` folder                                    | Shortcuts and programs under `%ProgramData%`, started for every user at logon.                      |
+//! | Per-user `Startup` folders                                 | Shortcuts and programs in the profile of every user listed under `ProfileList`, loaded or not.       |
+//! | Scheduled tasks with a logon trigger                       | Task definitions under `%SystemRoot%\System32\Tasks`, readable with administrative rights only.     |
+//!
+//! [`Win32_StartupCommand`](crate::operating_system::operating_system_settings::Win32_StartupCommand)
+//! covers the first sources for the loaded profiles only and misses scheduled tasks,
+//! [`PersistenceItems::merge_startup_commands`] adds what it lists on top, so nothing is lost.
+
+use crate::operating_system::operating_system_settings::StartupCommands;
+use crate::operating_system::registry::{self, Hive, RegistryValue};
+use crate::{state_member, Unavailable};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const RUN_KEYS: [&str; 5] = [
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Run",
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\RunOnce",
+    "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Run",
+    "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\RunOnce",
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Policies\\Explorer\\Run",
+];
+
+const PROFILE_LIST: &str = "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\ProfileList";
+
+const USER_STARTUP: &str = "AppData\\Roaming\\Microsoft\\Windows\\Start Menu\\Programs\\Startup";
+
+/// Represents the state of the items Windows runs automatically at boot or logon
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct PersistenceItems {
+    /// Sequence of autostart items, by source
+    pub persistence_items: Vec<PersistenceItem>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the scheduled tasks could not be read, `None` when they were, the other sources are
+    /// listed regardless
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(PersistenceItems, persistence_items);
+
+impl PersistenceItems {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let (items, unavailable) = read_sources();
+
+        self.refresh(items, unavailable);
+    }
+
+    /// Update fields asynchronously
+    ///
+    /// The registry, the startup folders and the Task Scheduler have no asynchronous reads, so
+    /// they run on a blocking thread of the runtime
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let (items, unavailable) = crate::blocking("PersistenceItem", read_sources)
+            .await
+            .unwrap_or_else(|unavailable| (Vec::new(), Some(unavailable)));
+
+        self.refresh(items, unavailable);
+    }
+
+    /// Items found in `source`
+    pub fn from_source(&self, source: PersistenceSource) -> Vec<&PersistenceItem> {
+        self.persistence_items
+            .iter()
+            .filter(|item| item.source == source)
+            .collect()
+    }
+
+    /// Items of the user `sid`, machine-wide items excluded
+    pub fn of_user(&self, sid: &str) -> Vec<&PersistenceItem> {
+        self.persistence_items
+            .iter()
+            .filter(|item| item.user.as_deref().is_some_and(|user| user.eq_ignore_ascii_case(sid)))
+            .collect()
+    }
+
+    /// Adds the startup commands WMI lists that none of the sources read found, tagged
+    /// [`PersistenceSource::StartupCommand`]
+    pub fn merge_startup_commands(&mut self, startup_commands: &StartupCommands) {
+        for command in &startup_commands.startup_commands {
+            let Some(text) = command.Command.as_deref() else {
+                continue;
+            };
+            let name = command.Name.clone().unwrap_or_default();
+
+            let known = self
+                .persistence_items
+                .iter()
+                .any(|item| item.name == name && item.command.eq_ignore_ascii_case(text));
+
+            if !known {
+                self.persistence_items.push(PersistenceItem {
+                    source: PersistenceSource::StartupCommand,
+                    name,
+                    command: text.to_string(),
+                    location: command.Location.clone().unwrap_or_default(),
+                    user: command.UserSID.clone(),
+                });
+            }
+        }
+    }
+
+    fn refresh(&mut self, items: Vec<PersistenceItem>, unavailable: Option<Unavailable>) {
+        let old_vec = std::mem::replace(&mut self.persistence_items, items);
+        let old_unavailable = std::mem::replace(&mut self.unavailable, unavailable);
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.persistence_items.len() != old_vec.len()
+            || crate::hash_vec(&self.persistence_items) != crate::hash_vec(&old_vec);
+    }
+}
+
+/// Items of every source, with why the logon tasks could not be read
+fn read_sources() -> (Vec<PersistenceItem>, Option<Unavailable>) {
+    let mut items = run_keys();
+    items.extend(startup_folders());
+
+    match logon_tasks() {
+        Ok(tasks) => {
+            items.extend(tasks);
+            (items, None)
+        }
+        Err(unavailable) => (items, Some(unavailable)),
+    }
+}
+
+impl Default for PersistenceItems {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        PersistenceItems {
+            persistence_items: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// Where an autostart item was found
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PersistenceSource {
+    /// A `Run` or `RunOnce` value under `HKEY_LOCAL_MACHINE`, run for every user
+    #[default]
+    MachineRunKey,
+    /// A `Run` or `RunOnce` value in the hive of one user
+    UserRunKey,
+    /// A file in the `StartUp` folder shared by every user
+    CommonStartupFolder,
+    /// A file in the `Startup` folder of one user
+    UserStartupFolder,
+    /// An action of a scheduled task started by a logon trigger
+    LogonTask,
+    /// A `Win32_StartupCommand` none of the other sources found
+    StartupCommand,
+}
+
+/// An item Windows runs automatically
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PersistenceItem {
+    /// Where the item was found
+    pub source: PersistenceSource,
+    /// Name of the registry value, file or task, e.g. `OneDrive` or `\Vendor\Updater`
+    pub name: String,
+    /// Command run, or path of the file for startup folders
+    pub command: String,
+    /// Registry key, folder or task file the item was read from
+    pub location: String,
+    /// SID of the user the item runs for, `None` for machine-wide items
+    pub user: Option<String>,
+}
+
+/// Values of the run keys of the machine and of every loaded user hive
+fn run_keys() -> Vec<PersistenceItem> {
+    let machine = RUN_KEYS
+        .iter()
+        .flat_map(|key| run_values(Hive::LocalMachine, key, PersistenceSource::MachineRunKey, None));
+
+    let users = registry::subkeys(Hive::Users, "")
+        .into_iter()
+        .filter(|sid| sid.starts_with("S-") && !sid.ends_with("_Classes"))
+        .flat_map(|sid| {
+            RUN_KEYS
+                .iter()
+                .flat_map(|key| {
+                    run_values(Hive::Users, &format!("{sid}\\{key}"), PersistenceSource::UserRunKey, Some(&sid))
+                })
+                .collect::<Vec<_>>()
+        });
+
+    machine.chain(users).collect()
+}
+
+fn run_values(hive: Hive, key: &str, source: PersistenceSource, sid: Option<&str>) -> Vec<PersistenceItem> {
+    let prefix = match hive {
+        Hive::LocalMachine => "HKLM",
+        Hive::CurrentUser => "HKCU",
+        Hive::Users => "HKU",
+    };
+
+    registry::values(hive, key)
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let command = match value {
+                RegistryValue::String(command) | RegistryValue::ExpandString(command) => command,
+                _ => return None,
+            };
+
+            Some(PersistenceItem {
+                source,
+                name,
+                command,
+                location: format!("{prefix}\\{key}"),
+                user: sid.map(String::from),
+            })
+        })
+        .collect()
+}
+
+/// Files of the common startup folder and of the startup folder of every profile
+fn startup_folders() -> Vec<PersistenceItem> {
+    let program_data = std::env::var_os("ProgramData").unwrap_or_else(|| "C:\\ProgramData".into());
+    let common = PathBuf::from(program_data).join("Microsoft\\Windows\\Start Menu\\Programs\\StartUp");
+
+    let mut items = folder_items(&common, PersistenceSource::CommonStartupFolder, None);
+
+    for sid in registry::subkeys(Hive::LocalMachine, PROFILE_LIST) {
+        let profile = registry::read_value(Hive::LocalMachine, &format!("{PROFILE_LIST}\\{sid}"), "ProfileImagePath");
+
+        if let Some(profile) = profile.as_ref().and_then(RegistryValue::as_str) {
+            let folder = PathBuf::from(crate::expand_environment(profile)).join(USER_STARTUP);
+
+            items.extend(folder_items(&folder, PersistenceSource::UserStartupFolder, Some(&sid)));
+        }
+    }
+
+    items
+}
+
+fn folder_items(folder: &Path, source: PersistenceSource, sid: Option<&str>) -> Vec<PersistenceItem> {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_file()))
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| !name.eq_ignore_ascii_case("desktop.ini"))
+        .map(|name| PersistenceItem {
+            source,
+            command: folder.join(&name).to_string_lossy().into_owned(),
+            name,
+            location: folder.to_string_lossy().into_owned(),
+            user: sid.map(String::from),
+        })
+        .collect()
+}
+
+/// Actions of the scheduled tasks started at logon, read from their definitions
+fn logon_tasks() -> Result<Vec<PersistenceItem>, Unavailable> {
+    let system_root = std::env::var_os("SystemRoot").unwrap_or_else(|| "C:\\Windows".into());
+    let root = PathBuf::from(system_root).join("System32").join("Tasks");
+
+    let mut files = Vec::new();
+    task_files(&root, &mut files).map_err(|e| Unavailable::from_io(&root.to_string_lossy(), &e))?;
+
+    Ok(files
+        .iter()
+        .flat_map(|file| logon_task(&root, file).unwrap_or_default())
+        .collect())
+}
+
+/// Actions of the task defined in `file`, `None` unless it has a logon trigger
+fn logon_task(root: &Path, file: &Path) -> Option<Vec<PersistenceItem>> {
+    let definition = decode(&std::fs::read(file).ok()?);
+
+    if !definition.contains("<LogonTrigger") {
+        return None;
+    }
+
+    let name = file
+        .strip_prefix(root)
+        .map(|path| format!("\\{}", path.to_string_lossy()))
+        .unwrap_or_default();
+    let user = element(&definition, "UserId").map(unescape);
+    let location = file.to_string_lossy().into_owned();
+
+    let items = elements(&definition, "Exec")
+        .into_iter()
+        .filter_map(|exec| {
+            let command = unescape(element(exec, "Command")?);
+            let command = match element(exec, "Arguments") {
+                Some(arguments) => format!("{command} {}", unescape(arguments)),
+                None => command,
+            };
+
+            Some(PersistenceItem {
+                source: PersistenceSource::LogonTask,
+                name: name.clone(),
+                command,
+                location: location.clone(),
+                user: user.clone(),
+            })
+        })
+        .collect();
+
+    Some(items)
+}
+
+/// Collects the task definition files under `folder`, recursively
+fn task_files(folder: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(folder)?.flatten() {
+        let path = entry.path();
+
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => {
+                let _ = task_files(&path, files);
+            }
+            Ok(file_type) if file_type.is_file() => files.push(path),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Task definitions are UTF-16 with a byte order mark, older ones UTF-8
+fn decode(bytes: &[u8]) -> String {
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => {
+            let units: Vec<u16> = rest.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+
+            String::from_utf16_lossy(&units)
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// Text of the first `<name>` element of `xml`
+fn element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    elements(xml, name).into_iter().next()
+}
+
+/// Text of every `<name>` element of `xml`, attributes ignored
+fn elements<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let open = format!("<{name}");
+    let close = format!("</{name}>");
+    let mut found = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+
+        // `<Exec` must not match `<ExecutionTimeLimit`
+        if !after.starts_with(['>', ' ', '\t', '\r', '\n']) {
+            rest = after;
+            continue;
+        }
+
+        let Some(content) = after.find('>').map(|end| &after[end + 1..]) else {
+            break;
+        };
+        let Some(end) = content.find(&close) else {
+            break;
+        };
+
+        found.push(content[..end].trim());
+        rest = &content[end + close.len()..];
+    }
+
+    found
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
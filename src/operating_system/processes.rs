@@ -4,10 +4,15 @@
 //! |-------------------------------------------------------|-----------------------------------------------------------------------------------------------------------------|
 //! | [**Win32\_Process**](Win32_Process)               | Instance class<br/> Represents a sequence of events on a computer system running Windows.<br/>      |
 //! | [**Win32\_Thread**](Win32_Thread)                 | Instance class<br/> Represents a thread of execution.<br/>                                          |
+//! | [**CIM\_ProcessExecutable**](https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/cim-processexecutable) | Association class<br/> Relates a process and the executable files (modules) it has loaded.<br/> |
 
-use crate::update;
+use crate::method::{exec_method, instance_path};
+use crate::report::{format_bytes, Summary, UNKNOWN};
+use crate::{associates, cim_datetime, keyed, referenced, update, wql_string, Unavailable, UnavailableReason, DEFAULT_NAMESPACE};
+use std::collections::{BTreeMap, HashMap};
 use serde::{Deserialize, Serialize};
-use std::time::SystemTime;
+use std::fmt;
+use std::time::{Duration, SystemTime};
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
 
 /// Represents the state of Windows Processes
@@ -22,9 +27,403 @@ pub struct Processes {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+    /// Change of the CPU and I/O counters of every process since the previous update, empty until
+    /// the state is updated twice
+    #[serde(default)]
+    pub deltas: Vec<ProcessDelta>,
 }
 
-update!(Processes, processes);
+update!(Processes, processes; deltas => record_deltas);
+keyed!(Processes, processes, Win32_Process, ProcessId: u32);
+
+impl Processes {
+    /// Number of processes and the largest ones by working set
+    pub fn summary(&self) -> Summary {
+        let mut largest: Vec<&Win32_Process> = self.processes.iter().collect();
+        largest.sort_by_key(|process| std::cmp::Reverse(process.WorkingSetSize));
+
+        let largest = largest
+            .iter()
+            .take(5)
+            .map(|process| {
+                format!(
+                    "{} ({})",
+                    process.Name.as_deref().unwrap_or(UNKNOWN),
+                    process.WorkingSetSize.map_or_else(|| UNKNOWN.to_string(), format_bytes)
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Summary::new("Processes")
+            .line("Running", self.processes.len())
+            .line("Largest", largest.join(", "))
+    }
+
+    /// Synchronously queries only the processes created after `since`
+    ///
+    /// Filters on `CreationDate` in the WQL query, far cheaper than listing every process to find
+    /// the new ones when polling at short intervals.
+    pub fn created_since(since: SystemTime) -> Result<Vec<Win32_Process>, Unavailable> {
+        crate::raw_query(&created_since_query(since))
+    }
+
+    /// Asynchronously queries only the processes created after `since`, see
+    /// [`Processes::created_since`]
+    pub async fn async_created_since(since: SystemTime) -> Result<Vec<Win32_Process>, Unavailable> {
+        crate::async_raw_query(&created_since_query(since)).await
+    }
+
+    /// Builds the parent/child tree of the processes
+    pub fn tree(&self) -> ProcessTree<'_> {
+        ProcessTree::new(&self.processes)
+    }
+
+    /// Synchronously captures the modules loaded by the processes `process_ids`, skipping the ones
+    /// missing from this snapshot
+    ///
+    /// Listing the modules of every process is expensive, so only selected processes are queried.
+    pub fn capture_modules_for(&self, process_ids: &[u32]) -> ProcessModules {
+        let mut modules = ProcessModules::new(self.known(process_ids));
+        modules.update();
+        modules
+    }
+
+    /// Asynchronously captures the modules loaded by the processes `process_ids`, skipping the
+    /// ones missing from this snapshot
+    pub async fn async_capture_modules_for(&self, process_ids: &[u32]) -> ProcessModules {
+        let mut modules = ProcessModules::new(self.known(process_ids));
+        modules.async_update().await;
+        modules
+    }
+
+    /// Change of the counters of the process `process_id` since the previous update
+    pub fn delta_of(&self, process_id: u32) -> Option<&ProcessDelta> {
+        self.deltas.iter().find(|delta| delta.process_id == process_id)
+    }
+
+    /// The `n` processes that used the most CPU time since the previous update
+    pub fn top_cpu(&self, n: usize) -> Vec<&ProcessDelta> {
+        let mut deltas: Vec<&ProcessDelta> = self.deltas.iter().collect();
+        deltas.sort_by_key(|delta| std::cmp::Reverse(delta.cpu_time()));
+        deltas.truncate(n);
+        deltas
+    }
+
+    /// The `n` processes that read and wrote the most bytes since the previous update
+    pub fn top_io(&self, n: usize) -> Vec<&ProcessDelta> {
+        let mut deltas: Vec<&ProcessDelta> = self.deltas.iter().collect();
+        deltas.sort_by_key(|delta| std::cmp::Reverse(delta.transfer_count()));
+        deltas.truncate(n);
+        deltas
+    }
+
+    /// Computes the deltas of the processes still running since the update that returned
+    /// `previous` at `previous_updated`
+    ///
+    /// Processes are matched by `ProcessId` and `CreationDate`, so an id reused by a new process
+    /// is not mistaken for the old one. New processes have no delta yet.
+    fn record_deltas(&mut self, previous: &[Win32_Process], previous_updated: SystemTime) {
+        let elapsed = self
+            .last_updated
+            .duration_since(previous_updated)
+            .unwrap_or_default();
+        let previous: HashMap<(u32, Option<WMIDateTime>), &Win32_Process> = previous
+            .iter()
+            .filter_map(|process| Some(((process.ProcessId?, process.CreationDate), process)))
+            .collect();
+
+        self.deltas = self
+            .processes
+            .iter()
+            .filter_map(|process| {
+                let before = previous.get(&(process.ProcessId?, process.CreationDate))?;
+
+                Some(ProcessDelta::between(before, process, elapsed))
+            })
+            .collect();
+    }
+
+    fn known(&self, process_ids: &[u32]) -> Vec<u32> {
+        process_ids
+            .iter()
+            .copied()
+            .filter(|process_id| self.get_by_key(process_id).is_some())
+            .collect()
+    }
+}
+
+fn created_since_query(since: SystemTime) -> String {
+    format!(
+        "SELECT * FROM Win32_Process WHERE CreationDate > '{}'",
+        cim_datetime(since)
+    )
+}
+
+impl fmt::Display for Processes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.summary().fmt(f)
+    }
+}
+
+/// Change of the CPU and I/O counters of a process between two updates of [`Processes`]
+///
+/// Counters that went backwards or were unavailable count as unchanged.
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ProcessDelta {
+    /// `ProcessId` of the process
+    pub process_id: u32,
+    /// `Name` of the process
+    pub name: Option<String>,
+    /// Time between the two updates
+    pub elapsed: Duration,
+    /// Change of `KernelModeTime`, in 100 nanosecond units
+    pub kernel_mode_time: u64,
+    /// Change of `UserModeTime`, in 100 nanosecond units
+    pub user_mode_time: u64,
+    /// Change of `ReadTransferCount`, in bytes
+    pub read_transfer_count: u64,
+    /// Change of `WriteTransferCount`, in bytes
+    pub write_transfer_count: u64,
+    /// Change of `OtherTransferCount`, in bytes
+    pub other_transfer_count: u64,
+    /// Change of `ReadOperationCount`
+    pub read_operation_count: u64,
+    /// Change of `WriteOperationCount`
+    pub write_operation_count: u64,
+    /// Change of `OtherOperationCount`
+    pub other_operation_count: u64,
+    /// Change of `PageFaults`
+    pub page_faults: u64,
+}
+
+impl ProcessDelta {
+    /// Change of the counters from `before` to `after`, the same process updated `elapsed` apart
+    pub fn between(before: &Win32_Process, after: &Win32_Process, elapsed: Duration) -> Self {
+        let delta = |counter: fn(&Win32_Process) -> Option<u64>| {
+            counter(after)
+                .unwrap_or_default()
+                .saturating_sub(counter(before).unwrap_or_default())
+        };
+
+        ProcessDelta {
+            process_id: after.ProcessId.unwrap_or_default(),
+            name: after.Name.clone(),
+            elapsed,
+            kernel_mode_time: delta(|process| process.KernelModeTime),
+            user_mode_time: delta(|process| process.UserModeTime),
+            read_transfer_count: delta(|process| process.ReadTransferCount),
+            write_transfer_count: delta(|process| process.WriteTransferCount),
+            other_transfer_count: delta(|process| process.OtherTransferCount),
+            read_operation_count: delta(|process| process.ReadOperationCount),
+            write_operation_count: delta(|process| process.WriteOperationCount),
+            other_operation_count: delta(|process| process.OtherOperationCount),
+            page_faults: delta(|process| process.PageFaults.map(u64::from)),
+        }
+    }
+
+    /// CPU time used, in kernel and user mode
+    pub fn cpu_time(&self) -> Duration {
+        Duration::from_nanos((self.kernel_mode_time + self.user_mode_time).saturating_mul(100))
+    }
+
+    /// CPU time used as a percentage of `elapsed`, up to 100 times the number of logical
+    /// processors, `None` if no time elapsed
+    pub fn cpu_percent(&self) -> Option<f64> {
+        rate(self.cpu_time().as_secs_f64(), self.elapsed).map(|share| share * 100.0)
+    }
+
+    /// Bytes read, written and transferred otherwise
+    pub fn transfer_count(&self) -> u64 {
+        self.read_transfer_count + self.write_transfer_count + self.other_transfer_count
+    }
+
+    /// Bytes read per second, `None` if no time elapsed
+    pub fn read_bytes_per_sec(&self) -> Option<f64> {
+        rate(self.read_transfer_count as f64, self.elapsed)
+    }
+
+    /// Bytes written per second, `None` if no time elapsed
+    pub fn write_bytes_per_sec(&self) -> Option<f64> {
+        rate(self.write_transfer_count as f64, self.elapsed)
+    }
+
+    /// Bytes read, written and transferred otherwise per second, `None` if no time elapsed
+    pub fn transfer_bytes_per_sec(&self) -> Option<f64> {
+        rate(self.transfer_count() as f64, self.elapsed)
+    }
+}
+
+/// `amount` per second of `elapsed`, `None` if no time elapsed
+fn rate(amount: f64, elapsed: Duration) -> Option<f64> {
+    (!elapsed.is_zero()).then(|| amount / elapsed.as_secs_f64())
+}
+
+/// Parent/child tree of a process snapshot
+///
+/// A process is the child of the process whose `ProcessId` is its `ParentProcessId` and that was
+/// created before it. A parent that exited and whose id was reused by a newer process is not
+/// matched, its orphaned children become roots of the tree.
+#[derive(Debug, Clone)]
+pub struct ProcessTree<'a> {
+    processes: &'a [Win32_Process],
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    roots: Vec<usize>,
+}
+
+impl<'a> ProcessTree<'a> {
+    /// Builds the tree of `processes`
+    pub fn new(processes: &'a [Win32_Process]) -> Self {
+        let mut by_id: HashMap<u32, Vec<usize>> = HashMap::new();
+
+        for (index, process) in processes.iter().enumerate() {
+            if let Some(process_id) = process.ProcessId {
+                by_id.entry(process_id).or_default().push(index);
+            }
+        }
+
+        let mut parents: Vec<Option<usize>> = processes
+            .iter()
+            .enumerate()
+            .map(|(index, process)| {
+                by_id
+                    .get(&process.ParentProcessId?)?
+                    .iter()
+                    .copied()
+                    .filter(|&candidate| candidate != index)
+                    .filter(|&candidate| {
+                        match (&processes[candidate].CreationDate, &process.CreationDate) {
+                            (Some(parent_created), Some(created)) => parent_created <= created,
+                            _ => true,
+                        }
+                    })
+                    .max_by(|&a, &b| processes[a].CreationDate.cmp(&processes[b].CreationDate))
+            })
+            .collect();
+
+        // Without creation dates two processes can claim each other, cut such loops into roots
+        for index in 0..parents.len() {
+            let mut current = parents[index];
+
+            for _ in 0..parents.len() {
+                match current {
+                    Some(ancestor) if ancestor == index => {
+                        parents[index] = None;
+                        break;
+                    }
+                    Some(ancestor) => current = parents[ancestor],
+                    None => break,
+                }
+            }
+        }
+
+        let mut children = vec![Vec::new(); processes.len()];
+        let mut roots = Vec::new();
+
+        for (index, parent) in parents.iter().enumerate() {
+            match parent {
+                Some(parent) => children[*parent].push(index),
+                None => roots.push(index),
+            }
+        }
+
+        ProcessTree {
+            processes,
+            parents,
+            children,
+            roots,
+        }
+    }
+
+    /// Processes without a parent in the snapshot
+    pub fn roots(&self) -> impl Iterator<Item = ProcessNode<'_>> {
+        self.roots.iter().map(move |&index| self.node(index))
+    }
+
+    /// Node of the process `process_id`, the most recently created one if the id appears twice
+    pub fn get(&self, process_id: u32) -> Option<ProcessNode<'_>> {
+        self.processes
+            .iter()
+            .enumerate()
+            .filter(|(_, process)| process.ProcessId == Some(process_id))
+            .max_by(|(_, a), (_, b)| a.CreationDate.cmp(&b.CreationDate))
+            .map(|(index, _)| self.node(index))
+    }
+
+    /// Every node, in snapshot order
+    pub fn iter(&self) -> impl Iterator<Item = ProcessNode<'_>> {
+        (0..self.processes.len()).map(move |index| self.node(index))
+    }
+
+    fn node(&self, index: usize) -> ProcessNode<'_> {
+        ProcessNode {
+            tree: self,
+            index,
+        }
+    }
+}
+
+/// A process in a [`ProcessTree`]
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessNode<'a> {
+    tree: &'a ProcessTree<'a>,
+    index: usize,
+}
+
+impl<'a> ProcessNode<'a> {
+    /// The process
+    pub fn process(&self) -> &'a Win32_Process {
+        &self.tree.processes[self.index]
+    }
+
+    /// Parent of the process, `None` for a root
+    pub fn parent(&self) -> Option<ProcessNode<'a>> {
+        Some(self.tree.node(self.tree.parents[self.index]?))
+    }
+
+    /// Direct children of the process
+    pub fn children(&self) -> impl Iterator<Item = ProcessNode<'a>> + 'a {
+        let tree = self.tree;
+
+        tree.children[self.index].iter().map(move |&index| tree.node(index))
+    }
+
+    /// Parent, grandparent and so on up to the root
+    pub fn ancestors(&self) -> impl Iterator<Item = ProcessNode<'a>> + 'a {
+        std::iter::successors(self.parent(), ProcessNode::parent)
+    }
+
+    /// Every process below this one, depth first
+    pub fn descendants(&self) -> impl Iterator<Item = ProcessNode<'a>> + 'a {
+        let tree = self.tree;
+        let mut stack: Vec<usize> = tree.children[self.index].iter().rev().copied().collect();
+
+        std::iter::from_fn(move || {
+            let index = stack.pop()?;
+            stack.extend(tree.children[index].iter().rev());
+            Some(tree.node(index))
+        })
+    }
+
+    /// Number of ancestors, 0 for a root
+    pub fn depth(&self) -> usize {
+        self.ancestors().count()
+    }
+
+    /// Names of the process and its ancestors, from the root down, e.g.
+    /// `["wininit.exe", "services.exe", "svchost.exe"]`
+    pub fn ancestry(&self) -> Vec<&'a str> {
+        let mut ancestry: Vec<&'a str> = std::iter::once(*self)
+            .chain(self.ancestors())
+            .map(|node| node.process().Name.as_deref().unwrap_or_default())
+            .collect();
+
+        ancestry.reverse();
+        ancestry
+    }
+}
 
 /// Represents the state of Windows threads
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -38,10 +437,365 @@ pub struct Threads {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Threads, threads);
 
+impl Threads {
+    /// Threads owned by the process `process_id`
+    pub fn of_process(&self, process_id: u32) -> Vec<&Win32_Thread> {
+        self.threads
+            .iter()
+            .filter(|thread| thread.process_id() == Some(process_id))
+            .collect()
+    }
+
+    /// Threads grouped by the `ProcessId` of their owning process
+    pub fn by_process(&self) -> BTreeMap<u32, Vec<&Win32_Thread>> {
+        let mut by_process: BTreeMap<u32, Vec<&Win32_Thread>> = BTreeMap::new();
+
+        for thread in &self.threads {
+            if let Some(process_id) = thread.process_id() {
+                by_process.entry(process_id).or_default().push(thread);
+            }
+        }
+
+        by_process
+    }
+}
+
+/// Represents the state of the threads of selected Windows processes
+///
+/// Only the threads owned by `process_ids` are queried, which is far cheaper than listing every
+/// thread of the machine.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ProcessThreads {
+    /// `ProcessId` of the processes whose threads are queried, every process when empty
+    pub process_ids: Vec<u32>,
+    /// Sequence of threads owned by `process_ids`
+    pub threads: Vec<Win32_Thread>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+impl ProcessThreads {
+    /// Creates an empty state that queries the threads of `process_ids`
+    pub fn new(process_ids: Vec<u32>) -> Self {
+        ProcessThreads {
+            process_ids,
+            threads: Vec::new(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let threads = crate::raw_query(&self.query());
+
+        self.refresh(threads);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let threads = crate::async_raw_query(&self.query()).await;
+
+        self.refresh(threads);
+    }
+
+    fn refresh(&mut self, threads: Result<Vec<Win32_Thread>, Unavailable>) {
+        let old_vec = std::mem::take(&mut self.threads);
+        let old_unavailable = self.unavailable.take();
+
+        match threads {
+            Ok(threads) => self.threads = threads,
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.threads.len() != old_vec.len()
+            || crate::hash_vec(&self.threads) != crate::hash_vec(&old_vec);
+    }
+
+    /// `ProcessHandle` holds the `ProcessId` of the owning process as a string
+    fn query(&self) -> String {
+        if self.process_ids.is_empty() {
+            return String::from("SELECT * FROM Win32_Thread");
+        }
+
+        let conditions = self
+            .process_ids
+            .iter()
+            .map(|process_id| format!("ProcessHandle = {}", wql_string(&process_id.to_string())))
+            .collect::<Vec<_>>();
+
+        format!("SELECT * FROM Win32_Thread WHERE {}", conditions.join(" OR "))
+    }
+}
+
+impl Default for ProcessThreads {
+    /// Queries every thread, `last_updated` defaults to the current `SystemTime`
+    fn default() -> Self {
+        ProcessThreads::new(Vec::new())
+    }
+}
+
+/// Represents the state of the modules loaded by selected Windows processes
+///
+/// Modules are listed per process through the `CIM_ProcessExecutable` association, see
+/// [`Processes::capture_modules_for`].
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ProcessModules {
+    /// `ProcessId` of the processes whose modules are queried, every process when empty
+    pub process_ids: Vec<u32>,
+    /// Sequence of modules loaded by `process_ids`
+    pub process_modules: Vec<ProcessModule>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+impl ProcessModules {
+    /// Creates an empty state that queries the modules of `process_ids`
+    pub fn new(process_ids: Vec<u32>) -> Self {
+        ProcessModules {
+            process_ids,
+            process_modules: Vec::new(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let associations = self
+            .queries()
+            .iter()
+            .map(|query| exited_as_empty(crate::raw_query(query)))
+            .collect::<Result<Vec<_>, _>>();
+
+        self.refresh(associations.map(|associations| associations.concat()));
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let mut associations = Ok(Vec::new());
+
+        for query in self.queries() {
+            let result = crate::async_raw_query(&query).await;
+
+            match (&mut associations, exited_as_empty(result)) {
+                (Ok(associations), Ok(found)) => associations.extend(found),
+                (_, Err(unavailable)) => {
+                    associations = Err(unavailable);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        self.refresh(associations);
+    }
+
+    /// Modules loaded by the process `process_id`
+    pub fn of_process(&self, process_id: u32) -> Vec<&ProcessModule> {
+        self.process_modules
+            .iter()
+            .filter(|module| module.process_id == process_id)
+            .collect()
+    }
+
+    /// Processes that loaded the module at `path`, compared case insensitively
+    pub fn loaded_by(&self, path: &str) -> Vec<u32> {
+        self.process_modules
+            .iter()
+            .filter(|module| module.path.eq_ignore_ascii_case(path))
+            .map(|module| module.process_id)
+            .collect()
+    }
+
+    fn refresh(&mut self, associations: Result<Vec<CIM_ProcessExecutable>, Unavailable>) {
+        let old_vec = std::mem::take(&mut self.process_modules);
+        let old_unavailable = self.unavailable.take();
+
+        match associations {
+            Ok(associations) => {
+                self.process_modules = associations
+                    .iter()
+                    .filter_map(ProcessModule::from_association)
+                    .collect()
+            }
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.process_modules.len() != old_vec.len()
+            || crate::hash_vec(&self.process_modules) != crate::hash_vec(&old_vec);
+    }
+
+    /// One `REFERENCES OF` query per process, as the association cannot be filtered on its
+    /// references with `SELECT`
+    fn queries(&self) -> Vec<String> {
+        if self.process_ids.is_empty() {
+            return vec![String::from("SELECT * FROM CIM_ProcessExecutable")];
+        }
+
+        self.process_ids
+            .iter()
+            .map(|process_id| {
+                format!(
+                    "REFERENCES OF {{Win32_Process.Handle=\"{process_id}\"}} WHERE ResultClass = CIM_ProcessExecutable"
+                )
+            })
+            .collect()
+    }
+}
+
+impl Default for ProcessModules {
+    /// Queries the modules of every process, `last_updated` defaults to the current `SystemTime`
+    fn default() -> Self {
+        ProcessModules::new(Vec::new())
+    }
+}
+
+/// A process that exited since it was selected is reported as not found, it has no module left
+fn exited_as_empty(
+    result: Result<Vec<CIM_ProcessExecutable>, Unavailable>,
+) -> Result<Vec<CIM_ProcessExecutable>, Unavailable> {
+    match result {
+        Err(unavailable) if unavailable.reason == UnavailableReason::InvalidClass => Ok(Vec::new()),
+        result => result,
+    }
+}
+
+/// A module loaded by a process
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ProcessModule {
+    /// `ProcessId` of the process that loaded the module
+    pub process_id: u32,
+    /// Path of the module file, e.g. `C:\Windows\System32\ntdll.dll`
+    pub path: String,
+    /// Address the module is loaded at in the process
+    pub base_address: Option<u64>,
+}
+
+impl ProcessModule {
+    fn from_association(association: &CIM_ProcessExecutable) -> Option<Self> {
+        Some(ProcessModule {
+            process_id: referenced_key(association.Dependent.as_deref()?, "Handle")?.parse().ok()?,
+            path: referenced_key(association.Antecedent.as_deref()?, "Name")?,
+            base_address: association.BaseAddress,
+        })
+    }
+}
+
+/// Extracts the value of `key` from a WMI object path, e.g. `Name` of
+/// `\\HOST\root\cimv2:CIM_DataFile.Name="C:\\Windows\\System32\\ntdll.dll"`
+fn referenced_key(path: &str, key: &str) -> Option<String> {
+    let (_, value) = path.split_once(&format!("{key}=\""))?;
+
+    Some(value.strip_suffix('"').unwrap_or(value).replace("\\\\", "\\"))
+}
+
+impl Win32_Process {
+    /// Threads of `threads` owned by this process
+    pub fn threads<'a>(&self, threads: &'a Threads) -> Vec<&'a Win32_Thread> {
+        self.ProcessId
+            .map(|process_id| threads.of_process(process_id))
+            .unwrap_or_default()
+    }
+
+    /// Modules of `modules` loaded by this process
+    pub fn modules<'a>(&self, modules: &'a ProcessModules) -> Vec<&'a ProcessModule> {
+        self.ProcessId
+            .map(|process_id| modules.of_process(process_id))
+            .unwrap_or_default()
+    }
+
+    /// User account the process runs as, through the `GetOwner` method
+    pub fn get_owner(&self) -> Result<ProcessOwner, Unavailable> {
+        exec_method(DEFAULT_NAMESPACE, &self.path(), "GetOwner")
+    }
+
+    /// Security identifier of the user account the process runs as, through the `GetOwnerSid`
+    /// method
+    pub fn get_owner_sid(&self) -> Result<ProcessOwnerSid, Unavailable> {
+        exec_method(DEFAULT_NAMESPACE, &self.path(), "GetOwnerSid")
+    }
+
+    /// Object path of the process, from `Handle` or else `ProcessId`
+    fn path(&self) -> String {
+        let handle = self
+            .Handle
+            .clone()
+            .or_else(|| self.ProcessId.map(|process_id| process_id.to_string()))
+            .unwrap_or_default();
+
+        instance_path("Win32_Process", &[("Handle", &handle)])
+    }
+}
+
+/// Output parameters of `Win32_Process.GetOwner`
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[allow(non_snake_case)]
+pub struct ProcessOwner {
+    /// 0 on success, 2 when access is denied, 3 for insufficient privilege, 8 for an unknown
+    /// failure, 9 when the path is not found and 21 for an invalid parameter.
+    pub ReturnValue: Option<u32>,
+    /// Domain name of the user account the process runs as.
+    pub Domain: Option<String>,
+    /// Name of the user account the process runs as.
+    pub User: Option<String>,
+}
+
+/// Output parameters of `Win32_Process.GetOwnerSid`
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[allow(non_snake_case)]
+pub struct ProcessOwnerSid {
+    /// 0 on success, 2 when access is denied, 3 for insufficient privilege, 8 for an unknown
+    /// failure, 9 when the path is not found and 21 for an invalid parameter.
+    pub ReturnValue: Option<u32>,
+    /// Security identifier (SID) of the user account the process runs as, e.g. `S-1-5-18`.
+    pub Sid: Option<String>,
+}
+
+impl Win32_Thread {
+    /// `ProcessId` of the owning process, parsed from `ProcessHandle`
+    pub fn process_id(&self) -> Option<u32> {
+        self.ProcessHandle.as_deref()?.parse().ok()
+    }
+
+    /// Owning process of this thread in `processes`
+    pub fn process<'a>(&self, processes: &'a Processes) -> Option<&'a Win32_Process> {
+        processes.get_by_key(&self.process_id()?)
+    }
+}
+
 /// The `Win32_Process` WMI class represents a process on an operating system.
 ///
 /// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-process>
@@ -234,6 +988,8 @@ pub struct Win32_Process {
     pub WriteTransferCount: Option<u64>,
 }
 
+referenced!(Win32_Process { Handle });
+
 /// The `Win32_Thread` WMI class represents a thread of execution. While a process must have one
 /// thread of execution, the process can create other threads to execute tasks in parallel. Threads
 /// share the process environment, thus multiple threads under the same process use less memory than
@@ -371,3 +1127,27 @@ pub struct Win32_Thread {
     /// of 0 (zero) should be used.
     pub UserModeTime: Option<u64>,
 }
+
+/// The `CIM_ProcessExecutable` WMI association class relates a process and the executable files,
+/// the program and the DLLs, it has loaded.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/cim-processexecutable>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct CIM_ProcessExecutable {
+    /// Object path of the `CIM_DataFile` of the module.
+    pub Antecedent: Option<String>,
+    /// Object path of the `Win32_Process` that loaded the module.
+    pub Dependent: Option<String>,
+    /// Base address of the module in the address space of the process.
+    pub BaseAddress: Option<u64>,
+    /// Number of processes that have loaded the module.
+    pub GlobalProcessCount: Option<u32>,
+    /// Handle of the module in the process, the same value as `BaseAddress` on 32-bit systems.
+    pub ModuleInstance: Option<u32>,
+    /// Number of times the process loaded the module.
+    pub ProcessCount: Option<u32>,
+}
+
+associates!(CIM_ProcessExecutable { Antecedent => Dependent });
@@ -1,12 +1,30 @@
 //! The class in the Registry subcategory represents the contents of the Windows registry.
 //!
+//! The registry contents themselves are captured by [`RegistryKeys`], which walks a configurable
+//! set of roots (persistence locations such as the `Run` keys and uninstall entries by default,
+//! services on request through [`RegistryRoot::services`]). WMI exposes them through the methods
+//! of the `StdRegProv` class, `EnumKey`, `EnumValues` and the `Get...Value` family, which
+//! [`RegistryKeys`] invokes over one [`MethodSession`], so the keys are read as the WMI provider
+//! sees them.
+//!
+//! Other members read a handful of known values on every update; [`read_value`], [`subkeys`] and
+//! [`values`] serve them through the Win32 registry API, without a WMI round trip per value.
+//!
 //! | Class                                     | Description                                                                                               |
 //! |-------------------------------------------|-----------------------------------------------------------------------------------------------------------|
 //! | [**Win32\_Registry**](Win32_Registry) | Instance class<br/> Represents the system registry on a computer system running Windows.<br/> |
 
-use crate::update;
+use crate::method::{MethodArg, MethodSession};
+use crate::{state_member, update, Unavailable, DEFAULT_NAMESPACE};
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
+use windows::core::{HSTRING, PWSTR};
+use windows::Win32::Foundation::ERROR_SUCCESS;
+use windows::Win32::System::Registry::{
+    RegCloseKey, RegEnumKeyExW, RegEnumValueW, RegOpenKeyExW, RegQueryInfoKeyW, RegQueryValueExW,
+    HKEY, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, HKEY_USERS, KEY_READ, KEY_WOW64_64KEY,
+    REG_BINARY, REG_DWORD, REG_EXPAND_SZ, REG_MULTI_SZ, REG_QWORD, REG_SZ, REG_VALUE_TYPE,
+};
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
 
 /// Represents the state of Windows Registry
@@ -21,10 +39,377 @@ pub struct Registry {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Registry, registries);
 
+/// Represents the contents of selected Windows registry keys
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct RegistryKeys {
+    /// Roots that are walked on every update
+    pub roots: Vec<RegistryRoot>,
+    /// Sequence of keys found under `roots`, parents before their subkeys
+    pub registry_keys: Vec<RegistryKey>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the first root that could not be read failed, `None` when every root was read. Keys
+    /// that do not exist are not a failure.
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(RegistryKeys, registry_keys);
+
+impl RegistryKeys {
+    /// Creates an empty state that walks `roots` instead of [`RegistryRoot::persistence`]
+    pub fn with_roots(roots: Vec<RegistryRoot>) -> Self {
+        RegistryKeys {
+            roots,
+            ..Default::default()
+        }
+    }
+
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let (registry_keys, unavailable) = walk_roots(&self.roots);
+
+        self.refresh(registry_keys, unavailable);
+    }
+
+    /// Update fields asynchronously
+    ///
+    /// WMI methods have no asynchronous form returning futures, so the walk runs on a blocking
+    /// thread of the runtime
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let roots = self.roots.clone();
+        let (registry_keys, unavailable) = tokio::task::spawn_blocking(move || {
+            let _com_con = COMLibrary::without_security();
+            walk_roots(&roots)
+        })
+        .await
+        .unwrap_or_else(|e| {
+            let unavailable = Unavailable {
+                namespace: DEFAULT_NAMESPACE.to_string(),
+                class: STD_REG_PROV.to_string(),
+                reason: crate::UnavailableReason::Other,
+                message: e.to_string(),
+            };
+
+            (Vec::new(), Some(unavailable))
+        });
+
+        self.refresh(registry_keys, unavailable);
+    }
+
+    fn refresh(&mut self, registry_keys: Vec<RegistryKey>, unavailable: Option<Unavailable>) {
+        self.state_change = registry_keys.len() != self.registry_keys.len()
+            || crate::hash_vec(&registry_keys) != crate::hash_vec(&self.registry_keys)
+            || unavailable != self.unavailable;
+
+        self.registry_keys = registry_keys;
+        self.unavailable = unavailable;
+    }
+}
+
+impl Default for RegistryKeys {
+    /// `roots` defaults to [`RegistryRoot::persistence`] and `last_updated` to the current
+    /// `SystemTime`
+    fn default() -> Self {
+        RegistryKeys {
+            roots: RegistryRoot::persistence(),
+            registry_keys: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// A registry key to walk, with how deep to descend into its subkeys
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct RegistryRoot {
+    /// Hive the key lives in
+    pub hive: Hive,
+    /// Path of the key relative to the hive
+    pub path: String,
+    /// Levels of subkeys to capture, 0 captures only the values of the key itself
+    pub depth: u32,
+}
+
+impl RegistryRoot {
+    /// Creates a root for `path` under `hive`
+    pub fn new(hive: Hive, path: &str, depth: u32) -> Self {
+        RegistryRoot {
+            hive,
+            path: path.to_string(),
+            depth,
+        }
+    }
+
+    /// Well known autostart and persistence locations
+    ///
+    /// The services are left out, their keys are numerous and change with every driver update;
+    /// add [`RegistryRoot::services`] to capture them too.
+    pub fn persistence() -> Vec<Self> {
+        const RUN: &str = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Run";
+        const RUN_ONCE: &str = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\RunOnce";
+        const RUN_WOW64: &str = "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Run";
+        const POLICIES_RUN: &str = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Policies\\Explorer\\Run";
+        const WINLOGON: &str = "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Winlogon";
+
+        vec![
+            RegistryRoot::new(Hive::LocalMachine, RUN, 0),
+            RegistryRoot::new(Hive::LocalMachine, RUN_ONCE, 0),
+            RegistryRoot::new(Hive::LocalMachine, RUN_WOW64, 0),
+            RegistryRoot::new(Hive::LocalMachine, POLICIES_RUN, 0),
+            RegistryRoot::new(Hive::CurrentUser, RUN, 0),
+            RegistryRoot::new(Hive::CurrentUser, RUN_ONCE, 0),
+            RegistryRoot::new(Hive::CurrentUser, POLICIES_RUN, 0),
+            RegistryRoot::new(Hive::LocalMachine, WINLOGON, 0),
+            RegistryRoot::new(Hive::LocalMachine, UNINSTALL, 1),
+            RegistryRoot::new(Hive::LocalMachine, UNINSTALL_WOW64, 1),
+            RegistryRoot::new(Hive::CurrentUser, UNINSTALL, 1),
+        ]
+    }
+
+    /// The configuration key of every service and driver, with its values
+    pub fn services() -> Self {
+        RegistryRoot::new(Hive::LocalMachine, SERVICES, 1)
+    }
+}
+
+/// Path of the configuration keys of services and drivers
+pub const SERVICES: &str = "SYSTEM\\CurrentControlSet\\Services";
+
+/// Path of the uninstall entries of installed software
+pub const UNINSTALL: &str = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall";
+
+/// Path of the uninstall entries of 32-bit software on 64-bit Windows
+pub const UNINSTALL_WOW64: &str = "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall";
+
+/// A registry key and its values
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct RegistryKey {
+    /// Hive the key lives in
+    pub hive: Hive,
+    /// Path of the key relative to the hive
+    pub path: String,
+    /// Values of the key, the default value has an empty name
+    pub values: Vec<RegistryEntry>,
+}
+
+impl RegistryKey {
+    /// Returns the data of the value `name`, if present
+    pub fn value(&self, name: &str) -> Option<&RegistryValue> {
+        self.values
+            .iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+            .map(|entry| &entry.value)
+    }
+}
+
+/// A named registry value
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct RegistryEntry {
+    /// Name of the value
+    pub name: String,
+    /// Data of the value
+    pub value: RegistryValue,
+}
+
+/// The keys under `roots`, with why the first root that could not be read failed
+fn walk_roots(roots: &[RegistryRoot]) -> (Vec<RegistryKey>, Option<Unavailable>) {
+    let mut session = MethodSession::new(DEFAULT_NAMESPACE);
+    let mut keys = Vec::new();
+    let mut unavailable = None;
+
+    for root in roots {
+        if let Err(e) = walk(&mut session, root.hive, &root.path, root.depth, &mut keys) {
+            unavailable.get_or_insert(e);
+        }
+    }
+
+    (keys, unavailable)
+}
+
+/// Pushes the key `path` and, up to `depth` levels, its subkeys onto `keys`, read through
+/// `StdRegProv`
+fn walk(
+    session: &mut MethodSession,
+    hive: Hive,
+    path: &str,
+    depth: u32,
+    keys: &mut Vec<RegistryKey>,
+) -> Result<(), Unavailable> {
+    let Some(subkeys) = provider_subkeys(session, hive, path)? else {
+        return Ok(());
+    };
+
+    keys.push(RegistryKey {
+        hive,
+        path: path.to_string(),
+        values: provider_values(session, hive, path)?
+            .into_iter()
+            .map(|(name, value)| RegistryEntry { name, value })
+            .collect(),
+    });
+
+    if depth > 0 {
+        for subkey in subkeys {
+            walk(session, hive, &format!("{path}\\{subkey}"), depth - 1, keys)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Class whose methods read the registry through WMI
+const STD_REG_PROV: &str = "StdRegProv";
+
+/// Output parameters of `StdRegProv.EnumKey`
+#[derive(Deserialize, Debug)]
+#[allow(non_snake_case)]
+struct EnumKeyOutput {
+    ReturnValue: u32,
+    sNames: Option<Vec<String>>,
+}
+
+/// Output parameters of `StdRegProv.EnumValues`
+#[derive(Deserialize, Debug)]
+#[allow(non_snake_case)]
+struct EnumValuesOutput {
+    ReturnValue: u32,
+    sNames: Option<Vec<String>>,
+    Types: Option<Vec<i32>>,
+}
+
+/// Output parameters of `StdRegProv.GetStringValue` and `GetMultiStringValue`, `T` being a string or a sequence of strings
+#[derive(Deserialize, Debug)]
+#[allow(non_snake_case)]
+struct StringValueOutput<T> {
+    ReturnValue: u32,
+    sValue: Option<T>,
+}
+
+/// Output parameters of `StdRegProv.GetDWORDValue`, `GetQWORDValue` and `GetBinaryValue`, `T`
+/// being the number or the bytes
+#[derive(Deserialize, Debug)]
+#[allow(non_snake_case)]
+struct NumericValueOutput<T> {
+    ReturnValue: u32,
+    uValue: Option<T>,
+}
+
+/// Runs the `StdRegProv` method `method` on the key `path` under `hive` over `session`, with the
+/// extra inputs `inputs`
+fn invoke<T: serde::de::DeserializeOwned>(
+    session: &mut MethodSession,
+    method: &str,
+    hive: Hive,
+    path: &str,
+    inputs: &[(&str, MethodArg)],
+) -> Result<T, Unavailable> {
+    let mut arguments = vec![
+        ("hDefKey", MethodArg::U32(hive.def_key())),
+        ("sSubKeyName", MethodArg::from(path)),
+    ];
+    arguments.extend(inputs.iter().cloned());
+
+    session.exec(STD_REG_PROV, method, &arguments)
+}
+
+/// Names of the subkeys of `path` under `hive` through `StdRegProv.EnumKey`, `None` if the key
+/// does not exist or cannot be opened
+fn provider_subkeys(session: &mut MethodSession, hive: Hive, path: &str) -> Result<Option<Vec<String>>, Unavailable> {
+    let output: EnumKeyOutput = invoke(session, "EnumKey", hive, path, &[])?;
+
+    Ok((output.ReturnValue == 0).then(|| output.sNames.unwrap_or_default()))
+}
+
+/// Values of `path` under `hive` through `StdRegProv.EnumValues` and the getter of each type
+///
+/// `REG_EXPAND_SZ` values are read with `GetStringValue`, which returns them as stored, rather than
+/// `GetExpandedStringValue`, which would expand them in the environment of the WMI provider.
+/// Values of other types than the ones [`RegistryValue`] names are left out, `StdRegProv` has no
+/// getter for their raw data.
+fn provider_values(session: &mut MethodSession, hive: Hive, path: &str) -> Result<Vec<(String, RegistryValue)>, Unavailable> {
+    let output: EnumValuesOutput = invoke(session, "EnumValues", hive, path, &[])?;
+
+    if output.ReturnValue != 0 {
+        return Ok(Vec::new());
+    }
+
+    // keys holding only their default value report no names
+    let names = output.sNames.unwrap_or_default();
+    let types = output.Types.unwrap_or_default();
+    let mut values = Vec::new();
+
+    for (name, value_type) in names.into_iter().zip(types) {
+        if let Some(value) = provider_value(session, hive, path, &name, value_type)? {
+            values.push((name, value));
+        }
+    }
+
+    Ok(values)
+}
+
+/// The value `name` of `path` under `hive`, of the `REG_*` type `value_type`, through the
+/// `StdRegProv` getter of that type, `None` if it vanished or its type has no getter
+fn provider_value(
+    session: &mut MethodSession,
+    hive: Hive,
+    path: &str,
+    name: &str,
+    value_type: i32,
+) -> Result<Option<RegistryValue>, Unavailable> {
+    let inputs = [("sValueName", MethodArg::from(name))];
+
+    // a non-zero `ReturnValue` is the Win32 error, e.g. 2 when the value vanished meanwhile
+    fn found<T>(return_value: u32, value: Option<T>) -> Option<T> {
+        (return_value == 0).then_some(value).flatten()
+    }
+
+    let value = match REG_VALUE_TYPE(value_type as u32) {
+        REG_SZ => {
+            let output: StringValueOutput<String> = invoke(session, "GetStringValue", hive, path, &inputs)?;
+            found(output.ReturnValue, output.sValue).map(RegistryValue::String)
+        }
+        REG_EXPAND_SZ => {
+            let output: StringValueOutput<String> = invoke(session, "GetStringValue", hive, path, &inputs)?;
+            found(output.ReturnValue, output.sValue).map(RegistryValue::ExpandString)
+        }
+        REG_MULTI_SZ => {
+            let output: StringValueOutput<Vec<String>> = invoke(session, "GetMultiStringValue", hive, path, &inputs)?;
+            found(output.ReturnValue, output.sValue).map(RegistryValue::MultiString)
+        }
+        REG_DWORD => {
+            let output: NumericValueOutput<u32> = invoke(session, "GetDWORDValue", hive, path, &inputs)?;
+            found(output.ReturnValue, output.uValue).map(RegistryValue::Dword)
+        }
+        REG_QWORD => {
+            let output: NumericValueOutput<u64> = invoke(session, "GetQWORDValue", hive, path, &inputs)?;
+            found(output.ReturnValue, output.uValue).map(RegistryValue::Qword)
+        }
+        REG_BINARY => {
+            let output: NumericValueOutput<Vec<u8>> = invoke(session, "GetBinaryValue", hive, path, &inputs)?;
+            found(output.ReturnValue, output.uValue).map(RegistryValue::Binary)
+        }
+        _ => None,
+    };
+
+    Ok(value)
+}
+
 /// The `Win32_Registry` WMI class represents a process on an operating system.
 ///
 /// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-registry>
@@ -77,3 +462,267 @@ pub struct Win32_Registry {
     /// and its proposal is attempted the next time the system boots.
     pub ProposedSize: Option<u32>,
 }
+
+/// Root keys the registry can be read from
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Hive {
+    /// `HKEY_LOCAL_MACHINE`
+    LocalMachine,
+    /// `HKEY_CURRENT_USER`, the user the snapshot runs as
+    CurrentUser,
+    /// `HKEY_USERS`, the loaded profiles of every user
+    Users,
+}
+
+impl Hive {
+    /// Handle of the hive as `StdRegProv` takes it in `hDefKey`
+    fn def_key(self) -> u32 {
+        match self {
+            Hive::LocalMachine => 0x8000_0002,
+            Hive::CurrentUser => 0x8000_0001,
+            Hive::Users => 0x8000_0003,
+        }
+    }
+
+    fn hkey(self) -> HKEY {
+        match self {
+            Hive::LocalMachine => HKEY_LOCAL_MACHINE,
+            Hive::CurrentUser => HKEY_CURRENT_USER,
+            Hive::Users => HKEY_USERS,
+        }
+    }
+}
+
+/// Data held by a registry value
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum RegistryValue {
+    /// `REG_SZ`
+    String(String),
+    /// `REG_EXPAND_SZ`, environment variables are left unexpanded
+    ExpandString(String),
+    /// `REG_MULTI_SZ`
+    MultiString(Vec<String>),
+    /// `REG_DWORD`
+    Dword(u32),
+    /// `REG_QWORD`
+    Qword(u64),
+    /// `REG_BINARY`
+    Binary(Vec<u8>),
+    /// Any other type, holding the type code and the raw data
+    Other(u32, Vec<u8>),
+}
+
+impl RegistryValue {
+    /// Returns the value as a string if it is a `REG_SZ` or `REG_EXPAND_SZ`
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            RegistryValue::String(value) | RegistryValue::ExpandString(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a number if it is a `REG_DWORD` or `REG_QWORD`
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            RegistryValue::Dword(value) => Some(u64::from(*value)),
+            RegistryValue::Qword(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn from_raw(value_type: REG_VALUE_TYPE, data: &[u8]) -> Self {
+        match value_type {
+            REG_SZ => RegistryValue::String(utf16_string(data)),
+            REG_EXPAND_SZ => RegistryValue::ExpandString(utf16_string(data)),
+            REG_MULTI_SZ => RegistryValue::MultiString(
+                utf16_string(data)
+                    .split('\0')
+                    .filter(|part| !part.is_empty())
+                    .map(String::from)
+                    .collect(),
+            ),
+            REG_DWORD if data.len() >= 4 => {
+                RegistryValue::Dword(u32::from_le_bytes([data[0], data[1], data[2], data[3]]))
+            }
+            REG_QWORD if data.len() >= 8 => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&data[..8]);
+                RegistryValue::Qword(u64::from_le_bytes(bytes))
+            }
+            REG_BINARY => RegistryValue::Binary(data.to_vec()),
+            other => RegistryValue::Other(other.0, data.to_vec()),
+        }
+    }
+}
+
+/// Decodes UTF-16LE registry data, dropping the trailing terminators
+fn utf16_string(data: &[u8]) -> String {
+    let wide = data
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect::<Vec<_>>();
+
+    String::from_utf16_lossy(&wide).trim_end_matches('\0').to_string()
+}
+
+/// An open registry key, closed on drop
+struct Key(HKEY);
+
+impl Key {
+    /// Opens `path` under `hive` for reading, always using the 64-bit view
+    fn open(hive: Hive, path: &str) -> Option<Self> {
+        let mut hkey = HKEY::default();
+
+        let result = unsafe {
+            RegOpenKeyExW(hive.hkey(), &HSTRING::from(path), 0, KEY_READ | KEY_WOW64_64KEY, &mut hkey)
+        };
+
+        (result == ERROR_SUCCESS).then_some(Key(hkey))
+    }
+
+    /// Returns the number of subkeys and values, and the longest subkey name, value name and
+    /// value data, in characters and bytes respectively
+    fn info(&self) -> Option<(u32, u32, u32, u32, u32)> {
+        let (mut subkeys, mut max_subkey_len) = (0u32, 0u32);
+        let (mut values, mut max_value_name_len, mut max_value_len) = (0u32, 0u32, 0u32);
+
+        let result = unsafe {
+            RegQueryInfoKeyW(
+                self.0,
+                PWSTR::null(),
+                None,
+                None,
+                Some(&mut subkeys),
+                Some(&mut max_subkey_len),
+                None,
+                Some(&mut values),
+                Some(&mut max_value_name_len),
+                Some(&mut max_value_len),
+                None,
+                None,
+            )
+        };
+
+        (result == ERROR_SUCCESS).then_some((
+            subkeys,
+            max_subkey_len,
+            values,
+            max_value_name_len,
+            max_value_len,
+        ))
+    }
+}
+
+impl Drop for Key {
+    fn drop(&mut self) {
+        unsafe { RegCloseKey(self.0) };
+    }
+}
+
+/// Reads the value `name` of the key `path` under `hive`, an empty `name` reads the default value
+pub fn read_value(hive: Hive, path: &str, name: &str) -> Option<RegistryValue> {
+    let key = Key::open(hive, path)?;
+    let name = HSTRING::from(name);
+
+    let mut value_type = REG_VALUE_TYPE::default();
+    let mut size = 0u32;
+
+    let result = unsafe { RegQueryValueExW(key.0, &name, None, Some(&mut value_type), None, Some(&mut size)) };
+    if result != ERROR_SUCCESS {
+        return None;
+    }
+
+    let mut data = vec![0u8; size as usize];
+
+    let result = unsafe {
+        RegQueryValueExW(
+            key.0,
+            &name,
+            None,
+            Some(&mut value_type),
+            Some(data.as_mut_ptr()),
+            Some(&mut size),
+        )
+    };
+    if result != ERROR_SUCCESS {
+        return None;
+    }
+
+    data.truncate(size as usize);
+
+    Some(RegistryValue::from_raw(value_type, &data))
+}
+
+/// Lists the names of the subkeys of the key `path` under `hive`
+pub fn subkeys(hive: Hive, path: &str) -> Vec<String> {
+    let Some(key) = Key::open(hive, path) else {
+        return Vec::new();
+    };
+    let Some((count, max_len, ..)) = key.info() else {
+        return Vec::new();
+    };
+
+    let mut buffer = vec![0u16; max_len as usize + 1];
+
+    (0..count)
+        .filter_map(|index| {
+            let mut len = buffer.len() as u32;
+
+            let result = unsafe {
+                RegEnumKeyExW(
+                    key.0,
+                    index,
+                    PWSTR(buffer.as_mut_ptr()),
+                    &mut len,
+                    None,
+                    PWSTR::null(),
+                    None,
+                    None,
+                )
+            };
+
+            (result == ERROR_SUCCESS).then(|| String::from_utf16_lossy(&buffer[..len as usize]))
+        })
+        .collect()
+}
+
+/// Lists the values of the key `path` under `hive` as `(name, data)` pairs
+pub fn values(hive: Hive, path: &str) -> Vec<(String, RegistryValue)> {
+    let Some(key) = Key::open(hive, path) else {
+        return Vec::new();
+    };
+    let Some((_, _, count, max_name_len, max_data_len)) = key.info() else {
+        return Vec::new();
+    };
+
+    let mut name = vec![0u16; max_name_len as usize + 1];
+    let mut data = vec![0u8; max_data_len as usize];
+
+    (0..count)
+        .filter_map(|index| {
+            let mut name_len = name.len() as u32;
+            let mut data_len = data.len() as u32;
+            let mut value_type = 0u32;
+
+            let result = unsafe {
+                RegEnumValueW(
+                    key.0,
+                    index,
+                    PWSTR(name.as_mut_ptr()),
+                    &mut name_len,
+                    None,
+                    Some(&mut value_type),
+                    Some(data.as_mut_ptr()),
+                    Some(&mut data_len),
+                )
+            };
+
+            (result == ERROR_SUCCESS).then(|| {
+                (
+                    String::from_utf16_lossy(&name[..name_len as usize]),
+                    RegistryValue::from_raw(REG_VALUE_TYPE(value_type), &data[..data_len as usize]),
+                )
+            })
+        })
+        .collect()
+}
@@ -3,9 +3,10 @@
 //! | [**Win32\_CurrentTime**](/previous-versions/windows/desktop/wmitimepprov/win32-currenttime) | Abstract class<br/> Represents an instance in time as component seconds, minutes, day of the week, and so on.<br/>                                                                                                                                        |
 //! | [**Win32\_ScheduledJob**](win32-scheduledjob)      | Instance class<br/> Represents a job scheduled using the Windows schedule service.<br/>                                                                                                                                                                   |
 //! | [**Win32\_LocalTime**](/previous-versions/windows/desktop/wmitimepprov/win32-localtime)     | Instance class<br/> Represents a point in time returned as [**Win32\_LocalTime**](/previous-versions/windows/desktop/wmitimepprov/win32-localtime) objects that result from a query. The **Hour** property is returned as the local time in a 24-hour clock.<br/>                                |
+//! | [**MSFT\_ScheduledTask**](/previous-versions/windows/desktop/schedwmi/msft-scheduledtask) | Instance class<br/> Represents a Task Scheduler task, in `root\Microsoft\Windows\TaskScheduler`. Unlike [**Win32\_ScheduledJob**](win32-scheduledjob) it lists the tasks of modern Windows.<br/> |
 //! | [**Win32\_UTCTime**](/previous-versions/windows/desktop/wmitimepprov/win32-utctime)         | Instance class<br/> Represents a point in time that is returned as [**Win32\_UTCTime**](/previous-versions/windows/desktop/wmitimepprov/win32-utctime) objects that result from a query. The **Hour** property is returned as the coordinated universal time (UTC) time in a 24 hour clock.<br/> |
 
-use crate::update;
+use crate::{update, TASK_SCHEDULER_NAMESPACE};
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
@@ -22,10 +23,67 @@ pub struct ScheduledJobs {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(ScheduledJobs, scheduled_jobs);
 
+/// Represents the state of Windows Task Scheduler tasks
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ScheduledTasks {
+    /// Sequence of Task Scheduler tasks
+    pub scheduled_tasks: Vec<MSFT_ScheduledTask>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(ScheduledTasks, scheduled_tasks, TASK_SCHEDULER_NAMESPACE);
+
+impl ScheduledTasks {
+    /// Tasks that are not disabled
+    pub fn enabled(&self) -> Vec<&MSFT_ScheduledTask> {
+        self.scheduled_tasks
+            .iter()
+            .filter(|task| task.State.is_some_and(|state| state != 1))
+            .collect()
+    }
+
+    /// Tasks in the folder `task_path`, e.g. `\`, subfolders excluded
+    pub fn in_folder(&self, task_path: &str) -> Vec<&MSFT_ScheduledTask> {
+        self.scheduled_tasks
+            .iter()
+            .filter(|task| {
+                task.TaskPath
+                    .as_deref()
+                    .is_some_and(|path| path.eq_ignore_ascii_case(task_path))
+            })
+            .collect()
+    }
+
+    /// Tasks outside the `\Microsoft\` folder, where Windows keeps its own tasks
+    pub fn third_party(&self) -> Vec<&MSFT_ScheduledTask> {
+        self.scheduled_tasks
+            .iter()
+            .filter(|task| {
+                !task
+                    .TaskPath
+                    .as_deref()
+                    .unwrap_or_default()
+                    .to_ascii_lowercase()
+                    .starts_with("\\microsoft\\")
+            })
+            .collect()
+    }
+}
+
 /// Represents the state of Windows `LocalTimes`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct LocalTimes {
@@ -38,10 +96,31 @@ pub struct LocalTimes {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(LocalTimes, local_times);
 
+impl LocalTimes {
+    /// Offset of the local time from UTC in effect, daylight saving included, in minutes, e.g. -420
+    /// for Pacific Daylight Time. `None` unless both states hold a time.
+    ///
+    /// The two states are queried at slightly different instants, so the difference is rounded to
+    /// the nearest quarter hour, the granularity of every time zone.
+    pub fn utc_offset_minutes(&self, utc: &UTCTimes) -> Option<i64> {
+        let local = self.local_times.first()?;
+        let utc = utc.utc_times.first()?;
+
+        let local = seconds_since_epoch(local.Year?, local.Month?, local.Day?, local.Hour?, local.Minute?, local.Second?)?;
+        let utc = seconds_since_epoch(utc.Year?, utc.Month?, utc.Day?, utc.Hour?, utc.Minute?, utc.Second?)?;
+
+        let quarters = ((local - utc) as f64 / 900.0).round() as i64;
+
+        Some(quarters * 15)
+    }
+}
+
 /// Represents the state of Windows `UTCTimes`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct UTCTimes {
@@ -54,6 +133,8 @@ pub struct UTCTimes {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(UTCTimes, utc_times);
@@ -267,4 +348,52 @@ pub struct Win32_UTCTime {
     pub WeekInMonth: Option<u32>,
     /// Current year matching the query (4 digits).
     pub Year: Option<u32>,
-}
\ No newline at end of file
+}
+
+/// The `MSFT_ScheduledTask` WMI class represents a task registered with Task Scheduler.
+///
+/// The actions, triggers, principal and settings of a task are embedded objects the WMI provider
+/// does not flatten, they are left out.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/schedwmi/msft-scheduledtask>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct MSFT_ScheduledTask {
+    /// Author of the task.
+    pub Author: Option<String>,
+    /// When the task was registered, as written by its author, usually in ISO 8601 form.
+    pub Date: Option<String>,
+    /// Description of the task.
+    pub Description: Option<String>,
+    /// Additional documentation of the task.
+    pub Documentation: Option<String>,
+    /// Security descriptor of the task, in SDDL form.
+    pub SecurityDescriptor: Option<String>,
+    /// Where the task was registered from, e.g. the application or component that created it.
+    pub Source: Option<String>,
+    /// Current state of the task.
+    ///
+    /// - `Unknown` (0)
+    /// - `Disabled` (1)
+    /// - `Queued` (2)
+    /// - `Ready` (3)
+    /// - `Running` (4)
+    pub State: Option<u32>,
+    /// Name of the task.
+    pub TaskName: Option<String>,
+    /// Folder of the task, e.g. "\Microsoft\Windows\Defrag\".
+    pub TaskPath: Option<String>,
+    /// Full path of the task, folder and name.
+    pub URI: Option<String>,
+    /// Version of the task.
+    pub Version: Option<String>,
+}
+
+fn seconds_since_epoch(year: u32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Option<i64> {
+    let midnight = crate::civil_date(i64::from(year), month, day)?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?;
+
+    Some(midnight.as_secs() as i64 + i64::from(hour * 3_600 + minute * 60 + second))
+}
@@ -0,0 +1,177 @@
+//! Endpoint protection products registered with Windows Security Center. The `root\SecurityCenter2`
+//! namespace only exists on client editions of Windows, on servers these states are `unavailable`.
+//!
+//! | Class                   | Description                                                                  |
+//! |-------------------------|------------------------------------------------------------------------------|
+//! | **AntiVirusProduct**    | Antivirus products, including Microsoft Defender Antivirus.                  |
+//! | **FirewallProduct**     | Third party firewalls, Windows Defender Firewall is not listed.              |
+//! | **AntiSpywareProduct**  | Antispyware products, usually the antivirus products registered a second time. |
+
+use crate::{update, SECURITY_CENTER2_NAMESPACE};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection};
+
+/// Represents the state of Windows antivirus products
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct AntiVirusProducts {
+    /// Sequence of windows antivirus product states
+    pub anti_virus_products: Vec<AntiVirusProduct>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(AntiVirusProducts, anti_virus_products, SECURITY_CENTER2_NAMESPACE);
+
+impl AntiVirusProducts {
+    /// Returns `true` if at least one antivirus product is enabled and up to date
+    pub fn protected(&self) -> bool {
+        self.anti_virus_products
+            .iter()
+            .any(|product| product.status().is_some_and(|status| status.enabled && status.up_to_date))
+    }
+}
+
+/// Represents the state of Windows firewall products
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct FirewallProducts {
+    /// Sequence of windows firewall product states
+    pub firewall_products: Vec<FirewallProduct>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(FirewallProducts, firewall_products, SECURITY_CENTER2_NAMESPACE);
+
+/// Represents the state of Windows antispyware products
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct AntiSpywareProducts {
+    /// Sequence of windows antispyware product states
+    pub anti_spyware_products: Vec<AntiSpywareProduct>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(AntiSpywareProducts, anti_spyware_products, SECURITY_CENTER2_NAMESPACE);
+
+/// Decoded `productState` of a Security Center product
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ProductStatus {
+    /// Whether real time protection is on
+    pub enabled: bool,
+    /// Whether the signatures are up to date, always `true` for firewalls
+    pub up_to_date: bool,
+}
+
+impl ProductStatus {
+    /// Decodes the undocumented `productState` bit field: the second byte holds the scanner state
+    /// (`0x10` on) and the lowest the signature state (`0x10` out of date)
+    pub fn from_product_state(product_state: u32) -> Self {
+        ProductStatus {
+            enabled: product_state & 0x1000 != 0,
+            up_to_date: product_state & 0x10 == 0,
+        }
+    }
+}
+
+macro_rules! product_status {
+    ($class: ident) => {
+        impl $class {
+            /// Decoded `productState`
+            pub fn status(&self) -> Option<ProductStatus> {
+                self.productState.map(ProductStatus::from_product_state)
+            }
+        }
+    };
+}
+
+product_status!(AntiVirusProduct);
+product_status!(FirewallProduct);
+product_status!(AntiSpywareProduct);
+
+/// The `AntiVirusProduct` WMI class represents an antivirus product registered with Windows
+/// Security Center.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/api/iwscapi/ne-iwscapi-wsc_security_product_state>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct AntiVirusProduct {
+    /// Name of the product, e.g. "Windows Defender".
+    pub displayName: Option<String>,
+    /// Unique identifier of the product registration.
+    pub instanceGuid: Option<String>,
+    /// Path of the signed executable of the product.
+    pub pathToSignedProductExe: Option<String>,
+    /// Path of the signed executable reporting the product state.
+    pub pathToSignedReportingExe: Option<String>,
+    /// Bit field of the product state, see [`ProductStatus::from_product_state`].
+    pub productState: Option<u32>,
+    /// When the product last reported its state, in RFC 1123 form.
+    pub timestamp: Option<String>,
+}
+
+/// The `FirewallProduct` WMI class represents a firewall product registered with Windows Security
+/// Center.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/api/iwscapi/ne-iwscapi-wsc_security_product_state>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct FirewallProduct {
+    /// Name of the product.
+    pub displayName: Option<String>,
+    /// Unique identifier of the product registration.
+    pub instanceGuid: Option<String>,
+    /// Path of the signed executable of the product.
+    pub pathToSignedProductExe: Option<String>,
+    /// Path of the signed executable reporting the product state.
+    pub pathToSignedReportingExe: Option<String>,
+    /// Bit field of the product state, see [`ProductStatus::from_product_state`].
+    pub productState: Option<u32>,
+    /// When the product last reported its state, in RFC 1123 form.
+    pub timestamp: Option<String>,
+}
+
+/// The `AntiSpywareProduct` WMI class represents an antispyware product registered with Windows
+/// Security Center.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/api/iwscapi/ne-iwscapi-wsc_security_product_state>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct AntiSpywareProduct {
+    /// Name of the product, e.g. "Windows Defender".
+    pub displayName: Option<String>,
+    /// Unique identifier of the product registration.
+    pub instanceGuid: Option<String>,
+    /// Path of the signed executable of the product.
+    pub pathToSignedProductExe: Option<String>,
+    /// Path of the signed executable reporting the product state.
+    pub pathToSignedReportingExe: Option<String>,
+    /// Bit field of the product state, see [`ProductStatus::from_product_state`].
+    pub productState: Option<u32>,
+    /// When the product last reported its state, in RFC 1123 form.
+    pub timestamp: Option<String>,
+}
@@ -0,0 +1,303 @@
+//! Local security policy essentials: the system audit policy and the UAC and LSA settings.
+//!
+//! | Source                                                                                                                     | Description                                                                                       |
+//! |----------------------------------------------------------------------------------------------------------------------------|---------------------------------------------------------------------------------------------------|
+//! | [**AuditQuerySystemPolicy**](https://learn.microsoft.com/en-us/windows/win32/api/ntsecapi/nf-ntsecapi-auditquerysystempolicy) | Whether success and failure events are audited, per subcategory, as `auditpol /get` shows them.  |
+//! | `HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Policies\System`                                                            | User Account Control settings.                                                                    |
+//! | `HKLM\SYSTEM\CurrentControlSet\Control\Lsa`                                                                                 | LSA settings: NTLM levels, anonymous access restrictions, LSA protection and Credential Guard.   |
+//! | `HKLM\SYSTEM\CurrentControlSet\Control\SecurityProviders\WDigest`                                                           | Whether WDigest keeps clear text credentials in memory.                                          |
+//!
+//! These settings change rarely and weaken the machine when they do, they show up as
+//! [`DriftCategory::AuditPolicy`](crate::drift::DriftCategory::AuditPolicy) and
+//! [`DriftCategory::SecurityPolicyValue`](crate::drift::DriftCategory::SecurityPolicyValue)
+//! deviations.
+//!
+//! Querying the audit policy requires `SeSecurityPrivilege`, held by administrators: other callers
+//! get an empty `audit_policies` and an [`Unavailable`] while the registry values are still read.
+
+use crate::operating_system::registry::{self, Hive, RegistryValue};
+use crate::{state_member, Unavailable};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use windows::core::{GUID, PWSTR};
+use windows::Win32::Foundation::BOOLEAN;
+use windows::Win32::Security::Authentication::Identity::{
+    AuditEnumerateCategories, AuditEnumerateSubCategories, AuditFree, AuditLookupCategoryNameW,
+    AuditLookupSubCategoryNameW, AuditQuerySystemPolicy, AUDIT_POLICY_INFORMATION,
+    POLICY_AUDIT_EVENT_FAILURE, POLICY_AUDIT_EVENT_SUCCESS,
+};
+
+/// Key of the User Account Control settings
+pub const UAC_POLICY: &str = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Policies\\System";
+
+/// Key of the LSA settings
+pub const LSA: &str = "SYSTEM\\CurrentControlSet\\Control\\Lsa";
+
+/// Key of the WDigest authentication package settings
+pub const WDIGEST: &str = "SYSTEM\\CurrentControlSet\\Control\\SecurityProviders\\WDigest";
+
+/// Registry values captured, as `(key, name)` pairs under `HKEY_LOCAL_MACHINE`
+///
+/// - `EnableLUA`: 0 turns UAC off, every administrator runs elevated
+/// - `ConsentPromptBehaviorAdmin`: 0 elevates administrators without prompting, up to 5 (the default)
+///   prompting for consent on non-Windows binaries
+/// - `ConsentPromptBehaviorUser`: 0 denies elevation to standard users, 3 prompts for credentials
+/// - `PromptOnSecureDesktop`: 0 shows the elevation prompt on the interactive desktop
+/// - `FilterAdministratorToken`: 1 runs the built-in Administrator in Admin Approval Mode
+/// - `LocalAccountTokenFilterPolicy`: 1 gives local administrators full rights over the network
+/// - `LmCompatibilityLevel`: 0 (send LM and NTLM) to 5 (send NTLMv2 only, refuse LM and NTLM)
+/// - `NoLMHash`: 1 stops storing LAN Manager hashes
+/// - `RestrictAnonymous`, `RestrictAnonymousSAM`, `EveryoneIncludesAnonymous`: anonymous enumeration
+///   of accounts and shares
+/// - `LimitBlankPasswordUse`: 1 restricts accounts with a blank password to console logons
+/// - `RunAsPPL`: 1 or 2 runs LSA as a protected process
+/// - `LsaCfgFlags`: 1 or 2 enables Credential Guard
+/// - `CrashOnAuditFail`: 1 halts the system when security events cannot be logged
+/// - `SCENoApplyLegacyAuditPolicy`: 1 applies the subcategory audit policy over the legacy one
+/// - `UseLogonCredential`: 1 makes WDigest keep clear text credentials in memory
+pub const POLICY_VALUES: &[(&str, &str)] = &[
+    (UAC_POLICY, "EnableLUA"),
+    (UAC_POLICY, "ConsentPromptBehaviorAdmin"),
+    (UAC_POLICY, "ConsentPromptBehaviorUser"),
+    (UAC_POLICY, "PromptOnSecureDesktop"),
+    (UAC_POLICY, "FilterAdministratorToken"),
+    (UAC_POLICY, "LocalAccountTokenFilterPolicy"),
+    (LSA, "LmCompatibilityLevel"),
+    (LSA, "NoLMHash"),
+    (LSA, "RestrictAnonymous"),
+    (LSA, "RestrictAnonymousSAM"),
+    (LSA, "EveryoneIncludesAnonymous"),
+    (LSA, "LimitBlankPasswordUse"),
+    (LSA, "RunAsPPL"),
+    (LSA, "LsaCfgFlags"),
+    (LSA, "CrashOnAuditFail"),
+    (LSA, "SCENoApplyLegacyAuditPolicy"),
+    (WDIGEST, "UseLogonCredential"),
+];
+
+/// Represents the state of the local security policy
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct SecuritySettings {
+    /// Sequence of audit policy subcategories, in the order `auditpol` lists them
+    pub audit_policies: Vec<AuditPolicy>,
+    /// Sequence of the UAC and LSA settings of [`POLICY_VALUES`], unset values included
+    pub policy_values: Vec<PolicyValue>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the audit policy could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(SecuritySettings, audit_policies; policy_values);
+
+impl SecuritySettings {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let (audit_policies, policy_values) = read_settings();
+
+        self.refresh(audit_policies, policy_values);
+    }
+
+    /// Update fields asynchronously
+    ///
+    /// The audit policy and the registry have no asynchronous reads, so they run on a blocking
+    /// thread of the runtime
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let (audit_policies, policy_values) = crate::blocking("AuditQuerySystemPolicy", read_settings)
+            .await
+            .unwrap_or_else(|unavailable| (Err(unavailable), Vec::new()));
+
+        self.refresh(audit_policies, policy_values);
+    }
+
+    fn refresh(&mut self, audit_policies: Result<Vec<AuditPolicy>, Unavailable>, policy_values: Vec<PolicyValue>) {
+        let old_audit = std::mem::take(&mut self.audit_policies);
+        let old_values = std::mem::replace(&mut self.policy_values, policy_values);
+        let old_unavailable = self.unavailable.take();
+
+        match audit_policies {
+            Ok(policies) => self.audit_policies = policies,
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.audit_policies.len() != old_audit.len()
+            || crate::hash_vec(&self.audit_policies) != crate::hash_vec(&old_audit)
+            || self.policy_values != old_values;
+    }
+
+    /// Value of the setting `name` of [`POLICY_VALUES`], `None` when it is not set
+    pub fn value(&self, name: &str) -> Option<u64> {
+        self.policy_values
+            .iter()
+            .find(|value| value.name == name)
+            .and_then(|value| value.value)
+    }
+
+    /// Returns `true` unless UAC is turned off, it is on when `EnableLUA` is not set
+    pub fn uac_enabled(&self) -> bool {
+        self.value("EnableLUA") != Some(0)
+    }
+
+    /// Returns `true` if LSA runs as a protected process
+    pub fn lsa_protected(&self) -> bool {
+        matches!(self.value("RunAsPPL"), Some(1 | 2))
+    }
+
+    /// Subcategories audited neither on success nor on failure
+    pub fn unaudited(&self) -> impl Iterator<Item = &AuditPolicy> {
+        self.audit_policies
+            .iter()
+            .filter(|policy| !policy.success && !policy.failure)
+    }
+}
+
+/// The audit policy, or why it could not be queried, and the [`POLICY_VALUES`]
+fn read_settings() -> (Result<Vec<AuditPolicy>, Unavailable>, Vec<PolicyValue>) {
+    let audit_policies = AuditPolicy::query().map_err(|e| Unavailable::from_com("AuditQuerySystemPolicy", &e));
+
+    (audit_policies, PolicyValue::read_all().collect())
+}
+
+impl Default for SecuritySettings {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        SecuritySettings {
+            audit_policies: Default::default(),
+            policy_values: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// Audit setting of a subcategory of the system audit policy
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct AuditPolicy {
+    /// Localized name of the category, e.g. `Logon/Logoff`.
+    pub category: String,
+    /// Localized name of the subcategory, e.g. `Logon`.
+    pub subcategory: String,
+    /// GUID of the subcategory, the same in every language, e.g.
+    /// `0CCE9215-69AE-11D9-BED3-505054503030` for `Logon`.
+    pub subcategory_guid: String,
+    /// Whether successful attempts are audited.
+    pub success: bool,
+    /// Whether failed attempts are audited.
+    pub failure: bool,
+}
+
+impl AuditPolicy {
+    /// Queries the audit setting of every subcategory
+    fn query() -> windows::core::Result<Vec<Self>> {
+        let mut policies = Vec::new();
+
+        unsafe {
+            let mut categories = std::ptr::null_mut::<GUID>();
+            let mut category_count = 0;
+            AuditEnumerateCategories(&mut categories, &mut category_count).ok()?;
+            let categories = AuditBuffer(categories);
+
+            for category in categories.slice(category_count) {
+                let category_name = audit_name(AuditLookupCategoryNameW, category);
+
+                let mut subcategories = std::ptr::null_mut::<GUID>();
+                let mut subcategory_count = 0;
+                AuditEnumerateSubCategories(
+                    Some(category),
+                    BOOLEAN::from(false),
+                    &mut subcategories,
+                    &mut subcategory_count,
+                )
+                .ok()?;
+                let subcategories = AuditBuffer(subcategories);
+
+                let mut information = std::ptr::null_mut::<AUDIT_POLICY_INFORMATION>();
+                AuditQuerySystemPolicy(subcategories.slice(subcategory_count), &mut information).ok()?;
+                let information = AuditBuffer(information);
+
+                policies.extend(information.slice(subcategory_count).iter().map(|information| {
+                    AuditPolicy {
+                        category: category_name.clone(),
+                        subcategory: audit_name(
+                            AuditLookupSubCategoryNameW,
+                            &information.AuditSubCategoryGuid,
+                        ),
+                        subcategory_guid: format!("{:?}", information.AuditSubCategoryGuid),
+                        success: information.AuditingInformation & POLICY_AUDIT_EVENT_SUCCESS as u32 != 0,
+                        failure: information.AuditingInformation & POLICY_AUDIT_EVENT_FAILURE as u32 != 0,
+                    }
+                }));
+            }
+        }
+
+        Ok(policies)
+    }
+}
+
+/// A registry value of the local security policy
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PolicyValue {
+    /// Key holding the value, under `HKEY_LOCAL_MACHINE`.
+    pub key: String,
+    /// Name of the value, e.g. `EnableLUA`.
+    pub name: String,
+    /// Data of the value, `None` when it is not set and Windows applies its default.
+    pub value: Option<u64>,
+}
+
+impl PolicyValue {
+    /// Reads every value of [`POLICY_VALUES`]
+    fn read_all() -> impl Iterator<Item = Self> {
+        POLICY_VALUES.iter().map(|(key, name)| PolicyValue {
+            key: key.to_string(),
+            name: name.to_string(),
+            value: registry::read_value(Hive::LocalMachine, key, name)
+                .as_ref()
+                .and_then(RegistryValue::as_u64),
+        })
+    }
+}
+
+/// Buffer allocated by the audit functions, released with `AuditFree`
+struct AuditBuffer<T>(*mut T);
+
+impl<T> AuditBuffer<T> {
+    /// The first `count` elements of the buffer
+    unsafe fn slice(&self, count: u32) -> &[T] {
+        if self.0.is_null() {
+            return &[];
+        }
+
+        std::slice::from_raw_parts(self.0, count as usize)
+    }
+}
+
+impl<T> Drop for AuditBuffer<T> {
+    fn drop(&mut self) {
+        unsafe { AuditFree(self.0 as *const _) };
+    }
+}
+
+/// Localized name `lookup` gives to `guid`, empty when it has none
+unsafe fn audit_name(lookup: unsafe fn(*const GUID, *mut PWSTR) -> BOOLEAN, guid: &GUID) -> String {
+    let mut name = PWSTR::null();
+
+    if !lookup(guid, &mut name).as_bool() || name.is_null() {
+        return String::new();
+    }
+
+    let name = AuditBuffer(name.0);
+
+    String::from_utf16_lossy(PWSTR(name.0).as_wide())
+}
@@ -3,14 +3,31 @@
 //! | Class                                           | Description                                                                                                                                             |
 //! |-------------------------------------------------|---------------------------------------------------------------------------------------------------------------------------------------------------------|
 //! | [**`Win32\_Servic`e**](win32-service)         | Instance class<br/> Represents a service on a computer system running Windows.<br/>                                                         |
+//! | [**Win32\_DependentService**](win32-dependentservice) | Association class<br/> Relates two interdependent base services.<br/>                                                                |
+//!
+//! [`ServiceMonitor`] pushes service changes as WMI reports them, [`Services::apply`] keeps the state
+//! current from them.
+//!
+//! [`Services::restart_storms`] flags services changing state more often than a threshold, from the
+//! transitions recorded across updates and applied changes.
+//!
+//! [`Services::binaries`] derives the executable of every service from its `PathName`, flags
+//! unquoted paths with spaces and can verify the Authenticode signature of the executables.
 
-use crate::update;
+use crate::operating_system::drivers::{self, SignatureStatus};
+use crate::report::Summary;
+use crate::{associates, keyed, labeled, referenced, update, Unavailable};
 use serde::{Deserialize, Serialize};
-use std::time::SystemTime;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
 
 /// Represents the state of Windows Drivers
-#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Services {
     /// Sequence of Drivers based on when they were loaded in chronological order
     pub services: Vec<Win32_Service>,
@@ -21,9 +38,574 @@ pub struct Services {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+    /// State transitions of the services seen by the updates and the applied changes over the
+    /// last [`TRANSITION_HISTORY`], oldest first. A history of the live state, neither serialized
+    /// with snapshots nor hashed.
+    #[serde(skip)]
+    pub transitions: Vec<ServiceTransition>,
+}
+
+impl Hash for Services {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.services.hash(state);
+        self.last_updated.hash(state);
+        self.state_change.hash(state);
+        self.unavailable.hash(state);
+    }
+}
+
+/// How long [`Services::transitions`] are kept
+pub const TRANSITION_HISTORY: Duration = Duration::from_secs(60 * 60);
+
+update!(Services, services; transitions => record_transitions);
+keyed!(Services, services, Win32_Service, Name: String);
+
+impl Services {
+    /// Number of services by state, and the automatic ones that are not running
+    pub fn summary(&self) -> Summary {
+        let in_state = |state: &str| {
+            self.services
+                .iter()
+                .filter(|service| service.State.as_deref() == Some(state))
+                .count()
+        };
+        let stopped_automatic = self
+            .services
+            .iter()
+            .filter(|service| {
+                service.StartMode.as_deref() == Some("Auto") && service.State.as_deref() == Some("Stopped")
+            })
+            .filter_map(|service| service.Name.as_deref())
+            .collect::<Vec<_>>();
+
+        Summary::new("Services")
+            .line("Installed", self.services.len())
+            .line("Running", in_state("Running"))
+            .line("Stopped", in_state("Stopped"))
+            .line("Automatic but stopped", match stopped_automatic.is_empty() {
+                true => String::from("none"),
+                false => stopped_automatic.join(", "),
+            })
+    }
+
+    /// Details of the executable of every service with a `PathName`, verifying the Authenticode
+    /// signature of the executables when `verify_signatures` is set
+    ///
+    /// Signatures are checked on the calling thread and read every file, so this is kept apart
+    /// from the update.
+    pub fn binaries(&self, verify_signatures: bool) -> Vec<ServiceBinary> {
+        self.services
+            .iter()
+            .filter_map(|service| {
+                let executable = service.executable_path()?;
+                let signature = verify_signatures.then(|| drivers::verify_signature(&executable));
+
+                Some(ServiceBinary {
+                    name: service.Name.clone()?,
+                    arguments: service.arguments().unwrap_or_default().to_string(),
+                    unquoted_with_spaces: service.unquoted_path_with_spaces(),
+                    executable,
+                    signature,
+                })
+            })
+            .collect()
+    }
+
+    /// Services whose executable path has spaces and no quotes, see
+    /// [`Win32_Service::unquoted_path_with_spaces`]
+    pub fn unquoted_paths(&self) -> Vec<&Win32_Service> {
+        self.services
+            .iter()
+            .filter(|service| service.unquoted_path_with_spaces())
+            .collect()
+    }
+
+    /// Applies a change pushed by a [`ServiceMonitor`] to the state, without querying every
+    /// service again, returns `true` if it changed the state
+    pub fn apply(&mut self, change: &ServiceChange) -> bool {
+        let position = self
+            .services
+            .iter()
+            .position(|service| service.Name.is_some() && service.Name == change.service.Name);
+
+        let changed = match (change.kind, position) {
+            (ServiceChangeKind::Deleted, Some(position)) => {
+                self.services.remove(position);
+                true
+            }
+            (ServiceChangeKind::Deleted, None) => false,
+            (_, Some(position)) => {
+                self.services[position] = change.service.clone();
+                true
+            }
+            (_, None) => {
+                self.services.push(change.service.clone());
+                true
+            }
+        };
+
+        if change.state_changed() {
+            if let Some(name) = &change.service.Name {
+                self.transitions.push(ServiceTransition {
+                    name: name.clone(),
+                    from: change.previous.as_ref().and_then(|previous| previous.State.clone()),
+                    to: change.service.State.clone(),
+                    observed: change.observed,
+                });
+            }
+        }
+
+        self.last_updated = change.observed;
+        self.state_change = changed;
+        self.forget_transitions();
+
+        changed
+    }
+
+    /// Services that changed state more than `threshold` times within any `window`, over the
+    /// [`Services::transitions`] kept, e.g. a crashing service restarted by its recovery actions
+    pub fn restart_storms(&self, threshold: usize, window: Duration) -> Vec<RestartStorm> {
+        restart_storms(&self.transitions, threshold, window)
+    }
+
+    /// Records the state transitions since the update that returned `previous` at
+    /// `previous_updated`
+    ///
+    /// Only the state of each service at both updates is compared, so a service that stopped and
+    /// started again in between is missed. Apply the changes of a [`ServiceMonitor`] to see every
+    /// transition.
+    fn record_transitions(&mut self, previous: &[Win32_Service], _previous_updated: SystemTime) {
+        let transitions = ServiceTransition::between(previous, &self.services, self.last_updated);
+
+        self.transitions.extend(transitions);
+        self.forget_transitions();
+    }
+
+    /// Drops the transitions older than [`TRANSITION_HISTORY`]
+    fn forget_transitions(&mut self) {
+        let Some(oldest) = self.last_updated.checked_sub(TRANSITION_HISTORY) else {
+            return;
+        };
+
+        self.transitions.retain(|transition| transition.observed >= oldest);
+    }
+}
+
+/// A service changing state, e.g. from `Running` to `Stopped`
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ServiceTransition {
+    /// `Name` of the service
+    pub name: String,
+    /// `State` before the transition, `None` if it was unknown
+    pub from: Option<String>,
+    /// `State` after the transition, `None` if it is unknown
+    pub to: Option<String>,
+    /// When the transition was seen
+    pub observed: SystemTime,
+}
+
+impl ServiceTransition {
+    /// Transitions of the services whose `State` differs between the `previous` and the `current`
+    /// instances, seen at `observed`
+    ///
+    /// Services are matched by `Name`, installed and removed services are left out.
+    pub fn between(previous: &[Win32_Service], current: &[Win32_Service], observed: SystemTime) -> Vec<Self> {
+        let previous: HashMap<&str, &Win32_Service> = previous
+            .iter()
+            .filter_map(|service| Some((service.Name.as_deref()?, service)))
+            .collect();
+
+        current
+            .iter()
+            .filter_map(|service| {
+                let name = service.Name.as_deref()?;
+                let before = previous.get(name)?;
+
+                (before.State != service.State).then(|| ServiceTransition {
+                    name: name.to_string(),
+                    from: before.State.clone(),
+                    to: service.State.clone(),
+                    observed,
+                })
+            })
+            .collect()
+    }
+
+    /// Transitions between consecutive saved snapshots of the services, e.g. to look for
+    /// [`restart_storms`] in a snapshot history, seen when each snapshot was last updated
+    pub fn from_history(history: &[Services]) -> Vec<Self> {
+        history
+            .windows(2)
+            .flat_map(|pair| ServiceTransition::between(&pair[0].services, &pair[1].services, pair[1].last_updated))
+            .collect()
+    }
+}
+
+/// A service that changed state more than a threshold within a window, see
+/// [`Services::restart_storms`]
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct RestartStorm {
+    /// `Name` of the service
+    pub name: String,
+    /// Number of transitions within the busiest window
+    pub transitions: usize,
+    /// When the first transition of the busiest window was seen
+    pub first: SystemTime,
+    /// When the last transition of the busiest window was seen
+    pub last: SystemTime,
+}
+
+/// Services that changed state more than `threshold` times within any `window` of `transitions`,
+/// ordered by name
+pub fn restart_storms(transitions: &[ServiceTransition], threshold: usize, window: Duration) -> Vec<RestartStorm> {
+    let mut by_service: BTreeMap<&str, Vec<SystemTime>> = BTreeMap::new();
+
+    for transition in transitions {
+        by_service
+            .entry(transition.name.as_str())
+            .or_default()
+            .push(transition.observed);
+    }
+
+    by_service
+        .into_iter()
+        .filter_map(|(name, mut observed)| {
+            observed.sort();
+
+            let mut start = 0;
+            let mut busiest: Option<(usize, usize)> = None;
+
+            for end in 0..observed.len() {
+                while observed[end]
+                    .duration_since(observed[start])
+                    .is_ok_and(|elapsed| elapsed > window)
+                {
+                    start += 1;
+                }
+
+                if busiest.is_none_or(|(first, last)| end - start > last - first) {
+                    busiest = Some((start, end));
+                }
+            }
+
+            let (first, last) = busiest?;
+
+            (last - first + 1 > threshold).then(|| RestartStorm {
+                name: name.to_string(),
+                transitions: last - first + 1,
+                first: observed[first],
+                last: observed[last],
+            })
+        })
+        .collect()
+}
+
+impl fmt::Display for Services {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.summary().fmt(f)
+    }
+}
+
+/// Pushes changes of services into a channel as WMI reports them, see [`ServiceMonitor::subscribe`]
+///
+/// Subscribes to `__InstanceOperationEvent WITHIN n WHERE TargetInstance ISA 'Win32_Service'`,
+/// which WMI answers by polling the services every `within` and reporting only the instances that
+/// were created, modified or deleted. This gives near real-time service states without querying
+/// every service from the caller, apply the changes with [`Services::apply`].
+///
+/// Example:
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use windows_snapshot::operating_system::services::ServiceMonitor;
+/// use windows_snapshot::COMLibrary;
+///
+/// let _com_con = COMLibrary::new().unwrap(); // initialise security context
+///
+/// let mut k = windows_snapshot::state::Windows::default();
+/// k.services.update();
+///
+/// let mut changes = ServiceMonitor::new(Duration::from_secs(2)).subscribe().unwrap();
+///
+/// while let Some(change) = changes.blocking_recv() {
+///     println!("{:?} {:?}: {:?}", change.kind, change.service.Name, change.service.State);
+///     k.services.apply(&change);
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ServiceMonitor {
+    /// Interval WMI polls the services at, rounded down to whole seconds and at least one
+    pub within: Duration,
+}
+
+impl ServiceMonitor {
+    /// Creates a monitor polling the services every `within`
+    pub fn new(within: Duration) -> Self {
+        ServiceMonitor { within }
+    }
+
+    /// Subscribes to service changes and pushes them into the returned channel, see
+    /// [`crate::events::subscribe`]
+    ///
+    /// A change that does not deserialize is skipped, with a warning under the `tracing` feature,
+    /// instead of ending the subscription.
+    pub fn subscribe(&self) -> Result<mpsc::UnboundedReceiver<ServiceChange>, Unavailable> {
+        crate::events::subscribe_map(crate::DEFAULT_NAMESPACE, &self.query(), ServiceEvent::into_change)
+    }
+
+    fn query(&self) -> String {
+        format!(
+            "SELECT * FROM __InstanceOperationEvent WITHIN {} WHERE TargetInstance ISA 'Win32_Service'",
+            self.within.as_secs().max(1)
+        )
+    }
+}
+
+impl Default for ServiceMonitor {
+    /// Polls the services every 5 seconds
+    fn default() -> Self {
+        ServiceMonitor::new(Duration::from_secs(5))
+    }
+}
+
+/// How a service changed
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ServiceChangeKind {
+    /// The service was installed
+    Created,
+    /// A property of the service changed, e.g. its `State` when it started or stopped
+    Modified,
+    /// The service was removed
+    Deleted,
+}
+
+/// A change of a service pushed by a [`ServiceMonitor`]
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ServiceChange {
+    /// How the service changed
+    pub kind: ServiceChangeKind,
+    /// The service after the change, or as it was before its removal
+    pub service: Win32_Service,
+    /// The service before a modification, `None` for the other changes
+    pub previous: Option<Win32_Service>,
+    /// When the change was received
+    pub observed: SystemTime,
+}
+
+impl ServiceChange {
+    /// Returns `true` if the change is the service starting, stopping or pausing
+    pub fn state_changed(&self) -> bool {
+        self.previous
+            .as_ref()
+            .is_some_and(|previous| previous.State != self.service.State)
+    }
+}
+
+/// `__InstanceCreationEvent`, `__InstanceModificationEvent` or `__InstanceDeletionEvent` of a
+/// service
+#[derive(Deserialize, Debug)]
+#[allow(non_snake_case)]
+struct ServiceEvent {
+    __CLASS: String,
+    TargetInstance: Win32_Service,
+    PreviousInstance: Option<Win32_Service>,
+}
+
+impl ServiceEvent {
+    fn into_change(self) -> ServiceChange {
+        let kind = match self.__CLASS.as_str() {
+            "__InstanceCreationEvent" => ServiceChangeKind::Created,
+            "__InstanceDeletionEvent" => ServiceChangeKind::Deleted,
+            _ => ServiceChangeKind::Modified,
+        };
+
+        ServiceChange {
+            kind,
+            service: self.TargetInstance,
+            previous: self.PreviousInstance,
+            observed: SystemTime::now(),
+        }
+    }
+}
+
+/// Details of the executable of a service, derived from its `PathName`
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ServiceBinary {
+    /// `Name` of the service
+    pub name: String,
+    /// Path of the executable, without quotes or arguments and with environment variables expanded
+    pub executable: PathBuf,
+    /// Arguments following the executable, e.g. `-k netsvcs -p`
+    pub arguments: String,
+    /// Whether the path is unquoted and has spaces, see [`Win32_Service::unquoted_path_with_spaces`]
+    pub unquoted_with_spaces: bool,
+    /// Authenticode signature status of the executable, `None` unless it was verified
+    pub signature: Option<SignatureStatus>,
+}
+
+/// Represents the state of Windows service dependencies
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct DependentServices {
+    /// Sequence of dependencies between services
+    pub dependent_services: Vec<Win32_DependentService>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    /// 
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(DependentServices, dependent_services);
+
+impl DependentServices {
+    /// Builds the dependency graph of the services
+    pub fn graph(&self) -> ServiceGraph {
+        ServiceGraph::new(&self.dependent_services)
+    }
+}
+
+/// Dependency graph of services, names are compared case insensitively like the service control
+/// manager does
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ServiceGraph {
+    /// Sequence of dependencies, `(dependent, antecedent)`: the first service needs the second
+    pub edges: Vec<(String, String)>,
 }
 
-update!(Services, services);
+impl ServiceGraph {
+    /// Builds the graph from the `Win32_DependentService` associations, skipping the ones whose
+    /// references cannot be resolved to a service name
+    pub fn new(dependent_services: &[Win32_DependentService]) -> Self {
+        ServiceGraph {
+            edges: dependent_services
+                .iter()
+                .filter_map(|dependency| {
+                    Some((
+                        service_name(dependency.Dependent.as_deref()?)?,
+                        service_name(dependency.Antecedent.as_deref()?)?,
+                    ))
+                })
+                .collect(),
+        }
+    }
+
+    /// Services `service` directly depends on
+    pub fn depends_on(&self, service: &str) -> Vec<&str> {
+        self.edges
+            .iter()
+            .filter(|(dependent, _)| dependent.eq_ignore_ascii_case(service))
+            .map(|(_, antecedent)| antecedent.as_str())
+            .collect()
+    }
+
+    /// Services that directly depend on `service`
+    pub fn dependents_of(&self, service: &str) -> Vec<&str> {
+        self.edges
+            .iter()
+            .filter(|(_, antecedent)| antecedent.eq_ignore_ascii_case(service))
+            .map(|(dependent, _)| dependent.as_str())
+            .collect()
+    }
+
+    /// Every service `service` needs to start, directly or through other services
+    pub fn all_depends_on(&self, service: &str) -> Vec<&str> {
+        self.walk(service, Self::depends_on)
+    }
+
+    /// Every service that stops when `service` stops, directly or through other services
+    pub fn all_dependents_of(&self, service: &str) -> Vec<&str> {
+        self.walk(service, Self::dependents_of)
+    }
+
+    /// Breadth first walk from `service`, each service is reported once even with cycles
+    fn walk<'a>(&'a self, service: &str, next: fn(&'a Self, &str) -> Vec<&'a str>) -> Vec<&'a str> {
+        let mut found: Vec<&str> = Vec::new();
+        let mut queue = VecDeque::from(next(self, service));
+
+        while let Some(current) = queue.pop_front() {
+            if current.eq_ignore_ascii_case(service)
+                || found.iter().any(|seen| seen.eq_ignore_ascii_case(current))
+            {
+                continue;
+            }
+
+            found.push(current);
+            queue.extend(next(self, current));
+        }
+
+        found
+    }
+}
+
+/// Extracts the service name from a reference such as
+/// `\\HOST\root\cimv2:Win32_Service.Name="RpcSs"`
+fn service_name(reference: &str) -> Option<String> {
+    let (_, name) = reference.split_once("Name=\"")?;
+
+    name.strip_suffix('"').map(String::from)
+}
+
+impl Win32_Service {
+    /// Path of the executable of the service, `PathName` stripped of its quotes and arguments,
+    /// with environment variables expanded and `\SystemRoot\` style prefixes resolved
+    pub fn executable_path(&self) -> Option<PathBuf> {
+        let (executable, _) = split_path_name(self.PathName.as_deref()?)?;
+
+        Some(drivers::resolve_driver_path(&crate::expand_environment(executable)))
+    }
+
+    /// Arguments following the executable in `PathName`
+    pub fn arguments(&self) -> Option<&str> {
+        let (_, arguments) = split_path_name(self.PathName.as_deref()?)?;
+
+        Some(arguments)
+    }
+
+    /// Returns `true` if `PathName` is unquoted and the executable path has spaces
+    ///
+    /// The service control manager then tries every prefix ending at a space, so
+    /// `C:\Program Files\Vendor App\app.exe` starts `C:\Program.exe` if it exists. Writable
+    /// folders along such paths let a user run code as the service account.
+    pub fn unquoted_path_with_spaces(&self) -> bool {
+        let Some(path_name) = self.PathName.as_deref().map(str::trim) else {
+            return false;
+        };
+
+        !path_name.starts_with('"')
+            && split_path_name(path_name).is_some_and(|(executable, _)| executable.contains(' '))
+    }
+}
+
+/// Splits a `PathName` into the executable and its arguments
+///
+/// A quoted executable ends at the closing quote. An unquoted one ends after the first `.exe`
+/// followed by a space or the end, or at the first space when there is none.
+fn split_path_name(path_name: &str) -> Option<(&str, &str)> {
+    let path_name = path_name.trim();
+
+    let (executable, arguments) = match path_name.strip_prefix('"') {
+        Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+        None => {
+            let lowercase = path_name.to_ascii_lowercase();
+            let end = lowercase
+                .match_indices(".exe")
+                .map(|(index, _)| index + ".exe".len())
+                .find(|&end| lowercase[end..].is_empty() || lowercase[end..].starts_with(' '))
+                .or_else(|| path_name.find(' '))
+                .unwrap_or(path_name.len());
+
+            path_name.split_at(end)
+        }
+    };
+
+    Some((executable, arguments.trim())).filter(|(executable, _)| !executable.is_empty())
+}
 
 /// The `Win32_Service` WMI class represents a process on an operating system.
 ///
@@ -197,3 +779,33 @@ pub struct Win32_Service {
     /// control program assumes that an error has occurred.
     pub WaitHint: Option<u32>,
 }
+
+labeled!(Win32_Service {
+    Status => status: DeviceStatus,
+});
+
+referenced!(Win32_Service { Name });
+
+/// The `Win32_DependentService` association WMI class relates two interdependent base services.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-dependentservice>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_DependentService {
+    /// Reference to the `Win32_BaseService` that must be running before the dependent service can
+    /// start.
+    pub Antecedent: Option<String>,
+    /// Reference to the `Win32_BaseService` that depends on the antecedent service.
+    pub Dependent: Option<String>,
+    /// Type of dependency.
+    /// 
+    /// - `Unknown` (0)
+    /// - `Other` (1)
+    /// - `Service Must Have Completed` (2)
+    /// - `Service Must Be Started` (3)
+    /// - `Service Must Not Be Started` (4)
+    pub TypeOfDependency: Option<u16>,
+}
+
+associates!(Win32_DependentService { Antecedent => Dependent });
@@ -13,8 +13,13 @@
 //! | [**Win32\_SessionProcess**](win32-sessionprocess)         | Association class<br/> Represents an association between a logon session and the processes associated with that session.<br/>                                                            |
 //! | [**Win32\_ShareToDirectory**](win32-sharetodirectory)     | Association class<br/> Relates a shared resource on the computer system and the directory to which it is mapped.<br/>                                                                    |
 //! | [**Win32\_Share**](win32-share)                         | Instance class<br/> Represents a shared resource on a computer system running Windows.<br/>                                                                                              |
+//!
+//! [`ShareSecurityDescriptors`] invokes `GetSecurityDescriptor` on the security settings of every
+//! share, so a snapshot shows who can access each share and with which rights.
 
-use crate::update;
+use crate::operating_system::security::{Win32_LogicalShareSecuritySetting, Win32_SecurityDescriptor};
+use crate::method::{exec_method, instance_path};
+use crate::{keyed, state_member, update, Unavailable, DEFAULT_NAMESPACE};
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
@@ -31,6 +36,8 @@ pub struct ServerConnections {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(ServerConnections, server_connections);
@@ -47,6 +54,8 @@ pub struct ServerSessions {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(ServerSessions, server_sessions);
@@ -63,9 +72,187 @@ pub struct Shares {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Shares, shares);
+keyed!(Shares, shares, Win32_Share, Name: String);
+
+/// Represents the share-level permissions of the Windows `Shares`
+///
+/// Read by invoking `GetSecurityDescriptor` on every `Win32_LogicalShareSecuritySetting`, which
+/// requires administrative rights. Administrative shares such as `C$` or `IPC$` have no security
+/// setting and are left out.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ShareSecurityDescriptors {
+    /// Sequence of shares and their security descriptor
+    pub share_security_descriptors: Vec<ShareSecurityDescriptor>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the security settings could not be queried, `None` when they were
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(ShareSecurityDescriptors, share_security_descriptors);
+
+impl ShareSecurityDescriptors {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+        self.refresh(share_security_descriptors());
+    }
+
+    /// Update fields asynchronously
+    ///
+    /// WMI methods have no asynchronous form returning futures, so they run on a blocking thread
+    /// of the runtime
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let shares = crate::blocking("Win32_LogicalShareSecuritySetting", share_security_descriptors).await;
+
+        self.refresh(shares.and_then(|shares| shares));
+    }
+
+    /// Security descriptor of the share `name`, compared case insensitively
+    pub fn of_share(&self, name: &str) -> Option<&ShareSecurityDescriptor> {
+        self.share_security_descriptors
+            .iter()
+            .find(|share| share.name.eq_ignore_ascii_case(name))
+    }
+
+    fn refresh(&mut self, shares: Result<Vec<ShareSecurityDescriptor>, Unavailable>) {
+        let old_vec = std::mem::take(&mut self.share_security_descriptors);
+        let old_unavailable = self.unavailable.take();
+
+        match shares {
+            Ok(shares) => self.share_security_descriptors = shares,
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.share_security_descriptors.len() != old_vec.len()
+            || crate::hash_vec(&self.share_security_descriptors) != crate::hash_vec(&old_vec);
+    }
+}
+
+impl Default for ShareSecurityDescriptors {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        ShareSecurityDescriptors {
+            share_security_descriptors: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// The security descriptor of a share
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ShareSecurityDescriptor {
+    /// Name of the share, e.g. `Users`
+    pub name: String,
+    /// Value `GetSecurityDescriptor` returned, 0 on success and 2 when access was denied, `None`
+    /// when the method could not be invoked
+    pub return_value: Option<u32>,
+    /// Owner, group and access control lists of the share, `None` unless `return_value` is 0
+    pub descriptor: Option<Win32_SecurityDescriptor>,
+}
+
+impl ShareSecurityDescriptor {
+    /// Access granted or denied by the discretionary access control list, in ACL order
+    pub fn permissions(&self) -> Vec<SharePermission> {
+        let Some(dacl) = self.descriptor.as_ref().and_then(|descriptor| descriptor.DACL.as_ref()) else {
+            return Vec::new();
+        };
+
+        dacl.iter()
+            .map(|ace| SharePermission {
+                trustee: ace.Trustee.as_ref().and_then(|trustee| {
+                    match (&trustee.Domain, &trustee.Name) {
+                        (Some(domain), Some(name)) if !domain.is_empty() => Some(format!("{domain}\\{name}")),
+                        (_, Some(name)) => Some(name.clone()),
+                        _ => trustee.SIDString.clone(),
+                    }
+                }),
+                allowed: ace.AceType == Some(0),
+                access_mask: ace.AccessMask.unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// Access to a share granted or denied to a trustee
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct SharePermission {
+    /// Trustee as `DOMAIN\Name`, or its SID when the name is unknown
+    pub trustee: Option<String>,
+    /// `true` if the access is allowed, `false` if it is denied
+    pub allowed: bool,
+    /// Access rights, see [`SharePermission::level`]
+    pub access_mask: u32,
+}
+
+impl SharePermission {
+    /// Share permission level the access mask corresponds to, as shown by the sharing dialog
+    pub fn level(&self) -> ShareAccessLevel {
+        match self.access_mask {
+            0x001F_01FF => ShareAccessLevel::Full,
+            0x0013_01BF => ShareAccessLevel::Change,
+            0x0012_00A9 => ShareAccessLevel::Read,
+            _ => ShareAccessLevel::Special,
+        }
+    }
+}
+
+/// Share permission levels
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ShareAccessLevel {
+    /// Full Control
+    Full,
+    /// Change
+    Change,
+    /// Read
+    Read,
+    /// Any other combination of rights
+    Special,
+}
+
+/// Output parameters of `Win32_LogicalShareSecuritySetting.GetSecurityDescriptor`
+#[derive(Deserialize)]
+#[allow(non_snake_case)]
+struct GetSecurityDescriptorOutput {
+    ReturnValue: Option<u32>,
+    Descriptor: Option<Win32_SecurityDescriptor>,
+}
+
+fn share_security_descriptors() -> Result<Vec<ShareSecurityDescriptor>, Unavailable> {
+    let settings: Vec<Win32_LogicalShareSecuritySetting> =
+        crate::query(DEFAULT_NAMESPACE, "Win32_LogicalShareSecuritySetting")?;
+
+    Ok(settings
+        .into_iter()
+        .filter_map(|setting| setting.Name)
+        .map(|name| {
+            let path = instance_path("Win32_LogicalShareSecuritySetting", &[("Name", &name)]);
+            let output: Option<GetSecurityDescriptorOutput> =
+                exec_method(DEFAULT_NAMESPACE, &path, "GetSecurityDescriptor").ok();
+
+            ShareSecurityDescriptor {
+                name,
+                return_value: output.as_ref().and_then(|output| output.ReturnValue),
+                descriptor: output.and_then(|output| output.Descriptor),
+            }
+        })
+        .collect())
+}
 
 /// The `Win32_ServerConnection` WMI class represents the connections made from a remote computer 
 /// to a shared resource on the local computer.
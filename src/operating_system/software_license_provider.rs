@@ -23,10 +23,108 @@ pub struct SoftwareLicensingProducts {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(SoftwareLicensingProducts, software_licensing_products);
 
+impl SoftwareLicensingProducts {
+    /// Activation status of every product with a product key installed
+    pub fn activation_status(&self) -> Vec<ActivationStatus> {
+        self.software_licensing_products
+            .iter()
+            .filter(|product| product.PartialProductKey.is_some())
+            .map(ActivationStatus::from_product)
+            .collect()
+    }
+}
+
+/// Channel a product key was issued through
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum LicenseChannel {
+    /// Key sold with a retail license
+    Retail,
+    /// Key preinstalled by the manufacturer (`OEM:*`)
+    Oem,
+    /// Generic volume license key, activated by a KMS host or Active Directory (`Volume:GVLK`)
+    VolumeKms,
+    /// Multiple activation key, activated once against Microsoft (`Volume:MAK`)
+    VolumeMak,
+    /// Any other channel, as reported
+    Other(String),
+}
+
+impl LicenseChannel {
+    /// Parses the `ProductKeyChannel` property
+    pub fn parse(channel: &str) -> Self {
+        let upper = channel.to_uppercase();
+
+        if upper.starts_with("OEM") {
+            LicenseChannel::Oem
+        } else if upper.starts_with("RETAIL") {
+            LicenseChannel::Retail
+        } else if upper == "VOLUME:GVLK" {
+            LicenseChannel::VolumeKms
+        } else if upper == "VOLUME:MAK" {
+            LicenseChannel::VolumeMak
+        } else {
+            LicenseChannel::Other(channel.to_string())
+        }
+    }
+}
+
+/// Activation details of a product with a product key installed
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct ActivationStatus {
+    /// Name of the product
+    pub name: Option<String>,
+    /// Last five characters of the installed product key
+    pub partial_product_key: Option<String>,
+    /// Channel of the installed product key
+    pub channel: Option<LicenseChannel>,
+    /// `LicenseStatus` of the product, 1 means licensed
+    pub license_status: Option<u32>,
+    /// Minutes left before notification mode, or before reactivation for volume clients
+    pub grace_period_remaining: Option<u32>,
+    /// Date after which the product cannot be activated
+    pub evaluation_end_date: Option<WMIDateTime>,
+    /// KMS host set on the client, or the one last discovered through DNS
+    pub kms_host: Option<String>,
+    /// Port of `kms_host`
+    pub kms_port: Option<u32>,
+}
+
+impl ActivationStatus {
+    fn from_product(product: &SoftwareLicensingProduct) -> Self {
+        let (kms_host, kms_port) = match &product.KeyManagementServiceMachine {
+            Some(machine) if !machine.is_empty() => {
+                (Some(machine.clone()), product.KeyManagementServicePort)
+            }
+            _ => (
+                product.DiscoveredKeyManagementServiceMachineName.clone().filter(|name| !name.is_empty()),
+                product.DiscoveredKeyManagementServiceMachinePort,
+            ),
+        };
+
+        ActivationStatus {
+            name: product.Name.clone(),
+            partial_product_key: product.PartialProductKey.clone(),
+            channel: product.ProductKeyChannel.as_deref().map(LicenseChannel::parse),
+            license_status: product.LicenseStatus,
+            grace_period_remaining: product.GracePeriodRemaining,
+            evaluation_end_date: product.EvaluationEndDate.clone(),
+            kms_host,
+            kms_port,
+        }
+    }
+
+    /// Returns `true` if the product is licensed
+    pub fn licensed(&self) -> bool {
+        self.license_status == Some(1)
+    }
+}
+
 /// Represents the state of Windows `SoftwareLicensingServices`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct SoftwareLicensingServices {
@@ -39,6 +137,8 @@ pub struct SoftwareLicensingServices {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(SoftwareLicensingServices, software_licensing_services);
@@ -55,6 +155,8 @@ pub struct SoftwareLicensingTokenActivationLicenses {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(SoftwareLicensingTokenActivationLicenses, software_licensing_token_activation_licenses);
@@ -110,6 +212,35 @@ pub struct SoftwareLicensingProduct {
     pub PartialProductKey: Option<String>,
     /// Specifies the product key ID. Returns a `null` value if a product key is not installed.
     pub ProductKeyID: Option<String>,
+    /// Specifies the channel of the installed product key, e.g. "Retail", "OEM:DM", "OEM:NONSLP",
+    /// "Volume:GVLK" (KMS client) or "Volume:MAK". Returns a `null` value if a product key is not
+    /// installed.
+    pub ProductKeyChannel: Option<String>,
+    /// Specifies the volume activation types allowed for this product.
+    /// 
+    /// Value: Description
+    /// - 0: All
+    /// - 1: Active Directory
+    /// - 2: KMS
+    /// - 3: Token
+    pub VLActivationTypeEnabled: Option<u32>,
+    /// Specifies the volume activation type used to activate this product.
+    /// 
+    /// Value: Description
+    /// - 0: All
+    /// - 1: Active Directory
+    /// - 2: KMS
+    /// - 3: Token
+    pub VLActivationType: Option<u32>,
+    /// Specifies the Active Directory activation object the product was activated with.
+    pub ADActivationObjectName: Option<String>,
+    /// Specifies the DNS domain in which KMS hosts are looked up. Returns `null` if
+    /// `SetKeyManagementServiceLookupDomain` has not been called.
+    pub KeyManagementServiceLookupDomain: Option<String>,
+    /// Specifies the remaining number of times the application can be rearmed.
+    pub RemainingAppReArmCount: Option<u32>,
+    /// Specifies the remaining number of times the SKU can be rearmed.
+    pub RemainingSkuReArmCount: Option<u32>,
     /// Specifies the group identifier for the SKU used to determine license relationships for add-ons.
     pub LicenseFamily: Option<String>,
     /// Specifies the dependency identifier for the set of SKUs used to determine license relationships 
@@ -26,6 +26,8 @@ pub struct LogicalProgramGroups {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(LogicalProgramGroups, logical_program_groups);
@@ -42,6 +44,8 @@ pub struct LogicalProgramGroupItems {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(LogicalProgramGroupItems, logical_program_group_items);
@@ -58,6 +62,8 @@ pub struct ProgramGroupOrItems {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(ProgramGroupOrItems, program_group_or_items);
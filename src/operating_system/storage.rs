@@ -14,7 +14,7 @@
 //! | [**Win32\_Volume**](/previous-versions/windows/desktop/legacy/aa394515(v=vs.85))                                   | Instance class<br/> Represents an area of storage on a hard disk.<br/>                                                           |
 //! | [**Win32\_VolumeUserQuota**](/previous-versions/windows/desktop/vdswmi/win32-volumeuserquota)                 | Association class<br/> Represents a volume to the per volume quota settings.<br/>                                                |
 
-use crate::update;
+use crate::{coded, keyed, update};
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 use wmi::{COMLibrary, WMIConnection, WMIDateTime};
@@ -31,6 +31,8 @@ pub struct ShadowCopys {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(ShadowCopys, shadow_copys);
@@ -47,9 +49,12 @@ pub struct Volumes {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Volumes, volumes);
+keyed!(Volumes, volumes, Win32_Volume, DeviceID: String);
 
 /// Represents the state of Windows `ShadowContexts`
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -63,6 +68,8 @@ pub struct ShadowContexts {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(ShadowContexts, shadow_contexts);
@@ -79,6 +86,8 @@ pub struct ShadowProviders {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(ShadowProviders, shadow_providers);
@@ -437,6 +446,13 @@ pub struct Win32_Volume {
     pub SupportsFileBasedCompression: Option<bool>,
 }
 
+coded!(Win32_Volume {
+    Availability => availability: Availability,
+    StatusInfo => status_info: StatusInfo,
+    ConfigManagerErrorCode => config_manager_error_code: ConfigManagerErrorCode,
+    DriveType => drive_type: DriveType,
+});
+
 /// The `Win32_ShadowContext` class specifies how a shadow copy is to be created, queried, or deleted, 
 /// and the degree of writer involvement.
 /// 
@@ -0,0 +1,188 @@
+//! Configuration of the Windows Time service (W32Time), read from the registry.
+//!
+//! | Key                                                                   | Description                                                                 |
+//! |-----------------------------------------------------------------------|-----------------------------------------------------------------------------|
+//! | `HKLM\SYSTEM\CurrentControlSet\Services\W32Time`                      | `Parameters`, `Config` and `TimeProviders` subkeys of the local settings.   |
+//! | `HKLM\SOFTWARE\Policies\Microsoft\W32Time\Parameters`                 | Group Policy settings, which take precedence over the local ones.           |
+//!
+//! The time zone and clock are covered by [`TimeZones`](crate::operating_system::desktop::TimeZones),
+//! [`LocalTimes`](crate::operating_system::scheduler_jobs::LocalTimes) and
+//! [`UTCTimes`](crate::operating_system::scheduler_jobs::UTCTimes), whose
+//! [`LocalTimes::utc_offset_minutes`](crate::operating_system::scheduler_jobs::LocalTimes::utc_offset_minutes)
+//! gives the offset in effect.
+
+use crate::operating_system::registry::{self, Hive, RegistryValue};
+use crate::{state_member, Unavailable};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+/// Key of the local W32Time settings
+pub const W32TIME: &str = "SYSTEM\\CurrentControlSet\\Services\\W32Time";
+
+/// Key of the W32Time settings set by Group Policy
+pub const W32TIME_POLICY: &str = "SOFTWARE\\Policies\\Microsoft\\W32Time\\Parameters";
+
+/// Represents the state of the Windows Time service configuration
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct TimeService {
+    /// Sequence holding the W32Time settings, empty when the service is not installed
+    pub time_settings: Vec<W32TimeSettings>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(TimeService, time_settings);
+
+impl TimeService {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+        self.refresh(Ok(W32TimeSettings::read()));
+    }
+
+    /// Update fields asynchronously
+    ///
+    /// The registry has no asynchronous reads, so they run on a blocking thread of the runtime
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+        self.refresh(crate::blocking("W32TimeSettings", W32TimeSettings::read).await);
+    }
+
+    fn refresh(&mut self, settings: Result<Option<W32TimeSettings>, Unavailable>) {
+        let old_vec = std::mem::take(&mut self.time_settings);
+        let old_unavailable = self.unavailable.take();
+
+        match settings {
+            Ok(settings) => self.time_settings.extend(settings),
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.time_settings.len() != old_vec.len()
+            || crate::hash_vec(&self.time_settings) != crate::hash_vec(&old_vec);
+    }
+
+    /// Effective W32Time settings, `None` when the service is not installed
+    pub fn settings(&self) -> Option<&W32TimeSettings> {
+        self.time_settings.first()
+    }
+}
+
+impl Default for TimeService {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        TimeService {
+            time_settings: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// Effective settings of the Windows Time service
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct W32TimeSettings {
+    /// How the clock is synchronized: `NTP` (from `ntp_servers`), `NT5DS` (from the domain
+    /// hierarchy), `AllSync` (both) or `NoSync`.
+    pub sync_type: Option<String>,
+    /// NTP peers the clock is synchronized from when `sync_type` is `NTP` or `AllSync`.
+    pub ntp_servers: Vec<NtpPeer>,
+    /// Whether `sync_type` and `ntp_servers` come from Group Policy.
+    pub from_policy: bool,
+    /// Whether the NTP client provider is enabled.
+    pub ntp_client_enabled: Option<bool>,
+    /// Whether the NTP server provider is enabled, serving time to other machines.
+    pub ntp_server_enabled: Option<bool>,
+    /// Seconds between polls of peers flagged with `SpecialInterval`.
+    pub special_poll_interval: Option<u32>,
+    /// Largest forward correction applied at once, in seconds, larger offsets are logged and not
+    /// corrected. `0xFFFFFFFF` accepts any correction.
+    pub max_pos_phase_correction: Option<u32>,
+    /// Largest backward correction applied at once, in seconds.
+    pub max_neg_phase_correction: Option<u32>,
+    /// Whether the machine advertises itself as a time source to the domain, a bit field.
+    pub announce_flags: Option<u32>,
+}
+
+impl W32TimeSettings {
+    /// Reads the settings, `None` when the service is not installed
+    fn read() -> Option<Self> {
+        let parameters = format!("{W32TIME}\\Parameters");
+        let config = format!("{W32TIME}\\Config");
+        let ntp_client = format!("{W32TIME}\\TimeProviders\\NtpClient");
+        let ntp_server = format!("{W32TIME}\\TimeProviders\\NtpServer");
+
+        let string = |path: &str, name: &str| {
+            registry::read_value(Hive::LocalMachine, path, name)
+                .as_ref()
+                .and_then(RegistryValue::as_str)
+                .map(String::from)
+        };
+        let number = |path: &str, name: &str| {
+            registry::read_value(Hive::LocalMachine, path, name)
+                .as_ref()
+                .and_then(RegistryValue::as_u64)
+                .and_then(|value| u32::try_from(value).ok())
+        };
+
+        let policy_type = string(W32TIME_POLICY, "Type");
+        let policy_servers = string(W32TIME_POLICY, "NtpServer");
+        let from_policy = policy_type.is_some() || policy_servers.is_some();
+
+        let sync_type = policy_type.or_else(|| string(&parameters, "Type"));
+        let servers = policy_servers.or_else(|| string(&parameters, "NtpServer"));
+
+        if sync_type.is_none() && servers.is_none() && registry::subkeys(Hive::LocalMachine, W32TIME).is_empty() {
+            return None;
+        }
+
+        Some(W32TimeSettings {
+            sync_type,
+            ntp_servers: servers.as_deref().map(NtpPeer::parse_list).unwrap_or_default(),
+            from_policy,
+            ntp_client_enabled: number(&ntp_client, "Enabled").map(|enabled| enabled != 0),
+            ntp_server_enabled: number(&ntp_server, "Enabled").map(|enabled| enabled != 0),
+            special_poll_interval: number(&ntp_client, "SpecialPollInterval"),
+            max_pos_phase_correction: number(&config, "MaxPosPhaseCorrection"),
+            max_neg_phase_correction: number(&config, "MaxNegPhaseCorrection"),
+            announce_flags: number(&config, "AnnounceFlags"),
+        })
+    }
+}
+
+/// An NTP peer, as listed in the `NtpServer` setting
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct NtpPeer {
+    /// Host name or address of the peer, e.g. `time.windows.com`
+    pub host: String,
+    /// Mode flags following the host, e.g. `0x9`: `SpecialInterval` (0x1), `UseAsFallbackOnly`
+    /// (0x2), `SymmetricActive` (0x4) and `Client` (0x8)
+    pub flags: Option<u32>,
+}
+
+impl NtpPeer {
+    /// Parses a space separated list of `host[,flags]` peers
+    fn parse_list(servers: &str) -> Vec<Self> {
+        servers
+            .split_whitespace()
+            .map(|peer| match peer.split_once(',') {
+                Some((host, flags)) => NtpPeer {
+                    host: host.to_string(),
+                    flags: u32::from_str_radix(flags.trim_start_matches("0x"), 16).ok(),
+                },
+                None => NtpPeer {
+                    host: peer.to_string(),
+                    flags: None,
+                },
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,365 @@
+//! Windows Update status: updates waiting to be installed, when Automatic Updates last searched and
+//! installed successfully, and where the machine gets its updates from.
+//!
+//! | Source                                                                                                            | Description                                                                                              |
+//! |-------------------------------------------------------------------------------------------------------------------|----------------------------------------------------------------------------------------------------------|
+//! | [**IUpdateSearcher**](https://learn.microsoft.com/en-us/windows/win32/api/wuapi/nn-wuapi-iupdatesearcher)          | Update Agent interface<br/> Searches for the updates not installed yet.                                 |
+//! | [**IAutomaticUpdatesResults**](https://learn.microsoft.com/en-us/windows/win32/api/wuapi/nn-wuapi-iautomaticupdatesresults) | Update Agent interface<br/> When Automatic Updates last searched and installed successfully.     |
+//! | [**ISystemInformation**](https://learn.microsoft.com/en-us/windows/win32/api/wuapi/nn-wuapi-isysteminformation)    | Update Agent interface<br/> Whether installed updates wait for a restart.                              |
+//! | `HKLM\SOFTWARE\Policies\Microsoft\Windows\WindowsUpdate`                                                          | WSUS server and Automatic Updates options set by Group Policy.                                          |
+//!
+//! [`QuickFixEngineerings`](crate::operating_system::operating_system_settings::QuickFixEngineerings)
+//! only lists the patches already installed through Component Based Servicing, this module tells
+//! what is still missing.
+//!
+//! The search runs offline, against the update metadata of the last scan made by Windows Update,
+//! so it neither contacts Windows Update or WSUS nor takes minutes. A machine that never scanned
+//! reports no pending update: check `last_search_success` before trusting an empty list.
+
+use crate::operating_system::registry::{self, Hive, RegistryValue};
+use crate::{state_member, Unavailable};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+use windows::core::BSTR;
+use windows::Win32::Foundation::VARIANT_FALSE;
+use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER, VARIANT, VT_DATE};
+use windows::Win32::System::UpdateAgent::{
+    AutomaticUpdates, IAutomaticUpdates2, ICategoryCollection, ISystemInformation, IStringCollection,
+    IUpdate, IUpdateSession, SystemInformation, UpdateSession,
+};
+
+/// Key of the Windows Update settings set by Group Policy
+pub const WINDOWS_UPDATE_POLICY: &str = "SOFTWARE\\Policies\\Microsoft\\Windows\\WindowsUpdate";
+
+/// Criteria of the search for pending updates
+const PENDING_CRITERIA: &str = "IsInstalled=0 and IsHidden=0";
+
+/// Represents the state of Windows Update
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct WindowsUpdates {
+    /// Sequence of updates applicable to the machine and not installed yet
+    pub pending_updates: Vec<PendingUpdate>,
+    /// When Automatic Updates last searched for updates successfully
+    pub last_search_success: Option<SystemTime>,
+    /// When Automatic Updates last installed updates successfully
+    pub last_installation_success: Option<SystemTime>,
+    /// Whether installed updates wait for a restart to complete
+    pub reboot_required: bool,
+    /// Where the machine gets its updates from and how they are installed
+    pub configuration: WindowsUpdateConfiguration,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the Update Agent could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(WindowsUpdates, pending_updates; last_search_success, last_installation_success, reboot_required, configuration);
+
+impl WindowsUpdates {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        self.refresh(AgentStatus::query(), WindowsUpdateConfiguration::read());
+    }
+
+    /// Update fields asynchronously
+    ///
+    /// The Update Agent has no asynchronous search returning futures, so the search runs on a
+    /// blocking thread of the runtime
+    pub async fn async_update(&mut self) {
+        self.last_updated = SystemTime::now();
+
+        let status = tokio::task::spawn_blocking(|| {
+            let _com_con = wmi::COMLibrary::without_security();
+            AgentStatus::query()
+        })
+        .await
+        .unwrap_or_else(|e| {
+            Err(Unavailable {
+                namespace: String::new(),
+                class: "IUpdateSearcher".to_string(),
+                reason: crate::UnavailableReason::Other,
+                message: e.to_string(),
+            })
+        });
+
+        self.refresh(status, WindowsUpdateConfiguration::read());
+    }
+
+    /// Pending updates Microsoft rates `Critical`
+    pub fn critical(&self) -> impl Iterator<Item = &PendingUpdate> {
+        self.pending_updates
+            .iter()
+            .filter(|update| update.msrc_severity.as_deref() == Some("Critical"))
+    }
+
+    /// Time elapsed since Automatic Updates last searched successfully, `None` if it never did
+    pub fn since_last_search(&self) -> Option<Duration> {
+        self.last_search_success
+            .and_then(|time| SystemTime::now().duration_since(time).ok())
+    }
+
+    fn refresh(&mut self, status: Result<AgentStatus, Unavailable>, configuration: WindowsUpdateConfiguration) {
+        let old_vec = std::mem::take(&mut self.pending_updates);
+        let old_unavailable = self.unavailable.take();
+        let old_status = (
+            self.last_search_success.take(),
+            self.last_installation_success.take(),
+            std::mem::take(&mut self.reboot_required),
+            std::mem::replace(&mut self.configuration, configuration),
+        );
+
+        match status {
+            Ok(status) => {
+                self.pending_updates = status.pending_updates;
+                self.last_search_success = status.last_search_success;
+                self.last_installation_success = status.last_installation_success;
+                self.reboot_required = status.reboot_required;
+            }
+            Err(unavailable) => self.unavailable = Some(unavailable),
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || old_status
+                != (
+                    self.last_search_success,
+                    self.last_installation_success,
+                    self.reboot_required,
+                    self.configuration.clone(),
+                )
+            || self.pending_updates.len() != old_vec.len()
+            || crate::hash_vec(&self.pending_updates) != crate::hash_vec(&old_vec);
+    }
+}
+
+impl Default for WindowsUpdates {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        WindowsUpdates {
+            pending_updates: Default::default(),
+            last_search_success: None,
+            last_installation_success: None,
+            reboot_required: false,
+            configuration: Default::default(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+/// An update applicable to the machine and not installed yet
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PendingUpdate {
+    /// Identifier of the update, a GUID shared by every machine offered it.
+    pub update_id: String,
+    /// Localized title, e.g. `2023-05 Cumulative Update for Windows 11 (KB5026372)`.
+    pub title: String,
+    /// Knowledge Base articles of the update, without the `KB` prefix.
+    pub kb_article_ids: Vec<String>,
+    /// Categories of the update, e.g. `Security Updates`, `Definition Updates` or `Drivers`.
+    pub categories: Vec<String>,
+    /// Severity rating of the Microsoft Security Response Center: `Critical`, `Important`,
+    /// `Moderate` or `Low`, `None` for updates not fixing a vulnerability.
+    pub msrc_severity: Option<String>,
+    /// Whether the update is already downloaded and only waits to be installed.
+    pub downloaded: bool,
+    /// Whether the update cannot be declined.
+    pub mandatory: bool,
+    /// When the update was last published or revised.
+    pub last_deployment_change: Option<SystemTime>,
+}
+
+impl PendingUpdate {
+    /// Reads the properties of `update`
+    fn read(update: &IUpdate) -> windows::core::Result<Self> {
+        unsafe {
+            Ok(PendingUpdate {
+                update_id: update.Identity()?.UpdateID()?.to_string(),
+                title: update.Title()?.to_string(),
+                kb_article_ids: strings(&update.KBArticleIDs()?),
+                categories: category_names(&update.Categories()?),
+                msrc_severity: Some(update.MsrcSeverity()?.to_string()).filter(|severity| !severity.is_empty()),
+                downloaded: update.IsDownloaded()?.as_bool(),
+                mandatory: update.IsMandatory()?.as_bool(),
+                last_deployment_change: update.LastDeploymentChangeTime().ok().and_then(ole_date),
+            })
+        }
+    }
+}
+
+/// Windows Update settings, from Group Policy
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct WindowsUpdateConfiguration {
+    /// Whether updates come from `wu_server` instead of Microsoft.
+    pub use_wu_server: bool,
+    /// URL of the WSUS server updates are searched on, e.g. `http://wsus.corp.local:8530`.
+    pub wu_server: Option<String>,
+    /// URL of the server the client reports its status to, usually `wu_server`.
+    pub wu_status_server: Option<String>,
+    /// WSUS computer group the machine joins when client-side targeting is enabled.
+    pub target_group: Option<String>,
+    /// Whether Automatic Updates is turned off.
+    pub no_auto_update: bool,
+    /// How Automatic Updates behaves, `None` when policy does not set it.
+    ///
+    /// - `Notify before download` (2)
+    /// - `Download and notify before installation` (3)
+    /// - `Download and schedule the installation` (4)
+    /// - `Let local administrators choose` (5)
+    pub au_options: Option<u32>,
+    /// Day scheduled installations run when `au_options` is 4, 0 for every day, 1 (Sunday) to 7
+    /// (Saturday) for a given day.
+    pub scheduled_install_day: Option<u32>,
+    /// Hour scheduled installations run when `au_options` is 4, 0 to 23.
+    pub scheduled_install_time: Option<u32>,
+    /// Whether users are denied access to every Windows Update feature.
+    pub disable_windows_update_access: bool,
+}
+
+impl WindowsUpdateConfiguration {
+    /// Reads the settings, all unset when policy does not configure Windows Update
+    fn read() -> Self {
+        let au = format!("{WINDOWS_UPDATE_POLICY}\\AU");
+
+        let string = |path: &str, name: &str| {
+            registry::read_value(Hive::LocalMachine, path, name)
+                .as_ref()
+                .and_then(RegistryValue::as_str)
+                .map(String::from)
+        };
+        let number = |path: &str, name: &str| {
+            registry::read_value(Hive::LocalMachine, path, name)
+                .as_ref()
+                .and_then(RegistryValue::as_u64)
+                .and_then(|value| u32::try_from(value).ok())
+        };
+
+        WindowsUpdateConfiguration {
+            use_wu_server: number(&au, "UseWUServer") == Some(1),
+            wu_server: string(WINDOWS_UPDATE_POLICY, "WUServer"),
+            wu_status_server: string(WINDOWS_UPDATE_POLICY, "WUStatusServer"),
+            target_group: string(WINDOWS_UPDATE_POLICY, "TargetGroup"),
+            no_auto_update: number(&au, "NoAutoUpdate") == Some(1),
+            au_options: number(&au, "AUOptions"),
+            scheduled_install_day: number(&au, "ScheduledInstallDay"),
+            scheduled_install_time: number(&au, "ScheduledInstallTime"),
+            disable_windows_update_access: number(WINDOWS_UPDATE_POLICY, "DisableWindowsUpdateAccess") == Some(1),
+        }
+    }
+
+    /// Returns `true` if updates come from a WSUS server rather than Microsoft
+    pub fn uses_wsus(&self) -> bool {
+        self.use_wu_server && self.wu_server.is_some()
+    }
+}
+
+/// What the Update Agent reports
+struct AgentStatus {
+    pending_updates: Vec<PendingUpdate>,
+    last_search_success: Option<SystemTime>,
+    last_installation_success: Option<SystemTime>,
+    reboot_required: bool,
+}
+
+impl AgentStatus {
+    /// Searches for pending updates, the other properties are left unset when their interface
+    /// fails since they only complement the search
+    fn query() -> Result<Self, Unavailable> {
+        let pending_updates =
+            search_pending().map_err(|e| Unavailable::from_com("IUpdateSearcher", &e))?;
+
+        let (last_search_success, last_installation_success) = unsafe {
+            CoCreateInstance::<_, IAutomaticUpdates2>(&AutomaticUpdates, None, CLSCTX_INPROC_SERVER)
+                .and_then(|automatic_updates| automatic_updates.Results())
+                .map(|results| {
+                    (
+                        results.LastSearchSuccessDate().ok().as_ref().and_then(variant_date),
+                        results.LastInstallationSuccessDate().ok().as_ref().and_then(variant_date),
+                    )
+                })
+                .unwrap_or_default()
+        };
+
+        let reboot_required = unsafe {
+            CoCreateInstance::<_, ISystemInformation>(&SystemInformation, None, CLSCTX_INPROC_SERVER)
+                .and_then(|information| information.RebootRequired())
+                .map(|required| required.as_bool())
+                .unwrap_or_default()
+        };
+
+        Ok(AgentStatus {
+            pending_updates,
+            last_search_success,
+            last_installation_success,
+            reboot_required,
+        })
+    }
+}
+
+/// Searches the metadata of the last scan for updates not installed yet
+fn search_pending() -> windows::core::Result<Vec<PendingUpdate>> {
+    unsafe {
+        let session: IUpdateSession = CoCreateInstance(&UpdateSession, None, CLSCTX_INPROC_SERVER)?;
+        let searcher = session.CreateUpdateSearcher()?;
+        searcher.SetOnline(VARIANT_FALSE)?;
+
+        let updates = searcher.Search(&BSTR::from(PENDING_CRITERIA))?.Updates()?;
+
+        (0..updates.Count()?)
+            .map(|index| PendingUpdate::read(&updates.get_Item(index)?))
+            .collect()
+    }
+}
+
+/// Strings held by `collection`
+fn strings(collection: &IStringCollection) -> Vec<String> {
+    unsafe {
+        (0..collection.Count().unwrap_or_default())
+            .filter_map(|index| collection.get_Item(index).ok())
+            .map(|string| string.to_string())
+            .collect()
+    }
+}
+
+/// Names of the categories held by `collection`
+fn category_names(collection: &ICategoryCollection) -> Vec<String> {
+    unsafe {
+        (0..collection.Count().unwrap_or_default())
+            .filter_map(|index| collection.get_Item(index).and_then(|category| category.Name()).ok())
+            .map(|name| name.to_string())
+            .collect()
+    }
+}
+
+/// Time held by `variant`, `None` unless it holds a date
+fn variant_date(variant: &VARIANT) -> Option<SystemTime> {
+    let value = unsafe { &variant.Anonymous.Anonymous };
+
+    (value.vt == VT_DATE)
+        .then(|| unsafe { value.Anonymous.date })
+        .and_then(ole_date)
+}
+
+/// Converts an OLE automation date, days since 1899-12-30, `None` for the zero date meaning unset
+fn ole_date(date: f64) -> Option<SystemTime> {
+    const UNIX_EPOCH_DAYS: f64 = 25569.0;
+
+    if date == 0.0 || !date.is_finite() {
+        return None;
+    }
+
+    let seconds = (date - UNIX_EPOCH_DAYS) * 86400.0;
+
+    if seconds >= 0.0 {
+        SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs_f64(seconds))
+    } else {
+        SystemTime::UNIX_EPOCH.checked_sub(Duration::from_secs_f64(-seconds))
+    }
+}
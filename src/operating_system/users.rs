@@ -11,12 +11,13 @@
 //! | [**Win32\_NetworkLoginProfile**](win32-networkloginprofile)       | Instance class<br/> Represents the network login information of a specific user on a computer system running Windows.<br/>           |
 //! | [**Win32\_SystemAccount**](win32-systemaccount)                   | Instance class<br/> Represents a system account.<br/>                                                                                |
 //! | [**Win32\_UserAccount**](win32-useraccount)                       | Instance class<br/> Represents information about a user account on a computer system running Windows.<br/>                           |
+//! | [**Win32\_UserProfile**](/previous-versions/windows/desktop/legacy/ee886409(v=vs.85))            | Instance class<br/> Represents a user profile stored on the computer.<br/>                                                            |
 //! | [**Win32\_UserInDomain**](/previous-versions/windows/desktop/cimwin32a/win32-userindomain)                     | Association class<br/> Relates a user account and a Windows NT domain.<br/>                                                          |
 
-use crate::update;
+use crate::{associates, keyed, referenced, state_member, update, Unavailable, DEFAULT_NAMESPACE};
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
-use wmi::{COMLibrary, WMIConnection, WMIDateTime};
+use wmi::{COMLibrary, WMIConnection, WMIDateTime, WMIResult};
 
 /// Represents the state of Windows User Accounts
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -30,9 +31,12 @@ pub struct UserAccounts {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(UserAccounts, user_accounts);
+keyed!(UserAccounts, user_accounts, Win32_UserAccount, SID: String);
 
 /// Represents the state of Windows user accounts and group accounts
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -46,6 +50,8 @@ pub struct Accounts {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Accounts, accounts);
@@ -62,9 +68,12 @@ pub struct Groups {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(Groups, groups);
+keyed!(Groups, groups, Win32_Group, SID: String);
 
 /// Represents the state of Windows data about logon session or sessions associated with a user logged
 #[derive(Deserialize, Serialize, Debug, Clone, Hash)]
@@ -78,6 +87,8 @@ pub struct LogonSessions {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(LogonSessions, logon_sessions);
@@ -94,6 +105,8 @@ pub struct NetworkLoginProfiles {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(NetworkLoginProfiles, network_login_profiles);
@@ -110,10 +123,220 @@ pub struct SystemAccounts {
     /// - TRUE : The state changed since last UPDATE
     /// - FALSE : The state is the same as last UPDATE
     pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
 }
 
 update!(SystemAccounts, system_accounts);
 
+/// Represents the state of Windows group memberships
+///
+/// Lists every membership known to the computer, domain groups included, which is slow on a
+/// domain controller. [`LocalGroups`] only resolves the members of local groups.
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct GroupUsers {
+    /// Sequence of windows group memberships
+    pub group_users: Vec<Win32_GroupUser>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(GroupUsers, group_users);
+
+/// Represents the state of Windows user profiles
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct UserProfiles {
+    /// Sequence of windows user profiles
+    pub user_profiles: Vec<Win32_UserProfile>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(UserProfiles, user_profiles);
+keyed!(UserProfiles, user_profiles, Win32_UserProfile, SID: String);
+
+/// `SID` of the built-in Administrators group, the same whatever the display language
+pub const ADMINISTRATORS_SID: &str = "S-1-5-32-544";
+
+/// Represents the state of Windows local groups and their members
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct LocalGroups {
+    /// Sequence of local groups with their members
+    pub local_groups: Vec<LocalGroup>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+state_member!(LocalGroups, local_groups);
+
+/// A local group and the accounts that are direct members of it
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct LocalGroup {
+    /// The group
+    pub group: Win32_Group,
+    /// Users, groups and system accounts that are members of the group
+    pub members: Vec<GroupMember>,
+}
+
+impl LocalGroups {
+    /// Update fields synchronously
+    pub fn update(&mut self) {
+        let com_con = unsafe { COMLibrary::assume_initialized() };
+
+        self.last_updated = SystemTime::now();
+
+        let groups = crate::retry::policy().run(|| {
+            let wmi_con = WMIConnection::new(com_con)?;
+            let groups: Vec<Win32_Group> = wmi_con.raw_query(LOCAL_GROUPS_QUERY)?;
+
+            groups
+                .into_iter()
+                .map(|group| {
+                    let members = wmi_con.raw_query(members_query(&group))?;
+
+                    Ok(LocalGroup { group, members })
+                })
+                .collect()
+        });
+
+        self.refresh(groups);
+    }
+
+    /// Update fields asynchronously
+    pub async fn async_update(&mut self) {
+        let com_con = unsafe { COMLibrary::assume_initialized() };
+
+        self.last_updated = SystemTime::now();
+
+        let groups = crate::retry::policy()
+            .run_async(|| async move {
+                let wmi_con = WMIConnection::new(com_con)?;
+                let groups: Vec<Win32_Group> = wmi_con.async_raw_query(LOCAL_GROUPS_QUERY).await?;
+                let mut local_groups = Vec::with_capacity(groups.len());
+
+                for group in groups {
+                    let members = wmi_con.async_raw_query(members_query(&group)).await?;
+
+                    local_groups.push(LocalGroup { group, members });
+                }
+
+                Ok(local_groups)
+            })
+            .await;
+
+        self.refresh(groups);
+    }
+
+    fn refresh(&mut self, groups: WMIResult<Vec<LocalGroup>>) {
+        let old_vec = std::mem::take(&mut self.local_groups);
+        let old_unavailable = self.unavailable.take();
+
+        match groups {
+            Ok(groups) => self.local_groups = groups,
+            Err(e) => {
+                self.unavailable = Some(Unavailable::new(DEFAULT_NAMESPACE, "Win32_GroupUser", &e))
+            }
+        }
+
+        self.state_change = old_unavailable != self.unavailable
+            || self.local_groups.len() != old_vec.len()
+            || crate::hash_vec(&self.local_groups) != crate::hash_vec(&old_vec);
+    }
+
+    /// Members of the local group `name`, compared case-insensitively
+    ///
+    /// Group names are localized, [`LocalGroups::administrators`] finds the Administrators group
+    /// by its `SID` instead.
+    pub fn members_of(&self, name: &str) -> Vec<&GroupMember> {
+        self.local_groups
+            .iter()
+            .filter(|local_group| {
+                local_group
+                    .group
+                    .Name
+                    .as_deref()
+                    .is_some_and(|group_name| group_name.eq_ignore_ascii_case(name))
+            })
+            .flat_map(|local_group| &local_group.members)
+            .collect()
+    }
+
+    /// Members of the built-in Administrators group
+    pub fn administrators(&self) -> Vec<&GroupMember> {
+        self.local_groups
+            .iter()
+            .filter(|local_group| local_group.group.SID.as_deref() == Some(ADMINISTRATORS_SID))
+            .flat_map(|local_group| &local_group.members)
+            .collect()
+    }
+
+    /// Local groups the account `sid` is a direct member of
+    pub fn groups_of(&self, sid: &str) -> Vec<&Win32_Group> {
+        self.local_groups
+            .iter()
+            .filter(|local_group| {
+                local_group
+                    .members
+                    .iter()
+                    .any(|member| member.SID.as_deref() == Some(sid))
+            })
+            .map(|local_group| &local_group.group)
+            .collect()
+    }
+}
+
+impl Default for LocalGroups {
+    /// `last_updated` defaults to the the current `SystemTime`
+    fn default() -> Self {
+        LocalGroups {
+            local_groups: Vec::new(),
+            last_updated: SystemTime::now(),
+            state_change: false,
+            unavailable: None,
+        }
+    }
+}
+
+const LOCAL_GROUPS_QUERY: &str = "SELECT * FROM Win32_Group WHERE LocalAccount = TRUE";
+
+/// Accounts related to `group` through `Win32_GroupUser`
+fn members_query(group: &Win32_Group) -> String {
+    let key = |value: &Option<String>| {
+        value
+            .as_deref()
+            .unwrap_or_default()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+    };
+
+    format!(
+        "ASSOCIATORS OF {{Win32_Group.Domain=\"{}\",Name=\"{}\"}} WHERE AssocClass = Win32_GroupUser Role = GroupComponent",
+        key(&group.Domain),
+        key(&group.Name)
+    )
+}
+
 /// The `Win32_UserAccount` WMI class contains information about a user account on a computer system
 /// running Windows.
 ///
@@ -220,6 +443,8 @@ pub struct Win32_UserAccount {
     pub Status: Option<String>,
 }
 
+referenced!(Win32_UserAccount { Domain, Name });
+
 /// The `Win32_Account` abstract WMI class contains information about user accounts and group accounts
 /// known to the computer system running Windows.
 /// User or group names recognized by a Windows domain are descendants (or members) of this class.
@@ -375,6 +600,8 @@ pub struct Win32_Group {
     pub Name: Option<String>,
 }
 
+referenced!(Win32_Group { Domain, Name });
+
 /// The `Win32_LogonSession` WMI class
 /// describes the logon session or sessions associated with a user
 /// logged on to a computer system running Windows.
@@ -735,3 +962,153 @@ pub struct Win32_SystemAccount {
     /// Name of the Windows system account on the domain specified by the Domain property of this class.
     pub Name: Option<String>,
 }
+
+referenced!(Win32_SystemAccount { Domain, Name });
+
+/// An account that is a member of a group: a user, a group or a system account
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct GroupMember {
+    /// Class of the account: `Win32_UserAccount`, `Win32_Group` or `Win32_SystemAccount`.
+    #[serde(rename = "__CLASS")]
+    pub Class: Option<String>,
+    /// A short textual description of the account, `DOMAIN\Name`.
+    pub Caption: Option<String>,
+    /// Name of the Windows domain to which the account belongs, the computer name for local
+    /// accounts.
+    pub Domain: Option<String>,
+    /// If TRUE, the account is defined on the local machine.
+    pub LocalAccount: Option<bool>,
+    /// Name of the account.
+    pub Name: Option<String>,
+    /// Security identifier (SID) for this account.
+    pub SID: Option<String>,
+    /// Enumerated values that specify the type of security identifier (SID).
+    ///
+    /// - SidTypeUser (1)
+    /// - SidTypeGroup (2)
+    /// - SidTypeDomain (3)
+    /// - SidTypeAlias (4)
+    /// - SidTypeWellKnownGroup (5)
+    /// - SidTypeDeletedAccount (6)
+    /// - SidTypeInvalid (7)
+    /// - SidTypeUnknown (8)
+    /// - SidTypeComputer (9)
+    pub SIDType: Option<u8>,
+}
+
+/// The `Win32_GroupUser` association WMI class relates a group and an account that is a member of
+/// that group.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-groupuser>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_GroupUser {
+    /// Reference to the group, e.g.
+    /// `\\HOST\root\cimv2:Win32_Group.Domain="HOST",Name="Administrators"`.
+    pub GroupComponent: Option<String>,
+    /// Reference to the member account, e.g.
+    /// `\\HOST\root\cimv2:Win32_UserAccount.Domain="HOST",Name="Administrator"`.
+    pub PartComponent: Option<String>,
+}
+
+associates!(Win32_GroupUser { GroupComponent => PartComponent });
+
+impl Win32_GroupUser {
+    /// The group, parsed from `GroupComponent`
+    pub fn group(&self) -> Option<AccountReference> {
+        AccountReference::parse(self.GroupComponent.as_deref()?)
+    }
+
+    /// The member, parsed from `PartComponent`
+    pub fn member(&self) -> Option<AccountReference> {
+        AccountReference::parse(self.PartComponent.as_deref()?)
+    }
+}
+
+/// Account named by a WMI object path
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct AccountReference {
+    /// Class of the account, e.g. `Win32_UserAccount`
+    pub class: String,
+    /// Domain of the account
+    pub domain: String,
+    /// Name of the account
+    pub name: String,
+}
+
+impl AccountReference {
+    /// Parses a path such as `\\HOST\root\cimv2:Win32_Group.Domain="HOST",Name="Users"`
+    pub fn parse(path: &str) -> Option<Self> {
+        let relative = path.rsplit_once(':').map_or(path, |(_, relative)| relative);
+        let (class, keys) = relative.split_once('.')?;
+
+        let key = |name: &str| -> Option<String> {
+            let (_, rest) = keys.split_once(&format!("{name}=\""))?;
+            let mut value = String::new();
+            let mut chars = rest.chars();
+
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '"' => return Some(value),
+                    c => value.push(c),
+                }
+            }
+
+            None
+        };
+
+        Some(AccountReference {
+            class: class.to_string(),
+            domain: key("Domain")?,
+            name: key("Name")?,
+        })
+    }
+}
+
+/// The `Win32_UserProfile` WMI class represents a user profile stored on the computer.
+///
+/// <https://learn.microsoft.com/en-us/previous-versions/windows/desktop/legacy/ee886409(v=vs.85)>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Win32_UserProfile {
+    /// Health of the profile.
+    ///
+    /// - `Healthy` (0)
+    /// - `Unhealthy` (1)
+    /// - `Unknown` (3)
+    pub HealthStatus: Option<u8>,
+    /// When the profile was last downloaded from its roaming location.
+    pub LastDownloadTime: Option<WMIDateTime>,
+    /// When the profile was last uploaded to its roaming location.
+    pub LastUploadTime: Option<WMIDateTime>,
+    /// When the profile was last used.
+    pub LastUseTime: Option<WMIDateTime>,
+    /// Whether the profile is loaded, i.e. its owner is logged on or a process runs as them.
+    pub Loaded: Option<bool>,
+    /// Local path of the profile, e.g. "C:\Users\alice".
+    pub LocalPath: Option<String>,
+    /// Number of handles held on the profile.
+    pub RefCount: Option<u32>,
+    /// Whether the profile is configured to roam.
+    pub RoamingConfigured: Option<bool>,
+    /// Roaming path of the profile.
+    pub RoamingPath: Option<String>,
+    /// Whether the roaming profile is preferred over the local one.
+    pub RoamingPreference: Option<bool>,
+    /// Security identifier (SID) of the owner of the profile.
+    pub SID: Option<String>,
+    /// Whether the profile belongs to a special system account such as LocalSystem.
+    pub Special: Option<bool>,
+    /// Flags of the profile.
+    ///
+    /// - `Temporary` (1)
+    /// - `Roaming` (2)
+    /// - `Mandatory` (4)
+    /// - `Corrupted` (8)
+    pub Status: Option<u32>,
+}
@@ -0,0 +1,324 @@
+//! Hyper-V guests, from the `root\virtualization\v2` namespace, available with the `hyperv`
+//! feature.
+//!
+//! | Class                                                                                              | Description                                                                                   |
+//! |----------------------------------------------------------------------------------------------------|-----------------------------------------------------------------------------------------------|
+//! | [**Msvm\_ComputerSystem**](https://learn.microsoft.com/en-us/windows/win32/hyperv_v2/msvm-computersystem)           | Instance class<br/> Represents a virtual machine, or the host running them.<br/> |
+//! | [**Msvm\_ProcessorSettingData**](https://learn.microsoft.com/en-us/windows/win32/hyperv_v2/msvm-processorsettingdata) | Instance class<br/> Represents the virtual processors assigned to a virtual machine.<br/> |
+//! | [**Msvm\_MemorySettingData**](https://learn.microsoft.com/en-us/windows/win32/hyperv_v2/msvm-memorysettingdata)     | Instance class<br/> Represents the memory assigned to a virtual machine.<br/>              |
+//!
+//! The namespace only exists when the Hyper-V role is installed, elsewhere the states record it as
+//! [`Unavailable`](crate::Unavailable). Querying it requires membership of the local
+//! Administrators or Hyper-V Administrators group.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.virtual_machines.update();
+//! k.virtual_processor_settings.update();
+//! k.virtual_memory_settings.update();
+//!
+//! for (vm, resources) in k
+//!     .virtual_machines
+//!     .with_resources(&k.virtual_processor_settings, &k.virtual_memory_settings)
+//! {
+//!     println!("{:?} {:?} {:?}", vm.ElementName, vm.state(), resources.processors);
+//! }
+//! ```
+
+use crate::codes::VirtualMachineState;
+use crate::{coded, keyed, update, VIRTUALIZATION_NAMESPACE};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use wmi::{COMLibrary, WMIConnection, WMIDateTime};
+
+/// `Caption` of the `Msvm_ComputerSystem` instances that are virtual machines rather than the host
+const VIRTUAL_MACHINE_CAPTION: &str = "Virtual Machine";
+
+/// Represents the state of Hyper-V virtual machines
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct VirtualMachines {
+    /// Sequence of Hyper-V computer systems, the host included
+    pub virtual_machines: Vec<Msvm_ComputerSystem>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(VirtualMachines, virtual_machines, VIRTUALIZATION_NAMESPACE);
+keyed!(VirtualMachines, virtual_machines, Msvm_ComputerSystem, Name: String);
+
+impl VirtualMachines {
+    /// Virtual machines, leaving out the host
+    pub fn guests(&self) -> Vec<&Msvm_ComputerSystem> {
+        self.virtual_machines
+            .iter()
+            .filter(|system| system.is_guest())
+            .collect()
+    }
+
+    /// Virtual machines that are running
+    pub fn running(&self) -> Vec<&Msvm_ComputerSystem> {
+        self.virtual_machines
+            .iter()
+            .filter(|system| system.is_guest() && system.state() == Some(VirtualMachineState::Running))
+            .collect()
+    }
+
+    /// Pairs every virtual machine with the resources assigned to it
+    pub fn with_resources<'a>(
+        &'a self,
+        processors: &'a VirtualProcessorSettings,
+        memory: &'a VirtualMemorySettings,
+    ) -> Vec<(&'a Msvm_ComputerSystem, VirtualMachineResources<'a>)> {
+        self.guests()
+            .into_iter()
+            .map(|vm| {
+                let resources = VirtualMachineResources {
+                    processors: processors
+                        .virtual_processor_settings
+                        .iter()
+                        .find(|setting| belongs_to(&setting.InstanceID, vm)),
+                    memory: memory
+                        .virtual_memory_settings
+                        .iter()
+                        .find(|setting| belongs_to(&setting.InstanceID, vm)),
+                };
+
+                (vm, resources)
+            })
+            .collect()
+    }
+}
+
+/// Represents the state of the virtual processors assigned to Hyper-V virtual machines
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct VirtualProcessorSettings {
+    /// Sequence of processor settings, checkpoints included
+    pub virtual_processor_settings: Vec<Msvm_ProcessorSettingData>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(VirtualProcessorSettings, virtual_processor_settings, VIRTUALIZATION_NAMESPACE);
+
+/// Represents the state of the memory assigned to Hyper-V virtual machines
+#[derive(Deserialize, Serialize, Debug, Clone, Hash)]
+pub struct VirtualMemorySettings {
+    /// Sequence of memory settings, checkpoints included
+    pub virtual_memory_settings: Vec<Msvm_MemorySettingData>,
+    /// When was the record last updated
+    pub last_updated: SystemTime,
+    /// Signifies change in state
+    ///
+    /// - TRUE : The state changed since last UPDATE
+    /// - FALSE : The state is the same as last UPDATE
+    pub state_change: bool,
+    /// Why the class could not be queried, `None` when it was
+    pub unavailable: Option<crate::Unavailable>,
+}
+
+update!(VirtualMemorySettings, virtual_memory_settings, VIRTUALIZATION_NAMESPACE);
+
+/// Resources assigned to a virtual machine by its current configuration
+#[derive(Serialize, Debug, Clone, Copy)]
+pub struct VirtualMachineResources<'a> {
+    /// Virtual processors, `None` if the setting was not found
+    pub processors: Option<&'a Msvm_ProcessorSettingData>,
+    /// Memory, `None` if the setting was not found
+    pub memory: Option<&'a Msvm_MemorySettingData>,
+}
+
+/// Settings of the current configuration of a virtual machine have an `InstanceID` of the form
+/// `Microsoft:<Name>\...`, checkpoints use the identifier of the checkpoint instead
+fn belongs_to(instance_id: &Option<String>, vm: &Msvm_ComputerSystem) -> bool {
+    let (Some(instance_id), Some(name)) = (instance_id, &vm.Name) else {
+        return false;
+    };
+
+    instance_id
+        .strip_prefix("Microsoft:")
+        .and_then(|rest| rest.split('\\').next())
+        .is_some_and(|id| id.eq_ignore_ascii_case(name))
+}
+
+/// The `Msvm_ComputerSystem` WMI class represents a virtual machine, or the host running them.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/hyperv_v2/msvm-computersystem>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Msvm_ComputerSystem {
+    /// `Virtual Machine` for virtual machines, `Hosting Computer System` for the host.
+    pub Caption: Option<String>,
+    /// Description of the system.
+    pub Description: Option<String>,
+    /// Friendly name of the virtual machine, as shown in Hyper-V Manager.
+    pub ElementName: Option<String>,
+    /// Identifier of the virtual machine, a GUID, or the computer name of the host.
+    pub Name: Option<String>,
+    /// When the virtual machine was created.
+    pub InstallDate: Option<WMIDateTime>,
+    /// State of the virtual machine.
+    ///
+    /// - `Unknown` (0)
+    /// - `Other` (1)
+    /// - `Enabled` (2): running
+    /// - `Disabled` (3): off
+    /// - `Shutting Down` (4)
+    /// - `Not Applicable` (5)
+    /// - `Enabled but Offline` (6): saved
+    /// - `In Test` (7)
+    /// - `Deferred` (8)
+    /// - `Quiesce` (9): paused
+    /// - `Starting` (10)
+    pub EnabledState: Option<u16>,
+    /// State last requested of the virtual machine, using the `EnabledState` codes.
+    pub RequestedState: Option<u16>,
+    /// When `EnabledState` last changed.
+    pub TimeOfLastStateChange: Option<WMIDateTime>,
+    /// Health of the virtual machine.
+    ///
+    /// - `OK` (5)
+    /// - `Major Failure` (20)
+    /// - `Critical Failure` (25)
+    pub HealthState: Option<u16>,
+    /// Current statuses of the virtual machine, e.g. `OK` (2) followed by an operation in progress.
+    pub OperationalStatus: Option<Vec<u16>>,
+    /// Descriptions matching `OperationalStatus`.
+    pub StatusDescriptions: Option<Vec<String>>,
+    /// Milliseconds the virtual machine has been running since it last started.
+    pub OnTimeInMilliseconds: Option<u64>,
+    /// Identifier of the worker process (`vmwp.exe`) of the virtual machine, while it runs.
+    pub ProcessID: Option<u32>,
+    /// When the configuration of the virtual machine last changed.
+    pub TimeOfLastConfigurationChange: Option<WMIDateTime>,
+    /// Number of NUMA nodes of the virtual machine.
+    pub NumberOfNumaNodes: Option<u16>,
+    /// State of Hyper-V Replica for the virtual machine.
+    ///
+    /// - `Disabled` (0)
+    /// - `Ready for replication` (1)
+    /// - `Waiting to complete initial replication` (2)
+    /// - `Replicating` (3)
+    /// - `Synced replication complete` (4)
+    /// - `Recovered` (5)
+    /// - `Committed` (6)
+    /// - `Suspended` (7)
+    /// - `Critical` (8)
+    /// - `Waiting to start resynchronization` (9)
+    /// - `Resynchronizing` (10)
+    /// - `Resynchronization suspended` (11)
+    /// - `Failover in progress` (12)
+    /// - `Failback in progress` (13)
+    /// - `Failback complete` (14)
+    pub ReplicationState: Option<u16>,
+    /// Health of Hyper-V Replica for the virtual machine.
+    ///
+    /// - `Not applicable` (0)
+    /// - `Ok` (1)
+    /// - `Warning` (2)
+    /// - `Critical` (3)
+    pub ReplicationHealth: Option<u16>,
+    /// Replication role of the virtual machine.
+    ///
+    /// - `None` (0)
+    /// - `Primary` (1)
+    /// - `Recovery` (2)
+    /// - `Replica` (3)
+    /// - `Extended Replica` (4)
+    pub ReplicationMode: Option<u16>,
+    /// When the virtual machine was last backed up.
+    pub LastSuccessfulBackupTime: Option<WMIDateTime>,
+    /// Whether enhanced session mode is available.
+    ///
+    /// - `Allowed and available` (2)
+    /// - `Not allowed` (3)
+    /// - `Allowed but not available` (6)
+    pub EnhancedSessionModeState: Option<u16>,
+}
+
+coded!(Msvm_ComputerSystem {
+    EnabledState => state: VirtualMachineState,
+});
+
+impl Msvm_ComputerSystem {
+    /// Returns `true` for a virtual machine, `false` for the host
+    pub fn is_guest(&self) -> bool {
+        self.Caption.as_deref() == Some(VIRTUAL_MACHINE_CAPTION)
+    }
+}
+
+/// The `Msvm_ProcessorSettingData` WMI class represents the virtual processors assigned to a
+/// virtual machine.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/hyperv_v2/msvm-processorsettingdata>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Msvm_ProcessorSettingData {
+    /// Identifier of the setting, `Microsoft:<virtual machine or checkpoint id>\...`.
+    pub InstanceID: Option<String>,
+    /// Name of the setting.
+    pub ElementName: Option<String>,
+    /// Number of virtual processors.
+    pub VirtualQuantity: Option<u64>,
+    /// Share of the host processors reserved for the virtual machine, in thousandths of a percent.
+    pub Reservation: Option<u64>,
+    /// Largest share of the host processors the virtual machine may use, in thousandths of a
+    /// percent.
+    pub Limit: Option<u64>,
+    /// Relative weight of the virtual machine when processors are contended, from 0 to 10000.
+    pub Weight: Option<u32>,
+    /// Whether processor features are limited to run older operating systems.
+    pub LimitCPUID: Option<bool>,
+    /// Whether processor features are limited to allow migration to other hosts.
+    pub LimitProcessorFeatures: Option<bool>,
+    /// Number of hardware threads per core exposed to the virtual machine, 0 follows the host.
+    pub HwThreadsPerCore: Option<u64>,
+    /// Whether the virtual processors expose virtualization extensions, for nested virtualization.
+    pub ExposeVirtualizationExtensions: Option<bool>,
+}
+
+/// The `Msvm_MemorySettingData` WMI class represents the memory assigned to a virtual machine.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/hyperv_v2/msvm-memorysettingdata>
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
+#[allow(non_snake_case)]
+#[allow(non_camel_case_types)]
+pub struct Msvm_MemorySettingData {
+    /// Identifier of the setting, `Microsoft:<virtual machine or checkpoint id>\...`.
+    pub InstanceID: Option<String>,
+    /// Name of the setting.
+    pub ElementName: Option<String>,
+    /// Memory assigned at startup, in MB.
+    pub VirtualQuantity: Option<u64>,
+    /// Smallest amount of memory with dynamic memory, in MB.
+    pub Reservation: Option<u64>,
+    /// Largest amount of memory with dynamic memory, in MB.
+    pub Limit: Option<u64>,
+    /// Relative weight of the virtual machine when memory is contended, from 0 to 10000.
+    pub Weight: Option<u32>,
+    /// Whether dynamic memory is enabled.
+    pub DynamicMemoryEnabled: Option<bool>,
+    /// Percentage of memory kept as a buffer above the current demand with dynamic memory.
+    pub TargetMemoryBuffer: Option<u32>,
+}
@@ -0,0 +1,444 @@
+//! Stable paths addressing the members, instances and properties of snapshots.
+//!
+//! Rule engines, such as compliance-as-code checks, address snapshot values by strings instead of
+//! compiled field accesses. A [`SnapshotPath`] reads:
+//!
+//! ```text
+//! os.services.services[Name=wuauserv].State
+//! ^^ ^^^^^^^^ ^^^^^^^^^^^^^^^^^^^^^^^ ^^^^^
+//! |  |        |                       property of the selected instances
+//! |  |        field of the member, selecting the service named wuauserv
+//! |  member, named as in `StateKind::name`
+//! category: `os`, `hw` or `custom` for the members registered with `Windows::register_custom`
+//! ```
+//!
+//! Segments after the member are the field names of the serialized snapshot, so paths stay valid
+//! as long as the [`SCHEMA_VERSION`](crate::state::SCHEMA_VERSION) does. A field holding instances
+//! is followed by a selector:
+//!
+//! | Selector          | Selects                                                               |
+//! |-------------------|-----------------------------------------------------------------------|
+//! | `[*]`             | every instance                                                        |
+//! | `[3]`             | the instance at index 3                                               |
+//! | `[Name=wuauserv]` | the instances whose property equals the value, strings ignoring case |
+//!
+//! `Windows::paths` lists the paths of every property a snapshot holds, with `[*]` selectors.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::path::SnapshotPath;
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.services.update();
+//!
+//! let path: SnapshotPath = "os.services.services[Name=wuauserv].State".parse().unwrap();
+//!
+//! for state in path.lookup(&k).unwrap() {
+//!     println!("Windows Update is {state}");
+//! }
+//! ```
+
+use crate::state::{StateKind, Windows};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Category of the members registered with `Windows::register_custom`
+pub const CUSTOM_CATEGORY: &str = "custom";
+
+/// Path addressing a member of a snapshot, or the instances and properties it holds, see the
+/// [module documentation](self)
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct SnapshotPath {
+    /// `os`, `hw` or [`CUSTOM_CATEGORY`]
+    pub category: String,
+    /// Name of the member, e.g. `services`
+    pub member: String,
+    /// Fields and selectors following the member
+    pub steps: Vec<PathStep>,
+}
+
+/// A segment of a [`SnapshotPath`] following the member
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum PathStep {
+    /// A field, e.g. `State`
+    Field(String),
+    /// `[*]`, every instance
+    All,
+    /// `[3]`, the instance at an index
+    Index(usize),
+    /// `[Name=wuauserv]`, the instances whose property equals a value
+    Matching {
+        /// Name of the property
+        property: String,
+        /// Value the property equals
+        value: String,
+    },
+}
+
+/// A path that could not be parsed or looked up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathError {
+    /// The path
+    pub path: String,
+    /// Why it was rejected
+    pub reason: String,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.path, self.reason)
+    }
+}
+
+impl std::error::Error for PathError {}
+
+impl SnapshotPath {
+    /// Path of the member `kind` of `windows`
+    pub fn of_member(windows: &Windows, kind: StateKind) -> Self {
+        SnapshotPath {
+            category: windows.member(kind).category().to_string(),
+            member: kind.name().to_string(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// The path followed by the field `name`
+    pub fn field(mut self, name: &str) -> Self {
+        self.steps.push(PathStep::Field(name.to_string()));
+        self
+    }
+
+    /// The path followed by `selector`, one of [`PathStep::All`], [`PathStep::Index`] or
+    /// [`PathStep::Matching`]
+    pub fn select(mut self, selector: PathStep) -> Self {
+        self.steps.push(selector);
+        self
+    }
+
+    /// Values the path addresses in `windows`, as serialized, one per selected instance
+    ///
+    /// Instances without the addressed property, or where it is `null`, are skipped. Fails if the
+    /// category or the member do not exist, or if a step does not fit the value it applies to,
+    /// e.g. a selector after a property that holds no instances.
+    pub fn lookup(&self, windows: &Windows) -> Result<Vec<Value>, PathError> {
+        let member = self.member_value(windows)?;
+        let mut values = vec![member];
+
+        for step in &self.steps {
+            values = values
+                .into_iter()
+                .map(|value| self.apply(step, value))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .flatten()
+                .collect();
+        }
+
+        Ok(values)
+    }
+
+    /// The first value the path addresses in `windows`, see [`SnapshotPath::lookup`]
+    pub fn lookup_one(&self, windows: &Windows) -> Result<Option<Value>, PathError> {
+        Ok(self.lookup(windows)?.into_iter().next())
+    }
+
+    /// The serialized member the path starts from
+    fn member_value(&self, windows: &Windows) -> Result<Value, PathError> {
+        if self.category == CUSTOM_CATEGORY {
+            let custom = windows
+                .custom
+                .get(&self.member)
+                .ok_or_else(|| self.error(format!("no custom member `{}`", self.member)))?;
+
+            return serde_json::to_value(custom).map_err(|e| self.error(e.to_string()));
+        }
+
+        let kind = StateKind::from_name(&self.member)
+            .ok_or_else(|| self.error(format!("unknown or disabled member `{}`", self.member)))?;
+        let member = windows.member(kind);
+
+        if member.category() != self.category {
+            return Err(self.error(format!(
+                "`{}` is in the category `{}`",
+                self.member,
+                member.category()
+            )));
+        }
+
+        Ok(member.to_json())
+    }
+
+    /// Values `step` addresses in `value`
+    fn apply(&self, step: &PathStep, value: Value) -> Result<Vec<Value>, PathError> {
+        match (step, value) {
+            (PathStep::Field(name), Value::Object(mut fields)) => Ok(fields.remove(name).into_iter().collect()),
+            (PathStep::All, Value::Array(instances)) => Ok(instances),
+            (PathStep::Index(index), Value::Array(mut instances)) => Ok(match *index < instances.len() {
+                true => vec![instances.swap_remove(*index)],
+                false => Vec::new(),
+            }),
+            (PathStep::Matching { property, value }, Value::Array(instances)) => Ok(instances
+                .into_iter()
+                .filter(|instance| instance.get(property).is_some_and(|found| equals(found, value)))
+                .collect()),
+            (_, Value::Null) => Ok(Vec::new()),
+            (PathStep::Field(name), _) => Err(self.error(format!("`{name}` follows a value without fields"))),
+            (selector, _) => Err(self.error(format!("`{selector}` follows a value without instances"))),
+        }
+    }
+
+    fn error(&self, reason: String) -> PathError {
+        PathError {
+            path: self.to_string(),
+            reason,
+        }
+    }
+}
+
+/// Returns `true` if the serialized `found` reads as `expected`, strings ignoring case like WMI
+/// does
+fn equals(found: &Value, expected: &str) -> bool {
+    match found {
+        Value::String(found) => found.eq_ignore_ascii_case(expected),
+        Value::Null => false,
+        found => serde_json::from_str::<Value>(expected).is_ok_and(|expected| expected == *found),
+    }
+}
+
+impl fmt::Display for SnapshotPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.category, self.member)?;
+
+        for step in &self.steps {
+            match step {
+                PathStep::Field(name) => write!(f, ".{name}")?,
+                selector => write!(f, "{selector}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for PathStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathStep::Field(name) => f.write_str(name),
+            PathStep::All => f.write_str("[*]"),
+            PathStep::Index(index) => write!(f, "[{index}]"),
+            PathStep::Matching { property, value } => write!(f, "[{property}={value}]"),
+        }
+    }
+}
+
+impl FromStr for SnapshotPath {
+    type Err = PathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let error = |reason: String| PathError {
+            path: path.to_string(),
+            reason,
+        };
+        let mut segments = Vec::new();
+        let mut rest = path.trim();
+
+        loop {
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            let (name, after) = rest.split_at(end);
+
+            if name.is_empty() {
+                return Err(error("empty segment".to_string()));
+            }
+
+            segments.push(PathStep::Field(name.to_string()));
+            rest = after;
+
+            while let Some(selector) = rest.strip_prefix('[') {
+                let (selector, after) = selector
+                    .split_once(']')
+                    .ok_or_else(|| error("unclosed `[`".to_string()))?;
+
+                segments.push(parse_selector(selector).map_err(error)?);
+                rest = after;
+            }
+
+            match rest.strip_prefix('.') {
+                Some(after) => rest = after,
+                None if rest.is_empty() => break,
+                None => return Err(error(format!("expected `.` or `[` before `{rest}`"))),
+            }
+        }
+
+        let mut segments = segments.into_iter();
+        let (Some(PathStep::Field(category)), Some(PathStep::Field(member))) = (segments.next(), segments.next())
+        else {
+            return Err(error("expected `category.member`".to_string()));
+        };
+
+        Ok(SnapshotPath {
+            category,
+            member,
+            steps: segments.collect(),
+        })
+    }
+}
+
+/// Parses the inside of a selector: `*`, an index or `Property=value`
+fn parse_selector(selector: &str) -> Result<PathStep, String> {
+    let selector = selector.trim();
+
+    if selector == "*" {
+        return Ok(PathStep::All);
+    }
+
+    if let Ok(index) = selector.parse() {
+        return Ok(PathStep::Index(index));
+    }
+
+    match selector.split_once('=') {
+        Some((property, value)) if !property.trim().is_empty() => Ok(PathStep::Matching {
+            property: property.trim().to_string(),
+            value: value.to_string(),
+        }),
+        _ => Err(format!("expected `*`, an index or `Property=value`, found `[{selector}]`")),
+    }
+}
+
+/// Paths of every property `windows` holds, with `[*]` selectors, sorted
+///
+/// Properties are listed from the instances the members hold, so those of empty members are
+/// missing.
+pub fn paths(windows: &Windows) -> Vec<String> {
+    let mut paths = BTreeSet::new();
+
+    for &kind in StateKind::ALL {
+        let path = SnapshotPath::of_member(windows, kind).to_string();
+
+        collect(&windows.member(kind).to_json(), &path, &mut paths);
+    }
+
+    for (name, custom) in &windows.custom {
+        let path = format!("{CUSTOM_CATEGORY}.{name}");
+
+        collect(&serde_json::to_value(custom).unwrap_or_default(), &path, &mut paths);
+    }
+
+    paths.into_iter().collect()
+}
+
+/// Adds `path` and the paths of the properties `value` holds to `paths`
+fn collect(value: &Value, path: &str, paths: &mut BTreeSet<String>) {
+    paths.insert(path.to_string());
+
+    match value {
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                collect(value, &format!("{path}.{name}"), paths);
+            }
+        }
+        Value::Array(instances) if instances.iter().any(Value::is_object) => {
+            for instance in instances {
+                collect(instance, &format!("{path}[*]"), paths);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parsed(path: &str) -> SnapshotPath {
+        path.parse().unwrap()
+    }
+
+    fn rejected(path: &str) -> String {
+        path.parse::<SnapshotPath>().unwrap_err().reason
+    }
+
+    #[test]
+    fn parses_selectors() {
+        let path = parsed("os.services.services[Name=wuauserv].State");
+
+        assert_eq!(path.category, "os");
+        assert_eq!(path.member, "services");
+        assert_eq!(
+            path.steps,
+            vec![
+                PathStep::Field("services".to_string()),
+                PathStep::Matching {
+                    property: "Name".to_string(),
+                    value: "wuauserv".to_string(),
+                },
+                PathStep::Field("State".to_string()),
+            ]
+        );
+        assert_eq!(parsed("os.services.services[*]").steps[1], PathStep::All);
+        assert_eq!(parsed("os.services.services[3]").steps[1], PathStep::Index(3));
+        assert_eq!(parsed(" os.services.services[ Name=a=b ] ").steps[1], PathStep::Matching {
+            property: "Name".to_string(),
+            value: "a=b".to_string(),
+        });
+        assert!(parsed("os.services").steps.is_empty());
+
+        for path in ["os.services.services[Name=wuauserv].State", "os.services.services[*][0]"] {
+            assert_eq!(parsed(path).to_string(), path);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_paths() {
+        assert_eq!(rejected(""), "empty segment");
+        assert_eq!(rejected("os..services"), "empty segment");
+        assert_eq!(rejected("os.services."), "empty segment");
+        assert_eq!(rejected("os"), "expected `category.member`");
+        assert_eq!(rejected("os[*].services"), "expected `category.member`");
+        assert_eq!(rejected("os.services.services[0"), "unclosed `[`");
+        assert_eq!(rejected("os.services.services[0]State"), "expected `.` or `[` before `State`");
+        assert!(rejected("os.services.services[]").starts_with("expected `*`, an index"));
+        assert!(rejected("os.services.services[=wuauserv]").starts_with("expected `*`, an index"));
+        assert!(rejected("os.services.services[-1]").starts_with("expected `*`, an index"));
+    }
+
+    #[cfg(feature = "os-services")]
+    #[test]
+    fn looks_up_selected_instances() {
+        use crate::operating_system::services::Win32_Service;
+
+        let service = |name: &str, state: &str, process_id: u32| Win32_Service {
+            Name: Some(name.to_string()),
+            State: Some(state.to_string()),
+            ProcessId: Some(process_id),
+            ..Default::default()
+        };
+
+        let mut windows = Windows::default();
+        windows.services.services = vec![service("wuauserv", "Running", 1044), service("Spooler", "Stopped", 0)];
+
+        let lookup = |path: &str| parsed(path).lookup(&windows);
+
+        assert_eq!(lookup("os.services.services[Name=WUAUSERV].State").unwrap(), vec![json!("Running")]);
+        assert_eq!(lookup("os.services.services[ProcessId=0].Name").unwrap(), vec![json!("Spooler")]);
+        assert_eq!(lookup("os.services.services[*].Name").unwrap(), vec![json!("wuauserv"), json!("Spooler")]);
+        assert_eq!(lookup("os.services.services[1].State").unwrap(), vec![json!("Stopped")]);
+        assert!(lookup("os.services.services[2]").unwrap().is_empty());
+        assert!(lookup("os.services.services[Name=bits]").unwrap().is_empty());
+        assert!(lookup("os.services.services[*].Missing").unwrap().is_empty());
+        assert!(lookup("os.services.unavailable.reason").unwrap().is_empty());
+
+        assert!(lookup("os.no_such_member").is_err());
+        assert!(lookup("hw.services").unwrap_err().reason.contains("category `os`"));
+        assert!(lookup("custom.contoso").unwrap_err().reason.contains("no custom member"));
+        assert!(lookup("os.services.services.Name").unwrap_err().reason.contains("without fields"));
+        assert!(lookup("os.services.state_change[*]").unwrap_err().reason.contains("without instances"));
+    }
+}
@@ -0,0 +1,243 @@
+//! Redaction of personal and identifying properties before snapshots leave the machine.
+//!
+//! A [`Redactor`] walks the serialized form of any snapshot and strips or hashes the properties it
+//! is configured with, whatever struct holds them: `UserName` is redacted in processes, sessions
+//! and computer systems alike. [`Redactor::default`] covers user names, host names, domains,
+//! serial numbers and hardware addresses; [`Redactor::field`] adds more.
+//!
+//! Hashing keeps redacted values comparable across snapshots, e.g. to tell that two machines share
+//! a user, without revealing them. Hashes are salted with [`Redactor::with_salt`] so they cannot be
+//! reversed by hashing likely values; keep the salt secret and stable to keep hashes comparable.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::redact::Redactor;
+//! use windows_snapshot::snapshot::{save_snapshot, SnapshotOptions};
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.update();
+//!
+//! let redactor = Redactor::default().with_salt("per-vendor secret").field("Description");
+//! let k = redactor.redacted(&k).unwrap();
+//!
+//! save_snapshot(&k, "for-vendor.json", &SnapshotOptions::default()).unwrap();
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+
+/// Properties [`Redactor::default`] redacts
+pub const DEFAULT_FIELDS: &[&str] = &[
+    "UserName",
+    "user_name",
+    "User",
+    "RegisteredUser",
+    "Organization",
+    "PrimaryOwnerName",
+    "Owner",
+    "Domain",
+    "DNSDomain",
+    "CSName",
+    "DNSHostName",
+    "hostname",
+    "ComputerName",
+    "SystemName",
+    "SerialNumber",
+    "IdentifyingNumber",
+    "SMBIOSAssetTag",
+    "UUID",
+    "MACAddress",
+    "PermanentAddress",
+    "IPAddress",
+    "SID",
+];
+
+/// Length, in hexadecimal digits, of the hashes replacing redacted values
+const HASH_LENGTH: usize = 16;
+
+/// How a [`Redactor`] treats the values of the properties it redacts
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum RedactionMode {
+    /// Replace strings with a salted SHA-256 hash, other values with `null`
+    #[default]
+    Hash,
+    /// Replace values with `null`
+    Strip,
+}
+
+/// Redacts the configured properties of serialized snapshots
+///
+/// Properties are matched by name, ignoring case, at any depth. Values inside arrays, such as the
+/// addresses of `IPAddress`, are redacted one by one.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Redactor {
+    /// Names of the redacted properties, lowercase
+    fields: BTreeSet<String>,
+    /// How values are redacted
+    mode: RedactionMode,
+    /// Prefixed to values before hashing them
+    salt: String,
+}
+
+impl Default for Redactor {
+    /// Hashes the [`DEFAULT_FIELDS`], without a salt
+    fn default() -> Self {
+        DEFAULT_FIELDS
+            .iter()
+            .fold(Redactor::new(RedactionMode::default()), |redactor, field| redactor.field(field))
+    }
+}
+
+impl Redactor {
+    /// A redactor without properties, redacting values as `mode` says
+    pub fn new(mode: RedactionMode) -> Self {
+        Redactor {
+            fields: BTreeSet::new(),
+            mode,
+            salt: String::new(),
+        }
+    }
+
+    /// Redacts the property `name` too
+    pub fn field(mut self, name: &str) -> Self {
+        self.fields.insert(name.to_lowercase());
+        self
+    }
+
+    /// Stops redacting the property `name`
+    pub fn keep(mut self, name: &str) -> Self {
+        self.fields.remove(&name.to_lowercase());
+        self
+    }
+
+    /// Redacts values as `mode` says
+    pub fn mode(mut self, mode: RedactionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Salts the hashes with `salt`
+    pub fn with_salt(mut self, salt: &str) -> Self {
+        self.salt = salt.to_string();
+        self
+    }
+
+    /// Returns `true` if the property `name` is redacted
+    pub fn redacts(&self, name: &str) -> bool {
+        self.fields.contains(&name.to_lowercase())
+    }
+
+    /// A copy of `snapshot` with the properties redacted, still of its type so it can be saved or
+    /// exported like the original
+    ///
+    /// Fails if a redacted property cannot hold the redacted value, e.g. a property that is not an
+    /// `Option` when it is stripped, or a number when it is hashed.
+    pub fn redacted<T: Serialize + DeserializeOwned>(&self, snapshot: &T) -> Result<T, serde_json::Error> {
+        serde_json::from_value(self.to_value(snapshot)?)
+    }
+
+    /// The serialized `snapshot` with the properties redacted
+    pub fn to_value<T: Serialize>(&self, snapshot: &T) -> Result<Value, serde_json::Error> {
+        let mut value = serde_json::to_value(snapshot)?;
+        self.redact(&mut value);
+
+        Ok(value)
+    }
+
+    /// Redacts the properties of the serialized `value` in place, returning the number of values
+    /// redacted
+    pub fn redact(&self, value: &mut Value) -> usize {
+        match value {
+            Value::Object(properties) => properties
+                .iter_mut()
+                .map(|(name, value)| {
+                    if self.redacts(name) {
+                        self.redact_value(value)
+                    } else {
+                        self.redact(value)
+                    }
+                })
+                .sum(),
+            Value::Array(values) => values.iter_mut().map(|value| self.redact(value)).sum(),
+            _ => 0,
+        }
+    }
+
+    /// Redacts `value`, the value of a redacted property, returning the number of values redacted
+    fn redact_value(&self, value: &mut Value) -> usize {
+        match value {
+            Value::Null => 0,
+            Value::Array(values) => values.iter_mut().map(|value| self.redact_value(value)).sum(),
+            Value::String(text) if self.mode == RedactionMode::Hash => {
+                *text = self.hash(text);
+                1
+            }
+            _ => {
+                *value = Value::Null;
+                1
+            }
+        }
+    }
+
+    /// Salted hash of `text`, the first [`HASH_LENGTH`] hexadecimal digits of its SHA-256
+    fn hash(&self, text: &str) -> String {
+        let digest = Sha256::new()
+            .chain_update(self.salt.as_bytes())
+            .chain_update(text.as_bytes())
+            .finalize();
+
+        digest
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>()[..HASH_LENGTH]
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Windows;
+
+    #[test]
+    fn default_fields_cover_host_and_user_names() {
+        let redactor = Redactor::default().with_salt("salt");
+
+        let mut windows = Windows::default();
+        windows.meta.hostname = "WORKSTATION-7".to_string();
+        #[cfg(feature = "os-dns")]
+        windows.hosts_file.hosts_entries.push(crate::operating_system::dns::HostsEntry {
+            address: "10.0.0.7".to_string(),
+            hostname: "build.contoso.local".to_string(),
+        });
+
+        let redacted = redactor.redacted(&windows).unwrap();
+        assert_eq!(redacted.meta.hostname, redactor.hash("WORKSTATION-7"));
+        #[cfg(feature = "os-dns")]
+        {
+            let entry = &redacted.hosts_file.hosts_entries[0];
+            assert_eq!(entry.address, "10.0.0.7");
+            assert_eq!(entry.hostname, redactor.hash("build.contoso.local"));
+        }
+
+        #[cfg(feature = "os-desktop")]
+        {
+            let status = crate::operating_system::desktop::ScreenLockStatus {
+                user_name: Some("CONTOSO\\jsmith".to_string()),
+                active: true,
+                secure: true,
+                timeout: Some(600),
+                compliant: true,
+            };
+            let redacted = redactor.redacted(&status).unwrap();
+            assert_eq!(redacted.user_name, Some(redactor.hash("CONTOSO\\jsmith")));
+        }
+    }
+}
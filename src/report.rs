@@ -0,0 +1,632 @@
+//! Report-ready views of the snapshot.
+//!
+//! Every serializable struct gets a [`Normalize::normalized`] view: a flat row where missing
+//! (`None`) properties read as [`ReportValue::Unknown`] instead of an `Option` to unwrap. A
+//! sequence of instances becomes a [`Table`] with [`Table::from_instances`], which also drops the
+//! columns that are empty in every row, so a `Vec<Win32_*>` turns into the handful of columns the
+//! host actually fills in.
+//!
+//! The main states, and [`Windows`](crate::state::Windows) as a whole, also have a `summary` and
+//! a `Display` implementation rendering a [`Summary`]: counts and key identifiers in a few lines,
+//! instead of the full `{:#?}` dump.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::report::Table;
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = windows_snapshot::state::Windows::default();
+//! k.services.update();
+//!
+//! let table = Table::from_instances(&k.services.services);
+//! println!("{}", table.columns.join("\t"));
+//! for row in &table.rows {
+//!     println!("{}", row.iter().map(ToString::to_string).collect::<Vec<_>>().join("\t"));
+//! }
+//! ```
+
+use serde::ser::{self, Serialize};
+use std::fmt;
+
+/// Marker displayed for missing values
+pub const UNKNOWN: &str = "unknown";
+
+/// A property value with the `Option` collapsed
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ReportValue {
+    /// The property is missing (`None`) or empty
+    #[default]
+    Unknown,
+    /// Boolean value
+    Bool(bool),
+    /// Signed integer value
+    Signed(i64),
+    /// Unsigned integer value
+    Unsigned(u64),
+    /// Floating point value
+    Float(f64),
+    /// Text value
+    Text(String),
+    /// Sequence of values
+    List(Vec<ReportValue>),
+    /// Nested structure, as `(property, value)` pairs
+    Record(Vec<(String, ReportValue)>),
+}
+
+impl ReportValue {
+    /// Returns `true` for [`ReportValue::Unknown`], empty strings and empty sequences
+    pub fn is_unknown(&self) -> bool {
+        match self {
+            ReportValue::Unknown => true,
+            ReportValue::Text(text) => text.is_empty(),
+            ReportValue::List(values) => values.is_empty(),
+            ReportValue::Record(fields) => fields.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Returns the value if it is text
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ReportValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a non-negative integer
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            ReportValue::Unsigned(value) => Some(*value),
+            ReportValue::Signed(value) => u64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ReportValue {
+    /// Unknown values display as [`UNKNOWN`], sequences as comma separated values
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_unknown() {
+            return f.write_str(UNKNOWN);
+        }
+
+        match self {
+            ReportValue::Bool(value) => write!(f, "{value}"),
+            ReportValue::Signed(value) => write!(f, "{value}"),
+            ReportValue::Unsigned(value) => write!(f, "{value}"),
+            ReportValue::Float(value) => write!(f, "{value}"),
+            ReportValue::Text(text) => f.write_str(text),
+            ReportValue::List(values) => {
+                let values: Vec<String> = values.iter().map(ToString::to_string).collect();
+                f.write_str(&values.join(", "))
+            }
+            ReportValue::Record(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, value)| format!("{name}={value}"))
+                    .collect();
+                f.write_str(&fields.join(", "))
+            }
+            ReportValue::Unknown => unreachable!(),
+        }
+    }
+}
+
+/// A flattened instance, one `(property, value)` pair per field in declaration order
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Row {
+    /// Properties of the instance
+    pub fields: Vec<(String, ReportValue)>,
+}
+
+impl Row {
+    /// Returns the value of `property`, [`ReportValue::Unknown`] if the instance has no such field
+    pub fn get(&self, property: &str) -> &ReportValue {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == property)
+            .map(|(_, value)| value)
+            .unwrap_or(&ReportValue::Unknown)
+    }
+
+    /// Drops the properties that are unknown
+    pub fn without_unknown(mut self) -> Self {
+        self.fields.retain(|(_, value)| !value.is_unknown());
+        self
+    }
+}
+
+/// Rows sharing the same columns
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Table {
+    /// Names of the columns
+    pub columns: Vec<String>,
+    /// Rows, each holding one value per column
+    pub rows: Vec<Vec<ReportValue>>,
+}
+
+impl Table {
+    /// Normalizes `instances`, dropping the columns that are unknown in every row
+    ///
+    /// Columns are the fields of every row, in the order they are first met, so rows of
+    /// instances holding different fields line up.
+    pub fn from_instances<T: Serialize>(instances: &[T]) -> Self {
+        let rows: Vec<Row> = instances.iter().map(Normalize::normalized).collect();
+
+        let mut columns: Vec<String> = Vec::new();
+
+        for (name, _) in rows.iter().flat_map(|row| &row.fields) {
+            if !columns.contains(name) {
+                columns.push(name.clone());
+            }
+        }
+
+        columns.retain(|name| rows.iter().any(|row| !row.get(name).is_unknown()));
+
+        Table {
+            rows: rows
+                .iter()
+                .map(|row| columns.iter().map(|name| row.get(name).clone()).collect())
+                .collect(),
+            columns,
+        }
+    }
+}
+
+/// Short human-readable account of a state: a title, a few labelled lines and nested sections
+///
+/// Built by the `summary` methods of the states and of [`Windows`](crate::state::Windows), and
+/// displayed as an indented block, one `label: value` per line. Missing values read as
+/// [`UNKNOWN`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Summary {
+    /// What is summarized
+    pub title: String,
+    /// `(label, value)` pairs, in display order
+    pub lines: Vec<(String, String)>,
+    /// Nested summaries, displayed after the lines
+    pub sections: Vec<Summary>,
+}
+
+impl Summary {
+    /// An empty summary titled `title`
+    pub fn new(title: impl Into<String>) -> Self {
+        Summary {
+            title: title.into(),
+            ..Summary::default()
+        }
+    }
+
+    /// Adds the line `label: value`
+    pub fn line(mut self, label: impl Into<String>, value: impl fmt::Display) -> Self {
+        self.lines.push((label.into(), value.to_string()));
+        self
+    }
+
+    /// Adds the line `label: value`, [`UNKNOWN`] when `value` is `None`
+    pub fn optional_line<T: fmt::Display>(self, label: impl Into<String>, value: Option<T>) -> Self {
+        match value {
+            Some(value) => self.line(label, value),
+            None => self.line(label, UNKNOWN),
+        }
+    }
+
+    /// Adds `section` after the lines
+    pub fn section(mut self, section: Summary) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = "  ".repeat(depth);
+        let width = self.lines.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+
+        writeln!(f, "{indent}{}", self.title)?;
+
+        for (label, value) in &self.lines {
+            writeln!(f, "{indent}  {:width$}  {value}", format!("{label}:"), width = width + 1)?;
+        }
+
+        self.sections
+            .iter()
+            .try_for_each(|section| section.write(f, depth + 1))
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+/// Formats a size in bytes with a binary unit, e.g. `476.9 GiB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+/// Formats a duration down to the minute, e.g. `3d 4h 12m`
+pub fn format_duration(duration: std::time::Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / 1_440, minutes % 1_440 / 60, minutes % 60);
+
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h {minutes}m"),
+    }
+}
+
+/// Report-ready view of a serializable struct
+pub trait Normalize {
+    /// Flattens the struct into a [`Row`], missing properties become [`ReportValue::Unknown`]
+    fn normalized(&self) -> Row;
+}
+
+impl<T: Serialize> Normalize for T {
+    fn normalized(&self) -> Row {
+        match to_report_value(self) {
+            ReportValue::Record(fields) => Row { fields },
+            value => Row {
+                fields: vec![(String::from("value"), value)],
+            },
+        }
+    }
+}
+
+/// Converts any serializable value into a [`ReportValue`]
+pub fn to_report_value<T: Serialize + ?Sized>(value: &T) -> ReportValue {
+    value.serialize(ValueSerializer).unwrap_or_default()
+}
+
+/// Error raised by a `Serialize` implementation while building a [`ReportValue`]
+#[derive(Debug)]
+pub struct ReportError(String);
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+impl ser::Error for ReportError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        ReportError(msg.to_string())
+    }
+}
+
+/// Serializer producing a [`ReportValue`]
+struct ValueSerializer;
+
+/// Sequences, maps and structures being serialized
+struct Compound {
+    fields: Vec<(String, ReportValue)>,
+    next_key: Option<String>,
+    is_list: bool,
+}
+
+impl Compound {
+    fn list() -> Self {
+        Compound {
+            fields: Vec::new(),
+            next_key: None,
+            is_list: true,
+        }
+    }
+
+    fn record() -> Self {
+        Compound {
+            fields: Vec::new(),
+            next_key: None,
+            is_list: false,
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, key: String, value: &T) {
+        self.fields.push((key, to_report_value(value)));
+    }
+
+    fn finish(self) -> ReportValue {
+        if self.is_list {
+            ReportValue::List(self.fields.into_iter().map(|(_, value)| value).collect())
+        } else {
+            ReportValue::Record(self.fields)
+        }
+    }
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = ReportValue;
+    type Error = ReportError;
+    type SerializeSeq = Compound;
+    type SerializeTuple = Compound;
+    type SerializeTupleStruct = Compound;
+    type SerializeTupleVariant = Compound;
+    type SerializeMap = Compound;
+    type SerializeStruct = Compound;
+    type SerializeStructVariant = Compound;
+
+    fn serialize_bool(self, v: bool) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Signed(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Signed(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Signed(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Signed(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Unsigned(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Unsigned(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Unsigned(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Unsigned(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Float(v.into()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Text(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Text(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::List(
+            v.iter().map(|&byte| ReportValue::Unsigned(byte.into())).collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Unknown)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<ReportValue, ReportError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Unknown)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Unknown)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Text(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<ReportValue, ReportError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<ReportValue, ReportError> {
+        Ok(ReportValue::Record(vec![(
+            variant.to_string(),
+            to_report_value(value),
+        )]))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound, ReportError> {
+        Ok(Compound::list())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound, ReportError> {
+        Ok(Compound::list())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound, ReportError> {
+        Ok(Compound::list())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound, ReportError> {
+        let mut compound = Compound::list();
+        compound.next_key = Some(variant.to_string());
+        Ok(compound)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound, ReportError> {
+        Ok(Compound::record())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Compound, ReportError> {
+        Ok(Compound::record())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound, ReportError> {
+        let mut compound = Compound::record();
+        compound.next_key = Some(variant.to_string());
+        Ok(compound)
+    }
+}
+
+impl ser::SerializeSeq for Compound {
+    type Ok = ReportValue;
+    type Error = ReportError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ReportError> {
+        self.push(String::new(), value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<ReportValue, ReportError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for Compound {
+    type Ok = ReportValue;
+    type Error = ReportError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ReportError> {
+        self.push(String::new(), value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<ReportValue, ReportError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound {
+    type Ok = ReportValue;
+    type Error = ReportError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ReportError> {
+        self.push(String::new(), value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<ReportValue, ReportError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound {
+    type Ok = ReportValue;
+    type Error = ReportError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ReportError> {
+        self.push(String::new(), value);
+        Ok(())
+    }
+
+    fn end(mut self) -> Result<ReportValue, ReportError> {
+        let variant = self.next_key.take().unwrap_or_default();
+        Ok(ReportValue::Record(vec![(variant, self.finish())]))
+    }
+}
+
+impl ser::SerializeMap for Compound {
+    type Ok = ReportValue;
+    type Error = ReportError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), ReportError> {
+        self.next_key = Some(to_report_value(key).to_string());
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ReportError> {
+        let key = self.next_key.take().unwrap_or_default();
+        self.push(key, value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<ReportValue, ReportError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStruct for Compound {
+    type Ok = ReportValue;
+    type Error = ReportError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), ReportError> {
+        self.push(key.to_string(), value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<ReportValue, ReportError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStructVariant for Compound {
+    type Ok = ReportValue;
+    type Error = ReportError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), ReportError> {
+        self.push(key.to_string(), value);
+        Ok(())
+    }
+
+    fn end(mut self) -> Result<ReportValue, ReportError> {
+        let variant = self.next_key.take().unwrap_or_default();
+        Ok(ReportValue::Record(vec![(variant, self.finish())]))
+    }
+}
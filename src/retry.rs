@@ -0,0 +1,195 @@
+//! Retrying WMI queries that fail transiently.
+//!
+//! Under load WMI sporadically fails with errors such as `RPC_E_DISCONNECTED` or
+//! `WBEM_E_QUOTA_VIOLATION` that go away when the query is repeated. The `update` and
+//! `async_update` methods generated by [`update!`](crate::update) retry such failures following
+//! the process-wide [`RetryPolicy`], and only record the class as unavailable once the attempts
+//! are exhausted or the error is not retryable.
+//!
+//! Example:
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use windows_snapshot::retry::{set_policy, RetryPolicy};
+//!
+//! set_policy(
+//!     RetryPolicy::default()
+//!         .with_attempts(5)
+//!         .with_initial_delay(Duration::from_millis(250)),
+//! );
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::RwLock;
+use std::time::Duration;
+use wmi::{WMIError, WMIResult};
+
+/// The RPC connection was closed (`RPC_E_DISCONNECTED`)
+pub const RPC_E_DISCONNECTED: u32 = 0x8001_0108;
+/// The callee rejected the call (`RPC_E_CALL_REJECTED`)
+pub const RPC_E_CALL_REJECTED: u32 = 0x8001_0001;
+/// The callee is busy and asked to retry later (`RPC_E_SERVERCALL_RETRYLATER`)
+pub const RPC_E_SERVERCALL_RETRYLATER: u32 = 0x8001_010A;
+/// The RPC server is unavailable (`RPC_S_SERVER_UNAVAILABLE`)
+pub const RPC_S_SERVER_UNAVAILABLE: u32 = 0x8007_06BA;
+/// The remote procedure call failed (`RPC_S_CALL_FAILED`)
+pub const RPC_S_CALL_FAILED: u32 = 0x8007_06BE;
+/// WMI ran out of memory (`WBEM_E_OUT_OF_MEMORY`)
+pub const WBEM_E_OUT_OF_MEMORY: u32 = 0x8004_1006;
+/// The connection to the WMI service failed (`WBEM_E_TRANSPORT_FAILURE`)
+pub const WBEM_E_TRANSPORT_FAILURE: u32 = 0x8004_1015;
+/// The WMI service is too busy (`WBEM_E_SERVER_TOO_BUSY`)
+pub const WBEM_E_SERVER_TOO_BUSY: u32 = 0x8004_1045;
+/// A WMI quota was exceeded (`WBEM_E_QUOTA_VIOLATION`)
+pub const WBEM_E_QUOTA_VIOLATION: u32 = 0x8004_106C;
+
+static POLICY: RwLock<Option<RetryPolicy>> = RwLock::new(None);
+
+/// How failed queries are retried
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of times a query is run at most, 1 to never retry
+    pub attempts: u32,
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    /// Factor the delay is multiplied by after every retry
+    pub multiplier: u32,
+    /// Upper bound of the delay between retries
+    pub max_delay: Duration,
+    /// `HRESULT`s worth retrying, any other error fails right away
+    pub retryable: Vec<u32>,
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts, 100ms apart then 200ms, on RPC disconnections and WMI quota or load errors
+    fn default() -> Self {
+        RetryPolicy {
+            attempts: 3,
+            initial_delay: Duration::from_millis(100),
+            multiplier: 2,
+            max_delay: Duration::from_secs(5),
+            retryable: vec![
+                RPC_E_DISCONNECTED,
+                RPC_E_CALL_REJECTED,
+                RPC_E_SERVERCALL_RETRYLATER,
+                RPC_S_SERVER_UNAVAILABLE,
+                RPC_S_CALL_FAILED,
+                WBEM_E_OUT_OF_MEMORY,
+                WBEM_E_TRANSPORT_FAILURE,
+                WBEM_E_SERVER_TOO_BUSY,
+                WBEM_E_QUOTA_VIOLATION,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy running every query once
+    pub fn never() -> Self {
+        RetryPolicy {
+            attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Runs queries at most `attempts` times
+    pub fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Waits `delay` before the first retry
+    pub fn with_initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Multiplies the delay by `multiplier` after every retry, up to `max_delay`
+    pub fn with_backoff(mut self, multiplier: u32, max_delay: Duration) -> Self {
+        self.multiplier = multiplier;
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Retries `hresult` too
+    pub fn retry_on(mut self, hresult: u32) -> Self {
+        if !self.retryable.contains(&hresult) {
+            self.retryable.push(hresult);
+        }
+        self
+    }
+
+    /// Returns `true` if `error` is worth retrying
+    pub fn is_retryable(&self, error: &WMIError) -> bool {
+        match error {
+            WMIError::HResultError { hres } => self.retryable.contains(&(*hres as u32)),
+            _ => false,
+        }
+    }
+
+    /// Delay before retry number `retry`, starting at 0
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.max(1).saturating_pow(retry);
+
+        self.initial_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+
+    /// Runs `query` until it succeeds, fails with an error that is not retryable, or the attempts
+    /// are exhausted, sleeping the thread between attempts
+    pub fn run<T>(&self, mut query: impl FnMut() -> WMIResult<T>) -> WMIResult<T> {
+        let mut retry = 0;
+
+        loop {
+            match query() {
+                Err(e) if retry + 1 < self.attempts && self.is_retryable(&e) => {
+                    let delay = self.delay(retry);
+
+                    crate::instrument::retrying(&e, retry, delay);
+                    std::thread::sleep(delay);
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Asynchronous [`RetryPolicy::run`], waiting on the tokio timer between attempts
+    pub async fn run_async<T, F: Future<Output = WMIResult<T>>>(
+        &self,
+        mut query: impl FnMut() -> F,
+    ) -> WMIResult<T> {
+        let mut retry = 0;
+
+        loop {
+            match query().await {
+                Err(e) if retry + 1 < self.attempts && self.is_retryable(&e) => {
+                    let delay = self.delay(retry);
+
+                    crate::instrument::retrying(&e, retry, delay);
+                    tokio::time::sleep(delay).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Policy the generated update methods follow, [`RetryPolicy::default`] unless set
+pub fn policy() -> RetryPolicy {
+    POLICY
+        .read()
+        .ok()
+        .and_then(|policy| policy.clone())
+        .unwrap_or_default()
+}
+
+/// Replaces the policy the generated update methods follow
+pub fn set_policy(policy: RetryPolicy) {
+    if let Ok(mut current) = POLICY.write() {
+        *current = Some(policy);
+    }
+}
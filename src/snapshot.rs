@@ -0,0 +1,474 @@
+//! Saving snapshots to disk and loading them back.
+//!
+//! Snapshots are written as JSON by default. The `bincode` and `postcard` features add compact
+//! binary formats, and the `zstd` feature compresses any of them, which keeps hourly snapshots of
+//! many hosts small. [`load_snapshot`] detects the format and compression from the file itself, so
+//! readers do not need to know how a snapshot was saved.
+//!
+//! | Content                    | Layout                                                   |
+//! |----------------------------|----------------------------------------------------------|
+//! | JSON                       | the JSON document                                        |
+//! | bincode, postcard          | [`MAGIC`], a format byte, then the encoded snapshot      |
+//! | zstd compressed            | a zstd frame holding one of the above                    |
+//!
+//! Binary formats are not self-describing: a snapshot saved by one version of this crate may not
+//! load in another whose state structs differ. Use JSON for long-term storage across upgrades, and
+//! [`validate_snapshot`] to tell which members of an archived JSON snapshot no longer load.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::snapshot::{load_snapshot, save_snapshot, SnapshotOptions};
+//! use windows_snapshot::state::Windows;
+//! use windows_snapshot::COMLibrary;
+//!
+//! let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//! let mut k = Windows::default();
+//! k.update();
+//!
+//! save_snapshot(&k, "snapshot.json", &SnapshotOptions::default()).unwrap();
+//!
+//! let k: Windows = load_snapshot("snapshot.json").unwrap();
+//! ```
+
+use crate::state::{StateKind, Windows};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Leading bytes of a snapshot in a binary format
+pub const MAGIC: &[u8; 5] = b"WSNAP";
+
+/// First bytes of a zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Encoding of a saved snapshot
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum SnapshotFormat {
+    /// Human readable JSON
+    #[default]
+    Json,
+    /// Compact binary with bincode, available with the `bincode` feature
+    #[cfg(feature = "bincode")]
+    Bincode,
+    /// Compact binary with postcard, smaller than bincode thanks to varint integers, available with
+    /// the `postcard` feature
+    #[cfg(feature = "postcard")]
+    Postcard,
+}
+
+impl SnapshotFormat {
+    /// Byte following [`MAGIC`] for the binary formats
+    fn tag(&self) -> Option<u8> {
+        match self {
+            SnapshotFormat::Json => None,
+            #[cfg(feature = "bincode")]
+            SnapshotFormat::Bincode => Some(1),
+            #[cfg(feature = "postcard")]
+            SnapshotFormat::Postcard => Some(2),
+        }
+    }
+}
+
+/// How [`save_snapshot`] encodes a snapshot
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub struct SnapshotOptions {
+    /// Encoding of the snapshot
+    pub format: SnapshotFormat,
+    /// zstd compression level, `None` to leave the snapshot uncompressed
+    pub compression_level: Option<i32>,
+}
+
+impl SnapshotOptions {
+    /// Uncompressed snapshot in `format`
+    pub fn new(format: SnapshotFormat) -> Self {
+        SnapshotOptions {
+            format,
+            compression_level: None,
+        }
+    }
+
+    /// Compresses the snapshot with zstd at `level`, 0 for the zstd default, available with the
+    /// `zstd` feature
+    #[cfg(feature = "zstd")]
+    pub fn compressed(mut self, level: i32) -> Self {
+        self.compression_level = Some(level);
+        self
+    }
+}
+
+/// Error returned when a snapshot could not be saved or loaded
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The file could not be read or written
+    Io(std::io::Error),
+    /// The snapshot could not be encoded or decoded as JSON
+    Json(serde_json::Error),
+    /// The snapshot could not be encoded or decoded with bincode
+    #[cfg(feature = "bincode")]
+    Bincode(bincode::Error),
+    /// The snapshot could not be encoded or decoded with postcard
+    #[cfg(feature = "postcard")]
+    Postcard(postcard::Error),
+    /// The file is in a format or compression whose feature is not enabled, e.g. `zstd`
+    Unsupported(&'static str),
+    /// The file is not a snapshot
+    UnknownFormat,
+    /// The snapshot does not match the state of this version of the crate
+    Invalid(SnapshotValidation),
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "snapshot file failed: {e}"),
+            SnapshotError::Json(e) => write!(f, "json encoding failed: {e}"),
+            #[cfg(feature = "bincode")]
+            SnapshotError::Bincode(e) => write!(f, "bincode encoding failed: {e}"),
+            #[cfg(feature = "postcard")]
+            SnapshotError::Postcard(e) => write!(f, "postcard encoding failed: {e}"),
+            SnapshotError::Unsupported(feature) => {
+                write!(f, "snapshot needs the `{feature}` feature")
+            }
+            SnapshotError::UnknownFormat => write!(f, "not a snapshot"),
+            SnapshotError::Invalid(validation) => write!(f, "invalid snapshot: {validation}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(e: std::io::Error) -> Self {
+        SnapshotError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SnapshotError {
+    fn from(e: serde_json::Error) -> Self {
+        SnapshotError::Json(e)
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl From<bincode::Error> for SnapshotError {
+    fn from(e: bincode::Error) -> Self {
+        SnapshotError::Bincode(e)
+    }
+}
+
+#[cfg(feature = "postcard")]
+impl From<postcard::Error> for SnapshotError {
+    fn from(e: postcard::Error) -> Self {
+        SnapshotError::Postcard(e)
+    }
+}
+
+/// Writes `snapshot` to the file at `path` as `options` say, replacing it
+pub fn save_snapshot<T: Serialize>(
+    snapshot: &T,
+    path: impl AsRef<Path>,
+    options: &SnapshotOptions,
+) -> Result<(), SnapshotError> {
+    std::fs::write(path, to_bytes(snapshot, options)?)?;
+
+    Ok(())
+}
+
+/// Reads the snapshot in the file at `path`, whatever format and compression it was saved with
+pub fn load_snapshot<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T, SnapshotError> {
+    from_bytes(&std::fs::read(path)?)
+}
+
+/// Encodes `snapshot` as `options` say
+pub fn to_bytes<T: Serialize>(
+    snapshot: &T,
+    options: &SnapshotOptions,
+) -> Result<Vec<u8>, SnapshotError> {
+    let mut bytes = match options.format.tag() {
+        Some(tag) => {
+            let mut bytes = MAGIC.to_vec();
+            bytes.push(tag);
+            bytes
+        }
+        None => Vec::new(),
+    };
+
+    match options.format {
+        SnapshotFormat::Json => serde_json::to_writer(&mut bytes, snapshot)?,
+        #[cfg(feature = "bincode")]
+        SnapshotFormat::Bincode => bincode::serialize_into(&mut bytes, snapshot)?,
+        #[cfg(feature = "postcard")]
+        SnapshotFormat::Postcard => bytes = postcard::to_extend(snapshot, bytes)?,
+    }
+
+    match options.compression_level {
+        #[cfg(feature = "zstd")]
+        Some(level) => Ok(zstd::encode_all(bytes.as_slice(), level)?),
+        #[cfg(not(feature = "zstd"))]
+        Some(_) => Err(SnapshotError::Unsupported("zstd")),
+        None => Ok(bytes),
+    }
+}
+
+/// Decodes a snapshot encoded by [`to_bytes`], detecting its format and compression
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, SnapshotError> {
+    if bytes.starts_with(&ZSTD_MAGIC) {
+        #[cfg(feature = "zstd")]
+        return from_bytes(&zstd::decode_all(bytes)?);
+        #[cfg(not(feature = "zstd"))]
+        return Err(SnapshotError::Unsupported("zstd"));
+    }
+
+    if let Some(encoded) = bytes.strip_prefix(MAGIC) {
+        return match encoded.split_first() {
+            #[cfg(feature = "bincode")]
+            Some((1, encoded)) => Ok(bincode::deserialize(encoded)?),
+            #[cfg(not(feature = "bincode"))]
+            Some((1, _)) => Err(SnapshotError::Unsupported("bincode")),
+            #[cfg(feature = "postcard")]
+            Some((2, encoded)) => Ok(postcard::from_bytes(encoded)?),
+            #[cfg(not(feature = "postcard"))]
+            Some((2, _)) => Err(SnapshotError::Unsupported("postcard")),
+            _ => Err(SnapshotError::UnknownFormat),
+        };
+    }
+
+    match bytes.iter().find(|byte| !byte.is_ascii_whitespace()) {
+        Some(b'{' | b'[') => Ok(serde_json::from_slice(bytes)?),
+        _ => Err(SnapshotError::UnknownFormat),
+    }
+}
+
+/// Differences between a saved snapshot and the [`Windows`] state of this version of the crate,
+/// made by [`validate_snapshot`]
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotValidation {
+    /// Members of the state the snapshot lacks
+    pub missing_members: Vec<String>,
+    /// Fields of the snapshot the state does not have, members that were removed or whose
+    /// feature is disabled. They are ignored when loading.
+    pub unknown_members: Vec<String>,
+    /// Unknown members that look like a missing member under an older name, as
+    /// `(name in the snapshot, name in the state)`. Guessed from the names alone.
+    pub renamed_members: Vec<(String, String)>,
+    /// Members of both whose content does not load
+    pub invalid_members: Vec<InvalidMember>,
+}
+
+/// A member of a snapshot whose content does not load
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct InvalidMember {
+    /// Name of the member, e.g. `processes`
+    pub member: String,
+    /// Fields of the state of the member the snapshot lacks, e.g. `unavailable`
+    pub missing_fields: Vec<String>,
+    /// Fields of the member in the snapshot the state does not have
+    pub unknown_fields: Vec<String>,
+    /// Why the member does not load
+    pub error: String,
+}
+
+impl SnapshotValidation {
+    /// Returns `true` if the snapshot loads, unknown members being ignored
+    pub fn is_valid(&self) -> bool {
+        self.missing_members.is_empty()
+            && self.renamed_members.is_empty()
+            && self.invalid_members.is_empty()
+    }
+}
+
+impl std::fmt::Display for SnapshotValidation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut problems = Vec::new();
+
+        if !self.missing_members.is_empty() {
+            problems.push(format!("missing {}", self.missing_members.join(", ")));
+        }
+        for (old, new) in &self.renamed_members {
+            problems.push(format!("{old} renamed to {new}"));
+        }
+        for invalid in &self.invalid_members {
+            problems.push(format!("{}: {}", invalid.member, invalid.error));
+        }
+        if !self.unknown_members.is_empty() {
+            problems.push(format!("ignoring {}", self.unknown_members.join(", ")));
+        }
+
+        if problems.is_empty() {
+            write!(f, "valid")
+        } else {
+            write!(f, "{}", problems.join("; "))
+        }
+    }
+}
+
+/// Validates the snapshot in the file at `path` against the [`Windows`] state of this version of
+/// the crate, member by member, instead of failing on the first error like [`load_snapshot`]
+///
+/// Only JSON snapshots, compressed or not, can be validated: binary formats do not record the
+/// names of the fields.
+pub fn validate_snapshot(path: impl AsRef<Path>) -> Result<SnapshotValidation, SnapshotError> {
+    validate_value(&from_bytes(&std::fs::read(path)?)?)
+}
+
+/// Validates the JSON `snapshot` against the [`Windows`] state, see [`validate_snapshot`]
+pub fn validate_value(snapshot: &Value) -> Result<SnapshotValidation, SnapshotError> {
+    let snapshot = snapshot.as_object().ok_or(SnapshotError::UnknownFormat)?;
+    let schema = match serde_json::to_value(Windows::default())? {
+        Value::Object(schema) => schema,
+        _ => Map::new(),
+    };
+
+    let mut validation = SnapshotValidation {
+        missing_members: StateKind::ALL
+            .iter()
+            .map(StateKind::name)
+            .filter(|name| !snapshot.contains_key(*name))
+            .map(String::from)
+            .collect(),
+        unknown_members: snapshot
+            .keys()
+            .filter(|name| !schema.contains_key(*name))
+            .cloned()
+            .collect(),
+        ..SnapshotValidation::default()
+    };
+
+    validation.renamed_members = validation
+        .unknown_members
+        .iter()
+        .filter_map(|old| {
+            let new = validation
+                .missing_members
+                .iter()
+                .find(|new| normalized(new) == normalized(old))?;
+
+            Some((old.clone(), new.clone()))
+        })
+        .collect();
+    validation.missing_members.retain(|name| {
+        !validation.renamed_members.iter().any(|(_, new)| new == name)
+    });
+    validation.unknown_members.retain(|name| {
+        !validation.renamed_members.iter().any(|(old, _)| old == name)
+    });
+
+    for (name, value) in snapshot {
+        let Some(current) = schema.get(name) else {
+            continue;
+        };
+
+        // every other member at its default, so the error can only come from this one
+        let mut probe = schema.clone();
+        probe.insert(name.clone(), value.clone());
+
+        if let Err(e) = serde_json::from_value::<Windows>(Value::Object(probe)) {
+            let fields = |value: &Value| -> BTreeSet<String> {
+                value.as_object().map(|fields| fields.keys().cloned().collect()).unwrap_or_default()
+            };
+            let (current, saved) = (fields(current), fields(value));
+
+            validation.invalid_members.push(InvalidMember {
+                member: name.clone(),
+                missing_fields: current.difference(&saved).cloned().collect(),
+                unknown_fields: saved.difference(&current).cloned().collect(),
+                error: e.to_string(),
+            });
+        }
+    }
+
+    Ok(validation)
+}
+
+/// Loads the [`Windows`] snapshot in the file at `path`, failing with the report of
+/// [`validate_snapshot`] when a JSON snapshot does not match the state
+pub fn load_validated(path: impl AsRef<Path>) -> Result<Windows, SnapshotError> {
+    let bytes = std::fs::read(path)?;
+
+    match from_bytes::<Value>(&bytes) {
+        Ok(snapshot) => {
+            let validation = validate_value(&snapshot)?;
+
+            if validation.is_valid() {
+                Ok(serde_json::from_value(snapshot)?)
+            } else {
+                Err(SnapshotError::Invalid(validation))
+            }
+        }
+        // binary snapshots cannot be validated
+        Err(_) => from_bytes(&bytes),
+    }
+}
+
+/// `name` without underscores and a plural `s`, lowercase, to match renamed members
+fn normalized(name: &str) -> String {
+    let name = name.replace('_', "").to_lowercase();
+
+    name.strip_suffix('s').map(String::from).unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::custom::{CustomInstance, CustomState};
+    use crate::extra::{ExtraProperties, JsonText};
+    use serde_json::json;
+
+    /// A snapshot holding untyped values, in its extra properties and a custom member
+    fn untyped_snapshot() -> Windows {
+        let instance: CustomInstance = [
+            ("Vendor", json!("Contoso")),
+            ("Lanes", json!(4)),
+            ("Ports", json!([1, 2])),
+            ("Serial", Value::Null),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), JsonText::from(value)))
+        .collect();
+
+        let mut custom = CustomState::new("SELECT * FROM Contoso_Device");
+        custom.instances.push(instance.clone());
+
+        let mut windows = Windows::default();
+        windows.extra_properties.insert(
+            "Win32_DiskDrive".to_string(),
+            vec![ExtraProperties {
+                key: "\\\\.\\PHYSICALDRIVE0".to_string(),
+                properties: instance,
+            }],
+        );
+        windows.register_custom("contoso", custom);
+
+        windows
+    }
+
+    fn assert_round_trip(format: SnapshotFormat) {
+        let windows = untyped_snapshot();
+        let bytes = to_bytes(&windows, &SnapshotOptions::new(format)).unwrap();
+        let loaded: Windows = from_bytes(&bytes).unwrap();
+
+        assert_eq!(loaded.extra_properties, windows.extra_properties);
+        assert_eq!(loaded.custom, windows.custom);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        assert_round_trip(SnapshotFormat::Json);
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_round_trip() {
+        assert_round_trip(SnapshotFormat::Bincode);
+    }
+
+    #[cfg(feature = "postcard")]
+    #[test]
+    fn postcard_round_trip() {
+        assert_round_trip(SnapshotFormat::Postcard);
+    }
+}
@@ -1,13 +1,1751 @@
 //! Stores the main state of Windows machine
 
-use crate::operating_system::{
-    desktop, drivers, file_system, processes, registry, services, users, event_log, memory_and_pagefiles, scheduler_jobs, product_activation, software_license_provider, shares, multimedia_audio_visual, storage, security, start_menu, networking, job_objects, operating_system_settings
-};
-use crate::hardware::{
-    cooling_device, input_device, mass_storage, networking_device, telephony, power, video_monitor
-};
+#[cfg(feature = "os-capture-privacy")]
+use crate::operating_system::capture_privacy;
+#[cfg(feature = "os-com")]
+use crate::operating_system::com;
+#[cfg(feature = "os-connections")]
+use crate::operating_system::connections;
+#[cfg(feature = "os-desktop")]
+use crate::operating_system::desktop;
+#[cfg(feature = "os-device-security")]
+use crate::operating_system::device_security;
+#[cfg(feature = "os-dns")]
+use crate::operating_system::dns;
+#[cfg(feature = "os-drivers")]
+use crate::operating_system::drivers;
+#[cfg(feature = "os-event-log")]
+use crate::operating_system::event_log;
+#[cfg(feature = "os-file-system")]
+use crate::operating_system::file_system;
+#[cfg(feature = "os-firewall")]
+use crate::operating_system::firewall;
+#[cfg(feature = "os-group-policy")]
+use crate::operating_system::group_policy;
+#[cfg(feature = "os-installed-software")]
+use crate::operating_system::installed_software;
+#[cfg(feature = "os-job-objects")]
+use crate::operating_system::job_objects;
+#[cfg(feature = "os-memory-and-pagefiles")]
+use crate::operating_system::memory_and_pagefiles;
+#[cfg(feature = "os-multimedia-audio-visual")]
+use crate::operating_system::multimedia_audio_visual;
+#[cfg(feature = "os-networking")]
+use crate::operating_system::networking;
+#[cfg(feature = "os-operating-system-settings")]
+use crate::operating_system::operating_system_settings;
+#[cfg(feature = "os-optional-features")]
+use crate::operating_system::optional_features;
+#[cfg(feature = "os-perf")]
+use crate::operating_system::perf;
+#[cfg(feature = "os-persistence")]
+use crate::operating_system::persistence;
+#[cfg(feature = "os-processes")]
+use crate::operating_system::processes;
+#[cfg(feature = "os-product-activation")]
+use crate::operating_system::product_activation;
+#[cfg(feature = "os-registry")]
+use crate::operating_system::registry;
+#[cfg(feature = "os-scheduler-jobs")]
+use crate::operating_system::scheduler_jobs;
+#[cfg(feature = "os-security")]
+use crate::operating_system::security;
+#[cfg(feature = "os-security-center")]
+use crate::operating_system::security_center;
+#[cfg(feature = "os-services")]
+use crate::operating_system::services;
+#[cfg(feature = "os-shares")]
+use crate::operating_system::shares;
+#[cfg(feature = "os-software-license-provider")]
+use crate::operating_system::software_license_provider;
+#[cfg(feature = "os-start-menu")]
+use crate::operating_system::start_menu;
+#[cfg(feature = "os-storage")]
+use crate::operating_system::storage;
+#[cfg(feature = "os-time-service")]
+use crate::operating_system::time_service;
+#[cfg(feature = "os-updates")]
+use crate::operating_system::updates;
+#[cfg(feature = "os-security-settings")]
+use crate::operating_system::security_settings;
+#[cfg(feature = "os-users")]
+use crate::operating_system::users;
+#[cfg(feature = "hw-cooling-device")]
+use crate::hardware::cooling_device;
+#[cfg(feature = "hw-input-device")]
+use crate::hardware::input_device;
+#[cfg(feature = "hw-mass-storage")]
+use crate::hardware::mass_storage;
+#[cfg(feature = "hw-motherboard-controller-port")]
+use crate::hardware::motherboard_controller_port;
+#[cfg(feature = "hw-networking-device")]
+use crate::hardware::networking_device;
+#[cfg(feature = "hw-power")]
+use crate::hardware::power;
+#[cfg(feature = "hw-printing")]
+use crate::hardware::printing;
+#[cfg(feature = "hw-telephony")]
+use crate::hardware::telephony;
+#[cfg(feature = "hw-usb")]
+use crate::hardware::usb;
+#[cfg(feature = "hw-video-monitor")]
+use crate::hardware::video_monitor;
+#[cfg(feature = "bluetooth")]
+use crate::hardware::bluetooth;
+#[cfg(feature = "hyperv")]
+use crate::operating_system::virtualization;
+use crate::custom::CustomState;
+use crate::extra::ExtraProperties;
+use crate::instrument;
+use crate::report::{Summary, UNKNOWN};
+use crate::watch::WindowsDiff;
+use crate::Unavailable;
 use serde::{Deserialize, Serialize};
-use tokio::join;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use futures::future::{self, FutureExt, LocalBoxFuture};
+use futures::stream::{self, Stream, StreamExt};
+use tokio::time::MissedTickBehavior;
+use windows::core::PWSTR;
+use windows::Win32::System::SystemInformation::{ComputerNameNetBIOS, GetComputerNameExW};
+
+/// Number of WMI queries [`Windows::async_update`] keeps in flight
+pub const DEFAULT_CONCURRENCY: usize = 16;
+
+/// Member of [`Windows`]
+///
+/// Reported by [`Windows::async_update_with_progress`] as each member completes, and selected for update by a [`SnapshotProfile`]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StateKind {
+    /// State of Windows Processes
+    #[cfg(feature = "os-processes")]
+    Processes,
+    /// State of Windows Threads
+    #[cfg(feature = "os-processes")]
+    Threads,
+    /// State of Windows processor utilization
+    #[cfg(feature = "os-perf")]
+    ProcessorPerformances,
+    /// State of Windows memory usage
+    #[cfg(feature = "os-perf")]
+    MemoryPerformances,
+    /// State of Windows logical disk activity
+    #[cfg(feature = "os-perf")]
+    LogicalDiskPerformances,
+    /// State of Windows GPU engine utilization
+    #[cfg(feature = "os-perf")]
+    GPUEnginePerformances,
+    /// State of Windows Drivers
+    #[cfg(feature = "os-drivers")]
+    Drivers,
+    /// State of Windows Registry
+    #[cfg(feature = "os-registry")]
+    Registry,
+    /// State of Windows Registry keys in persistence locations
+    #[cfg(feature = "os-registry")]
+    RegistryKeys,
+    /// State of Windows DCOM applications
+    #[cfg(feature = "os-com")]
+    DcomApplications,
+    /// State of Windows DCOM application settings
+    #[cfg(feature = "os-com")]
+    DcomApplicationSettings,
+    /// State of Windows COM settings
+    #[cfg(feature = "os-com")]
+    ComSettings,
+    /// State of Windows COM class settings
+    #[cfg(feature = "os-com")]
+    ClassicComClassSettings,
+    /// State of Windows Trusted Platform Modules
+    #[cfg(feature = "os-device-security")]
+    Tpms,
+    /// State of Windows Secure Boot
+    #[cfg(feature = "os-device-security")]
+    SecureBoot,
+    /// State of Windows BitLocker volumes
+    #[cfg(feature = "os-device-security")]
+    EncryptableVolumes,
+    /// State of Windows antivirus products
+    #[cfg(feature = "os-security-center")]
+    AntiVirusProducts,
+    /// State of Windows firewall products
+    #[cfg(feature = "os-security-center")]
+    FirewallProducts,
+    /// State of Windows antispyware products
+    #[cfg(feature = "os-security-center")]
+    AntiSpywareProducts,
+    /// State of Windows Services
+    #[cfg(feature = "os-services")]
+    Services,
+    /// State of Windows DependentServices
+    #[cfg(feature = "os-services")]
+    DependentServices,
+    /// State of Windows Desktops
+    #[cfg(feature = "os-desktop")]
+    Desktops,
+    /// State of Windows Environments
+    #[cfg(feature = "os-desktop")]
+    Environment,
+    /// State of Windows TimeZones
+    #[cfg(feature = "os-desktop")]
+    Timezones,
+    /// State of Windows User Accounts
+    #[cfg(feature = "os-users")]
+    UserAccounts,
+    /// State of Windows Groups
+    #[cfg(feature = "os-users")]
+    Groups,
+    /// State of Windows Logon Sessions
+    #[cfg(feature = "os-users")]
+    LogonSessions,
+    /// State of Windows Network Logins
+    #[cfg(feature = "os-users")]
+    NetworkLoginProfiles,
+    /// State of Windows System Accounts
+    #[cfg(feature = "os-users")]
+    SystemAccounts,
+    /// State of Windows local groups and their members
+    #[cfg(feature = "os-users")]
+    LocalGroups,
+    /// State of Windows user profiles
+    #[cfg(feature = "os-users")]
+    UserProfiles,
+    /// State of windows Directory
+    #[cfg(feature = "os-file-system")]
+    Directories,
+    /// State of windows Directory Specifications
+    #[cfg(feature = "os-file-system")]
+    DirectoriesSpecifications,
+    /// State of windows Directory Disk Partitions
+    #[cfg(feature = "os-file-system")]
+    DiskPartition,
+    /// State of Windows DiskDriveToDiskPartitions
+    #[cfg(feature = "os-file-system")]
+    DiskDriveToDiskPartitions,
+    /// State of windows Logical Disks
+    #[cfg(feature = "os-file-system")]
+    LogicalDisks,
+    /// State of Windows LogicalDiskToPartitions
+    #[cfg(feature = "os-file-system")]
+    LogicalDiskToPartitions,
+    /// State of windows Mapped Logical Disks
+    #[cfg(feature = "os-file-system")]
+    MappedLogicalDisks,
+    /// State of windows Quota Settings
+    #[cfg(feature = "os-file-system")]
+    QuotaSettings,
+    /// State of windows Shortcut Files
+    #[cfg(feature = "os-file-system")]
+    ShortcutFiles,
+    /// State of windows Volumes
+    #[cfg(feature = "os-file-system")]
+    Volumes,
+    /// State of windows NTEventLogFiles
+    #[cfg(feature = "os-event-log")]
+    NtEventLogFiles,
+    /// State of windows NTLogEvents
+    #[cfg(feature = "os-event-log")]
+    NtLogEvents,
+    /// State of windows boot history
+    #[cfg(feature = "os-event-log")]
+    BootHistory,
+    /// State of the last boot and the shutdown before it
+    #[cfg(feature = "os-event-log")]
+    BootInfos,
+    /// State of windows PageFiles
+    #[cfg(feature = "os-memory-and-pagefiles")]
+    Pagefiles,
+    /// State of windows PageFileSettings
+    #[cfg(feature = "os-memory-and-pagefiles")]
+    PagefileSettings,
+    /// State of windows PageFileUsages
+    #[cfg(feature = "os-memory-and-pagefiles")]
+    PagefileUsages,
+    /// State of windows ScheduledJobs
+    #[cfg(feature = "os-scheduler-jobs")]
+    ScheduledJobs,
+    /// State of Windows Task Scheduler tasks
+    #[cfg(feature = "os-scheduler-jobs")]
+    ScheduledTasks,
+    /// State of windows LocalTimes
+    #[cfg(feature = "os-scheduler-jobs")]
+    LocalTimes,
+    /// State of windows UTCTimes
+    #[cfg(feature = "os-scheduler-jobs")]
+    UtcTimes,
+    /// State of Windows Time service configuration
+    #[cfg(feature = "os-time-service")]
+    TimeService,
+    /// State of Windows Update
+    #[cfg(feature = "os-updates")]
+    WindowsUpdates,
+    /// State of the local security policy
+    #[cfg(feature = "os-security-settings")]
+    SecuritySettings,
+    /// State of the Group Policy objects processed for the computer
+    #[cfg(feature = "os-group-policy")]
+    GPOs,
+    /// State of the links between Group Policy objects and their scopes
+    #[cfg(feature = "os-group-policy")]
+    GPLinks,
+    /// State of the Group Policy client-side extensions
+    #[cfg(feature = "os-group-policy")]
+    GPExtensionStatuses,
+    /// State of windows SoftwareLicensingProducts
+    #[cfg(feature = "os-software-license-provider")]
+    SoftwareLicensingProducts,
+    /// State of windows SoftwareLicensingServices
+    #[cfg(feature = "os-software-license-provider")]
+    SoftwareLicensingServices,
+    /// State of windows SoftwareLicensingTokenActivationLicenses
+    #[cfg(feature = "os-software-license-provider")]
+    SoftwareLicensingTokenActivationLicenses,
+    /// State of windows ServerConnections
+    #[cfg(feature = "os-shares")]
+    ServerConnections,
+    /// State of windows ServerSessions
+    #[cfg(feature = "os-shares")]
+    ServerSessions,
+    /// State of windows Shares
+    #[cfg(feature = "os-shares")]
+    Shares,
+    /// Share-level permissions of the Windows Shares
+    #[cfg(feature = "os-shares")]
+    ShareSecurityDescriptors,
+    /// State of Windows CodecFiles
+    #[cfg(feature = "os-multimedia-audio-visual")]
+    CodecFiles,
+    /// State of Windows camera and microphone devices and privacy settings
+    #[cfg(feature = "os-capture-privacy")]
+    CapturePrivacy,
+    /// State of Windows ShadowCopys
+    #[cfg(feature = "os-storage")]
+    ShadowCopys,
+    /// State of Windows ShadowContexts
+    #[cfg(feature = "os-storage")]
+    ShadowContexts,
+    /// State of Windows ShadowProviders
+    #[cfg(feature = "os-storage")]
+    ShadowProviders,
+    /// State of Windows LogicalFileSecuritySettings
+    #[cfg(feature = "os-security")]
+    LogicalFileSecuritySettings,
+    /// State of Windows LogicalShareSecuritySettings
+    #[cfg(feature = "os-security")]
+    LogicalShareSecuritySettings,
+    /// State of Windows PrivilegesStatuses
+    #[cfg(feature = "os-security")]
+    PrivilegesStatuses,
+    /// State of Windows LogicalProgramGroups
+    #[cfg(feature = "os-start-menu")]
+    LogicalProgramGroups,
+    /// State of Windows LogicalProgramGroupItems
+    #[cfg(feature = "os-start-menu")]
+    LogicalProgramGroupItems,
+    /// State of Windows IP4PersistedRouteTables
+    #[cfg(feature = "os-networking")]
+    Ip4PersistedRouteTables,
+    /// State of Windows IP4RouteTables
+    #[cfg(feature = "os-networking")]
+    Ip4RouteTables,
+    /// State of Windows NetworkClients
+    #[cfg(feature = "os-networking")]
+    NeworkClients,
+    /// State of Windows NetworkConnections
+    #[cfg(feature = "os-networking")]
+    NeworkConnections,
+    /// State of Windows NetworkProtocols
+    #[cfg(feature = "os-networking")]
+    NeworkProtocols,
+    /// State of Windows NTDomains
+    #[cfg(feature = "os-networking")]
+    NtDomains,
+    /// State of Windows IP4RouteTableEvents
+    #[cfg(feature = "os-networking")]
+    Ip4RouteTableEvents,
+    /// State of Windows QosPolicies
+    #[cfg(feature = "os-networking")]
+    QosPolicies,
+    /// State of Windows firewall profiles
+    #[cfg(feature = "os-firewall")]
+    FirewallProfiles,
+    /// State of Windows firewall rules
+    #[cfg(feature = "os-firewall")]
+    FirewallRules,
+    /// State of Windows firewall port filters
+    #[cfg(feature = "os-firewall")]
+    FirewallPortFilters,
+    /// State of Windows firewall application filters
+    #[cfg(feature = "os-firewall")]
+    FirewallApplicationFilters,
+    /// State of Windows firewall address filters
+    #[cfg(feature = "os-firewall")]
+    FirewallAddressFilters,
+    /// State of Windows TCP connections
+    #[cfg(feature = "os-connections")]
+    TcpConnections,
+    /// State of Windows UDP endpoints
+    #[cfg(feature = "os-connections")]
+    UdpEndpoints,
+    /// State of Windows neighbor cache
+    #[cfg(feature = "os-connections")]
+    NetNeighbors,
+    /// State of Windows DNS client cache
+    #[cfg(feature = "os-dns")]
+    DnsClientCache,
+    /// State of Windows hosts file
+    #[cfg(feature = "os-dns")]
+    HostsFile,
+    /// State of Windows NamedJobObjects
+    #[cfg(feature = "os-job-objects")]
+    NamedJobObjects,
+    /// State of Windows NamedJobObjectActgInfos
+    #[cfg(feature = "os-job-objects")]
+    NamedJobObjectActgInfos,
+    /// State of Windows NamedJobObjectLimitSettings
+    #[cfg(feature = "os-job-objects")]
+    NamedJobObjectLimitSettings,
+    /// State of Windows BootConfigurations
+    #[cfg(feature = "os-operating-system-settings")]
+    BootConfigurations,
+    /// State of Windows ComputerSystems
+    #[cfg(feature = "os-operating-system-settings")]
+    ComputerSystems,
+    /// State of Windows SystemServices
+    #[cfg(feature = "os-operating-system-settings")]
+    SystemServices,
+    /// State of Windows ComputerSystemProducts
+    #[cfg(feature = "os-operating-system-settings")]
+    ComputerSystemProducts,
+    /// State of Windows LoadOrderGroups
+    #[cfg(feature = "os-operating-system-settings")]
+    LoadOrderGroups,
+    /// State of Windows OperatingSystems
+    #[cfg(feature = "os-operating-system-settings")]
+    OperatingSystems,
+    /// State of Windows OSRecoveryConfigurations
+    #[cfg(feature = "os-operating-system-settings")]
+    OsRecoveryConfigurations,
+    /// State of Windows QuickFixEngineerings
+    #[cfg(feature = "os-operating-system-settings")]
+    QuickFixEngineerings,
+    /// State of Windows optional features
+    #[cfg(feature = "os-optional-features")]
+    OptionalFeatures,
+    /// State of Windows Server roles and features
+    #[cfg(feature = "os-optional-features")]
+    ServerFeatures,
+    /// State of Windows StartupCommands
+    #[cfg(feature = "os-operating-system-settings")]
+    StartupCommands,
+    /// State of the items Windows runs at boot or logon, from every autostart source
+    #[cfg(feature = "os-persistence")]
+    PersistenceItems,
+    /// State of Windows InstalledSoftware
+    #[cfg(feature = "os-installed-software")]
+    InstalledSoftware,
+    /// State of Windows Fans
+    #[cfg(feature = "hw-cooling-device")]
+    Fans,
+    /// State of Windows HeatPipes
+    #[cfg(feature = "hw-cooling-device")]
+    HeatPipes,
+    /// State of Windows Refrigerations
+    #[cfg(feature = "hw-cooling-device")]
+    Refrigerations,
+    /// State of Windows TemperatureProbes
+    #[cfg(feature = "hw-cooling-device")]
+    TemperatureProbes,
+    /// State of Windows Keyboards
+    #[cfg(feature = "hw-input-device")]
+    Keyboards,
+    /// State of Windows PointingDevices
+    #[cfg(feature = "hw-input-device")]
+    PointingDevices,
+    /// Keyboards and pointing devices added or removed between updates
+    #[cfg(feature = "hw-input-device")]
+    InputDeviceEvents,
+    /// State of Windows AutochkSettings
+    #[cfg(feature = "hw-mass-storage")]
+    AutochkSettings,
+    /// State of Windows CDROMDrives
+    #[cfg(feature = "hw-mass-storage")]
+    CdRomDrives,
+    /// State of Windows DiskDrives
+    #[cfg(feature = "hw-mass-storage")]
+    DiskDrives,
+    /// State of Windows PhysicalMedias
+    #[cfg(feature = "hw-mass-storage")]
+    PhysicalMedias,
+    /// State of Windows TapeDrives
+    #[cfg(feature = "hw-mass-storage")]
+    TapeDrives,
+    /// State of Windows PhysicalDisks
+    #[cfg(feature = "hw-mass-storage")]
+    PhysicalDisks,
+    /// State of Windows NVMe capabilities
+    #[cfg(feature = "hw-mass-storage")]
+    NvmeCapabilities,
+    /// State of Windows Disks
+    #[cfg(feature = "hw-mass-storage")]
+    Disks,
+    /// State of Windows disk reliability counters
+    #[cfg(feature = "hw-mass-storage")]
+    StorageReliabilityCounters,
+    /// State of Windows disk failure predictions
+    #[cfg(feature = "hw-mass-storage")]
+    FailurePredictStatuses,
+    /// State of Windows PnPEntities
+    #[cfg(feature = "hw-motherboard-controller-port")]
+    PnpEntities,
+    /// State of Windows BIOSes
+    #[cfg(feature = "hw-motherboard-controller-port")]
+    Bioses,
+    /// State of Windows SystemEnclosures
+    #[cfg(feature = "hw-motherboard-controller-port")]
+    SystemEnclosures,
+    /// State of Windows SerialPortConfigurations
+    #[cfg(feature = "hw-motherboard-controller-port")]
+    SerialPortConfigurations,
+    /// State of Windows ParallelPorts
+    #[cfg(feature = "hw-motherboard-controller-port")]
+    ParallelPorts,
+    /// State of Windows USB devices
+    #[cfg(feature = "hw-usb")]
+    UsbDevices,
+    /// State of Windows NetworkAdapters
+    #[cfg(feature = "hw-networking-device")]
+    NetworkAdapters,
+    /// State of Windows NetworkAdapterConfigurations
+    #[cfg(feature = "hw-networking-device")]
+    NetworkAdapterConfigurations,
+    /// State of the `MSFT_NetAdapter` network adapters, classified by `MSFT_NetAdapter::class`
+    #[cfg(feature = "hw-networking-device")]
+    NetAdapters,
+    /// State of Windows POTSModems
+    #[cfg(feature = "hw-telephony")]
+    PotModems,
+    /// State of Windows POTSModemToSerialPorts
+    #[cfg(feature = "hw-telephony")]
+    PotModemToSerialPorts,
+    /// State of Windows Batteries
+    #[cfg(feature = "hw-power")]
+    Batteries,
+    /// State of Windows CurrentProbes
+    #[cfg(feature = "hw-power")]
+    CurrentProbes,
+    /// State of Windows PortableBatteries
+    #[cfg(feature = "hw-power")]
+    PortableBatteries,
+    /// State of Windows PowerManagementEvents
+    #[cfg(feature = "hw-power")]
+    PowerManagementEvents,
+    /// State of Windows VoltageProbes
+    #[cfg(feature = "hw-power")]
+    VoltageProbes,
+    /// State of Windows Printers
+    #[cfg(feature = "hw-printing")]
+    Printers,
+    /// State of Windows PrinterDrivers
+    #[cfg(feature = "hw-printing")]
+    PrinterDrivers,
+    /// State of Windows DriverForDevices
+    #[cfg(feature = "hw-printing")]
+    DriverForDevices,
+    /// State of Windows TCPIPPrinterPorts
+    #[cfg(feature = "hw-printing")]
+    TCPIPPrinterPorts,
+    /// State of Windows DesktopMonitors
+    #[cfg(feature = "hw-video-monitor")]
+    DesktopMonitors,
+    /// State of Windows DisplayControllerConfigurations
+    #[cfg(feature = "hw-video-monitor")]
+    DisplayControllerConfigurations,
+    /// State of Windows VideoControllers
+    #[cfg(feature = "hw-video-monitor")]
+    VideoControllers,
+    /// State of Windows BluetoothDevices
+    #[cfg(feature = "bluetooth")]
+    BluetoothDevices,
+    /// State of Hyper-V virtual machines
+    #[cfg(feature = "hyperv")]
+    VirtualMachines,
+    /// State of the virtual processors assigned to Hyper-V virtual machines
+    #[cfg(feature = "hyperv")]
+    VirtualProcessorSettings,
+    /// State of the memory assigned to Hyper-V virtual machines
+    #[cfg(feature = "hyperv")]
+    VirtualMemorySettings,
+}
+
+impl StateKind {
+    /// Every member, in the order of the fields of [`Windows`]
+    pub const ALL: &'static [StateKind] = &[
+        #[cfg(feature = "os-processes")]
+        StateKind::Processes,
+        #[cfg(feature = "os-processes")]
+        StateKind::Threads,
+        #[cfg(feature = "os-perf")]
+        StateKind::ProcessorPerformances,
+        #[cfg(feature = "os-perf")]
+        StateKind::MemoryPerformances,
+        #[cfg(feature = "os-perf")]
+        StateKind::LogicalDiskPerformances,
+        #[cfg(feature = "os-perf")]
+        StateKind::GPUEnginePerformances,
+        #[cfg(feature = "os-drivers")]
+        StateKind::Drivers,
+        #[cfg(feature = "os-registry")]
+        StateKind::Registry,
+        #[cfg(feature = "os-registry")]
+        StateKind::RegistryKeys,
+        #[cfg(feature = "os-com")]
+        StateKind::DcomApplications,
+        #[cfg(feature = "os-com")]
+        StateKind::DcomApplicationSettings,
+        #[cfg(feature = "os-com")]
+        StateKind::ComSettings,
+        #[cfg(feature = "os-com")]
+        StateKind::ClassicComClassSettings,
+        #[cfg(feature = "os-device-security")]
+        StateKind::Tpms,
+        #[cfg(feature = "os-device-security")]
+        StateKind::SecureBoot,
+        #[cfg(feature = "os-device-security")]
+        StateKind::EncryptableVolumes,
+        #[cfg(feature = "os-security-center")]
+        StateKind::AntiVirusProducts,
+        #[cfg(feature = "os-security-center")]
+        StateKind::FirewallProducts,
+        #[cfg(feature = "os-security-center")]
+        StateKind::AntiSpywareProducts,
+        #[cfg(feature = "os-services")]
+        StateKind::Services,
+        #[cfg(feature = "os-services")]
+        StateKind::DependentServices,
+        #[cfg(feature = "os-desktop")]
+        StateKind::Desktops,
+        #[cfg(feature = "os-desktop")]
+        StateKind::Environment,
+        #[cfg(feature = "os-desktop")]
+        StateKind::Timezones,
+        #[cfg(feature = "os-users")]
+        StateKind::UserAccounts,
+        #[cfg(feature = "os-users")]
+        StateKind::Groups,
+        #[cfg(feature = "os-users")]
+        StateKind::LogonSessions,
+        #[cfg(feature = "os-users")]
+        StateKind::NetworkLoginProfiles,
+        #[cfg(feature = "os-users")]
+        StateKind::SystemAccounts,
+        #[cfg(feature = "os-users")]
+        StateKind::LocalGroups,
+        #[cfg(feature = "os-users")]
+        StateKind::UserProfiles,
+        #[cfg(feature = "os-file-system")]
+        StateKind::Directories,
+        #[cfg(feature = "os-file-system")]
+        StateKind::DirectoriesSpecifications,
+        #[cfg(feature = "os-file-system")]
+        StateKind::DiskPartition,
+        #[cfg(feature = "os-file-system")]
+        StateKind::DiskDriveToDiskPartitions,
+        #[cfg(feature = "os-file-system")]
+        StateKind::LogicalDisks,
+        #[cfg(feature = "os-file-system")]
+        StateKind::LogicalDiskToPartitions,
+        #[cfg(feature = "os-file-system")]
+        StateKind::MappedLogicalDisks,
+        #[cfg(feature = "os-file-system")]
+        StateKind::QuotaSettings,
+        #[cfg(feature = "os-file-system")]
+        StateKind::ShortcutFiles,
+        #[cfg(feature = "os-file-system")]
+        StateKind::Volumes,
+        #[cfg(feature = "os-event-log")]
+        StateKind::NtEventLogFiles,
+        #[cfg(feature = "os-event-log")]
+        StateKind::NtLogEvents,
+        #[cfg(feature = "os-event-log")]
+        StateKind::BootHistory,
+        #[cfg(feature = "os-event-log")]
+        StateKind::BootInfos,
+        #[cfg(feature = "os-memory-and-pagefiles")]
+        StateKind::Pagefiles,
+        #[cfg(feature = "os-memory-and-pagefiles")]
+        StateKind::PagefileSettings,
+        #[cfg(feature = "os-memory-and-pagefiles")]
+        StateKind::PagefileUsages,
+        #[cfg(feature = "os-scheduler-jobs")]
+        StateKind::ScheduledJobs,
+        #[cfg(feature = "os-scheduler-jobs")]
+        StateKind::ScheduledTasks,
+        #[cfg(feature = "os-scheduler-jobs")]
+        StateKind::LocalTimes,
+        #[cfg(feature = "os-scheduler-jobs")]
+        StateKind::UtcTimes,
+        #[cfg(feature = "os-time-service")]
+        StateKind::TimeService,
+        #[cfg(feature = "os-updates")]
+        StateKind::WindowsUpdates,
+        #[cfg(feature = "os-security-settings")]
+        StateKind::SecuritySettings,
+        #[cfg(feature = "os-group-policy")]
+        StateKind::GPOs,
+        #[cfg(feature = "os-group-policy")]
+        StateKind::GPLinks,
+        #[cfg(feature = "os-group-policy")]
+        StateKind::GPExtensionStatuses,
+        #[cfg(feature = "os-software-license-provider")]
+        StateKind::SoftwareLicensingProducts,
+        #[cfg(feature = "os-software-license-provider")]
+        StateKind::SoftwareLicensingServices,
+        #[cfg(feature = "os-software-license-provider")]
+        StateKind::SoftwareLicensingTokenActivationLicenses,
+        #[cfg(feature = "os-shares")]
+        StateKind::ServerConnections,
+        #[cfg(feature = "os-shares")]
+        StateKind::ServerSessions,
+        #[cfg(feature = "os-shares")]
+        StateKind::Shares,
+        #[cfg(feature = "os-shares")]
+        StateKind::ShareSecurityDescriptors,
+        #[cfg(feature = "os-multimedia-audio-visual")]
+        StateKind::CodecFiles,
+        #[cfg(feature = "os-capture-privacy")]
+        StateKind::CapturePrivacy,
+        #[cfg(feature = "os-storage")]
+        StateKind::ShadowCopys,
+        #[cfg(feature = "os-storage")]
+        StateKind::ShadowContexts,
+        #[cfg(feature = "os-storage")]
+        StateKind::ShadowProviders,
+        #[cfg(feature = "os-security")]
+        StateKind::LogicalFileSecuritySettings,
+        #[cfg(feature = "os-security")]
+        StateKind::LogicalShareSecuritySettings,
+        #[cfg(feature = "os-security")]
+        StateKind::PrivilegesStatuses,
+        #[cfg(feature = "os-start-menu")]
+        StateKind::LogicalProgramGroups,
+        #[cfg(feature = "os-start-menu")]
+        StateKind::LogicalProgramGroupItems,
+        #[cfg(feature = "os-networking")]
+        StateKind::Ip4PersistedRouteTables,
+        #[cfg(feature = "os-networking")]
+        StateKind::Ip4RouteTables,
+        #[cfg(feature = "os-networking")]
+        StateKind::NeworkClients,
+        #[cfg(feature = "os-networking")]
+        StateKind::NeworkConnections,
+        #[cfg(feature = "os-networking")]
+        StateKind::NeworkProtocols,
+        #[cfg(feature = "os-networking")]
+        StateKind::NtDomains,
+        #[cfg(feature = "os-networking")]
+        StateKind::Ip4RouteTableEvents,
+        #[cfg(feature = "os-networking")]
+        StateKind::QosPolicies,
+        #[cfg(feature = "os-firewall")]
+        StateKind::FirewallProfiles,
+        #[cfg(feature = "os-firewall")]
+        StateKind::FirewallRules,
+        #[cfg(feature = "os-firewall")]
+        StateKind::FirewallPortFilters,
+        #[cfg(feature = "os-firewall")]
+        StateKind::FirewallApplicationFilters,
+        #[cfg(feature = "os-firewall")]
+        StateKind::FirewallAddressFilters,
+        #[cfg(feature = "os-connections")]
+        StateKind::TcpConnections,
+        #[cfg(feature = "os-connections")]
+        StateKind::UdpEndpoints,
+        #[cfg(feature = "os-connections")]
+        StateKind::NetNeighbors,
+        #[cfg(feature = "os-dns")]
+        StateKind::DnsClientCache,
+        #[cfg(feature = "os-dns")]
+        StateKind::HostsFile,
+        #[cfg(feature = "os-job-objects")]
+        StateKind::NamedJobObjects,
+        #[cfg(feature = "os-job-objects")]
+        StateKind::NamedJobObjectActgInfos,
+        #[cfg(feature = "os-job-objects")]
+        StateKind::NamedJobObjectLimitSettings,
+        #[cfg(feature = "os-operating-system-settings")]
+        StateKind::BootConfigurations,
+        #[cfg(feature = "os-operating-system-settings")]
+        StateKind::ComputerSystems,
+        #[cfg(feature = "os-operating-system-settings")]
+        StateKind::SystemServices,
+        #[cfg(feature = "os-operating-system-settings")]
+        StateKind::ComputerSystemProducts,
+        #[cfg(feature = "os-operating-system-settings")]
+        StateKind::LoadOrderGroups,
+        #[cfg(feature = "os-operating-system-settings")]
+        StateKind::OperatingSystems,
+        #[cfg(feature = "os-operating-system-settings")]
+        StateKind::OsRecoveryConfigurations,
+        #[cfg(feature = "os-operating-system-settings")]
+        StateKind::QuickFixEngineerings,
+        #[cfg(feature = "os-optional-features")]
+        StateKind::OptionalFeatures,
+        #[cfg(feature = "os-optional-features")]
+        StateKind::ServerFeatures,
+        #[cfg(feature = "os-operating-system-settings")]
+        StateKind::StartupCommands,
+        #[cfg(feature = "os-persistence")]
+        StateKind::PersistenceItems,
+        #[cfg(feature = "os-installed-software")]
+        StateKind::InstalledSoftware,
+        #[cfg(feature = "hw-cooling-device")]
+        StateKind::Fans,
+        #[cfg(feature = "hw-cooling-device")]
+        StateKind::HeatPipes,
+        #[cfg(feature = "hw-cooling-device")]
+        StateKind::Refrigerations,
+        #[cfg(feature = "hw-cooling-device")]
+        StateKind::TemperatureProbes,
+        #[cfg(feature = "hw-input-device")]
+        StateKind::Keyboards,
+        #[cfg(feature = "hw-input-device")]
+        StateKind::PointingDevices,
+        #[cfg(feature = "hw-input-device")]
+        StateKind::InputDeviceEvents,
+        #[cfg(feature = "hw-mass-storage")]
+        StateKind::AutochkSettings,
+        #[cfg(feature = "hw-mass-storage")]
+        StateKind::CdRomDrives,
+        #[cfg(feature = "hw-mass-storage")]
+        StateKind::DiskDrives,
+        #[cfg(feature = "hw-mass-storage")]
+        StateKind::PhysicalMedias,
+        #[cfg(feature = "hw-mass-storage")]
+        StateKind::TapeDrives,
+        #[cfg(feature = "hw-mass-storage")]
+        StateKind::PhysicalDisks,
+        #[cfg(feature = "hw-mass-storage")]
+        StateKind::NvmeCapabilities,
+        #[cfg(feature = "hw-mass-storage")]
+        StateKind::Disks,
+        #[cfg(feature = "hw-mass-storage")]
+        StateKind::StorageReliabilityCounters,
+        #[cfg(feature = "hw-mass-storage")]
+        StateKind::FailurePredictStatuses,
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        StateKind::PnpEntities,
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        StateKind::Bioses,
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        StateKind::SystemEnclosures,
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        StateKind::SerialPortConfigurations,
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        StateKind::ParallelPorts,
+        #[cfg(feature = "hw-usb")]
+        StateKind::UsbDevices,
+        #[cfg(feature = "hw-networking-device")]
+        StateKind::NetworkAdapters,
+        #[cfg(feature = "hw-networking-device")]
+        StateKind::NetworkAdapterConfigurations,
+        #[cfg(feature = "hw-networking-device")]
+        StateKind::NetAdapters,
+        #[cfg(feature = "hw-telephony")]
+        StateKind::PotModems,
+        #[cfg(feature = "hw-telephony")]
+        StateKind::PotModemToSerialPorts,
+        #[cfg(feature = "hw-power")]
+        StateKind::Batteries,
+        #[cfg(feature = "hw-power")]
+        StateKind::CurrentProbes,
+        #[cfg(feature = "hw-power")]
+        StateKind::PortableBatteries,
+        #[cfg(feature = "hw-power")]
+        StateKind::PowerManagementEvents,
+        #[cfg(feature = "hw-power")]
+        StateKind::VoltageProbes,
+        #[cfg(feature = "hw-printing")]
+        StateKind::Printers,
+        #[cfg(feature = "hw-printing")]
+        StateKind::PrinterDrivers,
+        #[cfg(feature = "hw-printing")]
+        StateKind::DriverForDevices,
+        #[cfg(feature = "hw-printing")]
+        StateKind::TCPIPPrinterPorts,
+        #[cfg(feature = "hw-video-monitor")]
+        StateKind::DesktopMonitors,
+        #[cfg(feature = "hw-video-monitor")]
+        StateKind::DisplayControllerConfigurations,
+        #[cfg(feature = "hw-video-monitor")]
+        StateKind::VideoControllers,
+        #[cfg(feature = "bluetooth")]
+        StateKind::BluetoothDevices,
+        #[cfg(feature = "hyperv")]
+        StateKind::VirtualMachines,
+        #[cfg(feature = "hyperv")]
+        StateKind::VirtualProcessorSettings,
+        #[cfg(feature = "hyperv")]
+        StateKind::VirtualMemorySettings,
+    ];
+
+    /// Name of the state field holding the member, e.g. `logical_disks`
+    pub fn name(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "os-processes")]
+            StateKind::Processes => "processes",
+            #[cfg(feature = "os-processes")]
+            StateKind::Threads => "threads",
+            #[cfg(feature = "os-perf")]
+            StateKind::ProcessorPerformances => "processor_performances",
+            #[cfg(feature = "os-perf")]
+            StateKind::MemoryPerformances => "memory_performances",
+            #[cfg(feature = "os-perf")]
+            StateKind::LogicalDiskPerformances => "logical_disk_performances",
+            #[cfg(feature = "os-perf")]
+            StateKind::GPUEnginePerformances => "gpu_engine_performances",
+            #[cfg(feature = "os-drivers")]
+            StateKind::Drivers => "drivers",
+            #[cfg(feature = "os-registry")]
+            StateKind::Registry => "registry",
+            #[cfg(feature = "os-registry")]
+            StateKind::RegistryKeys => "registry_keys",
+            #[cfg(feature = "os-com")]
+            StateKind::DcomApplications => "dcom_applications",
+            #[cfg(feature = "os-com")]
+            StateKind::DcomApplicationSettings => "dcom_application_settings",
+            #[cfg(feature = "os-com")]
+            StateKind::ComSettings => "com_settings",
+            #[cfg(feature = "os-com")]
+            StateKind::ClassicComClassSettings => "classic_com_class_settings",
+            #[cfg(feature = "os-device-security")]
+            StateKind::Tpms => "tpms",
+            #[cfg(feature = "os-device-security")]
+            StateKind::SecureBoot => "secure_boot",
+            #[cfg(feature = "os-device-security")]
+            StateKind::EncryptableVolumes => "encryptable_volumes",
+            #[cfg(feature = "os-security-center")]
+            StateKind::AntiVirusProducts => "anti_virus_products",
+            #[cfg(feature = "os-security-center")]
+            StateKind::FirewallProducts => "firewall_products",
+            #[cfg(feature = "os-security-center")]
+            StateKind::AntiSpywareProducts => "anti_spyware_products",
+            #[cfg(feature = "os-services")]
+            StateKind::Services => "services",
+            #[cfg(feature = "os-services")]
+            StateKind::DependentServices => "dependent_services",
+            #[cfg(feature = "os-desktop")]
+            StateKind::Desktops => "desktops",
+            #[cfg(feature = "os-desktop")]
+            StateKind::Environment => "environment",
+            #[cfg(feature = "os-desktop")]
+            StateKind::Timezones => "timezones",
+            #[cfg(feature = "os-users")]
+            StateKind::UserAccounts => "user_accounts",
+            #[cfg(feature = "os-users")]
+            StateKind::Groups => "groups",
+            #[cfg(feature = "os-users")]
+            StateKind::LogonSessions => "logon_sessions",
+            #[cfg(feature = "os-users")]
+            StateKind::NetworkLoginProfiles => "network_login_profiles",
+            #[cfg(feature = "os-users")]
+            StateKind::SystemAccounts => "system_accounts",
+            #[cfg(feature = "os-users")]
+            StateKind::LocalGroups => "local_groups",
+            #[cfg(feature = "os-users")]
+            StateKind::UserProfiles => "user_profiles",
+            #[cfg(feature = "os-file-system")]
+            StateKind::Directories => "directories",
+            #[cfg(feature = "os-file-system")]
+            StateKind::DirectoriesSpecifications => "directories_specifications",
+            #[cfg(feature = "os-file-system")]
+            StateKind::DiskPartition => "disk_partition",
+            #[cfg(feature = "os-file-system")]
+            StateKind::DiskDriveToDiskPartitions => "disk_drive_to_disk_partitions",
+            #[cfg(feature = "os-file-system")]
+            StateKind::LogicalDisks => "logical_disks",
+            #[cfg(feature = "os-file-system")]
+            StateKind::LogicalDiskToPartitions => "logical_disk_to_partitions",
+            #[cfg(feature = "os-file-system")]
+            StateKind::MappedLogicalDisks => "mapped_logical_disks",
+            #[cfg(feature = "os-file-system")]
+            StateKind::QuotaSettings => "quota_settings",
+            #[cfg(feature = "os-file-system")]
+            StateKind::ShortcutFiles => "shortcut_files",
+            #[cfg(feature = "os-file-system")]
+            StateKind::Volumes => "volumes",
+            #[cfg(feature = "os-event-log")]
+            StateKind::NtEventLogFiles => "nt_event_log_files",
+            #[cfg(feature = "os-event-log")]
+            StateKind::NtLogEvents => "nt_log_events",
+            #[cfg(feature = "os-event-log")]
+            StateKind::BootHistory => "boot_history",
+            #[cfg(feature = "os-event-log")]
+            StateKind::BootInfos => "boot_infos",
+            #[cfg(feature = "os-memory-and-pagefiles")]
+            StateKind::Pagefiles => "pagefiles",
+            #[cfg(feature = "os-memory-and-pagefiles")]
+            StateKind::PagefileSettings => "pagefile_settings",
+            #[cfg(feature = "os-memory-and-pagefiles")]
+            StateKind::PagefileUsages => "pagefile_usages",
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::ScheduledJobs => "scheduled_jobs",
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::ScheduledTasks => "scheduled_tasks",
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::LocalTimes => "local_times",
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::UtcTimes => "utc_times",
+            #[cfg(feature = "os-time-service")]
+            StateKind::TimeService => "time_service",
+            #[cfg(feature = "os-updates")]
+            StateKind::WindowsUpdates => "windows_updates",
+            #[cfg(feature = "os-security-settings")]
+            StateKind::SecuritySettings => "security_settings",
+            #[cfg(feature = "os-group-policy")]
+            StateKind::GPOs => "gpos",
+            #[cfg(feature = "os-group-policy")]
+            StateKind::GPLinks => "gp_links",
+            #[cfg(feature = "os-group-policy")]
+            StateKind::GPExtensionStatuses => "gp_extension_statuses",
+            #[cfg(feature = "os-software-license-provider")]
+            StateKind::SoftwareLicensingProducts => "software_licensing_products",
+            #[cfg(feature = "os-software-license-provider")]
+            StateKind::SoftwareLicensingServices => "software_licensing_services",
+            #[cfg(feature = "os-software-license-provider")]
+            StateKind::SoftwareLicensingTokenActivationLicenses => "software_licensing_token_activation_licenses",
+            #[cfg(feature = "os-shares")]
+            StateKind::ServerConnections => "server_connections",
+            #[cfg(feature = "os-shares")]
+            StateKind::ServerSessions => "server_sessions",
+            #[cfg(feature = "os-shares")]
+            StateKind::Shares => "shares",
+            #[cfg(feature = "os-shares")]
+            StateKind::ShareSecurityDescriptors => "share_security_descriptors",
+            #[cfg(feature = "os-multimedia-audio-visual")]
+            StateKind::CodecFiles => "codec_files",
+            #[cfg(feature = "os-capture-privacy")]
+            StateKind::CapturePrivacy => "capture_privacy",
+            #[cfg(feature = "os-storage")]
+            StateKind::ShadowCopys => "shadow_copys",
+            #[cfg(feature = "os-storage")]
+            StateKind::ShadowContexts => "shadow_contexts",
+            #[cfg(feature = "os-storage")]
+            StateKind::ShadowProviders => "shadow_providers",
+            #[cfg(feature = "os-security")]
+            StateKind::LogicalFileSecuritySettings => "logical_file_security_settings",
+            #[cfg(feature = "os-security")]
+            StateKind::LogicalShareSecuritySettings => "logical_share_security_settings",
+            #[cfg(feature = "os-security")]
+            StateKind::PrivilegesStatuses => "privileges_statuses",
+            #[cfg(feature = "os-start-menu")]
+            StateKind::LogicalProgramGroups => "logical_program_groups",
+            #[cfg(feature = "os-start-menu")]
+            StateKind::LogicalProgramGroupItems => "logical_program_group_items",
+            #[cfg(feature = "os-networking")]
+            StateKind::Ip4PersistedRouteTables => "ip4_persisted_route_tables",
+            #[cfg(feature = "os-networking")]
+            StateKind::Ip4RouteTables => "ip4_route_tables",
+            #[cfg(feature = "os-networking")]
+            StateKind::NeworkClients => "nework_clients",
+            #[cfg(feature = "os-networking")]
+            StateKind::NeworkConnections => "nework_connections",
+            #[cfg(feature = "os-networking")]
+            StateKind::NeworkProtocols => "nework_protocols",
+            #[cfg(feature = "os-networking")]
+            StateKind::NtDomains => "nt_domains",
+            #[cfg(feature = "os-networking")]
+            StateKind::Ip4RouteTableEvents => "ip4_route_table_events",
+            #[cfg(feature = "os-networking")]
+            StateKind::QosPolicies => "qos_policies",
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallProfiles => "firewall_profiles",
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallRules => "firewall_rules",
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallPortFilters => "firewall_port_filters",
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallApplicationFilters => "firewall_application_filters",
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallAddressFilters => "firewall_address_filters",
+            #[cfg(feature = "os-connections")]
+            StateKind::TcpConnections => "tcp_connections",
+            #[cfg(feature = "os-connections")]
+            StateKind::UdpEndpoints => "udp_endpoints",
+            #[cfg(feature = "os-connections")]
+            StateKind::NetNeighbors => "net_neighbors",
+            #[cfg(feature = "os-dns")]
+            StateKind::DnsClientCache => "dns_client_cache",
+            #[cfg(feature = "os-dns")]
+            StateKind::HostsFile => "hosts_file",
+            #[cfg(feature = "os-job-objects")]
+            StateKind::NamedJobObjects => "named_job_objects",
+            #[cfg(feature = "os-job-objects")]
+            StateKind::NamedJobObjectActgInfos => "named_job_object_actg_infos",
+            #[cfg(feature = "os-job-objects")]
+            StateKind::NamedJobObjectLimitSettings => "named_job_object_limit_settings",
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::BootConfigurations => "boot_configurations",
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::ComputerSystems => "computer_systems",
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::SystemServices => "system_services",
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::ComputerSystemProducts => "computer_system_products",
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::LoadOrderGroups => "load_order_groups",
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::OperatingSystems => "operating_systems",
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::OsRecoveryConfigurations => "os_recovery_configurations",
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::QuickFixEngineerings => "quick_fix_engineerings",
+            #[cfg(feature = "os-optional-features")]
+            StateKind::OptionalFeatures => "optional_features",
+            #[cfg(feature = "os-optional-features")]
+            StateKind::ServerFeatures => "server_features",
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::StartupCommands => "startup_commands",
+            #[cfg(feature = "os-persistence")]
+            StateKind::PersistenceItems => "persistence_items",
+            #[cfg(feature = "os-installed-software")]
+            StateKind::InstalledSoftware => "installed_software",
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::Fans => "fans",
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::HeatPipes => "heat_pipes",
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::Refrigerations => "refrigerations",
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::TemperatureProbes => "temperature_probes",
+            #[cfg(feature = "hw-input-device")]
+            StateKind::Keyboards => "keyboards",
+            #[cfg(feature = "hw-input-device")]
+            StateKind::PointingDevices => "pointing_devices",
+            #[cfg(feature = "hw-input-device")]
+            StateKind::InputDeviceEvents => "input_device_events",
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::AutochkSettings => "autochk_settings",
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::CdRomDrives => "cd_rom_drives",
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::DiskDrives => "disk_drives",
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::PhysicalMedias => "physical_medias",
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::TapeDrives => "tape_drives",
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::PhysicalDisks => "physical_disks",
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::NvmeCapabilities => "nvme_capabilities",
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::Disks => "disks",
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::StorageReliabilityCounters => "storage_reliability_counters",
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::FailurePredictStatuses => "failure_predict_statuses",
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::PnpEntities => "pnp_entities",
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::Bioses => "bioses",
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::SystemEnclosures => "system_enclosures",
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::SerialPortConfigurations => "serial_port_configurations",
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::ParallelPorts => "parallel_ports",
+            #[cfg(feature = "hw-usb")]
+            StateKind::UsbDevices => "usb_devices",
+            #[cfg(feature = "hw-networking-device")]
+            StateKind::NetworkAdapters => "network_adapters",
+            #[cfg(feature = "hw-networking-device")]
+            StateKind::NetworkAdapterConfigurations => "network_adapter_configurations",
+            #[cfg(feature = "hw-networking-device")]
+            StateKind::NetAdapters => "net_adapters",
+            #[cfg(feature = "hw-telephony")]
+            StateKind::PotModems => "pot_modems",
+            #[cfg(feature = "hw-telephony")]
+            StateKind::PotModemToSerialPorts => "pot_modem_to_serial_ports",
+            #[cfg(feature = "hw-power")]
+            StateKind::Batteries => "batteries",
+            #[cfg(feature = "hw-power")]
+            StateKind::CurrentProbes => "current_probes",
+            #[cfg(feature = "hw-power")]
+            StateKind::PortableBatteries => "portable_batteries",
+            #[cfg(feature = "hw-power")]
+            StateKind::PowerManagementEvents => "power_management_events",
+            #[cfg(feature = "hw-power")]
+            StateKind::VoltageProbes => "voltage_probes",
+            #[cfg(feature = "hw-printing")]
+            StateKind::Printers => "printers",
+            #[cfg(feature = "hw-printing")]
+            StateKind::PrinterDrivers => "printer_drivers",
+            #[cfg(feature = "hw-printing")]
+            StateKind::DriverForDevices => "driver_for_devices",
+            #[cfg(feature = "hw-printing")]
+            StateKind::TCPIPPrinterPorts => "tcpip_printer_ports",
+            #[cfg(feature = "hw-video-monitor")]
+            StateKind::DesktopMonitors => "desktop_monitors",
+            #[cfg(feature = "hw-video-monitor")]
+            StateKind::DisplayControllerConfigurations => "display_controller_configurations",
+            #[cfg(feature = "hw-video-monitor")]
+            StateKind::VideoControllers => "video_controllers",
+            #[cfg(feature = "bluetooth")]
+            StateKind::BluetoothDevices => "bluetooth_devices",
+            #[cfg(feature = "hyperv")]
+            StateKind::VirtualMachines => "virtual_machines",
+            #[cfg(feature = "hyperv")]
+            StateKind::VirtualProcessorSettings => "virtual_processor_settings",
+            #[cfg(feature = "hyperv")]
+            StateKind::VirtualMemorySettings => "virtual_memory_settings",
+        }
+    }
+
+    /// The member whose state field is named `name`, e.g. `logical_disks`, `None` if no enabled
+    /// member is
+    pub fn from_name(name: &str) -> Option<StateKind> {
+        StateKind::ALL.iter().copied().find(|kind| kind.name() == name)
+    }
+}
+
+/// Version of the layout of [`Windows`], increased when members are renamed or removed
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Provenance of a [`Windows`] snapshot, filled in by its update methods
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct SnapshotMeta {
+    /// Name of the computer the snapshot was taken on
+    pub hostname: String,
+    /// When the last update started, `None` until the snapshot is updated
+    pub capture_started: Option<SystemTime>,
+    /// When the last update finished, `None` while it runs
+    pub capture_finished: Option<SystemTime>,
+    /// Version of this crate that took the snapshot
+    pub crate_version: String,
+    /// [`SCHEMA_VERSION`] of the snapshot
+    pub schema_version: u32,
+    /// How the last update of each member went
+    pub members: BTreeMap<StateKind, MemberMeta>,
+}
+
+impl Default for SnapshotMeta {
+    /// `crate_version` and `schema_version` default to those of this crate
+    fn default() -> Self {
+        SnapshotMeta {
+            hostname: String::new(),
+            capture_started: None,
+            capture_finished: None,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version: SCHEMA_VERSION,
+            members: BTreeMap::new(),
+        }
+    }
+}
+
+impl SnapshotMeta {
+    /// Time the last update took, `None` until one finished
+    pub fn capture_duration(&self) -> Option<Duration> {
+        self.capture_finished?
+            .duration_since(self.capture_started?)
+            .ok()
+    }
+
+    /// Members whose last update failed, leaving out the [`MemberStatus::Unsupported`] ones
+    pub fn failed(&self) -> impl Iterator<Item = (&StateKind, &Unavailable)> {
+        self.members
+            .iter()
+            .filter(|(_, member)| member.status == MemberStatus::Failed)
+            .filter_map(|(kind, member)| Some((kind, member.error.as_ref()?)))
+    }
+
+    /// Members whose class or namespace the host does not provide
+    pub fn unsupported(&self) -> impl Iterator<Item = &StateKind> {
+        self.members
+            .iter()
+            .filter(|(_, member)| member.status == MemberStatus::Unsupported)
+            .map(|(kind, _)| kind)
+    }
+
+    fn start(&mut self) {
+        self.hostname = computer_name().unwrap_or_default();
+        self.capture_started = Some(SystemTime::now());
+        self.capture_finished = None;
+        self.crate_version = env!("CARGO_PKG_VERSION").to_string();
+        self.schema_version = SCHEMA_VERSION;
+    }
+
+    fn finish(&mut self) {
+        self.capture_finished = Some(SystemTime::now());
+    }
+
+    fn record(&mut self, kind: StateKind, duration: Duration, outcome: &Result<usize, Unavailable>) {
+        self.members.insert(
+            kind,
+            MemberMeta {
+                duration,
+                status: MemberStatus::of(outcome),
+                instances: outcome.as_ref().ok().copied(),
+                error: outcome.as_ref().err().cloned(),
+            },
+        );
+    }
+}
+
+/// NetBIOS name of the computer, the `Name` of `Win32_ComputerSystem`
+///
+/// Read from the system rather than the `COMPUTERNAME` variable, which the environment of the
+/// process can override.
+fn computer_name() -> Option<String> {
+    // Long enough for the 15 characters of a NetBIOS name and its terminating null
+    let mut buffer = [0u16; 16];
+    let mut len = buffer.len() as u32;
+
+    unsafe { GetComputerNameExW(ComputerNameNetBIOS, PWSTR(buffer.as_mut_ptr()), &mut len) }
+        .as_bool()
+        .then(|| String::from_utf16_lossy(&buffer[..len as usize]))
+}
+
+/// How the last update of a member went
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct MemberMeta {
+    /// Time the update took
+    pub duration: Duration,
+    /// Whether the member was collected
+    #[serde(default)]
+    pub status: MemberStatus,
+    /// Number of instances the member holds, `None` if its class was unavailable
+    pub instances: Option<usize>,
+    /// Why the class of the member was unavailable, `None` when it was queried
+    pub error: Option<Unavailable>,
+}
+
+/// Outcome of the last update of a member
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum MemberStatus {
+    /// The member was queried
+    #[default]
+    Collected,
+    /// The host does not provide the class or namespace of the member, as with classes missing
+    /// from some Windows editions. Expected, and not counted as a failure.
+    Unsupported,
+    /// The member could not be queried, see [`MemberMeta::error`]
+    Failed,
+}
+
+impl MemberStatus {
+    fn of(outcome: &Result<usize, Unavailable>) -> Self {
+        match outcome {
+            Ok(_) => MemberStatus::Collected,
+            Err(unavailable) if unavailable.unsupported() => MemberStatus::Unsupported,
+            Err(_) => MemberStatus::Failed,
+        }
+    }
+}
+
+/// Why [`Windows::merge`] refused a partial snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeError {
+    /// Host the snapshot merged into was taken on
+    pub hostname: String,
+    /// Host the partial snapshot was taken on
+    pub partial_hostname: String,
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot merge a snapshot of `{}` into a snapshot of `{}`",
+            self.partial_hostname, self.hostname
+        )
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+/// Members to update, for snapshots that do not need every class
+///
+/// ```rust
+/// use windows_snapshot::state::{SnapshotProfile, StateKind};
+///
+/// let profile = SnapshotProfile::new()
+///     .include(StateKind::Services)
+///     .include(StateKind::Drivers);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq, Default)]
+pub struct SnapshotProfile {
+    /// Members to update
+    pub kinds: BTreeSet<StateKind>,
+}
+
+impl SnapshotProfile {
+    /// A profile without members
+    pub fn new() -> Self {
+        SnapshotProfile::default()
+    }
+
+    /// Every member, as [`Windows::update`] refreshes
+    pub fn all() -> Self {
+        SnapshotProfile {
+            kinds: StateKind::ALL.iter().copied().collect(),
+        }
+    }
+
+    /// Cheap, high-signal members: operating system, computer system, logical disks, services and
+    /// processes. Expensive members such as directories, codec files and event log entries are left
+    /// out.
+    pub fn quick() -> Self {
+        #[allow(unused_mut)]
+        let mut profile = SnapshotProfile::new();
+
+        #[cfg(feature = "os-operating-system-settings")]
+        profile.kinds.insert(StateKind::OperatingSystems);
+        #[cfg(feature = "os-operating-system-settings")]
+        profile.kinds.insert(StateKind::ComputerSystems);
+        #[cfg(feature = "os-file-system")]
+        profile.kinds.insert(StateKind::LogicalDisks);
+        #[cfg(feature = "os-services")]
+        profile.kinds.insert(StateKind::Services);
+        #[cfg(feature = "os-processes")]
+        profile.kinds.insert(StateKind::Processes);
+
+        profile
+    }
+
+    /// Every hardware member, the classes in `crate::hardware`
+    pub fn hardware() -> Self {
+        #[allow(unused_mut)]
+        let mut profile = SnapshotProfile::new();
+
+        #[cfg(feature = "hw-cooling-device")]
+        profile.kinds.insert(StateKind::Fans);
+        #[cfg(feature = "hw-cooling-device")]
+        profile.kinds.insert(StateKind::HeatPipes);
+        #[cfg(feature = "hw-cooling-device")]
+        profile.kinds.insert(StateKind::Refrigerations);
+        #[cfg(feature = "hw-cooling-device")]
+        profile.kinds.insert(StateKind::TemperatureProbes);
+        #[cfg(feature = "hw-input-device")]
+        profile.kinds.insert(StateKind::Keyboards);
+        #[cfg(feature = "hw-input-device")]
+        profile.kinds.insert(StateKind::PointingDevices);
+        #[cfg(feature = "hw-input-device")]
+        profile.kinds.insert(StateKind::InputDeviceEvents);
+        #[cfg(feature = "hw-mass-storage")]
+        profile.kinds.insert(StateKind::AutochkSettings);
+        #[cfg(feature = "hw-mass-storage")]
+        profile.kinds.insert(StateKind::CdRomDrives);
+        #[cfg(feature = "hw-mass-storage")]
+        profile.kinds.insert(StateKind::DiskDrives);
+        #[cfg(feature = "hw-mass-storage")]
+        profile.kinds.insert(StateKind::PhysicalMedias);
+        #[cfg(feature = "hw-mass-storage")]
+        profile.kinds.insert(StateKind::TapeDrives);
+        #[cfg(feature = "hw-mass-storage")]
+        profile.kinds.insert(StateKind::PhysicalDisks);
+        #[cfg(feature = "hw-mass-storage")]
+        profile.kinds.insert(StateKind::NvmeCapabilities);
+        #[cfg(feature = "hw-mass-storage")]
+        profile.kinds.insert(StateKind::Disks);
+        #[cfg(feature = "hw-mass-storage")]
+        profile.kinds.insert(StateKind::StorageReliabilityCounters);
+        #[cfg(feature = "hw-mass-storage")]
+        profile.kinds.insert(StateKind::FailurePredictStatuses);
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        profile.kinds.insert(StateKind::PnpEntities);
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        profile.kinds.insert(StateKind::Bioses);
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        profile.kinds.insert(StateKind::SystemEnclosures);
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        profile.kinds.insert(StateKind::SerialPortConfigurations);
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        profile.kinds.insert(StateKind::ParallelPorts);
+        #[cfg(feature = "hw-usb")]
+        profile.kinds.insert(StateKind::UsbDevices);
+        #[cfg(feature = "hw-networking-device")]
+        profile.kinds.insert(StateKind::NetworkAdapters);
+        #[cfg(feature = "hw-networking-device")]
+        profile.kinds.insert(StateKind::NetworkAdapterConfigurations);
+        #[cfg(feature = "hw-networking-device")]
+        profile.kinds.insert(StateKind::NetAdapters);
+        #[cfg(feature = "hw-telephony")]
+        profile.kinds.insert(StateKind::PotModems);
+        #[cfg(feature = "hw-telephony")]
+        profile.kinds.insert(StateKind::PotModemToSerialPorts);
+        #[cfg(feature = "hw-power")]
+        profile.kinds.insert(StateKind::Batteries);
+        #[cfg(feature = "hw-power")]
+        profile.kinds.insert(StateKind::CurrentProbes);
+        #[cfg(feature = "hw-power")]
+        profile.kinds.insert(StateKind::PortableBatteries);
+        #[cfg(feature = "hw-power")]
+        profile.kinds.insert(StateKind::PowerManagementEvents);
+        #[cfg(feature = "hw-power")]
+        profile.kinds.insert(StateKind::VoltageProbes);
+        #[cfg(feature = "hw-printing")]
+        profile.kinds.insert(StateKind::Printers);
+        #[cfg(feature = "hw-printing")]
+        profile.kinds.insert(StateKind::PrinterDrivers);
+        #[cfg(feature = "hw-printing")]
+        profile.kinds.insert(StateKind::DriverForDevices);
+        #[cfg(feature = "hw-printing")]
+        profile.kinds.insert(StateKind::TCPIPPrinterPorts);
+        #[cfg(feature = "hw-video-monitor")]
+        profile.kinds.insert(StateKind::DesktopMonitors);
+        #[cfg(feature = "hw-video-monitor")]
+        profile.kinds.insert(StateKind::DisplayControllerConfigurations);
+        #[cfg(feature = "hw-video-monitor")]
+        profile.kinds.insert(StateKind::VideoControllers);
+        #[cfg(feature = "bluetooth")]
+        profile.kinds.insert(StateKind::BluetoothDevices);
+
+        profile
+    }
+
+    /// Adds `kind` to the profile
+    pub fn include(mut self, kind: StateKind) -> Self {
+        self.kinds.insert(kind);
+        self
+    }
+
+    /// Removes `kind` from the profile
+    pub fn exclude(mut self, kind: StateKind) -> Self {
+        self.kinds.remove(&kind);
+        self
+    }
+
+    /// Returns `true` if the profile updates `kind`
+    pub fn contains(&self, kind: StateKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    /// The members named in the comma separated `names`, e.g. `processes,services`, failing with
+    /// the first name that is not a member
+    pub fn from_names(names: &str) -> Result<Self, String> {
+        names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .try_fold(SnapshotProfile::new(), |profile, name| {
+                StateKind::from_name(name)
+                    .map(|kind| profile.include(kind))
+                    .ok_or_else(|| name.to_string())
+            })
+    }
+}
+
+/// How [`Windows::watch_on_battery`] backs off while the machine runs on a battery running low
+///
+/// ```rust
+/// use windows_snapshot::state::{BatteryThrottle, StateKind};
+///
+/// let throttle = BatteryThrottle {
+///     threshold: 30,
+///     ..BatteryThrottle::default()
+/// }
+/// .skip(StateKind::Processes);
+/// ```
+#[cfg(feature = "hw-power")]
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct BatteryThrottle {
+    /// Remaining charge, in percent, below which a discharging battery throttles the watch
+    pub threshold: u16,
+    /// How many intervals pass between two updates while throttled, 1 to keep the interval
+    pub slowdown: u32,
+    /// Members left out of the updates while throttled
+    pub skipped: BTreeSet<StateKind>,
+}
+
+#[cfg(feature = "hw-power")]
+impl Default for BatteryThrottle {
+    /// Below 50 percent, update every 4 intervals and skip the members walking the file system or
+    /// the event logs
+    fn default() -> Self {
+        #[allow(unused_mut)]
+        let mut skipped = BTreeSet::new();
+
+        #[cfg(feature = "os-file-system")]
+        skipped.extend([
+            StateKind::Directories,
+            StateKind::DirectoriesSpecifications,
+            StateKind::ShortcutFiles,
+        ]);
+        #[cfg(feature = "os-event-log")]
+        skipped.insert(StateKind::NtLogEvents);
+        #[cfg(feature = "os-multimedia-audio-visual")]
+        skipped.insert(StateKind::CodecFiles);
+
+        BatteryThrottle {
+            threshold: 50,
+            slowdown: 4,
+            skipped,
+        }
+    }
+}
+
+#[cfg(feature = "hw-power")]
+impl BatteryThrottle {
+    /// Adds `kind` to the members skipped while throttled
+    pub fn skip(mut self, kind: StateKind) -> Self {
+        self.skipped.insert(kind);
+        self
+    }
+
+    /// Returns `true` if `batteries` should throttle the watch
+    pub fn engaged(&self, batteries: &power::Batteries) -> bool {
+        batteries.discharging_below(self.threshold)
+    }
+
+    /// `profile` without the members skipped while throttled
+    pub fn apply(&self, profile: &SnapshotProfile) -> SnapshotProfile {
+        SnapshotProfile {
+            kinds: profile.kinds.difference(&self.skipped).copied().collect(),
+        }
+    }
+}
+
+/// Pairs the [`StateKind`] of a member with the boxed asynchronous update of the member, yielding
+/// how many instances it holds or why its class is unavailable
+macro_rules! tracked {
+    ($kind: ident, $state: expr, $instances: ident) => {{
+        let state = &mut $state;
+
+        let update = async move {
+            state.async_update().await;
+
+            match &state.unavailable {
+                Some(unavailable) => Err(unavailable.clone()),
+                None => Ok(state.$instances.len()),
+            }
+        };
+
+        (StateKind::$kind, instrument::async_update(StateKind::$kind, update).boxed_local())
+    }};
+}
+
+/// Synchronously updates a member, yielding how many instances it holds or why its class is
+/// unavailable
+macro_rules! synced {
+    ($kind: ident, $state: expr, $instances: ident) => {{
+        let state = &mut $state;
+
+        instrument::update(StateKind::$kind, || {
+            state.update();
+
+            match &state.unavailable {
+                Some(unavailable) => Err(unavailable.clone()),
+                None => Ok(state.$instances.len()),
+            }
+        })
+    }};
+}
+
+/// Moves the instances of the member of a partial snapshot into `$state` unless `$state` was
+/// `$recorded` as updated and updated later, yielding whether it did
+macro_rules! merged {
+    ($state: expr, $partial: expr, $recorded: expr) => {{
+        let newer = !$recorded || $partial.last_updated >= $state.last_updated;
+
+        if newer {
+            $state.merge_from(&mut $partial);
+        }
+
+        newer
+    }};
+}
+
+/// Pairs the [`StateKind`] of a member with its boxed synchronous update, to be run on another
+/// thread, yielding how many instances it holds or why its class is unavailable
+macro_rules! dispatched {
+    ($kind: ident, $state: expr, $instances: ident) => {{
+        let state = &mut $state;
+        let update: Box<dyn FnOnce() -> Result<usize, Unavailable> + Send + '_> =
+            Box::new(move || synced!($kind, *state, $instances));
+
+        (StateKind::$kind, update)
+    }};
+}
+
+/// Classes [`Windows::capture_extra_properties`] covers, those implementing
+/// [`Referenced`](crate::association::Referenced) so extras can be matched back to their instance
+macro_rules! extra_classes {
+    ($capture: ident) => {
+        #[cfg(feature = "os-processes")]
+        $capture!(processes::Win32_Process);
+        #[cfg(feature = "os-services")]
+        $capture!(services::Win32_Service);
+        #[cfg(feature = "os-users")]
+        $capture!(users::Win32_UserAccount);
+        #[cfg(feature = "os-users")]
+        $capture!(users::Win32_Group);
+        #[cfg(feature = "os-users")]
+        $capture!(users::Win32_SystemAccount);
+        #[cfg(feature = "os-file-system")]
+        $capture!(file_system::Win32_DiskPartition);
+        #[cfg(feature = "os-file-system")]
+        $capture!(file_system::Win32_LogicalDisk);
+        #[cfg(feature = "os-operating-system-settings")]
+        $capture!(operating_system_settings::Win32_ComputerSystem);
+        #[cfg(feature = "hw-mass-storage")]
+        $capture!(mass_storage::Win32_DiskDrive);
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        $capture!(motherboard_controller_port::Win32_PnPEntity);
+    };
+}
+
+/// A member of [`Windows`], whatever class it holds, for tooling such as exporters, diffing or
+/// user interfaces that treats every member the same way
+///
+/// Implemented by every state, see [`Windows::members`].
+pub trait StateMember {
+    /// Name of the field holding the instances of the state, e.g. `processes`
+    fn name(&self) -> &'static str;
+
+    /// Name of the type of the instances of the state, the WMI class for most states, e.g.
+    /// `Win32_Process`
+    fn class(&self) -> &'static str;
+
+    /// First segment of the [paths](crate::path) addressing the state, `hw` for the classes in
+    /// `crate::hardware` and `os` for the others
+    fn category(&self) -> &'static str;
+
+    /// When the state was last updated
+    fn last_updated(&self) -> SystemTime;
+
+    /// Whether the last update changed the state
+    fn state_change(&self) -> bool;
+
+    /// Why the last update could not query the state, `None` when it could
+    fn unavailable(&self) -> Option<&Unavailable>;
+
+    /// Number of instances the state holds
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the state holds no instance
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The state serialized as JSON, as it appears in a snapshot
+    fn to_json(&self) -> serde_json::Value;
+
+    /// Takes the instances of `partial`, with the data derived from them and the outcome of its
+    /// last update, keeping the configuration of this state, e.g. the roots walked or the sources
+    /// read
+    fn merge_from(&mut self, partial: &mut Self)
+    where
+        Self: Sized;
+
+    /// How the last update was truncated by the [`RowLimit`](crate::limits::RowLimit) of the
+    /// class, `None` if every instance was kept
+    fn truncation(&self) -> Option<crate::limits::Truncation> {
+        crate::limits::truncation(self.class())
+    }
+}
 
 /// Our main struct
 ///
@@ -15,100 +1753,239 @@ use tokio::join;
 #[derive(Default, Deserialize, Serialize, Debug, Clone, Hash)]
 pub struct Windows {
     /// State of Windows Processes
+    #[cfg(feature = "os-processes")]
     pub processes: processes::Processes,
     /// State of Windows Threads
+    #[cfg(feature = "os-processes")]
     pub threads: processes::Threads,
+    /// State of Windows processor utilization
+    #[cfg(feature = "os-perf")]
+    pub processor_performances: perf::ProcessorPerformances,
+    /// State of Windows memory usage
+    #[cfg(feature = "os-perf")]
+    pub memory_performances: perf::MemoryPerformances,
+    /// State of Windows logical disk activity
+    #[cfg(feature = "os-perf")]
+    pub logical_disk_performances: perf::LogicalDiskPerformances,
+    /// State of Windows GPU engine utilization
+    #[cfg(feature = "os-perf")]
+    pub gpu_engine_performances: perf::GPUEnginePerformances,
     /// State of Windows Drivers
+    #[cfg(feature = "os-drivers")]
     pub drivers: drivers::Drivers,
     /// State of Windows Registry
+    #[cfg(feature = "os-registry")]
     pub registry: registry::Registry,
+    /// State of Windows Registry keys in persistence locations
+    #[cfg(feature = "os-registry")]
+    pub registry_keys: registry::RegistryKeys,
+    /// State of Windows DCOM applications
+    #[cfg(feature = "os-com")]
+    pub dcom_applications: com::DCOMApplications,
+    /// State of Windows DCOM application settings
+    #[cfg(feature = "os-com")]
+    pub dcom_application_settings: com::DCOMApplicationSettings,
+    /// State of Windows COM settings
+    #[cfg(feature = "os-com")]
+    pub com_settings: com::COMSettings,
+    /// State of Windows COM class settings
+    #[cfg(feature = "os-com")]
+    pub classic_com_class_settings: com::ClassicCOMClassSettings,
+    /// State of Windows Trusted Platform Modules
+    #[cfg(feature = "os-device-security")]
+    pub tpms: device_security::Tpms,
+    /// State of Windows Secure Boot
+    #[cfg(feature = "os-device-security")]
+    pub secure_boot: device_security::SecureBoot,
+    /// State of Windows BitLocker volumes
+    #[cfg(feature = "os-device-security")]
+    pub encryptable_volumes: device_security::EncryptableVolumes,
+    /// State of Windows antivirus products
+    #[cfg(feature = "os-security-center")]
+    pub anti_virus_products: security_center::AntiVirusProducts,
+    /// State of Windows firewall products
+    #[cfg(feature = "os-security-center")]
+    pub firewall_products: security_center::FirewallProducts,
+    /// State of Windows antispyware products
+    #[cfg(feature = "os-security-center")]
+    pub anti_spyware_products: security_center::AntiSpywareProducts,
     /// State of Windows Services
+    #[cfg(feature = "os-services")]
     pub services: services::Services,
+    /// State of Windows DependentServices
+    #[cfg(feature = "os-services")]
+    pub dependent_services: services::DependentServices,
     /// State of Windows Desktops
+    #[cfg(feature = "os-desktop")]
     pub desktops: desktop::Desktops,
     /// State of Windows Environments
+    #[cfg(feature = "os-desktop")]
     pub environment: desktop::Environments,
     /// State of Windows TimeZones
+    #[cfg(feature = "os-desktop")]
     pub timezones: desktop::TimeZones,
     /// State of Windows User Accounts
+    #[cfg(feature = "os-users")]
     pub user_accounts: users::UserAccounts,
     /* This fails for now as a string is returned on object
     /// Relation of user account and desktop settings that are specific to it
+    #[cfg(feature = "os-desktop")]
     pub user_desktops: desktop::UserDesktops,
     */
     /* Abstract class, don't need it
     /// State of Windows user accounts and group accounts
+    #[cfg(feature = "os-users")]
     pub accounts: users::Accounts,
     */
     /// State of Windows Groups
+    #[cfg(feature = "os-users")]
     pub groups: users::Groups,
     /// State of Windows Logon Sessions
+    #[cfg(feature = "os-users")]
     pub logon_sessions: users::LogonSessions,
     /// State of Windows Network Logins
+    #[cfg(feature = "os-users")]
     pub network_login_profiles: users::NetworkLoginProfiles,
     /// State of Windows System Accounts
+    #[cfg(feature = "os-users")]
     pub system_accounts: users::SystemAccounts,
+    /// State of Windows local groups and their members
+    #[cfg(feature = "os-users")]
+    pub local_groups: users::LocalGroups,
+    /// State of Windows user profiles
+    #[cfg(feature = "os-users")]
+    pub user_profiles: users::UserProfiles,
     /// State of windows Directory
+    #[cfg(feature = "os-file-system")]
     pub directories: file_system::Directories,
     /// State of windows Directory Specifications
+    #[cfg(feature = "os-file-system")]
     pub directories_specifications: file_system::DirectorySpecifications,
     /// State of windows Directory Disk Partitions
+    #[cfg(feature = "os-file-system")]
     pub disk_partition: file_system::DiskPartitions,
+    /// State of Windows DiskDriveToDiskPartitions
+    #[cfg(feature = "os-file-system")]
+    pub disk_drive_to_disk_partitions: file_system::DiskDriveToDiskPartitions,
     /// State of windows Logical Disks
+    #[cfg(feature = "os-file-system")]
     pub logical_disks: file_system::LogicalDisks,
+    /// State of Windows LogicalDiskToPartitions
+    #[cfg(feature = "os-file-system")]
+    pub logical_disk_to_partitions: file_system::LogicalDiskToPartitions,
     /// State of windows Mapped Logical Disks
+    #[cfg(feature = "os-file-system")]
     pub mapped_logical_disks: file_system::MappedLogicalDisks,
     /// State of windows Quota Settings
+    #[cfg(feature = "os-file-system")]
     pub quota_settings: file_system::QuotaSettings,
     /// State of windows Shortcut Files
+    #[cfg(feature = "os-file-system")]
     pub shortcut_files: file_system::ShortcutFiles,
     /// State of windows Volumes
+    #[cfg(feature = "os-file-system")]
     pub volumes: file_system::Volumes,
     /// State of windows NTEventLogFiles
+    #[cfg(feature = "os-event-log")]
     pub nt_event_log_files: event_log::NTEventlogFiles,
     /// State of windows NTLogEvents
+    #[cfg(feature = "os-event-log")]
     pub nt_log_events: event_log::NTLogEvents,
+    /// State of windows boot history
+    #[cfg(feature = "os-event-log")]
+    pub boot_history: event_log::BootHistory,
+    /// State of the last boot and the shutdown before it
+    #[cfg(feature = "os-event-log")]
+    pub boot_infos: event_log::BootInfos,
     /// State of windows PageFiles
+    #[cfg(feature = "os-memory-and-pagefiles")]
     pub pagefiles: memory_and_pagefiles::PageFiles,
     /// State of windows PageFileSettings
+    #[cfg(feature = "os-memory-and-pagefiles")]
     pub pagefile_settings: memory_and_pagefiles::PageFileSettings,
     /// State of windows PageFileUsages
+    #[cfg(feature = "os-memory-and-pagefiles")]
     pub pagefile_usages: memory_and_pagefiles::PageFileUsages,
     /// State of windows ScheduledJobs
+    #[cfg(feature = "os-scheduler-jobs")]
     pub scheduled_jobs: scheduler_jobs::ScheduledJobs,
+    /// State of Windows Task Scheduler tasks
+    #[cfg(feature = "os-scheduler-jobs")]
+    pub scheduled_tasks: scheduler_jobs::ScheduledTasks,
     /// State of windows LocalTimes
+    #[cfg(feature = "os-scheduler-jobs")]
     pub local_times: scheduler_jobs::LocalTimes,
     /// State of windows UTCTimes
+    #[cfg(feature = "os-scheduler-jobs")]
     pub utc_times: scheduler_jobs::UTCTimes,
+    /// State of Windows Time service configuration
+    #[cfg(feature = "os-time-service")]
+    pub time_service: time_service::TimeService,
+    /// State of Windows Update
+    #[cfg(feature = "os-updates")]
+    pub windows_updates: updates::WindowsUpdates,
+    /// State of the local security policy
+    #[cfg(feature = "os-security-settings")]
+    pub security_settings: security_settings::SecuritySettings,
+    /// State of the Group Policy objects processed for the computer
+    #[cfg(feature = "os-group-policy")]
+    pub gpos: group_policy::GPOs,
+    /// State of the links between Group Policy objects and their scopes
+    #[cfg(feature = "os-group-policy")]
+    pub gp_links: group_policy::GPLinks,
+    /// State of the Group Policy client-side extensions
+    #[cfg(feature = "os-group-policy")]
+    pub gp_extension_statuses: group_policy::GPExtensionStatuses,
     /// State of windows Proxys
+    #[cfg(feature = "os-product-activation")]
     pub proxys: product_activation::Proxys,
     /// State of windows WindowsProductActivations
+    #[cfg(feature = "os-product-activation")]
     pub windows_product_activations: product_activation::WindowsProductActivations,
     /// State of windows SoftwareLicensingProducts
+    #[cfg(feature = "os-software-license-provider")]
     pub software_licensing_products: software_license_provider::SoftwareLicensingProducts,
     /// State of windows SoftwareLicensingServices
+    #[cfg(feature = "os-software-license-provider")]
     pub software_licensing_services: software_license_provider::SoftwareLicensingServices,
     /// State of windows SoftwareLicensingTokenActivationLicenses
+    #[cfg(feature = "os-software-license-provider")]
     pub software_licensing_token_activation_licenses: software_license_provider::SoftwareLicensingTokenActivationLicenses,
     /// State of windows ServerConnections
+    #[cfg(feature = "os-shares")]
     pub server_connections: shares::ServerConnections,
     /// State of windows ServerSessions
+    #[cfg(feature = "os-shares")]
     pub server_sessions: shares::ServerSessions,
     /// State of windows Shares
+    #[cfg(feature = "os-shares")]
     pub shares: shares::Shares,
+    /// Share-level permissions of the Windows Shares
+    #[cfg(feature = "os-shares")]
+    pub share_security_descriptors: shares::ShareSecurityDescriptors,
     /// State of Windows CodecFiles
+    #[cfg(feature = "os-multimedia-audio-visual")]
     pub codec_files: multimedia_audio_visual::CodecFiles,
+    /// State of Windows camera and microphone devices and privacy settings
+    #[cfg(feature = "os-capture-privacy")]
+    pub capture_privacy: capture_privacy::CapturePrivacy,
     /// State of Windows ShadowCopys
+    #[cfg(feature = "os-storage")]
     pub shadow_copys: storage::ShadowCopys,
     /// State of Windows ShadowContexts
+    #[cfg(feature = "os-storage")]
     pub shadow_contexts: storage::ShadowContexts,
     /// State of Windows ShadowProviders
+    #[cfg(feature = "os-storage")]
     pub shadow_providers: storage::ShadowProviders,
     /// State of Windows LogicalFileSecuritySettings
+    #[cfg(feature = "os-security")]
     pub logical_file_security_settings: security::LogicalFileSecuritySettings,
     /// State of Windows LogicalShareSecuritySettings
+    #[cfg(feature = "os-security")]
     pub logical_share_security_settings: security::LogicalShareSecuritySettings,
     /// State of Windows PrivilegesStatuses
+    #[cfg(feature = "os-security")]
     pub privileges_statuses: security::PrivilegesStatuses,
     // /// State of Windows Trustees
     // pub trustees: security::Trustees,
@@ -119,277 +1996,2391 @@ pub struct Windows {
     // /// State of Windows SecuritySettings
     // pub security_settings: security::SecuritySettings,
     /// State of Windows LogicalProgramGroups
+    #[cfg(feature = "os-start-menu")]
     pub logical_program_groups: start_menu::LogicalProgramGroups,
     /// State of Windows LogicalProgramGroupItems
+    #[cfg(feature = "os-start-menu")]
     pub logical_program_group_items: start_menu::LogicalProgramGroupItems,
     // /// State of Windows ProgramGroupOrItems
     // pub program_group_or_items: start_menu::ProgramGroupOrItems,
     /// State of Windows IP4PersistedRouteTables
+    #[cfg(feature = "os-networking")]
     pub ip4_persisted_route_tables: networking::IP4PersistedRouteTables,
     /// State of Windows IP4RouteTables
+    #[cfg(feature = "os-networking")]
     pub ip4_route_tables: networking::IP4RouteTables,
     /// State of Windows NetworkClients
+    #[cfg(feature = "os-networking")]
     pub nework_clients: networking::NetworkClients,
     /// State of Windows NetworkConnections
+    #[cfg(feature = "os-networking")]
     pub nework_connections: networking::NetworkConnections,
     /// State of Windows NetworkProtocols
+    #[cfg(feature = "os-networking")]
     pub nework_protocols: networking::NetworkProtocols,
     /// State of Windows NTDomains
+    #[cfg(feature = "os-networking")]
     pub nt_domains: networking::NTDomains,
     /// State of Windows IP4RouteTableEvents
+    #[cfg(feature = "os-networking")]
     pub ip4_route_table_events: networking::IP4RouteTableEvents,
+    /// State of Windows QosPolicies
+    #[cfg(feature = "os-networking")]
+    pub qos_policies: networking::QosPolicies,
+    /// State of Windows firewall profiles
+    #[cfg(feature = "os-firewall")]
+    pub firewall_profiles: firewall::FirewallProfiles,
+    /// State of Windows firewall rules
+    #[cfg(feature = "os-firewall")]
+    pub firewall_rules: firewall::FirewallRules,
+    /// State of Windows firewall port filters
+    #[cfg(feature = "os-firewall")]
+    pub firewall_port_filters: firewall::FirewallPortFilters,
+    /// State of Windows firewall application filters
+    #[cfg(feature = "os-firewall")]
+    pub firewall_application_filters: firewall::FirewallApplicationFilters,
+    /// State of Windows firewall address filters
+    #[cfg(feature = "os-firewall")]
+    pub firewall_address_filters: firewall::FirewallAddressFilters,
+    /// State of Windows TCP connections
+    #[cfg(feature = "os-connections")]
+    pub tcp_connections: connections::TcpConnections,
+    /// State of Windows UDP endpoints
+    #[cfg(feature = "os-connections")]
+    pub udp_endpoints: connections::UdpEndpoints,
+    /// State of Windows neighbor cache
+    #[cfg(feature = "os-connections")]
+    pub net_neighbors: connections::NetNeighbors,
+    /// State of Windows DNS client cache
+    #[cfg(feature = "os-dns")]
+    pub dns_client_cache: dns::DnsClientCache,
+    /// State of Windows hosts file
+    #[cfg(feature = "os-dns")]
+    pub hosts_file: dns::HostsFile,
     // /// State of Windows LUIDs
     // pub luids: job_objects::LUIDs,
     // /// State of Windows LUIDandAttributes
     // pub luid_and_attributes: job_objects::LUIDandAttributes,
     /// State of Windows NamedJobObjects
+    #[cfg(feature = "os-job-objects")]
     pub named_job_objects: job_objects::NamedJobObjects,
     /// State of Windows NamedJobObjectActgInfos
+    #[cfg(feature = "os-job-objects")]
     pub named_job_object_actg_infos: job_objects::NamedJobObjectActgInfos,
     /// State of Windows NamedJobObjectLimitSettings
+    #[cfg(feature = "os-job-objects")]
     pub named_job_object_limit_settings: job_objects::NamedJobObjectLimitSettings,
     /// State of Windows BootConfigurations
+    #[cfg(feature = "os-operating-system-settings")]
     pub boot_configurations: operating_system_settings::BootConfigurations,
     /// State of Windows ComputerSystems
+    #[cfg(feature = "os-operating-system-settings")]
     pub computer_systems: operating_system_settings::ComputerSystems,
+    /// State of Windows SystemServices
+    #[cfg(feature = "os-operating-system-settings")]
+    pub system_services: operating_system_settings::SystemServices,
     /// State of Windows ComputerSystemProducts
+    #[cfg(feature = "os-operating-system-settings")]
     pub computer_system_products: operating_system_settings::ComputerSystemProducts,
     /// State of Windows LoadOrderGroups
+    #[cfg(feature = "os-operating-system-settings")]
     pub load_order_groups: operating_system_settings::LoadOrderGroups,
     /// State of Windows OperatingSystems
+    #[cfg(feature = "os-operating-system-settings")]
     pub operating_systems: operating_system_settings::OperatingSystems,
     /// State of Windows OSRecoveryConfigurations
+    #[cfg(feature = "os-operating-system-settings")]
     pub os_recovery_configurations: operating_system_settings::OSRecoveryConfigurations,
     /// State of Windows QuickFixEngineerings
+    #[cfg(feature = "os-operating-system-settings")]
     pub quick_fix_engineerings: operating_system_settings::QuickFixEngineerings,
+    /// State of Windows optional features
+    #[cfg(feature = "os-optional-features")]
+    pub optional_features: optional_features::OptionalFeatures,
+    /// State of Windows Server roles and features
+    #[cfg(feature = "os-optional-features")]
+    pub server_features: optional_features::ServerFeatures,
     /// State of Windows StartupCommands
+    #[cfg(feature = "os-operating-system-settings")]
     pub startup_commands: operating_system_settings::StartupCommands,
+    /// State of the items Windows runs at boot or logon, from every autostart source
+    #[cfg(feature = "os-persistence")]
+    pub persistence_items: persistence::PersistenceItems,
+    /// State of Windows InstalledSoftware
+    #[cfg(feature = "os-installed-software")]
+    pub installed_software: installed_software::InstalledSoftware,
     /// State of Windows Fans
+    #[cfg(feature = "hw-cooling-device")]
     pub fans: cooling_device::Fans,
     /// State of Windows HeatPipes
+    #[cfg(feature = "hw-cooling-device")]
     pub heat_pipes: cooling_device::HeatPipes,
     /// State of Windows Refrigerations
+    #[cfg(feature = "hw-cooling-device")]
     pub refrigerations: cooling_device::Refrigerations,
     /// State of Windows TemperatureProbes
+    #[cfg(feature = "hw-cooling-device")]
     pub temperature_probes: cooling_device::TemperatureProbes,
     /// State of Windows Keyboards
+    #[cfg(feature = "hw-input-device")]
     pub keyboards: input_device::Keyboards,
     /// State of Windows PointingDevices
+    #[cfg(feature = "hw-input-device")]
     pub pointing_devices: input_device::PointingDevices,
+    /// Keyboards and pointing devices added or removed between updates
+    #[cfg(feature = "hw-input-device")]
+    pub input_device_events: input_device::InputDeviceEvents,
     /// State of Windows AutochkSettings
+    #[cfg(feature = "hw-mass-storage")]
     pub autochk_settings: mass_storage::AutochkSettings,
     /// State of Windows CDROMDrives
+    #[cfg(feature = "hw-mass-storage")]
     pub cd_rom_drives: mass_storage::CDROMDrives,
     /// State of Windows DiskDrives
+    #[cfg(feature = "hw-mass-storage")]
     pub disk_drives: mass_storage::DiskDrives,
     /// State of Windows PhysicalMedias
+    #[cfg(feature = "hw-mass-storage")]
     pub physical_medias: mass_storage::PhysicalMedias,
     /// State of Windows TapeDrives
+    #[cfg(feature = "hw-mass-storage")]
     pub tape_drives: mass_storage::TapeDrives,
+    /// State of Windows PhysicalDisks
+    #[cfg(feature = "hw-mass-storage")]
+    pub physical_disks: mass_storage::PhysicalDisks,
+    /// State of Windows NVMe capabilities
+    #[cfg(feature = "hw-mass-storage")]
+    pub nvme_capabilities: mass_storage::NvmeCapabilities,
+    /// State of Windows Disks
+    #[cfg(feature = "hw-mass-storage")]
+    pub disks: mass_storage::Disks,
+    /// State of Windows disk reliability counters
+    #[cfg(feature = "hw-mass-storage")]
+    pub storage_reliability_counters: mass_storage::StorageReliabilityCounters,
+    /// State of Windows disk failure predictions
+    #[cfg(feature = "hw-mass-storage")]
+    pub failure_predict_statuses: mass_storage::FailurePredictStatuses,
+    /// State of Windows PnPEntities
+    #[cfg(feature = "hw-motherboard-controller-port")]
+    pub pnp_entities: motherboard_controller_port::PnPEntities,
+    /// State of Windows BIOSes
+    #[cfg(feature = "hw-motherboard-controller-port")]
+    pub bioses: motherboard_controller_port::BIOSes,
+    /// State of Windows SystemEnclosures
+    #[cfg(feature = "hw-motherboard-controller-port")]
+    pub system_enclosures: motherboard_controller_port::SystemEnclosures,
+    /// State of Windows SerialPortConfigurations
+    #[cfg(feature = "hw-motherboard-controller-port")]
+    pub serial_port_configurations: motherboard_controller_port::SerialPortConfigurations,
+    /// State of Windows ParallelPorts
+    #[cfg(feature = "hw-motherboard-controller-port")]
+    pub parallel_ports: motherboard_controller_port::ParallelPorts,
+    /// State of Windows USB devices
+    #[cfg(feature = "hw-usb")]
+    pub usb_devices: usb::UsbDevices,
     /// State of Windows NetworkAdapters
+    #[cfg(feature = "hw-networking-device")]
     pub network_adapters: networking_device::NetworkAdapters,
     /// State of Windows NetworkAdapterConfigurations
+    #[cfg(feature = "hw-networking-device")]
     pub network_adapter_configurations: networking_device::NetworkAdapterConfigurations,
+    /// State of the `MSFT_NetAdapter` network adapters, classified by `MSFT_NetAdapter::class`
+    #[cfg(feature = "hw-networking-device")]
+    pub net_adapters: networking_device::NetAdapters,
     /// State of Windows POTSModems
+    #[cfg(feature = "hw-telephony")]
     pub pot_modems: telephony::POTSModems,
+    /// State of Windows POTSModemToSerialPorts
+    #[cfg(feature = "hw-telephony")]
+    pub pot_modem_to_serial_ports: telephony::POTSModemToSerialPorts,
     /// State of Windows Batteries
+    #[cfg(feature = "hw-power")]
     pub batteries: power::Batteries,
     /// State of Windows CurrentProbes
+    #[cfg(feature = "hw-power")]
     pub current_probes: power::CurrentProbes,
     /// State of Windows PortableBatteries
+    #[cfg(feature = "hw-power")]
     pub portable_batteries: power::PortableBatteries,
     /// State of Windows PowerManagementEvents
+    #[cfg(feature = "hw-power")]
     pub power_management_events: power::PowerManagementEvents,
     /// State of Windows VoltageProbes
+    #[cfg(feature = "hw-power")]
     pub voltage_probes: power::VoltageProbes,
+    /// State of Windows Printers
+    #[cfg(feature = "hw-printing")]
+    pub printers: printing::Printers,
+    /// State of Windows PrinterDrivers
+    #[cfg(feature = "hw-printing")]
+    pub printer_drivers: printing::PrinterDrivers,
+    /// State of Windows DriverForDevices
+    #[cfg(feature = "hw-printing")]
+    pub driver_for_devices: printing::DriverForDevices,
+    /// State of Windows TCPIPPrinterPorts
+    #[cfg(feature = "hw-printing")]
+    pub tcpip_printer_ports: printing::TCPIPPrinterPorts,
     /// State of Windows DesktopMonitors
+    #[cfg(feature = "hw-video-monitor")]
     pub desktop_monitors: video_monitor::DesktopMonitors,
     /// State of Windows DisplayControllerConfigurations
+    #[cfg(feature = "hw-video-monitor")]
     pub display_controller_configurations: video_monitor::DisplayControllerConfigurations,
     /// State of Windows VideoControllers
+    #[cfg(feature = "hw-video-monitor")]
     pub video_controllers: video_monitor::VideoControllers,
+    /// State of Windows BluetoothDevices
+    #[cfg(feature = "bluetooth")]
+    pub bluetooth_devices: bluetooth::BluetoothDevices,
+    /// State of Hyper-V virtual machines
+    #[cfg(feature = "hyperv")]
+    pub virtual_machines: virtualization::VirtualMachines,
+    /// State of the virtual processors assigned to Hyper-V virtual machines
+    #[cfg(feature = "hyperv")]
+    pub virtual_processor_settings: virtualization::VirtualProcessorSettings,
+    /// State of the memory assigned to Hyper-V virtual machines
+    #[cfg(feature = "hyperv")]
+    pub virtual_memory_settings: virtualization::VirtualMemorySettings,
+    /// Provenance of the snapshot: host, capture time, versions and how each member update went
+    #[serde(default)]
+    pub meta: SnapshotMeta,
+    /// Properties of the instances their structs do not model, by class, filled in by
+    /// [`Windows::capture_extra_properties`]
+    #[serde(default)]
+    pub extra_properties: BTreeMap<String, Vec<ExtraProperties>>,
+    /// Members backed by WQL queries of the user's own, by name, see [`Windows::register_custom`]
+    #[serde(default)]
+    pub custom: BTreeMap<String, CustomState>,
 }
 
 impl Windows {
     /// Synchronously update all the fields
     pub fn update(&mut self) {
-        self.processes.update();
-        self.threads.update();
-        self.drivers.update();
-        self.registry.update();
-        self.services.update();
-        self.desktops.update();
-        self.environment.update();
-        self.timezones.update();
-        self.user_accounts.update();
-        // self.user_desktops.update();
-        // self.accounts.update();
-        self.groups.update();
-        self.logon_sessions.update();
-        self.network_login_profiles.update();
-        self.system_accounts.update();
-        self.directories.update();
-        self.directories_specifications.update();
-        self.disk_partition.update();
-        self.logical_disks.update();
-        self.mapped_logical_disks.update();
-        self.quota_settings.update();
-        self.shortcut_files.update();
-        self.volumes.update();
-        self.nt_event_log_files.update();
-        self.nt_log_events.update();
-        self.pagefiles.update();
-        self.pagefile_settings.update();
-        self.pagefile_usages.update();
-        self.scheduled_jobs.update();
-        self.local_times.update();
-        self.utc_times.update();
-        self.software_licensing_products.update();
-        self.software_licensing_services.update();
-        self.software_licensing_token_activation_licenses.update();
-        self.server_connections.update();
-        self.server_sessions.update();
-        self.shares.update();
-        self.codec_files.update();
-        self.shadow_copys.update();
-        self.shadow_contexts.update();
-        self.shadow_providers.update();
-        self.logical_file_security_settings.update();
-        self.logical_share_security_settings.update();
-        self.privileges_statuses.update();
-        self.logical_program_groups.update();
-        self.logical_program_group_items.update();
-        self.ip4_persisted_route_tables.update();
-        self.ip4_route_tables.update();
-        self.nework_clients.update();
-        self.nework_connections.update();
-        self.nework_protocols.update();
-        self.nt_domains.update();
-        self.ip4_route_table_events.update();
-        self.named_job_objects.update();
-        self.named_job_object_actg_infos.update();
-        self.named_job_object_limit_settings.update();
-        self.boot_configurations.update();
-        self.computer_systems.update();
-        self.computer_system_products.update();
-        self.load_order_groups.update();
-        self.operating_systems.update();
-        self.os_recovery_configurations.update();
-        self.quick_fix_engineerings.update();
-        self.startup_commands.update();
-        self.fans.update();
-        self.heat_pipes.update();
-        self.refrigerations.update();
-        self.temperature_probes.update();
-        self.keyboards.update();
-        self.pointing_devices.update();
-        self.autochk_settings.update();
-        self.cd_rom_drives.update();
-        self.disk_drives.update();
-        self.physical_medias.update();
-        self.tape_drives.update();
-        self.network_adapters.update();
-        self.network_adapter_configurations.update();
-        self.pot_modems.update();
-        self.batteries.update();
-        self.current_probes.update();
-        self.portable_batteries.update();
-        self.power_management_events.update();
-        self.voltage_probes.update();
-        self.desktop_monitors.update();
-        self.display_controller_configurations.update();
-        self.video_controllers.update();
-    }
-
-    /// Asynchronously update all the fields
+        self.meta.start();
+
+        for kind in StateKind::ALL {
+            let _ = self.update_member(*kind);
+        }
+
+        self.update_custom();
+        self.meta.finish();
+    }
+
+    /// Adds the custom member `name`, replacing and returning the member of that name if any
+    ///
+    /// Custom members are updated by the updates of every member, such as [`Windows::update`] or
+    /// [`Windows::async_update`], not by the updates of a [`SnapshotProfile`]; use
+    /// [`Windows::update_custom`] for those.
+    pub fn register_custom(&mut self, name: &str, state: CustomState) -> Option<CustomState> {
+        self.custom.insert(name.to_string(), state)
+    }
+
+    /// Removes and returns the custom member `name`, `None` if there is none
+    pub fn unregister_custom(&mut self, name: &str) -> Option<CustomState> {
+        self.custom.remove(name)
+    }
+
+    /// Synchronously update the custom members
+    pub fn update_custom(&mut self) {
+        for state in self.custom.values_mut() {
+            state.update();
+        }
+    }
+
+    /// Asynchronously update the custom members, one query at a time
+    pub async fn async_update_custom(&mut self) {
+        for state in self.custom.values_mut() {
+            state.async_update().await;
+        }
+    }
+
+    /// The member `kind`, to treat it like any other member
+    pub fn member(&self, kind: StateKind) -> &dyn StateMember {
+        match kind {
+            #[cfg(feature = "os-processes")]
+            StateKind::Processes => &self.processes,
+            #[cfg(feature = "os-processes")]
+            StateKind::Threads => &self.threads,
+            #[cfg(feature = "os-perf")]
+            StateKind::ProcessorPerformances => &self.processor_performances,
+            #[cfg(feature = "os-perf")]
+            StateKind::MemoryPerformances => &self.memory_performances,
+            #[cfg(feature = "os-perf")]
+            StateKind::LogicalDiskPerformances => &self.logical_disk_performances,
+            #[cfg(feature = "os-perf")]
+            StateKind::GPUEnginePerformances => &self.gpu_engine_performances,
+            #[cfg(feature = "os-drivers")]
+            StateKind::Drivers => &self.drivers,
+            #[cfg(feature = "os-registry")]
+            StateKind::Registry => &self.registry,
+            #[cfg(feature = "os-registry")]
+            StateKind::RegistryKeys => &self.registry_keys,
+            #[cfg(feature = "os-com")]
+            StateKind::DcomApplications => &self.dcom_applications,
+            #[cfg(feature = "os-com")]
+            StateKind::DcomApplicationSettings => &self.dcom_application_settings,
+            #[cfg(feature = "os-com")]
+            StateKind::ComSettings => &self.com_settings,
+            #[cfg(feature = "os-com")]
+            StateKind::ClassicComClassSettings => &self.classic_com_class_settings,
+            #[cfg(feature = "os-device-security")]
+            StateKind::Tpms => &self.tpms,
+            #[cfg(feature = "os-device-security")]
+            StateKind::SecureBoot => &self.secure_boot,
+            #[cfg(feature = "os-device-security")]
+            StateKind::EncryptableVolumes => &self.encryptable_volumes,
+            #[cfg(feature = "os-security-center")]
+            StateKind::AntiVirusProducts => &self.anti_virus_products,
+            #[cfg(feature = "os-security-center")]
+            StateKind::FirewallProducts => &self.firewall_products,
+            #[cfg(feature = "os-security-center")]
+            StateKind::AntiSpywareProducts => &self.anti_spyware_products,
+            #[cfg(feature = "os-services")]
+            StateKind::Services => &self.services,
+            #[cfg(feature = "os-services")]
+            StateKind::DependentServices => &self.dependent_services,
+            #[cfg(feature = "os-desktop")]
+            StateKind::Desktops => &self.desktops,
+            #[cfg(feature = "os-desktop")]
+            StateKind::Environment => &self.environment,
+            #[cfg(feature = "os-desktop")]
+            StateKind::Timezones => &self.timezones,
+            #[cfg(feature = "os-users")]
+            StateKind::UserAccounts => &self.user_accounts,
+            #[cfg(feature = "os-users")]
+            StateKind::Groups => &self.groups,
+            #[cfg(feature = "os-users")]
+            StateKind::LogonSessions => &self.logon_sessions,
+            #[cfg(feature = "os-users")]
+            StateKind::NetworkLoginProfiles => &self.network_login_profiles,
+            #[cfg(feature = "os-users")]
+            StateKind::SystemAccounts => &self.system_accounts,
+            #[cfg(feature = "os-users")]
+            StateKind::LocalGroups => &self.local_groups,
+            #[cfg(feature = "os-users")]
+            StateKind::UserProfiles => &self.user_profiles,
+            #[cfg(feature = "os-file-system")]
+            StateKind::Directories => &self.directories,
+            #[cfg(feature = "os-file-system")]
+            StateKind::DirectoriesSpecifications => &self.directories_specifications,
+            #[cfg(feature = "os-file-system")]
+            StateKind::DiskPartition => &self.disk_partition,
+            #[cfg(feature = "os-file-system")]
+            StateKind::DiskDriveToDiskPartitions => &self.disk_drive_to_disk_partitions,
+            #[cfg(feature = "os-file-system")]
+            StateKind::LogicalDisks => &self.logical_disks,
+            #[cfg(feature = "os-file-system")]
+            StateKind::LogicalDiskToPartitions => &self.logical_disk_to_partitions,
+            #[cfg(feature = "os-file-system")]
+            StateKind::MappedLogicalDisks => &self.mapped_logical_disks,
+            #[cfg(feature = "os-file-system")]
+            StateKind::QuotaSettings => &self.quota_settings,
+            #[cfg(feature = "os-file-system")]
+            StateKind::ShortcutFiles => &self.shortcut_files,
+            #[cfg(feature = "os-file-system")]
+            StateKind::Volumes => &self.volumes,
+            #[cfg(feature = "os-event-log")]
+            StateKind::NtEventLogFiles => &self.nt_event_log_files,
+            #[cfg(feature = "os-event-log")]
+            StateKind::NtLogEvents => &self.nt_log_events,
+            #[cfg(feature = "os-event-log")]
+            StateKind::BootHistory => &self.boot_history,
+            #[cfg(feature = "os-event-log")]
+            StateKind::BootInfos => &self.boot_infos,
+            #[cfg(feature = "os-memory-and-pagefiles")]
+            StateKind::Pagefiles => &self.pagefiles,
+            #[cfg(feature = "os-memory-and-pagefiles")]
+            StateKind::PagefileSettings => &self.pagefile_settings,
+            #[cfg(feature = "os-memory-and-pagefiles")]
+            StateKind::PagefileUsages => &self.pagefile_usages,
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::ScheduledJobs => &self.scheduled_jobs,
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::ScheduledTasks => &self.scheduled_tasks,
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::LocalTimes => &self.local_times,
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::UtcTimes => &self.utc_times,
+            #[cfg(feature = "os-time-service")]
+            StateKind::TimeService => &self.time_service,
+            #[cfg(feature = "os-updates")]
+            StateKind::WindowsUpdates => &self.windows_updates,
+            #[cfg(feature = "os-security-settings")]
+            StateKind::SecuritySettings => &self.security_settings,
+            #[cfg(feature = "os-group-policy")]
+            StateKind::GPOs => &self.gpos,
+            #[cfg(feature = "os-group-policy")]
+            StateKind::GPLinks => &self.gp_links,
+            #[cfg(feature = "os-group-policy")]
+            StateKind::GPExtensionStatuses => &self.gp_extension_statuses,
+            #[cfg(feature = "os-software-license-provider")]
+            StateKind::SoftwareLicensingProducts => &self.software_licensing_products,
+            #[cfg(feature = "os-software-license-provider")]
+            StateKind::SoftwareLicensingServices => &self.software_licensing_services,
+            #[cfg(feature = "os-software-license-provider")]
+            StateKind::SoftwareLicensingTokenActivationLicenses => &self.software_licensing_token_activation_licenses,
+            #[cfg(feature = "os-shares")]
+            StateKind::ServerConnections => &self.server_connections,
+            #[cfg(feature = "os-shares")]
+            StateKind::ServerSessions => &self.server_sessions,
+            #[cfg(feature = "os-shares")]
+            StateKind::Shares => &self.shares,
+            #[cfg(feature = "os-shares")]
+            StateKind::ShareSecurityDescriptors => &self.share_security_descriptors,
+            #[cfg(feature = "os-multimedia-audio-visual")]
+            StateKind::CodecFiles => &self.codec_files,
+            #[cfg(feature = "os-capture-privacy")]
+            StateKind::CapturePrivacy => &self.capture_privacy,
+            #[cfg(feature = "os-storage")]
+            StateKind::ShadowCopys => &self.shadow_copys,
+            #[cfg(feature = "os-storage")]
+            StateKind::ShadowContexts => &self.shadow_contexts,
+            #[cfg(feature = "os-storage")]
+            StateKind::ShadowProviders => &self.shadow_providers,
+            #[cfg(feature = "os-security")]
+            StateKind::LogicalFileSecuritySettings => &self.logical_file_security_settings,
+            #[cfg(feature = "os-security")]
+            StateKind::LogicalShareSecuritySettings => &self.logical_share_security_settings,
+            #[cfg(feature = "os-security")]
+            StateKind::PrivilegesStatuses => &self.privileges_statuses,
+            #[cfg(feature = "os-start-menu")]
+            StateKind::LogicalProgramGroups => &self.logical_program_groups,
+            #[cfg(feature = "os-start-menu")]
+            StateKind::LogicalProgramGroupItems => &self.logical_program_group_items,
+            #[cfg(feature = "os-networking")]
+            StateKind::Ip4PersistedRouteTables => &self.ip4_persisted_route_tables,
+            #[cfg(feature = "os-networking")]
+            StateKind::Ip4RouteTables => &self.ip4_route_tables,
+            #[cfg(feature = "os-networking")]
+            StateKind::NeworkClients => &self.nework_clients,
+            #[cfg(feature = "os-networking")]
+            StateKind::NeworkConnections => &self.nework_connections,
+            #[cfg(feature = "os-networking")]
+            StateKind::NeworkProtocols => &self.nework_protocols,
+            #[cfg(feature = "os-networking")]
+            StateKind::NtDomains => &self.nt_domains,
+            #[cfg(feature = "os-networking")]
+            StateKind::Ip4RouteTableEvents => &self.ip4_route_table_events,
+            #[cfg(feature = "os-networking")]
+            StateKind::QosPolicies => &self.qos_policies,
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallProfiles => &self.firewall_profiles,
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallRules => &self.firewall_rules,
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallPortFilters => &self.firewall_port_filters,
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallApplicationFilters => &self.firewall_application_filters,
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallAddressFilters => &self.firewall_address_filters,
+            #[cfg(feature = "os-connections")]
+            StateKind::TcpConnections => &self.tcp_connections,
+            #[cfg(feature = "os-connections")]
+            StateKind::UdpEndpoints => &self.udp_endpoints,
+            #[cfg(feature = "os-connections")]
+            StateKind::NetNeighbors => &self.net_neighbors,
+            #[cfg(feature = "os-dns")]
+            StateKind::DnsClientCache => &self.dns_client_cache,
+            #[cfg(feature = "os-dns")]
+            StateKind::HostsFile => &self.hosts_file,
+            #[cfg(feature = "os-job-objects")]
+            StateKind::NamedJobObjects => &self.named_job_objects,
+            #[cfg(feature = "os-job-objects")]
+            StateKind::NamedJobObjectActgInfos => &self.named_job_object_actg_infos,
+            #[cfg(feature = "os-job-objects")]
+            StateKind::NamedJobObjectLimitSettings => &self.named_job_object_limit_settings,
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::BootConfigurations => &self.boot_configurations,
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::ComputerSystems => &self.computer_systems,
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::SystemServices => &self.system_services,
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::ComputerSystemProducts => &self.computer_system_products,
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::LoadOrderGroups => &self.load_order_groups,
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::OperatingSystems => &self.operating_systems,
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::OsRecoveryConfigurations => &self.os_recovery_configurations,
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::QuickFixEngineerings => &self.quick_fix_engineerings,
+            #[cfg(feature = "os-optional-features")]
+            StateKind::OptionalFeatures => &self.optional_features,
+            #[cfg(feature = "os-optional-features")]
+            StateKind::ServerFeatures => &self.server_features,
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::StartupCommands => &self.startup_commands,
+            #[cfg(feature = "os-persistence")]
+            StateKind::PersistenceItems => &self.persistence_items,
+            #[cfg(feature = "os-installed-software")]
+            StateKind::InstalledSoftware => &self.installed_software,
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::Fans => &self.fans,
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::HeatPipes => &self.heat_pipes,
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::Refrigerations => &self.refrigerations,
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::TemperatureProbes => &self.temperature_probes,
+            #[cfg(feature = "hw-input-device")]
+            StateKind::Keyboards => &self.keyboards,
+            #[cfg(feature = "hw-input-device")]
+            StateKind::PointingDevices => &self.pointing_devices,
+            #[cfg(feature = "hw-input-device")]
+            StateKind::InputDeviceEvents => &self.input_device_events,
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::AutochkSettings => &self.autochk_settings,
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::CdRomDrives => &self.cd_rom_drives,
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::DiskDrives => &self.disk_drives,
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::PhysicalMedias => &self.physical_medias,
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::TapeDrives => &self.tape_drives,
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::PhysicalDisks => &self.physical_disks,
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::NvmeCapabilities => &self.nvme_capabilities,
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::Disks => &self.disks,
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::StorageReliabilityCounters => &self.storage_reliability_counters,
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::FailurePredictStatuses => &self.failure_predict_statuses,
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::PnpEntities => &self.pnp_entities,
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::Bioses => &self.bioses,
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::SystemEnclosures => &self.system_enclosures,
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::SerialPortConfigurations => &self.serial_port_configurations,
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::ParallelPorts => &self.parallel_ports,
+            #[cfg(feature = "hw-usb")]
+            StateKind::UsbDevices => &self.usb_devices,
+            #[cfg(feature = "hw-networking-device")]
+            StateKind::NetworkAdapters => &self.network_adapters,
+            #[cfg(feature = "hw-networking-device")]
+            StateKind::NetworkAdapterConfigurations => &self.network_adapter_configurations,
+            #[cfg(feature = "hw-networking-device")]
+            StateKind::NetAdapters => &self.net_adapters,
+            #[cfg(feature = "hw-telephony")]
+            StateKind::PotModems => &self.pot_modems,
+            #[cfg(feature = "hw-telephony")]
+            StateKind::PotModemToSerialPorts => &self.pot_modem_to_serial_ports,
+            #[cfg(feature = "hw-power")]
+            StateKind::Batteries => &self.batteries,
+            #[cfg(feature = "hw-power")]
+            StateKind::CurrentProbes => &self.current_probes,
+            #[cfg(feature = "hw-power")]
+            StateKind::PortableBatteries => &self.portable_batteries,
+            #[cfg(feature = "hw-power")]
+            StateKind::PowerManagementEvents => &self.power_management_events,
+            #[cfg(feature = "hw-power")]
+            StateKind::VoltageProbes => &self.voltage_probes,
+            #[cfg(feature = "hw-printing")]
+            StateKind::Printers => &self.printers,
+            #[cfg(feature = "hw-printing")]
+            StateKind::PrinterDrivers => &self.printer_drivers,
+            #[cfg(feature = "hw-printing")]
+            StateKind::DriverForDevices => &self.driver_for_devices,
+            #[cfg(feature = "hw-printing")]
+            StateKind::TCPIPPrinterPorts => &self.tcpip_printer_ports,
+            #[cfg(feature = "hw-video-monitor")]
+            StateKind::DesktopMonitors => &self.desktop_monitors,
+            #[cfg(feature = "hw-video-monitor")]
+            StateKind::DisplayControllerConfigurations => &self.display_controller_configurations,
+            #[cfg(feature = "hw-video-monitor")]
+            StateKind::VideoControllers => &self.video_controllers,
+            #[cfg(feature = "bluetooth")]
+            StateKind::BluetoothDevices => &self.bluetooth_devices,
+            #[cfg(feature = "hyperv")]
+            StateKind::VirtualMachines => &self.virtual_machines,
+            #[cfg(feature = "hyperv")]
+            StateKind::VirtualProcessorSettings => &self.virtual_processor_settings,
+            #[cfg(feature = "hyperv")]
+            StateKind::VirtualMemorySettings => &self.virtual_memory_settings,
+        }
+    }
+
+    /// Every member, in the order of the fields
+    pub fn members(&self) -> Vec<&dyn StateMember> {
+        StateKind::ALL.iter().map(|&kind| self.member(kind)).collect()
+    }
+
+    /// Bounds the instances the member `kind` keeps on its next updates, see [`crate::limits`]
+    ///
+    /// The limit applies to the class of the member, process-wide.
+    pub fn set_row_limit(&self, kind: StateKind, limit: crate::limits::RowLimit) {
+        crate::limits::set_limit(self.member(kind).class(), limit);
+    }
+
+    /// Synchronously update the member `kind`, returning how many instances it holds or why its
+    /// class is unavailable, and record the outcome in [`Windows::meta`]
+    pub fn update_member(&mut self, kind: StateKind) -> Result<usize, Unavailable> {
+        let start = Instant::now();
+
+        let outcome = match kind {
+            #[cfg(feature = "os-processes")]
+            StateKind::Processes => synced!(Processes, self.processes, processes),
+            #[cfg(feature = "os-processes")]
+            StateKind::Threads => synced!(Threads, self.threads, threads),
+            #[cfg(feature = "os-perf")]
+            StateKind::ProcessorPerformances => synced!(ProcessorPerformances, self.processor_performances, processor_performances),
+            #[cfg(feature = "os-perf")]
+            StateKind::MemoryPerformances => synced!(MemoryPerformances, self.memory_performances, memory_performances),
+            #[cfg(feature = "os-perf")]
+            StateKind::LogicalDiskPerformances => synced!(LogicalDiskPerformances, self.logical_disk_performances, logical_disk_performances),
+            #[cfg(feature = "os-perf")]
+            StateKind::GPUEnginePerformances => synced!(GPUEnginePerformances, self.gpu_engine_performances, gpu_engine_performances),
+            #[cfg(feature = "os-drivers")]
+            StateKind::Drivers => synced!(Drivers, self.drivers, drivers),
+            #[cfg(feature = "os-registry")]
+            StateKind::Registry => synced!(Registry, self.registry, registries),
+            #[cfg(feature = "os-registry")]
+            StateKind::RegistryKeys => synced!(RegistryKeys, self.registry_keys, registry_keys),
+            #[cfg(feature = "os-com")]
+            StateKind::DcomApplications => synced!(DcomApplications, self.dcom_applications, dcom_applications),
+            #[cfg(feature = "os-com")]
+            StateKind::DcomApplicationSettings => synced!(DcomApplicationSettings, self.dcom_application_settings, dcom_application_settings),
+            #[cfg(feature = "os-com")]
+            StateKind::ComSettings => synced!(ComSettings, self.com_settings, com_settings),
+            #[cfg(feature = "os-com")]
+            StateKind::ClassicComClassSettings => synced!(ClassicComClassSettings, self.classic_com_class_settings, classic_com_class_settings),
+            #[cfg(feature = "os-device-security")]
+            StateKind::Tpms => synced!(Tpms, self.tpms, tpms),
+            #[cfg(feature = "os-device-security")]
+            StateKind::SecureBoot => synced!(SecureBoot, self.secure_boot, secure_boot),
+            #[cfg(feature = "os-device-security")]
+            StateKind::EncryptableVolumes => synced!(EncryptableVolumes, self.encryptable_volumes, encryptable_volumes),
+            #[cfg(feature = "os-security-center")]
+            StateKind::AntiVirusProducts => synced!(AntiVirusProducts, self.anti_virus_products, anti_virus_products),
+            #[cfg(feature = "os-security-center")]
+            StateKind::FirewallProducts => synced!(FirewallProducts, self.firewall_products, firewall_products),
+            #[cfg(feature = "os-security-center")]
+            StateKind::AntiSpywareProducts => synced!(AntiSpywareProducts, self.anti_spyware_products, anti_spyware_products),
+            #[cfg(feature = "os-services")]
+            StateKind::Services => synced!(Services, self.services, services),
+            #[cfg(feature = "os-services")]
+            StateKind::DependentServices => synced!(DependentServices, self.dependent_services, dependent_services),
+            #[cfg(feature = "os-desktop")]
+            StateKind::Desktops => synced!(Desktops, self.desktops, desktops),
+            #[cfg(feature = "os-desktop")]
+            StateKind::Environment => synced!(Environment, self.environment, environments),
+            #[cfg(feature = "os-desktop")]
+            StateKind::Timezones => synced!(Timezones, self.timezones, timezones),
+            #[cfg(feature = "os-users")]
+            StateKind::UserAccounts => synced!(UserAccounts, self.user_accounts, user_accounts),
+            #[cfg(feature = "os-users")]
+            StateKind::Groups => synced!(Groups, self.groups, groups),
+            #[cfg(feature = "os-users")]
+            StateKind::LogonSessions => synced!(LogonSessions, self.logon_sessions, logon_sessions),
+            #[cfg(feature = "os-users")]
+            StateKind::NetworkLoginProfiles => synced!(NetworkLoginProfiles, self.network_login_profiles, network_login_profiles),
+            #[cfg(feature = "os-users")]
+            StateKind::SystemAccounts => synced!(SystemAccounts, self.system_accounts, system_accounts),
+            #[cfg(feature = "os-users")]
+            StateKind::LocalGroups => synced!(LocalGroups, self.local_groups, local_groups),
+            #[cfg(feature = "os-users")]
+            StateKind::UserProfiles => synced!(UserProfiles, self.user_profiles, user_profiles),
+            #[cfg(feature = "os-file-system")]
+            StateKind::Directories => synced!(Directories, self.directories, directories),
+            #[cfg(feature = "os-file-system")]
+            StateKind::DirectoriesSpecifications => synced!(DirectoriesSpecifications, self.directories_specifications, directory_specifications),
+            #[cfg(feature = "os-file-system")]
+            StateKind::DiskPartition => synced!(DiskPartition, self.disk_partition, disk_partitions),
+            #[cfg(feature = "os-file-system")]
+            StateKind::DiskDriveToDiskPartitions => synced!(DiskDriveToDiskPartitions, self.disk_drive_to_disk_partitions, disk_drive_to_disk_partitions),
+            #[cfg(feature = "os-file-system")]
+            StateKind::LogicalDisks => synced!(LogicalDisks, self.logical_disks, logical_disks),
+            #[cfg(feature = "os-file-system")]
+            StateKind::LogicalDiskToPartitions => synced!(LogicalDiskToPartitions, self.logical_disk_to_partitions, logical_disk_to_partitions),
+            #[cfg(feature = "os-file-system")]
+            StateKind::MappedLogicalDisks => synced!(MappedLogicalDisks, self.mapped_logical_disks, mapped_logical_disks),
+            #[cfg(feature = "os-file-system")]
+            StateKind::QuotaSettings => synced!(QuotaSettings, self.quota_settings, quota_settings),
+            #[cfg(feature = "os-file-system")]
+            StateKind::ShortcutFiles => synced!(ShortcutFiles, self.shortcut_files, shortcut_files),
+            #[cfg(feature = "os-file-system")]
+            StateKind::Volumes => synced!(Volumes, self.volumes, volumes),
+            #[cfg(feature = "os-event-log")]
+            StateKind::NtEventLogFiles => synced!(NtEventLogFiles, self.nt_event_log_files, nt_event_log_files),
+            #[cfg(feature = "os-event-log")]
+            StateKind::NtLogEvents => synced!(NtLogEvents, self.nt_log_events, nt_log_events),
+            #[cfg(feature = "os-event-log")]
+            StateKind::BootHistory => synced!(BootHistory, self.boot_history, boot_events),
+            #[cfg(feature = "os-event-log")]
+            StateKind::BootInfos => synced!(BootInfos, self.boot_infos, boot_infos),
+            #[cfg(feature = "os-memory-and-pagefiles")]
+            StateKind::Pagefiles => synced!(Pagefiles, self.pagefiles, pagefiles),
+            #[cfg(feature = "os-memory-and-pagefiles")]
+            StateKind::PagefileSettings => synced!(PagefileSettings, self.pagefile_settings, pagefile_settings),
+            #[cfg(feature = "os-memory-and-pagefiles")]
+            StateKind::PagefileUsages => synced!(PagefileUsages, self.pagefile_usages, pagefile_usage),
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::ScheduledJobs => synced!(ScheduledJobs, self.scheduled_jobs, scheduled_jobs),
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::ScheduledTasks => synced!(ScheduledTasks, self.scheduled_tasks, scheduled_tasks),
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::LocalTimes => synced!(LocalTimes, self.local_times, local_times),
+            #[cfg(feature = "os-scheduler-jobs")]
+            StateKind::UtcTimes => synced!(UtcTimes, self.utc_times, utc_times),
+            #[cfg(feature = "os-time-service")]
+            StateKind::TimeService => synced!(TimeService, self.time_service, time_settings),
+            #[cfg(feature = "os-updates")]
+            StateKind::WindowsUpdates => synced!(WindowsUpdates, self.windows_updates, pending_updates),
+            #[cfg(feature = "os-security-settings")]
+            StateKind::SecuritySettings => synced!(SecuritySettings, self.security_settings, audit_policies),
+            #[cfg(feature = "os-group-policy")]
+            StateKind::GPOs => synced!(GPOs, self.gpos, gpos),
+            #[cfg(feature = "os-group-policy")]
+            StateKind::GPLinks => synced!(GPLinks, self.gp_links, gp_links),
+            #[cfg(feature = "os-group-policy")]
+            StateKind::GPExtensionStatuses => synced!(GPExtensionStatuses, self.gp_extension_statuses, gp_extension_statuses),
+            #[cfg(feature = "os-software-license-provider")]
+            StateKind::SoftwareLicensingProducts => synced!(SoftwareLicensingProducts, self.software_licensing_products, software_licensing_products),
+            #[cfg(feature = "os-software-license-provider")]
+            StateKind::SoftwareLicensingServices => synced!(SoftwareLicensingServices, self.software_licensing_services, software_licensing_services),
+            #[cfg(feature = "os-software-license-provider")]
+            StateKind::SoftwareLicensingTokenActivationLicenses => synced!(SoftwareLicensingTokenActivationLicenses, self.software_licensing_token_activation_licenses, software_licensing_token_activation_licenses),
+            #[cfg(feature = "os-shares")]
+            StateKind::ServerConnections => synced!(ServerConnections, self.server_connections, server_connections),
+            #[cfg(feature = "os-shares")]
+            StateKind::ServerSessions => synced!(ServerSessions, self.server_sessions, server_sessions),
+            #[cfg(feature = "os-shares")]
+            StateKind::Shares => synced!(Shares, self.shares, shares),
+            #[cfg(feature = "os-shares")]
+            StateKind::ShareSecurityDescriptors => synced!(ShareSecurityDescriptors, self.share_security_descriptors, share_security_descriptors),
+            #[cfg(feature = "os-multimedia-audio-visual")]
+            StateKind::CodecFiles => synced!(CodecFiles, self.codec_files, codec_files),
+            #[cfg(feature = "os-capture-privacy")]
+            StateKind::CapturePrivacy => synced!(CapturePrivacy, self.capture_privacy, capture_devices),
+            #[cfg(feature = "os-storage")]
+            StateKind::ShadowCopys => synced!(ShadowCopys, self.shadow_copys, shadow_copys),
+            #[cfg(feature = "os-storage")]
+            StateKind::ShadowContexts => synced!(ShadowContexts, self.shadow_contexts, shadow_contexts),
+            #[cfg(feature = "os-storage")]
+            StateKind::ShadowProviders => synced!(ShadowProviders, self.shadow_providers, shadow_providers),
+            #[cfg(feature = "os-security")]
+            StateKind::LogicalFileSecuritySettings => synced!(LogicalFileSecuritySettings, self.logical_file_security_settings, logical_file_security_settings),
+            #[cfg(feature = "os-security")]
+            StateKind::LogicalShareSecuritySettings => synced!(LogicalShareSecuritySettings, self.logical_share_security_settings, logical_share_security_settings),
+            #[cfg(feature = "os-security")]
+            StateKind::PrivilegesStatuses => synced!(PrivilegesStatuses, self.privileges_statuses, privileges_statuses),
+            #[cfg(feature = "os-start-menu")]
+            StateKind::LogicalProgramGroups => synced!(LogicalProgramGroups, self.logical_program_groups, logical_program_groups),
+            #[cfg(feature = "os-start-menu")]
+            StateKind::LogicalProgramGroupItems => synced!(LogicalProgramGroupItems, self.logical_program_group_items, logical_program_group_items),
+            #[cfg(feature = "os-networking")]
+            StateKind::Ip4PersistedRouteTables => synced!(Ip4PersistedRouteTables, self.ip4_persisted_route_tables, ip4_persisted_route_tables),
+            #[cfg(feature = "os-networking")]
+            StateKind::Ip4RouteTables => synced!(Ip4RouteTables, self.ip4_route_tables, ip4_route_tables),
+            #[cfg(feature = "os-networking")]
+            StateKind::NeworkClients => synced!(NeworkClients, self.nework_clients, nework_clients),
+            #[cfg(feature = "os-networking")]
+            StateKind::NeworkConnections => synced!(NeworkConnections, self.nework_connections, nework_connections),
+            #[cfg(feature = "os-networking")]
+            StateKind::NeworkProtocols => synced!(NeworkProtocols, self.nework_protocols, nework_protocols),
+            #[cfg(feature = "os-networking")]
+            StateKind::NtDomains => synced!(NtDomains, self.nt_domains, nt_domains),
+            #[cfg(feature = "os-networking")]
+            StateKind::Ip4RouteTableEvents => synced!(Ip4RouteTableEvents, self.ip4_route_table_events, ip4_route_table_events),
+            #[cfg(feature = "os-networking")]
+            StateKind::QosPolicies => synced!(QosPolicies, self.qos_policies, qos_policies),
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallProfiles => synced!(FirewallProfiles, self.firewall_profiles, firewall_profiles),
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallRules => synced!(FirewallRules, self.firewall_rules, firewall_rules),
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallPortFilters => synced!(FirewallPortFilters, self.firewall_port_filters, port_filters),
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallApplicationFilters => synced!(FirewallApplicationFilters, self.firewall_application_filters, application_filters),
+            #[cfg(feature = "os-firewall")]
+            StateKind::FirewallAddressFilters => synced!(FirewallAddressFilters, self.firewall_address_filters, address_filters),
+            #[cfg(feature = "os-connections")]
+            StateKind::TcpConnections => synced!(TcpConnections, self.tcp_connections, tcp_connections),
+            #[cfg(feature = "os-connections")]
+            StateKind::UdpEndpoints => synced!(UdpEndpoints, self.udp_endpoints, udp_endpoints),
+            #[cfg(feature = "os-connections")]
+            StateKind::NetNeighbors => synced!(NetNeighbors, self.net_neighbors, net_neighbors),
+            #[cfg(feature = "os-dns")]
+            StateKind::DnsClientCache => synced!(DnsClientCache, self.dns_client_cache, dns_client_cache),
+            #[cfg(feature = "os-dns")]
+            StateKind::HostsFile => synced!(HostsFile, self.hosts_file, hosts_entries),
+            #[cfg(feature = "os-job-objects")]
+            StateKind::NamedJobObjects => synced!(NamedJobObjects, self.named_job_objects, named_job_objects),
+            #[cfg(feature = "os-job-objects")]
+            StateKind::NamedJobObjectActgInfos => synced!(NamedJobObjectActgInfos, self.named_job_object_actg_infos, named_job_object_actg_infos),
+            #[cfg(feature = "os-job-objects")]
+            StateKind::NamedJobObjectLimitSettings => synced!(NamedJobObjectLimitSettings, self.named_job_object_limit_settings, named_job_object_limit_settings),
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::BootConfigurations => synced!(BootConfigurations, self.boot_configurations, boot_configurations),
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::ComputerSystems => synced!(ComputerSystems, self.computer_systems, computer_systems),
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::SystemServices => synced!(SystemServices, self.system_services, system_services),
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::ComputerSystemProducts => synced!(ComputerSystemProducts, self.computer_system_products, computer_system_products),
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::LoadOrderGroups => synced!(LoadOrderGroups, self.load_order_groups, load_order_groups),
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::OperatingSystems => synced!(OperatingSystems, self.operating_systems, operating_systems),
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::OsRecoveryConfigurations => synced!(OsRecoveryConfigurations, self.os_recovery_configurations, os_recovery_configurations),
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::QuickFixEngineerings => synced!(QuickFixEngineerings, self.quick_fix_engineerings, quick_fix_engineerings),
+            #[cfg(feature = "os-optional-features")]
+            StateKind::OptionalFeatures => synced!(OptionalFeatures, self.optional_features, optional_features),
+            #[cfg(feature = "os-optional-features")]
+            StateKind::ServerFeatures => synced!(ServerFeatures, self.server_features, server_features),
+            #[cfg(feature = "os-operating-system-settings")]
+            StateKind::StartupCommands => synced!(StartupCommands, self.startup_commands, startup_commands),
+            #[cfg(feature = "os-persistence")]
+            StateKind::PersistenceItems => synced!(PersistenceItems, self.persistence_items, persistence_items),
+            #[cfg(feature = "os-installed-software")]
+            StateKind::InstalledSoftware => synced!(InstalledSoftware, self.installed_software, installed_software),
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::Fans => synced!(Fans, self.fans, fans),
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::HeatPipes => synced!(HeatPipes, self.heat_pipes, heat_pipes),
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::Refrigerations => synced!(Refrigerations, self.refrigerations, refrigerations),
+            #[cfg(feature = "hw-cooling-device")]
+            StateKind::TemperatureProbes => synced!(TemperatureProbes, self.temperature_probes, temperature_probes),
+            #[cfg(feature = "hw-input-device")]
+            StateKind::Keyboards => synced!(Keyboards, self.keyboards, keyboards),
+            #[cfg(feature = "hw-input-device")]
+            StateKind::PointingDevices => synced!(PointingDevices, self.pointing_devices, pointing_devices),
+            #[cfg(feature = "hw-input-device")]
+            StateKind::InputDeviceEvents => synced!(InputDeviceEvents, self.input_device_events, input_device_events),
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::AutochkSettings => synced!(AutochkSettings, self.autochk_settings, autochk_settings),
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::CdRomDrives => synced!(CdRomDrives, self.cd_rom_drives, cd_rom_drives),
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::DiskDrives => synced!(DiskDrives, self.disk_drives, disk_drives),
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::PhysicalMedias => synced!(PhysicalMedias, self.physical_medias, physical_medias),
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::TapeDrives => synced!(TapeDrives, self.tape_drives, tape_drives),
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::PhysicalDisks => synced!(PhysicalDisks, self.physical_disks, physical_disks),
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::NvmeCapabilities => synced!(NvmeCapabilities, self.nvme_capabilities, nvme_devices),
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::Disks => synced!(Disks, self.disks, disks),
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::StorageReliabilityCounters => synced!(StorageReliabilityCounters, self.storage_reliability_counters, storage_reliability_counters),
+            #[cfg(feature = "hw-mass-storage")]
+            StateKind::FailurePredictStatuses => synced!(FailurePredictStatuses, self.failure_predict_statuses, failure_predict_statuses),
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::PnpEntities => synced!(PnpEntities, self.pnp_entities, pnp_entities),
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::Bioses => synced!(Bioses, self.bioses, bioses),
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::SystemEnclosures => synced!(SystemEnclosures, self.system_enclosures, system_enclosures),
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::SerialPortConfigurations => synced!(SerialPortConfigurations, self.serial_port_configurations, serial_port_configurations),
+            #[cfg(feature = "hw-motherboard-controller-port")]
+            StateKind::ParallelPorts => synced!(ParallelPorts, self.parallel_ports, parallel_ports),
+            #[cfg(feature = "hw-usb")]
+            StateKind::UsbDevices => synced!(UsbDevices, self.usb_devices, usb_devices),
+            #[cfg(feature = "hw-networking-device")]
+            StateKind::NetworkAdapters => synced!(NetworkAdapters, self.network_adapters, network_adapters),
+            #[cfg(feature = "hw-networking-device")]
+            StateKind::NetworkAdapterConfigurations => synced!(NetworkAdapterConfigurations, self.network_adapter_configurations, network_adapter_configurations),
+            #[cfg(feature = "hw-networking-device")]
+            StateKind::NetAdapters => synced!(NetAdapters, self.net_adapters, net_adapters),
+            #[cfg(feature = "hw-telephony")]
+            StateKind::PotModems => synced!(PotModems, self.pot_modems, pot_modems),
+            #[cfg(feature = "hw-telephony")]
+            StateKind::PotModemToSerialPorts => synced!(PotModemToSerialPorts, self.pot_modem_to_serial_ports, pot_modem_to_serial_ports),
+            #[cfg(feature = "hw-power")]
+            StateKind::Batteries => synced!(Batteries, self.batteries, batteries),
+            #[cfg(feature = "hw-power")]
+            StateKind::CurrentProbes => synced!(CurrentProbes, self.current_probes, current_probes),
+            #[cfg(feature = "hw-power")]
+            StateKind::PortableBatteries => synced!(PortableBatteries, self.portable_batteries, portable_batteries),
+            #[cfg(feature = "hw-power")]
+            StateKind::PowerManagementEvents => synced!(PowerManagementEvents, self.power_management_events, power_management_events),
+            #[cfg(feature = "hw-power")]
+            StateKind::VoltageProbes => synced!(VoltageProbes, self.voltage_probes, voltage_probes),
+            #[cfg(feature = "hw-printing")]
+            StateKind::Printers => synced!(Printers, self.printers, printers),
+            #[cfg(feature = "hw-printing")]
+            StateKind::PrinterDrivers => synced!(PrinterDrivers, self.printer_drivers, printer_drivers),
+            #[cfg(feature = "hw-printing")]
+            StateKind::DriverForDevices => synced!(DriverForDevices, self.driver_for_devices, driver_for_devices),
+            #[cfg(feature = "hw-printing")]
+            StateKind::TCPIPPrinterPorts => synced!(TCPIPPrinterPorts, self.tcpip_printer_ports, tcpip_printer_ports),
+            #[cfg(feature = "hw-video-monitor")]
+            StateKind::DesktopMonitors => synced!(DesktopMonitors, self.desktop_monitors, desktop_monitors),
+            #[cfg(feature = "hw-video-monitor")]
+            StateKind::DisplayControllerConfigurations => synced!(DisplayControllerConfigurations, self.display_controller_configurations, display_controller_configurations),
+            #[cfg(feature = "hw-video-monitor")]
+            StateKind::VideoControllers => synced!(VideoControllers, self.video_controllers, video_controllers),
+            #[cfg(feature = "bluetooth")]
+            StateKind::BluetoothDevices => synced!(BluetoothDevices, self.bluetooth_devices, bluetooth_devices),
+            #[cfg(feature = "hyperv")]
+            StateKind::VirtualMachines => synced!(VirtualMachines, self.virtual_machines, virtual_machines),
+            #[cfg(feature = "hyperv")]
+            StateKind::VirtualProcessorSettings => synced!(VirtualProcessorSettings, self.virtual_processor_settings, virtual_processor_settings),
+            #[cfg(feature = "hyperv")]
+            StateKind::VirtualMemorySettings => synced!(VirtualMemorySettings, self.virtual_memory_settings, virtual_memory_settings),
+        };
+
+        self.meta.record(kind, start.elapsed(), &outcome);
+        outcome
+    }
+
+    /// Merges the members of `partial`, e.g. a snapshot refreshing only some classes on its own
+    /// schedule, into this snapshot
+    ///
+    /// The members of `partial` are the ones recorded in its [`Windows::meta`], as updated by
+    /// [`Windows::update_member`] or a profile. Each replaces the member of this snapshot unless
+    /// that one was recorded as updated later, and its [`MemberMeta`] comes along. Only the
+    /// instances and the outcome of the update are taken, the configuration of the members of this
+    /// snapshot is kept. Returns the members merged.
+    ///
+    /// Fails without merging anything if the snapshots were taken on different hosts.
+    pub fn merge(&mut self, mut partial: Windows) -> Result<Vec<StateKind>, MergeError> {
+        let hostname = &self.meta.hostname;
+        let partial_hostname = &partial.meta.hostname;
+
+        if !hostname.is_empty() && !partial_hostname.is_empty() && !hostname.eq_ignore_ascii_case(partial_hostname) {
+            return Err(MergeError {
+                hostname: hostname.clone(),
+                partial_hostname: partial_hostname.clone(),
+            });
+        }
+
+        let members = std::mem::take(&mut partial.meta.members);
+        let mut merged = Vec::new();
+
+        for (kind, member) in members {
+            let recorded = self.meta.members.contains_key(&kind);
+            let newer = match kind {
+                #[cfg(feature = "os-processes")]
+                StateKind::Processes => merged!(self.processes, partial.processes, recorded),
+                #[cfg(feature = "os-processes")]
+                StateKind::Threads => merged!(self.threads, partial.threads, recorded),
+                #[cfg(feature = "os-perf")]
+                StateKind::ProcessorPerformances => merged!(self.processor_performances, partial.processor_performances, recorded),
+                #[cfg(feature = "os-perf")]
+                StateKind::MemoryPerformances => merged!(self.memory_performances, partial.memory_performances, recorded),
+                #[cfg(feature = "os-perf")]
+                StateKind::LogicalDiskPerformances => merged!(self.logical_disk_performances, partial.logical_disk_performances, recorded),
+                #[cfg(feature = "os-perf")]
+                StateKind::GPUEnginePerformances => merged!(self.gpu_engine_performances, partial.gpu_engine_performances, recorded),
+                #[cfg(feature = "os-drivers")]
+                StateKind::Drivers => merged!(self.drivers, partial.drivers, recorded),
+                #[cfg(feature = "os-registry")]
+                StateKind::Registry => merged!(self.registry, partial.registry, recorded),
+                #[cfg(feature = "os-registry")]
+                StateKind::RegistryKeys => merged!(self.registry_keys, partial.registry_keys, recorded),
+                #[cfg(feature = "os-com")]
+                StateKind::DcomApplications => merged!(self.dcom_applications, partial.dcom_applications, recorded),
+                #[cfg(feature = "os-com")]
+                StateKind::DcomApplicationSettings => merged!(self.dcom_application_settings, partial.dcom_application_settings, recorded),
+                #[cfg(feature = "os-com")]
+                StateKind::ComSettings => merged!(self.com_settings, partial.com_settings, recorded),
+                #[cfg(feature = "os-com")]
+                StateKind::ClassicComClassSettings => merged!(self.classic_com_class_settings, partial.classic_com_class_settings, recorded),
+                #[cfg(feature = "os-device-security")]
+                StateKind::Tpms => merged!(self.tpms, partial.tpms, recorded),
+                #[cfg(feature = "os-device-security")]
+                StateKind::SecureBoot => merged!(self.secure_boot, partial.secure_boot, recorded),
+                #[cfg(feature = "os-device-security")]
+                StateKind::EncryptableVolumes => merged!(self.encryptable_volumes, partial.encryptable_volumes, recorded),
+                #[cfg(feature = "os-security-center")]
+                StateKind::AntiVirusProducts => merged!(self.anti_virus_products, partial.anti_virus_products, recorded),
+                #[cfg(feature = "os-security-center")]
+                StateKind::FirewallProducts => merged!(self.firewall_products, partial.firewall_products, recorded),
+                #[cfg(feature = "os-security-center")]
+                StateKind::AntiSpywareProducts => merged!(self.anti_spyware_products, partial.anti_spyware_products, recorded),
+                #[cfg(feature = "os-services")]
+                StateKind::Services => merged!(self.services, partial.services, recorded),
+                #[cfg(feature = "os-services")]
+                StateKind::DependentServices => merged!(self.dependent_services, partial.dependent_services, recorded),
+                #[cfg(feature = "os-desktop")]
+                StateKind::Desktops => merged!(self.desktops, partial.desktops, recorded),
+                #[cfg(feature = "os-desktop")]
+                StateKind::Environment => merged!(self.environment, partial.environment, recorded),
+                #[cfg(feature = "os-desktop")]
+                StateKind::Timezones => merged!(self.timezones, partial.timezones, recorded),
+                #[cfg(feature = "os-users")]
+                StateKind::UserAccounts => merged!(self.user_accounts, partial.user_accounts, recorded),
+                #[cfg(feature = "os-users")]
+                StateKind::Groups => merged!(self.groups, partial.groups, recorded),
+                #[cfg(feature = "os-users")]
+                StateKind::LogonSessions => merged!(self.logon_sessions, partial.logon_sessions, recorded),
+                #[cfg(feature = "os-users")]
+                StateKind::NetworkLoginProfiles => merged!(self.network_login_profiles, partial.network_login_profiles, recorded),
+                #[cfg(feature = "os-users")]
+                StateKind::SystemAccounts => merged!(self.system_accounts, partial.system_accounts, recorded),
+                #[cfg(feature = "os-users")]
+                StateKind::LocalGroups => merged!(self.local_groups, partial.local_groups, recorded),
+                #[cfg(feature = "os-users")]
+                StateKind::UserProfiles => merged!(self.user_profiles, partial.user_profiles, recorded),
+                #[cfg(feature = "os-file-system")]
+                StateKind::Directories => merged!(self.directories, partial.directories, recorded),
+                #[cfg(feature = "os-file-system")]
+                StateKind::DirectoriesSpecifications => merged!(self.directories_specifications, partial.directories_specifications, recorded),
+                #[cfg(feature = "os-file-system")]
+                StateKind::DiskPartition => merged!(self.disk_partition, partial.disk_partition, recorded),
+                #[cfg(feature = "os-file-system")]
+                StateKind::DiskDriveToDiskPartitions => merged!(self.disk_drive_to_disk_partitions, partial.disk_drive_to_disk_partitions, recorded),
+                #[cfg(feature = "os-file-system")]
+                StateKind::LogicalDisks => merged!(self.logical_disks, partial.logical_disks, recorded),
+                #[cfg(feature = "os-file-system")]
+                StateKind::LogicalDiskToPartitions => merged!(self.logical_disk_to_partitions, partial.logical_disk_to_partitions, recorded),
+                #[cfg(feature = "os-file-system")]
+                StateKind::MappedLogicalDisks => merged!(self.mapped_logical_disks, partial.mapped_logical_disks, recorded),
+                #[cfg(feature = "os-file-system")]
+                StateKind::QuotaSettings => merged!(self.quota_settings, partial.quota_settings, recorded),
+                #[cfg(feature = "os-file-system")]
+                StateKind::ShortcutFiles => merged!(self.shortcut_files, partial.shortcut_files, recorded),
+                #[cfg(feature = "os-file-system")]
+                StateKind::Volumes => merged!(self.volumes, partial.volumes, recorded),
+                #[cfg(feature = "os-event-log")]
+                StateKind::NtEventLogFiles => merged!(self.nt_event_log_files, partial.nt_event_log_files, recorded),
+                #[cfg(feature = "os-event-log")]
+                StateKind::NtLogEvents => merged!(self.nt_log_events, partial.nt_log_events, recorded),
+                #[cfg(feature = "os-event-log")]
+                StateKind::BootHistory => merged!(self.boot_history, partial.boot_history, recorded),
+                #[cfg(feature = "os-event-log")]
+                StateKind::BootInfos => merged!(self.boot_infos, partial.boot_infos, recorded),
+                #[cfg(feature = "os-memory-and-pagefiles")]
+                StateKind::Pagefiles => merged!(self.pagefiles, partial.pagefiles, recorded),
+                #[cfg(feature = "os-memory-and-pagefiles")]
+                StateKind::PagefileSettings => merged!(self.pagefile_settings, partial.pagefile_settings, recorded),
+                #[cfg(feature = "os-memory-and-pagefiles")]
+                StateKind::PagefileUsages => merged!(self.pagefile_usages, partial.pagefile_usages, recorded),
+                #[cfg(feature = "os-scheduler-jobs")]
+                StateKind::ScheduledJobs => merged!(self.scheduled_jobs, partial.scheduled_jobs, recorded),
+                #[cfg(feature = "os-scheduler-jobs")]
+                StateKind::ScheduledTasks => merged!(self.scheduled_tasks, partial.scheduled_tasks, recorded),
+                #[cfg(feature = "os-scheduler-jobs")]
+                StateKind::LocalTimes => merged!(self.local_times, partial.local_times, recorded),
+                #[cfg(feature = "os-scheduler-jobs")]
+                StateKind::UtcTimes => merged!(self.utc_times, partial.utc_times, recorded),
+                #[cfg(feature = "os-time-service")]
+                StateKind::TimeService => merged!(self.time_service, partial.time_service, recorded),
+                #[cfg(feature = "os-updates")]
+                StateKind::WindowsUpdates => merged!(self.windows_updates, partial.windows_updates, recorded),
+                #[cfg(feature = "os-security-settings")]
+                StateKind::SecuritySettings => merged!(self.security_settings, partial.security_settings, recorded),
+                #[cfg(feature = "os-group-policy")]
+                StateKind::GPOs => merged!(self.gpos, partial.gpos, recorded),
+                #[cfg(feature = "os-group-policy")]
+                StateKind::GPLinks => merged!(self.gp_links, partial.gp_links, recorded),
+                #[cfg(feature = "os-group-policy")]
+                StateKind::GPExtensionStatuses => merged!(self.gp_extension_statuses, partial.gp_extension_statuses, recorded),
+                #[cfg(feature = "os-software-license-provider")]
+                StateKind::SoftwareLicensingProducts => merged!(self.software_licensing_products, partial.software_licensing_products, recorded),
+                #[cfg(feature = "os-software-license-provider")]
+                StateKind::SoftwareLicensingServices => merged!(self.software_licensing_services, partial.software_licensing_services, recorded),
+                #[cfg(feature = "os-software-license-provider")]
+                StateKind::SoftwareLicensingTokenActivationLicenses => merged!(self.software_licensing_token_activation_licenses, partial.software_licensing_token_activation_licenses, recorded),
+                #[cfg(feature = "os-shares")]
+                StateKind::ServerConnections => merged!(self.server_connections, partial.server_connections, recorded),
+                #[cfg(feature = "os-shares")]
+                StateKind::ServerSessions => merged!(self.server_sessions, partial.server_sessions, recorded),
+                #[cfg(feature = "os-shares")]
+                StateKind::Shares => merged!(self.shares, partial.shares, recorded),
+                #[cfg(feature = "os-shares")]
+                StateKind::ShareSecurityDescriptors => merged!(self.share_security_descriptors, partial.share_security_descriptors, recorded),
+                #[cfg(feature = "os-multimedia-audio-visual")]
+                StateKind::CodecFiles => merged!(self.codec_files, partial.codec_files, recorded),
+                #[cfg(feature = "os-capture-privacy")]
+                StateKind::CapturePrivacy => merged!(self.capture_privacy, partial.capture_privacy, recorded),
+                #[cfg(feature = "os-storage")]
+                StateKind::ShadowCopys => merged!(self.shadow_copys, partial.shadow_copys, recorded),
+                #[cfg(feature = "os-storage")]
+                StateKind::ShadowContexts => merged!(self.shadow_contexts, partial.shadow_contexts, recorded),
+                #[cfg(feature = "os-storage")]
+                StateKind::ShadowProviders => merged!(self.shadow_providers, partial.shadow_providers, recorded),
+                #[cfg(feature = "os-security")]
+                StateKind::LogicalFileSecuritySettings => merged!(self.logical_file_security_settings, partial.logical_file_security_settings, recorded),
+                #[cfg(feature = "os-security")]
+                StateKind::LogicalShareSecuritySettings => merged!(self.logical_share_security_settings, partial.logical_share_security_settings, recorded),
+                #[cfg(feature = "os-security")]
+                StateKind::PrivilegesStatuses => merged!(self.privileges_statuses, partial.privileges_statuses, recorded),
+                #[cfg(feature = "os-start-menu")]
+                StateKind::LogicalProgramGroups => merged!(self.logical_program_groups, partial.logical_program_groups, recorded),
+                #[cfg(feature = "os-start-menu")]
+                StateKind::LogicalProgramGroupItems => merged!(self.logical_program_group_items, partial.logical_program_group_items, recorded),
+                #[cfg(feature = "os-networking")]
+                StateKind::Ip4PersistedRouteTables => merged!(self.ip4_persisted_route_tables, partial.ip4_persisted_route_tables, recorded),
+                #[cfg(feature = "os-networking")]
+                StateKind::Ip4RouteTables => merged!(self.ip4_route_tables, partial.ip4_route_tables, recorded),
+                #[cfg(feature = "os-networking")]
+                StateKind::NeworkClients => merged!(self.nework_clients, partial.nework_clients, recorded),
+                #[cfg(feature = "os-networking")]
+                StateKind::NeworkConnections => merged!(self.nework_connections, partial.nework_connections, recorded),
+                #[cfg(feature = "os-networking")]
+                StateKind::NeworkProtocols => merged!(self.nework_protocols, partial.nework_protocols, recorded),
+                #[cfg(feature = "os-networking")]
+                StateKind::NtDomains => merged!(self.nt_domains, partial.nt_domains, recorded),
+                #[cfg(feature = "os-networking")]
+                StateKind::Ip4RouteTableEvents => merged!(self.ip4_route_table_events, partial.ip4_route_table_events, recorded),
+                #[cfg(feature = "os-networking")]
+                StateKind::QosPolicies => merged!(self.qos_policies, partial.qos_policies, recorded),
+                #[cfg(feature = "os-firewall")]
+                StateKind::FirewallProfiles => merged!(self.firewall_profiles, partial.firewall_profiles, recorded),
+                #[cfg(feature = "os-firewall")]
+                StateKind::FirewallRules => merged!(self.firewall_rules, partial.firewall_rules, recorded),
+                #[cfg(feature = "os-firewall")]
+                StateKind::FirewallPortFilters => merged!(self.firewall_port_filters, partial.firewall_port_filters, recorded),
+                #[cfg(feature = "os-firewall")]
+                StateKind::FirewallApplicationFilters => merged!(self.firewall_application_filters, partial.firewall_application_filters, recorded),
+                #[cfg(feature = "os-firewall")]
+                StateKind::FirewallAddressFilters => merged!(self.firewall_address_filters, partial.firewall_address_filters, recorded),
+                #[cfg(feature = "os-connections")]
+                StateKind::TcpConnections => merged!(self.tcp_connections, partial.tcp_connections, recorded),
+                #[cfg(feature = "os-connections")]
+                StateKind::UdpEndpoints => merged!(self.udp_endpoints, partial.udp_endpoints, recorded),
+                #[cfg(feature = "os-connections")]
+                StateKind::NetNeighbors => merged!(self.net_neighbors, partial.net_neighbors, recorded),
+                #[cfg(feature = "os-dns")]
+                StateKind::DnsClientCache => merged!(self.dns_client_cache, partial.dns_client_cache, recorded),
+                #[cfg(feature = "os-dns")]
+                StateKind::HostsFile => merged!(self.hosts_file, partial.hosts_file, recorded),
+                #[cfg(feature = "os-job-objects")]
+                StateKind::NamedJobObjects => merged!(self.named_job_objects, partial.named_job_objects, recorded),
+                #[cfg(feature = "os-job-objects")]
+                StateKind::NamedJobObjectActgInfos => merged!(self.named_job_object_actg_infos, partial.named_job_object_actg_infos, recorded),
+                #[cfg(feature = "os-job-objects")]
+                StateKind::NamedJobObjectLimitSettings => merged!(self.named_job_object_limit_settings, partial.named_job_object_limit_settings, recorded),
+                #[cfg(feature = "os-operating-system-settings")]
+                StateKind::BootConfigurations => merged!(self.boot_configurations, partial.boot_configurations, recorded),
+                #[cfg(feature = "os-operating-system-settings")]
+                StateKind::ComputerSystems => merged!(self.computer_systems, partial.computer_systems, recorded),
+                #[cfg(feature = "os-operating-system-settings")]
+                StateKind::SystemServices => merged!(self.system_services, partial.system_services, recorded),
+                #[cfg(feature = "os-operating-system-settings")]
+                StateKind::ComputerSystemProducts => merged!(self.computer_system_products, partial.computer_system_products, recorded),
+                #[cfg(feature = "os-operating-system-settings")]
+                StateKind::LoadOrderGroups => merged!(self.load_order_groups, partial.load_order_groups, recorded),
+                #[cfg(feature = "os-operating-system-settings")]
+                StateKind::OperatingSystems => merged!(self.operating_systems, partial.operating_systems, recorded),
+                #[cfg(feature = "os-operating-system-settings")]
+                StateKind::OsRecoveryConfigurations => merged!(self.os_recovery_configurations, partial.os_recovery_configurations, recorded),
+                #[cfg(feature = "os-operating-system-settings")]
+                StateKind::QuickFixEngineerings => merged!(self.quick_fix_engineerings, partial.quick_fix_engineerings, recorded),
+                #[cfg(feature = "os-optional-features")]
+                StateKind::OptionalFeatures => merged!(self.optional_features, partial.optional_features, recorded),
+                #[cfg(feature = "os-optional-features")]
+                StateKind::ServerFeatures => merged!(self.server_features, partial.server_features, recorded),
+                #[cfg(feature = "os-operating-system-settings")]
+                StateKind::StartupCommands => merged!(self.startup_commands, partial.startup_commands, recorded),
+                #[cfg(feature = "os-persistence")]
+                StateKind::PersistenceItems => merged!(self.persistence_items, partial.persistence_items, recorded),
+                #[cfg(feature = "os-installed-software")]
+                StateKind::InstalledSoftware => merged!(self.installed_software, partial.installed_software, recorded),
+                #[cfg(feature = "hw-cooling-device")]
+                StateKind::Fans => merged!(self.fans, partial.fans, recorded),
+                #[cfg(feature = "hw-cooling-device")]
+                StateKind::HeatPipes => merged!(self.heat_pipes, partial.heat_pipes, recorded),
+                #[cfg(feature = "hw-cooling-device")]
+                StateKind::Refrigerations => merged!(self.refrigerations, partial.refrigerations, recorded),
+                #[cfg(feature = "hw-cooling-device")]
+                StateKind::TemperatureProbes => merged!(self.temperature_probes, partial.temperature_probes, recorded),
+                #[cfg(feature = "hw-input-device")]
+                StateKind::Keyboards => merged!(self.keyboards, partial.keyboards, recorded),
+                #[cfg(feature = "hw-input-device")]
+                StateKind::PointingDevices => merged!(self.pointing_devices, partial.pointing_devices, recorded),
+                #[cfg(feature = "hw-input-device")]
+                StateKind::InputDeviceEvents => merged!(self.input_device_events, partial.input_device_events, recorded),
+                #[cfg(feature = "hw-mass-storage")]
+                StateKind::AutochkSettings => merged!(self.autochk_settings, partial.autochk_settings, recorded),
+                #[cfg(feature = "hw-mass-storage")]
+                StateKind::CdRomDrives => merged!(self.cd_rom_drives, partial.cd_rom_drives, recorded),
+                #[cfg(feature = "hw-mass-storage")]
+                StateKind::DiskDrives => merged!(self.disk_drives, partial.disk_drives, recorded),
+                #[cfg(feature = "hw-mass-storage")]
+                StateKind::PhysicalMedias => merged!(self.physical_medias, partial.physical_medias, recorded),
+                #[cfg(feature = "hw-mass-storage")]
+                StateKind::TapeDrives => merged!(self.tape_drives, partial.tape_drives, recorded),
+                #[cfg(feature = "hw-mass-storage")]
+                StateKind::PhysicalDisks => merged!(self.physical_disks, partial.physical_disks, recorded),
+                #[cfg(feature = "hw-mass-storage")]
+                StateKind::NvmeCapabilities => merged!(self.nvme_capabilities, partial.nvme_capabilities, recorded),
+                #[cfg(feature = "hw-mass-storage")]
+                StateKind::Disks => merged!(self.disks, partial.disks, recorded),
+                #[cfg(feature = "hw-mass-storage")]
+                StateKind::StorageReliabilityCounters => merged!(self.storage_reliability_counters, partial.storage_reliability_counters, recorded),
+                #[cfg(feature = "hw-mass-storage")]
+                StateKind::FailurePredictStatuses => merged!(self.failure_predict_statuses, partial.failure_predict_statuses, recorded),
+                #[cfg(feature = "hw-motherboard-controller-port")]
+                StateKind::PnpEntities => merged!(self.pnp_entities, partial.pnp_entities, recorded),
+                #[cfg(feature = "hw-motherboard-controller-port")]
+                StateKind::Bioses => merged!(self.bioses, partial.bioses, recorded),
+                #[cfg(feature = "hw-motherboard-controller-port")]
+                StateKind::SystemEnclosures => merged!(self.system_enclosures, partial.system_enclosures, recorded),
+                #[cfg(feature = "hw-motherboard-controller-port")]
+                StateKind::SerialPortConfigurations => merged!(self.serial_port_configurations, partial.serial_port_configurations, recorded),
+                #[cfg(feature = "hw-motherboard-controller-port")]
+                StateKind::ParallelPorts => merged!(self.parallel_ports, partial.parallel_ports, recorded),
+                #[cfg(feature = "hw-usb")]
+                StateKind::UsbDevices => merged!(self.usb_devices, partial.usb_devices, recorded),
+                #[cfg(feature = "hw-networking-device")]
+                StateKind::NetworkAdapters => merged!(self.network_adapters, partial.network_adapters, recorded),
+                #[cfg(feature = "hw-networking-device")]
+                StateKind::NetworkAdapterConfigurations => merged!(self.network_adapter_configurations, partial.network_adapter_configurations, recorded),
+                #[cfg(feature = "hw-networking-device")]
+                StateKind::NetAdapters => merged!(self.net_adapters, partial.net_adapters, recorded),
+                #[cfg(feature = "hw-telephony")]
+                StateKind::PotModems => merged!(self.pot_modems, partial.pot_modems, recorded),
+                #[cfg(feature = "hw-telephony")]
+                StateKind::PotModemToSerialPorts => merged!(self.pot_modem_to_serial_ports, partial.pot_modem_to_serial_ports, recorded),
+                #[cfg(feature = "hw-power")]
+                StateKind::Batteries => merged!(self.batteries, partial.batteries, recorded),
+                #[cfg(feature = "hw-power")]
+                StateKind::CurrentProbes => merged!(self.current_probes, partial.current_probes, recorded),
+                #[cfg(feature = "hw-power")]
+                StateKind::PortableBatteries => merged!(self.portable_batteries, partial.portable_batteries, recorded),
+                #[cfg(feature = "hw-power")]
+                StateKind::PowerManagementEvents => merged!(self.power_management_events, partial.power_management_events, recorded),
+                #[cfg(feature = "hw-power")]
+                StateKind::VoltageProbes => merged!(self.voltage_probes, partial.voltage_probes, recorded),
+                #[cfg(feature = "hw-printing")]
+                StateKind::Printers => merged!(self.printers, partial.printers, recorded),
+                #[cfg(feature = "hw-printing")]
+                StateKind::PrinterDrivers => merged!(self.printer_drivers, partial.printer_drivers, recorded),
+                #[cfg(feature = "hw-printing")]
+                StateKind::DriverForDevices => merged!(self.driver_for_devices, partial.driver_for_devices, recorded),
+                #[cfg(feature = "hw-printing")]
+                StateKind::TCPIPPrinterPorts => merged!(self.tcpip_printer_ports, partial.tcpip_printer_ports, recorded),
+                #[cfg(feature = "hw-video-monitor")]
+                StateKind::DesktopMonitors => merged!(self.desktop_monitors, partial.desktop_monitors, recorded),
+                #[cfg(feature = "hw-video-monitor")]
+                StateKind::DisplayControllerConfigurations => merged!(self.display_controller_configurations, partial.display_controller_configurations, recorded),
+                #[cfg(feature = "hw-video-monitor")]
+                StateKind::VideoControllers => merged!(self.video_controllers, partial.video_controllers, recorded),
+                #[cfg(feature = "bluetooth")]
+                StateKind::BluetoothDevices => merged!(self.bluetooth_devices, partial.bluetooth_devices, recorded),
+                #[cfg(feature = "hyperv")]
+                StateKind::VirtualMachines => merged!(self.virtual_machines, partial.virtual_machines, recorded),
+                #[cfg(feature = "hyperv")]
+                StateKind::VirtualProcessorSettings => merged!(self.virtual_processor_settings, partial.virtual_processor_settings, recorded),
+                #[cfg(feature = "hyperv")]
+                StateKind::VirtualMemorySettings => merged!(self.virtual_memory_settings, partial.virtual_memory_settings, recorded),
+            };
+
+            if newer {
+                self.meta.members.insert(kind, member);
+                merged.push(kind);
+            }
+        }
+
+        if self.meta.hostname.is_empty() {
+            self.meta.hostname = partial.meta.hostname;
+        }
+        self.meta.capture_finished = self.meta.capture_finished.max(partial.meta.capture_finished);
+        self.extra_properties.extend(partial.extra_properties);
+
+        for (name, state) in partial.custom {
+            if self.custom.get(&name).is_none_or(|current| current.last_updated < state.last_updated) {
+                self.custom.insert(name, state);
+            }
+        }
+
+        Ok(merged)
+    }
+
+    /// Synchronously update all the fields on `threads` threads, for callers without an async
+    /// runtime. Every thread initializes COM for itself.
+    ///
+    /// A `threads` of 0 is treated as 1
+    pub fn update_parallel(&mut self, threads: usize) {
+        #[allow(unused_mut)]
+        let mut updates: Vec<(StateKind, Box<dyn FnOnce() -> Result<usize, Unavailable> + Send + '_>)> = Vec::new();
+
+        #[cfg(feature = "os-processes")]
+        updates.push(dispatched!(Threads, self.threads, threads));
+        #[cfg(feature = "os-perf")]
+        updates.push(dispatched!(ProcessorPerformances, self.processor_performances, processor_performances));
+        #[cfg(feature = "os-perf")]
+        updates.push(dispatched!(MemoryPerformances, self.memory_performances, memory_performances));
+        #[cfg(feature = "os-perf")]
+        updates.push(dispatched!(LogicalDiskPerformances, self.logical_disk_performances, logical_disk_performances));
+        #[cfg(feature = "os-perf")]
+        updates.push(dispatched!(GPUEnginePerformances, self.gpu_engine_performances, gpu_engine_performances));
+        #[cfg(feature = "os-processes")]
+        updates.push(dispatched!(Processes, self.processes, processes));
+        #[cfg(feature = "os-drivers")]
+        updates.push(dispatched!(Drivers, self.drivers, drivers));
+        #[cfg(feature = "os-registry")]
+        updates.push(dispatched!(Registry, self.registry, registries));
+        #[cfg(feature = "os-registry")]
+        updates.push(dispatched!(RegistryKeys, self.registry_keys, registry_keys));
+        #[cfg(feature = "os-com")]
+        updates.push(dispatched!(DcomApplications, self.dcom_applications, dcom_applications));
+        #[cfg(feature = "os-com")]
+        updates.push(dispatched!(DcomApplicationSettings, self.dcom_application_settings, dcom_application_settings));
+        #[cfg(feature = "os-com")]
+        updates.push(dispatched!(ComSettings, self.com_settings, com_settings));
+        #[cfg(feature = "os-com")]
+        updates.push(dispatched!(ClassicComClassSettings, self.classic_com_class_settings, classic_com_class_settings));
+        #[cfg(feature = "os-device-security")]
+        updates.push(dispatched!(Tpms, self.tpms, tpms));
+        #[cfg(feature = "os-device-security")]
+        updates.push(dispatched!(SecureBoot, self.secure_boot, secure_boot));
+        #[cfg(feature = "os-device-security")]
+        updates.push(dispatched!(EncryptableVolumes, self.encryptable_volumes, encryptable_volumes));
+        #[cfg(feature = "os-security-center")]
+        updates.push(dispatched!(AntiVirusProducts, self.anti_virus_products, anti_virus_products));
+        #[cfg(feature = "os-security-center")]
+        updates.push(dispatched!(FirewallProducts, self.firewall_products, firewall_products));
+        #[cfg(feature = "os-security-center")]
+        updates.push(dispatched!(AntiSpywareProducts, self.anti_spyware_products, anti_spyware_products));
+        #[cfg(feature = "os-services")]
+        updates.push(dispatched!(Services, self.services, services));
+        #[cfg(feature = "os-services")]
+        updates.push(dispatched!(DependentServices, self.dependent_services, dependent_services));
+        #[cfg(feature = "os-desktop")]
+        updates.push(dispatched!(Desktops, self.desktops, desktops));
+        #[cfg(feature = "os-desktop")]
+        updates.push(dispatched!(Environment, self.environment, environments));
+        #[cfg(feature = "os-desktop")]
+        updates.push(dispatched!(Timezones, self.timezones, timezones));
+        #[cfg(feature = "os-users")]
+        updates.push(dispatched!(UserAccounts, self.user_accounts, user_accounts));
+        #[cfg(feature = "os-users")]
+        updates.push(dispatched!(Groups, self.groups, groups));
+        #[cfg(feature = "os-users")]
+        updates.push(dispatched!(LogonSessions, self.logon_sessions, logon_sessions));
+        #[cfg(feature = "os-users")]
+        updates.push(dispatched!(NetworkLoginProfiles, self.network_login_profiles, network_login_profiles));
+        #[cfg(feature = "os-users")]
+        updates.push(dispatched!(SystemAccounts, self.system_accounts, system_accounts));
+        #[cfg(feature = "os-users")]
+        updates.push(dispatched!(LocalGroups, self.local_groups, local_groups));
+        #[cfg(feature = "os-users")]
+        updates.push(dispatched!(UserProfiles, self.user_profiles, user_profiles));
+        #[cfg(feature = "os-file-system")]
+        updates.push(dispatched!(Directories, self.directories, directories));
+        #[cfg(feature = "os-file-system")]
+        updates.push(dispatched!(DirectoriesSpecifications, self.directories_specifications, directory_specifications));
+        #[cfg(feature = "os-file-system")]
+        updates.push(dispatched!(DiskPartition, self.disk_partition, disk_partitions));
+        #[cfg(feature = "os-file-system")]
+        updates.push(dispatched!(DiskDriveToDiskPartitions, self.disk_drive_to_disk_partitions, disk_drive_to_disk_partitions));
+        #[cfg(feature = "os-file-system")]
+        updates.push(dispatched!(LogicalDisks, self.logical_disks, logical_disks));
+        #[cfg(feature = "os-file-system")]
+        updates.push(dispatched!(LogicalDiskToPartitions, self.logical_disk_to_partitions, logical_disk_to_partitions));
+        #[cfg(feature = "os-file-system")]
+        updates.push(dispatched!(MappedLogicalDisks, self.mapped_logical_disks, mapped_logical_disks));
+        #[cfg(feature = "os-file-system")]
+        updates.push(dispatched!(QuotaSettings, self.quota_settings, quota_settings));
+        #[cfg(feature = "os-file-system")]
+        updates.push(dispatched!(ShortcutFiles, self.shortcut_files, shortcut_files));
+        #[cfg(feature = "os-file-system")]
+        updates.push(dispatched!(Volumes, self.volumes, volumes));
+        #[cfg(feature = "os-event-log")]
+        updates.push(dispatched!(NtEventLogFiles, self.nt_event_log_files, nt_event_log_files));
+        #[cfg(feature = "os-event-log")]
+        updates.push(dispatched!(NtLogEvents, self.nt_log_events, nt_log_events));
+        #[cfg(feature = "os-event-log")]
+        updates.push(dispatched!(BootHistory, self.boot_history, boot_events));
+        #[cfg(feature = "os-event-log")]
+        updates.push(dispatched!(BootInfos, self.boot_infos, boot_infos));
+        #[cfg(feature = "os-memory-and-pagefiles")]
+        updates.push(dispatched!(Pagefiles, self.pagefiles, pagefiles));
+        #[cfg(feature = "os-memory-and-pagefiles")]
+        updates.push(dispatched!(PagefileSettings, self.pagefile_settings, pagefile_settings));
+        #[cfg(feature = "os-memory-and-pagefiles")]
+        updates.push(dispatched!(PagefileUsages, self.pagefile_usages, pagefile_usage));
+        #[cfg(feature = "os-scheduler-jobs")]
+        updates.push(dispatched!(ScheduledJobs, self.scheduled_jobs, scheduled_jobs));
+        #[cfg(feature = "os-scheduler-jobs")]
+        updates.push(dispatched!(ScheduledTasks, self.scheduled_tasks, scheduled_tasks));
+        #[cfg(feature = "os-scheduler-jobs")]
+        updates.push(dispatched!(LocalTimes, self.local_times, local_times));
+        #[cfg(feature = "os-scheduler-jobs")]
+        updates.push(dispatched!(UtcTimes, self.utc_times, utc_times));
+        #[cfg(feature = "os-time-service")]
+        updates.push(dispatched!(TimeService, self.time_service, time_settings));
+        #[cfg(feature = "os-updates")]
+        updates.push(dispatched!(WindowsUpdates, self.windows_updates, pending_updates));
+        #[cfg(feature = "os-security-settings")]
+        updates.push(dispatched!(SecuritySettings, self.security_settings, audit_policies));
+        #[cfg(feature = "os-group-policy")]
+        updates.push(dispatched!(GPOs, self.gpos, gpos));
+        #[cfg(feature = "os-group-policy")]
+        updates.push(dispatched!(GPLinks, self.gp_links, gp_links));
+        #[cfg(feature = "os-group-policy")]
+        updates.push(dispatched!(GPExtensionStatuses, self.gp_extension_statuses, gp_extension_statuses));
+        #[cfg(feature = "os-software-license-provider")]
+        updates.push(dispatched!(SoftwareLicensingProducts, self.software_licensing_products, software_licensing_products));
+        #[cfg(feature = "os-software-license-provider")]
+        updates.push(dispatched!(SoftwareLicensingServices, self.software_licensing_services, software_licensing_services));
+        #[cfg(feature = "os-software-license-provider")]
+        updates.push(dispatched!(SoftwareLicensingTokenActivationLicenses, self.software_licensing_token_activation_licenses, software_licensing_token_activation_licenses));
+        #[cfg(feature = "os-shares")]
+        updates.push(dispatched!(ServerConnections, self.server_connections, server_connections));
+        #[cfg(feature = "os-shares")]
+        updates.push(dispatched!(ServerSessions, self.server_sessions, server_sessions));
+        #[cfg(feature = "os-shares")]
+        updates.push(dispatched!(Shares, self.shares, shares));
+        #[cfg(feature = "os-shares")]
+        updates.push(dispatched!(ShareSecurityDescriptors, self.share_security_descriptors, share_security_descriptors));
+        #[cfg(feature = "os-multimedia-audio-visual")]
+        updates.push(dispatched!(CodecFiles, self.codec_files, codec_files));
+        #[cfg(feature = "os-capture-privacy")]
+        updates.push(dispatched!(CapturePrivacy, self.capture_privacy, capture_devices));
+        #[cfg(feature = "os-storage")]
+        updates.push(dispatched!(ShadowCopys, self.shadow_copys, shadow_copys));
+        #[cfg(feature = "os-storage")]
+        updates.push(dispatched!(ShadowContexts, self.shadow_contexts, shadow_contexts));
+        #[cfg(feature = "os-storage")]
+        updates.push(dispatched!(ShadowProviders, self.shadow_providers, shadow_providers));
+        #[cfg(feature = "os-security")]
+        updates.push(dispatched!(LogicalFileSecuritySettings, self.logical_file_security_settings, logical_file_security_settings));
+        #[cfg(feature = "os-security")]
+        updates.push(dispatched!(LogicalShareSecuritySettings, self.logical_share_security_settings, logical_share_security_settings));
+        #[cfg(feature = "os-security")]
+        updates.push(dispatched!(PrivilegesStatuses, self.privileges_statuses, privileges_statuses));
+        #[cfg(feature = "os-start-menu")]
+        updates.push(dispatched!(LogicalProgramGroups, self.logical_program_groups, logical_program_groups));
+        #[cfg(feature = "os-start-menu")]
+        updates.push(dispatched!(LogicalProgramGroupItems, self.logical_program_group_items, logical_program_group_items));
+        #[cfg(feature = "os-networking")]
+        updates.push(dispatched!(Ip4PersistedRouteTables, self.ip4_persisted_route_tables, ip4_persisted_route_tables));
+        #[cfg(feature = "os-networking")]
+        updates.push(dispatched!(Ip4RouteTables, self.ip4_route_tables, ip4_route_tables));
+        #[cfg(feature = "os-networking")]
+        updates.push(dispatched!(NeworkClients, self.nework_clients, nework_clients));
+        #[cfg(feature = "os-networking")]
+        updates.push(dispatched!(NeworkConnections, self.nework_connections, nework_connections));
+        #[cfg(feature = "os-networking")]
+        updates.push(dispatched!(NeworkProtocols, self.nework_protocols, nework_protocols));
+        #[cfg(feature = "os-networking")]
+        updates.push(dispatched!(NtDomains, self.nt_domains, nt_domains));
+        #[cfg(feature = "os-networking")]
+        updates.push(dispatched!(Ip4RouteTableEvents, self.ip4_route_table_events, ip4_route_table_events));
+        #[cfg(feature = "os-networking")]
+        updates.push(dispatched!(QosPolicies, self.qos_policies, qos_policies));
+        #[cfg(feature = "os-firewall")]
+        updates.push(dispatched!(FirewallProfiles, self.firewall_profiles, firewall_profiles));
+        #[cfg(feature = "os-firewall")]
+        updates.push(dispatched!(FirewallRules, self.firewall_rules, firewall_rules));
+        #[cfg(feature = "os-firewall")]
+        updates.push(dispatched!(FirewallPortFilters, self.firewall_port_filters, port_filters));
+        #[cfg(feature = "os-firewall")]
+        updates.push(dispatched!(FirewallApplicationFilters, self.firewall_application_filters, application_filters));
+        #[cfg(feature = "os-firewall")]
+        updates.push(dispatched!(FirewallAddressFilters, self.firewall_address_filters, address_filters));
+        #[cfg(feature = "os-connections")]
+        updates.push(dispatched!(TcpConnections, self.tcp_connections, tcp_connections));
+        #[cfg(feature = "os-connections")]
+        updates.push(dispatched!(UdpEndpoints, self.udp_endpoints, udp_endpoints));
+        #[cfg(feature = "os-connections")]
+        updates.push(dispatched!(NetNeighbors, self.net_neighbors, net_neighbors));
+        #[cfg(feature = "os-dns")]
+        updates.push(dispatched!(DnsClientCache, self.dns_client_cache, dns_client_cache));
+        #[cfg(feature = "os-dns")]
+        updates.push(dispatched!(HostsFile, self.hosts_file, hosts_entries));
+        #[cfg(feature = "os-job-objects")]
+        updates.push(dispatched!(NamedJobObjects, self.named_job_objects, named_job_objects));
+        #[cfg(feature = "os-job-objects")]
+        updates.push(dispatched!(NamedJobObjectActgInfos, self.named_job_object_actg_infos, named_job_object_actg_infos));
+        #[cfg(feature = "os-job-objects")]
+        updates.push(dispatched!(NamedJobObjectLimitSettings, self.named_job_object_limit_settings, named_job_object_limit_settings));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(dispatched!(BootConfigurations, self.boot_configurations, boot_configurations));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(dispatched!(ComputerSystems, self.computer_systems, computer_systems));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(dispatched!(SystemServices, self.system_services, system_services));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(dispatched!(ComputerSystemProducts, self.computer_system_products, computer_system_products));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(dispatched!(LoadOrderGroups, self.load_order_groups, load_order_groups));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(dispatched!(OperatingSystems, self.operating_systems, operating_systems));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(dispatched!(OsRecoveryConfigurations, self.os_recovery_configurations, os_recovery_configurations));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(dispatched!(QuickFixEngineerings, self.quick_fix_engineerings, quick_fix_engineerings));
+        #[cfg(feature = "os-optional-features")]
+        updates.push(dispatched!(OptionalFeatures, self.optional_features, optional_features));
+        #[cfg(feature = "os-optional-features")]
+        updates.push(dispatched!(ServerFeatures, self.server_features, server_features));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(dispatched!(StartupCommands, self.startup_commands, startup_commands));
+        #[cfg(feature = "os-persistence")]
+        updates.push(dispatched!(PersistenceItems, self.persistence_items, persistence_items));
+        #[cfg(feature = "os-installed-software")]
+        updates.push(dispatched!(InstalledSoftware, self.installed_software, installed_software));
+        #[cfg(feature = "hw-cooling-device")]
+        updates.push(dispatched!(Fans, self.fans, fans));
+        #[cfg(feature = "hw-cooling-device")]
+        updates.push(dispatched!(HeatPipes, self.heat_pipes, heat_pipes));
+        #[cfg(feature = "hw-cooling-device")]
+        updates.push(dispatched!(Refrigerations, self.refrigerations, refrigerations));
+        #[cfg(feature = "hw-cooling-device")]
+        updates.push(dispatched!(TemperatureProbes, self.temperature_probes, temperature_probes));
+        #[cfg(feature = "hw-input-device")]
+        updates.push(dispatched!(Keyboards, self.keyboards, keyboards));
+        #[cfg(feature = "hw-input-device")]
+        updates.push(dispatched!(PointingDevices, self.pointing_devices, pointing_devices));
+        #[cfg(feature = "hw-input-device")]
+        updates.push(dispatched!(InputDeviceEvents, self.input_device_events, input_device_events));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(dispatched!(AutochkSettings, self.autochk_settings, autochk_settings));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(dispatched!(CdRomDrives, self.cd_rom_drives, cd_rom_drives));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(dispatched!(DiskDrives, self.disk_drives, disk_drives));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(dispatched!(PhysicalMedias, self.physical_medias, physical_medias));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(dispatched!(TapeDrives, self.tape_drives, tape_drives));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(dispatched!(PhysicalDisks, self.physical_disks, physical_disks));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(dispatched!(NvmeCapabilities, self.nvme_capabilities, nvme_devices));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(dispatched!(Disks, self.disks, disks));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(dispatched!(StorageReliabilityCounters, self.storage_reliability_counters, storage_reliability_counters));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(dispatched!(FailurePredictStatuses, self.failure_predict_statuses, failure_predict_statuses));
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        updates.push(dispatched!(PnpEntities, self.pnp_entities, pnp_entities));
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        updates.push(dispatched!(Bioses, self.bioses, bioses));
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        updates.push(dispatched!(SystemEnclosures, self.system_enclosures, system_enclosures));
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        updates.push(dispatched!(SerialPortConfigurations, self.serial_port_configurations, serial_port_configurations));
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        updates.push(dispatched!(ParallelPorts, self.parallel_ports, parallel_ports));
+        #[cfg(feature = "hw-usb")]
+        updates.push(dispatched!(UsbDevices, self.usb_devices, usb_devices));
+        #[cfg(feature = "hw-networking-device")]
+        updates.push(dispatched!(NetworkAdapters, self.network_adapters, network_adapters));
+        #[cfg(feature = "hw-networking-device")]
+        updates.push(dispatched!(NetworkAdapterConfigurations, self.network_adapter_configurations, network_adapter_configurations));
+        #[cfg(feature = "hw-networking-device")]
+        updates.push(dispatched!(NetAdapters, self.net_adapters, net_adapters));
+        #[cfg(feature = "hw-telephony")]
+        updates.push(dispatched!(PotModems, self.pot_modems, pot_modems));
+        #[cfg(feature = "hw-telephony")]
+        updates.push(dispatched!(PotModemToSerialPorts, self.pot_modem_to_serial_ports, pot_modem_to_serial_ports));
+        #[cfg(feature = "hw-power")]
+        updates.push(dispatched!(Batteries, self.batteries, batteries));
+        #[cfg(feature = "hw-power")]
+        updates.push(dispatched!(CurrentProbes, self.current_probes, current_probes));
+        #[cfg(feature = "hw-power")]
+        updates.push(dispatched!(PortableBatteries, self.portable_batteries, portable_batteries));
+        #[cfg(feature = "hw-power")]
+        updates.push(dispatched!(PowerManagementEvents, self.power_management_events, power_management_events));
+        #[cfg(feature = "hw-power")]
+        updates.push(dispatched!(VoltageProbes, self.voltage_probes, voltage_probes));
+        #[cfg(feature = "hw-printing")]
+        updates.push(dispatched!(Printers, self.printers, printers));
+        #[cfg(feature = "hw-printing")]
+        updates.push(dispatched!(PrinterDrivers, self.printer_drivers, printer_drivers));
+        #[cfg(feature = "hw-printing")]
+        updates.push(dispatched!(DriverForDevices, self.driver_for_devices, driver_for_devices));
+        #[cfg(feature = "hw-printing")]
+        updates.push(dispatched!(TCPIPPrinterPorts, self.tcpip_printer_ports, tcpip_printer_ports));
+        #[cfg(feature = "hw-video-monitor")]
+        updates.push(dispatched!(DesktopMonitors, self.desktop_monitors, desktop_monitors));
+        #[cfg(feature = "hw-video-monitor")]
+        updates.push(dispatched!(DisplayControllerConfigurations, self.display_controller_configurations, display_controller_configurations));
+        #[cfg(feature = "hw-video-monitor")]
+        updates.push(dispatched!(VideoControllers, self.video_controllers, video_controllers));
+
+        #[cfg(feature = "bluetooth")]
+        updates.push(dispatched!(BluetoothDevices, self.bluetooth_devices, bluetooth_devices));
+        #[cfg(feature = "hyperv")]
+        updates.push(dispatched!(VirtualMachines, self.virtual_machines, virtual_machines));
+        #[cfg(feature = "hyperv")]
+        updates.push(dispatched!(VirtualProcessorSettings, self.virtual_processor_settings, virtual_processor_settings));
+        #[cfg(feature = "hyperv")]
+        updates.push(dispatched!(VirtualMemorySettings, self.virtual_memory_settings, virtual_memory_settings));
+
+        let meta = &mut self.meta;
+        meta.start();
+
+        let threads = threads.max(1).min(updates.len());
+        let queue = Mutex::new(updates.into_iter());
+
+        let outcomes: Vec<(StateKind, Result<usize, Unavailable>, Duration)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let _com_con = wmi::COMLibrary::new();
+                        let mut outcomes = Vec::new();
+
+                        while let Some((kind, update)) = queue.lock().ok().and_then(|mut queue| queue.next()) {
+                            let start = Instant::now();
+                            let outcome = update();
+
+                            outcomes.push((kind, outcome, start.elapsed()));
+                        }
+
+                        outcomes
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        });
+
+        for (kind, outcome, duration) in outcomes {
+            meta.record(kind, duration, &outcome);
+        }
+
+        if !self.custom.is_empty() {
+            let _com_con = wmi::COMLibrary::new();
+
+            for state in self.custom.values_mut() {
+                state.update();
+            }
+        }
+
+        meta.finish();
+    }
+
+    /// Asynchronously update all the fields, running at most [`DEFAULT_CONCURRENCY`] queries at a time
     pub async fn async_update(&mut self) {
-        join!(
-            self.threads.async_update(),
-            self.processes.async_update(),
-            self.drivers.async_update(),
-            self.registry.async_update(),
-            self.services.async_update(),
-            self.desktops.async_update(),
-            self.environment.async_update(),
-            self.timezones.async_update(),
-            self.user_accounts.async_update(),
-            // self.user_desktops.async_update(),
-            // self.accounts.async_update(),
-            self.groups.async_update(),
-            self.logon_sessions.async_update(),
-            self.network_login_profiles.async_update(),
-            self.system_accounts.async_update(),
-            self.directories.async_update(),
-            self.directories_specifications.async_update(),
-            self.disk_partition.async_update(),
-            self.logical_disks.async_update(),
-            self.mapped_logical_disks.async_update(),
-            self.quota_settings.async_update(),
-            self.shortcut_files.async_update(),
-            self.volumes.async_update(),
-            self.nt_event_log_files.async_update(),
-            self.nt_log_events.async_update(),
-            self.pagefiles.async_update(),
-            self.pagefile_settings.async_update(),
-            self.pagefile_usages.async_update(),
-            self.scheduled_jobs.async_update(),
-            self.local_times.async_update(),
-            self.utc_times.async_update(),
-            self.software_licensing_products.async_update(),
-            self.software_licensing_services.async_update(),
-            self.software_licensing_token_activation_licenses.async_update(),
-            self.server_connections.async_update(),
-            self.server_sessions.async_update(),
-            self.shares.async_update(),
-            self.codec_files.async_update(),
-            self.shadow_copys.async_update(),
-            self.shadow_contexts.async_update(),
-            self.shadow_providers.async_update(),
-            self.logical_file_security_settings.async_update(),
-            self.logical_share_security_settings.async_update(),
-            self.privileges_statuses.async_update(),
-            self.logical_program_groups.async_update(),
-            self.logical_program_group_items.async_update(),
-            self.ip4_persisted_route_tables.async_update(),
-            self.ip4_route_tables.async_update(),
-            self.nework_clients.async_update(),
-            self.nework_connections.async_update(),
-            self.nework_protocols.async_update(),
-            self.nt_domains.async_update(),
-            self.ip4_route_table_events.async_update(),
-            self.named_job_objects.async_update(),
-            self.named_job_object_actg_infos.async_update(),
-            self.named_job_object_limit_settings.async_update(),
-            self.boot_configurations.async_update(),
-            self.computer_systems.async_update(),
-            self.computer_system_products.async_update(),
-            self.load_order_groups.async_update(),
-            self.operating_systems.async_update(),
-            self.os_recovery_configurations.async_update(),
-            self.quick_fix_engineerings.async_update(),
-            self.startup_commands.async_update(),
-            self.fans.async_update(),
-            self.heat_pipes.async_update(),
-            self.refrigerations.async_update(),
-            self.temperature_probes.async_update(),
-            self.keyboards.async_update(),
-            self.pointing_devices.async_update(),
-            self.autochk_settings.async_update(),
-            self.cd_rom_drives.async_update(),
-            self.disk_drives.async_update(),
-            self.physical_medias.async_update(),
-            self.tape_drives.async_update(),
-            self.network_adapters.async_update(),
-            self.network_adapter_configurations.async_update(),
-            self.pot_modems.async_update(),
-            self.batteries.async_update(),
-            self.current_probes.async_update(),
-            self.portable_batteries.async_update(),
-            self.power_management_events.async_update(),
-            self.voltage_probes.async_update(),
-            self.desktop_monitors.async_update(),
-            self.display_controller_configurations.async_update(),
-            self.video_controllers.async_update(),
-        );
+        self.async_update_with_concurrency(DEFAULT_CONCURRENCY).await;
+    }
+
+    /// Asynchronously update all the fields, running at most `limit` queries at a time
+    ///
+    /// A `limit` of 0 is treated as 1
+    pub async fn async_update_with_concurrency(&mut self, limit: usize) {
+        self.async_update_with_progress(limit, |_, _| {}).await;
+    }
+
+    /// Asynchronously update all the fields, running at most `limit` queries at a time and calling
+    /// `on_progress` as each member completes, with the number of instances it holds or why its
+    /// class is unavailable
+    ///
+    /// A `limit` of 0 is treated as 1
+    pub async fn async_update_with_progress(
+        &mut self,
+        limit: usize,
+        on_progress: impl FnMut(StateKind, Result<usize, Unavailable>),
+    ) {
+        self.async_update_members(&SnapshotProfile::all(), limit, None, on_progress).await;
+        self.async_update_custom().await;
+    }
+
+    /// Synchronously update the members in `profile`
+    pub fn update_profile(&mut self, profile: &SnapshotProfile) {
+        self.meta.start();
+
+        for kind in &profile.kinds {
+            let _ = self.update_member(*kind);
+        }
+
+        self.meta.finish();
+    }
+
+    /// Asynchronously update the members in `profile`, running at most [`DEFAULT_CONCURRENCY`]
+    /// queries at a time
+    pub async fn async_update_profile(&mut self, profile: &SnapshotProfile) {
+        self.async_update_members(profile, DEFAULT_CONCURRENCY, None, |_, _| {}).await;
+    }
+
+    /// Asynchronously update the members `config` selects, running at most as many queries at a
+    /// time as it allows and giving up on members whose update exceeds its timeout
+    ///
+    /// A member given up on keeps its previous instances, its timeout is recorded in
+    /// [`SnapshotMeta`]. Custom members are updated when every member is selected.
+    pub async fn async_update_with_config(&mut self, config: &crate::config::Config) {
+        let profile = config.profile();
+
+        self.async_update_members(
+            &profile,
+            config.concurrency.unwrap_or(DEFAULT_CONCURRENCY),
+            config.timeout,
+            |_, _| {},
+        )
+        .await;
+
+        if profile == SnapshotProfile::all() {
+            self.async_update_custom().await;
+        }
+    }
+
+    /// Updates the members in `selection` every `interval` and yields what changed since the
+    /// previous update, see [`crate::watch`]
+    ///
+    /// The first update primes the watch, updates that change nothing yield nothing. The stream
+    /// never ends.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn watch(
+        self,
+        interval: Duration,
+        selection: SnapshotProfile,
+    ) -> impl Stream<Item = WindowsDiff> {
+        let mut ticks = tokio::time::interval(interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        stream::unfold(
+            (self, selection, ticks, None),
+            |(mut windows, selection, mut ticks, mut previous)| async move {
+                loop {
+                    ticks.tick().await;
+                    windows.async_update_profile(&selection).await;
+
+                    let current = serde_json::to_value(&windows).unwrap_or_default();
+                    let diff = previous
+                        .as_ref()
+                        .map(|previous| WindowsDiff::between(previous, &current, &selection.kinds));
+
+                    previous = Some(current);
+
+                    if let Some(diff) = diff.filter(|diff| !diff.is_empty()) {
+                        return Some((diff, (windows, selection, ticks, previous)));
+                    }
+                }
+            },
+        )
+    }
+
+    /// Like [`Windows::watch`], updating the members `config` selects every
+    /// [`Config::interval`](crate::config::Config::interval) as
+    /// [`Windows::async_update_with_config`] does
+    ///
+    /// The stream never ends.
+    pub fn watch_with_config(self, config: crate::config::Config) -> impl Stream<Item = WindowsDiff> {
+        let mut ticks = tokio::time::interval(config.interval());
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        stream::unfold(
+            (self, config, ticks, None),
+            |(mut windows, config, mut ticks, mut previous)| async move {
+                loop {
+                    ticks.tick().await;
+                    windows.async_update_with_config(&config).await;
+
+                    let current = serde_json::to_value(&windows).unwrap_or_default();
+                    let diff = previous
+                        .as_ref()
+                        .map(|previous| WindowsDiff::between(previous, &current, &config.profile().kinds));
+
+                    previous = Some(current);
+
+                    if let Some(diff) = diff.filter(|diff| !diff.is_empty()) {
+                        return Some((diff, (windows, config, ticks, previous)));
+                    }
+                }
+            },
+        )
+    }
+
+    /// Like [`Windows::watch`], backing off as `throttle` says while a battery is discharging
+    /// below its threshold, so laptops do not spend their charge on expensive members
+    ///
+    /// `batteries` is updated before every update to tell, throttled intervals are skipped
+    /// altogether. The stream never ends.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    #[cfg(feature = "hw-power")]
+    pub fn watch_on_battery(
+        self,
+        interval: Duration,
+        selection: SnapshotProfile,
+        throttle: BatteryThrottle,
+    ) -> impl Stream<Item = WindowsDiff> {
+        let mut ticks = tokio::time::interval(interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        stream::unfold(
+            (self, selection, throttle, ticks, None, 0u32),
+            |(mut windows, selection, throttle, mut ticks, mut previous, mut skipped_ticks)| async move {
+                loop {
+                    ticks.tick().await;
+                    windows.batteries.async_update().await;
+
+                    let profile = if throttle.engaged(&windows.batteries) {
+                        if skipped_ticks + 1 < throttle.slowdown && previous.is_some() {
+                            skipped_ticks += 1;
+                            continue;
+                        }
+
+                        throttle.apply(&selection)
+                    } else {
+                        selection.clone()
+                    };
+
+                    skipped_ticks = 0;
+                    windows.async_update_profile(&profile).await;
+
+                    let current = serde_json::to_value(&windows).unwrap_or_default();
+                    let diff = previous
+                        .as_ref()
+                        .map(|previous| WindowsDiff::between(previous, &current, &profile.kinds));
+
+                    previous = Some(current);
+
+                    if let Some(diff) = diff.filter(|diff| !diff.is_empty()) {
+                        return Some((diff, (windows, selection, throttle, ticks, previous, skipped_ticks)));
+                    }
+                }
+            },
+        )
+    }
+
+    /// Synchronously update the members of [`SnapshotProfile::quick`]
+    pub fn quick_update(&mut self) {
+        self.update_profile(&SnapshotProfile::quick());
+    }
+
+    /// Asynchronously update the members of [`SnapshotProfile::quick`]
+    pub async fn async_quick_update(&mut self) {
+        self.async_update_profile(&SnapshotProfile::quick()).await;
+    }
+
+    async fn async_update_members(
+        &mut self,
+        profile: &SnapshotProfile,
+        limit: usize,
+        timeout: Option<Duration>,
+        mut on_progress: impl FnMut(StateKind, Result<usize, Unavailable>),
+    ) {
+        #[allow(unused_mut)]
+        let mut updates: Vec<(StateKind, LocalBoxFuture<'_, Result<usize, Unavailable>>)> = Vec::new();
+
+        #[cfg(feature = "os-processes")]
+        updates.push(tracked!(Threads, self.threads, threads));
+        #[cfg(feature = "os-perf")]
+        updates.push(tracked!(ProcessorPerformances, self.processor_performances, processor_performances));
+        #[cfg(feature = "os-perf")]
+        updates.push(tracked!(MemoryPerformances, self.memory_performances, memory_performances));
+        #[cfg(feature = "os-perf")]
+        updates.push(tracked!(LogicalDiskPerformances, self.logical_disk_performances, logical_disk_performances));
+        #[cfg(feature = "os-perf")]
+        updates.push(tracked!(GPUEnginePerformances, self.gpu_engine_performances, gpu_engine_performances));
+        #[cfg(feature = "os-processes")]
+        updates.push(tracked!(Processes, self.processes, processes));
+        #[cfg(feature = "os-drivers")]
+        updates.push(tracked!(Drivers, self.drivers, drivers));
+        #[cfg(feature = "os-registry")]
+        updates.push(tracked!(Registry, self.registry, registries));
+        #[cfg(feature = "os-registry")]
+        updates.push(tracked!(RegistryKeys, self.registry_keys, registry_keys));
+        #[cfg(feature = "os-com")]
+        updates.push(tracked!(DcomApplications, self.dcom_applications, dcom_applications));
+        #[cfg(feature = "os-com")]
+        updates.push(tracked!(DcomApplicationSettings, self.dcom_application_settings, dcom_application_settings));
+        #[cfg(feature = "os-com")]
+        updates.push(tracked!(ComSettings, self.com_settings, com_settings));
+        #[cfg(feature = "os-com")]
+        updates.push(tracked!(ClassicComClassSettings, self.classic_com_class_settings, classic_com_class_settings));
+        #[cfg(feature = "os-device-security")]
+        updates.push(tracked!(Tpms, self.tpms, tpms));
+        #[cfg(feature = "os-device-security")]
+        updates.push(tracked!(SecureBoot, self.secure_boot, secure_boot));
+        #[cfg(feature = "os-device-security")]
+        updates.push(tracked!(EncryptableVolumes, self.encryptable_volumes, encryptable_volumes));
+        #[cfg(feature = "os-security-center")]
+        updates.push(tracked!(AntiVirusProducts, self.anti_virus_products, anti_virus_products));
+        #[cfg(feature = "os-security-center")]
+        updates.push(tracked!(FirewallProducts, self.firewall_products, firewall_products));
+        #[cfg(feature = "os-security-center")]
+        updates.push(tracked!(AntiSpywareProducts, self.anti_spyware_products, anti_spyware_products));
+        #[cfg(feature = "os-services")]
+        updates.push(tracked!(Services, self.services, services));
+        #[cfg(feature = "os-services")]
+        updates.push(tracked!(DependentServices, self.dependent_services, dependent_services));
+        #[cfg(feature = "os-desktop")]
+        updates.push(tracked!(Desktops, self.desktops, desktops));
+        #[cfg(feature = "os-desktop")]
+        updates.push(tracked!(Environment, self.environment, environments));
+        #[cfg(feature = "os-desktop")]
+        updates.push(tracked!(Timezones, self.timezones, timezones));
+        #[cfg(feature = "os-users")]
+        updates.push(tracked!(UserAccounts, self.user_accounts, user_accounts));
+        #[cfg(feature = "os-users")]
+        updates.push(tracked!(Groups, self.groups, groups));
+        #[cfg(feature = "os-users")]
+        updates.push(tracked!(LogonSessions, self.logon_sessions, logon_sessions));
+        #[cfg(feature = "os-users")]
+        updates.push(tracked!(NetworkLoginProfiles, self.network_login_profiles, network_login_profiles));
+        #[cfg(feature = "os-users")]
+        updates.push(tracked!(SystemAccounts, self.system_accounts, system_accounts));
+        #[cfg(feature = "os-users")]
+        updates.push(tracked!(LocalGroups, self.local_groups, local_groups));
+        #[cfg(feature = "os-users")]
+        updates.push(tracked!(UserProfiles, self.user_profiles, user_profiles));
+        #[cfg(feature = "os-file-system")]
+        updates.push(tracked!(Directories, self.directories, directories));
+        #[cfg(feature = "os-file-system")]
+        updates.push(tracked!(DirectoriesSpecifications, self.directories_specifications, directory_specifications));
+        #[cfg(feature = "os-file-system")]
+        updates.push(tracked!(DiskPartition, self.disk_partition, disk_partitions));
+        #[cfg(feature = "os-file-system")]
+        updates.push(tracked!(DiskDriveToDiskPartitions, self.disk_drive_to_disk_partitions, disk_drive_to_disk_partitions));
+        #[cfg(feature = "os-file-system")]
+        updates.push(tracked!(LogicalDisks, self.logical_disks, logical_disks));
+        #[cfg(feature = "os-file-system")]
+        updates.push(tracked!(LogicalDiskToPartitions, self.logical_disk_to_partitions, logical_disk_to_partitions));
+        #[cfg(feature = "os-file-system")]
+        updates.push(tracked!(MappedLogicalDisks, self.mapped_logical_disks, mapped_logical_disks));
+        #[cfg(feature = "os-file-system")]
+        updates.push(tracked!(QuotaSettings, self.quota_settings, quota_settings));
+        #[cfg(feature = "os-file-system")]
+        updates.push(tracked!(ShortcutFiles, self.shortcut_files, shortcut_files));
+        #[cfg(feature = "os-file-system")]
+        updates.push(tracked!(Volumes, self.volumes, volumes));
+        #[cfg(feature = "os-event-log")]
+        updates.push(tracked!(NtEventLogFiles, self.nt_event_log_files, nt_event_log_files));
+        #[cfg(feature = "os-event-log")]
+        updates.push(tracked!(NtLogEvents, self.nt_log_events, nt_log_events));
+        #[cfg(feature = "os-event-log")]
+        updates.push(tracked!(BootHistory, self.boot_history, boot_events));
+        #[cfg(feature = "os-event-log")]
+        updates.push(tracked!(BootInfos, self.boot_infos, boot_infos));
+        #[cfg(feature = "os-memory-and-pagefiles")]
+        updates.push(tracked!(Pagefiles, self.pagefiles, pagefiles));
+        #[cfg(feature = "os-memory-and-pagefiles")]
+        updates.push(tracked!(PagefileSettings, self.pagefile_settings, pagefile_settings));
+        #[cfg(feature = "os-memory-and-pagefiles")]
+        updates.push(tracked!(PagefileUsages, self.pagefile_usages, pagefile_usage));
+        #[cfg(feature = "os-scheduler-jobs")]
+        updates.push(tracked!(ScheduledJobs, self.scheduled_jobs, scheduled_jobs));
+        #[cfg(feature = "os-scheduler-jobs")]
+        updates.push(tracked!(ScheduledTasks, self.scheduled_tasks, scheduled_tasks));
+        #[cfg(feature = "os-scheduler-jobs")]
+        updates.push(tracked!(LocalTimes, self.local_times, local_times));
+        #[cfg(feature = "os-scheduler-jobs")]
+        updates.push(tracked!(UtcTimes, self.utc_times, utc_times));
+        #[cfg(feature = "os-time-service")]
+        updates.push(tracked!(TimeService, self.time_service, time_settings));
+        #[cfg(feature = "os-updates")]
+        updates.push(tracked!(WindowsUpdates, self.windows_updates, pending_updates));
+        #[cfg(feature = "os-security-settings")]
+        updates.push(tracked!(SecuritySettings, self.security_settings, audit_policies));
+        #[cfg(feature = "os-group-policy")]
+        updates.push(tracked!(GPOs, self.gpos, gpos));
+        #[cfg(feature = "os-group-policy")]
+        updates.push(tracked!(GPLinks, self.gp_links, gp_links));
+        #[cfg(feature = "os-group-policy")]
+        updates.push(tracked!(GPExtensionStatuses, self.gp_extension_statuses, gp_extension_statuses));
+        #[cfg(feature = "os-software-license-provider")]
+        updates.push(tracked!(SoftwareLicensingProducts, self.software_licensing_products, software_licensing_products));
+        #[cfg(feature = "os-software-license-provider")]
+        updates.push(tracked!(SoftwareLicensingServices, self.software_licensing_services, software_licensing_services));
+        #[cfg(feature = "os-software-license-provider")]
+        updates.push(tracked!(SoftwareLicensingTokenActivationLicenses, self.software_licensing_token_activation_licenses, software_licensing_token_activation_licenses));
+        #[cfg(feature = "os-shares")]
+        updates.push(tracked!(ServerConnections, self.server_connections, server_connections));
+        #[cfg(feature = "os-shares")]
+        updates.push(tracked!(ServerSessions, self.server_sessions, server_sessions));
+        #[cfg(feature = "os-shares")]
+        updates.push(tracked!(Shares, self.shares, shares));
+        #[cfg(feature = "os-shares")]
+        updates.push(tracked!(ShareSecurityDescriptors, self.share_security_descriptors, share_security_descriptors));
+        #[cfg(feature = "os-multimedia-audio-visual")]
+        updates.push(tracked!(CodecFiles, self.codec_files, codec_files));
+        #[cfg(feature = "os-capture-privacy")]
+        updates.push(tracked!(CapturePrivacy, self.capture_privacy, capture_devices));
+        #[cfg(feature = "os-storage")]
+        updates.push(tracked!(ShadowCopys, self.shadow_copys, shadow_copys));
+        #[cfg(feature = "os-storage")]
+        updates.push(tracked!(ShadowContexts, self.shadow_contexts, shadow_contexts));
+        #[cfg(feature = "os-storage")]
+        updates.push(tracked!(ShadowProviders, self.shadow_providers, shadow_providers));
+        #[cfg(feature = "os-security")]
+        updates.push(tracked!(LogicalFileSecuritySettings, self.logical_file_security_settings, logical_file_security_settings));
+        #[cfg(feature = "os-security")]
+        updates.push(tracked!(LogicalShareSecuritySettings, self.logical_share_security_settings, logical_share_security_settings));
+        #[cfg(feature = "os-security")]
+        updates.push(tracked!(PrivilegesStatuses, self.privileges_statuses, privileges_statuses));
+        #[cfg(feature = "os-start-menu")]
+        updates.push(tracked!(LogicalProgramGroups, self.logical_program_groups, logical_program_groups));
+        #[cfg(feature = "os-start-menu")]
+        updates.push(tracked!(LogicalProgramGroupItems, self.logical_program_group_items, logical_program_group_items));
+        #[cfg(feature = "os-networking")]
+        updates.push(tracked!(Ip4PersistedRouteTables, self.ip4_persisted_route_tables, ip4_persisted_route_tables));
+        #[cfg(feature = "os-networking")]
+        updates.push(tracked!(Ip4RouteTables, self.ip4_route_tables, ip4_route_tables));
+        #[cfg(feature = "os-networking")]
+        updates.push(tracked!(NeworkClients, self.nework_clients, nework_clients));
+        #[cfg(feature = "os-networking")]
+        updates.push(tracked!(NeworkConnections, self.nework_connections, nework_connections));
+        #[cfg(feature = "os-networking")]
+        updates.push(tracked!(NeworkProtocols, self.nework_protocols, nework_protocols));
+        #[cfg(feature = "os-networking")]
+        updates.push(tracked!(NtDomains, self.nt_domains, nt_domains));
+        #[cfg(feature = "os-networking")]
+        updates.push(tracked!(Ip4RouteTableEvents, self.ip4_route_table_events, ip4_route_table_events));
+        #[cfg(feature = "os-networking")]
+        updates.push(tracked!(QosPolicies, self.qos_policies, qos_policies));
+        #[cfg(feature = "os-firewall")]
+        updates.push(tracked!(FirewallProfiles, self.firewall_profiles, firewall_profiles));
+        #[cfg(feature = "os-firewall")]
+        updates.push(tracked!(FirewallRules, self.firewall_rules, firewall_rules));
+        #[cfg(feature = "os-firewall")]
+        updates.push(tracked!(FirewallPortFilters, self.firewall_port_filters, port_filters));
+        #[cfg(feature = "os-firewall")]
+        updates.push(tracked!(FirewallApplicationFilters, self.firewall_application_filters, application_filters));
+        #[cfg(feature = "os-firewall")]
+        updates.push(tracked!(FirewallAddressFilters, self.firewall_address_filters, address_filters));
+        #[cfg(feature = "os-connections")]
+        updates.push(tracked!(TcpConnections, self.tcp_connections, tcp_connections));
+        #[cfg(feature = "os-connections")]
+        updates.push(tracked!(UdpEndpoints, self.udp_endpoints, udp_endpoints));
+        #[cfg(feature = "os-connections")]
+        updates.push(tracked!(NetNeighbors, self.net_neighbors, net_neighbors));
+        #[cfg(feature = "os-dns")]
+        updates.push(tracked!(DnsClientCache, self.dns_client_cache, dns_client_cache));
+        #[cfg(feature = "os-dns")]
+        updates.push(tracked!(HostsFile, self.hosts_file, hosts_entries));
+        #[cfg(feature = "os-job-objects")]
+        updates.push(tracked!(NamedJobObjects, self.named_job_objects, named_job_objects));
+        #[cfg(feature = "os-job-objects")]
+        updates.push(tracked!(NamedJobObjectActgInfos, self.named_job_object_actg_infos, named_job_object_actg_infos));
+        #[cfg(feature = "os-job-objects")]
+        updates.push(tracked!(NamedJobObjectLimitSettings, self.named_job_object_limit_settings, named_job_object_limit_settings));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(tracked!(BootConfigurations, self.boot_configurations, boot_configurations));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(tracked!(ComputerSystems, self.computer_systems, computer_systems));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(tracked!(SystemServices, self.system_services, system_services));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(tracked!(ComputerSystemProducts, self.computer_system_products, computer_system_products));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(tracked!(LoadOrderGroups, self.load_order_groups, load_order_groups));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(tracked!(OperatingSystems, self.operating_systems, operating_systems));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(tracked!(OsRecoveryConfigurations, self.os_recovery_configurations, os_recovery_configurations));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(tracked!(QuickFixEngineerings, self.quick_fix_engineerings, quick_fix_engineerings));
+        #[cfg(feature = "os-optional-features")]
+        updates.push(tracked!(OptionalFeatures, self.optional_features, optional_features));
+        #[cfg(feature = "os-optional-features")]
+        updates.push(tracked!(ServerFeatures, self.server_features, server_features));
+        #[cfg(feature = "os-operating-system-settings")]
+        updates.push(tracked!(StartupCommands, self.startup_commands, startup_commands));
+        #[cfg(feature = "os-persistence")]
+        updates.push(tracked!(PersistenceItems, self.persistence_items, persistence_items));
+        #[cfg(feature = "os-installed-software")]
+        updates.push(tracked!(InstalledSoftware, self.installed_software, installed_software));
+        #[cfg(feature = "hw-cooling-device")]
+        updates.push(tracked!(Fans, self.fans, fans));
+        #[cfg(feature = "hw-cooling-device")]
+        updates.push(tracked!(HeatPipes, self.heat_pipes, heat_pipes));
+        #[cfg(feature = "hw-cooling-device")]
+        updates.push(tracked!(Refrigerations, self.refrigerations, refrigerations));
+        #[cfg(feature = "hw-cooling-device")]
+        updates.push(tracked!(TemperatureProbes, self.temperature_probes, temperature_probes));
+        #[cfg(feature = "hw-input-device")]
+        updates.push(tracked!(Keyboards, self.keyboards, keyboards));
+        #[cfg(feature = "hw-input-device")]
+        updates.push(tracked!(PointingDevices, self.pointing_devices, pointing_devices));
+        #[cfg(feature = "hw-input-device")]
+        updates.push(tracked!(InputDeviceEvents, self.input_device_events, input_device_events));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(tracked!(AutochkSettings, self.autochk_settings, autochk_settings));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(tracked!(CdRomDrives, self.cd_rom_drives, cd_rom_drives));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(tracked!(DiskDrives, self.disk_drives, disk_drives));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(tracked!(PhysicalMedias, self.physical_medias, physical_medias));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(tracked!(TapeDrives, self.tape_drives, tape_drives));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(tracked!(PhysicalDisks, self.physical_disks, physical_disks));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(tracked!(NvmeCapabilities, self.nvme_capabilities, nvme_devices));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(tracked!(Disks, self.disks, disks));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(tracked!(StorageReliabilityCounters, self.storage_reliability_counters, storage_reliability_counters));
+        #[cfg(feature = "hw-mass-storage")]
+        updates.push(tracked!(FailurePredictStatuses, self.failure_predict_statuses, failure_predict_statuses));
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        updates.push(tracked!(PnpEntities, self.pnp_entities, pnp_entities));
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        updates.push(tracked!(Bioses, self.bioses, bioses));
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        updates.push(tracked!(SystemEnclosures, self.system_enclosures, system_enclosures));
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        updates.push(tracked!(SerialPortConfigurations, self.serial_port_configurations, serial_port_configurations));
+        #[cfg(feature = "hw-motherboard-controller-port")]
+        updates.push(tracked!(ParallelPorts, self.parallel_ports, parallel_ports));
+        #[cfg(feature = "hw-usb")]
+        updates.push(tracked!(UsbDevices, self.usb_devices, usb_devices));
+        #[cfg(feature = "hw-networking-device")]
+        updates.push(tracked!(NetworkAdapters, self.network_adapters, network_adapters));
+        #[cfg(feature = "hw-networking-device")]
+        updates.push(tracked!(NetworkAdapterConfigurations, self.network_adapter_configurations, network_adapter_configurations));
+        #[cfg(feature = "hw-networking-device")]
+        updates.push(tracked!(NetAdapters, self.net_adapters, net_adapters));
+        #[cfg(feature = "hw-telephony")]
+        updates.push(tracked!(PotModems, self.pot_modems, pot_modems));
+        #[cfg(feature = "hw-telephony")]
+        updates.push(tracked!(PotModemToSerialPorts, self.pot_modem_to_serial_ports, pot_modem_to_serial_ports));
+        #[cfg(feature = "hw-power")]
+        updates.push(tracked!(Batteries, self.batteries, batteries));
+        #[cfg(feature = "hw-power")]
+        updates.push(tracked!(CurrentProbes, self.current_probes, current_probes));
+        #[cfg(feature = "hw-power")]
+        updates.push(tracked!(PortableBatteries, self.portable_batteries, portable_batteries));
+        #[cfg(feature = "hw-power")]
+        updates.push(tracked!(PowerManagementEvents, self.power_management_events, power_management_events));
+        #[cfg(feature = "hw-power")]
+        updates.push(tracked!(VoltageProbes, self.voltage_probes, voltage_probes));
+        #[cfg(feature = "hw-printing")]
+        updates.push(tracked!(Printers, self.printers, printers));
+        #[cfg(feature = "hw-printing")]
+        updates.push(tracked!(PrinterDrivers, self.printer_drivers, printer_drivers));
+        #[cfg(feature = "hw-printing")]
+        updates.push(tracked!(DriverForDevices, self.driver_for_devices, driver_for_devices));
+        #[cfg(feature = "hw-printing")]
+        updates.push(tracked!(TCPIPPrinterPorts, self.tcpip_printer_ports, tcpip_printer_ports));
+        #[cfg(feature = "hw-video-monitor")]
+        updates.push(tracked!(DesktopMonitors, self.desktop_monitors, desktop_monitors));
+        #[cfg(feature = "hw-video-monitor")]
+        updates.push(tracked!(DisplayControllerConfigurations, self.display_controller_configurations, display_controller_configurations));
+        #[cfg(feature = "hw-video-monitor")]
+        updates.push(tracked!(VideoControllers, self.video_controllers, video_controllers));
+
+        #[cfg(feature = "bluetooth")]
+        updates.push(tracked!(BluetoothDevices, self.bluetooth_devices, bluetooth_devices));
+        #[cfg(feature = "hyperv")]
+        updates.push(tracked!(VirtualMachines, self.virtual_machines, virtual_machines));
+        #[cfg(feature = "hyperv")]
+        updates.push(tracked!(VirtualProcessorSettings, self.virtual_processor_settings, virtual_processor_settings));
+        #[cfg(feature = "hyperv")]
+        updates.push(tracked!(VirtualMemorySettings, self.virtual_memory_settings, virtual_memory_settings));
+
+        let updates = updates
+            .into_iter()
+            .filter(|(kind, _)| profile.contains(*kind))
+            .map(|(kind, update)| async move {
+                let start = Instant::now();
+                let outcome = match timeout {
+                    Some(timeout) => tokio::time::timeout(timeout, update)
+                        .await
+                        .unwrap_or_else(|_| Err(Unavailable::timed_out(kind.name(), timeout))),
+                    None => update.await,
+                };
+
+                (kind, outcome, start.elapsed())
+            });
+
+        let meta = &mut self.meta;
+        meta.start();
+
+        stream::iter(updates)
+            .buffer_unordered(limit.max(1))
+            .for_each(|(kind, outcome, duration)| {
+                meta.record(kind, duration, &outcome);
+                on_progress(kind, outcome);
+                future::ready(())
+            })
+            .await;
+
+        meta.finish();
+    }
+
+    /// Server or workstation, virtual or physical and domain membership of the machine, `None`
+    /// until `operating_systems` and `computer_systems` are updated
+    #[cfg(feature = "os-operating-system-settings")]
+    pub fn machine_profile(&self) -> Option<operating_system_settings::MachineProfile> {
+        operating_system_settings::MachineProfile::from_states(&self.operating_systems, &self.computer_systems)
+    }
+
+    /// Physical memory, commit and page file usage, `None` until `operating_systems` is updated
+    ///
+    /// The memory performance counters refine it once `memory_performances` is updated.
+    #[cfg(feature = "os-operating-system-settings")]
+    pub fn memory_summary(&self) -> Option<operating_system_settings::MemorySummary> {
+        let summary = operating_system_settings::MemorySummary::from_state(&self.operating_systems)?;
+
+        #[cfg(feature = "os-perf")]
+        if let Some(memory) = self.memory_performances.memory_performances.first() {
+            return Some(summary.with_performance(memory));
+        }
+
+        Some(summary)
+    }
+
+    /// Physical disks, their partitions and logical disks, joined from the members holding them,
+    /// empty until they are updated
+    #[cfg(feature = "hw-mass-storage")]
+    pub fn disk_topology(&self) -> mass_storage::DiskTopology {
+        mass_storage::DiskTopology::from_states(
+            &self.disk_drives,
+            &self.disk_partition,
+            &self.logical_disks,
+            &self.volumes,
+            &self.disk_drive_to_disk_partitions,
+            &self.logical_disk_to_partitions,
+        )
+    }
+
+    /// Values addressed by the [`path`](crate::path), e.g. `os.services.services[Name=wuauserv].State`
+    pub fn lookup(&self, path: &str) -> Result<Vec<serde_json::Value>, crate::path::PathError> {
+        path.parse::<crate::path::SnapshotPath>()?.lookup(self)
+    }
+
+    /// Paths of every property the snapshot holds, see [`crate::path::paths`]
+    pub fn paths(&self) -> Vec<String> {
+        crate::path::paths(self)
+    }
+
+    /// Removable disks and optical drives, joined from the members holding them, empty until
+    /// `logical_disks` is updated
+    #[cfg(feature = "hw-mass-storage")]
+    pub fn removable_drives(&self) -> mass_storage::RemovableDrives {
+        mass_storage::RemovableDrives::from_states(&self.logical_disks, &self.cd_rom_drives)
+    }
+
+    /// Printers with their ports and drivers, joined from the members holding them, empty until
+    /// `printers` is updated
+    #[cfg(feature = "hw-printing")]
+    pub fn printer_map(&self) -> printing::PrinterMap {
+        printing::PrinterMap::from_states(
+            &self.printers,
+            &self.printer_drivers,
+            &self.driver_for_devices,
+            &self.tcpip_printer_ports,
+        )
+    }
+
+    /// Queries the classes implementing [`Referenced`](crate::association::Referenced) again,
+    /// untyped, and keeps the properties their structs do not model in `extra_properties`,
+    /// replacing what was captured before
+    ///
+    /// Classes that cannot be queried are left out, returns the number of instances with extras
+    pub fn capture_extra_properties(&mut self) -> usize {
+        self.extra_properties.clear();
+
+        #[allow(unused_macros)] // every class captured may be disabled
+        macro_rules! capture {
+            ($class: ty) => {
+                if let Ok(instances) = crate::extra::extra_properties::<$class>(crate::DEFAULT_NAMESPACE) {
+                    self.keep_extra_properties(crate::class_name::<$class>(&[]), instances);
+                }
+            };
+        }
+
+        extra_classes!(capture);
+
+        self.extra_properties.values().map(Vec::len).sum()
+    }
+
+    /// Asynchronous [`Windows::capture_extra_properties`]
+    pub async fn async_capture_extra_properties(&mut self) -> usize {
+        self.extra_properties.clear();
+
+        #[allow(unused_macros)] // every class captured may be disabled
+        macro_rules! capture {
+            ($class: ty) => {
+                if let Ok(instances) =
+                    crate::extra::async_extra_properties::<$class>(crate::DEFAULT_NAMESPACE).await
+                {
+                    self.keep_extra_properties(crate::class_name::<$class>(&[]), instances);
+                }
+            };
+        }
+
+        extra_classes!(capture);
+
+        self.extra_properties.values().map(Vec::len).sum()
+    }
+
+    #[allow(dead_code)] // unused when every class captured is disabled
+    fn keep_extra_properties(&mut self, class: &str, instances: Vec<ExtraProperties>) {
+        if !instances.is_empty() {
+            self.extra_properties.insert(class.to_string(), instances);
+        }
+    }
+
+    /// Concise human-readable report of the snapshot: the machine, how the last update went and a
+    /// section per main state, leaving out the states that were neither updated nor filled in
+    pub fn summary(&self) -> Summary {
+        let status_count = |status: MemberStatus| {
+            self.meta
+                .members
+                .values()
+                .filter(|member| member.status == status)
+                .count()
+        };
+        let failed = self.meta.failed().map(|(kind, _)| kind.name()).collect::<Vec<_>>();
+
+        #[allow(unused_mut)]
+        let mut summary = Summary::new(format!(
+            "Windows snapshot of {}",
+            Some(self.meta.hostname.as_str())
+                .filter(|hostname| !hostname.is_empty())
+                .unwrap_or(UNKNOWN)
+        ))
+        .optional_line(
+            "Captured in",
+            self.meta
+                .capture_duration()
+                .map(|duration| format!("{:.1}s", duration.as_secs_f64())),
+        )
+        .line("Members collected", status_count(MemberStatus::Collected))
+        .line("Members unsupported", status_count(MemberStatus::Unsupported))
+        .line("Members failed", match failed.is_empty() {
+            true => String::from("none"),
+            false => failed.join(", "),
+        });
+
+        #[cfg(feature = "os-operating-system-settings")]
+        if let Some(profile) = self.machine_profile() {
+            summary = summary
+                .line("Role", format!("{:?}", profile.role))
+                .optional_line("Virtual machine", profile.virtual_machine)
+                .optional_line("Domain", profile.domain);
+        }
+
+        #[allow(unused_mut)]
+        let mut sections: Vec<(StateKind, usize, Summary)> = Vec::new();
+
+        #[cfg(feature = "os-operating-system-settings")]
+        sections.push((StateKind::OperatingSystems, self.operating_systems.operating_systems.len(), self.operating_systems.summary()));
+        #[cfg(feature = "os-operating-system-settings")]
+        sections.push((StateKind::ComputerSystems, self.computer_systems.computer_systems.len(), self.computer_systems.summary()));
+        #[cfg(feature = "os-file-system")]
+        sections.push((StateKind::LogicalDisks, self.logical_disks.logical_disks.len(), self.logical_disks.summary()));
+        #[cfg(feature = "os-processes")]
+        sections.push((StateKind::Processes, self.processes.processes.len(), self.processes.summary()));
+        #[cfg(feature = "os-services")]
+        sections.push((StateKind::Services, self.services.services.len(), self.services.summary()));
+        #[cfg(feature = "os-operating-system-settings")]
+        sections.push((StateKind::QuickFixEngineerings, self.quick_fix_engineerings.quick_fix_engineerings.len(), self.quick_fix_engineerings.summary()));
+
+        sections
+            .into_iter()
+            .filter(|(kind, instances, _)| *instances > 0 || self.meta.members.contains_key(kind))
+            .fold(summary, |summary, (_, _, section)| summary.section(section))
+    }
+}
+
+impl fmt::Display for Windows {
+    /// Displays [`Windows::summary`]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.summary().fmt(f)
     }
 }
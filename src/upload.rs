@@ -0,0 +1,340 @@
+//! Ships snapshots to an HTTP collector, available with the `upload` feature.
+//!
+//! Items are serialized to JSON and sent in batches as a JSON array with a `POST` request. A
+//! failed request is retried with exponential backoff; once the retries are exhausted the batch
+//! stays queued for the next flush. Once `max_pending` items are queued they are written to the
+//! spool directory, if any, and resent before newer batches on the next flush, so a collector that
+//! cannot keep up pushes batches to disk instead of growing the agent.
+//!
+//! Batches the collector rejects with a client error other than `408 Request Timeout` and
+//! `429 Too Many Requests` would be rejected again, so they are dropped and reported instead of
+//! retried. Spooled batches that cannot be parsed are renamed with the `.rejected` extension.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use windows_snapshot::upload::Uploader;
+//! use windows_snapshot::COMLibrary;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//!     let mut uploader = Uploader::new("https://collector.example/snapshots")
+//!         .with_spool_dir("C:\\ProgramData\\agent\\spool");
+//!
+//!     let mut k = windows_snapshot::state::Windows::default();
+//!     k.async_update().await;
+//!
+//!     uploader.push(&k).await.unwrap();
+//!     uploader.flush().await.unwrap();
+//! }
+//! ```
+
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Items sent in a single request by default
+pub const DEFAULT_BATCH_SIZE: usize = 16;
+
+/// Retries of a failed request by default
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Delay before the first retry by default, doubled on every attempt
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest delay between two attempts by default
+pub const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Items held in memory by default before batches are spooled to disk
+pub const DEFAULT_MAX_PENDING: usize = 256;
+
+/// Error returned when items could not be delivered
+#[derive(Debug)]
+pub enum UploadError {
+    /// The request could not be sent
+    Http(reqwest::Error),
+    /// The collector answered with a non success status
+    Status(reqwest::StatusCode),
+    /// The collector rejected a batch with a client error, the batch was dropped
+    Rejected {
+        /// Status the collector answered
+        status: reqwest::StatusCode,
+        /// Number of items in the dropped batch
+        items: usize,
+    },
+    /// A spooled batch could not be parsed, the file was renamed with the `.rejected` extension
+    Corrupted {
+        /// Path of the renamed file
+        path: PathBuf,
+        /// Why the batch could not be parsed
+        error: serde_json::Error,
+    },
+    /// The spool directory could not be read or written
+    Io(std::io::Error),
+    /// An item could not be serialized to JSON
+    Serialize(serde_json::Error),
+}
+
+impl std::fmt::Display for UploadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UploadError::Http(e) => write!(f, "request failed: {e}"),
+            UploadError::Status(status) => write!(f, "collector answered {status}"),
+            UploadError::Rejected { status, items } => {
+                write!(f, "collector answered {status}, dropped a batch of {items} items")
+            }
+            UploadError::Corrupted { path, error } => {
+                write!(f, "spooled batch {} is corrupted: {error}", path.display())
+            }
+            UploadError::Io(e) => write!(f, "spool failed: {e}"),
+            UploadError::Serialize(e) => write!(f, "serialization failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for UploadError {}
+
+impl From<reqwest::Error> for UploadError {
+    fn from(e: reqwest::Error) -> Self {
+        UploadError::Http(e)
+    }
+}
+
+impl From<std::io::Error> for UploadError {
+    fn from(e: std::io::Error) -> Self {
+        UploadError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for UploadError {
+    fn from(e: serde_json::Error) -> Self {
+        UploadError::Serialize(e)
+    }
+}
+
+/// Batches serialized items and sends them to an HTTP endpoint
+#[derive(Debug, Clone)]
+pub struct Uploader {
+    /// URL the batches are posted to
+    pub endpoint: String,
+    /// Items sent in a single request
+    pub batch_size: usize,
+    /// Retries of a failed request before giving up on it
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on every attempt
+    pub retry_delay: Duration,
+    /// Longest delay between two attempts, however many there were
+    pub max_retry_delay: Duration,
+    /// Items held in memory before batches are spooled to disk
+    pub max_pending: usize,
+    /// Directory undelivered batches are written to, they are dropped without one
+    pub spool_dir: Option<PathBuf>,
+    client: reqwest::Client,
+    pending: Vec<serde_json::Value>,
+}
+
+impl Uploader {
+    /// Creates an uploader posting to `endpoint` with the default limits and no spool directory
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Uploader {
+            endpoint: endpoint.into(),
+            batch_size: DEFAULT_BATCH_SIZE,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            max_retry_delay: DEFAULT_MAX_RETRY_DELAY,
+            max_pending: DEFAULT_MAX_PENDING,
+            spool_dir: None,
+            client: reqwest::Client::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Writes undelivered batches to `spool_dir`
+    pub fn with_spool_dir(mut self, spool_dir: impl Into<PathBuf>) -> Self {
+        self.spool_dir = Some(spool_dir.into());
+        self
+    }
+
+    /// Sends `batch_size` items per request
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Retries a failed request `max_retries` times, waiting `retry_delay` before the first retry
+    pub fn with_retries(mut self, max_retries: u32, retry_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Waits at most `max_retry_delay` between two attempts
+    pub fn with_max_retry_delay(mut self, max_retry_delay: Duration) -> Self {
+        self.max_retry_delay = max_retry_delay;
+        self
+    }
+
+    /// Holds at most `max_pending` items in memory
+    pub fn with_max_pending(mut self, max_pending: usize) -> Self {
+        self.max_pending = max_pending.max(1);
+        self
+    }
+
+    /// Number of items waiting to be sent
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Queues `item`, sending a batch once `batch_size` items are queued
+    ///
+    /// When the batch cannot be delivered the items stay queued, up to `max_pending`, past which
+    /// they are moved to the spool directory.
+    pub async fn push<T: Serialize>(&mut self, item: &T) -> Result<(), UploadError> {
+        self.pending.push(serde_json::to_value(item)?);
+
+        if self.pending.len() < self.batch_size {
+            return Ok(());
+        }
+
+        let result = self.flush().await;
+
+        if self.pending.len() >= self.max_pending {
+            self.spool_pending()?;
+        }
+
+        result
+    }
+
+    /// Resends spooled batches, oldest first, then sends every queued item
+    ///
+    /// Stops at the first batch that cannot be delivered, keeping it and everything after it for
+    /// the next flush. Batches the collector rejects and spooled batches that cannot be parsed are
+    /// set aside and the flush goes on, returning the first of them as an error once done.
+    pub async fn flush(&mut self) -> Result<(), UploadError> {
+        let mut set_aside = None;
+
+        for path in self.spooled()? {
+            let batch: Vec<serde_json::Value> = match serde_json::from_slice(&std::fs::read(&path)?) {
+                Ok(batch) => batch,
+                Err(error) => {
+                    let rejected = path.with_extension("rejected");
+
+                    std::fs::rename(&path, &rejected)?;
+                    set_aside.get_or_insert(UploadError::Corrupted { path: rejected, error });
+                    continue;
+                }
+            };
+
+            match self.send(&batch).await {
+                Ok(()) => {}
+                Err(rejected @ UploadError::Rejected { .. }) => {
+                    set_aside.get_or_insert(rejected);
+                }
+                Err(e) => return Err(e),
+            }
+
+            std::fs::remove_file(path)?;
+        }
+
+        while !self.pending.is_empty() {
+            let len = self.pending.len().min(self.batch_size);
+
+            match self.send(&self.pending[..len]).await {
+                Ok(()) => {}
+                Err(rejected @ UploadError::Rejected { .. }) => {
+                    set_aside.get_or_insert(rejected);
+                }
+                Err(e) => return Err(e),
+            }
+
+            self.pending.drain(..len);
+        }
+
+        set_aside.map_or(Ok(()), Err)
+    }
+
+    /// Moves every queued item to the spool directory, dropping them if there is none
+    pub fn spool_pending(&mut self) -> Result<(), UploadError> {
+        let batch = std::mem::take(&mut self.pending);
+
+        let Some(spool_dir) = &self.spool_dir else {
+            return Ok(());
+        };
+
+        std::fs::create_dir_all(spool_dir)?;
+
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+
+        std::fs::write(
+            spool_dir.join(format!("{nanos:020}.json")),
+            serde_json::to_vec(&batch)?,
+        )?;
+
+        Ok(())
+    }
+
+    /// Spooled batches, oldest first
+    fn spooled(&self) -> Result<Vec<PathBuf>, UploadError> {
+        let Some(spool_dir) = &self.spool_dir else {
+            return Ok(Vec::new());
+        };
+
+        if !spool_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths = std::fs::read_dir(spool_dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .collect::<Vec<_>>();
+
+        paths.sort();
+
+        Ok(paths)
+    }
+
+    /// Posts `batch`, retrying with exponential backoff unless the collector rejects it
+    async fn send(&self, batch: &[serde_json::Value]) -> Result<(), UploadError> {
+        let mut attempt = 0;
+
+        loop {
+            let result = match self.client.post(&self.endpoint).json(batch).send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) if rejected(response.status()) => {
+                    return Err(UploadError::Rejected {
+                        status: response.status(),
+                        items: batch.len(),
+                    })
+                }
+                Ok(response) => UploadError::Status(response.status()),
+                Err(e) => UploadError::Http(e),
+            };
+
+            if attempt >= self.max_retries {
+                return Err(result);
+            }
+
+            tokio::time::sleep(self.retry_delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Delay before retry number `attempt`, starting at 0
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        self.retry_delay
+            .checked_mul(2u32.saturating_pow(attempt))
+            .map_or(self.max_retry_delay, |delay| delay.min(self.max_retry_delay))
+    }
+}
+
+/// Returns `true` if `status` is a client error that retrying the same batch cannot fix
+fn rejected(status: reqwest::StatusCode) -> bool {
+    status.is_client_error()
+        && status != reqwest::StatusCode::REQUEST_TIMEOUT
+        && status != reqwest::StatusCode::TOO_MANY_REQUESTS
+}
@@ -0,0 +1,193 @@
+//! Watch mode: periodic snapshots reduced to what changed between them.
+//!
+//! [`Windows::watch`] updates the members of a [`SnapshotProfile`] at a fixed interval and yields a
+//! [`WindowsDiff`] whenever an update changed something, so a caller does not have to schedule the
+//! updates or keep the previous snapshot around itself. The first update only primes the watch.
+//!
+//! Instances are compared on every property, as serialized, without a key: an instance whose
+//! properties changed is reported as removed with its old values and added with its new ones.
+//! Members holding counters, such as processes and their CPU times, report the instances whose
+//! counters moved. Use [`drift`](crate::drift) to compare configuration properties only.
+//!
+//! On laptops, `Windows::watch_on_battery` updates less often and skips expensive members while a
+//! battery is discharging below a threshold, see `BatteryThrottle`.
+//!
+//! Example:
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use std::time::Duration;
+//! use windows_snapshot::state::{SnapshotProfile, StateKind, Windows};
+//! use windows_snapshot::COMLibrary;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let _com_con = COMLibrary::new().unwrap(); // initialise security context
+//!
+//!     let profile = SnapshotProfile::new().include(StateKind::Services);
+//!     let mut diffs = Box::pin(Windows::default().watch(Duration::from_secs(60), profile));
+//!
+//!     while let Some(diff) = diffs.next().await {
+//!         for member in &diff.members {
+//!             println!("{:?}: +{} -{}", member.kind, member.added.len(), member.removed.len());
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! [`Windows::watch`]: crate::state::Windows::watch
+//! [`SnapshotProfile`]: crate::state::SnapshotProfile
+
+use crate::state::{StateKind, Windows};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+/// Changes between two consecutive updates of a watch
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WindowsDiff {
+    /// When the update that produced the changes finished
+    pub taken: SystemTime,
+    /// Members that changed, in [`StateKind`] order
+    pub members: Vec<MemberDiff>,
+    /// Custom members that changed, by name, see [`CustomState`](crate::custom::CustomState)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom: Vec<CustomDiff>,
+}
+
+impl WindowsDiff {
+    /// Returns `true` if no member changed
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty() && self.custom.is_empty()
+    }
+
+    /// Changes of the member `kind`, `None` if it did not change
+    pub fn member(&self, kind: StateKind) -> Option<&MemberDiff> {
+        self.members.iter().find(|member| member.kind == kind)
+    }
+
+    /// Changes from the `previous` snapshot to the `current` one on every member, e.g. to compare
+    /// two saved snapshots the way a watch compares its updates
+    pub fn of(previous: &Windows, current: &Windows) -> Self {
+        WindowsDiff::between(
+            &serde_json::to_value(previous).unwrap_or_default(),
+            &serde_json::to_value(current).unwrap_or_default(),
+            StateKind::ALL,
+        )
+    }
+
+    /// Compares the serialized `previous` and `current` snapshots on the members `kinds` and on
+    /// the custom members
+    pub(crate) fn between<'a>(
+        previous: &Value,
+        current: &Value,
+        kinds: impl IntoIterator<Item = &'a StateKind>,
+    ) -> Self {
+        let members = kinds
+            .into_iter()
+            .filter_map(|kind| {
+                MemberDiff::between(*kind, previous.get(kind.name())?, current.get(kind.name())?)
+            })
+            .collect();
+        let custom = current
+            .get("custom")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, current)| {
+                let previous = previous.get("custom")?.get(name)?;
+                let (added, removed) = changes(previous, current)?;
+
+                Some(CustomDiff {
+                    name: name.clone(),
+                    added,
+                    removed,
+                })
+            })
+            .collect();
+
+        WindowsDiff {
+            taken: SystemTime::now(),
+            members,
+            custom,
+        }
+    }
+}
+
+/// Instances of a member added or removed between two updates
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct MemberDiff {
+    /// The member
+    pub kind: StateKind,
+    /// Instances only in the current update, as serialized
+    pub added: Vec<Value>,
+    /// Instances only in the previous update, as serialized
+    pub removed: Vec<Value>,
+}
+
+impl MemberDiff {
+    /// Diffs every sequence held by the member, `None` if none changed
+    fn between(kind: StateKind, previous: &Value, current: &Value) -> Option<Self> {
+        let (added, removed) = changes(previous, current)?;
+
+        Some(MemberDiff {
+            kind,
+            added,
+            removed,
+        })
+    }
+}
+
+/// Instances of a custom member added or removed between two updates
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct CustomDiff {
+    /// Name the custom member is registered under
+    pub name: String,
+    /// Instances only in the current update, as serialized
+    pub added: Vec<Value>,
+    /// Instances only in the previous update, as serialized
+    pub removed: Vec<Value>,
+}
+
+/// Instances added to and removed from every sequence held by a serialized member, `None` if none
+/// changed
+fn changes(previous: &Value, current: &Value) -> Option<(Vec<Value>, Vec<Value>)> {
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+
+    for (field, current) in current.as_object()? {
+        let (Some(current), Some(previous)) = (
+            current.as_array(),
+            previous.get(field).and_then(Value::as_array),
+        ) else {
+            continue;
+        };
+
+        added.extend(missing_from(current, previous));
+        removed.extend(missing_from(previous, current));
+    }
+
+    (!added.is_empty() || !removed.is_empty()).then_some((added, removed))
+}
+
+/// Instances of `instances` that `other` does not hold as many times, compared as multisets
+fn missing_from(instances: &[Value], other: &[Value]) -> Vec<Value> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for instance in other {
+        *counts.entry(instance.to_string()).or_default() += 1;
+    }
+
+    instances
+        .iter()
+        .filter(|instance| match counts.get_mut(&instance.to_string()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .cloned()
+        .collect()
+}